@@ -0,0 +1,192 @@
+//! Benchmarks for protocol hot paths
+//!
+//! Emit throughput, URN parsing, tx.jsonl appends, inbox iteration and RDF
+//! loading, each on a synthetic project by default:
+//!
+//! ```text
+//! cargo bench --bench protocol
+//! ```
+//!
+//! With the `bench-project` feature, inbox iteration and RDF loading read an
+//! existing project instead. Point CKP_BENCH_PROJECT at its root and
+//! optionally name the kernel with CKP_BENCH_KERNEL (by default the first
+//! kernel with an ontology.ttl). Emits and tx appends always write to a
+//! scratch project, so benchmarking never adds jobs or transactions to it.
+//!
+//! ```text
+//! CKP_BENCH_PROJECT=~/my-project cargo bench --features bench-project --bench protocol
+//! ```
+
+use ckp_core::drivers::{FileSystemDriver, TxCommit, GROUP_COMMIT_WINDOW};
+use ckp_core::{Kernel, OntologyLibrary, UrnResolver};
+use criterion::{black_box, criterion_group, criterion_main, BatchSize, Bencher, Criterion, Throughput};
+use serde_json::json;
+use std::fs;
+use std::path::PathBuf;
+use tempfile::TempDir;
+
+const SOURCE: &str = "Bench.Source";
+const TARGET: &str = "Bench.Target";
+
+const KERNEL_URNS: [&str; 3] = [
+    "ckp://Recipes.BakeCake:v0.1",
+    "ckp://Recipes.BakeCake:v0.1#inbox",
+    "ckp://Recipes.BakeCake:v0.1#storage/1764410400000-ab12cd34.inst",
+];
+const EDGE_URN: &str = "ckp://Edge.PRODUCES.MixIngredients-to-BakeCake:v1.3.12";
+const QUERY_URN: &str = "ckp://Process?limit=10&order=desc";
+
+const ONTOLOGY_TTL: &str = r#"@prefix rdf: <http://www.w3.org/1999/02/22-rdf-syntax-ns#> .
+@prefix rdfs: <http://www.w3.org/2000/01/rdf-schema#> .
+@prefix owl: <http://www.w3.org/2002/07/owl#> .
+@prefix bfo: <http://purl.obolibrary.org/obo/BFO_> .
+@prefix ckp: <https://conceptkernel.org/ontology/core#> .
+
+<https://conceptkernel.org/ontology/bench-target> a owl:Ontology ;
+    owl:imports <https://conceptkernel.org/ontology/core> ;
+    owl:imports <http://purl.obolibrary.org/obo/bfo.owl> .
+
+ckp:BenchTarget a owl:Class ;
+    rdfs:subClassOf bfo:0000040 ;
+    rdfs:label "Bench.Target" .
+"#;
+
+/// Project root and kernel the read-only benches run against
+struct Project {
+    root: PathBuf,
+    kernel: String,
+    _temp: Option<TempDir>,
+}
+
+impl Project {
+    #[cfg(feature = "bench-project")]
+    fn load() -> Self {
+        let root = PathBuf::from(
+            std::env::var("CKP_BENCH_PROJECT").expect("bench-project needs CKP_BENCH_PROJECT set to a project root"),
+        );
+        let kernel = std::env::var("CKP_BENCH_KERNEL").ok().unwrap_or_else(|| {
+            let mut kernels: Vec<String> = fs::read_dir(root.join("concepts"))
+                .expect("CKP_BENCH_PROJECT has no concepts/ directory")
+                .flatten()
+                .filter(|entry| entry.path().join("ontology.ttl").is_file())
+                .filter_map(|entry| entry.file_name().into_string().ok())
+                .collect();
+            kernels.sort();
+            kernels.into_iter().next().expect("no kernel with an ontology.ttl; set CKP_BENCH_KERNEL")
+        });
+        Self { root, kernel, _temp: None }
+    }
+
+    #[cfg(not(feature = "bench-project"))]
+    fn load() -> Self {
+        const INBOX_JOBS: usize = 1000;
+
+        let project = Self::scratch();
+        let inbox = project.root.join("concepts").join(TARGET).join("queue/inbox");
+        for i in 0..INBOX_JOBS {
+            let tx_id = format!("1764410400000-{:08x}", i);
+            let job = json!({
+                "target": TARGET,
+                "payload": {"order": i, "items": ["flour", "sugar", "eggs"]},
+                "timestamp": "2025-11-29T10:00:00Z",
+                "txId": tx_id,
+                "source": SOURCE,
+            });
+            fs::write(inbox.join(format!("{}.job", tx_id)), job.to_string()).unwrap();
+        }
+        project
+    }
+
+    /// Synthetic project with a source and a target kernel
+    fn scratch() -> Self {
+        let temp = TempDir::new().unwrap();
+        for kernel in [SOURCE, TARGET] {
+            let kernel_dir = temp.path().join("concepts").join(kernel);
+            for dir in ["queue/inbox", "queue/archive", "storage"] {
+                fs::create_dir_all(kernel_dir.join(dir)).unwrap();
+            }
+            fs::write(kernel_dir.join("ontology.ttl"), ONTOLOGY_TTL).unwrap();
+        }
+        Self { root: temp.path().to_path_buf(), kernel: TARGET.to_string(), _temp: Some(temp) }
+    }
+
+    fn kernel_dir(&self) -> PathBuf {
+        self.root.join("concepts").join(&self.kernel)
+    }
+}
+
+fn emit(c: &mut Criterion) {
+    let project = Project::scratch();
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+    let mut kernel = Kernel::new(project.root.clone(), Some(SOURCE.to_string()), false);
+    let payload = json!({"order": 42, "items": ["flour", "sugar", "eggs"]});
+
+    let mut group = c.benchmark_group("emit");
+    group.throughput(Throughput::Elements(1));
+    group.bench_function("kernel_emit", |b| {
+        b.iter(|| runtime.block_on(kernel.emit(TARGET, payload.clone())).unwrap())
+    });
+    group.finish();
+}
+
+fn urn_parsing(c: &mut Criterion) {
+    let mut group = c.benchmark_group("urn");
+    group.bench_function("parse", |b| {
+        b.iter(|| KERNEL_URNS.map(|urn| UrnResolver::parse(black_box(urn)).unwrap()))
+    });
+    group.bench_function("parse_edge", |b| b.iter(|| UrnResolver::parse_edge_urn(black_box(EDGE_URN)).unwrap()));
+    group.bench_function("parse_query", |b| b.iter(|| UrnResolver::parse_query_urn(black_box(QUERY_URN)).unwrap()));
+    group.finish();
+}
+
+fn tx_log(c: &mut Criterion) {
+    let project = Project::scratch();
+    let tx_log = |commit: TxCommit| {
+        let driver = FileSystemDriver::new(project.root.clone(), TARGET.to_string()).with_tx_commit(commit);
+        move |b: &mut Bencher| {
+            b.iter(|| driver.record_transaction("1764410400000-ab12cd34", json!({"event": "minted"})).unwrap())
+        }
+    };
+
+    let mut group = c.benchmark_group("tx_log");
+    group.sample_size(20);
+    group.throughput(Throughput::Elements(1));
+    group.bench_function("append_immediate", tx_log(TxCommit::Immediate));
+    // A single writer waits out the whole window for every record
+    group.bench_function("append_group_commit", tx_log(TxCommit::Group(GROUP_COMMIT_WINDOW)));
+    group.finish();
+}
+
+fn inbox_iteration(c: &mut Criterion, project: &Project) {
+    let kernel = Kernel::new(project.root.clone(), Some(project.kernel.clone()), false);
+
+    let mut group = c.benchmark_group("inbox");
+    group.bench_function("iterate", |b| {
+        b.iter(|| kernel.inbox_iter().unwrap().map(|job| job.map(drop)).count())
+    });
+    group.finish();
+}
+
+fn rdf_loading(c: &mut Criterion, project: &Project) {
+    let mut group = c.benchmark_group("rdf");
+    group.sample_size(20);
+    group.bench_function("load_kernel_ontology", |b| {
+        b.iter_batched(
+            || OntologyLibrary::new(project.root.clone()).unwrap(),
+            |mut library| library.load_kernel_ontology(&project.kernel).unwrap(),
+            BatchSize::LargeInput,
+        )
+    });
+    group.bench_function("load_project", |b| b.iter(|| OntologyLibrary::new(project.root.clone()).unwrap()));
+    group.finish();
+}
+
+fn project_reads(c: &mut Criterion) {
+    let project = Project::load();
+    assert!(project.kernel_dir().is_dir(), "kernel {} not found", project.kernel);
+    inbox_iteration(c, &project);
+    rdf_loading(c, &project);
+}
+
+criterion_group!(benches, emit, urn_parsing, tx_log, project_reads);
+criterion_main!(benches);
@@ -0,0 +1,81 @@
+//! Benchmarks for project-wide scans
+//!
+//! Builds a synthetic project (many kernels with stored instances and edges)
+//! and times the scans that run on the shared parallel walker. Compare
+//! thread counts with CKP_SCAN_THREADS, e.g.
+//!
+//! ```text
+//! CKP_SCAN_THREADS=1 cargo bench --bench scan
+//! cargo bench --bench scan
+//! ```
+
+use ckp_core::edge::EdgeKernel;
+use ckp_core::{InstanceScanner, ProjectScanner, RetentionPolicy};
+use criterion::{criterion_group, criterion_main, Criterion};
+use std::fs;
+use std::path::Path;
+use tempfile::TempDir;
+
+const KERNELS: usize = 32;
+const INSTANCES_PER_KERNEL: usize = 200;
+const EDGES: usize = 64;
+
+fn build_project(root: &Path) {
+    for k in 0..KERNELS {
+        let kernel = format!("Bench.Kernel{:02}", k);
+        let storage = root.join("concepts").join(&kernel).join("storage");
+        for i in 0..INSTANCES_PER_KERNEL {
+            let inst_dir = storage.join(format!("1764410400000-{:04x}{:04x}.inst", k, i));
+            fs::create_dir_all(&inst_dir).unwrap();
+            let receipt = serde_json::json!({
+                "id": format!("1764410400000-{:04x}{:04x}", k, i),
+                "name": format!("instance-{}-{}", k, i),
+                "kernel": kernel,
+                "timestamp": "2025-11-29T10:00:00Z",
+            });
+            fs::write(inst_dir.join("receipt.bin"), receipt.to_string()).unwrap();
+            fs::write(inst_dir.join("payload.json"), r#"{"value":42}"#).unwrap();
+        }
+    }
+
+    let mut edges = EdgeKernel::new(root.to_path_buf()).unwrap();
+    for e in 0..EDGES {
+        edges
+            .create_edge("PRODUCES", &format!("Bench.Kernel{:02}", e % KERNELS), &format!("Bench.Target{:02}", e))
+            .unwrap();
+    }
+}
+
+fn scans(c: &mut Criterion) {
+    let temp = TempDir::new().unwrap();
+    let root = temp.path();
+    build_project(root);
+
+    let mut group = c.benchmark_group("scan");
+    group.sample_size(20);
+
+    group.bench_function("retention_check_expired_data", |b| {
+        let policy = RetentionPolicy::new(0, root.join(".archive"));
+        b.iter(|| policy.check_expired_data(root.join("concepts")).unwrap())
+    });
+
+    group.bench_function("instance_scanner_rebuild_index", |b| {
+        let scanner = InstanceScanner::new(root.join("concepts/Bench.Kernel00"), "Bench.Kernel00".to_string());
+        b.iter(|| scanner.rebuild_index().unwrap())
+    });
+
+    group.bench_function("project_scanner_list_instances", |b| {
+        let scanner = ProjectScanner::new(root.to_path_buf());
+        b.iter(|| scanner.list_instances(0).unwrap())
+    });
+
+    group.bench_function("edge_kernel_list_all_edges", |b| {
+        let mut edges = EdgeKernel::new(root.to_path_buf()).unwrap();
+        b.iter(|| edges.list_all_edges().unwrap())
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, scans);
+criterion_main!(benches);
@@ -0,0 +1,26 @@
+fn main() {
+    // Link flags for loading the library as a Node.js addon
+    #[cfg(feature = "napi")]
+    napi_build::setup();
+
+    #[cfg(feature = "ffi")]
+    generate_c_header();
+}
+
+/// Regenerate core-rs/include/ckp_core.h from the C ABI in core-rs/src/ffi.rs
+#[cfg(feature = "ffi")]
+fn generate_c_header() {
+    let manifest_dir = std::path::PathBuf::from(std::env::var("CARGO_MANIFEST_DIR").unwrap());
+    let config = cbindgen::Config::from_file(manifest_dir.join("core-rs/cbindgen.toml"))
+        .expect("Failed to read core-rs/cbindgen.toml");
+
+    cbindgen::Builder::new()
+        .with_config(config)
+        .with_src(manifest_dir.join("core-rs/src/ffi.rs"))
+        .generate()
+        .expect("Failed to generate C header")
+        .write_to_file(manifest_dir.join("core-rs/include/ckp_core.h"));
+
+    println!("cargo:rerun-if-changed=core-rs/src/ffi.rs");
+    println!("cargo:rerun-if-changed=core-rs/cbindgen.toml");
+}
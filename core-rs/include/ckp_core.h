@@ -0,0 +1,84 @@
+/* ConceptKernel C ABI. Generated by cbindgen from core-rs/src/ffi.rs - do not edit. */
+
+#ifndef CKP_CORE_H
+#define CKP_CORE_H
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+// Version of this C ABI, bumped on incompatible changes
+#define CKP_ABI_VERSION 1
+
+// Success return code
+#define CKP_OK 0
+
+// Failure return code; see `ckp_last_error()`
+#define CKP_ERROR -1
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+// Version of the C ABI implemented by this library
+uint32_t ckp_abi_version(void);
+
+// Message of the last failed call on this thread, or NULL
+//
+// The string is owned by the library and stays valid until the next
+// `ckp_*` call on the same thread.
+const char *ckp_last_error(void);
+
+// Release a string returned by this library
+//
+// # Safety
+// `value` must be null or a string returned through an `out` parameter of
+// this library that has not been freed yet.
+void ckp_string_free(char *value);
+
+// Emit a job to a kernel's inbox
+//
+// `payload_json` is the job payload as JSON. With a non-NULL `source`
+// kernel the emit is RBAC-checked against its ontology; with NULL the job
+// is recorded as coming from `external`. On success `tx_id_out` receives
+// the transaction ID.
+//
+// # Safety
+// String arguments must be valid NUL-terminated strings (`source` may be
+// NULL) and `tx_id_out` must be valid for writes.
+int ckp_emit(const char *root,
+             const char *target,
+             const char *payload_json,
+             const char *source,
+             char **tx_id_out);
+
+// Parse a kernel URN into its components
+//
+// On success `json_out` receives an object with `kernel`, `version`,
+// `stage` and `path` (the last two may be null), plus `txId` when the URN
+// addresses an instance.
+//
+// # Safety
+// `urn` must be a valid NUL-terminated string and `json_out` must be valid
+// for writes.
+int ckp_parse_urn(const char *urn, char **json_out);
+
+// List a kernel's storage instances, sorted by name
+//
+// `limit` caps the number of instances (0 = all). On success `json_out`
+// receives a JSON array of instance summaries.
+//
+// # Safety
+// String arguments must be valid NUL-terminated strings and `json_out`
+// must be valid for writes.
+int ckp_list_instances(const char *kernel_root,
+                       const char *kernel_name,
+                       uint32_t limit,
+                       char **json_out);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* CKP_CORE_H */
@@ -9,6 +9,10 @@ use clap::{Parser, Subcommand};
 #[command(version = "1.3.19")]
 #[command(about = "ConceptKernel Rust Runtime", long_about = None)]
 struct Cli {
+    /// Log output format (text, json); defaults to $CKP_LOG_FORMAT, then text
+    #[arg(long, global = true)]
+    log_format: Option<ckp_core::LogFormat>,
+
     #[command(subcommand)]
     command: Commands,
 }
@@ -25,12 +29,12 @@ enum Commands {
         #[command(subcommand)]
         command: ProjectCommands,
     },
-    /// Manage edges (list, create)
+    /// Manage edges (list, create, keygen)
     Edge {
         #[command(subcommand)]
         command: EdgeCommands,
     },
-    /// Manage packages (list, import, fork)
+    /// Manage packages (list, import, pack, search, publish, trust, gc, fork)
     Package {
         #[command(subcommand)]
         command: TopLevelPackageCommands,
@@ -57,6 +61,11 @@ enum Commands {
         /// URN to validate
         urn: String,
     },
+    /// JSON Schemas of protocol files (show, export, validate)
+    Schema {
+        #[command(subcommand)]
+        command: SchemaCommands,
+    },
     /// Fork a cached package to create new kernel
     Fork {
         /// Source package name (e.g., System.Gateway.HTTP)
@@ -74,6 +83,30 @@ enum Commands {
         #[arg(long)]
         no_start: bool,
     },
+    /// Install a concept package and its dependencies from the cache or a remote registry
+    Install {
+        /// Package name, optionally with a semver range (e.g., System.Gateway.HTTP@^1.2)
+        #[arg(required_unless_present = "locked")]
+        name: Option<String>,
+        /// Exact version to install (latest compatible if omitted)
+        #[arg(long, short)]
+        version: Option<String>,
+        /// Registry URL (defaults to $CKP_REGISTRY_URL)
+        #[arg(long)]
+        registry: Option<String>,
+        /// Custom instance name
+        #[arg(long = "as")]
+        as_name: Option<String>,
+        /// Refuse packages without a valid signature from a trusted key
+        #[arg(long)]
+        strict: bool,
+        /// Install exactly the packages recorded in ckp.lock
+        #[arg(long, conflicts_with_all = ["name", "version", "as_name"])]
+        locked: bool,
+        /// Use only the cache and vendored directories ($CKP_VENDOR_DIR), never the registry
+        #[arg(long)]
+        offline: bool,
+    },
     /// System daemons (governor, edge-router)
     Daemon {
         #[command(subcommand)]
@@ -111,6 +144,133 @@ enum DaemonCommands {
         /// Project root directory
         #[arg(long, default_value = ".")]
         project: std::path::PathBuf,
+        /// Window for batching filesystem events, in milliseconds
+        #[arg(long, default_value_t = 50)]
+        debounce_ms: u64,
+        /// Interval between reconciliation sweeps, in seconds
+        #[arg(long, default_value_t = 30)]
+        reconcile_interval: u64,
+        /// Serve Prometheus metrics at http://<addr>/metrics
+        #[arg(long)]
+        metrics: Option<std::net::SocketAddr>,
+        /// Accept admin commands on this Unix socket
+        #[arg(long)]
+        control: Option<std::path::PathBuf>,
+        /// Route only one partition of the kernels, as <index>/<count> (e.g. 0/3)
+        #[arg(long)]
+        partition: Option<ckp_core::daemon::Partition>,
+        /// Partition lease lifetime in seconds
+        #[arg(long, default_value_t = 30)]
+        lease_ttl: u64,
+        /// Number of routed tx_ids remembered to catch re-emitted instances
+        #[arg(long, default_value_t = 10_000)]
+        dedup_window: usize,
+        /// What to do with a re-emitted instance: drop, or flag (deliver with a duplicate marker)
+        #[arg(long, default_value = "drop")]
+        on_duplicate: ckp_core::daemon::DuplicatePolicy,
+        /// Enable verbose logging
+        #[arg(long, short = 'v')]
+        verbose: bool,
+    },
+    /// Start disposition evaluator daemon
+    Dispositions {
+        /// Project root directory
+        #[arg(long, default_value = ".")]
+        project: std::path::PathBuf,
+        /// Evaluation interval in seconds
+        #[arg(long, default_value_t = 5)]
+        interval: u64,
+        /// Enable verbose logging
+        #[arg(long, short = 'v')]
+        verbose: bool,
+    },
+    /// Start edge queue depth alarm daemon
+    Alarms {
+        /// Project root directory
+        #[arg(long, default_value = ".")]
+        project: std::path::PathBuf,
+        /// Evaluation interval in seconds
+        #[arg(long, default_value_t = 10)]
+        interval: u64,
+        /// Enable verbose logging
+        #[arg(long, short = 'v')]
+        verbose: bool,
+    },
+    /// Replicate the project's tx logs and storage to a warm standby tree
+    Replicate {
+        /// Project root directory
+        #[arg(long, default_value = ".")]
+        project: std::path::PathBuf,
+        /// Standby project root (e.g. a mount of the standby host's project)
+        #[arg(long)]
+        standby: std::path::PathBuf,
+        /// Replication interval in seconds
+        #[arg(long, default_value_t = 5)]
+        interval: u64,
+        /// Enable verbose logging
+        #[arg(long, short = 'v')]
+        verbose: bool,
+    },
+    /// Promote a standby tree to primary (failover); replication into it stops
+    Promote {
+        /// Standby project root
+        #[arg(default_value = ".")]
+        standby: std::path::PathBuf,
+    },
+    /// Start data retention daemon
+    Retention {
+        /// Project root directory
+        #[arg(long, default_value = ".")]
+        project: std::path::PathBuf,
+        /// Enforce retention for every registered project
+        #[arg(long)]
+        all: bool,
+        /// Enforcement mode (dry-run, approval, enforce)
+        #[arg(long, default_value = "dry-run")]
+        mode: String,
+        /// Approve the project's pending retention list and exit
+        #[arg(long)]
+        approve: bool,
+        /// Enforcement interval in seconds
+        #[arg(long, default_value_t = 3600)]
+        interval: u64,
+        /// Enable verbose logging
+        #[arg(long, short = 'v')]
+        verbose: bool,
+    },
+    /// Run project daemons under one supervisor
+    Supervise {
+        /// Supervisor config file (default: edge router for every registered project)
+        #[arg(long)]
+        config: Option<std::path::PathBuf>,
+        /// Enable verbose logging
+        #[arg(long, short = 'v')]
+        verbose: bool,
+    },
+    /// Send an admin command to a running daemon's control socket
+    Control {
+        /// Control socket of the daemon
+        #[arg(long)]
+        socket: std::path::PathBuf,
+        /// Action (pause, resume, drain, dump, flush, log-level)
+        action: String,
+        /// Kernel for pause, resume and drain
+        kernel: Option<String>,
+        /// Queue to drain (inbox, staging, ready, edges/<name>)
+        #[arg(long)]
+        queue: Option<String>,
+        /// Log level for log-level (error, info, debug)
+        #[arg(long)]
+        level: Option<String>,
+    },
+    /// Start HTTP gateway daemon
+    Gateway {
+        /// Project root directory
+        #[arg(long, default_value = ".")]
+        project: std::path::PathBuf,
+        /// Listen address (default: 127.0.0.1 on the reserved gateway port)
+        #[arg(long)]
+        listen: Option<std::net::SocketAddr>,
         /// Enable verbose logging
         #[arg(long, short = 'v')]
         verbose: bool,
@@ -139,10 +299,10 @@ enum ConceptCommands {
         /// Version (e.g., v1.3.14) - optional if only one version exists
         #[arg(long, short)]
         version: Option<String>,
-        /// Filter by architecture (e.g., aarch64-darwin, x86_64-linux, universal)
+        /// Architecture to select for instead of the host's (e.g., aarch64-darwin, x86_64-linux, universal)
         #[arg(long)]
         arch: Option<String>,
-        /// Filter by runtime (e.g., rs, py, js)
+        /// Only accept this runtime (e.g., rs, py, js; default order from $CKP_RUNTIME_PREFERENCE)
         #[arg(long)]
         runtime: Option<String>,
         /// Optional instance name (e.g., --as mykernel.custom)
@@ -154,6 +314,34 @@ enum ConceptCommands {
         /// Concept name
         name: String,
     },
+    /// Move a concept's storage and inbox between flat and sharded layouts
+    Shard {
+        /// Concept name
+        name: String,
+        /// Flatten a sharded concept back into single directories
+        #[arg(long)]
+        unshard: bool,
+    },
+    /// List a concept's quarantined jobs with their diagnosis
+    Quarantine {
+        /// Concept name
+        name: String,
+    },
+    /// Move a fixed quarantined job back to a concept's inbox
+    Requeue {
+        /// Concept name
+        name: String,
+        /// Transaction ID of the quarantined job
+        tx_id: String,
+    },
+    /// Show a concept's instances and queued jobs as they stood at a past time
+    History {
+        /// Concept name
+        name: String,
+        /// Point in time (RFC 3339, e.g. 2025-11-29T03:12:00Z)
+        #[arg(long)]
+        at: String,
+    },
     /// Start a concept instance
     Start {
         /// Concept name or URN
@@ -241,6 +429,57 @@ enum ProjectCommands {
         /// Project name
         name: String,
     },
+    /// Show aggregated health of a project's kernels
+    Status {
+        /// Project name (defaults to current project)
+        name: Option<String>,
+        /// Print the report as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Export the RBAC policy of every kernel as one bundle for review
+    ExportPolicy {
+        /// Output file (stdout if omitted)
+        #[arg(long, short)]
+        out: Option<String>,
+        /// Bundle format (json, turtle); only JSON bundles can be imported
+        #[arg(long, default_value = "json")]
+        format: String,
+    },
+    /// Apply a reviewed RBAC policy bundle to the kernels' ontologies
+    ImportPolicy {
+        /// JSON bundle file
+        file: String,
+        /// Apply even to kernels whose ontology changed since export
+        #[arg(long)]
+        force: bool,
+    },
+    /// Write the allowed/denied edge matrix of the project's kernels, or check it
+    EdgeMatrix {
+        /// Output file (stdout if omitted)
+        #[arg(long, short, conflicts_with = "check")]
+        out: Option<String>,
+        /// Fixture to compare the project against (exits non-zero on changes)
+        #[arg(long)]
+        check: Option<String>,
+    },
+}
+
+#[derive(Subcommand)]
+enum SchemaCommands {
+    /// Print the schema of a protocol file (job, receipt, edge, notification, transaction)
+    Show {
+        kind: ckp_core::ProtocolFile,
+    },
+    /// Write every schema to <dir>/<kind>.schema.json
+    Export {
+        dir: std::path::PathBuf,
+    },
+    /// Check a file against its protocol schema
+    Validate {
+        kind: ckp_core::ProtocolFile,
+        file: std::path::PathBuf,
+    },
 }
 
 #[derive(Subcommand)]
@@ -259,6 +498,11 @@ enum EdgeCommands {
         /// Target concept
         target: String,
     },
+    /// Create a concept's payload encryption key and print its public key
+    Keygen {
+        /// Concept that receives sealed payloads
+        concept: String,
+    },
 }
 
 #[derive(Subcommand)]
@@ -270,6 +514,44 @@ enum TopLevelPackageCommands {
         /// Path to .tar.gz file
         file: String,
     },
+    /// Pack a kernel directory into a package in the cache
+    Pack {
+        /// Kernel directory containing conceptkernel.yaml
+        path: String,
+    },
+    /// Search packages in the remote registry
+    Search {
+        /// Name fragment to search for
+        query: String,
+        /// Registry URL (defaults to $CKP_REGISTRY_URL)
+        #[arg(long)]
+        registry: Option<String>,
+    },
+    /// Publish a cached package to the remote registry ($CKP_REGISTRY_TOKEN)
+    Publish {
+        /// Package name
+        name: String,
+        /// Version to publish
+        #[arg(long, short)]
+        version: String,
+        /// Registry URL (defaults to $CKP_REGISTRY_URL)
+        #[arg(long)]
+        registry: Option<String>,
+    },
+    /// Manage keys trusted to sign packages
+    Trust {
+        #[command(subcommand)]
+        command: TrustCommands,
+    },
+    /// Evict least-recently-used packages not referenced by any registered project
+    Gc {
+        /// Cache size budget, e.g. 500M or 2G (defaults to $CKP_CACHE_MAX_SIZE or 1G)
+        #[arg(long)]
+        max_size: Option<String>,
+        /// Report what would be evicted without deleting anything
+        #[arg(long)]
+        dry_run: bool,
+    },
     /// Fork a cached package to create new kernel
     Fork {
         /// Source package name (e.g., System.Gateway.HTTP)
@@ -289,6 +571,22 @@ enum TopLevelPackageCommands {
     },
 }
 
+#[derive(Subcommand)]
+enum TrustCommands {
+    /// List trusted signing keys
+    List,
+    /// Trust a minisign public key file
+    Add {
+        /// Path to .pub file
+        file: String,
+    },
+    /// Stop trusting a key
+    Remove {
+        /// Key id (as shown by list)
+        key_id: String,
+    },
+}
+
 /// Handle `ckr stop <kernel>` command
 async fn handle_stop(kernel: &str) -> Result<(), Box<dyn std::error::Error>> {
     use ckp_core::KernelManager;
@@ -519,6 +817,7 @@ fn print_status_table(rows: Vec<StatusRow>, wide: bool) {
 /// - node:hot/python:hot/rust:hot without PID = DOWN (service is not running)
 /// - node:cold/python:cold/rust:cold with PID = IDLE (processing)
 /// - node:cold/python:cold/rust:cold without PID = SLEEP (awaiting work)
+/// - node:warm/python:warm/rust:warm with PID = IDLE, without = STANDBY (started on first job)
 /// - Unknown type = SLEEP
 fn calculate_mode(kernel_type: &str, pid: Option<u32>) -> String {
     match (kernel_type, pid) {
@@ -530,6 +829,10 @@ fn calculate_mode(kernel_type: &str, pid: Option<u32>) -> String {
         (t, Some(_)) if t.contains(":cold") => "IDLE".to_string(),
         (t, None) if t.contains(":cold") => "SLEEP".to_string(),
 
+        // Warm kernels (started by the router on their first job)
+        (t, Some(_)) if t.contains(":warm") => "IDLE".to_string(),
+        (t, None) if t.contains(":warm") => "STANDBY".to_string(),
+
         // Manual or unknown
         (_, None) => "SLEEP".to_string(),
         (_, Some(_)) => "IDLE".to_string(),
@@ -631,6 +934,30 @@ fn resolve_project_root() -> Result<std::path::PathBuf, Box<dyn std::error::Erro
     Err("Error: Not in a ConceptKernel project and no current project set".into())
 }
 
+/// PackageManager with a registry from `--registry` or `$CKP_REGISTRY_URL`,
+/// the default trust store and the trust policy from `$CKP_TRUST_POLICY`
+fn package_manager_with_registry(url: Option<&str>) -> Result<ckp_core::PackageManager, Box<dyn std::error::Error>> {
+    use ckp_core::{PackageManager, RegistryClient, TrustPolicy, TrustStore};
+
+    let policy = match std::env::var(ckp_core::cache::TRUST_POLICY_ENV) {
+        Ok(value) if !value.is_empty() => value.parse::<TrustPolicy>()?,
+        _ => TrustPolicy::default(),
+    };
+    let pm = PackageManager::new()?
+        .with_trust_store(TrustStore::load(TrustStore::default_dir()?)?)
+        .with_trust_policy(policy)
+        .with_env_options();
+    let registry = match url {
+        Some(url) => Some(RegistryClient::new(url)?.with_env_options()?),
+        None => RegistryClient::from_env()?,
+    };
+
+    Ok(match registry {
+        Some(registry) => pm.with_registry(registry),
+        None => pm,
+    })
+}
+
 /// Handle `ckr create-edge <predicate> <source> <target>` command
 fn handle_create_edge(predicate: &str, source: &str, target: &str) -> Result<(), Box<dyn std::error::Error>> {
     use ckp_core::EdgeKernel;
@@ -886,6 +1213,32 @@ fn handle_projects_remove(project_name: &str) -> Result<(), Box<dyn std::error::
     Ok(())
 }
 
+/// Handle `ckr project status [name]` command
+async fn handle_project_status(name: Option<String>, json: bool) -> Result<(), Box<dyn std::error::Error>> {
+    use ckp_core::{OutputFormat, ProjectRegistry, StatusView};
+
+    let mut registry = ProjectRegistry::new()?;
+
+    let project_name = match name.or(registry.get_current_name()?) {
+        Some(name) => name,
+        None => {
+            eprintln!("No project given and no current project set.");
+            println!("\nUse `ckr project switch <name>` to set a current project.");
+            std::process::exit(1);
+        }
+    };
+
+    let health = registry.status(&project_name).await?;
+
+    let format = OutputFormat::from_json_flag(json);
+    if format == OutputFormat::Table {
+        println!();
+    }
+    print!("{}", health.render_status(format));
+
+    Ok(())
+}
+
 /// Generate a unique project name
 fn generate_project_name() -> String {
     use rand::Rng;
@@ -948,6 +1301,9 @@ async fn handle_init_with_path(path: Option<String>, force: bool) -> Result<(),
                     protocol: None,
                     default_user: None,
                     ontology: None,
+                    redaction: None,
+                    retention: None,
+                    quotas: None,
                 },
             };
 
@@ -995,6 +1351,9 @@ async fn handle_init_with_path(path: Option<String>, force: bool) -> Result<(),
                 protocol: None,
                 default_user: None,
                 ontology: None,
+                redaction: None,
+                retention: None,
+                quotas: None,
             },
         };
 
@@ -1110,7 +1469,8 @@ fn print_custom_help() {
     println!("  concept       Manage concepts (list, create, load, unload, start, stop, export, cache)");
     println!("  project       Manage projects (list, create, current, switch, remove)");
     println!("  edge          Manage edges (list, create)");
-    println!("  package       Manage packages (list, import, fork)");
+    println!("  package       Manage packages (list, import, pack, search, publish, trust, gc, fork)");
+    println!("  install       Install a package from the cache or a remote registry");
     println!("  up            Start all concepts in the project");
     println!("  down          Stop all running concepts in the project");
     println!("  status        Show status of all concepts");
@@ -1208,6 +1568,9 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     let cli = Cli::parse();
 
+    // Diagnostics go to stderr as tracing events; filter with $CKP_LOG
+    ckp_core::logging::init(cli.log_format.unwrap_or_else(ckp_core::LogFormat::from_env), "info")?;
+
     match cli.command {
         // ===== CONCEPT COMMANDS =====
         Commands::Concept { command } => {
@@ -1236,7 +1599,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                 }
 
                 ConceptCommands::Load { name, version, arch, runtime, as_name } => {
-                    use ckp_core::{PackageManager, ProjectRegistry, CkpError};
+                    use ckp_core::{PackageManager, ProjectRegistry};
                     use std::path::PathBuf;
 
                     // Try current directory first
@@ -1254,59 +1617,30 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
                     let pm = PackageManager::new()?;
 
-                    // List cached packages for this concept
-                    let packages = pm.list_cached()?;
-                    let mut matching: Vec<_> = packages.iter().filter(|p| p.name == name).collect();
-
-                    // Apply filters
-                    if let Some(ref a) = arch {
-                        matching.retain(|p| &p.arch == a);
+                    // --arch/--runtime override the host platform used to pick a build
+                    let mut platform = pm.platform().clone();
+                    if let Some(a) = arch {
+                        platform.arch = a;
                     }
-                    if let Some(ref r) = runtime {
-                        matching.retain(|p| &p.runtime == r);
+                    if let Some(r) = runtime {
+                        platform.runtimes = vec![r];
                     }
 
-                    // Auto-detect version if not specified
-                    let final_version = if let Some(v) = version {
-                        v
-                    } else {
-                        match matching.len() {
-                            0 => {
-                                eprintln!("Error: No matching packages found for '{}'", name);
-                                if arch.is_some() || runtime.is_some() {
-                                    eprintln!("  Filters: arch={:?}, runtime={:?}", arch, runtime);
-                                }
-                                eprintln!("\nAvailable packages for '{}':", name);
-                                let all_matching: Vec<_> = packages.iter().filter(|p| p.name == name).collect();
-                                for pkg in all_matching {
-                                    eprintln!("  {}@{} [{}/{}]", pkg.name, pkg.version, pkg.arch, pkg.runtime);
-                                }
-                                std::process::exit(1);
-                            }
-                            1 => {
-                                println!("Auto-detected: {}@{} [{}/{}]", matching[0].name, matching[0].version, matching[0].arch, matching[0].runtime);
-                                matching[0].version.clone()
-                            }
-                            _ => {
-                                eprintln!("Error: Multiple matching packages found for '{}':", name);
-                                for pkg in &matching {
-                                    eprintln!("  {}@{} [{}/{}]", pkg.name, pkg.version, pkg.arch, pkg.runtime);
-                                }
-                                eprintln!("\nPlease specify which package to load:");
-                                eprintln!("  ckr concept load {} --runtime <RUNTIME>", name);
-                                eprintln!("  ckr concept load {} --arch <ARCH>", name);
-                                eprintln!("\nExample:");
-                                eprintln!("  ckr concept load {} --runtime rs   # Load Rust version", name);
-                                eprintln!("  ckr concept load {} --runtime py   # Load Python version", name);
-                                std::process::exit(1);
-                            }
+                    let packages = pm.list_cached()?;
+                    let selected_pkg = match platform.select(&name, version.as_deref(), &packages) {
+                        Ok(pkg) => pkg,
+                        Err(e) => {
+                            eprintln!("Error: {}", e);
+                            eprintln!("\nTo choose a build explicitly:");
+                            eprintln!("  ckr concept load {} --runtime <RUNTIME>", name);
+                            eprintln!("  ckr concept load {} --arch <ARCH>", name);
+                            std::process::exit(1);
                         }
                     };
-
-                    // Find the selected package
-                    let selected_pkg = matching.into_iter().find(|p| p.version == final_version).ok_or_else(|| {
-                        CkpError::FileNotFound(format!("Package not found: {}@{}", name, final_version))
-                    })?;
+                    let final_version = selected_pkg.version.clone();
+                    if version.is_none() {
+                        println!("Auto-detected: {}@{} [{}/{}]", selected_pkg.name, selected_pkg.version, selected_pkg.arch, selected_pkg.runtime);
+                    }
 
                     // Resolve instance name (supports multi-instance)
                     let instance_name = pm.resolve_instance_name(&name, as_name.as_deref(), &root)?;
@@ -1323,21 +1657,83 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                 }
 
                 ConceptCommands::Unload { name } => {
-                    use std::fs;
+                    use ckp_core::KernelManager;
 
                     let root = std::env::current_dir()?;
-                    let concept_dir = root.join("concepts").join(&name);
+                    let manager = KernelManager::new(root.clone())?;
 
-                    if !concept_dir.exists() {
+                    if !manager.get_kernel_dir(&name).exists() {
                         eprintln!("Concept not found: {}", name);
                         std::process::exit(1);
                     }
 
-                    fs::remove_dir_all(&concept_dir)?;
+                    // Records the Deleted lifecycle transition
+                    manager.delete_kernel(&name, false)?;
+                    ckp_core::Lockfile::unlock(&root, &name)?;
                     println!("✓ Unloaded concept: {}", name);
                     println!("  (Package remains in cache)");
                 }
 
+                ConceptCommands::Shard { name, unshard } => {
+                    use ckp_core::{InstanceScanner, KernelManager};
+
+                    let root = std::env::current_dir()?;
+                    let manager = KernelManager::new(root.clone())?;
+                    let kernel_dir = manager.get_kernel_dir(&name);
+
+                    if !kernel_dir.exists() {
+                        eprintln!("Concept not found: {}", name);
+                        std::process::exit(1);
+                    }
+
+                    let migration = ckp_core::storage::shard::migrate_kernel(&root.join("concepts"), &name, !unshard)?;
+                    if kernel_dir.join("storage").is_dir() {
+                        InstanceScanner::new(kernel_dir, name.clone()).rebuild_index()?;
+                    }
+
+                    let layout = if unshard { "flat" } else { "sharded" };
+                    println!("✓ Migrated {} to {} layout", name, layout);
+                    println!("  Instances moved: {}", migration.instances);
+                    println!("  Inbox jobs moved: {}", migration.jobs);
+                    println!("  Edge links repointed: {}", migration.relinked);
+                    if !unshard {
+                        println!("  (Add the '{}' capability to the ontology to shard new writes)", ckp_core::storage::SHARDING_CAPABILITY);
+                    }
+                }
+
+                ConceptCommands::Quarantine { name } => {
+                    use ckp_core::KernelManager;
+
+                    let manager = KernelManager::new(std::env::current_dir()?)?;
+                    let diagnoses = manager.quarantined(&name)?;
+                    if diagnoses.is_empty() {
+                        println!("No quarantined jobs for {}", name);
+                    }
+                    for diagnosis in diagnoses {
+                        println!("{}  {:?}  {}", diagnosis.tx_id, diagnosis.reason, diagnosis.quarantined_at);
+                        match diagnosis.path {
+                            Some(path) => println!("  at {}: {}", path, diagnosis.error),
+                            None => println!("  {}", diagnosis.error),
+                        }
+                    }
+                }
+
+                ConceptCommands::Requeue { name, tx_id } => {
+                    use ckp_core::KernelManager;
+
+                    let manager = KernelManager::new(std::env::current_dir()?)?;
+                    let inbox_path = manager.requeue_quarantined(&name, &tx_id)?;
+                    println!("✓ Requeued {} to {}", tx_id, inbox_path.display());
+                }
+
+                ConceptCommands::History { name, at } => {
+                    let at = chrono::DateTime::parse_from_rfc3339(&at)
+                        .map_err(|e| format!("Invalid --at '{}': {}", at, e))?
+                        .with_timezone(&chrono::Utc);
+                    let state = ckp_core::KernelHistory::new(std::env::current_dir()?).state_at(&name, at)?;
+                    println!("{}", serde_json::to_string_pretty(&state)?);
+                }
+
                 ConceptCommands::Start { name, as_name, watch: _ } => {
                     use ckp_core::KernelManager;
                     use std::collections::HashMap;
@@ -1373,6 +1769,12 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                         if let Some(watcher_pid) = result.watcher_pid {
                             println!("  Watcher PID: {}", watcher_pid);
                         }
+                    } else if let Some(error) = &result.error {
+                        eprintln!("✗ Concept {} failed to start: {}", name, error);
+                        for line in &result.log_tail {
+                            eprintln!("  | {}", line);
+                        }
+                        std::process::exit(1);
                     } else {
                         println!("✓ Started concept: {}", name);
                         if let Some(watcher_pid) = result.watcher_pid {
@@ -1413,20 +1815,23 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                     let final_version = if let Some(driver) = VersionDriverFactory::detect(&concept_path, &name) {
                         match driver.get_version()? {
                             Some(version_info) => {
-                                eprintln!("[VersionDriver] Detected version from {}: {}",
-                                    version_info.backend, version_info.version);
+                                tracing::info!(
+                                    backend = %version_info.backend,
+                                    version = %version_info.version,
+                                    "detected version"
+                                );
                                 if !version_info.is_clean {
-                                    eprintln!("[VersionDriver] Warning: Version is not clean (uncommitted changes)");
+                                    tracing::warn!(version = %version_info.version, "version has uncommitted changes");
                                 }
                                 version_info.version
                             }
                             None => {
-                                eprintln!("[VersionDriver] No version tags found, using CLI version: {}", version);
+                                tracing::info!(version = %version, "no version tags found, using CLI version");
                                 version.clone()
                             }
                         }
                     } else {
-                        eprintln!("[VersionDriver] No versioning detected, using CLI version: {}", version);
+                        tracing::info!(version = %version, "no versioning detected, using CLI version");
                         version.clone()
                     };
 
@@ -1518,6 +1923,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                             }
 
                             fs::remove_dir_all(&concept_dir)?;
+                            ckp_core::Lockfile::unlock(&root, name)?;
                             println!("✓ Unloaded package: {}", name_version);
                             println!("  (Package remains in cache)");
                         }
@@ -1556,6 +1962,29 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         }
 
         // ===== FORK COMMAND =====
+        Commands::Schema { command } => {
+            match command {
+                SchemaCommands::Show { kind } => {
+                    println!("{}", serde_json::to_string_pretty(&kind.schema())?);
+                }
+                SchemaCommands::Export { dir } => {
+                    for path in ckp_core::protocol::write_protocol_schemas(&dir)? {
+                        println!("✓ {}", path.display());
+                    }
+                }
+                SchemaCommands::Validate { kind, file } => {
+                    let bytes = std::fs::read(&file)?;
+                    match ckp_core::validate_protocol_file(kind, &bytes) {
+                        Ok(()) => println!("✓ {} is a valid {} file", file.display(), kind),
+                        Err(e) => {
+                            println!("✗ {}", e);
+                            std::process::exit(1);
+                        }
+                    }
+                }
+            }
+        }
+
         Commands::Fork { source, name, clean, tag, no_start } => {
             use ckp_core::PackageManager;
 
@@ -1597,6 +2026,68 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             }
         }
 
+        // ===== INSTALL COMMAND =====
+        Commands::Install { name, version, registry, as_name, strict, locked, offline } => {
+            let root = resolve_project_root()?;
+            let mut pm = package_manager_with_registry(registry.as_deref())?;
+            if strict {
+                pm = pm.with_trust_policy(ckp_core::TrustPolicy::Strict);
+            }
+            if offline {
+                pm = pm.with_offline(true);
+            }
+
+            if locked {
+                println!("Installing from {}", ckp_core::cache::LOCKFILE_NAME);
+
+                // Registry client uses blocking HTTP
+                let installed = tokio::task::block_in_place(|| pm.install_from_lock(&root))?;
+                for concept_dir in &installed {
+                    println!("  + {}", concept_dir.display());
+                }
+
+                println!("\n✓ Installed {} locked package(s)", installed.len());
+                return Ok(());
+            }
+
+            let name = name.expect("clap requires a name without --locked");
+            let (name, range) = match name.split_once('@') {
+                Some((name, range)) => (name.to_string(), range.to_string()),
+                None => {
+                    let range = version.as_deref().map(|v| format!("={}", v)).unwrap_or_else(|| "*".to_string());
+                    (name, range)
+                }
+            };
+            let instance_name = pm.resolve_instance_name(&name, as_name.as_deref(), &root)?;
+
+            println!("Resolving {} {}", name, range);
+
+            let mut requests = std::collections::BTreeMap::new();
+            requests.insert(name.clone(), range);
+
+            // Registry client uses blocking HTTP
+            let resolution = tokio::task::block_in_place(|| pm.resolve(&requests))?;
+
+            for package in &resolution.packages {
+                let requested = package.name == name;
+                if !requested && root.join("concepts").join(&package.name).exists() {
+                    println!("  = {}@{} (already installed)", package.name, package.version);
+                    continue;
+                }
+
+                let instance = if requested { Some(instance_name.as_str()) } else { None };
+                let concept_dir = tokio::task::block_in_place(|| {
+                    let cached = pm.fetch(&package.name, Some(&package.version))?;
+                    pm.install_from_package(&cached, &root, instance)
+                })?;
+                println!("  + {}@{} → {}", package.name, package.version, concept_dir.display());
+            }
+
+            println!("\n✓ Installed successfully");
+            println!("  Instance: {}", instance_name);
+            println!("\nTo start: ckr concept start {}", instance_name);
+        }
+
         // ===== PROJECT COMMANDS =====
         Commands::Project { command } => {
             match command {
@@ -1619,6 +2110,71 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                 ProjectCommands::Remove { name } => {
                     handle_projects_remove(&name)?;
                 }
+
+                ProjectCommands::Status { name, json } => {
+                    handle_project_status(name, json).await?;
+                }
+
+                ProjectCommands::ExportPolicy { out, format } => {
+                    let bundle = ckp_core::PolicyBundle::export(&resolve_project_root()?)?;
+                    let content = match format.as_str() {
+                        "json" => serde_json::to_string_pretty(&bundle)?,
+                        "turtle" | "ttl" => bundle.to_turtle(),
+                        other => return Err(format!("Unknown bundle format '{}' (json, turtle)", other).into()),
+                    };
+                    match out {
+                        Some(out) => {
+                            std::fs::write(&out, content)?;
+                            println!("✓ Exported policy of {} kernel(s) to {}", bundle.kernels.len(), out);
+                        }
+                        None => println!("{}", content),
+                    }
+                }
+
+                ProjectCommands::ImportPolicy { file, force } => {
+                    let bundle = ckp_core::PolicyBundle::from_json(&std::fs::read_to_string(&file)?)?;
+                    let applied = bundle.apply(&resolve_project_root()?, force)?;
+                    if applied.is_empty() {
+                        println!("Policy already up to date");
+                    }
+                    for kernel in applied {
+                        println!("✓ Updated policy of {}", kernel);
+                    }
+                }
+
+                ProjectCommands::EdgeMatrix { out, check } => {
+                    let root = resolve_project_root()?;
+                    if let Some(fixture) = check {
+                        let changes = ckp_core::EdgeMatrix::verify(&root, std::path::Path::new(&fixture))?;
+                        if changes.is_empty() {
+                            println!("✓ Edge authorization matches {}", fixture);
+                            return Ok(());
+                        }
+                        eprintln!("Edge authorization differs from {} ({} pair(s)):", fixture, changes.len());
+                        for change in changes {
+                            eprintln!("  {}", change);
+                        }
+                        std::process::exit(1);
+                    }
+
+                    let matrix = ckp_core::EdgeMatrix::generate(&root)?;
+                    let content = serde_json::to_string_pretty(&matrix)?;
+                    match out {
+                        Some(out) => {
+                            std::fs::write(&out, content)?;
+                            let pairs = |decisions: &std::collections::BTreeMap<String, std::collections::BTreeSet<String>>| {
+                                decisions.values().map(|targets| targets.len()).sum::<usize>()
+                            };
+                            println!(
+                                "✓ Wrote edge matrix ({} allowed, {} denied) to {}",
+                                pairs(&matrix.allowed),
+                                pairs(&matrix.denied),
+                                out
+                            );
+                        }
+                        None => println!("{}", content),
+                    }
+                }
             }
         }
 
@@ -1636,6 +2192,23 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                 EdgeCommands::Create { predicate, source, target } => {
                     handle_create_edge(&predicate, &source, &target)?;
                 }
+
+                EdgeCommands::Keygen { concept } => {
+                    use ckp_core::PayloadKeyPair;
+
+                    let root = resolve_project_root()?;
+                    let kernel_dir = root.join("concepts").join(&concept);
+                    if !kernel_dir.exists() {
+                        return Err(format!("Concept not found: {}", concept).into());
+                    }
+
+                    let keys = PayloadKeyPair::load_or_generate(&kernel_dir)?;
+                    println!("Private key: {}", kernel_dir.join(ckp_core::edge::PAYLOAD_KEY_FILE).display());
+                    println!("\nAdd to {}/conceptkernel.yaml:\n", concept);
+                    println!("spec:");
+                    println!("  payload_encryption:");
+                    println!("    public_key: {}", keys.public_key_hex());
+                }
             }
         }
 
@@ -1696,6 +2269,123 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                     println!("  Size: {} bytes", pkg_info.size_bytes);
                 }
 
+                TopLevelPackageCommands::Pack { path } => {
+                    use ckp_core::PackageManager;
+                    use std::path::Path;
+
+                    println!("Packing kernel: {}", path);
+
+                    let pm = PackageManager::new()?;
+                    let pkg_info = pm.pack(Path::new(&path))?;
+
+                    println!("\n✓ Package created successfully");
+                    println!("  Name: {}", pkg_info.name);
+                    println!("  Version: {}", pkg_info.version);
+                    println!("  Arch: {}", pkg_info.arch);
+                    println!("  Runtime: {}", pkg_info.runtime);
+                    println!("  Size: {} bytes", pkg_info.size_bytes);
+                    println!("  File: {}", pm.get_cache_dir().join(&pkg_info.filename).display());
+                }
+
+                TopLevelPackageCommands::Search { query, registry } => {
+                    let pm = package_manager_with_registry(registry.as_deref())?;
+                    let packages = tokio::task::block_in_place(|| pm.search_remote(&query))?;
+
+                    if packages.is_empty() {
+                        println!("No packages matching '{}'.", query);
+                    } else {
+                        println!("\nNAME                            VERSION    ARCH            RUNTIME  SIZE");
+                        println!("-----------------------------------------------------------------------");
+
+                        for pkg in &packages {
+                            println!(
+                                "{:<32}{:<11}{:<16}{:<9}{}K",
+                                pkg.name,
+                                pkg.version,
+                                pkg.arch,
+                                pkg.runtime,
+                                pkg.size_bytes / 1024
+                            );
+                        }
+
+                        println!("\nTotal: {} package(s)", packages.len());
+                    }
+                }
+
+                TopLevelPackageCommands::Publish { name, version, registry } => {
+                    let pm = package_manager_with_registry(registry.as_deref())?;
+
+                    println!("Publishing package: {}@{}", name, version);
+                    let published = tokio::task::block_in_place(|| pm.publish(&name, &version))?;
+
+                    println!("\n✓ Package published successfully");
+                    for pkg in &published {
+                        println!("  {} [{}/{}]", pkg.filename, pkg.arch, pkg.runtime);
+                    }
+                }
+
+                TopLevelPackageCommands::Trust { command } => {
+                    use ckp_core::TrustStore;
+                    use ckp_core::cache::TrustedKey;
+
+                    let mut store = TrustStore::load(TrustStore::default_dir()?)?;
+
+                    match command {
+                        TrustCommands::List => {
+                            if store.keys().is_empty() {
+                                println!("No trusted keys.");
+                            } else {
+                                println!("\nKEY ID            COMMENT");
+                                println!("--------------------------------------------------");
+                                for key in store.keys() {
+                                    println!("{:<18}{}", key.key_id(), key.comment());
+                                }
+                                println!("\nTotal: {} key(s)", store.keys().len());
+                            }
+                            println!("Trust store: {}", TrustStore::default_dir()?.display());
+                        }
+                        TrustCommands::Add { file } => {
+                            let key = TrustedKey::parse(&std::fs::read_to_string(&file)?)?;
+                            let key_id = key.key_id();
+                            let path = store.add(key)?;
+                            println!("✓ Trusted key {}", key_id);
+                            println!("  Stored at: {}", path.display());
+                        }
+                        TrustCommands::Remove { key_id } => {
+                            if store.remove(&key_id)? {
+                                println!("✓ Removed trusted key {}", key_id);
+                            } else {
+                                println!("Key not found: {}", key_id);
+                            }
+                        }
+                    }
+                }
+
+                TopLevelPackageCommands::Gc { max_size, dry_run } => {
+                    use ckp_core::{GcPolicy, PackageManager};
+
+                    let policy = match max_size {
+                        Some(size) => GcPolicy::new(ckp_core::cache::parse_size(&size)?),
+                        None => GcPolicy::from_env()?,
+                    }.with_dry_run(dry_run);
+
+                    let pm = PackageManager::new()?;
+                    let report = pm.gc(&policy)?;
+                    let mib = |bytes: u64| bytes as f64 / (1024.0 * 1024.0);
+
+                    let verb = if dry_run { "Would evict" } else { "Evicted" };
+                    for pkg in &report.evicted {
+                        println!("  - {} {}@{} ({}, {})", verb, pkg.name, pkg.version, pkg.arch, pkg.runtime);
+                    }
+
+                    println!("\n{} {} package(s), freeing {:.1}M", verb, report.evicted.len(), mib(report.freed_bytes()));
+                    println!("  Cache size: {:.1}M → {:.1}M (budget {:.1}M)", mib(report.size_before), mib(report.size_after), mib(policy.max_size_bytes));
+                    println!("  Protected by projects: {}", report.protected.len());
+                    if report.over_budget(&policy) {
+                        println!("  ⚠ Cache still exceeds budget: remaining packages are referenced by projects");
+                    }
+                }
+
                 TopLevelPackageCommands::Fork { source, name, clean, tag, no_start } => {
                     use ckp_core::PackageManager;
 
@@ -1762,7 +2452,8 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             let manager = KernelManager::new(root)?;
             let results = manager.start_all().await?;
 
-            let started = results.iter().filter(|r| !r.already_running).count();
+            let failed = results.iter().filter(|r| r.error.is_some()).count();
+            let started = results.iter().filter(|r| !r.already_running).count() - failed;
             let already_running = results.iter().filter(|r| r.already_running).count();
 
             if started > 0 {
@@ -1771,6 +2462,9 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             if already_running > 0 {
                 println!("  {} concept(s) already running", already_running);
             }
+            if failed > 0 {
+                println!("  {} concept(s) exited during startup (see logs/governor.log and logs/tool.log)", failed);
+            }
             if results.is_empty() {
                 println!("No concepts found.");
             }
@@ -1869,7 +2563,18 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
         Commands::Daemon { command } => {
             match command {
-                DaemonCommands::EdgeRouter { project, verbose } => {
+                DaemonCommands::EdgeRouter {
+                    project,
+                    debounce_ms,
+                    reconcile_interval,
+                    metrics,
+                    control,
+                    partition,
+                    lease_ttl,
+                    dedup_window,
+                    on_duplicate,
+                    verbose,
+                } => {
                     // Resolve project path
                     let project_path = if project.is_absolute() {
                         project.clone()
@@ -1885,15 +2590,239 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
                     // Set up SIGTERM/SIGINT handler
                     ctrlc::set_handler(move || {
-                        eprintln!("[EdgeRouter] Received SIGTERM/SIGINT, shutting down gracefully...");
+                        tracing::info!(daemon = "edge-router", "received SIGTERM/SIGINT, shutting down");
                         shutdown_clone.store(true, std::sync::atomic::Ordering::SeqCst);
                     })?;
 
                     // Create and start the daemon using library module
-                    let daemon = ckp_core::EdgeRouterDaemon::new(project_path, verbose)?;
+                    let mut daemon = ckp_core::EdgeRouterDaemon::new(project_path, verbose)?
+                        .with_debounce(std::time::Duration::from_millis(debounce_ms))
+                        .with_reconcile_interval(std::time::Duration::from_secs(reconcile_interval.max(1)))
+                        .with_dedup(dedup_window, on_duplicate);
+
+                    // Optional metrics endpoint, stopped when it goes out of scope
+                    let _metrics_server = match metrics {
+                        Some(addr) => {
+                            let registry = std::sync::Arc::new(ckp_core::DaemonMetrics::new());
+                            daemon = daemon.with_metrics(registry.clone());
+                            let server = ckp_core::MetricsServer::start(addr, registry)?;
+                            tracing::info!(addr = %server.local_addr(), "serving metrics at /metrics");
+                            Some(server)
+                        }
+                        None => None,
+                    };
+
+                    if let Some(socket) = control {
+                        daemon = daemon.with_control_socket(socket);
+                    }
+
+                    if let Some(partition) = partition {
+                        daemon = daemon
+                            .with_partition(partition)
+                            .with_lease_ttl(std::time::Duration::from_secs(lease_ttl.max(3)));
+                    }
+
                     daemon.start(shutdown)?;
 
-                    eprintln!("[EdgeRouter] Shutdown complete");
+                    tracing::info!(daemon = "edge-router", "shutdown complete");
+                }
+                DaemonCommands::Control { socket, action, kernel, queue, level } => {
+                    use ckp_core::daemon::ControlCommand;
+
+                    let require_kernel = || kernel.clone().ok_or_else(|| format!("'{}' requires a kernel name", action));
+                    let command = match action.as_str() {
+                        "pause" => ControlCommand::Pause { kernel: require_kernel()? },
+                        "resume" => ControlCommand::Resume { kernel: require_kernel()? },
+                        "drain" => ControlCommand::Drain { kernel: require_kernel()?, queue },
+                        "dump" => ControlCommand::Dump,
+                        "flush" => ControlCommand::Flush,
+                        "log-level" => {
+                            let level = level.ok_or("'log-level' requires --level")?;
+                            ControlCommand::LogLevel { level: level.parse()? }
+                        }
+                        other => return Err(format!("Unknown control action: {}", other).into()),
+                    };
+
+                    #[cfg(unix)]
+                    {
+                        let response = ckp_core::daemon::send_command(&socket, &command)?;
+                        println!("{}", serde_json::to_string_pretty(&response)?);
+                        if !response.ok {
+                            std::process::exit(1);
+                        }
+                    }
+
+                    #[cfg(not(unix))]
+                    {
+                        let _ = (socket, command);
+                        return Err("Control sockets require a Unix platform".into());
+                    }
+                }
+                DaemonCommands::Dispositions { project, interval, verbose } => {
+                    // Resolve project path
+                    let project_path = if project.is_absolute() {
+                        project.clone()
+                    } else {
+                        std::env::current_dir()?.join(project)
+                    };
+
+                    println!("[Daemon] Starting disposition evaluator for project: {}", project_path.display());
+
+                    let shutdown = std::sync::Arc::new(std::sync::atomic::AtomicBool::new(false));
+                    let shutdown_clone = shutdown.clone();
+
+                    ctrlc::set_handler(move || {
+                        tracing::info!(daemon = "dispositions", "received SIGTERM/SIGINT, shutting down");
+                        shutdown_clone.store(true, std::sync::atomic::Ordering::SeqCst);
+                    })?;
+
+                    let daemon = ckp_core::DispositionEvaluatorDaemon::new(project_path, verbose)
+                        .with_interval(std::time::Duration::from_secs(interval));
+                    daemon.start(shutdown)?;
+
+                    tracing::info!(daemon = "dispositions", "shutdown complete");
+                }
+                DaemonCommands::Alarms { project, interval, verbose } => {
+                    // Resolve project path
+                    let project_path = if project.is_absolute() {
+                        project.clone()
+                    } else {
+                        std::env::current_dir()?.join(project)
+                    };
+
+                    println!("[Daemon] Starting queue alarm daemon for project: {}", project_path.display());
+
+                    let shutdown = std::sync::Arc::new(std::sync::atomic::AtomicBool::new(false));
+                    let shutdown_clone = shutdown.clone();
+
+                    ctrlc::set_handler(move || {
+                        tracing::info!(daemon = "alarms", "received SIGTERM/SIGINT, shutting down");
+                        shutdown_clone.store(true, std::sync::atomic::Ordering::SeqCst);
+                    })?;
+
+                    let daemon = ckp_core::QueueAlarmDaemon::new(project_path, verbose)
+                        .with_interval(std::time::Duration::from_secs(interval));
+                    daemon.start(shutdown)?;
+
+                    tracing::info!(daemon = "alarms", "shutdown complete");
+                }
+                DaemonCommands::Replicate { project, standby, interval, verbose } => {
+                    // Resolve project path
+                    let project_path = if project.is_absolute() {
+                        project.clone()
+                    } else {
+                        std::env::current_dir()?.join(project)
+                    };
+
+                    println!("[Daemon] Replicating {} to standby {}", project_path.display(), standby.display());
+
+                    let shutdown = std::sync::Arc::new(std::sync::atomic::AtomicBool::new(false));
+                    let shutdown_clone = shutdown.clone();
+
+                    ctrlc::set_handler(move || {
+                        tracing::info!(daemon = "replicate", "received SIGTERM/SIGINT, shutting down");
+                        shutdown_clone.store(true, std::sync::atomic::Ordering::SeqCst);
+                    })?;
+
+                    let daemon = ckp_core::ReplicationDaemon::new(project_path, standby, verbose)
+                        .with_interval(std::time::Duration::from_secs(interval));
+                    daemon.start(shutdown)?;
+
+                    tracing::info!(daemon = "replicate", "shutdown complete");
+                }
+                DaemonCommands::Promote { standby } => {
+                    let promotion = ckp_core::daemon::promote(&standby)?;
+                    println!("✓ Promoted {} to primary at {}", standby.display(), promotion.promoted_at);
+                    for (kernel, records) in promotion.kernels {
+                        println!("  {} ({} tx records)", kernel, records);
+                    }
+                }
+                DaemonCommands::Retention { project, all, mode, approve, interval, verbose } => {
+                    // Resolve project path
+                    let project_path = if project.is_absolute() {
+                        project.clone()
+                    } else {
+                        std::env::current_dir()?.join(project)
+                    };
+
+                    if approve {
+                        let count = ckp_core::RetentionDaemon::approve(&project_path)?;
+                        println!("[Daemon] Approved {} files for retention in {}", count, project_path.display());
+                        return Ok(());
+                    }
+
+                    let mode: ckp_core::RetentionMode = mode.parse()?;
+                    let daemon = if all {
+                        println!("[Daemon] Starting retention daemon for all registered projects ({:?})", mode);
+                        ckp_core::RetentionDaemon::from_registry(verbose)?
+                    } else {
+                        println!("[Daemon] Starting retention daemon for project: {} ({:?})", project_path.display(), mode);
+                        ckp_core::RetentionDaemon::for_project(project_path, verbose)?
+                    }
+                    .with_mode(mode)
+                    .with_interval(std::time::Duration::from_secs(interval));
+
+                    let shutdown = std::sync::Arc::new(std::sync::atomic::AtomicBool::new(false));
+                    let shutdown_clone = shutdown.clone();
+
+                    ctrlc::set_handler(move || {
+                        tracing::info!(daemon = "retention", "received SIGTERM/SIGINT, shutting down");
+                        shutdown_clone.store(true, std::sync::atomic::Ordering::SeqCst);
+                    })?;
+
+                    daemon.start(shutdown)?;
+
+                    tracing::info!(daemon = "retention", "shutdown complete");
+                }
+                DaemonCommands::Supervise { config, verbose } => {
+                    let mut supervisor = match config {
+                        Some(path) => {
+                            println!("[Daemon] Starting supervisor with config: {}", path.display());
+                            ckp_core::DaemonSupervisor::from_file(path, verbose)?
+                        }
+                        None => {
+                            println!("[Daemon] Starting supervisor for all registered projects");
+                            ckp_core::DaemonSupervisor::from_registry(verbose)?
+                        }
+                    };
+
+                    let shutdown = std::sync::Arc::new(std::sync::atomic::AtomicBool::new(false));
+                    let shutdown_clone = shutdown.clone();
+
+                    ctrlc::set_handler(move || {
+                        tracing::info!(daemon = "supervisor", "received SIGTERM/SIGINT, shutting down");
+                        shutdown_clone.store(true, std::sync::atomic::Ordering::SeqCst);
+                    })?;
+
+                    supervisor.start(shutdown)?;
+
+                    tracing::info!(daemon = "supervisor", "shutdown complete");
+                }
+                DaemonCommands::Gateway { project, listen, verbose } => {
+                    // Resolve project path
+                    let project_path = if project.is_absolute() {
+                        project.clone()
+                    } else {
+                        std::env::current_dir()?.join(project)
+                    };
+
+                    println!("[Daemon] Starting gateway for project: {}", project_path.display());
+
+                    let shutdown = std::sync::Arc::new(std::sync::atomic::AtomicBool::new(false));
+                    let shutdown_clone = shutdown.clone();
+
+                    ctrlc::set_handler(move || {
+                        tracing::info!(daemon = "gateway", "received SIGTERM/SIGINT, shutting down");
+                        shutdown_clone.store(true, std::sync::atomic::Ordering::SeqCst);
+                    })?;
+
+                    let mut daemon = ckp_core::GatewayDaemon::new(project_path, verbose);
+                    if let Some(addr) = listen {
+                        daemon = daemon.with_addr(addr);
+                    }
+                    daemon.start(shutdown).await?;
+
+                    tracing::info!(daemon = "gateway", "shutdown complete");
                 }
                 DaemonCommands::Governor { kernel, project, verbose } => {
                     // Resolve project path
@@ -1904,7 +2833,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                     };
 
                     if verbose {
-                        eprintln!("[Daemon] Starting governor for kernel: {} in project: {}", kernel, project_path.display());
+                        tracing::info!(kernel = %kernel, project = %project_path.display(), "starting governor");
                     }
 
                     // Set up shutdown handling
@@ -1912,7 +2841,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                     let shutdown_clone = shutdown.clone();
 
                     ctrlc::set_handler(move || {
-                        eprintln!("[Governor] Received SIGTERM/SIGINT, shutting down gracefully...");
+                        tracing::info!(daemon = "governor", "received SIGTERM/SIGINT, shutting down");
                         shutdown_clone.store(true, std::sync::atomic::Ordering::SeqCst);
                     })?;
 
@@ -1921,7 +2850,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                     governor.start(shutdown).await?;
 
                     if verbose {
-                        eprintln!("[Governor] Shutdown complete");
+                        tracing::info!(daemon = "governor", "shutdown complete");
                     }
                 }
             }
@@ -1932,6 +2861,9 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         }
     }
 
+    // Flush spans still waiting for OTLP export
+    ckp_core::telemetry::shutdown();
+
     Ok(())
 }
 
@@ -2126,6 +3058,30 @@ async fn handle_query(urn: &str, format: &str) -> Result<(), Box<dyn std::error:
                 sort_field,
             };
 
+            // Phase timings (e.g. ckp://Process?view=phases&kernel=Shop.Orders)
+            if parsed.params.get("view").map(String::as_str) == Some("phases") {
+                let breakdown = tracker.get_phase_breakdown(filters)?;
+                match format {
+                    "json" => println!("{}", serde_json::to_string_pretty(&breakdown)?),
+                    "yaml" => println!("{}", serde_yaml::to_string(&breakdown)?),
+                    _ => {
+                        if breakdown.is_empty() {
+                            println!("No timed phases recorded (enable CKP_PROCESS_TRACKING=phases).");
+                            return Ok(());
+                        }
+                        println!("\n{:<16} {:>8} {:>10} {:>10} {:>10}", "PHASE", "COUNT", "P50_MS", "P95_MS", "P99_MS");
+                        println!("{}", "-".repeat(58));
+                        for (phase, durations) in &breakdown {
+                            println!(
+                                "{:<16} {:>8} {:>10} {:>10} {:>10}",
+                                phase, durations.count, durations.p50, durations.p95, durations.p99
+                            );
+                        }
+                    }
+                }
+                return Ok(());
+            }
+
             // Query processes
             let processes = tracker.query_processes(filters)?;
 
@@ -0,0 +1,295 @@
+//! Cache garbage collection
+//!
+//! Evicts least-recently-used package versions until the cache fits a size
+//! budget. Versions referenced by a registered project — pinned in its
+//! `ckp.lock`, or backing an unlocked instance in its `concepts/` — are
+//! never evicted.
+
+use super::lockfile::Lockfile;
+use super::package_manager::{PackageInfo, PackageManager};
+use super::signature::signature_path;
+use crate::errors::{CkpError, Result};
+use crate::project::ProjectRegistry;
+use chrono::{DateTime, Utc};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Environment variable overriding the default cache size budget
+pub const CACHE_MAX_SIZE_ENV: &str = "CKP_CACHE_MAX_SIZE";
+
+/// Default cache size budget (1 GiB)
+pub const DEFAULT_CACHE_MAX_SIZE: u64 = 1024 * 1024 * 1024;
+
+/// Last-use times of cached packages, kept inside the cache directory
+const USAGE_INDEX: &str = ".usage.json";
+
+/// Garbage collection policy
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GcPolicy {
+    /// Cache size budget in bytes (tarballs and signatures)
+    pub max_size_bytes: u64,
+
+    /// Report what would be evicted without deleting anything
+    pub dry_run: bool,
+}
+
+impl Default for GcPolicy {
+    fn default() -> Self {
+        Self::new(DEFAULT_CACHE_MAX_SIZE)
+    }
+}
+
+impl GcPolicy {
+    pub fn new(max_size_bytes: u64) -> Self {
+        Self { max_size_bytes, dry_run: false }
+    }
+
+    /// Budget from `$CKP_CACHE_MAX_SIZE`, or the default
+    pub fn from_env() -> Result<Self> {
+        match std::env::var(CACHE_MAX_SIZE_ENV) {
+            Ok(size) => Ok(Self::new(parse_size(&size)?)),
+            Err(_) => Ok(Self::default()),
+        }
+    }
+
+    pub fn with_dry_run(mut self, dry_run: bool) -> Self {
+        self.dry_run = dry_run;
+        self
+    }
+}
+
+/// Outcome of a garbage collection run
+#[derive(Debug, Clone, Default)]
+pub struct GcReport {
+    /// Packages evicted (or that would be, on a dry run), oldest use first
+    pub evicted: Vec<PackageInfo>,
+
+    /// Packages kept because a registered project references them
+    pub protected: Vec<PackageInfo>,
+
+    /// Cache size before collection
+    pub size_before: u64,
+
+    /// Cache size after collection
+    pub size_after: u64,
+
+    pub dry_run: bool,
+}
+
+impl GcReport {
+    /// Bytes freed (or that would be freed)
+    pub fn freed_bytes(&self) -> u64 {
+        self.size_before - self.size_after
+    }
+
+    /// Whether the cache still exceeds the budget after collection
+    pub fn over_budget(&self, policy: &GcPolicy) -> bool {
+        self.size_after > policy.max_size_bytes
+    }
+}
+
+/// Parse a human-readable size: plain bytes or a `K`/`M`/`G`/`T` suffix (binary units)
+pub fn parse_size(size: &str) -> Result<u64> {
+    let trimmed = size.trim();
+    let upper = trimmed.to_ascii_uppercase();
+    let digits = upper.trim_end_matches("IB").trim_end_matches('B');
+
+    let (number, multiplier) = match digits.chars().last() {
+        Some('K') => (&digits[..digits.len() - 1], 1u64 << 10),
+        Some('M') => (&digits[..digits.len() - 1], 1u64 << 20),
+        Some('G') => (&digits[..digits.len() - 1], 1u64 << 30),
+        Some('T') => (&digits[..digits.len() - 1], 1u64 << 40),
+        _ => (digits, 1),
+    };
+
+    number.trim().parse::<u64>()
+        .ok()
+        .and_then(|n| n.checked_mul(multiplier))
+        .ok_or_else(|| CkpError::ValidationError(format!("Invalid size: {}", trimmed)))
+}
+
+/// Record that a cached package was just used
+pub(super) fn record_use(cache_dir: &Path, filename: &str) -> Result<()> {
+    let mut usage = load_usage(cache_dir);
+    usage.insert(filename.to_string(), Utc::now());
+    save_usage(cache_dir, &usage)
+}
+
+fn load_usage(cache_dir: &Path) -> BTreeMap<String, DateTime<Utc>> {
+    fs::read_to_string(cache_dir.join(USAGE_INDEX))
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+fn save_usage(cache_dir: &Path, usage: &BTreeMap<String, DateTime<Utc>>) -> Result<()> {
+    fs::write(cache_dir.join(USAGE_INDEX), serde_json::to_string_pretty(usage)?)
+        .map_err(|e| CkpError::IoError(format!("Failed to write cache usage index: {}", e)))
+}
+
+impl PackageManager {
+    /// Evict least-recently-used packages beyond the policy's size budget
+    ///
+    /// Projects are taken from the global `ProjectRegistry`.
+    pub fn gc(&self, policy: &GcPolicy) -> Result<GcReport> {
+        self.gc_with_registry(policy, &mut ProjectRegistry::new()?)
+    }
+
+    /// Evict least-recently-used packages, protecting those referenced by
+    /// the projects of a specific registry
+    pub fn gc_with_registry(&self, policy: &GcPolicy, registry: &mut ProjectRegistry) -> Result<GcReport> {
+        let roots: Vec<PathBuf> = registry.list()?
+            .into_iter()
+            .map(|project| PathBuf::from(project.path))
+            .collect();
+        let (locked, unlocked) = project_references(&roots)?;
+
+        let cache_dir = self.get_cache_dir();
+        let mut usage = load_usage(cache_dir);
+        let packages = self.list_cached()?;
+
+        let footprint = |package: &PackageInfo| {
+            let signature = fs::metadata(signature_path(&cache_dir.join(&package.filename)))
+                .map(|m| m.len())
+                .unwrap_or(0);
+            package.size_bytes + signature
+        };
+
+        let mut report = GcReport {
+            size_before: packages.iter().map(footprint).sum(),
+            dry_run: policy.dry_run,
+            ..Default::default()
+        };
+        report.size_after = report.size_before;
+
+        let (protected, mut candidates): (Vec<PackageInfo>, Vec<PackageInfo>) = packages
+            .into_iter()
+            .partition(|p| locked.contains(&p.filename) || unlocked.contains(&p.name));
+        report.protected = protected;
+
+        // Least recently used first; packages never recorded fall back to their file time
+        let last_used = |package: &PackageInfo| -> DateTime<Utc> {
+            usage.get(&package.filename).copied().unwrap_or_else(|| {
+                fs::metadata(cache_dir.join(&package.filename))
+                    .and_then(|m| m.modified())
+                    .map(DateTime::<Utc>::from)
+                    .unwrap_or_default()
+            })
+        };
+        candidates.sort_by_cached_key(|p| (last_used(p), p.filename.clone()));
+
+        for package in candidates {
+            if report.size_after <= policy.max_size_bytes {
+                break;
+            }
+
+            if !policy.dry_run {
+                let path = cache_dir.join(&package.filename);
+                fs::remove_file(&path).map_err(|e| {
+                    CkpError::IoError(format!("Failed to evict {}: {}", package.filename, e))
+                })?;
+                let _ = fs::remove_file(signature_path(&path));
+                usage.remove(&package.filename);
+            }
+
+            report.size_after -= footprint(&package);
+            report.evicted.push(package);
+        }
+
+        if !policy.dry_run && !report.evicted.is_empty() {
+            save_usage(cache_dir, &usage)?;
+        }
+
+        Ok(report)
+    }
+}
+
+/// Package filenames pinned by project lockfiles, and names of package
+/// instances installed without a lock entry (every version of those is kept)
+fn project_references(roots: &[PathBuf]) -> Result<(BTreeSet<String>, BTreeSet<String>)> {
+    let mut locked = BTreeSet::new();
+    let mut unlocked = BTreeSet::new();
+
+    for root in roots {
+        let lockfile = match Lockfile::load(root) {
+            Ok(lockfile) => lockfile.unwrap_or_default(),
+            Err(e) => {
+                tracing::warn!(project = %root.display(), error = %e, "skipping unreadable lockfile");
+                Lockfile::default()
+            }
+        };
+        locked.extend(lockfile.packages.iter().map(|p| p.filename.clone()));
+
+        let Ok(entries) = fs::read_dir(root.join("concepts")) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let instance = entry.file_name().to_string_lossy().to_string();
+            if lockfile.get(&instance).is_none() {
+                unlocked.insert(instance);
+            }
+        }
+    }
+
+    Ok((locked, unlocked))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::project::ProjectInfo;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_parse_size() {
+        assert_eq!(parse_size("512").unwrap(), 512);
+        assert_eq!(parse_size("4K").unwrap(), 4096);
+        assert_eq!(parse_size("1.5G").ok(), None);
+        assert_eq!(parse_size("2GiB").unwrap(), 2 << 30);
+        assert_eq!(parse_size("10mb").unwrap(), 10 << 20);
+        assert!(parse_size("lots").is_err());
+    }
+
+    #[test]
+    fn test_gc_evicts_lru_and_keeps_project_references() {
+        let temp_dir = TempDir::new().unwrap();
+        let source = temp_dir.path().join("source");
+        let pm = PackageManager::from_cache_dir(temp_dir.path().join("cache")).unwrap();
+
+        for name in ["Demo.Locked", "Demo.Old", "Demo.Recent"] {
+            fs::create_dir_all(source.join("concepts").join(name)).unwrap();
+            fs::write(source.join("concepts").join(name).join("conceptkernel.yaml"), "metadata:\n  type: python:hot\n").unwrap();
+            pm.export(name, "v0.1", &source).unwrap();
+        }
+        let cached = pm.list_cached().unwrap();
+        let find = |name: &str| cached.iter().find(|p| p.name == name).unwrap().clone();
+
+        let project = temp_dir.path().join("project");
+        pm.install_from_package(&find("Demo.Locked"), &project, None).unwrap();
+        record_use(pm.get_cache_dir(), &find("Demo.Old").filename).unwrap();
+        record_use(pm.get_cache_dir(), &find("Demo.Recent").filename).unwrap();
+
+        let mut registry = ProjectRegistry::from_dir(temp_dir.path().join("projects")).unwrap();
+        registry.register(ProjectInfo {
+            name: "demo".to_string(),
+            id: "demo".to_string(),
+            path: project.to_string_lossy().to_string(),
+            version: "v1.3.14".to_string(),
+            preferred_slot: None,
+        }).unwrap();
+
+        let budget = find("Demo.Locked").size_bytes + find("Demo.Recent").size_bytes;
+        let policy = GcPolicy::new(budget).with_dry_run(true);
+        let report = pm.gc_with_registry(&policy, &mut registry).unwrap();
+        let names = |packages: &[PackageInfo]| packages.iter().map(|p| p.name.clone()).collect::<Vec<_>>();
+        assert_eq!(names(&report.evicted), vec!["Demo.Old"]);
+        assert_eq!(names(&report.protected), vec!["Demo.Locked"]);
+        assert_eq!(pm.list_cached().unwrap().len(), 3);
+
+        let report = pm.gc_with_registry(&GcPolicy::new(0), &mut registry).unwrap();
+        assert_eq!(names(&report.evicted), vec!["Demo.Old", "Demo.Recent"]);
+        assert!(report.over_budget(&GcPolicy::new(0)));
+        assert_eq!(names(&pm.list_cached().unwrap()), vec!["Demo.Locked"]);
+    }
+}
@@ -0,0 +1,212 @@
+//! Package lockfile (`ckp.lock`)
+//!
+//! Records the exact package build behind every concept instance installed
+//! into a project — version, arch, runtime, tarball filename and SHA-256 —
+//! so `PackageManager::install_from_lock` can reproduce the environment
+//! byte-for-byte in CI and production.
+
+use crate::errors::{CkpError, Result};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Lockfile name at the project root
+pub const LOCKFILE_NAME: &str = "ckp.lock";
+
+/// Current lockfile format version
+pub const LOCKFILE_VERSION: u32 = 1;
+
+/// Exact package build installed as one concept instance
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LockedPackage {
+    /// Directory name under `concepts/`
+    pub instance: String,
+
+    pub name: String,
+    pub version: String,
+    pub arch: String,
+    pub runtime: String,
+    pub filename: String,
+
+    /// SHA-256 of the package tarball
+    pub sha256: String,
+
+    /// Registry the package was fetched from, if any
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub dependencies: BTreeMap<String, String>,
+}
+
+/// Contents of `ckp.lock`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Lockfile {
+    pub version: u32,
+
+    /// Locked instances, sorted by instance name
+    pub packages: Vec<LockedPackage>,
+}
+
+impl Default for Lockfile {
+    fn default() -> Self {
+        Self { version: LOCKFILE_VERSION, packages: Vec::new() }
+    }
+}
+
+impl Lockfile {
+    /// Path of the lockfile in a project
+    pub fn path(project_root: &Path) -> PathBuf {
+        project_root.join(LOCKFILE_NAME)
+    }
+
+    /// Load a project's lockfile
+    ///
+    /// # Returns
+    /// None if the project has no lockfile
+    pub fn load(project_root: &Path) -> Result<Option<Self>> {
+        let path = Self::path(project_root);
+        if !path.exists() {
+            return Ok(None);
+        }
+
+        let content = fs::read_to_string(&path)
+            .map_err(|e| CkpError::IoError(format!("Failed to read {}: {}", LOCKFILE_NAME, e)))?;
+        let lockfile: Lockfile = serde_yaml::from_str(&content)?;
+
+        if lockfile.version > LOCKFILE_VERSION {
+            return Err(CkpError::ValidationError(format!(
+                "{} version {} is newer than supported version {}",
+                LOCKFILE_NAME, lockfile.version, LOCKFILE_VERSION
+            )));
+        }
+
+        Ok(Some(lockfile))
+    }
+
+    /// Write the lockfile to a project
+    pub fn save(&self, project_root: &Path) -> Result<()> {
+        let content = format!(
+            "# This file is generated by ckp. Do not edit it by hand.\n{}",
+            serde_yaml::to_string(self)?
+        );
+
+        fs::write(Self::path(project_root), content)
+            .map_err(|e| CkpError::IoError(format!("Failed to write {}: {}", LOCKFILE_NAME, e)))
+    }
+
+    /// Locked entry for an instance
+    pub fn get(&self, instance: &str) -> Option<&LockedPackage> {
+        self.packages.iter().find(|p| p.instance == instance)
+    }
+
+    /// Add or replace the entry for an instance
+    pub fn upsert(&mut self, package: LockedPackage) {
+        self.packages.retain(|p| p.instance != package.instance);
+        self.packages.push(package);
+        self.packages.sort_by(|a, b| a.instance.cmp(&b.instance));
+    }
+
+    /// Remove the entry for an instance
+    ///
+    /// # Returns
+    /// Whether an entry was removed
+    pub fn remove(&mut self, instance: &str) -> bool {
+        let before = self.packages.len();
+        self.packages.retain(|p| p.instance != instance);
+        self.packages.len() < before
+    }
+
+    /// Drop an instance from a project's lockfile, if the project has one
+    pub fn unlock(project_root: &Path, instance: &str) -> Result<bool> {
+        let mut lockfile = match Self::load(project_root)? {
+            Some(lockfile) => lockfile,
+            None => return Ok(false),
+        };
+
+        let removed = lockfile.remove(instance);
+        if removed {
+            lockfile.save(project_root)?;
+        }
+        Ok(removed)
+    }
+
+    /// Entries ordered so dependencies are installed before dependents
+    pub fn install_order(&self) -> Vec<&LockedPackage> {
+        fn visit<'a>(
+            package: &'a LockedPackage,
+            lockfile: &'a Lockfile,
+            visited: &mut BTreeSet<&'a str>,
+            ordered: &mut Vec<&'a LockedPackage>,
+        ) {
+            if !visited.insert(&package.instance) {
+                return;
+            }
+
+            for dependency in package.dependencies.keys() {
+                for locked in lockfile.packages.iter().filter(|p| &p.name == dependency) {
+                    visit(locked, lockfile, visited, ordered);
+                }
+            }
+            ordered.push(package);
+        }
+
+        let mut visited = BTreeSet::new();
+        let mut ordered = Vec::with_capacity(self.packages.len());
+        for package in &self.packages {
+            visit(package, self, &mut visited, &mut ordered);
+        }
+
+        ordered
+    }
+}
+
+/// SHA-256 hex digest of a file
+pub fn file_sha256(path: &Path) -> Result<String> {
+    let bytes = fs::read(path)
+        .map_err(|e| CkpError::IoError(format!("Failed to read {}: {}", path.display(), e)))?;
+    Ok(hex::encode(Sha256::digest(bytes)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn locked(instance: &str, name: &str, dependencies: &[&str]) -> LockedPackage {
+        LockedPackage {
+            instance: instance.to_string(),
+            name: name.to_string(),
+            version: "v0.1".to_string(),
+            arch: "universal".to_string(),
+            runtime: "py".to_string(),
+            filename: format!("{}-v0.1.universal.py.tar.gz", name),
+            sha256: "00".repeat(32),
+            source: None,
+            dependencies: dependencies.iter().map(|d| (d.to_string(), "*".to_string())).collect(),
+        }
+    }
+
+    #[test]
+    fn test_lockfile_round_trip_and_order() {
+        let temp_dir = TempDir::new().unwrap();
+        assert!(Lockfile::load(temp_dir.path()).unwrap().is_none());
+
+        let mut lockfile = Lockfile::default();
+        lockfile.upsert(locked("App", "App", &["Core"]));
+        lockfile.upsert(locked("App.1", "App", &["Core"]));
+        lockfile.upsert(locked("Core", "Core", &[]));
+        lockfile.save(temp_dir.path()).unwrap();
+
+        let loaded = Lockfile::load(temp_dir.path()).unwrap().unwrap();
+        assert_eq!(loaded, lockfile);
+        let order: Vec<&str> = loaded.install_order().iter().map(|p| p.instance.as_str()).collect();
+        assert_eq!(order, vec!["Core", "App", "App.1"]);
+
+        assert!(Lockfile::unlock(temp_dir.path(), "App.1").unwrap());
+        assert!(!Lockfile::unlock(temp_dir.path(), "App.1").unwrap());
+        assert_eq!(Lockfile::load(temp_dir.path()).unwrap().unwrap().packages.len(), 2);
+    }
+}
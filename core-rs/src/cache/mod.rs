@@ -1,11 +1,29 @@
 //! Cache module for ConceptKernel packages
 //!
 //! Manages local package cache at ~/.config/conceptkernel/cache/
-//! Handles tar.gz packages for concepts
+//! Packs kernel directories into tar.gz packages, picks the build best
+//! suited to the host, fetches packages from remote registries and their
+//! mirrors (or only from vendored directories in offline mode), verifies
+//! detached signatures, resolves dependencies and records installs in a
+//! project lockfile. `PackageManager::gc` keeps the cache within a size budget.
 
+pub mod gc;
+pub mod lockfile;
+pub mod pack;
 pub mod package_manager;
+pub mod platform;
+pub mod registry;
+pub mod resolver;
+pub mod signature;
 
-pub use package_manager::{PackageManager, PackageInfo};
+pub use gc::{parse_size, GcPolicy, GcReport, CACHE_MAX_SIZE_ENV, DEFAULT_CACHE_MAX_SIZE};
+pub use lockfile::{LockedPackage, Lockfile, LOCKFILE_NAME};
+pub use pack::{ManifestFile, PackageManifest, PACKAGE_MANIFEST};
+pub use package_manager::{PackageManager, PackageInfo, OFFLINE_ENV, VENDOR_DIR_ENV};
+pub use platform::{host_arch, HostPlatform, PackageBuild, DEFAULT_RUNTIME_PREFERENCE, RUNTIME_PREFERENCE_ENV};
+pub use registry::{RegistryClient, RegistryPackage, REGISTRY_MIRRORS_ENV, REGISTRY_TOKEN_ENV, REGISTRY_URL_ENV};
+pub use resolver::{DependencyResolver, Requirement, Resolution, ResolutionConflict};
+pub use signature::{PackageSignature, PackageSigner, SignatureStatus, TrustPolicy, TrustStore, TrustedKey, TRUST_POLICY_ENV};
 
 #[cfg(test)]
 mod tests {
@@ -44,6 +62,7 @@ mod tests {
             filename: "Test.Kernel-v1.0.0-aarch64-darwin-rs.tar.gz".to_string(),
             size_bytes: 1024,
             created_at: "2025-11-29".to_string(),
+            dependencies: Default::default(),
         };
 
         accepts_package_info(info);
@@ -0,0 +1,391 @@
+//! Kernel packing
+//!
+//! Assembles a kernel directory into the canonical package tarball
+//! `<name>-<version>.<arch>.<runtime>.tar.gz` with a single `<name>/` root
+//! and a `ckp-package.json` manifest listing every file with its SHA-256.
+//! Runtime state (queues, storage and its index, transactions, logs) and build
+//! by-products are left out, and archives are reproducible: entries are
+//! sorted and timestamps zeroed.
+
+use super::package_manager::{parse_dependencies, PackageInfo, PackageManager};
+use crate::errors::{CkpError, Result};
+use crate::storage::{INDEX_FILE, INDEX_META_FILE};
+use crate::urn::{UrnResolver, UrnValidator};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::fs::{self, File};
+use std::path::{Component, Path, PathBuf};
+
+/// Manifest file name inside the package root
+pub const PACKAGE_MANIFEST: &str = "ckp-package.json";
+
+/// Top-level kernel directories holding runtime state
+pub(crate) const RUNTIME_STATE_DIRS: &[&str] = &["queue", "storage", "tx", "consensus", "logs", "archive"];
+
+/// Directories never packed at any depth
+pub(crate) const EXCLUDED_DIRS: &[&str] = &[".git", "node_modules", "__pycache__"];
+
+/// Package manifest (`ckp-package.json`)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PackageManifest {
+    pub name: String,
+    pub version: String,
+    pub arch: String,
+    pub runtime: String,
+
+    /// Kernel type from `metadata.type` (e.g., "rust:hot", "node:cold")
+    pub kernel_type: String,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub entrypoint: Option<String>,
+
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub dependencies: BTreeMap<String, String>,
+
+    /// Packed files, relative to the package root, sorted by path
+    pub files: Vec<ManifestFile>,
+}
+
+/// File entry in a package manifest
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ManifestFile {
+    pub path: String,
+    pub size: u64,
+    pub sha256: String,
+}
+
+impl PackageManager {
+    /// Pack a kernel directory into the cache
+    ///
+    /// Name and version come from `metadata.urn` (or the legacy
+    /// `metadata.name` / `metadata.version`). Every `ckp://` URN in
+    /// `conceptkernel.yaml` must be valid. Rust kernels must have their
+    /// entrypoint binary built; `target/` directories are otherwise left
+    /// out. Node and Python kernels must ship their entrypoint script.
+    ///
+    /// # Returns
+    /// PackageInfo of the cached package
+    ///
+    /// # Errors
+    /// `CkpError::ValidationError` if the ontology or a URN is invalid,
+    /// `CkpError::BuildError` if the entrypoint is missing
+    pub fn pack(&self, kernel_dir: &Path) -> Result<PackageInfo> {
+        let ontology_path = kernel_dir.join("conceptkernel.yaml");
+        let content = fs::read_to_string(&ontology_path).map_err(|e| {
+            CkpError::FileNotFound(format!("Failed to read {}: {}", ontology_path.display(), e))
+        })?;
+        let ontology: serde_yaml::Value = serde_yaml::from_str(&content)
+            .map_err(|e| CkpError::ParseError(format!("Failed to parse conceptkernel.yaml: {}", e)))?;
+
+        let metadata = ontology.get("metadata");
+        let field = |key: &str| metadata.and_then(|m| m.get(key)).and_then(|v| v.as_str());
+
+        validate_urns(&ontology)?;
+        let (name, version) = match field("urn") {
+            Some(urn) => {
+                let parsed = UrnResolver::parse(urn)?;
+                (parsed.kernel, parsed.version)
+            }
+            None => {
+                let name = field("name").ok_or_else(|| CkpError::ValidationError(
+                    "conceptkernel.yaml has neither metadata.urn nor metadata.name".to_string()
+                ))?;
+                let version = field("version").ok_or_else(|| CkpError::ValidationError(format!(
+                    "conceptkernel.yaml of {} has no metadata.version",
+                    name
+                )))?;
+                (name.to_string(), version.to_string())
+            }
+        };
+
+        if !UrnValidator::is_valid_kernel_name(&name) || !UrnValidator::is_valid_version(&version) {
+            return Err(CkpError::ValidationError(format!(
+                "Invalid package identity: {}:{}",
+                name, version
+            )));
+        }
+
+        let kernel_type = field("type").unwrap_or("unknown").to_string();
+        let entrypoint = field("entrypoint").map(str::to_string);
+        let (arch, runtime) = self.detect_runtime_and_arch(kernel_dir)?;
+        if runtime == "unknown" {
+            return Err(CkpError::ValidationError(format!(
+                "Unsupported kernel type for packing: {}",
+                kernel_type
+            )));
+        }
+
+        if let Some(entrypoint) = &entrypoint {
+            if !kernel_dir.join(entrypoint).is_file() {
+                let hint = if runtime == "rs" { " (run `ckp build` first)" } else { "" };
+                return Err(CkpError::BuildError(format!(
+                    "Entrypoint not found: {}{}",
+                    entrypoint, hint
+                )));
+            }
+        }
+
+        let files = collect_files(kernel_dir, runtime == "rs", entrypoint.as_deref())?;
+        let mut manifest = PackageManifest {
+            name: name.clone(),
+            version: version.clone(),
+            arch: arch.clone(),
+            runtime: runtime.clone(),
+            kernel_type,
+            entrypoint,
+            dependencies: ontology.get("spec")
+                .and_then(|s| s.get("dependencies"))
+                .map(parse_dependencies)
+                .unwrap_or_default(),
+            files: Vec::with_capacity(files.len()),
+        };
+        for relative in &files {
+            let bytes = fs::read(kernel_dir.join(relative))
+                .map_err(|e| CkpError::IoError(format!("Failed to read {}: {}", relative, e)))?;
+            manifest.files.push(ManifestFile {
+                path: relative.clone(),
+                size: bytes.len() as u64,
+                sha256: hex::encode(Sha256::digest(&bytes)),
+            });
+        }
+
+        let filename = format!("{}-{}.{}.{}.tar.gz", name, version, arch, runtime);
+        let package_path = self.get_cache_dir().join(&filename);
+        write_tarball(kernel_dir, &package_path, &manifest)?;
+
+        let size_bytes = fs::metadata(&package_path)
+            .map_err(|e| CkpError::IoError(format!("Failed to get file metadata: {}", e)))?
+            .len();
+
+        Ok(PackageInfo {
+            name,
+            version,
+            arch,
+            runtime,
+            filename,
+            size_bytes,
+            created_at: chrono::Local::now().format("%Y-%m-%d").to_string(),
+            dependencies: manifest.dependencies,
+        })
+    }
+}
+
+/// Validate every `ckp://` URN in the ontology
+///
+/// Dependency references carry semver ranges rather than versions and are
+/// checked by the resolver instead.
+fn validate_urns(ontology: &serde_yaml::Value) -> Result<()> {
+    fn walk(value: &serde_yaml::Value, path: &str, errors: &mut Vec<String>) {
+        match value {
+            serde_yaml::Value::String(s) if s.starts_with("ckp://") && !s.contains('?') => {
+                let result = UrnValidator::validate(s);
+                if !result.valid {
+                    errors.push(format!("{}: {}", path, result.errors.join("; ")));
+                }
+            }
+            serde_yaml::Value::Sequence(items) => {
+                for (i, item) in items.iter().enumerate() {
+                    walk(item, &format!("{}[{}]", path, i), errors);
+                }
+            }
+            serde_yaml::Value::Mapping(map) => {
+                for (key, item) in map {
+                    let key = key.as_str().unwrap_or_default();
+                    let child = if path.is_empty() { key.to_string() } else { format!("{}.{}", path, key) };
+                    if child != "spec.dependencies" {
+                        walk(item, &child, errors);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let mut errors = Vec::new();
+    walk(ontology, "", &mut errors);
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(CkpError::ValidationError(format!(
+            "Invalid URNs in conceptkernel.yaml: {}",
+            errors.join(", ")
+        )))
+    }
+}
+
+/// Files to pack, relative to the kernel directory with `/` separators, sorted
+fn collect_files(kernel_dir: &Path, rust: bool, entrypoint: Option<&str>) -> Result<Vec<String>> {
+    let entrypoint = entrypoint.map(|e| e.trim_start_matches("./").to_string());
+    let mut files = Vec::new();
+
+    let walker = walkdir::WalkDir::new(kernel_dir)
+        .follow_links(false)
+        .sort_by_file_name()
+        .into_iter()
+        .filter_entry(|entry| {
+            let Ok(relative) = entry.path().strip_prefix(kernel_dir) else {
+                return true;
+            };
+            let name = entry.file_name().to_string_lossy();
+            let top_level = relative.components().count() == 1;
+
+            if !entry.file_type().is_dir() {
+                let index = top_level && [INDEX_FILE, INDEX_META_FILE].contains(&name.as_ref());
+                return !index && !name.ends_with(".pid");
+            }
+            if top_level && RUNTIME_STATE_DIRS.contains(&name.as_ref()) {
+                return false;
+            }
+            if EXCLUDED_DIRS.contains(&name.as_ref()) {
+                return false;
+            }
+
+            // Rust build output is kept only on the way to the entrypoint binary
+            if rust && name == "target" {
+                return entrypoint.as_deref()
+                    .is_some_and(|e| Path::new(e).starts_with(relative));
+            }
+            true
+        });
+
+    for entry in walker {
+        let entry = entry.map_err(|e| CkpError::IoError(format!("Failed to walk kernel directory: {}", e)))?;
+        if entry.file_type().is_dir() {
+            continue;
+        }
+
+        let relative = entry.path().strip_prefix(kernel_dir).unwrap_or(entry.path());
+        if rust && relative.components().any(|c| c == Component::Normal("target".as_ref()))
+            && entrypoint.as_deref() != Some(relative.to_string_lossy().as_ref())
+        {
+            continue;
+        }
+
+        if entry.file_type().is_file() {
+            files.push(relative.components()
+                .map(|c| c.as_os_str().to_string_lossy())
+                .collect::<Vec<_>>()
+                .join("/"));
+        }
+    }
+
+    if !files.iter().any(|f| f == "conceptkernel.yaml") {
+        return Err(CkpError::ValidationError("conceptkernel.yaml is required".to_string()));
+    }
+
+    Ok(files)
+}
+
+/// Write a reproducible tar.gz with `<name>/` as root
+fn write_tarball(kernel_dir: &Path, package_path: &Path, manifest: &PackageManifest) -> Result<()> {
+    use flate2::write::GzEncoder;
+    use flate2::Compression;
+    use tar::{Builder, Header};
+
+    let partial = PathBuf::from(format!("{}.part", package_path.display()));
+    let file = File::create(&partial)
+        .map_err(|e| CkpError::IoError(format!("Failed to create tarball: {}", e)))?;
+    let mut tar = Builder::new(GzEncoder::new(file, Compression::default()));
+
+    let mut append = |path: String, mode: u32, bytes: &[u8]| -> Result<()> {
+        let mut header = Header::new_gnu();
+        header.set_size(bytes.len() as u64);
+        header.set_mode(mode);
+        header.set_mtime(0);
+        header.set_cksum();
+        tar.append_data(&mut header, path, bytes)
+            .map_err(|e| CkpError::IoError(format!("Failed to add file to tarball: {}", e)))
+    };
+
+    let manifest_json = serde_json::to_vec_pretty(manifest)?;
+    append(format!("{}/{}", manifest.name, PACKAGE_MANIFEST), 0o644, &manifest_json)?;
+
+    for entry in &manifest.files {
+        let source = kernel_dir.join(&entry.path);
+        let bytes = fs::read(&source)
+            .map_err(|e| CkpError::IoError(format!("Failed to read {}: {}", entry.path, e)))?;
+        append(format!("{}/{}", manifest.name, entry.path), file_mode(&source), &bytes)?;
+    }
+
+    tar.into_inner()
+        .and_then(|encoder| encoder.finish())
+        .map_err(|e| CkpError::IoError(format!("Failed to finish tarball: {}", e)))?;
+
+    fs::rename(&partial, package_path)
+        .map_err(|e| CkpError::IoError(format!("Failed to finalize tarball: {}", e)))
+}
+
+#[cfg(unix)]
+fn file_mode(path: &Path) -> u32 {
+    use std::os::unix::fs::PermissionsExt;
+    let executable = fs::metadata(path).map(|m| m.permissions().mode() & 0o111 != 0).unwrap_or(false);
+    if executable { 0o755 } else { 0o644 }
+}
+
+#[cfg(not(unix))]
+fn file_mode(_path: &Path) -> u32 {
+    0o644
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn write(path: &Path, content: &str) {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, content).unwrap();
+    }
+
+    #[test]
+    fn test_pack_node_kernel_excludes_state_and_installs() {
+        let temp_dir = TempDir::new().unwrap();
+        let kernel = temp_dir.path().join("src/Demo.Greeter");
+        write(&kernel.join("conceptkernel.yaml"), "metadata:\n  urn: ckp://Demo.Greeter:v0.2\n  type: node:cold\n  entrypoint: tool/tool.js\nspec:\n  dependencies:\n    - ckp://Demo.Core:^0.1\n");
+        write(&kernel.join("ontology.ttl"), "@prefix ckp: <https://conceptkernel.org/ontology#> .\n");
+        write(&kernel.join("tool/tool.js"), "console.log('hi');\n");
+        write(&kernel.join("tool/node_modules/dep/index.js"), "");
+        write(&kernel.join("queue/inbox/job.job"), "{}");
+        write(&kernel.join("storage/tx-1.inst/receipt.bin"), "{}");
+        write(&kernel.join(INDEX_META_FILE), "{}");
+
+        let pm = PackageManager::from_cache_dir(temp_dir.path().join("cache")).unwrap();
+        let package = pm.pack(&kernel).unwrap();
+        assert_eq!(package.filename, "Demo.Greeter-v0.2.universal.js.tar.gz");
+        assert_eq!(package.dependencies.get("Demo.Core").map(String::as_str), Some("^0.1"));
+
+        let first = fs::read(pm.get_cache_dir().join(&package.filename)).unwrap();
+        pm.pack(&kernel).unwrap();
+        assert_eq!(first, fs::read(pm.get_cache_dir().join(&package.filename)).unwrap());
+
+        let project = temp_dir.path().join("project");
+        let installed = pm.install_from_package(&package, &project, None).unwrap();
+        let manifest: PackageManifest = serde_json::from_slice(&fs::read(installed.join(PACKAGE_MANIFEST)).unwrap()).unwrap();
+        let paths: Vec<&str> = manifest.files.iter().map(|f| f.path.as_str()).collect();
+        assert_eq!(paths, vec!["conceptkernel.yaml", "ontology.ttl", "tool/tool.js"]);
+        assert!(!installed.join("queue").exists());
+    }
+
+    #[test]
+    fn test_pack_rust_kernel_keeps_only_entrypoint_binary() {
+        let temp_dir = TempDir::new().unwrap();
+        let kernel = temp_dir.path().join("Demo.Worker");
+        write(&kernel.join("conceptkernel.yaml"), "metadata:\n  name: Demo.Worker\n  version: v1.0\n  type: rust:hot\n  entrypoint: tool/rs/target/release/worker\n");
+        write(&kernel.join("tool/rs/Cargo.toml"), "[package]\nname = \"worker\"\n");
+
+        let pm = PackageManager::from_cache_dir(temp_dir.path().join("cache")).unwrap();
+        assert!(matches!(pm.pack(&kernel), Err(CkpError::BuildError(_))));
+
+        write(&kernel.join("tool/rs/target/release/worker"), "binary");
+        write(&kernel.join("tool/rs/target/release/deps/worker.d"), "");
+        let files = collect_files(&kernel, true, Some("tool/rs/target/release/worker")).unwrap();
+        assert_eq!(files, vec!["conceptkernel.yaml", "tool/rs/Cargo.toml", "tool/rs/target/release/worker"]);
+        assert!(pm.pack(&kernel).unwrap().filename.starts_with("Demo.Worker-v1.0."));
+
+        write(&kernel.join("conceptkernel.yaml"), "metadata:\n  urn: ckp://Demo.Worker:latest\n  type: rust:hot\n");
+        assert!(matches!(pm.pack(&kernel), Err(CkpError::ValidationError(_))));
+    }
+}
@@ -2,12 +2,29 @@
 //!
 //! Manages packages in ~/.config/conceptkernel/cache/
 //! Packages are tar.gz files named: <concept>@<version>.tar.gz
-
+//! Missing packages can be fetched from a remote registry (see `RegistryClient`).
+//! Detached `.minisig` signatures are checked against a trust store before extraction.
+//! Installs are recorded in the project's `ckp.lock` (see `Lockfile`).
+//! Vendored directories of tarballs back the cache, and offline mode keeps
+//! every operation off the network.
+
+use super::platform::{host_arch, HostPlatform};
+use super::lockfile::{file_sha256, LockedPackage, Lockfile, LOCKFILE_NAME};
+use super::registry::{validate_filename, RegistryClient, RegistryPackage};
+use super::resolver::{DependencyResolver, Resolution};
+use super::signature::{signature_path, PackageSigner, SignatureStatus, TrustPolicy, TrustStore};
 use crate::errors::{CkpError, Result};
 use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
 use std::env;
 use std::fs::{self, File};
-use std::path::{Path, PathBuf};
+use std::path::{Component, Path, PathBuf};
+
+/// Environment variable enabling offline mode (`1`, `true` or `yes`)
+pub const OFFLINE_ENV: &str = "CKP_OFFLINE";
+
+/// Environment variable holding vendored package directories (path-separated)
+pub const VENDOR_DIR_ENV: &str = "CKP_VENDOR_DIR";
 
 /// Package information
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -19,11 +36,21 @@ pub struct PackageInfo {
     pub filename: String,
     pub size_bytes: u64,
     pub created_at: String, // YYYY-MM-DD format
+
+    /// Required packages: name → semver range (from `spec.dependencies`)
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub dependencies: BTreeMap<String, String>,
 }
 
 /// Package Manager - manages local cache of concept packages
 pub struct PackageManager {
     cache_dir: PathBuf,
+    registry: Option<RegistryClient>,
+    trust_store: Option<TrustStore>,
+    trust_policy: TrustPolicy,
+    platform: HostPlatform,
+    vendor_dirs: Vec<PathBuf>,
+    offline: bool,
 }
 
 impl PackageManager {
@@ -40,6 +67,11 @@ impl PackageManager {
             .join("conceptkernel")
             .join("cache");
 
+        Self::from_cache_dir(cache_dir)
+    }
+
+    /// Create a PackageManager over a specific cache directory
+    pub fn from_cache_dir(cache_dir: PathBuf) -> Result<Self> {
         // Create cache directory if not exists
         if !cache_dir.exists() {
             fs::create_dir_all(&cache_dir).map_err(|e| {
@@ -47,7 +79,107 @@ impl PackageManager {
             })?;
         }
 
-        Ok(PackageManager { cache_dir })
+        Ok(PackageManager {
+            cache_dir,
+            registry: None,
+            trust_store: None,
+            trust_policy: TrustPolicy::default(),
+            platform: HostPlatform::detect(),
+            vendor_dirs: Vec::new(),
+            offline: false,
+        })
+    }
+
+    /// Fetch missing packages from a remote registry
+    pub fn with_registry(mut self, registry: RegistryClient) -> Self {
+        self.registry = Some(registry);
+        self
+    }
+
+    /// Configured remote registry
+    pub fn registry(&self) -> Option<&RegistryClient> {
+        self.registry.as_ref()
+    }
+
+    /// Verify package signatures against a trust store before extraction
+    pub fn with_trust_store(mut self, store: TrustStore) -> Self {
+        self.trust_store = Some(store);
+        self
+    }
+
+    /// Set how unsigned packages and unknown signers are treated
+    pub fn with_trust_policy(mut self, policy: TrustPolicy) -> Self {
+        self.trust_policy = policy;
+        self
+    }
+
+    /// Select builds for a platform other than the detected host
+    pub fn with_platform(mut self, platform: HostPlatform) -> Self {
+        self.platform = platform;
+        self
+    }
+
+    /// Platform used to pick between builds of a package
+    pub fn platform(&self) -> &HostPlatform {
+        &self.platform
+    }
+
+    /// Take packages missing from the cache from a vendored directory of tarballs
+    pub fn with_vendor_dir(mut self, dir: PathBuf) -> Self {
+        self.vendor_dirs.push(dir);
+        self
+    }
+
+    /// Resolve exclusively from the cache and vendored directories
+    pub fn with_offline(mut self, offline: bool) -> Self {
+        self.offline = offline;
+        self
+    }
+
+    /// Apply `CKP_OFFLINE` and `CKP_VENDOR_DIR` from the environment
+    pub fn with_env_options(mut self) -> Self {
+        if let Ok(value) = env::var(OFFLINE_ENV) {
+            self.offline = matches!(value.to_ascii_lowercase().as_str(), "1" | "true" | "yes");
+        }
+        if let Some(dirs) = env::var_os(VENDOR_DIR_ENV) {
+            self.vendor_dirs.extend(env::split_paths(&dirs).filter(|d| !d.as_os_str().is_empty()));
+        }
+        self
+    }
+
+    pub fn is_offline(&self) -> bool {
+        self.offline
+    }
+
+    /// Check a package's signature under the configured trust policy
+    ///
+    /// Without a trust store, permissive mode skips verification entirely
+    /// and strict mode rejects every package.
+    ///
+    /// # Errors
+    /// `CkpError::AuthenticationFailed` if the policy rejects the package,
+    /// `CkpError::ValidationError` if its signature is invalid
+    pub fn verify_package(&self, package_path: &Path) -> Result<SignatureStatus> {
+        match &self.trust_store {
+            Some(store) => store.enforce(package_path, self.trust_policy),
+            None if self.trust_policy == TrustPolicy::Strict => {
+                TrustStore::default().enforce(package_path, self.trust_policy)
+            }
+            None => Ok(SignatureStatus::Unsigned),
+        }
+    }
+
+    /// Sign a cached package, writing `{filename}.minisig` next to it
+    pub fn sign(&self, package: &PackageInfo, signer: &PackageSigner) -> Result<PathBuf> {
+        let package_path = self.cache_dir.join(&package.filename);
+        if !package_path.exists() {
+            return Err(CkpError::FileNotFound(format!(
+                "Package not found in cache: {}",
+                package.filename
+            )));
+        }
+
+        signer.sign_file(&package_path)
     }
 
     /// List all cached packages
@@ -55,14 +187,32 @@ impl PackageManager {
     /// # Returns
     /// Vector of PackageInfo for all .tar.gz files in cache
     pub fn list_cached(&self) -> Result<Vec<PackageInfo>> {
+        self.list_dir(&self.cache_dir)
+    }
+
+    /// List packages in the vendored directories that are not cached yet
+    pub fn list_vendored(&self) -> Result<Vec<PackageInfo>> {
         let mut packages = Vec::new();
+        for dir in &self.vendor_dirs {
+            for package in self.list_dir(dir)? {
+                let known = packages.iter().any(|p: &PackageInfo| p.filename == package.filename);
+                if !known && !self.cache_dir.join(&package.filename).exists() {
+                    packages.push(package);
+                }
+            }
+        }
+        Ok(packages)
+    }
 
-        if !self.cache_dir.exists() {
+    fn list_dir(&self, dir: &Path) -> Result<Vec<PackageInfo>> {
+        let mut packages = Vec::new();
+
+        if !dir.exists() {
             return Ok(packages);
         }
 
-        let entries = fs::read_dir(&self.cache_dir).map_err(|e| {
-            CkpError::IoError(format!("Failed to read cache directory: {}", e))
+        let entries = fs::read_dir(dir).map_err(|e| {
+            CkpError::IoError(format!("Failed to read package directory {}: {}", dir.display(), e))
         })?;
 
         for entry in entries {
@@ -95,6 +245,7 @@ impl PackageManager {
                         filename,
                         size_bytes: metadata.len(),
                         created_at,
+                        dependencies: self.read_dependencies(&path),
                     });
                 }
             }
@@ -207,16 +358,9 @@ impl PackageManager {
             return self.install_from_path(concept_name, &old_path, target_dir, instance_name);
         }
 
-        // Try to find any package with matching name and version
-        let packages = self.list_cached()?;
-        let matching = packages.iter()
-            .find(|p| p.name == concept_name && p.version == version)
-            .ok_or_else(|| CkpError::FileNotFound(format!(
-                "Package not found in cache: {}@{}",
-                concept_name, version
-            )))?;
-
-        self.install_from_package(matching, target_dir, instance_name)
+        // Pick the best cached build of the version for this host
+        let package = self.select_cached(concept_name, Some(version))?;
+        self.install_from_package(&package, target_dir, instance_name)
     }
 
     /// Internal method to install from a specific package path
@@ -243,6 +387,9 @@ impl PackageManager {
             })?;
         }
 
+        // Refuse untrusted packages before anything is extracted
+        self.verify_package(package_path)?;
+
         // Extract to a temporary directory first to avoid overwriting existing instances
         use std::env;
         let temp_extract_dir = env::temp_dir().join(format!("ckp-extract-{}", uuid::Uuid::new_v4()));
@@ -278,6 +425,11 @@ impl PackageManager {
         // Clean up temp directory
         let _ = fs::remove_dir_all(&temp_extract_dir);
 
+        self.lock_install(concept_name, package_path, target_dir, final_name)?;
+        if let Some(filename) = package_path.file_name() {
+            super::gc::record_use(&self.cache_dir, &filename.to_string_lossy())?;
+        }
+
         Ok(concept_dir)
     }
 
@@ -342,17 +494,7 @@ impl PackageManager {
         tag: Option<&str>,
     ) -> Result<PathBuf> {
         // 1. Find latest version of source package in cache
-        let packages = self.list_cached()?;
-        let source_pkg = packages
-            .iter()
-            .filter(|p| p.name == source_name)
-            .max_by_key(|p| &p.version)
-            .ok_or_else(|| {
-                CkpError::FileNotFound(format!(
-                    "No cached package found for '{}'",
-                    source_name
-                ))
-            })?;
+        let source_pkg = self.select_cached(source_name, None)?;
 
         // 2. Extract to concepts/<new_name>
         let concepts_dir = target_dir.join("concepts");
@@ -366,7 +508,10 @@ impl PackageManager {
         }
 
         // Use install_from_package to extract
-        let extracted_dir = self.install_from_package(source_pkg, target_dir, Some(new_name))?;
+        let extracted_dir = self.install_from_package(&source_pkg, target_dir, Some(new_name))?;
+
+        // A fork diverges from its source package, so it cannot be locked to it
+        Lockfile::unlock(target_dir, new_name)?;
 
         // 3. Update conceptkernel.yaml name field
         let yaml_path = extracted_dir.join("conceptkernel.yaml");
@@ -448,8 +593,11 @@ impl PackageManager {
                 // Ontology generated successfully
             }
             Err(e) => {
-                eprintln!("Warning: Failed to generate ontology.ttl: {}", e);
-                eprintln!("  Kernel may not load until ontology.ttl is created manually");
+                tracing::warn!(
+                    kernel = new_name,
+                    error = %e,
+                    "failed to generate ontology.ttl, kernel may not load until it is created manually"
+                );
             }
         }
 
@@ -498,6 +646,14 @@ impl PackageManager {
             CkpError::IoError(format!("Failed to copy package to cache: {}", e))
         })?;
 
+        // Keep a detached signature with the package
+        let source_signature = signature_path(tarball_path);
+        if source_signature.exists() {
+            fs::copy(&source_signature, signature_path(&dest_path)).map_err(|e| {
+                CkpError::IoError(format!("Failed to copy package signature to cache: {}", e))
+            })?;
+        }
+
         let metadata = fs::metadata(&dest_path).map_err(|e| {
             CkpError::IoError(format!("Failed to get file metadata: {}", e))
         })?;
@@ -519,6 +675,7 @@ impl PackageManager {
             filename,
             size_bytes: metadata.len(),
             created_at,
+            dependencies: self.read_dependencies(&dest_path),
         })
     }
 
@@ -542,6 +699,303 @@ impl PackageManager {
         Ok(true)
     }
 
+    /// Search the remote registry
+    pub fn search_remote(&self, query: &str) -> Result<Vec<RegistryPackage>> {
+        self.require_registry()?.search(query)
+    }
+
+    /// Best cached build of a package for this host (see `HostPlatform`)
+    ///
+    /// # Arguments
+    /// * `concept_name` - Name of concept
+    /// * `version` - Exact version (highest usable if None)
+    ///
+    /// # Errors
+    /// `CkpError::NoCompatiblePackage` explaining which builds exist instead
+    pub fn select_cached(&self, concept_name: &str, version: Option<&str>) -> Result<PackageInfo> {
+        let cached = self.list_cached()?;
+        self.platform.select(concept_name, version, &cached).cloned()
+    }
+
+    /// Best build from the cache or a vendored directory, copied into the cache
+    ///
+    /// # Errors
+    /// `CkpError::MissingPackages` if no local build of the package exists,
+    /// `CkpError::NoCompatiblePackage` if none suits this host
+    pub fn select_local(&self, concept_name: &str, version: Option<&str>) -> Result<PackageInfo> {
+        let mut local = self.list_cached()?;
+        local.extend(self.list_vendored()?);
+
+        if !local.iter().any(|p| p.name == concept_name && version.is_none_or(|v| p.version == v)) {
+            let wanted = match version {
+                Some(version) => format!("{}@{}", concept_name, version),
+                None => concept_name.to_string(),
+            };
+            return Err(self.missing_packages(&[wanted]));
+        }
+
+        let selected = self.platform.select(concept_name, version, &local)?;
+        self.localize(selected)
+    }
+
+    /// Ensure a package is in the cache, copying it from a vendored directory
+    /// or downloading it from the registry if needed
+    ///
+    /// Without a version the highest published version with a build usable on
+    /// this host is fetched; the best build is chosen by `HostPlatform`.
+    /// Offline, only the cache and vendored directories are consulted.
+    ///
+    /// # Arguments
+    /// * `concept_name` - Name of concept
+    /// * `version` - Version to fetch (latest if None)
+    ///
+    /// # Returns
+    /// PackageInfo of the cached package
+    pub fn fetch(&self, concept_name: &str, version: Option<&str>) -> Result<PackageInfo> {
+        if self.offline {
+            return self.select_local(concept_name, version);
+        }
+        if version.is_some() {
+            if let Ok(local) = self.select_local(concept_name, version) {
+                return Ok(local);
+            }
+        }
+
+        let registry = self.require_registry()?;
+        let builds: Vec<RegistryPackage> = match version {
+            Some(version) => registry.fetch(concept_name, version)?,
+            None => registry.versions(concept_name)?,
+        };
+        let selected = self.platform.select(concept_name, version, &builds)?;
+
+        if let Some(cached) = self.list_cached()?.into_iter().find(|p| p.filename == selected.filename) {
+            return Ok(cached);
+        }
+
+        let path = registry.download(selected, &self.cache_dir)?;
+        registry.download_signature(selected, &self.cache_dir)?;
+        let metadata = fs::metadata(&path)
+            .map_err(|e| CkpError::IoError(format!("Failed to get file metadata: {}", e)))?;
+
+        Ok(PackageInfo {
+            name: selected.name.clone(),
+            version: selected.version.clone(),
+            arch: selected.arch.clone(),
+            runtime: selected.runtime.clone(),
+            filename: selected.filename.clone(),
+            size_bytes: metadata.len(),
+            created_at: chrono::Local::now().format("%Y-%m-%d").to_string(),
+            dependencies: self.read_dependencies(&path),
+        })
+    }
+
+    /// Resolve requested packages (name → semver range) and their dependencies
+    ///
+    /// Candidates are every cached package plus, with a registry configured,
+    /// every published build of each package reachable from the requests.
+    /// Builds unusable on this host are left out; of the rest, the best
+    /// ranked by `HostPlatform` represents each version.
+    ///
+    /// # Errors
+    /// `CkpError::DependencyConflict` if no compatible set exists
+    pub fn resolve(&self, requests: &BTreeMap<String, String>) -> Result<Resolution> {
+        let usable = |p: &PackageInfo| self.platform.is_compatible(&p.arch, &p.runtime);
+
+        let mut candidates: Vec<PackageInfo> = self.list_cached()?
+            .into_iter()
+            .chain(self.list_vendored()?)
+            .filter(|p| usable(p))
+            .collect();
+
+        if let Some(registry) = self.registry.as_ref().filter(|_| !self.offline) {
+            let mut pending: Vec<String> = requests.keys().cloned().collect();
+            let mut seen = BTreeSet::new();
+
+            while let Some(name) = pending.pop() {
+                if !seen.insert(name.clone()) {
+                    continue;
+                }
+
+                let published = match registry.versions(&name) {
+                    Ok(published) => published,
+                    Err(CkpError::FileNotFound(_)) => Vec::new(),
+                    Err(e) => return Err(e),
+                };
+                candidates.extend(published.into_iter()
+                    .map(|p| p.to_package_info())
+                    .filter(|p| p.name == name && usable(p)));
+
+                for package in candidates.iter().filter(|p| p.name == name) {
+                    pending.extend(package.dependencies.keys().cloned());
+                }
+            }
+        }
+
+        // Report every package with no build at all before resolving versions
+        let missing = unavailable(requests, &candidates);
+        if !missing.is_empty() {
+            return Err(self.missing_packages(&missing));
+        }
+
+        // First build of a version wins: best ranked, then cached before remote
+        candidates.sort_by_key(|p| self.platform.rank(&p.arch, &p.runtime));
+        DependencyResolver::new(candidates).resolve(requests)
+    }
+
+    /// Resolve requests and install the result in dependency order
+    ///
+    /// Packages whose concept directory already exists in the project are
+    /// left as they are.
+    ///
+    /// # Returns
+    /// Paths of newly installed concept directories
+    pub fn install_resolved(&self, requests: &BTreeMap<String, String>, target_dir: &Path) -> Result<Vec<PathBuf>> {
+        let resolution = self.resolve(requests)?;
+        let mut installed = Vec::new();
+
+        for package in &resolution.packages {
+            if target_dir.join("concepts").join(&package.name).exists() {
+                continue;
+            }
+
+            let cached = self.fetch(&package.name, Some(&package.version))?;
+            installed.push(self.install_from_package(&cached, target_dir, None)?);
+        }
+
+        Ok(installed)
+    }
+
+    /// Fetch a package (from cache or registry) and install it into a project
+    ///
+    /// # Returns
+    /// Path to extracted concept directory
+    pub fn install_remote(
+        &self,
+        concept_name: &str,
+        version: Option<&str>,
+        target_dir: &Path,
+        instance_name: Option<&str>,
+    ) -> Result<PathBuf> {
+        let package = self.fetch(concept_name, version)?;
+        self.install_from_package(&package, target_dir, instance_name)
+    }
+
+    /// Reinstall exactly the packages recorded in a project's `ckp.lock`
+    ///
+    /// Each locked tarball is taken from the cache, or downloaded from the
+    /// registry by its exact filename, and must match the locked SHA-256.
+    /// Instances whose concept directory already exists are left as they are.
+    ///
+    /// # Returns
+    /// Paths of newly installed concept directories
+    ///
+    /// # Errors
+    /// `CkpError::FileNotFound` if the project has no lockfile,
+    /// `CkpError::ValidationError` if a lock entry names an instance or
+    /// package that is not a plain kernel name, or a tarball does not match
+    /// its lock entry
+    pub fn install_from_lock(&self, target_dir: &Path) -> Result<Vec<PathBuf>> {
+        let lockfile = Lockfile::load(target_dir)?.ok_or_else(|| CkpError::FileNotFound(format!(
+            "No {} in {}",
+            LOCKFILE_NAME,
+            target_dir.display()
+        )))?;
+        for locked in &lockfile.packages {
+            validate_kernel_name(&locked.instance)?;
+            validate_kernel_name(&locked.name)?;
+        }
+        let pending: Vec<&LockedPackage> = lockfile.install_order()
+            .into_iter()
+            .filter(|locked| !target_dir.join("concepts").join(&locked.instance).exists())
+            .collect();
+        for locked in &pending {
+            validate_filename(&locked.filename)?;
+        }
+
+        // Without a registry, everything must already be at hand
+        if self.offline || self.registry.is_none() {
+            let missing: Vec<String> = pending.iter()
+                .filter(|locked| self.local_path(&locked.filename).is_none())
+                .map(|locked| format!("{}@{} ({})", locked.name, locked.version, locked.filename))
+                .collect();
+            if !missing.is_empty() {
+                return Err(self.missing_packages(&missing));
+            }
+        }
+
+        let mut installed = Vec::new();
+        for locked in pending {
+            let mut package_path = match self.local_path(&locked.filename) {
+                Some(path) if !path.starts_with(&self.cache_dir) => self.cache_dir.join(self.import(&path)?.filename),
+                Some(path) => path,
+                None => self.cache_dir.join(&locked.filename),
+            };
+            if !package_path.exists() {
+                let registry = self.require_registry()?;
+                let package = RegistryPackage {
+                    name: locked.name.clone(),
+                    version: locked.version.clone(),
+                    arch: locked.arch.clone(),
+                    runtime: locked.runtime.clone(),
+                    filename: locked.filename.clone(),
+                    size_bytes: 0,
+                    sha256: Some(locked.sha256.clone()),
+                    published_at: None,
+                    dependencies: locked.dependencies.clone(),
+                };
+                package_path = registry.download(&package, &self.cache_dir)?;
+                registry.download_signature(&package, &self.cache_dir)?;
+            }
+
+            let actual = file_sha256(&package_path)?;
+            if !actual.eq_ignore_ascii_case(&locked.sha256) {
+                return Err(CkpError::ValidationError(format!(
+                    "Cached {} does not match {}: expected {}, got {}",
+                    locked.filename, LOCKFILE_NAME, locked.sha256, actual
+                )));
+            }
+
+            installed.push(self.install_from_path(&locked.name, &package_path, target_dir, Some(&locked.instance))?);
+        }
+
+        Ok(installed)
+    }
+
+    /// Publish a cached package to the registry
+    ///
+    /// Every cached build (arch/runtime) of the version is uploaded.
+    pub fn publish(&self, concept_name: &str, version: &str) -> Result<Vec<RegistryPackage>> {
+        let registry = self.require_registry()?;
+        let builds: Vec<PackageInfo> = self.list_cached()?
+            .into_iter()
+            .filter(|p| p.name == concept_name && p.version == version)
+            .collect();
+
+        if builds.is_empty() {
+            return Err(CkpError::FileNotFound(format!(
+                "Package not found in cache: {}@{}",
+                concept_name, version
+            )));
+        }
+
+        builds.iter()
+            .map(|p| {
+                let package = RegistryPackage {
+                    name: p.name.clone(),
+                    version: p.version.clone(),
+                    arch: p.arch.clone(),
+                    runtime: p.runtime.clone(),
+                    filename: p.filename.clone(),
+                    size_bytes: p.size_bytes,
+                    sha256: None,
+                    published_at: None,
+                    dependencies: p.dependencies.clone(),
+                };
+                registry.publish(&package, &self.cache_dir.join(&p.filename))
+            })
+            .collect()
+    }
+
     /// Get cache directory path
     pub fn get_cache_dir(&self) -> &Path {
         &self.cache_dir
@@ -549,12 +1003,137 @@ impl PackageManager {
 
     // ===== PRIVATE HELPER METHODS =====
 
+    fn require_registry(&self) -> Result<&RegistryClient> {
+        if self.offline {
+            return Err(CkpError::ValidationError(format!(
+                "Registry access is disabled in offline mode (unset {})",
+                OFFLINE_ENV
+            )));
+        }
+
+        self.registry.as_ref().ok_or_else(|| CkpError::ValidationError(format!(
+            "No package registry configured (set {})",
+            super::registry::REGISTRY_URL_ENV
+        )))
+    }
+
+    /// Path of a package tarball in the cache or, failing that, a vendored directory
+    fn local_path(&self, filename: &str) -> Option<PathBuf> {
+        std::iter::once(&self.cache_dir)
+            .chain(&self.vendor_dirs)
+            .map(|dir| dir.join(filename))
+            .find(|path| path.is_file())
+    }
+
+    /// Copy a selected build into the cache if it is only vendored
+    fn localize(&self, package: &PackageInfo) -> Result<PackageInfo> {
+        match self.local_path(&package.filename) {
+            Some(path) if path.starts_with(&self.cache_dir) => Ok(package.clone()),
+            Some(path) => self.import(&path),
+            None => Err(self.missing_packages(std::slice::from_ref(&package.filename))),
+        }
+    }
+
+    /// Error listing packages that are not available locally, with how to provide them
+    fn missing_packages(&self, missing: &[String]) -> CkpError {
+        let mut sources = vec![format!("the cache ({})", self.cache_dir.display())];
+        sources.extend(self.vendor_dirs.iter().map(|d| format!("{}", d.display())));
+
+        let remedy = if self.offline {
+            format!("or disable offline mode (${}) to fetch them from the registry", OFFLINE_ENV)
+        } else if self.registry.is_none() {
+            format!("or configure a registry (${})", super::registry::REGISTRY_URL_ENV)
+        } else {
+            "or publish them to the registry".to_string()
+        };
+
+        CkpError::MissingPackages(format!(
+            "{} (searched {}). Copy their package tarballs into a vendored directory (${}) {}",
+            missing.join(", "),
+            sources.join(", "),
+            VENDOR_DIR_ENV,
+            remedy
+        ))
+    }
+
+    /// Record an installed instance in the project's `ckp.lock`
+    fn lock_install(&self, concept_name: &str, package_path: &Path, target_dir: &Path, instance: &str) -> Result<()> {
+        let filename = package_path
+            .file_name()
+            .map(|f| f.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let (_, version, arch, runtime) = self.parse_package_filename(&filename)
+            .unwrap_or_else(|| (concept_name.to_string(), "unknown".to_string(), "unknown".to_string(), "unknown".to_string()));
+
+        let sha256 = file_sha256(package_path)?;
+        let mut lockfile = Lockfile::load(target_dir)?.unwrap_or_default();
+
+        // Reinstalling the same tarball keeps the registry it was locked from
+        let source = self.registry.as_ref().map(|r| r.base_url().to_string()).or_else(|| {
+            lockfile.get(instance)
+                .filter(|locked| locked.sha256 == sha256)
+                .and_then(|locked| locked.source.clone())
+        });
+
+        lockfile.upsert(LockedPackage {
+            instance: instance.to_string(),
+            name: concept_name.to_string(),
+            version,
+            arch,
+            runtime,
+            filename,
+            sha256,
+            source,
+            dependencies: self.read_dependencies(package_path),
+        });
+        lockfile.save(target_dir)
+    }
+
+    /// Dependencies declared in a package's `conceptkernel.yaml`
+    ///
+    /// Unreadable packages and missing declarations yield no dependencies.
+    pub(super) fn read_dependencies(&self, tarball_path: &Path) -> BTreeMap<String, String> {
+        use flate2::read::GzDecoder;
+        use std::io::Read;
+        use tar::Archive;
+
+        let file = match File::open(tarball_path) {
+            Ok(file) => file,
+            Err(_) => return BTreeMap::new(),
+        };
+        let mut archive = Archive::new(GzDecoder::new(file));
+        let entries = match archive.entries() {
+            Ok(entries) => entries,
+            Err(_) => return BTreeMap::new(),
+        };
+
+        for mut entry in entries.flatten() {
+            let is_ontology = entry.path()
+                .map(|p| p.components().count() == 2 && p.ends_with("conceptkernel.yaml"))
+                .unwrap_or(false);
+            if !is_ontology {
+                continue;
+            }
+
+            let mut content = String::new();
+            if entry.read_to_string(&mut content).is_err() {
+                break;
+            }
+            return serde_yaml::from_str::<serde_yaml::Value>(&content)
+                .ok()
+                .and_then(|yaml| yaml.get("spec").and_then(|spec| spec.get("dependencies")).map(parse_dependencies))
+                .unwrap_or_default();
+        }
+
+        BTreeMap::new()
+    }
+
     /// Detect runtime and architecture from ontology and system
     ///
     /// Returns (arch, runtime) tuple
     /// - arch: "aarch64-darwin", "x86_64-linux", "universal"
     /// - runtime: "rs", "py", "js"
-    fn detect_runtime_and_arch(&self, concept_dir: &Path) -> Result<(String, String)> {
+    pub(super) fn detect_runtime_and_arch(&self, concept_dir: &Path) -> Result<(String, String)> {
         // Read conceptkernel.yaml
         let ontology_path = concept_dir.join("conceptkernel.yaml");
         if !ontology_path.exists() {
@@ -580,7 +1159,7 @@ impl PackageManager {
         let (runtime, arch) = match kernel_type {
             t if t.starts_with("rust:") => {
                 // Rust binary - detect architecture
-                let arch = self.detect_system_arch();
+                let arch = host_arch();
                 ("rs".to_string(), arch)
             }
             t if t.starts_with("python:") => {
@@ -597,23 +1176,6 @@ impl PackageManager {
         Ok((arch, runtime))
     }
 
-    /// Detect system architecture
-    ///
-    /// Returns simplified arch string for package naming
-    fn detect_system_arch(&self) -> String {
-        let os = std::env::consts::OS;
-        let arch = std::env::consts::ARCH;
-
-        match (arch, os) {
-            ("x86_64", "linux") => "x86_64-linux".to_string(),
-            ("aarch64", "linux") => "aarch64-linux".to_string(),
-            ("x86_64", "windows") => "x86_64-windows".to_string(),
-            ("x86_64", "macos") => "x86_64-darwin".to_string(),
-            ("aarch64", "macos") => "aarch64-darwin".to_string(),
-            _ => format!("{}-{}", arch, os),
-        }
-    }
-
     /// Parse package filename to extract name, version, arch, runtime
     ///
     /// Supports both new format and old format (backward compat):
@@ -706,6 +1268,66 @@ impl PackageManager {
     }
 }
 
+/// Names reachable from the requests that have no candidate build at all
+fn unavailable(requests: &BTreeMap<String, String>, candidates: &[PackageInfo]) -> Vec<String> {
+    let mut pending: Vec<&String> = requests.keys().collect();
+    let mut seen = BTreeSet::new();
+    let mut missing = Vec::new();
+
+    while let Some(name) = pending.pop() {
+        if !seen.insert(name) {
+            continue;
+        }
+
+        let builds: Vec<&PackageInfo> = candidates.iter().filter(|p| &p.name == name).collect();
+        if builds.is_empty() {
+            missing.push(name.clone());
+        }
+        pending.extend(builds.iter().flat_map(|p| p.dependencies.keys()));
+    }
+
+    missing.sort();
+    missing
+}
+
+/// Parse `spec.dependencies`: a list of `ckp://Name[:range]` URNs or a name → range map
+pub(super) fn parse_dependencies(value: &serde_yaml::Value) -> BTreeMap<String, String> {
+    let mut dependencies = BTreeMap::new();
+
+    if let Some(list) = value.as_sequence() {
+        for item in list.iter().filter_map(|v| v.as_str()) {
+            let reference = item.strip_prefix("ckp://").unwrap_or(item);
+            let reference = reference.split('#').next().unwrap_or_default();
+            let (name, range) = reference.split_once(':').unwrap_or((reference, "*"));
+            if !name.is_empty() {
+                dependencies.insert(name.to_string(), range.to_string());
+            }
+        }
+    } else if let Some(map) = value.as_mapping() {
+        for (name, range) in map {
+            if let Some(name) = name.as_str() {
+                let range = match range {
+                    serde_yaml::Value::String(s) => s.clone(),
+                    serde_yaml::Value::Number(n) => n.to_string(),
+                    _ => "*".to_string(),
+                };
+                dependencies.insert(name.to_string(), range);
+            }
+        }
+    }
+
+    dependencies
+}
+
+/// Reject lock entries whose name is not a single plain path component
+fn validate_kernel_name(name: &str) -> Result<()> {
+    let mut components = Path::new(name).components();
+    match (components.next(), components.next()) {
+        (Some(Component::Normal(_)), None) if !name.contains('\\') => Ok(()),
+        _ => Err(CkpError::ValidationError(format!("Invalid kernel name in {}: {}", LOCKFILE_NAME, name))),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -716,6 +1338,173 @@ mod tests {
         assert!(pm.is_ok());
     }
 
+    #[test]
+    fn test_strict_policy_requires_trusted_signature() {
+        use ed25519_dalek::SigningKey;
+        use tempfile::TempDir;
+
+        let temp_dir = TempDir::new().unwrap();
+        let project = temp_dir.path().join("project");
+        fs::create_dir_all(project.join("concepts/Demo.Kernel")).unwrap();
+        fs::write(project.join("concepts/Demo.Kernel/conceptkernel.yaml"), "metadata:\n  type: python:hot\n").unwrap();
+
+        let trust_dir = temp_dir.path().join("trust");
+        let pm = PackageManager::from_cache_dir(temp_dir.path().join("cache")).unwrap()
+            .with_trust_store(TrustStore::load(trust_dir.clone()).unwrap())
+            .with_trust_policy(TrustPolicy::Strict);
+        pm.export("Demo.Kernel", "v0.1", &project).unwrap();
+        let package = pm.list_cached().unwrap().remove(0);
+
+        let target = temp_dir.path().join("target");
+        let err = pm.install_from_package(&package, &target, None).unwrap_err();
+        assert!(matches!(err, CkpError::AuthenticationFailed(_)));
+        assert!(!target.join("concepts/Demo.Kernel").exists());
+
+        let signer = PackageSigner::new(SigningKey::from_bytes(&[3u8; 32]));
+        pm.sign(&package, &signer).unwrap();
+        let mut store = TrustStore::load(trust_dir.clone()).unwrap();
+        store.add(signer.trusted_key()).unwrap();
+
+        let pm = pm.with_trust_store(TrustStore::load(trust_dir).unwrap());
+        assert!(matches!(pm.verify_package(&pm.get_cache_dir().join(&package.filename)).unwrap(), SignatureStatus::Verified { .. }));
+        let installed = pm.install_from_package(&package, &target, None).unwrap();
+        assert!(installed.join("conceptkernel.yaml").exists());
+    }
+
+    #[test]
+    fn test_install_resolved_installs_dependencies_first() {
+        use tempfile::TempDir;
+
+        let temp_dir = TempDir::new().unwrap();
+        let project = temp_dir.path().join("project");
+        for (name, spec) in [
+            ("Demo.Core", ""),
+            ("Demo.App", "spec:\n  dependencies:\n    - ckp://Demo.Core:^0.1\n"),
+        ] {
+            fs::create_dir_all(project.join("concepts").join(name)).unwrap();
+            fs::write(
+                project.join("concepts").join(name).join("conceptkernel.yaml"),
+                format!("metadata:\n  type: python:hot\n{}", spec),
+            ).unwrap();
+        }
+
+        let pm = PackageManager::from_cache_dir(temp_dir.path().join("cache")).unwrap();
+        pm.export("Demo.Core", "v0.1", &project).unwrap();
+        pm.export("Demo.App", "v0.2", &project).unwrap();
+
+        let app = pm.list_cached().unwrap().into_iter().find(|p| p.name == "Demo.App").unwrap();
+        assert_eq!(app.dependencies.get("Demo.Core").map(String::as_str), Some("^0.1"));
+
+        let mut requests = BTreeMap::new();
+        requests.insert("Demo.App".to_string(), "*".to_string());
+        let target = temp_dir.path().join("target");
+        let installed = pm.install_resolved(&requests, &target).unwrap();
+
+        assert_eq!(installed, vec![target.join("concepts/Demo.Core"), target.join("concepts/Demo.App")]);
+        assert!(pm.install_resolved(&requests, &target).unwrap().is_empty());
+
+        requests.insert("Demo.Core".to_string(), "^1.0".to_string());
+        assert!(matches!(pm.resolve(&requests), Err(CkpError::DependencyConflict(_))));
+    }
+
+    #[test]
+    fn test_install_from_lock_reproduces_locked_instances() {
+        use tempfile::TempDir;
+
+        let temp_dir = TempDir::new().unwrap();
+        let project = temp_dir.path().join("project");
+        fs::create_dir_all(project.join("concepts/Demo.Kernel")).unwrap();
+        fs::write(project.join("concepts/Demo.Kernel/conceptkernel.yaml"), "metadata:\n  type: python:hot\n").unwrap();
+
+        let pm = PackageManager::from_cache_dir(temp_dir.path().join("cache")).unwrap();
+        pm.export("Demo.Kernel", "v0.1", &project).unwrap();
+        let package = pm.list_cached().unwrap().remove(0);
+
+        let target = temp_dir.path().join("target");
+        pm.install_from_package(&package, &target, None).unwrap();
+        pm.install_from_package(&package, &target, Some("Demo.Kernel.Blue")).unwrap();
+
+        let lockfile = Lockfile::load(&target).unwrap().unwrap();
+        assert_eq!(lockfile.packages.len(), 2);
+        let locked = lockfile.get("Demo.Kernel.Blue").unwrap();
+        assert_eq!((locked.name.as_str(), locked.version.as_str()), ("Demo.Kernel", "v0.1"));
+
+        let replica = temp_dir.path().join("replica");
+        fs::create_dir_all(&replica).unwrap();
+        fs::copy(Lockfile::path(&target), Lockfile::path(&replica)).unwrap();
+        let installed = pm.install_from_lock(&replica).unwrap();
+        assert_eq!(installed, vec![replica.join("concepts/Demo.Kernel"), replica.join("concepts/Demo.Kernel.Blue")]);
+        assert_eq!(Lockfile::load(&replica).unwrap().unwrap(), lockfile);
+        assert!(pm.install_from_lock(&replica).unwrap().is_empty());
+
+        // A rebuilt tarball no longer matches the lock
+        fs::write(project.join("concepts/Demo.Kernel/extra.txt"), "changed").unwrap();
+        pm.export("Demo.Kernel", "v0.1", &project).unwrap();
+        let rebuilt = temp_dir.path().join("rebuilt");
+        fs::create_dir_all(&rebuilt).unwrap();
+        fs::copy(Lockfile::path(&target), Lockfile::path(&rebuilt)).unwrap();
+        assert!(matches!(pm.install_from_lock(&rebuilt), Err(CkpError::ValidationError(_))));
+
+        // An instance outside the project's concepts directory is refused before anything is written
+        let escaping = temp_dir.path().join("escaping");
+        fs::create_dir_all(&escaping).unwrap();
+        let lock = fs::read_to_string(Lockfile::path(&target)).unwrap();
+        fs::write(Lockfile::path(&escaping), lock.replace("Demo.Kernel.Blue", "../x")).unwrap();
+        assert!(matches!(pm.install_from_lock(&escaping), Err(CkpError::ValidationError(_))));
+        assert!(!escaping.join("x").exists() && !escaping.join("concepts").exists());
+    }
+
+    #[test]
+    fn test_offline_resolves_from_vendored_dir_and_lists_missing() {
+        use crate::cache::RegistryClient;
+        use tempfile::TempDir;
+
+        let temp_dir = TempDir::new().unwrap();
+        let project = temp_dir.path().join("project");
+        for (name, spec) in [
+            ("Demo.Core", ""),
+            ("Demo.App", "spec:\n  dependencies:\n    - ckp://Demo.Core:^0.1\n    - ckp://Demo.Auth\n"),
+        ] {
+            fs::create_dir_all(project.join("concepts").join(name)).unwrap();
+            fs::write(
+                project.join("concepts").join(name).join("conceptkernel.yaml"),
+                format!("metadata:\n  type: python:hot\n{}", spec),
+            ).unwrap();
+        }
+
+        let vendor = PackageManager::from_cache_dir(temp_dir.path().join("vendor")).unwrap();
+        vendor.export("Demo.Core", "v0.1", &project).unwrap();
+        vendor.export("Demo.App", "v0.2", &project).unwrap();
+
+        let pm = PackageManager::from_cache_dir(temp_dir.path().join("cache")).unwrap()
+            .with_registry(RegistryClient::new("http://127.0.0.1:9").unwrap())
+            .with_vendor_dir(temp_dir.path().join("vendor"))
+            .with_offline(true);
+        assert!(matches!(pm.search_remote("Demo"), Err(CkpError::ValidationError(_))));
+
+        let mut requests = BTreeMap::new();
+        requests.insert("Demo.App".to_string(), "*".to_string());
+        requests.insert("Demo.Billing".to_string(), "*".to_string());
+        let target = temp_dir.path().join("target");
+        match pm.install_resolved(&requests, &target) {
+            Err(CkpError::MissingPackages(message)) => {
+                assert!(message.starts_with("Demo.Auth, Demo.Billing (searched"));
+                assert!(message.contains("CKP_OFFLINE"));
+            }
+            other => panic!("expected MissingPackages, got {:?}", other),
+        }
+
+        fs::create_dir_all(project.join("concepts/Demo.Auth")).unwrap();
+        fs::write(project.join("concepts/Demo.Auth/conceptkernel.yaml"), "metadata:\n  type: python:hot\n").unwrap();
+        vendor.export("Demo.Auth", "v1.0", &project).unwrap();
+        requests.remove("Demo.Billing");
+
+        let installed = pm.install_resolved(&requests, &target).unwrap();
+        assert_eq!(installed.len(), 3);
+        assert_eq!(pm.list_cached().unwrap().len(), 3);
+        assert!(pm.list_vendored().unwrap().is_empty());
+    }
+
     #[test]
     fn test_list_empty_cache() {
         let pm = PackageManager::new().unwrap();
@@ -0,0 +1,240 @@
+//! Host platform matching for package builds
+//!
+//! A package version may be published as several builds, one per
+//! architecture and runtime. The host accepts builds for its own
+//! architecture or an architecture-independent one (`any`/`universal`), in
+//! a runtime it is willing to run. Among accepted builds the runtime
+//! preference order decides first, then native beats architecture-independent.
+
+use super::registry::RegistryPackage;
+use super::resolver::parse_version;
+use super::PackageInfo;
+use crate::errors::{CkpError, Result};
+use std::env;
+
+/// Environment variable overriding the runtime preference order (comma-separated)
+pub const RUNTIME_PREFERENCE_ENV: &str = "CKP_RUNTIME_PREFERENCE";
+
+/// Runtimes in default order of preference
+pub const DEFAULT_RUNTIME_PREFERENCE: &[&str] = &["rs", "js", "py"];
+
+/// Architecture names for builds that run anywhere
+pub const ANY_ARCH: &[&str] = &["any", "universal"];
+
+/// Placeholder arch/runtime of legacy `<name>@<version>.tar.gz` packages
+const UNKNOWN: &str = "unknown";
+
+/// Architecture of this host as used in package filenames
+pub fn host_arch() -> String {
+    let os = env::consts::OS;
+    let arch = env::consts::ARCH;
+
+    match (arch, os) {
+        ("x86_64", "linux") => "x86_64-linux".to_string(),
+        ("aarch64", "linux") => "aarch64-linux".to_string(),
+        ("x86_64", "windows") => "x86_64-windows".to_string(),
+        ("x86_64", "macos") => "x86_64-darwin".to_string(),
+        ("aarch64", "macos") => "aarch64-darwin".to_string(),
+        _ => format!("{}-{}", arch, os),
+    }
+}
+
+/// A build that can be matched against a host
+pub trait PackageBuild {
+    fn name(&self) -> &str;
+    fn version(&self) -> &str;
+    fn arch(&self) -> &str;
+    fn runtime(&self) -> &str;
+}
+
+impl PackageBuild for PackageInfo {
+    fn name(&self) -> &str { &self.name }
+    fn version(&self) -> &str { &self.version }
+    fn arch(&self) -> &str { &self.arch }
+    fn runtime(&self) -> &str { &self.runtime }
+}
+
+impl PackageBuild for RegistryPackage {
+    fn name(&self) -> &str { &self.name }
+    fn version(&self) -> &str { &self.version }
+    fn arch(&self) -> &str { &self.arch }
+    fn runtime(&self) -> &str { &self.runtime }
+}
+
+impl<T: PackageBuild> PackageBuild for &T {
+    fn name(&self) -> &str { (*self).name() }
+    fn version(&self) -> &str { (*self).version() }
+    fn arch(&self) -> &str { (*self).arch() }
+    fn runtime(&self) -> &str { (*self).runtime() }
+}
+
+/// Architecture and runtimes a host can use
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HostPlatform {
+    pub arch: String,
+
+    /// Acceptable runtimes, most preferred first
+    pub runtimes: Vec<String>,
+}
+
+impl Default for HostPlatform {
+    fn default() -> Self {
+        Self::detect()
+    }
+}
+
+impl HostPlatform {
+    /// A platform with the default runtime preference
+    pub fn new(arch: impl Into<String>) -> Self {
+        Self {
+            arch: arch.into(),
+            runtimes: DEFAULT_RUNTIME_PREFERENCE.iter().map(|r| r.to_string()).collect(),
+        }
+    }
+
+    /// This host, with runtime preference from `$CKP_RUNTIME_PREFERENCE` if set
+    pub fn detect() -> Self {
+        let platform = Self::new(host_arch());
+        match env::var(RUNTIME_PREFERENCE_ENV) {
+            Ok(preference) => platform.with_runtimes(preference.split(',').map(str::trim).filter(|r| !r.is_empty())),
+            Err(_) => platform,
+        }
+    }
+
+    pub fn with_runtimes<I, S>(mut self, runtimes: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.runtimes = runtimes.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Rank of a build, lower is better
+    ///
+    /// # Returns
+    /// None if the host cannot use the build
+    pub fn rank(&self, arch: &str, runtime: &str) -> Option<(usize, usize)> {
+        let arch_rank = if arch == self.arch {
+            0
+        } else if ANY_ARCH.contains(&arch) {
+            1
+        } else if arch == UNKNOWN {
+            2
+        } else {
+            return None;
+        };
+
+        let runtime_rank = match self.runtimes.iter().position(|r| r == runtime) {
+            Some(position) => position,
+            None if runtime == UNKNOWN => self.runtimes.len(),
+            None => return None,
+        };
+
+        Some((runtime_rank, arch_rank))
+    }
+
+    pub fn is_compatible(&self, arch: &str, runtime: &str) -> bool {
+        self.rank(arch, runtime).is_some()
+    }
+
+    /// Best build of a package for this host
+    ///
+    /// Without a version, the highest version that has a usable build is chosen.
+    ///
+    /// # Errors
+    /// `CkpError::NoCompatiblePackage` describing what is available instead
+    pub fn select<'a, T: PackageBuild>(&self, name: &str, version: Option<&str>, builds: &'a [T]) -> Result<&'a T> {
+        let usable = builds.iter()
+            .filter(|b| b.name() == name && version.is_none_or(|v| b.version() == v))
+            .filter_map(|b| self.rank(b.arch(), b.runtime()).map(|rank| (b, rank)));
+
+        // Highest version first, then best rank; earlier builds win ties
+        usable
+            .min_by(|(a, a_rank), (b, b_rank)| {
+                parse_version(b.version()).cmp(&parse_version(a.version()))
+                    .then_with(|| a_rank.cmp(b_rank))
+            })
+            .map(|(build, _)| build)
+            .ok_or_else(|| CkpError::NoCompatiblePackage(self.explain(name, version, builds)))
+    }
+
+    /// Why no build of a package matches this host
+    pub fn explain<T: PackageBuild>(&self, name: &str, version: Option<&str>, builds: &[T]) -> String {
+        let wanted = match version {
+            Some(version) => format!("{}@{}", name, version),
+            None => name.to_string(),
+        };
+        let available: Vec<String> = builds.iter()
+            .filter(|b| b.name() == name && version.is_none_or(|v| b.version() == v))
+            .map(|b| {
+                let arch_ok = b.arch() == self.arch || ANY_ARCH.contains(&b.arch()) || b.arch() == UNKNOWN;
+                let runtime_ok = b.runtime() == UNKNOWN || self.runtimes.iter().any(|r| r == b.runtime());
+                let reason = match (arch_ok, runtime_ok) {
+                    (false, false) => "wrong arch and runtime",
+                    (false, true) => "wrong arch",
+                    _ => "runtime not accepted",
+                };
+                format!("{}@{} [{}/{}] ({})", b.name(), b.version(), b.arch(), b.runtime(), reason)
+            })
+            .collect();
+
+        if available.is_empty() {
+            return format!("no builds of {} exist", wanted);
+        }
+
+        format!(
+            "no build of {} for {} with runtime {}; available: {}",
+            wanted,
+            self.arch,
+            self.runtimes.join(", "),
+            available.join(", ")
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn build(version: &str, arch: &str, runtime: &str) -> PackageInfo {
+        PackageInfo {
+            name: "Demo.Kernel".to_string(),
+            version: version.to_string(),
+            arch: arch.to_string(),
+            runtime: runtime.to_string(),
+            filename: format!("Demo.Kernel-{}.{}.{}.tar.gz", version, arch, runtime),
+            size_bytes: 0,
+            created_at: String::new(),
+            dependencies: Default::default(),
+        }
+    }
+
+    #[test]
+    fn test_select_prefers_runtime_then_native_arch() {
+        let host = HostPlatform::new("x86_64-linux");
+        let builds = vec![
+            build("v1.0", "universal", "py"),
+            build("v1.0", "any", "rs"),
+            build("v1.0", "x86_64-linux", "rs"),
+            build("v1.1", "aarch64-darwin", "rs"),
+            build("v0.9", "x86_64-linux", "rs"),
+        ];
+
+        let best = host.select("Demo.Kernel", None, &builds).unwrap();
+        assert_eq!((best.version.as_str(), best.arch.as_str(), best.runtime.as_str()), ("v1.0", "x86_64-linux", "rs"));
+
+        let python_first = host.clone().with_runtimes(["py", "rs"]);
+        assert_eq!(python_first.select("Demo.Kernel", Some("v1.0"), &builds).unwrap().runtime, "py");
+
+        let node_only = host.with_runtimes(["js"]);
+        match node_only.select("Demo.Kernel", Some("v1.1"), &builds) {
+            Err(CkpError::NoCompatiblePackage(message)) => {
+                assert!(message.contains("for x86_64-linux with runtime js"));
+                assert!(message.contains("[aarch64-darwin/rs] (wrong arch and runtime)"));
+            }
+            other => panic!("expected NoCompatiblePackage, got {:?}", other),
+        }
+        assert!(node_only.explain("Demo.Missing", None, &builds).contains("no builds of Demo.Missing"));
+    }
+}
@@ -0,0 +1,588 @@
+//! Remote package registry client
+//!
+//! Talks to a shared HTTP registry of concept packages:
+//! - `GET  /api/v1/packages?q={query}`                   search
+//! - `GET  /api/v1/packages/{name}`                      all published builds
+//! - `GET  /api/v1/packages/{name}/{version}/{filename}` download (supports `Range`)
+//! - `PUT  /api/v1/packages/{name}/{version}/{filename}` publish (bearer token)
+//!
+//! Detached signatures are served and published alongside as `{filename}.minisig`.
+//!
+//! Downloads are written to `{filename}.part` first and resumed from its
+//! length if interrupted.
+//!
+//! Reads fail over to mirrors, in order, when a registry is unreachable or
+//! answers with a server error. Publishing always targets the primary.
+
+use super::signature::{signature_path, SIGNATURE_EXTENSION};
+use super::PackageInfo;
+use crate::errors::{CkpError, Result};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::env;
+use std::fs::{self, OpenOptions};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// Environment variable holding the registry base URL
+pub const REGISTRY_URL_ENV: &str = "CKP_REGISTRY_URL";
+
+/// Environment variable holding the registry auth token
+pub const REGISTRY_TOKEN_ENV: &str = "CKP_REGISTRY_TOKEN";
+
+/// Environment variable holding comma-separated mirror URLs
+pub const REGISTRY_MIRRORS_ENV: &str = "CKP_REGISTRY_MIRRORS";
+
+/// Default request timeout
+pub const DEFAULT_REGISTRY_TIMEOUT: Duration = Duration::from_secs(300);
+
+/// A package build published to the registry
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RegistryPackage {
+    pub name: String,
+    pub version: String,
+    pub arch: String,
+    pub runtime: String,
+    pub filename: String,
+
+    #[serde(rename = "sizeBytes", default)]
+    pub size_bytes: u64,
+
+    /// SHA-256 of the tarball, verified after download when present
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sha256: Option<String>,
+
+    #[serde(rename = "publishedAt", default, skip_serializing_if = "Option::is_none")]
+    pub published_at: Option<String>,
+
+    /// Required packages: name → semver range
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub dependencies: BTreeMap<String, String>,
+}
+
+impl RegistryPackage {
+    /// Package metadata as it will appear once cached
+    pub fn to_package_info(&self) -> PackageInfo {
+        PackageInfo {
+            name: self.name.clone(),
+            version: self.version.clone(),
+            arch: self.arch.clone(),
+            runtime: self.runtime.clone(),
+            filename: self.filename.clone(),
+            size_bytes: self.size_bytes,
+            created_at: self.published_at.clone().unwrap_or_default(),
+            dependencies: self.dependencies.clone(),
+        }
+    }
+}
+
+/// HTTP client for a concept package registry
+///
+/// # Examples
+///
+/// ```no_run
+/// use ckp_core::cache::RegistryClient;
+///
+/// let registry = RegistryClient::new("https://registry.example.com").unwrap();
+/// for package in registry.search("Gateway").unwrap() {
+///     println!("{}@{} [{}/{}]", package.name, package.version, package.arch, package.runtime);
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct RegistryClient {
+    base_url: String,
+    mirrors: Vec<String>,
+    token: Option<String>,
+    timeout: Duration,
+}
+
+impl RegistryClient {
+    /// Create a client for a registry base URL (http or https)
+    pub fn new(base_url: &str) -> Result<Self> {
+        Ok(Self {
+            base_url: normalize_url(base_url)?,
+            mirrors: Vec::new(),
+            token: None,
+            timeout: DEFAULT_REGISTRY_TIMEOUT,
+        })
+    }
+
+    /// Client configured from `CKP_REGISTRY_URL`, `CKP_REGISTRY_MIRRORS`
+    /// and `CKP_REGISTRY_TOKEN`
+    ///
+    /// # Returns
+    /// None if no registry URL is set
+    pub fn from_env() -> Result<Option<Self>> {
+        let url = match env::var(REGISTRY_URL_ENV) {
+            Ok(url) if !url.is_empty() => url,
+            _ => return Ok(None),
+        };
+
+        Self::new(&url)?.with_env_options().map(Some)
+    }
+
+    /// Apply mirrors and token from the environment, if set
+    pub fn with_env_options(mut self) -> Result<Self> {
+        if let Ok(mirrors) = env::var(REGISTRY_MIRRORS_ENV) {
+            self = self.with_mirrors(mirrors.split(',').map(str::trim).filter(|m| !m.is_empty()))?;
+        }
+        if let Ok(token) = env::var(REGISTRY_TOKEN_ENV) {
+            if !token.is_empty() {
+                self = self.with_token(&token);
+            }
+        }
+
+        Ok(self)
+    }
+
+    /// Add mirrors tried, in order, when the registry is unreachable
+    pub fn with_mirrors<I, S>(mut self, mirrors: I) -> Result<Self>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        for mirror in mirrors {
+            let mirror = normalize_url(mirror.as_ref())?;
+            if mirror != self.base_url && !self.mirrors.contains(&mirror) {
+                self.mirrors.push(mirror);
+            }
+        }
+
+        Ok(self)
+    }
+
+    /// Set the bearer token sent with every request (required to publish)
+    pub fn with_token(mut self, token: &str) -> Self {
+        self.token = Some(token.to_string());
+        self
+    }
+
+    /// Set the request timeout
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Registry base URL
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    /// Mirror base URLs, in failover order
+    pub fn mirrors(&self) -> &[String] {
+        &self.mirrors
+    }
+
+    /// Search packages by name fragment
+    pub fn search(&self, query: &str) -> Result<Vec<RegistryPackage>> {
+        self.read(&["api", "v1", "packages"], |mut url| {
+            url.query_pairs_mut().append_pair("q", query);
+            let response = self.send(self.http()?.get(url))?;
+            response.json()
+                .map_err(|e| CkpError::ParseError(format!("Invalid registry search response: {}", e)))
+        })
+    }
+
+    /// All published builds of a package
+    ///
+    /// # Errors
+    /// `CkpError::FileNotFound` if the registry does not know the package
+    pub fn versions(&self, name: &str) -> Result<Vec<RegistryPackage>> {
+        self.read(&["api", "v1", "packages", name], |url| {
+            let response = self.send(self.http()?.get(url))?;
+            response.json()
+                .map_err(|e| CkpError::ParseError(format!("Invalid registry package response: {}", e)))
+        })
+    }
+
+    /// Builds of one package version
+    ///
+    /// # Errors
+    /// `CkpError::FileNotFound` if the version was never published
+    pub fn fetch(&self, name: &str, version: &str) -> Result<Vec<RegistryPackage>> {
+        let builds: Vec<RegistryPackage> = self.versions(name)?
+            .into_iter()
+            .filter(|p| p.version == version)
+            .collect();
+
+        if builds.is_empty() {
+            return Err(CkpError::FileNotFound(format!("Package not found in registry: {}@{}", name, version)));
+        }
+
+        Ok(builds)
+    }
+
+    /// Download a package tarball into `dest_dir`, resuming a partial download
+    ///
+    /// # Returns
+    /// Path of the downloaded `{filename}`
+    pub fn download(&self, package: &RegistryPackage, dest_dir: &Path) -> Result<PathBuf> {
+        validate_filename(&package.filename)?;
+
+        fs::create_dir_all(dest_dir)
+            .map_err(|e| CkpError::IoError(format!("Failed to create download directory: {}", e)))?;
+        let target = dest_dir.join(&package.filename);
+        let partial = dest_dir.join(format!("{}.part", package.filename));
+
+        // A mirror picks up wherever a failed attempt left the partial file
+        self.read(&["api", "v1", "packages", &package.name, &package.version, &package.filename], |url| {
+            let offset = fs::metadata(&partial).map(|m| m.len()).unwrap_or(0);
+            let mut request = self.http()?.get(url);
+            if offset > 0 {
+                request = request.header(reqwest::header::RANGE, format!("bytes={}-", offset));
+            }
+
+            let response = request.send()
+                .map_err(|e| CkpError::IoError(format!("Registry request failed: {}", e)))?;
+
+            match response.status() {
+                reqwest::StatusCode::RANGE_NOT_SATISFIABLE if offset > 0 && offset == package.size_bytes => Ok(()),
+                reqwest::StatusCode::PARTIAL_CONTENT | reqwest::StatusCode::OK => {
+                    let resume = response.status() == reqwest::StatusCode::PARTIAL_CONTENT;
+                    let mut file = OpenOptions::new()
+                        .create(true)
+                        .write(true)
+                        .append(resume)
+                        .truncate(!resume)
+                        .open(&partial)
+                        .map_err(|e| CkpError::IoError(format!("Failed to open {}: {}", partial.display(), e)))?;
+
+                    let mut response = response;
+                    response.copy_to(&mut file)
+                        .map(|_| ())
+                        .map_err(|e| CkpError::IoError(format!("Download of {} interrupted: {}", package.filename, e)))
+                }
+                _ => {
+                    self.check_status(response)?;
+                    Err(CkpError::IoError(format!("Unexpected registry response for {}", package.filename)))
+                }
+            }
+        })?;
+
+        if let Some(expected) = &package.sha256 {
+            let actual = hex::encode(Sha256::digest(fs::read(&partial)?));
+            if !actual.eq_ignore_ascii_case(expected) {
+                let _ = fs::remove_file(&partial);
+                return Err(CkpError::ValidationError(format!(
+                    "Checksum mismatch for {}: expected {}, got {}",
+                    package.filename, expected, actual
+                )));
+            }
+        }
+
+        fs::rename(&partial, &target)
+            .map_err(|e| CkpError::IoError(format!("Failed to finalize download: {}", e)))?;
+
+        Ok(target)
+    }
+
+    /// Download a package's detached signature into `dest_dir`
+    ///
+    /// # Returns
+    /// Path of `{filename}.minisig`, or None if the package is unsigned
+    pub fn download_signature(&self, package: &RegistryPackage, dest_dir: &Path) -> Result<Option<PathBuf>> {
+        validate_filename(&package.filename)?;
+        let sig_name = format!("{}.{}", package.filename, SIGNATURE_EXTENSION);
+
+        let response = match self.read(&["api", "v1", "packages", &package.name, &package.version, &sig_name], |url| {
+            self.send(self.http()?.get(url))
+        }) {
+            Ok(response) => response,
+            Err(CkpError::FileNotFound(_)) => return Ok(None),
+            Err(e) => return Err(e),
+        };
+        let body = response.bytes()
+            .map_err(|e| CkpError::IoError(format!("Failed to read signature: {}", e)))?;
+
+        let path = dest_dir.join(sig_name);
+        fs::write(&path, &body)
+            .map_err(|e| CkpError::IoError(format!("Failed to write signature: {}", e)))?;
+
+        Ok(Some(path))
+    }
+
+    /// Publish a package tarball (and its `.minisig` signature, if present)
+    ///
+    /// # Errors
+    /// `CkpError::AuthenticationFailed` without a token or if the registry rejects it
+    pub fn publish(&self, package: &RegistryPackage, tarball: &Path) -> Result<RegistryPackage> {
+        validate_filename(&package.filename)?;
+        if self.token.is_none() {
+            return Err(CkpError::AuthenticationFailed(format!(
+                "Publishing requires a registry token (set {})",
+                REGISTRY_TOKEN_ENV
+            )));
+        }
+
+        let body = fs::read(tarball)
+            .map_err(|e| CkpError::IoError(format!("Failed to read {}: {}", tarball.display(), e)))?;
+        let sha256 = hex::encode(Sha256::digest(&body));
+
+        let url = self.url(&["api", "v1", "packages", &package.name, &package.version, &package.filename])?;
+        let request = self.http()?
+            .put(url)
+            .header(reqwest::header::CONTENT_TYPE, "application/gzip")
+            .header("X-Package-Arch", &package.arch)
+            .header("X-Package-Runtime", &package.runtime)
+            .header("X-Checksum-Sha256", &sha256)
+            .body(body);
+
+        let response = self.send(request)?;
+        let text = response.text()
+            .map_err(|e| CkpError::IoError(format!("Failed to read registry response: {}", e)))?;
+
+        let sig_path = signature_path(tarball);
+        if sig_path.exists() {
+            let sig_name = format!("{}.{}", package.filename, SIGNATURE_EXTENSION);
+            let signature = fs::read(&sig_path)
+                .map_err(|e| CkpError::IoError(format!("Failed to read signature: {}", e)))?;
+            let url = self.url(&["api", "v1", "packages", &package.name, &package.version, &sig_name])?;
+            self.send(self.http()?.put(url).header(reqwest::header::CONTENT_TYPE, "text/plain").body(signature))?;
+        }
+
+        // Registries may answer with the stored record or nothing at all
+        Ok(serde_json::from_str(&text).unwrap_or_else(|_| RegistryPackage {
+            sha256: Some(sha256),
+            ..package.clone()
+        }))
+    }
+
+    fn url(&self, segments: &[&str]) -> Result<reqwest::Url> {
+        endpoint(&self.base_url, segments)
+    }
+
+    /// Run a read against the registry, failing over to each mirror in turn
+    ///
+    /// Only unreachable registries and server errors fail over; answers such
+    /// as not-found or rejected credentials are returned as they are.
+    fn read<T>(&self, segments: &[&str], mut attempt: impl FnMut(reqwest::Url) -> Result<T>) -> Result<T> {
+        let mut failures = Vec::new();
+
+        for base in std::iter::once(&self.base_url).chain(&self.mirrors) {
+            match attempt(endpoint(base, segments)?) {
+                Err(CkpError::IoError(e)) => failures.push(e),
+                result => return result,
+            }
+        }
+
+        Err(CkpError::IoError(failures.join("; ")))
+    }
+
+    fn http(&self) -> Result<reqwest::blocking::Client> {
+        let mut headers = reqwest::header::HeaderMap::new();
+        if let Some(token) = &self.token {
+            let value = reqwest::header::HeaderValue::from_str(&format!("Bearer {}", token))
+                .map_err(|e| CkpError::ValidationError(format!("Invalid registry token: {}", e)))?;
+            headers.insert(reqwest::header::AUTHORIZATION, value);
+        }
+
+        reqwest::blocking::Client::builder()
+            .timeout(self.timeout)
+            .default_headers(headers)
+            .build()
+            .map_err(|e| CkpError::IoError(format!("Failed to build registry client: {}", e)))
+    }
+
+    fn send(&self, request: reqwest::blocking::RequestBuilder) -> Result<reqwest::blocking::Response> {
+        let response = request.send()
+            .map_err(|e| CkpError::IoError(format!("Registry request failed: {}", e)))?;
+        self.check_status(response)
+    }
+
+    fn check_status(&self, response: reqwest::blocking::Response) -> Result<reqwest::blocking::Response> {
+        let status = response.status();
+        if status.is_success() {
+            return Ok(response);
+        }
+
+        let url = response.url().to_string();
+        match status {
+            reqwest::StatusCode::NOT_FOUND => Err(CkpError::FileNotFound(format!("Not found in registry: {}", url))),
+            reqwest::StatusCode::UNAUTHORIZED | reqwest::StatusCode::FORBIDDEN => Err(CkpError::AuthenticationFailed(
+                format!("Registry rejected credentials ({}): {}", status, url),
+            )),
+            _ => Err(CkpError::IoError(format!("Registry returned {}: {}", status, url))),
+        }
+    }
+}
+
+/// Validate a registry URL and strip its trailing slash
+fn normalize_url(url: &str) -> Result<String> {
+    let parsed = reqwest::Url::parse(url)
+        .map_err(|e| CkpError::ValidationError(format!("Invalid registry URL '{}': {}", url, e)))?;
+    if !matches!(parsed.scheme(), "http" | "https") {
+        return Err(CkpError::ValidationError(format!(
+            "Registry URL must be http or https: {}",
+            url
+        )));
+    }
+
+    Ok(url.trim_end_matches('/').to_string())
+}
+
+/// URL of an API path under a registry base URL
+fn endpoint(base: &str, segments: &[&str]) -> Result<reqwest::Url> {
+    let mut url = reqwest::Url::parse(base)
+        .map_err(|e| CkpError::ValidationError(format!("Invalid registry URL: {}", e)))?;
+    url.path_segments_mut()
+        .map_err(|_| CkpError::ValidationError(format!("Registry URL cannot be a base: {}", base)))?
+        .pop_if_empty()
+        .extend(segments);
+    Ok(url)
+}
+
+/// Reject filenames that would escape the cache directory
+pub(super) fn validate_filename(filename: &str) -> Result<()> {
+    if filename.is_empty() || filename.contains(['/', '\\']) || filename.starts_with('.') || !filename.ends_with(".tar.gz") {
+        return Err(CkpError::ValidationError(format!("Invalid package filename: {}", filename)));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cache::PackageManager;
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+    use std::sync::{Arc, Mutex};
+    use tempfile::TempDir;
+
+    const TARBALL: &[u8] = b"0123456789abcdefghijklmnopqrstuvwxyz";
+
+    /// Minimal registry: package listing, ranged downloads and publish
+    fn spawn_registry(requests: usize, seen: Arc<Mutex<Vec<String>>>) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let listing = serde_json::json!([
+            {"name": "Demo.Kernel", "version": "v0.1", "arch": "universal", "runtime": "py",
+             "filename": "Demo.Kernel-v0.1.universal.py.tar.gz", "sizeBytes": TARBALL.len(),
+             "sha256": hex::encode(Sha256::digest(TARBALL))},
+            {"name": "Demo.Kernel", "version": "v0.2", "arch": "universal", "runtime": "py",
+             "filename": "Demo.Kernel-v0.2.universal.py.tar.gz", "sizeBytes": TARBALL.len(),
+             "sha256": hex::encode(Sha256::digest(TARBALL))}
+        ]).to_string();
+
+        std::thread::spawn(move || {
+            for _ in 0..requests {
+                let (stream, _) = listener.accept().unwrap();
+                let mut reader = BufReader::new(stream);
+                let mut request_line = String::new();
+                reader.read_line(&mut request_line).unwrap();
+
+                let mut headers = Vec::new();
+                let mut content_length = 0;
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    if line == "\r\n" {
+                        break;
+                    }
+                    let lower = line.to_ascii_lowercase();
+                    if let Some(value) = lower.strip_prefix("content-length:") {
+                        content_length = value.trim().parse().unwrap();
+                    }
+                    headers.push(line.trim().to_string());
+                }
+                let mut body = vec![0u8; content_length];
+                reader.read_exact(&mut body).unwrap();
+
+                let range = headers.iter()
+                    .find_map(|h| h.strip_prefix("range: bytes=").or_else(|| h.strip_prefix("Range: bytes=")))
+                    .map(|r| r.trim_end_matches('-').parse::<usize>().unwrap());
+                seen.lock().unwrap().push(format!("{} {}", request_line.trim(), headers.join("; ")));
+
+                let path = request_line.split_whitespace().nth(1).unwrap().to_string();
+                let (status, payload): (&str, Vec<u8>) = if request_line.starts_with("PUT") {
+                    ("201 Created", Vec::new())
+                } else if path.ends_with(".minisig") {
+                    ("404 Not Found", Vec::new())
+                } else if path.ends_with(".tar.gz") {
+                    match range {
+                        Some(start) => ("206 Partial Content", TARBALL[start..].to_vec()),
+                        None => ("200 OK", TARBALL.to_vec()),
+                    }
+                } else if path.starts_with("/api/v1/packages/Demo.Kernel") || path.starts_with("/api/v1/packages?") {
+                    ("200 OK", listing.clone().into_bytes())
+                } else {
+                    ("404 Not Found", Vec::new())
+                };
+
+                let head = format!("HTTP/1.1 {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n", status, payload.len());
+                let stream = reader.get_mut();
+                stream.write_all(head.as_bytes()).unwrap();
+                stream.write_all(&payload).unwrap();
+            }
+        });
+
+        url
+    }
+
+    #[test]
+    fn test_resumes_partial_download_and_verifies_checksum() {
+        let temp_dir = TempDir::new().unwrap();
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let registry = RegistryClient::new(&spawn_registry(3, seen.clone())).unwrap();
+
+        let builds = registry.fetch("Demo.Kernel", "v0.1").unwrap();
+        assert_eq!(builds.len(), 1);
+        assert!(matches!(registry.fetch("Demo.Kernel", "v9"), Err(CkpError::FileNotFound(_))));
+
+        // Half the tarball is already on disk from an interrupted attempt
+        fs::write(temp_dir.path().join(format!("{}.part", builds[0].filename)), &TARBALL[..10]).unwrap();
+        let path = registry.download(&builds[0], temp_dir.path()).unwrap();
+
+        assert_eq!(fs::read(&path).unwrap(), TARBALL);
+        assert!(seen.lock().unwrap()[2].to_ascii_lowercase().contains("range: bytes=10-"));
+
+        let tampered = RegistryPackage { sha256: Some("00".to_string()), ..builds[0].clone() };
+        let err = RegistryClient::new("http://127.0.0.1:9").unwrap()
+            .publish(&tampered, &path)
+            .unwrap_err();
+        assert!(matches!(err, CkpError::AuthenticationFailed(_)));
+    }
+
+    #[test]
+    fn test_package_manager_fetches_latest_and_publishes() {
+        let temp_dir = TempDir::new().unwrap();
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let registry = RegistryClient::new(&spawn_registry(5, seen.clone())).unwrap().with_token("s3cret");
+        let pm = PackageManager::from_cache_dir(temp_dir.path().join("cache")).unwrap().with_registry(registry);
+
+        assert_eq!(pm.search_remote("Demo").unwrap().len(), 2);
+
+        let info = pm.fetch("Demo.Kernel", None).unwrap();
+        assert_eq!(info.version, "v0.2");
+        assert_eq!(info.size_bytes, TARBALL.len() as u64);
+
+        // Already cached: no registry round-trip
+        assert_eq!(pm.fetch("Demo.Kernel", Some("v0.2")).unwrap().filename, info.filename);
+
+        let published = pm.publish("Demo.Kernel", "v0.2").unwrap();
+        assert_eq!(published.len(), 1);
+        assert_eq!(published[0].sha256, Some(hex::encode(Sha256::digest(TARBALL))));
+        let requests = seen.lock().unwrap();
+        assert_eq!(requests.len(), 5);
+        assert!(requests[4].starts_with("PUT /api/v1/packages/Demo.Kernel/v0.2/Demo.Kernel-v0.2.universal.py.tar.gz"));
+        assert!(requests[4].to_ascii_lowercase().contains("authorization: bearer s3cret"));
+    }
+
+    #[test]
+    fn test_reads_fail_over_to_mirrors() {
+        let temp_dir = TempDir::new().unwrap();
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let mirror = spawn_registry(2, seen.clone());
+        let registry = RegistryClient::new("http://127.0.0.1:9").unwrap()
+            .with_mirrors([format!("{}/", mirror), "http://127.0.0.1:9".to_string()])
+            .unwrap();
+        assert_eq!(registry.mirrors(), [mirror]);
+
+        let builds = registry.fetch("Demo.Kernel", "v0.2").unwrap();
+        let path = registry.download(&builds[0], temp_dir.path()).unwrap();
+        assert_eq!(fs::read(path).unwrap(), TARBALL);
+        assert_eq!(seen.lock().unwrap().len(), 2);
+
+        assert!(RegistryClient::new("http://127.0.0.1:9").unwrap().with_mirrors(["ftp://mirror"]).is_err());
+    }
+}
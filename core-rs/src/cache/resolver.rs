@@ -0,0 +1,342 @@
+//! Semver-aware dependency resolution for concept packages
+//!
+//! Package versions are tag-style (`v0.1`, `v1.3.14`) and are read as semver
+//! with missing components filled with zero. Ranges use Cargo syntax
+//! (`^1.2`, `>=0.3, <0.5`, `*`); a leading `v` on the version is accepted.
+//!
+//! Resolution picks the highest version of each package that satisfies every
+//! requirement on it, backtracking when a choice leads to a conflict, and
+//! returns the install set ordered dependencies first.
+
+use super::PackageInfo;
+use crate::errors::{CkpError, Result};
+use semver::{Version, VersionReq};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+
+/// Requirer name used for top-level requests
+pub const ROOT_REQUIRER: &str = "(requested)";
+
+/// Parse a tag-style package version (`v1.2` → 1.2.0)
+pub fn parse_version(version: &str) -> Option<Version> {
+    let trimmed = version.trim().trim_start_matches(['v', 'V']);
+    let (core, rest) = match trimmed.find(['-', '+']) {
+        Some(index) => trimmed.split_at(index),
+        None => (trimmed, ""),
+    };
+
+    let mut parts: Vec<&str> = core.split('.').collect();
+    if parts.is_empty() || parts.len() > 3 || parts.iter().any(|p| p.is_empty()) {
+        return None;
+    }
+    while parts.len() < 3 {
+        parts.push("0");
+    }
+
+    Version::parse(&format!("{}{}", parts.join("."), rest)).ok()
+}
+
+/// Parse a version range (`*`, `latest` and empty match anything)
+pub fn parse_range(range: &str) -> Result<VersionReq> {
+    let range = range.trim();
+    if range.is_empty() || range == "*" || range == "latest" {
+        return Ok(VersionReq::STAR);
+    }
+
+    // Drop tag prefixes inside each comparator: "^v1.2, <v2" -> "^1.2, <2"
+    let normalized: Vec<String> = range
+        .split(',')
+        .map(|comparator| {
+            let comparator = comparator.trim();
+            let split = comparator.find(|c: char| c.is_ascii_digit() || c == 'v' || c == 'V' || c == '*')
+                .unwrap_or(comparator.len());
+            let (op, version) = comparator.split_at(split);
+            format!("{}{}", op.trim(), version.trim_start_matches(['v', 'V']))
+        })
+        .collect();
+
+    VersionReq::parse(&normalized.join(", "))
+        .map_err(|e| CkpError::InvalidVersion(format!("Invalid version range '{}': {}", range, e)))
+}
+
+/// One requirement placed on a package
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Requirement {
+    /// Package that declared the requirement (`ROOT_REQUIRER` for requests)
+    pub required_by: String,
+    pub range: String,
+}
+
+/// Requirements on a package that no available version satisfies
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResolutionConflict {
+    pub package: String,
+    pub requirements: Vec<Requirement>,
+
+    /// Versions that were available, highest first
+    pub available: Vec<String>,
+}
+
+impl fmt::Display for ResolutionConflict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let requirements: Vec<String> = self.requirements.iter()
+            .map(|r| format!("{} requires {}", r.required_by, r.range))
+            .collect();
+        let available = if self.available.is_empty() {
+            "none".to_string()
+        } else {
+            self.available.join(", ")
+        };
+
+        write!(f, "{}: {} (available: {})", self.package, requirements.join("; "), available)
+    }
+}
+
+/// Compatible install set
+#[derive(Debug, Clone)]
+pub struct Resolution {
+    /// Selected packages, dependencies before dependents
+    pub packages: Vec<PackageInfo>,
+}
+
+impl Resolution {
+    /// Selected package by name
+    pub fn get(&self, name: &str) -> Option<&PackageInfo> {
+        self.packages.iter().find(|p| p.name == name)
+    }
+}
+
+/// Resolves requested packages and their dependencies against candidates
+///
+/// # Examples
+///
+/// ```no_run
+/// use ckp_core::cache::{DependencyResolver, PackageManager};
+/// use std::collections::BTreeMap;
+///
+/// let pm = PackageManager::new().unwrap();
+/// let resolver = DependencyResolver::new(pm.list_cached().unwrap());
+///
+/// let mut requests = BTreeMap::new();
+/// requests.insert("System.Gateway.HTTP".to_string(), "^1.2".to_string());
+/// for package in resolver.resolve(&requests).unwrap().packages {
+///     println!("{}@{}", package.name, package.version);
+/// }
+/// ```
+pub struct DependencyResolver {
+    candidates: BTreeMap<String, Vec<(Version, PackageInfo)>>,
+}
+
+type Constraints = BTreeMap<String, Vec<(Requirement, VersionReq)>>;
+
+impl DependencyResolver {
+    /// Create a resolver over available packages
+    ///
+    /// Packages with unparseable versions are ignored. When a version has
+    /// several builds, the first one given wins, so callers should order
+    /// preferred builds (e.g. this system's arch) first.
+    pub fn new(packages: Vec<PackageInfo>) -> Self {
+        let mut candidates: BTreeMap<String, Vec<(Version, PackageInfo)>> = BTreeMap::new();
+
+        for package in packages {
+            if let Some(version) = parse_version(&package.version) {
+                let versions = candidates.entry(package.name.clone()).or_default();
+                if !versions.iter().any(|(v, _)| *v == version) {
+                    versions.push((version, package));
+                }
+            }
+        }
+
+        for versions in candidates.values_mut() {
+            versions.sort_by(|a, b| b.0.cmp(&a.0));
+        }
+
+        Self { candidates }
+    }
+
+    /// Compute a compatible install set for `requests` (name → range)
+    ///
+    /// # Errors
+    /// `CkpError::DependencyConflict` listing the requirements that could not
+    /// be satisfied together; `CkpError::InvalidVersion` for malformed ranges
+    pub fn resolve(&self, requests: &BTreeMap<String, String>) -> Result<Resolution> {
+        let mut constraints = Constraints::new();
+        for (name, range) in requests {
+            let requirement = Requirement { required_by: ROOT_REQUIRER.to_string(), range: range.clone() };
+            constraints.entry(name.clone()).or_default().push((requirement, parse_range(range)?));
+        }
+
+        let mut selected = BTreeMap::new();
+        let mut conflicts = BTreeMap::new();
+
+        if self.search(&mut selected, constraints, &mut conflicts)? {
+            return Ok(Resolution { packages: install_order(selected) });
+        }
+
+        let report: Vec<String> = conflicts.values().map(|c: &ResolutionConflict| c.to_string()).collect();
+        Err(CkpError::DependencyConflict(report.join("\n")))
+    }
+
+    fn search(
+        &self,
+        selected: &mut BTreeMap<String, PackageInfo>,
+        constraints: Constraints,
+        conflicts: &mut BTreeMap<String, ResolutionConflict>,
+    ) -> Result<bool> {
+        let name = match constraints.keys().find(|name| !selected.contains_key(*name)) {
+            Some(name) => name.clone(),
+            None => return Ok(true),
+        };
+
+        let available = self.candidates.get(&name).map(Vec::as_slice).unwrap_or_default();
+        let requirements = &constraints[&name];
+        let matching: Vec<&(Version, PackageInfo)> = available.iter()
+            .filter(|(version, _)| requirements.iter().all(|(_, req)| req.matches(version)))
+            .collect();
+
+        if matching.is_empty() {
+            conflicts.insert(name.clone(), ResolutionConflict {
+                package: name.clone(),
+                requirements: requirements.iter().map(|(r, _)| r.clone()).collect(),
+                available: available.iter().map(|(_, p)| p.version.clone()).collect(),
+            });
+            return Ok(false);
+        }
+
+        'candidates: for (_, package) in matching {
+            let mut next = constraints.clone();
+
+            for (dependency, range) in &package.dependencies {
+                let req = parse_range(range)?;
+                let requirement = Requirement { required_by: format!("{}@{}", package.name, package.version), range: range.clone() };
+
+                // A dependency already chosen must satisfy the new requirement
+                if let Some(chosen) = selected.get(dependency) {
+                    let satisfied = parse_version(&chosen.version).is_some_and(|v| req.matches(&v));
+                    if !satisfied {
+                        let mut requirements: Vec<Requirement> = next.get(dependency)
+                            .map(|reqs| reqs.iter().map(|(r, _)| r.clone()).collect())
+                            .unwrap_or_default();
+                        requirements.push(requirement);
+                        conflicts.insert(dependency.clone(), ResolutionConflict {
+                            package: dependency.clone(),
+                            requirements,
+                            available: vec![chosen.version.clone()],
+                        });
+                        continue 'candidates;
+                    }
+                }
+
+                next.entry(dependency.clone()).or_default().push((requirement, req));
+            }
+
+            selected.insert(name.clone(), package.clone());
+            if self.search(selected, next, conflicts)? {
+                return Ok(true);
+            }
+            selected.remove(&name);
+        }
+
+        Ok(false)
+    }
+}
+
+/// Order packages so dependencies come before dependents
+///
+/// Depth-first from each package in name order; a dependency cycle (e.g.
+/// between bootstrap kernels) is broken where it is first re-entered.
+fn install_order(selected: BTreeMap<String, PackageInfo>) -> Vec<PackageInfo> {
+    fn visit(
+        name: &str,
+        selected: &BTreeMap<String, PackageInfo>,
+        visited: &mut BTreeSet<String>,
+        ordered: &mut Vec<PackageInfo>,
+    ) {
+        if !visited.insert(name.to_string()) {
+            return;
+        }
+
+        let package = &selected[name];
+        for dependency in package.dependencies.keys() {
+            if selected.contains_key(dependency) {
+                visit(dependency, selected, visited, ordered);
+            }
+        }
+        ordered.push(package.clone());
+    }
+
+    let mut visited = BTreeSet::new();
+    let mut ordered = Vec::with_capacity(selected.len());
+    for name in selected.keys() {
+        visit(name, &selected, &mut visited, &mut ordered);
+    }
+
+    ordered
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn package(name: &str, version: &str, dependencies: &[(&str, &str)]) -> PackageInfo {
+        PackageInfo {
+            name: name.to_string(),
+            version: version.to_string(),
+            arch: "universal".to_string(),
+            runtime: "py".to_string(),
+            filename: format!("{}-{}.universal.py.tar.gz", name, version),
+            size_bytes: 0,
+            created_at: "2026-01-01".to_string(),
+            dependencies: dependencies.iter().map(|(n, r)| (n.to_string(), r.to_string())).collect(),
+        }
+    }
+
+    fn requests(items: &[(&str, &str)]) -> BTreeMap<String, String> {
+        items.iter().map(|(n, r)| (n.to_string(), r.to_string())).collect()
+    }
+
+    #[test]
+    fn test_parse_tag_versions_and_ranges() {
+        assert_eq!(parse_version("v0.1"), Some(Version::new(0, 1, 0)));
+        assert_eq!(parse_version("v1.3.14"), Some(Version::new(1, 3, 14)));
+        assert_eq!(parse_version("2"), Some(Version::new(2, 0, 0)));
+        assert_eq!(parse_version("latest"), None);
+
+        assert!(parse_range("^v1.2").unwrap().matches(&Version::new(1, 9, 0)));
+        assert!(!parse_range(">=v0.3, <v0.5").unwrap().matches(&Version::new(0, 5, 0)));
+        assert!(parse_range("*").unwrap().matches(&Version::new(9, 9, 9)));
+        assert!(matches!(parse_range("~>nonsense"), Err(CkpError::InvalidVersion(_))));
+    }
+
+    #[test]
+    fn test_backtracks_to_compatible_set_in_install_order() {
+        let resolver = DependencyResolver::new(vec![
+            package("App", "v2.0", &[("Gateway", "^1.0"), ("Auth", "*")]),
+            package("Auth", "v1.1", &[("Gateway", "^2.0")]),
+            package("Auth", "v1.0", &[("Gateway", "^1.2")]),
+            package("Gateway", "v2.0", &[]),
+            package("Gateway", "v1.4", &[("Core", "v0.1")]),
+            package("Core", "v0.1", &[("Gateway", "*")]),
+        ]);
+
+        let resolution = resolver.resolve(&requests(&[("App", "^2")])).unwrap();
+        let versions: Vec<String> = resolution.packages.iter().map(|p| format!("{}@{}", p.name, p.version)).collect();
+
+        // Auth v1.1 needs Gateway 2.x, which App rules out; the Gateway <-> Core cycle is broken
+        assert_eq!(versions, vec!["Core@v0.1", "Gateway@v1.4", "Auth@v1.0", "App@v2.0"]);
+        assert_eq!(resolution.get("Gateway").unwrap().version, "v1.4");
+    }
+
+    #[test]
+    fn test_conflict_diagnostics() {
+        let resolver = DependencyResolver::new(vec![
+            package("App", "v1.0", &[("Gateway", "^1.0")]),
+            package("Gateway", "v2.0", &[]),
+        ]);
+
+        let err = resolver.resolve(&requests(&[("App", "*"), ("Missing", "^1")])).unwrap_err();
+        let message = err.to_string();
+        assert!(matches!(err, CkpError::DependencyConflict(_)));
+        assert!(message.contains("Gateway: App@v1.0 requires ^1.0 (available: v2.0)"), "{}", message);
+    }
+}
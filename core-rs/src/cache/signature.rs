@@ -0,0 +1,500 @@
+//! Detached package signatures and trust policy
+//!
+//! Signatures use the minisign format, so packages signed with `minisign -S`
+//! verify here and vice versa. A tarball's signature lives next to it as
+//! `{filename}.minisig`; trusted public keys live as minisign `.pub` files in
+//! the trust store (default `~/.config/conceptkernel/trust/`).
+
+use crate::errors::{CkpError, Result};
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use blake2::Blake2b512;
+use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+/// Extension of detached signature files
+pub const SIGNATURE_EXTENSION: &str = "minisig";
+
+/// Environment variable selecting the trust policy (permissive or strict)
+pub const TRUST_POLICY_ENV: &str = "CKP_TRUST_POLICY";
+
+/// Signature algorithm over the raw file
+const ALG_PURE: &[u8; 2] = b"Ed";
+
+/// Signature algorithm over the BLAKE2b-512 digest of the file
+const ALG_PREHASHED: &[u8; 2] = b"ED";
+
+/// How unsigned packages and unknown signers are treated
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum TrustPolicy {
+    /// Verify signatures that are present; allow unsigned or unknown signers with a warning
+    #[default]
+    Permissive,
+
+    /// Require a valid signature from a trusted key
+    Strict,
+}
+
+impl FromStr for TrustPolicy {
+    type Err = CkpError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "permissive" => Ok(TrustPolicy::Permissive),
+            "strict" => Ok(TrustPolicy::Strict),
+            other => Err(CkpError::ValidationError(format!(
+                "Unknown trust policy '{}' (expected permissive or strict)",
+                other
+            ))),
+        }
+    }
+}
+
+/// Outcome of checking a package against the trust store
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SignatureStatus {
+    /// Valid signature from a trusted key
+    Verified { key_id: String, trusted_comment: String },
+
+    /// No signature file next to the package
+    Unsigned,
+
+    /// Signed by a key that is not in the trust store
+    UntrustedKey { key_id: String },
+}
+
+/// Minisign key id formatted as minisign prints it
+fn format_key_id(key_id: &[u8; 8]) -> String {
+    format!("{:016X}", u64::from_le_bytes(*key_id))
+}
+
+/// Public key accepted for package signatures
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TrustedKey {
+    key_id: [u8; 8],
+    public_key: VerifyingKey,
+    comment: String,
+}
+
+impl TrustedKey {
+    /// Parse a minisign public key (`untrusted comment:` line optional)
+    pub fn parse(content: &str) -> Result<Self> {
+        let mut comment = String::new();
+        let mut encoded = None;
+        for line in content.lines().map(str::trim).filter(|l| !l.is_empty()) {
+            match line.strip_prefix("untrusted comment:") {
+                Some(text) => comment = text.trim().to_string(),
+                None => {
+                    encoded = Some(line);
+                    break;
+                }
+            }
+        }
+
+        let bytes = BASE64.decode(encoded.unwrap_or_default())
+            .map_err(|e| CkpError::ParseError(format!("Invalid public key encoding: {}", e)))?;
+        if bytes.len() != 42 || &bytes[..2] != ALG_PURE {
+            return Err(CkpError::ParseError("Not an Ed25519 minisign public key".to_string()));
+        }
+
+        let key_id: [u8; 8] = bytes[2..10].try_into().expect("slice length checked");
+        let key: [u8; 32] = bytes[10..].try_into().expect("slice length checked");
+        let public_key = VerifyingKey::from_bytes(&key)
+            .map_err(|e| CkpError::ParseError(format!("Invalid Ed25519 public key: {}", e)))?;
+
+        Ok(Self { key_id, public_key, comment })
+    }
+
+    /// Key id as minisign prints it (16 uppercase hex digits)
+    pub fn key_id(&self) -> String {
+        format_key_id(&self.key_id)
+    }
+
+    pub fn comment(&self) -> &str {
+        &self.comment
+    }
+
+    /// Serialize in minisign public key format
+    pub fn to_minisign_string(&self) -> String {
+        let mut bytes = ALG_PURE.to_vec();
+        bytes.extend_from_slice(&self.key_id);
+        bytes.extend_from_slice(self.public_key.as_bytes());
+
+        let comment = if self.comment.is_empty() {
+            format!("minisign public key {}", self.key_id())
+        } else {
+            self.comment.clone()
+        };
+        format!("untrusted comment: {}\n{}\n", comment, BASE64.encode(bytes))
+    }
+}
+
+/// Parsed `.minisig` signature
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PackageSignature {
+    prehashed: bool,
+    key_id: [u8; 8],
+    signature: [u8; 64],
+    trusted_comment: String,
+    global_signature: [u8; 64],
+}
+
+impl PackageSignature {
+    /// Parse a minisign signature file
+    pub fn parse(content: &str) -> Result<Self> {
+        let lines: Vec<&str> = content.lines().map(str::trim_end).collect();
+        if lines.len() < 4 || !lines[0].starts_with("untrusted comment:") {
+            return Err(CkpError::ParseError("Malformed minisign signature".to_string()));
+        }
+
+        let bytes = BASE64.decode(lines[1])
+            .map_err(|e| CkpError::ParseError(format!("Invalid signature encoding: {}", e)))?;
+        if bytes.len() != 74 {
+            return Err(CkpError::ParseError("Invalid signature length".to_string()));
+        }
+        let prehashed = match &bytes[..2] {
+            alg if alg == ALG_PREHASHED => true,
+            alg if alg == ALG_PURE => false,
+            _ => return Err(CkpError::ParseError("Unsupported signature algorithm".to_string())),
+        };
+
+        let trusted_comment = lines[2].strip_prefix("trusted comment: ")
+            .ok_or_else(|| CkpError::ParseError("Missing trusted comment".to_string()))?
+            .to_string();
+        let global = BASE64.decode(lines[3])
+            .map_err(|e| CkpError::ParseError(format!("Invalid global signature encoding: {}", e)))?;
+
+        Ok(Self {
+            prehashed,
+            key_id: bytes[2..10].try_into().expect("slice length checked"),
+            signature: bytes[10..].try_into().expect("slice length checked"),
+            trusted_comment,
+            global_signature: global.as_slice().try_into()
+                .map_err(|_| CkpError::ParseError("Invalid global signature length".to_string()))?,
+        })
+    }
+
+    /// Id of the signing key
+    pub fn key_id(&self) -> String {
+        format_key_id(&self.key_id)
+    }
+
+    pub fn trusted_comment(&self) -> &str {
+        &self.trusted_comment
+    }
+
+    /// Verify the signature and its trusted comment against `data`
+    ///
+    /// # Errors
+    /// `CkpError::ValidationError` if the key id differs or either signature is invalid
+    pub fn verify(&self, data: &[u8], key: &TrustedKey) -> Result<()> {
+        if self.key_id != key.key_id {
+            return Err(CkpError::ValidationError(format!(
+                "Signature key {} does not match trusted key {}",
+                self.key_id(),
+                key.key_id()
+            )));
+        }
+
+        let message = if self.prehashed { Blake2b512::digest(data).to_vec() } else { data.to_vec() };
+        key.public_key
+            .verify(&message, &Signature::from_bytes(&self.signature))
+            .map_err(|_| CkpError::ValidationError("Package signature is invalid".to_string()))?;
+
+        let mut global = self.signature.to_vec();
+        global.extend_from_slice(self.trusted_comment.as_bytes());
+        key.public_key
+            .verify(&global, &Signature::from_bytes(&self.global_signature))
+            .map_err(|_| CkpError::ValidationError("Trusted comment signature is invalid".to_string()))
+    }
+
+    /// Serialize in minisign signature format
+    pub fn to_minisign_string(&self) -> String {
+        let mut bytes = if self.prehashed { ALG_PREHASHED.to_vec() } else { ALG_PURE.to_vec() };
+        bytes.extend_from_slice(&self.key_id);
+        bytes.extend_from_slice(&self.signature);
+
+        format!(
+            "untrusted comment: signature from ckp secret key\n{}\ntrusted comment: {}\n{}\n",
+            BASE64.encode(bytes),
+            self.trusted_comment,
+            BASE64.encode(self.global_signature)
+        )
+    }
+}
+
+/// Creates detached package signatures
+pub struct PackageSigner {
+    key: SigningKey,
+    key_id: [u8; 8],
+}
+
+impl PackageSigner {
+    /// Create a signer; the key id is derived from the public key
+    pub fn new(key: SigningKey) -> Self {
+        let digest = Sha256::digest(key.verifying_key().as_bytes());
+        let key_id = digest[..8].try_into().expect("digest is 32 bytes");
+        Self { key, key_id }
+    }
+
+    /// Use an explicit key id (e.g. one assigned by minisign)
+    pub fn with_key_id(mut self, key_id: [u8; 8]) -> Self {
+        self.key_id = key_id;
+        self
+    }
+
+    /// Public half, for adding to a trust store
+    pub fn trusted_key(&self) -> TrustedKey {
+        TrustedKey {
+            key_id: self.key_id,
+            public_key: self.key.verifying_key(),
+            comment: format!("ckp public key {}", format_key_id(&self.key_id)),
+        }
+    }
+
+    /// Sign data (BLAKE2b-prehashed, as minisign does by default)
+    pub fn sign(&self, data: &[u8], trusted_comment: &str) -> PackageSignature {
+        let signature = self.key.sign(&Blake2b512::digest(data)).to_bytes();
+
+        let mut global = signature.to_vec();
+        global.extend_from_slice(trusted_comment.as_bytes());
+
+        PackageSignature {
+            prehashed: true,
+            key_id: self.key_id,
+            signature,
+            trusted_comment: trusted_comment.to_string(),
+            global_signature: self.key.sign(&global).to_bytes(),
+        }
+    }
+
+    /// Write `{tarball}.minisig` next to a package
+    ///
+    /// # Returns
+    /// Path of the signature file
+    pub fn sign_file(&self, tarball: &Path) -> Result<PathBuf> {
+        let data = fs::read(tarball)
+            .map_err(|e| CkpError::IoError(format!("Failed to read {}: {}", tarball.display(), e)))?;
+        let filename = tarball.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+        let comment = format!("timestamp:{}\tfile:{}", chrono::Utc::now().timestamp(), filename);
+
+        let path = signature_path(tarball);
+        fs::write(&path, self.sign(&data, &comment).to_minisign_string())
+            .map_err(|e| CkpError::IoError(format!("Failed to write signature: {}", e)))?;
+
+        Ok(path)
+    }
+}
+
+/// Path of a package's detached signature
+pub fn signature_path(tarball: &Path) -> PathBuf {
+    let mut name = tarball.file_name().map(|n| n.to_os_string()).unwrap_or_default();
+    name.push(format!(".{}", SIGNATURE_EXTENSION));
+    tarball.with_file_name(name)
+}
+
+/// Directory of trusted public keys
+#[derive(Debug, Clone, Default)]
+pub struct TrustStore {
+    dir: PathBuf,
+    keys: Vec<TrustedKey>,
+}
+
+impl TrustStore {
+    /// Default trust store: ~/.config/conceptkernel/trust/
+    pub fn default_dir() -> Result<PathBuf> {
+        let home_dir = env::var("HOME").map_err(|_| {
+            CkpError::IoError("HOME environment variable not set".to_string())
+        })?;
+
+        Ok(PathBuf::from(home_dir).join(".config").join("conceptkernel").join("trust"))
+    }
+
+    /// Load every `.pub` key in a directory (missing directory = empty store)
+    pub fn load(dir: PathBuf) -> Result<Self> {
+        let mut keys = Vec::new();
+
+        if dir.exists() {
+            let mut paths: Vec<PathBuf> = fs::read_dir(&dir)
+                .map_err(|e| CkpError::IoError(format!("Failed to read trust store: {}", e)))?
+                .filter_map(|entry| entry.ok().map(|e| e.path()))
+                .filter(|path| path.extension().is_some_and(|ext| ext == "pub"))
+                .collect();
+            paths.sort();
+
+            for path in paths {
+                let content = fs::read_to_string(&path)
+                    .map_err(|e| CkpError::IoError(format!("Failed to read {}: {}", path.display(), e)))?;
+                keys.push(TrustedKey::parse(&content)?);
+            }
+        }
+
+        Ok(Self { dir, keys })
+    }
+
+    pub fn keys(&self) -> &[TrustedKey] {
+        &self.keys
+    }
+
+    /// Trusted key with the given id
+    pub fn find(&self, key_id: &str) -> Option<&TrustedKey> {
+        self.keys.iter().find(|k| k.key_id().eq_ignore_ascii_case(key_id))
+    }
+
+    /// Trust a key, writing it as `{key_id}.pub`
+    pub fn add(&mut self, key: TrustedKey) -> Result<PathBuf> {
+        fs::create_dir_all(&self.dir)
+            .map_err(|e| CkpError::IoError(format!("Failed to create trust store: {}", e)))?;
+
+        let path = self.dir.join(format!("{}.pub", key.key_id()));
+        fs::write(&path, key.to_minisign_string())
+            .map_err(|e| CkpError::IoError(format!("Failed to write trusted key: {}", e)))?;
+
+        self.keys.retain(|k| k.key_id != key.key_id);
+        self.keys.push(key);
+        Ok(path)
+    }
+
+    /// Stop trusting a key
+    ///
+    /// # Returns
+    /// Whether the key was present
+    pub fn remove(&mut self, key_id: &str) -> Result<bool> {
+        let before = self.keys.len();
+        self.keys.retain(|k| !k.key_id().eq_ignore_ascii_case(key_id));
+
+        let path = self.dir.join(format!("{}.pub", key_id.to_ascii_uppercase()));
+        if path.exists() {
+            fs::remove_file(&path)
+                .map_err(|e| CkpError::IoError(format!("Failed to remove trusted key: {}", e)))?;
+        }
+
+        Ok(self.keys.len() < before)
+    }
+
+    /// Check a package's detached signature
+    ///
+    /// # Errors
+    /// `CkpError::ValidationError` if a signature is present but invalid
+    pub fn check(&self, tarball: &Path) -> Result<SignatureStatus> {
+        let sig_path = signature_path(tarball);
+        if !sig_path.exists() {
+            return Ok(SignatureStatus::Unsigned);
+        }
+
+        let signature = PackageSignature::parse(&fs::read_to_string(&sig_path)
+            .map_err(|e| CkpError::IoError(format!("Failed to read signature: {}", e)))?)?;
+
+        let key = match self.find(&signature.key_id()) {
+            Some(key) => key,
+            None => return Ok(SignatureStatus::UntrustedKey { key_id: signature.key_id() }),
+        };
+
+        let data = fs::read(tarball)
+            .map_err(|e| CkpError::IoError(format!("Failed to read {}: {}", tarball.display(), e)))?;
+        signature.verify(&data, key)?;
+
+        Ok(SignatureStatus::Verified {
+            key_id: signature.key_id(),
+            trusted_comment: signature.trusted_comment,
+        })
+    }
+
+    /// Check a package and apply a trust policy
+    ///
+    /// # Errors
+    /// `CkpError::AuthenticationFailed` if the policy rejects the package,
+    /// `CkpError::ValidationError` if its signature is invalid
+    pub fn enforce(&self, tarball: &Path, policy: TrustPolicy) -> Result<SignatureStatus> {
+        let status = self.check(tarball)?;
+        let filename = tarball.display();
+
+        match (&status, policy) {
+            (SignatureStatus::Verified { .. }, _) => {}
+            (SignatureStatus::Unsigned, TrustPolicy::Strict) => {
+                return Err(CkpError::AuthenticationFailed(format!("Package is unsigned: {}", filename)));
+            }
+            (SignatureStatus::UntrustedKey { key_id }, TrustPolicy::Strict) => {
+                return Err(CkpError::AuthenticationFailed(format!(
+                    "Package {} is signed by untrusted key {}",
+                    filename, key_id
+                )));
+            }
+            (SignatureStatus::Unsigned, TrustPolicy::Permissive) => {
+                tracing::warn!(package = %filename, "package is unsigned");
+            }
+            (SignatureStatus::UntrustedKey { key_id }, TrustPolicy::Permissive) => {
+                tracing::warn!(package = %filename, key_id = %key_id, "package is signed by untrusted key");
+            }
+        }
+
+        Ok(status)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn signer() -> PackageSigner {
+        PackageSigner::new(SigningKey::from_bytes(&[7u8; 32]))
+    }
+
+    #[test]
+    fn test_sign_and_enforce_policy() {
+        let temp_dir = TempDir::new().unwrap();
+        let tarball = temp_dir.path().join("Demo.Kernel-v0.1.universal.py.tar.gz");
+        fs::write(&tarball, b"tarball bytes").unwrap();
+
+        let mut store = TrustStore::load(temp_dir.path().join("trust")).unwrap();
+
+        // Unsigned: allowed only when permissive
+        assert_eq!(store.enforce(&tarball, TrustPolicy::Permissive).unwrap(), SignatureStatus::Unsigned);
+        assert!(matches!(store.enforce(&tarball, TrustPolicy::Strict), Err(CkpError::AuthenticationFailed(_))));
+
+        let signer = signer();
+        signer.sign_file(&tarball).unwrap();
+        assert!(matches!(store.check(&tarball).unwrap(), SignatureStatus::UntrustedKey { .. }));
+        assert!(store.enforce(&tarball, TrustPolicy::Strict).is_err());
+
+        // Trusted keys persist as minisign .pub files
+        store.add(signer.trusted_key()).unwrap();
+        let store = TrustStore::load(temp_dir.path().join("trust")).unwrap();
+        assert_eq!(store.keys().len(), 1);
+        match store.enforce(&tarball, TrustPolicy::Strict).unwrap() {
+            SignatureStatus::Verified { key_id, trusted_comment } => {
+                assert_eq!(key_id, signer.trusted_key().key_id());
+                assert!(trusted_comment.ends_with("file:Demo.Kernel-v0.1.universal.py.tar.gz"));
+            }
+            other => panic!("unexpected status {:?}", other),
+        }
+
+        // Tampered package fails under any policy
+        fs::write(&tarball, b"tampered bytes").unwrap();
+        assert!(matches!(store.enforce(&tarball, TrustPolicy::Permissive), Err(CkpError::ValidationError(_))));
+    }
+
+    #[test]
+    fn test_minisign_format_round_trip() {
+        let signer = signer().with_key_id(*b"\x01\x02\x03\x04\x05\x06\x07\x08");
+        let key = TrustedKey::parse(&signer.trusted_key().to_minisign_string()).unwrap();
+        assert_eq!(key.key_id(), "0807060504030201");
+
+        let signature = signer.sign(b"data", "timestamp:0");
+        let parsed = PackageSignature::parse(&signature.to_minisign_string()).unwrap();
+        assert_eq!(parsed, signature);
+        assert!(parsed.verify(b"data", &key).is_ok());
+        assert!(parsed.verify(b"other", &key).is_err());
+
+        // Trusted comment is covered by the global signature
+        let forged = signature.to_minisign_string().replace("timestamp:0", "timestamp:1");
+        assert!(PackageSignature::parse(&forged).unwrap().verify(b"data", &key).is_err());
+    }
+}
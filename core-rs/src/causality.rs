@@ -0,0 +1,260 @@
+//! Causality across jobs, edge requests and instances
+//!
+//! Jobs, edge requests and receipts name the transaction that directly
+//! caused them (`causationId`) and the transaction that started their chain
+//! (`correlationId`). The runtime fills both in:
+//!
+//! - Writing a job without a cause links it to the current cause, which the
+//!   governor hands to the tool process it spawns for a job through
+//!   `CK_CAUSATION_ID` and `CK_CORRELATION_ID`; a job with no cause at all
+//!   starts its own chain.
+//! - Instances minted by that tool record the same cause in their receipts.
+//! - Edge requests are caused by the instance they notify about and stay
+//!   in its chain.
+//!
+//! `caused_by` and `correlated_with` read the records back from a project's
+//! queues and storage, answering "what did transaction X lead to".
+
+use crate::drivers::{Encoding, JobStamp};
+use crate::errors::Result;
+use crate::storage::shard;
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::{HashSet, VecDeque};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Environment variable carrying the causing transaction into tool processes
+pub const CAUSATION_ENV: &str = "CK_CAUSATION_ID";
+
+/// Environment variable carrying the correlation ID into tool processes
+pub const CORRELATION_ENV: &str = "CK_CORRELATION_ID";
+
+/// Transaction that work is being done on behalf of
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cause {
+    /// Transaction ID of the cause
+    pub tx_id: String,
+
+    /// Correlation ID of the chain the cause belongs to
+    pub correlation_id: String,
+}
+
+impl Cause {
+    /// Cause of work done for a job: the job itself, continuing its chain
+    pub fn of_job(tx_id: &str, stamp: &JobStamp) -> Self {
+        Self {
+            tx_id: tx_id.to_string(),
+            correlation_id: stamp.correlation_id.clone().unwrap_or_else(|| tx_id.to_string()),
+        }
+    }
+
+    /// Cause of work done for an instance, continuing the chain in its receipt
+    pub fn of_instance(inst_dir: &Path) -> Option<Self> {
+        let tx_id = inst_dir.file_name()?.to_str()?.strip_suffix(".inst")?;
+        let correlation_id = Encoding::find_receipt(inst_dir)
+            .and_then(|(encoding, path)| encoding.decode::<Value>(&fs::read(path).ok()?).ok())
+            .and_then(|receipt| receipt.get("correlationId")?.as_str().map(|s| s.to_string()));
+
+        Some(Self {
+            tx_id: tx_id.to_string(),
+            correlation_id: correlation_id.unwrap_or_else(|| tx_id.to_string()),
+        })
+    }
+
+    /// Environment variables handing the cause to a tool process
+    pub fn env_vars(&self) -> [(&'static str, &str); 2] {
+        [(CAUSATION_ENV, &self.tx_id), (CORRELATION_ENV, &self.correlation_id)]
+    }
+}
+
+/// Cause of the work done now
+///
+/// Set for tools spawned by a governor to process a single job.
+pub fn current_cause() -> Option<Cause> {
+    let tx_id = std::env::var(CAUSATION_ENV).ok().filter(|tx_id| !tx_id.is_empty())?;
+    let correlation_id = std::env::var(CORRELATION_ENV)
+        .ok()
+        .filter(|id| !id.is_empty())
+        .unwrap_or_else(|| tx_id.clone());
+    Some(Cause { tx_id, correlation_id })
+}
+
+/// Kind of file a causal record was read from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum CausalKind {
+    Job,
+    EdgeRequest,
+    Instance,
+}
+
+/// Job, edge request or instance with its place in a chain
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CausalRecord {
+    pub kind: CausalKind,
+
+    /// Kernel (or edge kernel) holding the file
+    pub kernel: String,
+
+    pub tx_id: String,
+    pub causation_id: Option<String>,
+    pub correlation_id: Option<String>,
+    pub path: PathBuf,
+}
+
+/// Everything a transaction led to, directly or through other transactions
+///
+/// Records are in breadth-first order: direct effects first.
+pub fn caused_by(project: &Path, tx_id: &str) -> Result<Vec<CausalRecord>> {
+    let records = collect_records(project)?;
+
+    let mut seen = HashSet::from([tx_id.to_string()]);
+    let mut frontier = VecDeque::from([tx_id.to_string()]);
+    let mut caused = Vec::new();
+    while let Some(cause) = frontier.pop_front() {
+        for record in records.iter().filter(|r| r.causation_id.as_deref() == Some(cause.as_str())) {
+            if seen.insert(record.tx_id.clone()) {
+                frontier.push_back(record.tx_id.clone());
+            }
+            if !caused.contains(record) {
+                caused.push(record.clone());
+            }
+        }
+    }
+    Ok(caused)
+}
+
+/// Everything recorded in a chain, sorted by transaction ID
+pub fn correlated_with(project: &Path, correlation_id: &str) -> Result<Vec<CausalRecord>> {
+    let mut records: Vec<CausalRecord> = collect_records(project)?
+        .into_iter()
+        .filter(|record| record.correlation_id.as_deref() == Some(correlation_id))
+        .collect();
+    records.sort_by(|a, b| a.tx_id.cmp(&b.tx_id).then_with(|| a.path.cmp(&b.path)));
+    Ok(records)
+}
+
+/// Causal records of every kernel and edge kernel in a project
+pub fn collect_records(project: &Path) -> Result<Vec<CausalRecord>> {
+    let concepts = project.join("concepts");
+    let mut kernel_dirs: Vec<PathBuf> = child_dirs(&concepts)
+        .into_iter()
+        .filter(|dir| dir.file_name().is_some_and(|name| name != ".edges"))
+        .collect();
+    kernel_dirs.extend(child_dirs(&concepts.join(".edges")));
+    kernel_dirs.sort();
+
+    let mut records = Vec::new();
+    for kernel_dir in kernel_dirs {
+        let kernel = kernel_dir.file_name().unwrap_or_default().to_string_lossy().to_string();
+
+        // Jobs and edge requests in any queue; routed instances are symlinks
+        for entry in walkdir::WalkDir::new(kernel_dir.join("queue")).follow_links(false).into_iter().flatten() {
+            if !entry.file_type().is_file() {
+                continue;
+            }
+            let name = entry.file_name().to_string_lossy();
+            let record = if let Some(request_tx) = name.strip_suffix(".edgereq") {
+                read_stamped(entry.path(), Encoding::Json)
+                    .map(|value| record_from(CausalKind::EdgeRequest, &kernel, request_tx, &value, entry.path()))
+            } else if let Some((encoding, job_tx)) = Encoding::parse_job_file_name(&name) {
+                read_stamped(entry.path(), encoding).map(|value| {
+                    let tx_id = value.get("txId").and_then(|v| v.as_str()).unwrap_or(job_tx).to_string();
+                    record_from(CausalKind::Job, &kernel, &tx_id, &value, entry.path())
+                })
+            } else {
+                None
+            };
+            records.extend(record);
+        }
+
+        for inst_dir in shard::list_entries(&kernel_dir.join("storage")) {
+            let Some(tx_id) = inst_dir.file_name().and_then(|n| n.to_str()).and_then(|n| n.strip_suffix(".inst")) else {
+                continue;
+            };
+            let receipt = Encoding::find_receipt(&inst_dir).and_then(|(encoding, path)| read_stamped(&path, encoding));
+            if let Some(receipt) = receipt {
+                records.push(record_from(CausalKind::Instance, &kernel, tx_id, &receipt, &inst_dir));
+            }
+        }
+    }
+    Ok(records)
+}
+
+fn read_stamped(path: &Path, encoding: Encoding) -> Option<Value> {
+    encoding.decode(&fs::read(path).ok()?).ok()
+}
+
+fn record_from(kind: CausalKind, kernel: &str, tx_id: &str, value: &Value, path: &Path) -> CausalRecord {
+    let field = |key: &str| value.get(key).and_then(|v| v.as_str()).map(|s| s.to_string());
+    CausalRecord {
+        kind,
+        kernel: kernel.to_string(),
+        tx_id: tx_id.to_string(),
+        causation_id: field("causationId"),
+        correlation_id: field("correlationId"),
+        path: path.to_path_buf(),
+    }
+}
+
+fn child_dirs(dir: &Path) -> Vec<PathBuf> {
+    fs::read_dir(dir)
+        .map(|entries| entries.flatten().map(|e| e.path()).filter(|p| p.is_dir()).collect())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::drivers::{FileSystemDriver, JobFileBuilder, StorageDriver};
+    use serde_json::json;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_chain_from_job_to_instance_to_follow_up() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path();
+        for kernel in ["Orders", "Billing", "Shipping"] {
+            fs::create_dir_all(root.join("concepts").join(kernel).join("queue/inbox")).unwrap();
+        }
+        let driver = FileSystemDriver::new(root.to_path_buf(), "Orders".to_string());
+
+        // An external job starts its own chain
+        let order = JobFileBuilder::new("Orders", json!({})).with_tx_id("1000-00000001").build().unwrap();
+        driver.write_job("Orders", order.clone()).unwrap();
+
+        // Orders processes it: the instance and the follow-up carry its cause
+        let cause = Cause::of_job(&order.tx_id, &order.stamp);
+        assert_eq!(cause.correlation_id, "1000-00000001");
+        let evidence = json!({"causationId": cause.tx_id, "correlationId": cause.correlation_id});
+        StorageDriver::mint_storage_artifact(&driver, "Orders", "1001-00000002", evidence).unwrap();
+        let inst_dir = root.join("concepts/Orders/storage/1001-00000002.inst");
+        let from_instance = Cause::of_instance(&inst_dir).unwrap();
+        assert_eq!(from_instance.correlation_id, "1000-00000001");
+
+        let invoice = JobFileBuilder::new("Billing", json!({}))
+            .with_tx_id("1002-00000003")
+            .caused_by(&from_instance.tx_id, Some(&from_instance.correlation_id))
+            .build()
+            .unwrap();
+        driver.write_job("Billing", invoice).unwrap();
+        let unrelated = JobFileBuilder::new("Shipping", json!({})).with_tx_id("1003-00000004").build().unwrap();
+        driver.write_job("Shipping", unrelated).unwrap();
+
+        let caused: Vec<(CausalKind, String)> = caused_by(root, "1000-00000001")
+            .unwrap()
+            .into_iter()
+            .map(|record| (record.kind, record.tx_id))
+            .collect();
+        assert_eq!(
+            caused,
+            vec![(CausalKind::Instance, "1001-00000002".to_string()), (CausalKind::Job, "1002-00000003".to_string())]
+        );
+
+        let chain = correlated_with(root, "1000-00000001").unwrap();
+        assert_eq!(chain.len(), 3);
+        assert!(chain.iter().all(|record| record.kernel != "Shipping"));
+    }
+}
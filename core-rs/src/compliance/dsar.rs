@@ -0,0 +1,549 @@
+//! Data subject access request (DSAR) workflow
+//!
+//! A DSAR is planned as a [`Workflow`]: starting from entry kernels it follows
+//! edges to every downstream kernel, adding one query phase per kernel. Running
+//! the workflow queries each kernel's storage and archived jobs for the
+//! subject, aggregates the results into [`DataAccessResult`] fragments and
+//! writes the final access package next to the request.
+//!
+//! Requests are persisted as `System.Compliance` instances and carry the
+//! statutory deadline (one month, extendable once by up to two more months,
+//! GDPR Art. 12(3)).
+
+use super::erasure::{artifact_files, ErasureEngine, COMPLIANCE_KERNEL, DEFAULT_SUBJECT_FIELDS};
+use super::{ConsentRecord, DataAccessResult, RedactionRules};
+use crate::edge::EdgeKernel;
+use crate::errors::{CkpError, Result};
+use crate::workflow::{PhaseStatus, Workflow, WorkflowEdge, WorkflowPhase, WorkflowStatus, WorkflowTrigger};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value as JsonValue;
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
+use std::fs;
+use std::path::PathBuf;
+
+/// Days to answer a request
+pub const DSAR_DEADLINE_DAYS: i64 = 30;
+
+/// Maximum extension of the deadline, in days
+pub const DSAR_MAX_EXTENSION_DAYS: i64 = 60;
+
+/// Phase name of per-kernel queries
+pub const QUERY_PHASE: &str = "dsar.query";
+
+/// Phase name of the final package
+pub const PACKAGE_PHASE: &str = "dsar.package";
+
+/// A tracked access request
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DsarRequest {
+    pub id: String,
+
+    /// Instance URN (`ckp://System.Compliance#storage/{id}`)
+    pub urn: String,
+
+    pub user_id: String,
+
+    pub received_at: DateTime<Utc>,
+
+    pub deadline: DateTime<Utc>,
+
+    /// Why the deadline was extended, if it was
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub extension_reason: Option<String>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub completed_at: Option<DateTime<Utc>>,
+
+    /// Planned fan-out: one query phase per kernel, then the package phase
+    pub workflow: Workflow,
+}
+
+impl DsarRequest {
+    /// Whether the deadline passed without the request being completed
+    pub fn is_overdue(&self) -> bool {
+        self.completed_at.is_none() && Utc::now() > self.deadline
+    }
+
+    /// Whole days until the deadline (negative once overdue)
+    pub fn days_remaining(&self) -> i64 {
+        (self.deadline - Utc::now()).num_days()
+    }
+
+    /// Kernels queried by this request, in fan-out order
+    pub fn kernels(&self) -> Vec<String> {
+        self.workflow
+            .phases
+            .iter()
+            .filter(|phase| phase.phase_name == QUERY_PHASE)
+            .map(|phase| kernel_name(&phase.kernel_urn).to_string())
+            .collect()
+    }
+}
+
+/// Final access package delivered to the subject
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DsarPackage {
+    pub request_id: String,
+
+    pub user_id: String,
+
+    pub generated_at: DateTime<Utc>,
+
+    pub deadline: DateTime<Utc>,
+
+    /// Kernels queried, in fan-out order
+    pub kernels: Vec<String>,
+
+    /// One result per kernel holding data about the subject
+    pub results: Vec<DataAccessResult>,
+
+    /// Consent state on record, if any
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub consent: Option<ConsentRecord>,
+}
+
+/// DSAR planner and executor
+///
+/// # Examples
+///
+/// ```no_run
+/// use ckp_core::compliance::{DsarWorkflow, GdprChecker};
+/// use std::path::PathBuf;
+///
+/// let workflow = DsarWorkflow::new(PathBuf::from("/project"))
+///     .with_entry_kernels(vec!["Shop.Signup".to_string()]);
+///
+/// let package = GdprChecker::new().data_subject_access_request("user123", &workflow).unwrap();
+/// println!("{} kernels hold data", package.results.len());
+///
+/// for request in workflow.overdue().unwrap() {
+///     eprintln!("DSAR {} is overdue", request.id);
+/// }
+/// ```
+pub struct DsarWorkflow {
+    project_root: PathBuf,
+    entry_kernels: Vec<String>,
+    subject_fields: Vec<String>,
+    deadline_days: i64,
+}
+
+impl DsarWorkflow {
+    /// Create a workflow over a project; every kernel is an entry by default
+    pub fn new(project_root: PathBuf) -> Self {
+        Self {
+            project_root,
+            entry_kernels: Vec::new(),
+            subject_fields: DEFAULT_SUBJECT_FIELDS.iter().map(|s| s.to_string()).collect(),
+            deadline_days: DSAR_DEADLINE_DAYS,
+        }
+    }
+
+    /// Start the fan-out from these kernels only (others are reached via edges)
+    pub fn with_entry_kernels(mut self, kernels: Vec<String>) -> Self {
+        self.entry_kernels = kernels;
+        self
+    }
+
+    /// Replace the dotted field paths checked for the subject identifier
+    pub fn with_subject_fields(mut self, fields: Vec<String>) -> Self {
+        self.subject_fields = fields;
+        self
+    }
+
+    /// Set the response deadline in days
+    pub fn with_deadline_days(mut self, days: i64) -> Self {
+        self.deadline_days = days;
+        self
+    }
+
+    /// Register a request and plan its fan-out
+    pub fn open(&self, user_id: &str) -> Result<DsarRequest> {
+        let id = format!(
+            "dsar-{}-{}",
+            Utc::now().timestamp_millis(),
+            &uuid::Uuid::new_v4().to_string()[..8]
+        );
+        let received_at = Utc::now();
+
+        let (kernels, edges) = self.plan()?;
+        let mut phases: Vec<WorkflowPhase> = kernels
+            .iter()
+            .map(|kernel| phase(QUERY_PHASE, kernel))
+            .collect();
+        phases.push(phase(PACKAGE_PHASE, COMPLIANCE_KERNEL));
+
+        let request = DsarRequest {
+            urn: format!("ckp://{}#storage/{}", COMPLIANCE_KERNEL, id),
+            user_id: user_id.to_string(),
+            received_at,
+            deadline: received_at + Duration::days(self.deadline_days),
+            extension_reason: None,
+            completed_at: None,
+            workflow: Workflow {
+                workflow_urn: format!("ckp://Workflow.DSAR#{}", id),
+                label: format!("DSAR {}", id),
+                description: "Data subject access request".to_string(),
+                version: "1.0".to_string(),
+                trigger: WorkflowTrigger::OnActionRequest,
+                phases,
+                edges,
+                status: WorkflowStatus::Pending,
+            },
+            id,
+        };
+
+        self.save(&request)?;
+        Ok(request)
+    }
+
+    /// Load a request by ID
+    pub fn load(&self, id: &str) -> Result<DsarRequest> {
+        let path = self.instance_dir(id).join("payload.json");
+        if !path.exists() {
+            return Err(CkpError::FileNotFound(format!("DSAR not found: {}", id)));
+        }
+
+        Ok(serde_json::from_str(&fs::read_to_string(path)?)?)
+    }
+
+    /// All requests, earliest deadline first
+    pub fn list(&self) -> Result<Vec<DsarRequest>> {
+        let storage = self.project_root.join("concepts").join(COMPLIANCE_KERNEL).join("storage");
+        if !storage.exists() {
+            return Ok(Vec::new());
+        }
+
+        let mut requests = Vec::new();
+        for entry in fs::read_dir(&storage)? {
+            let name = entry?.file_name().to_string_lossy().to_string();
+            if let Some(id) = name.strip_suffix(".inst").filter(|id| id.starts_with("dsar-")) {
+                requests.push(self.load(id)?);
+            }
+        }
+        requests.sort_by_key(|request| request.deadline);
+
+        Ok(requests)
+    }
+
+    /// Open requests past their deadline
+    pub fn overdue(&self) -> Result<Vec<DsarRequest>> {
+        Ok(self.list()?.into_iter().filter(|request| request.is_overdue()).collect())
+    }
+
+    /// Extend a request's deadline
+    ///
+    /// # Errors
+    /// `CkpError::ValidationError` if the request was already extended or the
+    /// extension exceeds [`DSAR_MAX_EXTENSION_DAYS`]
+    pub fn extend_deadline(&self, request: &mut DsarRequest, days: i64, reason: &str) -> Result<()> {
+        if request.extension_reason.is_some() {
+            return Err(CkpError::ValidationError(format!("DSAR {} was already extended", request.id)));
+        }
+        if !(1..=DSAR_MAX_EXTENSION_DAYS).contains(&days) {
+            return Err(CkpError::ValidationError(format!(
+                "DSAR extension must be 1-{} days, got {}",
+                DSAR_MAX_EXTENSION_DAYS, days
+            )));
+        }
+
+        request.deadline += Duration::days(days);
+        request.extension_reason = Some(reason.to_string());
+        self.save(request)
+    }
+
+    /// Run the planned phases and write the access package
+    ///
+    /// The request is persisted after each phase, so progress survives a
+    /// failure; a failed kernel query fails the whole request rather than
+    /// producing an incomplete package.
+    pub fn execute(
+        &self,
+        request: &mut DsarRequest,
+        consent: Option<ConsentRecord>,
+        redaction: Option<&RedactionRules>,
+    ) -> Result<DsarPackage> {
+        let engine = ErasureEngine::new(self.project_root.join("concepts"))
+            .with_subject_fields(self.subject_fields.clone());
+        let mut results = Vec::new();
+
+        request.workflow.status = WorkflowStatus::InProgress;
+        for index in 0..request.workflow.phases.len() {
+            if request.workflow.phases[index].phase_name != QUERY_PHASE
+                || request.workflow.phases[index].status == PhaseStatus::Completed
+            {
+                continue;
+            }
+
+            let kernel = kernel_name(&request.workflow.phases[index].kernel_urn).to_string();
+            request.workflow.phases[index].status = PhaseStatus::InProgress;
+            request.workflow.phases[index].started_at = Some(Utc::now().to_rfc3339());
+
+            match self.query_kernel(&engine, &kernel, &request.user_id, redaction) {
+                Ok(fragments) => {
+                    if !fragments.is_empty() {
+                        results.push(DataAccessResult {
+                            user_id: request.user_id.clone(),
+                            data: fragments,
+                            timestamp: Utc::now(),
+                        });
+                    }
+                    request.workflow.phases[index].status = PhaseStatus::Completed;
+                    request.workflow.phases[index].completed_at = Some(Utc::now().to_rfc3339());
+                    self.save(request)?;
+                }
+                Err(e) => {
+                    request.workflow.phases[index].status = PhaseStatus::Failed;
+                    request.workflow.status = WorkflowStatus::Failed;
+                    self.save(request)?;
+                    return Err(e);
+                }
+            }
+        }
+
+        let package = DsarPackage {
+            request_id: request.id.clone(),
+            user_id: request.user_id.clone(),
+            generated_at: Utc::now(),
+            deadline: request.deadline,
+            kernels: request.kernels(),
+            results,
+            consent,
+        };
+        fs::write(
+            self.instance_dir(&request.id).join("package.json"),
+            serde_json::to_string_pretty(&package)?,
+        )
+        .map_err(|e| CkpError::IoError(format!("Failed to write DSAR package: {}", e)))?;
+
+        for phase in request.workflow.phases.iter_mut().filter(|p| p.phase_name == PACKAGE_PHASE) {
+            phase.status = PhaseStatus::Completed;
+            phase.completed_at = Some(package.generated_at.to_rfc3339());
+        }
+        request.workflow.status = WorkflowStatus::Completed;
+        request.completed_at = Some(package.generated_at);
+        self.save(request)?;
+
+        Ok(package)
+    }
+
+    /// Kernels to query (entries, then edge targets breadth-first) and the
+    /// edges that reached them
+    fn plan(&self) -> Result<(Vec<String>, Vec<WorkflowEdge>)> {
+        let concepts = self.project_root.join("concepts");
+        let existing: BTreeSet<String> = if concepts.exists() {
+            fs::read_dir(&concepts)?
+                .filter_map(|entry| entry.ok())
+                .filter(|entry| entry.path().is_dir())
+                .map(|entry| entry.file_name().to_string_lossy().to_string())
+                .filter(|name| !name.starts_with('.') && name != COMPLIANCE_KERNEL)
+                .collect()
+        } else {
+            BTreeSet::new()
+        };
+
+        let mut outgoing: BTreeMap<String, Vec<(String, String, String)>> = BTreeMap::new();
+        for edge in EdgeKernel::new(self.project_root.clone())?.list_all_edges()? {
+            outgoing.entry(edge.source.clone()).or_default().push((edge.urn, edge.predicate, edge.target));
+        }
+
+        let mut queue: VecDeque<String> = if self.entry_kernels.is_empty() {
+            existing.iter().cloned().collect()
+        } else {
+            self.entry_kernels.iter().cloned().collect()
+        };
+        let mut visited: BTreeSet<String> = queue.iter().cloned().collect();
+        let mut kernels = Vec::new();
+        let mut edges = Vec::new();
+
+        while let Some(kernel) = queue.pop_front() {
+            for (urn, predicate, target) in outgoing.get(&kernel).into_iter().flatten() {
+                if target == COMPLIANCE_KERNEL {
+                    continue;
+                }
+                edges.push(WorkflowEdge {
+                    edge_urn: urn.clone(),
+                    source: kernel.clone(),
+                    target: target.clone(),
+                    predicate: predicate.clone(),
+                    trigger: "dsar".to_string(),
+                    action: Some(QUERY_PHASE.to_string()),
+                });
+                if visited.insert(target.clone()) {
+                    queue.push_back(target.clone());
+                }
+            }
+            kernels.push(kernel);
+        }
+
+        Ok((kernels, edges))
+    }
+
+    /// Data fragments about the subject held by one kernel
+    fn query_kernel(
+        &self,
+        engine: &ErasureEngine,
+        kernel: &str,
+        user_id: &str,
+        redaction: Option<&RedactionRules>,
+    ) -> Result<Vec<JsonValue>> {
+        let concepts = self.project_root.join("concepts");
+        let mut fragments = Vec::new();
+
+        // Kernels reached through edges may not exist locally
+        if !concepts.join(kernel).exists() {
+            return Ok(fragments);
+        }
+
+        for artifact in engine.find_in_kernel(kernel, user_id)? {
+            let mut data = serde_json::Map::new();
+            for file in artifact_files(&artifact)? {
+                let name = file.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+                let content = fs::read_to_string(&file)
+                    .map_err(|e| CkpError::IoError(format!("Failed to read {}: {}", file.display(), e)))?;
+                let value = serde_json::from_str(&content).unwrap_or(JsonValue::String(content));
+                data.insert(name, value);
+            }
+
+            let mut data = JsonValue::Object(data);
+            if let Some(rules) = redaction {
+                rules.apply(&mut data);
+            }
+            fragments.push(serde_json::json!({
+                "kernel": kernel,
+                "artifact": artifact.strip_prefix(&concepts).unwrap_or(&artifact),
+                "data": data,
+            }));
+        }
+
+        Ok(fragments)
+    }
+
+    fn instance_dir(&self, id: &str) -> PathBuf {
+        self.project_root
+            .join("concepts")
+            .join(COMPLIANCE_KERNEL)
+            .join("storage")
+            .join(format!("{}.inst", id))
+    }
+
+    fn save(&self, request: &DsarRequest) -> Result<()> {
+        let inst_dir = self.instance_dir(&request.id);
+        fs::create_dir_all(&inst_dir)
+            .map_err(|e| CkpError::IoError(format!("Failed to create DSAR instance: {}", e)))?;
+
+        let receipt = serde_json::json!({
+            "id": request.id,
+            "name": request.id,
+            "kernel": COMPLIANCE_KERNEL,
+            "timestamp": request.received_at,
+            "action": "gdpr.dsar",
+            "success": request.workflow.status != WorkflowStatus::Failed,
+            "data": {
+                "urn": request.urn,
+                "status": request.workflow.status,
+                "deadline": request.deadline,
+            },
+        });
+
+        fs::write(inst_dir.join("payload.json"), serde_json::to_string_pretty(request)?)
+            .map_err(|e| CkpError::IoError(format!("Failed to write DSAR request: {}", e)))?;
+        fs::write(inst_dir.join("receipt.bin"), serde_json::to_string_pretty(&receipt)?)
+            .map_err(|e| CkpError::IoError(format!("Failed to write DSAR receipt: {}", e)))?;
+
+        Ok(())
+    }
+}
+
+fn phase(name: &str, kernel: &str) -> WorkflowPhase {
+    WorkflowPhase {
+        phase_name: name.to_string(),
+        kernel_urn: format!("ckp://{}", kernel),
+        status: PhaseStatus::Pending,
+        started_at: None,
+        completed_at: None,
+        outputs: Vec::new(),
+    }
+}
+
+fn kernel_name(kernel_urn: &str) -> &str {
+    kernel_urn.strip_prefix("ckp://").unwrap_or(kernel_urn)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::compliance::{ConsentPurpose, GdprChecker};
+    use std::path::Path;
+    use tempfile::TempDir;
+
+    fn setup(root: &Path) {
+        let concepts = root.join("concepts");
+        for (kernel, file, content) in [
+            ("Shop.Signup", "storage/tx1.inst/payload.json", r#"{"user_id": "alice", "password": "pw"}"#),
+            ("Shop.Orders", "queue/archive/tx2.job", r#"{"payload": {"userId": "alice", "total": 12}}"#),
+            ("Shop.Unrelated", "storage/tx3.inst/payload.json", r#"{"user_id": "alice"}"#),
+        ] {
+            let path = concepts.join(kernel).join(file);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, content).unwrap();
+        }
+
+        let mut edges = EdgeKernel::new(root.to_path_buf()).unwrap();
+        edges.create_edge("PRODUCES", "Shop.Signup", "Shop.Orders").unwrap();
+        edges.create_edge("NOTIFIES", "Shop.Orders", "Shop.Mailer").unwrap();
+    }
+
+    #[test]
+    fn test_dsar_fans_out_via_edges() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        setup(root);
+
+        let workflow = DsarWorkflow::new(root.to_path_buf())
+            .with_entry_kernels(vec!["Shop.Signup".to_string()]);
+
+        let mut checker = GdprChecker::new().with_redaction_rules(RedactionRules::default());
+        checker.record_consent("alice", ConsentPurpose::Analytics, false);
+        let package = checker.data_subject_access_request("alice", &workflow).unwrap();
+
+        // Unrelated is not reachable from Signup; Mailer has no local data
+        assert_eq!(package.kernels, vec!["Shop.Signup", "Shop.Orders", "Shop.Mailer"]);
+        assert_eq!(package.results.len(), 2);
+        assert_eq!(package.results[0].data[0]["data"]["payload.json"]["password"], "[REDACTED]");
+        assert_eq!(package.results[1].data[0]["kernel"], "Shop.Orders");
+        assert!(package.consent.is_some());
+
+        let request = workflow.load(&package.request_id).unwrap();
+        assert_eq!(request.workflow.status, WorkflowStatus::Completed);
+        assert_eq!(request.workflow.edges.len(), 2);
+        assert!(request.workflow.phases.iter().all(|p| p.status == PhaseStatus::Completed));
+        assert!(workflow.instance_dir(&request.id).join("package.json").exists());
+    }
+
+    #[test]
+    fn test_dsar_deadline_tracking() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        setup(root);
+
+        let workflow = DsarWorkflow::new(root.to_path_buf()).with_deadline_days(-1);
+        let mut request = workflow.open("alice").unwrap();
+        assert_eq!(request.kernels().len(), 4);
+        assert_eq!(workflow.overdue().unwrap().len(), 1);
+
+        workflow.extend_deadline(&mut request, 60, "complex request").unwrap();
+        assert!(!request.is_overdue());
+        assert!(workflow.overdue().unwrap().is_empty());
+        assert!(matches!(
+            workflow.extend_deadline(&mut request, 10, "again"),
+            Err(CkpError::ValidationError(_))
+        ));
+
+        workflow.execute(&mut request, None, None).unwrap();
+        assert!(request.completed_at.is_some());
+        assert_eq!(workflow.list().unwrap()[0].id, request.id);
+    }
+}
@@ -0,0 +1,453 @@
+//! Cascading erasure for GDPR Right to Erasure
+//!
+//! Scans every kernel's `storage/`, `queue/archive/` and `archive/` for
+//! artifacts tagged with a data subject, erases them (delete or crypto-shred)
+//! and mints an erasure report instance under `System.Compliance` as proof.
+//!
+//! The report lists where artifacts were erased, not what they held. It
+//! names the subject only as an HMAC under a project secret (see
+//! `with_subject_key`), and not at all without one: a plain hash of an email
+//! or other enumerable identifier would re-identify the erased subject.
+
+use crate::errors::{CkpError, Result};
+use chacha20poly1305::aead::{Aead, AeadCore, KeyInit, OsRng};
+use chacha20poly1305::ChaCha20Poly1305;
+use chrono::{DateTime, Utc};
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use serde_json::Value as JsonValue;
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Kernel that stores erasure reports
+pub const COMPLIANCE_KERNEL: &str = "System.Compliance";
+
+/// Kernel subdirectories holding subject data: instances, archived queue
+/// jobs, and the kernel archive that processed jobs and archived instances
+/// are moved to
+pub const KERNEL_DATA_DIRS: &[&str] = &["storage", "queue/archive", "archive"];
+
+/// Field paths checked for the subject identifier by default
+pub const DEFAULT_SUBJECT_FIELDS: &[&str] = &[
+    "user_id",
+    "userId",
+    "subject",
+    "data.user_id",
+    "data.userId",
+    "payload.user_id",
+    "payload.userId",
+];
+
+/// How matching artifacts are erased
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ErasureMode {
+    /// Remove the artifact from disk
+    #[default]
+    Delete,
+
+    /// Encrypt the artifact in place with a one-time key and discard the key
+    ///
+    /// Keeps file layout (and any references to the artifact) intact while
+    /// making the content unrecoverable.
+    CryptoShred,
+}
+
+/// An artifact erased on behalf of a subject
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ErasedArtifact {
+    /// Kernel that held the artifact
+    pub kernel: String,
+
+    /// Artifact path (file, or `.inst` directory)
+    pub path: PathBuf,
+}
+
+/// Result of a cascading erasure, persisted as the proof artifact
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ErasureReport {
+    /// Proof URN of the report instance (`ckp://System.Compliance#storage/{id}`)
+    pub urn: String,
+
+    /// Report instance ID
+    pub id: String,
+
+    /// HMAC-SHA256 of the subject identifier under the project's subject
+    /// key (None when the engine has no key)
+    #[serde(rename = "subjectHash", default, skip_serializing_if = "Option::is_none")]
+    pub subject_hash: Option<String>,
+
+    pub mode: ErasureMode,
+
+    pub timestamp: DateTime<Utc>,
+
+    /// Artifacts erased, in scan order
+    pub artifacts: Vec<ErasedArtifact>,
+}
+
+/// Cascading erasure engine
+///
+/// # Examples
+///
+/// ```no_run
+/// use ckp_core::compliance::{ErasureEngine, ErasureMode};
+/// use std::path::PathBuf;
+///
+/// let engine = ErasureEngine::new(PathBuf::from("/project/concepts"))
+///     .with_subject_fields(vec!["data.owner".to_string()])
+///     .with_mode(ErasureMode::CryptoShred);
+///
+/// let report = engine.erase_subject("user123").unwrap();
+/// println!("{} artifacts erased, proof {}", report.artifacts.len(), report.urn);
+/// ```
+pub struct ErasureEngine {
+    concepts_root: PathBuf,
+    subject_fields: Vec<String>,
+    mode: ErasureMode,
+    exceptions: Vec<String>,
+    subject_key: Option<Vec<u8>>,
+}
+
+impl ErasureEngine {
+    /// Create engine over a `concepts/` directory with default subject fields
+    pub fn new(concepts_root: PathBuf) -> Self {
+        Self {
+            concepts_root,
+            subject_fields: DEFAULT_SUBJECT_FIELDS.iter().map(|s| s.to_string()).collect(),
+            mode: ErasureMode::default(),
+            exceptions: Vec::new(),
+            subject_key: None,
+        }
+    }
+
+    /// Replace the dotted field paths checked for the subject identifier
+    pub fn with_subject_fields(mut self, fields: Vec<String>) -> Self {
+        self.subject_fields = fields;
+        self
+    }
+
+    /// Set erasure mode
+    pub fn with_mode(mut self, mode: ErasureMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Project secret keying the subject hash in reports
+    ///
+    /// Lets a later request for the same subject be matched to its report
+    /// by whoever holds the key. Without it, reports do not name the subject.
+    pub fn with_subject_key(mut self, key: &[u8]) -> Self {
+        self.subject_key = Some(key.to_vec());
+        self
+    }
+
+    /// Add kernel exception (never scanned, e.g. legal-hold kernels)
+    pub fn add_exception(&mut self, kernel_name: String) {
+        self.exceptions.push(kernel_name);
+    }
+
+    /// Find artifacts tagged with a subject without erasing them
+    pub fn find_artifacts(&self, user_id: &str) -> Result<Vec<(String, PathBuf)>> {
+        let mut found = Vec::new();
+
+        if !self.concepts_root.exists() {
+            return Ok(found);
+        }
+
+        let mut kernels: Vec<PathBuf> = fs::read_dir(&self.concepts_root)
+            .map_err(|e| CkpError::IoError(format!("Failed to read concepts directory: {}", e)))?
+            .filter_map(|entry| entry.ok().map(|e| e.path()))
+            .filter(|path| path.is_dir())
+            .collect();
+        kernels.sort();
+
+        for kernel_path in kernels {
+            let kernel = kernel_path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+
+            // Skip hidden dirs (.continuants, .archive) and our own proofs
+            if kernel.starts_with('.') || kernel == COMPLIANCE_KERNEL || self.exceptions.contains(&kernel) {
+                continue;
+            }
+
+            for path in self.find_in_kernel(&kernel, user_id)? {
+                found.push((kernel.clone(), path));
+            }
+        }
+
+        Ok(found)
+    }
+
+    /// Find artifacts tagged with a subject in one kernel's storage and archive
+    pub fn find_in_kernel(&self, kernel: &str, user_id: &str) -> Result<Vec<PathBuf>> {
+        let kernel_path = self.concepts_root.join(kernel);
+        let mut found = Vec::new();
+
+        for dir in KERNEL_DATA_DIRS {
+            self.scan_directory(&kernel_path.join(dir), user_id, &mut |path| found.push(path))?;
+        }
+
+        Ok(found)
+    }
+
+    /// Erase all artifacts tagged with a subject and write the proof report
+    pub fn erase_subject(&self, user_id: &str) -> Result<ErasureReport> {
+        let mut artifacts = Vec::new();
+
+        for (kernel, path) in self.find_artifacts(user_id)? {
+            match self.mode {
+                ErasureMode::Delete => delete_artifact(&path)?,
+                ErasureMode::CryptoShred => shred_artifact(&path)?,
+            }
+            artifacts.push(ErasedArtifact { kernel, path });
+        }
+
+        let id = format!(
+            "erasure-{}-{}",
+            Utc::now().timestamp_millis(),
+            &uuid::Uuid::new_v4().to_string()[..8]
+        );
+        let report = ErasureReport {
+            urn: format!("ckp://{}#storage/{}", COMPLIANCE_KERNEL, id),
+            id,
+            subject_hash: self.subject_key.as_ref().map(|key| {
+                let mut mac = <Hmac<Sha256> as Mac>::new_from_slice(key).expect("HMAC accepts keys of any length");
+                mac.update(user_id.as_bytes());
+                hex::encode(mac.finalize().into_bytes())
+            }),
+            mode: self.mode,
+            timestamp: Utc::now(),
+            artifacts,
+        };
+
+        self.write_report(&report)?;
+        Ok(report)
+    }
+
+    fn scan_directory(&self, dir: &Path, user_id: &str, found: &mut dyn FnMut(PathBuf)) -> Result<()> {
+        if !dir.exists() {
+            return Ok(());
+        }
+
+        let mut entries: Vec<PathBuf> = fs::read_dir(dir)
+            .map_err(|e| CkpError::IoError(format!("Failed to read directory: {}", e)))?
+            .filter_map(|entry| entry.ok().map(|e| e.path()))
+            .collect();
+        entries.sort();
+
+        for path in entries {
+            if path.is_dir() {
+                // An instance is erased as a whole if any of its files is tagged
+                if path.extension().and_then(|s| s.to_str()) == Some("inst") {
+                    let tagged = fs::read_dir(&path)
+                        .map_err(|e| CkpError::IoError(format!("Failed to read instance: {}", e)))?
+                        .filter_map(|entry| entry.ok().map(|e| e.path()))
+                        .any(|file| file.is_file() && self.is_tagged(&file, user_id));
+                    if tagged {
+                        found(path);
+                    }
+                } else {
+                    self.scan_directory(&path, user_id, found)?;
+                }
+            } else if self.is_tagged(&path, user_id) {
+                found(path);
+            }
+        }
+
+        Ok(())
+    }
+
+    fn is_tagged(&self, file: &Path, user_id: &str) -> bool {
+        let value: JsonValue = match fs::read_to_string(file).ok().and_then(|s| serde_json::from_str(&s).ok()) {
+            Some(value) => value,
+            None => return false,
+        };
+
+        self.subject_fields.iter().any(|field| matches_subject(lookup(&value, field), user_id))
+    }
+
+    fn write_report(&self, report: &ErasureReport) -> Result<()> {
+        let inst_dir = self.concepts_root
+            .join(COMPLIANCE_KERNEL)
+            .join("storage")
+            .join(format!("{}.inst", report.id));
+        fs::create_dir_all(&inst_dir)
+            .map_err(|e| CkpError::IoError(format!("Failed to create report instance: {}", e)))?;
+
+        let payload = serde_json::to_string_pretty(report)
+            .map_err(|e| CkpError::SerializationError(format!("Failed to serialize erasure report: {}", e)))?;
+        let receipt = serde_json::json!({
+            "id": report.id,
+            "name": report.id,
+            "kernel": COMPLIANCE_KERNEL,
+            "timestamp": report.timestamp,
+            "action": "gdpr.erasure",
+            "success": true,
+            "data": {
+                "urn": report.urn,
+                "subjectHash": report.subject_hash,
+                "mode": report.mode,
+                "artifactCount": report.artifacts.len(),
+                "proofHash": hex::encode(Sha256::digest(payload.as_bytes())),
+            },
+        });
+
+        fs::write(inst_dir.join("payload.json"), &payload)
+            .map_err(|e| CkpError::IoError(format!("Failed to write erasure report: {}", e)))?;
+        fs::write(inst_dir.join("receipt.bin"), serde_json::to_string_pretty(&receipt)?)
+            .map_err(|e| CkpError::IoError(format!("Failed to write erasure receipt: {}", e)))?;
+
+        Ok(())
+    }
+}
+
+/// Resolve a dotted path (e.g. "data.user_id") in a JSON value
+pub(super) fn lookup<'a>(value: &'a JsonValue, path: &str) -> Option<&'a JsonValue> {
+    path.split('.').try_fold(value, |current, key| current.get(key))
+}
+
+pub(super) fn matches_subject(value: Option<&JsonValue>, user_id: &str) -> bool {
+    match value {
+        Some(JsonValue::String(s)) => s == user_id,
+        Some(JsonValue::Number(n)) => n.to_string() == user_id,
+        Some(JsonValue::Array(items)) => items.iter().any(|item| matches_subject(Some(item), user_id)),
+        _ => false,
+    }
+}
+
+/// Files of an artifact (the file itself, or an instance's files), sorted
+pub(super) fn artifact_files(path: &Path) -> Result<Vec<PathBuf>> {
+    if !path.is_dir() {
+        return Ok(vec![path.to_path_buf()]);
+    }
+
+    let mut files: Vec<PathBuf> = fs::read_dir(path)
+        .map_err(|e| CkpError::IoError(format!("Failed to read instance: {}", e)))?
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|p| p.is_file())
+        .collect();
+    files.sort();
+
+    Ok(files)
+}
+
+fn delete_artifact(path: &Path) -> Result<()> {
+    let result = if path.is_dir() { fs::remove_dir_all(path) } else { fs::remove_file(path) };
+    result.map_err(|e| CkpError::IoError(format!("Failed to erase {}: {}", path.display(), e)))
+}
+
+fn shred_artifact(path: &Path) -> Result<()> {
+    for file in artifact_files(path)? {
+        let plaintext = fs::read(&file)
+            .map_err(|e| CkpError::IoError(format!("Failed to read artifact: {}", e)))?;
+
+        // One-time key, dropped when this iteration ends
+        let cipher = ChaCha20Poly1305::new(&ChaCha20Poly1305::generate_key(&mut OsRng));
+        let nonce = ChaCha20Poly1305::generate_nonce(&mut OsRng);
+        let ciphertext = cipher.encrypt(&nonce, plaintext.as_slice())
+            .map_err(|e| CkpError::IoError(format!("Failed to shred artifact: {}", e)))?;
+
+        let shredded = serde_json::json!({
+            "shredded": true,
+            "algorithm": "chacha20poly1305",
+            "nonce": hex::encode(nonce),
+            "ciphertext": hex::encode(ciphertext),
+        });
+        fs::write(&file, serde_json::to_string(&shredded)?)
+            .map_err(|e| CkpError::IoError(format!("Failed to shred {}: {}", file.display(), e)))?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn setup(concepts: &Path) {
+        let alice_inst = concepts.join("Orders/storage/tx1.inst");
+        fs::create_dir_all(&alice_inst).unwrap();
+        fs::write(alice_inst.join("payload.json"), r#"{"data": {"user_id": "alice", "total": 10}}"#).unwrap();
+        fs::write(alice_inst.join("receipt.bin"), r#"{"id": "tx1"}"#).unwrap();
+
+        let bob_inst = concepts.join("Orders/storage/tx2.inst");
+        fs::create_dir_all(&bob_inst).unwrap();
+        fs::write(bob_inst.join("payload.json"), r#"{"data": {"user_id": "bob"}}"#).unwrap();
+
+        let archive = concepts.join("Profile/queue/archive");
+        fs::create_dir_all(&archive).unwrap();
+        fs::write(archive.join("tx3.job"), r#"{"payload": {"userId": "alice"}}"#).unwrap();
+        fs::write(archive.join("tx4.job"), r#"{"payload": {"owner": "alice"}}"#).unwrap();
+    }
+
+    #[test]
+    fn test_erase_subject_deletes_and_writes_proof() {
+        let temp_dir = TempDir::new().unwrap();
+        let concepts = temp_dir.path().join("concepts");
+        setup(&concepts);
+
+        let report = ErasureEngine::new(concepts.clone()).erase_subject("alice").unwrap();
+
+        assert_eq!(report.artifacts.len(), 2);
+        assert!(!concepts.join("Orders/storage/tx1.inst").exists());
+        assert!(concepts.join("Orders/storage/tx2.inst").exists());
+        assert!(!concepts.join("Profile/queue/archive/tx3.job").exists());
+        // Not a configured subject field
+        assert!(concepts.join("Profile/queue/archive/tx4.job").exists());
+
+        assert!(report.urn.starts_with("ckp://System.Compliance#storage/erasure-"));
+        let proof = concepts.join(COMPLIANCE_KERNEL).join("storage").join(format!("{}.inst", report.id));
+        let payload = fs::read_to_string(proof.join("payload.json")).unwrap();
+        assert!(!payload.contains("\"alice\""));
+        assert!(!payload.contains("subjectHash"));
+        assert!(proof.join("receipt.bin").exists());
+    }
+
+    #[test]
+    fn test_erase_subject_reaches_kernel_archive() {
+        let temp_dir = TempDir::new().unwrap();
+        let concepts = temp_dir.path().join("concepts");
+
+        // A processed job and an archived instance, both moved to archive/
+        let archive = concepts.join("Orders/archive");
+        fs::create_dir_all(archive.join("tx2.inst")).unwrap();
+        fs::write(archive.join("tx1.job"), r#"{"payload": {"userId": "alice"}}"#).unwrap();
+        fs::write(archive.join("tx2.inst/payload.json"), r#"{"user_id": "alice"}"#).unwrap();
+
+        let engine = ErasureEngine::new(concepts.clone()).with_subject_key(b"project-secret");
+        let report = engine.erase_subject("alice").unwrap();
+
+        assert_eq!(report.artifacts.len(), 2);
+        assert!(!archive.join("tx1.job").exists());
+        assert!(!archive.join("tx2.inst").exists());
+
+        // Keyed, so the same subject is recognizable only with the key
+        let subject_hash = report.subject_hash.unwrap();
+        assert_ne!(subject_hash, hex::encode(Sha256::digest(b"alice")));
+        let again = ErasureEngine::new(concepts).with_subject_key(b"project-secret").erase_subject("alice").unwrap();
+        assert_eq!(again.subject_hash.as_deref(), Some(subject_hash.as_str()));
+    }
+
+    #[test]
+    fn test_crypto_shred_with_custom_fields() {
+        let temp_dir = TempDir::new().unwrap();
+        let concepts = temp_dir.path().join("concepts");
+        setup(&concepts);
+
+        let report = ErasureEngine::new(concepts.clone())
+            .with_subject_fields(vec!["payload.owner".to_string()])
+            .with_mode(ErasureMode::CryptoShred)
+            .erase_subject("alice")
+            .unwrap();
+
+        assert_eq!(report.artifacts.len(), 1);
+        let shredded = fs::read_to_string(concepts.join("Profile/queue/archive/tx4.job")).unwrap();
+        assert!(shredded.contains("\"shredded\":true"));
+        assert!(!shredded.contains("alice"));
+
+        // Shredded content no longer matches, so a second pass finds nothing
+        let engine = ErasureEngine::new(concepts).with_subject_fields(vec!["payload.owner".to_string()]);
+        assert!(engine.find_artifacts("alice").unwrap().is_empty());
+    }
+}
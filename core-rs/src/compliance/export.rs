@@ -0,0 +1,458 @@
+//! Data portability export bundles
+//!
+//! Gathers a subject's instances across kernels (via `InstanceScanner`),
+//! serializes them to JSON, NDJSON, CSV or Parquet, and packages everything
+//! in a zip with a `manifest.json` and `checksums.sha256`.
+//!
+//! Parquet output requires the `parquet` cargo feature.
+
+use super::erasure::{lookup, matches_subject, COMPLIANCE_KERNEL, DEFAULT_SUBJECT_FIELDS};
+use super::RedactionRules;
+use crate::errors::{CkpError, Result};
+use crate::storage::InstanceScanner;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value as JsonValue;
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+/// Serialization format for exported records
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportFormat {
+    Json,
+    Ndjson,
+    Csv,
+    Parquet,
+}
+
+impl ExportFormat {
+    /// File name of this format inside the bundle
+    pub fn file_name(&self) -> &'static str {
+        match self {
+            ExportFormat::Json => "records.json",
+            ExportFormat::Ndjson => "records.ndjson",
+            ExportFormat::Csv => "records.csv",
+            ExportFormat::Parquet => "records.parquet",
+        }
+    }
+}
+
+/// One instance belonging to the subject
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SubjectRecord {
+    pub kernel: String,
+    pub id: String,
+    pub name: String,
+    pub timestamp: DateTime<Utc>,
+    pub data: JsonValue,
+}
+
+/// A file inside the bundle
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExportFileEntry {
+    pub name: String,
+    pub format: ExportFormat,
+    pub bytes: usize,
+    pub sha256: String,
+}
+
+/// Bundle manifest (`manifest.json`)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportManifest {
+    /// SHA-256 of the subject identifier
+    pub subject_hash: String,
+    pub generated_at: DateTime<Utc>,
+    pub record_count: usize,
+    /// Kernels that contributed records
+    pub kernels: Vec<String>,
+    pub files: Vec<ExportFileEntry>,
+}
+
+/// A written export bundle
+#[derive(Debug, Clone)]
+pub struct ExportBundle {
+    /// Path of the zip file
+    pub path: PathBuf,
+    pub manifest: ExportManifest,
+}
+
+/// Builds data portability bundles for a subject
+///
+/// # Examples
+///
+/// ```no_run
+/// use ckp_core::compliance::{ExportFormat, PortabilityExporter};
+/// use std::path::{Path, PathBuf};
+///
+/// let exporter = PortabilityExporter::new(PathBuf::from("/project/concepts"))
+///     .with_formats(vec![ExportFormat::Csv, ExportFormat::Ndjson]);
+///
+/// let bundle = exporter.export("user123", Path::new("/tmp/exports")).unwrap();
+/// println!("{} records in {}", bundle.manifest.record_count, bundle.path.display());
+/// ```
+pub struct PortabilityExporter {
+    concepts_root: PathBuf,
+    subject_fields: Vec<String>,
+    formats: Vec<ExportFormat>,
+    redaction: Option<RedactionRules>,
+}
+
+impl PortabilityExporter {
+    /// Create exporter over a `concepts/` directory (JSON + CSV by default)
+    pub fn new(concepts_root: PathBuf) -> Self {
+        Self {
+            concepts_root,
+            subject_fields: DEFAULT_SUBJECT_FIELDS.iter().map(|s| s.to_string()).collect(),
+            formats: vec![ExportFormat::Json, ExportFormat::Csv],
+            redaction: None,
+        }
+    }
+
+    /// Replace the dotted field paths checked for the subject identifier
+    pub fn with_subject_fields(mut self, fields: Vec<String>) -> Self {
+        self.subject_fields = fields;
+        self
+    }
+
+    /// Set the formats written to the bundle
+    pub fn with_formats(mut self, formats: Vec<ExportFormat>) -> Self {
+        self.formats = formats;
+        self
+    }
+
+    /// Redact record data before serialization
+    pub fn with_redaction_rules(mut self, rules: RedactionRules) -> Self {
+        self.redaction = Some(rules);
+        self
+    }
+
+    /// Collect the subject's instances from every kernel's storage and archive
+    ///
+    /// # Returns
+    /// Records sorted by kernel, then timestamp
+    pub fn gather(&self, user_id: &str) -> Result<Vec<SubjectRecord>> {
+        let mut records = Vec::new();
+
+        if !self.concepts_root.exists() {
+            return Ok(records);
+        }
+
+        let mut kernels: Vec<PathBuf> = fs::read_dir(&self.concepts_root)
+            .map_err(|e| CkpError::IoError(format!("Failed to read concepts directory: {}", e)))?
+            .filter_map(|entry| entry.ok().map(|e| e.path()))
+            .filter(|path| path.is_dir())
+            .collect();
+        kernels.sort();
+
+        for kernel_path in kernels {
+            let kernel = kernel_path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+            if kernel.starts_with('.') || kernel == COMPLIANCE_KERNEL {
+                continue;
+            }
+
+            let details = match InstanceScanner::new(kernel_path, kernel.clone()).list_instance_details_with_archive() {
+                Ok(details) => details,
+                // Kernel without storage
+                Err(CkpError::FileNotFound(_)) => continue,
+                Err(e) => return Err(e),
+            };
+
+            for detail in details {
+                let envelope = serde_json::to_value(&detail)?;
+                let tagged = self.subject_fields.iter().any(|field| {
+                    matches_subject(lookup(&envelope, field), user_id)
+                        || matches_subject(lookup(&detail.data, field), user_id)
+                });
+                if !tagged {
+                    continue;
+                }
+
+                let mut data = detail.data;
+                if let Some(rules) = &self.redaction {
+                    rules.apply(&mut data);
+                }
+
+                records.push(SubjectRecord {
+                    kernel: kernel.clone(),
+                    id: detail.id,
+                    name: detail.name,
+                    timestamp: detail.timestamp,
+                    data,
+                });
+            }
+        }
+
+        Ok(records)
+    }
+
+    /// Gather the subject's data and write a zip bundle into `output_dir`
+    pub fn export(&self, user_id: &str, output_dir: &Path) -> Result<ExportBundle> {
+        let records = self.gather(user_id)?;
+        let subject_hash = hex::encode(Sha256::digest(user_id.as_bytes()));
+        let generated_at = Utc::now();
+
+        let mut files = Vec::new();
+        let mut contents = Vec::new();
+        for format in &self.formats {
+            let bytes = serialize_records(&records, *format)?;
+            files.push(ExportFileEntry {
+                name: format.file_name().to_string(),
+                format: *format,
+                bytes: bytes.len(),
+                sha256: hex::encode(Sha256::digest(&bytes)),
+            });
+            contents.push(bytes);
+        }
+
+        let kernels: BTreeSet<&str> = records.iter().map(|r| r.kernel.as_str()).collect();
+        let manifest = ExportManifest {
+            subject_hash: subject_hash.clone(),
+            generated_at,
+            record_count: records.len(),
+            kernels: kernels.into_iter().map(String::from).collect(),
+            files,
+        };
+
+        fs::create_dir_all(output_dir)
+            .map_err(|e| CkpError::IoError(format!("Failed to create export directory: {}", e)))?;
+        let path = output_dir.join(format!(
+            "export-{}-{}.zip",
+            &subject_hash[..12],
+            generated_at.format("%Y%m%d-%H%M%S")
+        ));
+
+        let manifest_json = serde_json::to_vec_pretty(&manifest)?;
+        let mut checksums = String::new();
+        for entry in &manifest.files {
+            checksums.push_str(&format!("{}  {}\n", entry.sha256, entry.name));
+        }
+        checksums.push_str(&format!("{}  manifest.json\n", hex::encode(Sha256::digest(&manifest_json))));
+
+        let file = fs::File::create(&path)
+            .map_err(|e| CkpError::IoError(format!("Failed to create export bundle: {}", e)))?;
+        let mut zip = zip::ZipWriter::new(file);
+        let options = zip::write::SimpleFileOptions::default()
+            .compression_method(zip::CompressionMethod::Deflated);
+
+        let entries = manifest.files.iter().map(|f| f.name.as_str()).zip(contents.iter().map(|c| c.as_slice()))
+            .chain([("manifest.json", manifest_json.as_slice()), ("checksums.sha256", checksums.as_bytes())]);
+        for (name, bytes) in entries {
+            zip.start_file(name, options)
+                .map_err(|e| CkpError::IoError(format!("Failed to add {} to bundle: {}", name, e)))?;
+            zip.write_all(bytes)
+                .map_err(|e| CkpError::IoError(format!("Failed to write {} to bundle: {}", name, e)))?;
+        }
+        zip.finish()
+            .map_err(|e| CkpError::IoError(format!("Failed to finish export bundle: {}", e)))?;
+
+        Ok(ExportBundle { path, manifest })
+    }
+}
+
+/// Flatten record data to dotted columns (arrays kept as JSON)
+fn flatten(value: &JsonValue, prefix: &str, out: &mut BTreeMap<String, String>) {
+    match value {
+        JsonValue::Object(map) => {
+            for (key, child) in map {
+                let path = if prefix.is_empty() { key.clone() } else { format!("{}.{}", prefix, key) };
+                flatten(child, &path, out);
+            }
+        }
+        JsonValue::Null => {}
+        JsonValue::String(s) => {
+            out.insert(prefix.to_string(), s.clone());
+        }
+        other => {
+            out.insert(prefix.to_string(), other.to_string());
+        }
+    }
+}
+
+/// Tabular view of records: column names and one optional cell per column per row
+fn tabulate(records: &[SubjectRecord]) -> (Vec<String>, Vec<Vec<Option<String>>>) {
+    let flattened: Vec<BTreeMap<String, String>> = records.iter().map(|record| {
+        let mut row = BTreeMap::new();
+        flatten(&record.data, "data", &mut row);
+        row
+    }).collect();
+
+    let data_columns: BTreeSet<&String> = flattened.iter().flat_map(|row| row.keys()).collect();
+    let mut columns: Vec<String> = ["kernel", "id", "name", "timestamp"].iter().map(|c| c.to_string()).collect();
+    columns.extend(data_columns.into_iter().cloned());
+
+    let rows = records.iter().zip(&flattened).map(|(record, row)| {
+        let mut cells = vec![
+            Some(record.kernel.clone()),
+            Some(record.id.clone()),
+            Some(record.name.clone()),
+            Some(record.timestamp.to_rfc3339()),
+        ];
+        cells.extend(columns[4..].iter().map(|c| row.get(c).cloned()));
+        cells
+    }).collect();
+
+    (columns, rows)
+}
+
+fn serialize_records(records: &[SubjectRecord], format: ExportFormat) -> Result<Vec<u8>> {
+    match format {
+        ExportFormat::Json => Ok(serde_json::to_vec_pretty(records)?),
+        ExportFormat::Ndjson => {
+            let mut out = Vec::new();
+            for record in records {
+                serde_json::to_writer(&mut out, record)?;
+                out.push(b'\n');
+            }
+            Ok(out)
+        }
+        ExportFormat::Csv => {
+            let (columns, rows) = tabulate(records);
+            let mut writer = csv::Writer::from_writer(Vec::new());
+            let csv_err = |e: csv::Error| CkpError::SerializationError(format!("Failed to write CSV: {}", e));
+
+            writer.write_record(&columns).map_err(csv_err)?;
+            for row in rows {
+                writer.write_record(row.iter().map(|c| c.as_deref().unwrap_or(""))).map_err(csv_err)?;
+            }
+            writer.into_inner()
+                .map_err(|e| CkpError::SerializationError(format!("Failed to write CSV: {}", e)))
+        }
+        ExportFormat::Parquet => write_parquet(records),
+    }
+}
+
+#[cfg(feature = "parquet")]
+fn write_parquet(records: &[SubjectRecord]) -> Result<Vec<u8>> {
+    use parquet::basic::{ConvertedType, Repetition, Type as PhysicalType};
+    use parquet::data_type::{ByteArray, ByteArrayType};
+    use parquet::file::properties::WriterProperties;
+    use parquet::file::writer::SerializedFileWriter;
+    use parquet::schema::types::Type;
+    use std::sync::Arc;
+
+    let parquet_err = |e: parquet::errors::ParquetError| {
+        CkpError::SerializationError(format!("Failed to write Parquet: {}", e))
+    };
+
+    // Every column is an optional UTF-8 string, mirroring the CSV layout
+    let (columns, rows) = tabulate(records);
+    let fields = columns.iter()
+        .map(|name| {
+            Type::primitive_type_builder(name, PhysicalType::BYTE_ARRAY)
+                .with_repetition(Repetition::OPTIONAL)
+                .with_converted_type(ConvertedType::UTF8)
+                .build()
+                .map(Arc::new)
+        })
+        .collect::<std::result::Result<Vec<_>, _>>()
+        .map_err(parquet_err)?;
+    let schema = Arc::new(Type::group_type_builder("record").with_fields(fields).build().map_err(parquet_err)?);
+
+    let mut writer = SerializedFileWriter::new(Vec::new(), schema, Arc::new(WriterProperties::builder().build()))
+        .map_err(parquet_err)?;
+    let mut row_group = writer.next_row_group().map_err(parquet_err)?;
+
+    for index in 0..columns.len() {
+        let mut column = row_group.next_column().map_err(parquet_err)?
+            .ok_or_else(|| CkpError::SerializationError("Parquet schema/column mismatch".to_string()))?;
+
+        let cells: Vec<&Option<String>> = rows.iter().map(|row| &row[index]).collect();
+        let values: Vec<ByteArray> = cells.iter().filter_map(|c| c.as_deref()).map(ByteArray::from).collect();
+        let levels: Vec<i16> = cells.iter().map(|c| i16::from(c.is_some())).collect();
+
+        column.typed::<ByteArrayType>().write_batch(&values, Some(&levels), None).map_err(parquet_err)?;
+        column.close().map_err(parquet_err)?;
+    }
+
+    row_group.close().map_err(parquet_err)?;
+    writer.into_inner().map_err(parquet_err)
+}
+
+#[cfg(not(feature = "parquet"))]
+fn write_parquet(_records: &[SubjectRecord]) -> Result<Vec<u8>> {
+    Err(CkpError::ValidationError(
+        "Parquet export requires ckp_core to be built with the `parquet` feature".to_string(),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Read;
+    use tempfile::TempDir;
+
+    fn write_instance(concepts: &Path, kernel: &str, id: &str, data: JsonValue) {
+        let inst = concepts.join(kernel).join("storage").join(format!("{}.inst", id));
+        fs::create_dir_all(&inst).unwrap();
+        let receipt = serde_json::json!({
+            "id": id,
+            "name": id,
+            "timestamp": "2025-11-29T10:00:00Z",
+            "data": data
+        });
+        fs::write(inst.join("receipt.bin"), receipt.to_string()).unwrap();
+    }
+
+    #[test]
+    fn test_export_bundle_with_manifest_and_checksums() {
+        let temp_dir = TempDir::new().unwrap();
+        let concepts = temp_dir.path().join("concepts");
+        write_instance(&concepts, "Orders", "tx1", serde_json::json!({"user_id": "alice", "total": 10, "tags": ["a"]}));
+        write_instance(&concepts, "Profile", "tx2", serde_json::json!({"user_id": "alice", "password": "pw"}));
+        write_instance(&concepts, "Profile", "tx3", serde_json::json!({"user_id": "bob"}));
+
+        let exporter = PortabilityExporter::new(concepts)
+            .with_formats(vec![ExportFormat::Csv, ExportFormat::Ndjson])
+            .with_redaction_rules(RedactionRules::default());
+        let bundle = exporter.export("alice", &temp_dir.path().join("out")).unwrap();
+
+        assert_eq!(bundle.manifest.record_count, 2);
+        assert_eq!(bundle.manifest.kernels, vec!["Orders", "Profile"]);
+
+        let mut archive = zip::ZipArchive::new(fs::File::open(&bundle.path).unwrap()).unwrap();
+        let mut read = |name: &str| {
+            let mut s = String::new();
+            archive.by_name(name).unwrap().read_to_string(&mut s).unwrap();
+            s
+        };
+
+        let csv = read("records.csv");
+        let header = csv.lines().next().unwrap();
+        assert_eq!(header, "kernel,id,name,timestamp,data.password,data.tags,data.total,data.user_id");
+        assert!(csv.contains("[REDACTED]"));
+        assert!(!csv.contains("bob"));
+        assert_eq!(read("records.ndjson").lines().count(), 2);
+
+        let checksums = read("checksums.sha256");
+        let csv_hash = hex::encode(Sha256::digest(csv.as_bytes()));
+        assert!(checksums.contains(&format!("{}  records.csv", csv_hash)));
+        assert!(checksums.contains("manifest.json"));
+    }
+
+    #[cfg(feature = "parquet")]
+    #[test]
+    fn test_parquet_output() {
+        let record = SubjectRecord {
+            kernel: "Orders".to_string(),
+            id: "tx1".to_string(),
+            name: "tx1".to_string(),
+            timestamp: Utc::now(),
+            data: serde_json::json!({"user_id": "alice"}),
+        };
+
+        let bytes = serialize_records(&[record], ExportFormat::Parquet).unwrap();
+        assert!(bytes.starts_with(b"PAR1") && bytes.ends_with(b"PAR1"));
+    }
+
+    #[cfg(not(feature = "parquet"))]
+    #[test]
+    fn test_parquet_requires_feature() {
+        let result = serialize_records(&[], ExportFormat::Parquet);
+        assert!(matches!(result, Err(CkpError::ValidationError(_))));
+    }
+}
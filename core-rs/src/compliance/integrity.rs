@@ -0,0 +1,519 @@
+//! Tamper-evident audit log support
+//!
+//! Every AuditEntry carries a sequence number, the previous entry's hash and
+//! its own SHA-256 hash, forming a chain across rotated segments. A sidecar
+//! `{log}.head` file records the last written entry so tail truncation is
+//! detectable, and optional Ed25519-signed checkpoints anchor the chain.
+//! Writers continue from the log tail they find on disk, so several loggers
+//! (or processes) can append to one chain.
+
+use super::archival::{decompress, is_compressed, COMPRESSED_SEGMENT_EXTENSION};
+use super::{AuditEntry, AuditLogger};
+use crate::errors::{CkpError, Result};
+use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fs::{self, File};
+use std::io::{Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+
+/// Operation name used for checkpoint entries
+pub const CHECKPOINT_OPERATION: &str = "audit.checkpoint";
+
+/// Last written entry of the chain
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ChainHead {
+    pub seq: u64,
+    pub hash: String,
+}
+
+/// A problem found by `AuditLogger::verify_integrity`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "kebab-case")]
+pub enum IntegrityIssue {
+    /// Line is not a valid AuditEntry
+    Unparseable { segment: PathBuf, line: usize },
+
+    /// Entry content does not match its stored hash (modified)
+    HashMismatch { segment: PathBuf, seq: u64 },
+
+    /// Entry does not link to the previous entry (removed or reordered)
+    BrokenLink { segment: PathBuf, seq: u64 },
+
+    /// Sequence numbers skip (entries removed)
+    SequenceGap { segment: PathBuf, expected: u64, found: u64 },
+
+    /// Chained entry follows an unchained one mid-log
+    MissingHash { segment: PathBuf, line: usize },
+
+    /// Checkpoint signature is invalid
+    BadCheckpoint { segment: PathBuf, seq: u64 },
+
+    /// Log ends before the recorded head (tail truncated)
+    Truncated { expected_seq: u64, last_seq: Option<u64> },
+
+    /// Last entry does not match the recorded head (rewritten tail)
+    HeadMismatch { expected_seq: u64, last_seq: u64 },
+}
+
+/// Result of an integrity verification
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct IntegrityReport {
+    /// Segments examined, oldest first
+    pub segments: Vec<PathBuf>,
+
+    /// Chained entries verified
+    pub entries: usize,
+
+    /// Entries written before chaining was enabled
+    #[serde(rename = "legacyEntries")]
+    pub legacy_entries: usize,
+
+    /// Checkpoints whose signature verified against the trusted key
+    #[serde(rename = "checkpointsVerified")]
+    pub checkpoints_verified: usize,
+
+    /// Checkpoints only self-consistent with their embedded key (no trusted key configured)
+    #[serde(default, rename = "checkpointsUntrusted")]
+    pub checkpoints_untrusted: usize,
+
+    /// Problems found
+    pub issues: Vec<IntegrityIssue>,
+}
+
+impl IntegrityReport {
+    /// Whether no tampering was detected
+    pub fn is_valid(&self) -> bool {
+        self.issues.is_empty()
+    }
+}
+
+/// SHA-256 hex digest of an entry's canonical JSON (with `hash` omitted)
+pub fn entry_hash(entry: &AuditEntry) -> Result<String> {
+    let mut unhashed = entry.clone();
+    unhashed.hash = None;
+
+    let bytes = serde_json::to_vec(&unhashed)
+        .map_err(|e| CkpError::SerializationError(format!("Failed to serialize audit entry: {}", e)))?;
+
+    Ok(hex::encode(Sha256::digest(&bytes)))
+}
+
+/// Outcome of checking one checkpoint entry
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CheckpointCheck {
+    /// Signed by the trusted key
+    Verified,
+
+    /// Signature matches the embedded key, which nothing vouches for
+    Untrusted,
+    Invalid,
+}
+
+fn checkpoint_message(seq: u64, hash: &str) -> String {
+    format!("{}:{}", seq, hash)
+}
+
+impl AuditLogger {
+    /// Rotated segments followed by the active log, oldest first
+    pub fn segments(&self) -> Result<Vec<PathBuf>> {
+        let mut segments = Vec::new();
+
+        // Same naming as rotate_if_needed: {stem}.log.{YYYYmmdd-HHMMSS}[.zst]
+        let rotated = self.log_path.with_extension("log.");
+        if let (Some(parent), Some(name)) = (rotated.parent(), rotated.file_name()) {
+            let prefix = name.to_string_lossy().to_string();
+
+            if parent.exists() {
+                for entry in fs::read_dir(parent)
+                    .map_err(|e| CkpError::IoError(format!("Failed to read log directory: {}", e)))?
+                {
+                    let path = entry
+                        .map_err(|e| CkpError::IoError(format!("Failed to read entry: {}", e)))?
+                        .path();
+                    let file_name = path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+
+                    if let Some(suffix) = file_name.strip_prefix(&prefix) {
+                        let suffix = suffix.strip_suffix(&format!(".{}", COMPRESSED_SEGMENT_EXTENSION)).unwrap_or(suffix);
+                        if suffix.len() == 15 && suffix.chars().all(|c| c.is_ascii_digit() || c == '-') {
+                            segments.push(path);
+                        }
+                    }
+                }
+            }
+        }
+
+        // Timestamp suffix sorts chronologically
+        segments.sort();
+
+        if self.log_path.exists() {
+            segments.push(self.log_path.clone());
+        }
+
+        Ok(segments)
+    }
+
+    /// Path of the sidecar head file
+    pub(super) fn head_path(&self) -> PathBuf {
+        let mut name = self.log_path.file_name().map(|n| n.to_os_string()).unwrap_or_default();
+        name.push(".head");
+        self.log_path.with_file_name(name)
+    }
+
+    /// Head recorded in the sidecar, if any
+    fn recorded_head(&self) -> Result<Option<ChainHead>> {
+        let head_path = self.head_path();
+        if !head_path.exists() {
+            return Ok(None);
+        }
+
+        let content = fs::read_to_string(&head_path)
+            .map_err(|e| CkpError::IoError(format!("Failed to read audit head: {}", e)))?;
+        Ok(Some(serde_json::from_str(&content)?))
+    }
+
+    /// Chain head to append after, re-derived from the tail of the open log
+    ///
+    /// The sidecar is used when the active log has no chained tail (just
+    /// rotated) or when it is ahead of the tail, so a truncated log keeps its
+    /// gap instead of being re-chained over. Without either, segments are scanned.
+    pub(super) fn current_head(&self, log: &mut File) -> Result<Option<ChainHead>> {
+        let tail = read_last_line(log)?
+            .and_then(|line| serde_json::from_str::<AuditEntry>(&line).ok())
+            .and_then(|entry| entry.hash.map(|hash| ChainHead { seq: entry.seq, hash }));
+
+        match (tail, self.recorded_head()?) {
+            (Some(tail), Some(recorded)) if recorded.seq > tail.seq => Ok(Some(recorded)),
+            (Some(tail), _) => Ok(Some(tail)),
+            (None, Some(recorded)) => Ok(Some(recorded)),
+            (None, None) => self.scan_chain_head(),
+        }
+    }
+
+    /// Last chained entry across all segments
+    fn scan_chain_head(&self) -> Result<Option<ChainHead>> {
+        let mut head = None;
+        for segment in self.segments()? {
+            for entry in read_entries(&segment)?.into_iter().flatten() {
+                if let Some(hash) = entry.hash {
+                    head = Some(ChainHead { seq: entry.seq, hash });
+                }
+            }
+        }
+
+        Ok(head)
+    }
+
+    pub(super) fn store_chain_head(&self, head: &ChainHead) -> Result<()> {
+        fs::write(self.head_path(), serde_json::to_string(head)?)
+            .map_err(|e| CkpError::IoError(format!("Failed to write audit head: {}", e)))
+    }
+
+    /// Build a signed checkpoint entry for the given head (None without a signing key)
+    pub(super) fn checkpoint_for(&self, head: &ChainHead) -> Option<(String, serde_json::Value)> {
+        let key: &SigningKey = self.signing_key.as_ref()?;
+        let signature = key.sign(checkpoint_message(head.seq, &head.hash).as_bytes());
+
+        Some((CHECKPOINT_OPERATION.to_string(), serde_json::json!({
+            "seq": head.seq,
+            "hash": head.hash,
+            "signature": hex::encode(signature.to_bytes()),
+            "publicKey": hex::encode(key.verifying_key().to_bytes()),
+        })))
+    }
+
+    /// Verify the hash chain across all segments
+    ///
+    /// Detects modified entries, removed or reordered entries, tail
+    /// truncation (against the `.head` sidecar) and forged checkpoints.
+    /// Checkpoint signatures are checked against the logger's own key. Without
+    /// one, a checkpoint that only matches the key embedded in it is counted as
+    /// untrusted rather than verified, since a forger can embed their own key.
+    pub fn verify_integrity(&self) -> Result<IntegrityReport> {
+        let trusted_key: Option<VerifyingKey> = self.signing_key.as_ref().map(|k| k.verifying_key());
+        let mut report = IntegrityReport {
+            segments: self.segments()?,
+            ..Default::default()
+        };
+
+        let mut previous: Option<ChainHead> = None;
+
+        for segment in report.segments.clone() {
+            for (index, entry) in read_entries(&segment)?.into_iter().enumerate() {
+                let line = index + 1;
+                let entry = match entry {
+                    Some(entry) => entry,
+                    None => {
+                        report.issues.push(IntegrityIssue::Unparseable { segment: segment.clone(), line });
+                        continue;
+                    }
+                };
+
+                let hash = match &entry.hash {
+                    Some(hash) => hash.clone(),
+                    None => {
+                        if previous.is_some() {
+                            report.issues.push(IntegrityIssue::MissingHash { segment: segment.clone(), line });
+                        } else {
+                            report.legacy_entries += 1;
+                        }
+                        continue;
+                    }
+                };
+
+                if entry_hash(&entry)? != hash {
+                    report.issues.push(IntegrityIssue::HashMismatch { segment: segment.clone(), seq: entry.seq });
+                }
+
+                if let Some(prev) = &previous {
+                    if entry.seq != prev.seq + 1 {
+                        report.issues.push(IntegrityIssue::SequenceGap {
+                            segment: segment.clone(),
+                            expected: prev.seq + 1,
+                            found: entry.seq,
+                        });
+                    }
+                    if entry.prev_hash.as_deref() != Some(prev.hash.as_str()) {
+                        report.issues.push(IntegrityIssue::BrokenLink { segment: segment.clone(), seq: entry.seq });
+                    }
+                }
+
+                if entry.operation == CHECKPOINT_OPERATION {
+                    match verify_checkpoint(&entry, trusted_key.as_ref()) {
+                        CheckpointCheck::Verified => report.checkpoints_verified += 1,
+                        CheckpointCheck::Untrusted => report.checkpoints_untrusted += 1,
+                        CheckpointCheck::Invalid => {
+                            report.issues.push(IntegrityIssue::BadCheckpoint { segment: segment.clone(), seq: entry.seq });
+                        }
+                    }
+                }
+
+                report.entries += 1;
+                previous = Some(ChainHead { seq: entry.seq, hash });
+            }
+        }
+
+        // Compare against the sidecar head to detect tail truncation
+        if let Some(recorded) = self.recorded_head()? {
+            match previous {
+                Some(last) if last == recorded => {}
+                Some(last) if last.seq >= recorded.seq => {
+                    report.issues.push(IntegrityIssue::HeadMismatch {
+                        expected_seq: recorded.seq,
+                        last_seq: last.seq,
+                    });
+                }
+                last => {
+                    report.issues.push(IntegrityIssue::Truncated {
+                        expected_seq: recorded.seq,
+                        last_seq: last.map(|p| p.seq),
+                    });
+                }
+            }
+        }
+
+        Ok(report)
+    }
+}
+
+fn verify_checkpoint(entry: &AuditEntry, trusted_key: Option<&VerifyingKey>) -> CheckpointCheck {
+    let field = |name: &str| entry.data.get(name).and_then(|v| v.as_str());
+
+    let (Some(hash), Some(seq), Some(signature)) = (
+        field("hash"),
+        entry.data.get("seq").and_then(|v| v.as_u64()),
+        field("signature").and_then(|s| hex::decode(s).ok()),
+    ) else {
+        return CheckpointCheck::Invalid;
+    };
+
+    // Checkpoint must attest the entry it follows
+    if entry.seq != seq + 1 || entry.prev_hash.as_deref() != Some(hash) {
+        return CheckpointCheck::Invalid;
+    }
+
+    let (key, outcome) = match trusted_key {
+        Some(key) => (*key, CheckpointCheck::Verified),
+        None => {
+            let embedded = field("publicKey")
+                .and_then(|k| hex::decode(k).ok())
+                .and_then(|b| <[u8; 32]>::try_from(b).ok())
+                .and_then(|b| VerifyingKey::from_bytes(&b).ok());
+            match embedded {
+                Some(key) => (key, CheckpointCheck::Untrusted),
+                None => return CheckpointCheck::Invalid,
+            }
+        }
+    };
+
+    let signed = Signature::from_slice(&signature)
+        .map(|sig| key.verify(checkpoint_message(seq, hash).as_bytes(), &sig).is_ok())
+        .unwrap_or(false);
+    if signed {
+        outcome
+    } else {
+        CheckpointCheck::Invalid
+    }
+}
+
+/// Last non-empty line of a file, read backwards from the end
+fn read_last_line(file: &mut File) -> Result<Option<String>> {
+    const CHUNK: u64 = 4096;
+
+    let mut start = file.seek(SeekFrom::End(0))?;
+    let mut buffer: Vec<u8> = Vec::new();
+
+    while start > 0 {
+        let size = CHUNK.min(start);
+        start -= size;
+
+        let mut chunk = vec![0u8; size as usize];
+        file.seek(SeekFrom::Start(start))?;
+        file.read_exact(&mut chunk)?;
+        chunk.extend_from_slice(&buffer);
+        buffer = chunk;
+
+        let content = buffer.trim_ascii_end();
+        if let Some(newline) = content.iter().rposition(|&b| b == b'\n') {
+            return Ok(Some(String::from_utf8_lossy(&content[newline + 1..]).into_owned()));
+        }
+    }
+
+    let content = buffer.trim_ascii_end();
+    Ok((!content.is_empty()).then(|| String::from_utf8_lossy(content).into_owned()))
+}
+
+/// Read a segment, decompressing `.zst` segments; unparseable lines are returned as None
+pub(super) fn read_entries(path: &Path) -> Result<Vec<Option<AuditEntry>>> {
+    let content = if is_compressed(path) {
+        decompress(path)?
+    } else {
+        fs::read_to_string(path)
+            .map_err(|e| CkpError::IoError(format!("Failed to read audit log {}: {}", path.display(), e)))?
+    };
+
+    Ok(content
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| serde_json::from_str(line).ok())
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn write_entries(logger: &AuditLogger, count: usize) {
+        for i in 0..count {
+            logger.log_operation("kernel.emit", Some("alice"), serde_json::json!({"i": i})).unwrap();
+        }
+    }
+
+    fn rewrite_lines(path: &Path, edit: impl FnOnce(&mut Vec<String>)) {
+        let mut lines: Vec<String> = fs::read_to_string(path).unwrap().lines().map(String::from).collect();
+        edit(&mut lines);
+        fs::write(path, lines.join("\n") + "\n").unwrap();
+    }
+
+    #[test]
+    fn test_chain_spans_rotation() {
+        let temp_dir = TempDir::new().unwrap();
+        let log_path = temp_dir.path().join("audit.log");
+        let mut logger = AuditLogger::new(log_path.clone());
+        logger.max_log_size = 100;
+
+        write_entries(&logger, 3);
+        assert!(logger.rotate_if_needed().unwrap());
+        write_entries(&logger, 2);
+
+        let entries: Vec<AuditEntry> = read_entries(&log_path).unwrap().into_iter().flatten().collect();
+        assert_eq!(entries[0].seq, 3);
+        assert!(entries[0].prev_hash.is_some());
+
+        let report = logger.verify_integrity().unwrap();
+        assert_eq!(report.segments.len(), 2);
+        assert_eq!(report.entries, 5);
+        assert!(report.is_valid(), "{:?}", report.issues);
+
+        // A fresh logger resumes the chain from the sidecar head
+        write_entries(&AuditLogger::new(log_path), 1);
+        assert!(logger.verify_integrity().unwrap().is_valid());
+    }
+
+    #[test]
+    fn test_loggers_sharing_a_log_continue_from_its_tail() {
+        let temp_dir = TempDir::new().unwrap();
+        let log_path = temp_dir.path().join("audit.log");
+        let first = AuditLogger::new(log_path.clone());
+        let second = AuditLogger::new(log_path);
+
+        write_entries(&first, 2);
+        write_entries(&second, 2);
+        write_entries(&first, 1);
+
+        let report = first.verify_integrity().unwrap();
+        assert_eq!(report.entries, 5);
+        assert!(report.is_valid(), "{:?}", report.issues);
+    }
+
+    #[test]
+    fn test_detects_modification_and_removal() {
+        let temp_dir = TempDir::new().unwrap();
+        let log_path = temp_dir.path().join("audit.log");
+        let logger = AuditLogger::new(log_path.clone());
+        write_entries(&logger, 4);
+
+        let original = fs::read_to_string(&log_path).unwrap();
+
+        rewrite_lines(&log_path, |lines| lines[1] = lines[1].replace("alice", "mallory"));
+        let report = logger.verify_integrity().unwrap();
+        assert!(matches!(report.issues[..], [IntegrityIssue::HashMismatch { seq: 1, .. }]));
+
+        fs::write(&log_path, &original).unwrap();
+        rewrite_lines(&log_path, |lines| { lines.remove(2); });
+        let report = logger.verify_integrity().unwrap();
+        assert!(report.issues.iter().any(|i| matches!(i, IntegrityIssue::SequenceGap { expected: 2, found: 3, .. })));
+        assert!(report.issues.iter().any(|i| matches!(i, IntegrityIssue::BrokenLink { seq: 3, .. })));
+    }
+
+    #[test]
+    fn test_detects_truncation() {
+        let temp_dir = TempDir::new().unwrap();
+        let log_path = temp_dir.path().join("audit.log");
+        let logger = AuditLogger::new(log_path.clone());
+        write_entries(&logger, 3);
+
+        rewrite_lines(&log_path, |lines| { lines.pop(); });
+
+        let report = logger.verify_integrity().unwrap();
+        assert_eq!(report.issues, vec![IntegrityIssue::Truncated { expected_seq: 2, last_seq: Some(1) }]);
+    }
+
+    #[test]
+    fn test_signed_checkpoints() {
+        let temp_dir = TempDir::new().unwrap();
+        let log_path = temp_dir.path().join("audit.log");
+        let logger = AuditLogger::new(log_path.clone())
+            .with_signing_key(SigningKey::from_bytes(&[9u8; 32]))
+            .with_checkpoint_interval(3);
+        write_entries(&logger, 5);
+
+        // Checkpoints occupy seq 3 and 6
+        let report = logger.verify_integrity().unwrap();
+        assert_eq!(report.checkpoints_verified, 2);
+        assert_eq!(report.entries, 7);
+        assert!(report.is_valid());
+
+        // Without a trusted key the embedded key proves nothing
+        let report = AuditLogger::new(log_path.clone()).verify_integrity().unwrap();
+        assert_eq!(report.checkpoints_verified, 0);
+        assert_eq!(report.checkpoints_untrusted, 2);
+
+        // Verifying with a different trusted key rejects the checkpoints
+        let other = AuditLogger::new(log_path).with_signing_key(SigningKey::from_bytes(&[1u8; 32]));
+        let report = other.verify_integrity().unwrap();
+        assert_eq!(report.checkpoints_verified, 0);
+        assert!(report.issues.iter().all(|i| matches!(i, IntegrityIssue::BadCheckpoint { .. })));
+    }
+}
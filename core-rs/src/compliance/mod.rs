@@ -2,14 +2,23 @@
 //!
 //! Provides:
 //! - Audit log generation with sensitive data redaction
-//! - GDPR compliance checks (consent, access, erasure, portability)
+//! - GDPR compliance checks (per-purpose consent, access, erasure, portability)
+//! - Cascading erasure across kernel storage and archives
+//! - Data subject access requests fanned out across kernels via edges
 //! - Data retention policies with archival
 //! - Privacy controls
+//! - Tamper-evident audit chain with integrity verification
+//! - Audit log queries across rotated segments
+//! - zstd compression, upload and indexing of rotated audit segments
+//! - Configurable redaction rules for audit logs and exports
+//! - Data portability bundles (JSON/NDJSON/CSV/Parquet in a zip)
+//! - Pseudonymization and anonymization of identifier fields
+//! - Audit forwarding to syslog/CEF collectors and HTTPS webhooks
 //!
 //! # Example
 //!
 //! ```rust
-//! use ckp_core::compliance::{AuditLogger, GdprChecker, RetentionPolicy};
+//! use ckp_core::compliance::{AuditLogger, ConsentPurpose, GdprChecker, RetentionPolicy};
 //! use std::path::PathBuf;
 //!
 //! // Audit logging
@@ -18,52 +27,143 @@
 //!
 //! // GDPR compliance
 //! let mut gdpr = GdprChecker::new();
-//! gdpr.record_consent("user123", true);
-//! assert!(gdpr.check_consent("user123").unwrap());
+//! gdpr.record_consent("user123", ConsentPurpose::Analytics, true);
+//! assert!(gdpr.check_consent("user123", ConsentPurpose::Analytics).unwrap());
 //!
 //! // Retention policy
 //! let policy = RetentionPolicy::new(90, PathBuf::from("/concepts/archive"));
 //! policy.check_expired_data(PathBuf::from("/concepts"));
 //! ```
 
+pub mod archival;
+pub mod dsar;
+pub mod erasure;
+pub mod export;
+pub mod integrity;
+pub mod pseudonymize;
+pub mod query;
+pub mod redaction;
+pub mod sinks;
+
+pub use archival::{SegmentIndexEntry, AUDIT_ARCHIVE_KERNEL, COMPRESSED_SEGMENT_EXTENSION};
+pub use dsar::{DsarPackage, DsarRequest, DsarWorkflow};
+pub use erasure::{ErasedArtifact, ErasureEngine, ErasureMode, ErasureReport, KERNEL_DATA_DIRS};
+pub use export::{ExportBundle, ExportFormat, ExportManifest, PortabilityExporter, SubjectRecord};
+pub use integrity::{entry_hash, ChainHead, IntegrityIssue, IntegrityReport, CHECKPOINT_OPERATION};
+pub use pseudonymize::{PseudonymMode, PseudonymVault, PseudonymizationReport, Pseudonymizer};
+pub use query::{AuditFilters, AuditPage, DEFAULT_AUDIT_PAGE_SIZE};
+pub use redaction::{RedactionConfig, RedactionRules, HASHED_PREFIX, REDACTED};
+pub use sinks::{AuditSink, SyslogFormat, SyslogSink, SyslogTransport, WebhookSink};
+
+use crate::drivers::StorageDriver;
 use crate::errors::{CkpError, Result};
+use crate::ontology::{OntologyReader, RetentionContract};
+use crate::storage::walk::{par_map, walk, Visit};
 use chrono::{DateTime, Duration, Utc};
+use ed25519_dalek::SigningKey;
 use serde::{Deserialize, Serialize};
 use serde_json::Value as JsonValue;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+/// Default number of entries between signed checkpoints
+pub const DEFAULT_CHECKPOINT_INTERVAL: u64 = 100;
 
 /// Audit logger for recording kernel operations
 pub struct AuditLogger {
     log_path: PathBuf,
     max_log_size: u64, // bytes
+    append_lock: Mutex<()>,
+    signing_key: Option<SigningKey>,
+    checkpoint_interval: u64,
+    redaction: RedactionRules,
+    sinks: Vec<Arc<dyn AuditSink>>,
+    compress_rotated: bool,
+    archive_driver: Option<Arc<dyn StorageDriver>>,
 }
 
 /// Audit log entry
+///
+/// `seq`, `prev_hash` and `hash` chain entries together; entries written
+/// before chaining was introduced deserialize with `hash: None`.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AuditEntry {
+    #[serde(default)]
+    pub seq: u64,
     pub timestamp: DateTime<Utc>,
     pub operation: String,
     pub user_id: Option<String>,
     pub data: JsonValue,
     pub redacted: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prev_hash: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hash: Option<String>,
 }
 
 /// GDPR compliance checker
 pub struct GdprChecker {
     consent_records: HashMap<String, ConsentRecord>,
+    redaction: Option<RedactionRules>,
+}
+
+/// Purpose a subject's data is processed for
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ConsentPurpose {
+    /// Core processing needed to provide the service
+    Processing,
+    Analytics,
+    ModelTraining,
+    Communication,
 }
 
-/// Consent record for GDPR compliance
+/// GDPR Art. 6 lawful basis for processing
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum LawfulBasis {
+    #[default]
+    Consent,
+    Contract,
+    LegalObligation,
+    VitalInterests,
+    PublicTask,
+    LegitimateInterests,
+}
+
+/// Consent state for one purpose
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct ConsentRecord {
-    pub user_id: String,
+pub struct PurposeConsent {
     pub consented: bool,
+    #[serde(default)]
+    pub lawful_basis: LawfulBasis,
     pub timestamp: DateTime<Utc>,
     pub expiry: Option<DateTime<Utc>>,
 }
 
+impl PurposeConsent {
+    /// Whether processing is permitted now
+    ///
+    /// Under a basis other than consent the `consented` flag is not required,
+    /// but an expiry still bounds the processing.
+    pub fn permits(&self) -> bool {
+        if self.expiry.map(|expiry| Utc::now() > expiry).unwrap_or(false) {
+            return false;
+        }
+        self.consented || self.lawful_basis != LawfulBasis::Consent
+    }
+}
+
+/// Consent record for GDPR compliance, one entry per purpose
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConsentRecord {
+    pub user_id: String,
+    #[serde(default)]
+    pub purposes: BTreeMap<ConsentPurpose, PurposeConsent>,
+}
+
 /// Data retention policy manager
 pub struct RetentionPolicy {
     retention_days: i64,
@@ -71,6 +171,22 @@ pub struct RetentionPolicy {
     exceptions: Vec<String>, // Kernel names exempt from retention
 }
 
+/// Serializable retention settings, e.g. `spec.retention` in `.ckproject`
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct RetentionConfig {
+    /// Days after which storage and archived jobs expire
+    pub days: i64,
+
+    /// Archive directory, relative to the project root (default: concepts/.archive/retention)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub archive_path: Option<String>,
+
+    /// Kernel names exempt from retention
+    #[serde(default)]
+    pub exceptions: Vec<String>,
+}
+
 /// Retention check result
 #[derive(Debug, Clone)]
 pub struct RetentionCheckResult {
@@ -101,17 +217,53 @@ impl AuditLogger {
         Self {
             log_path,
             max_log_size: 10_000_000, // 10MB default
+            append_lock: Mutex::new(()),
+            signing_key: None,
+            checkpoint_interval: DEFAULT_CHECKPOINT_INTERVAL,
+            redaction: RedactionRules::default(),
+            sinks: Vec::new(),
+            compress_rotated: true,
+            archive_driver: None,
         }
     }
 
+    /// Replace the rules used by `log_with_redaction`
+    pub fn with_redaction_rules(mut self, rules: RedactionRules) -> Self {
+        self.redaction = rules;
+        self
+    }
+
+    /// Sign periodic checkpoints with an Ed25519 key
+    pub fn with_signing_key(mut self, signing_key: SigningKey) -> Self {
+        self.signing_key = Some(signing_key);
+        self
+    }
+
+    /// Set the number of entries between signed checkpoints (0 disables)
+    pub fn with_checkpoint_interval(mut self, interval: u64) -> Self {
+        self.checkpoint_interval = interval;
+        self
+    }
+
+    /// Forward every written entry (including checkpoints) to a sink
+    ///
+    /// Sink failures are reported but never fail the local append.
+    pub fn with_sink(mut self, sink: Arc<dyn AuditSink>) -> Self {
+        self.sinks.push(sink);
+        self
+    }
+
     /// Log a kernel operation
     pub fn log_operation(&self, operation: &str, user_id: Option<&str>, data: JsonValue) -> Result<()> {
         let entry = AuditEntry {
+            seq: 0,
             timestamp: Utc::now(),
             operation: operation.to_string(),
             user_id: user_id.map(|s| s.to_string()),
             data,
             redacted: false,
+            prev_hash: None,
+            hash: None,
         };
 
         self.write_entry(entry)
@@ -124,28 +276,27 @@ impl AuditLogger {
 
     /// Log with sensitive data redaction
     pub fn log_with_redaction(&self, operation: &str, user_id: Option<&str>, mut data: JsonValue) -> Result<()> {
-        // Redact sensitive fields
-        if let Some(obj) = data.as_object_mut() {
-            let sensitive_fields = ["password", "token", "secret", "api_key", "credit_card"];
-            for field in &sensitive_fields {
-                if obj.contains_key(*field) {
-                    obj.insert(field.to_string(), JsonValue::String("[REDACTED]".to_string()));
-                }
-            }
-        }
+        // Redact sensitive fields and values (recursively)
+        self.redaction.apply(&mut data);
 
         let entry = AuditEntry {
+            seq: 0,
             timestamp: Utc::now(),
             operation: operation.to_string(),
             user_id: user_id.map(|s| s.to_string()),
             data,
             redacted: true,
+            prev_hash: None,
+            hash: None,
         };
 
         self.write_entry(entry)
     }
 
     /// Rotate log file if it exceeds max size
+    ///
+    /// The rotated segment is compressed, uploaded and indexed as configured
+    /// (see `with_compression` and `with_archive_driver`).
     pub fn rotate_if_needed(&self) -> Result<bool> {
         if !self.log_path.exists() {
             return Ok(false);
@@ -160,6 +311,7 @@ impl AuditLogger {
 
             fs::rename(&self.log_path, &rotated_path)
                 .map_err(|e| CkpError::IoError(format!("Failed to rotate log: {}", e)))?;
+            self.archive_segment(&rotated_path)?;
 
             Ok(true)
         } else {
@@ -174,22 +326,80 @@ impl AuditLogger {
                 .map_err(|e| CkpError::IoError(format!("Failed to create log directory: {}", e)))?;
         }
 
-        // Serialize entry
-        let json = serde_json::to_string(&entry)
-            .map_err(|e| CkpError::SerializationError(format!("Failed to serialize audit entry: {}", e)))?;
+        let _guard = self.append_lock.lock()
+            .map_err(|_| CkpError::IoError("Audit chain lock poisoned".to_string()))?;
 
-        // Append to log file (JSONL format)
-        use std::io::Write;
         let mut file = fs::OpenOptions::new()
             .create(true)
+            .read(true)
             .append(true)
             .open(&self.log_path)
             .map_err(|e| CkpError::IoError(format!("Failed to open log file: {}", e)))?;
 
+        // Other loggers and processes append to the same log: hold its lock
+        // and continue from what is on disk, not from a cached head
+        #[cfg(unix)]
+        {
+            use std::os::unix::io::AsRawFd;
+
+            // Closing the file releases the lock
+            unsafe {
+                if libc::flock(file.as_raw_fd(), libc::LOCK_EX) != 0 {
+                    return Err(CkpError::Io(std::io::Error::last_os_error()));
+                }
+            }
+        }
+
+        let head = self.current_head(&mut file)?;
+        let written = self.append_chained(&mut file, entry, head.as_ref())?;
+
+        // Periodic signed checkpoint over the entry just written
+        if self.checkpoint_interval > 0 && (written.seq + 1) % self.checkpoint_interval == 0 {
+            if let Some((operation, data)) = self.checkpoint_for(&written) {
+                let checkpoint = AuditEntry {
+                    seq: 0,
+                    timestamp: Utc::now(),
+                    operation,
+                    user_id: None,
+                    data,
+                    redacted: false,
+                    prev_hash: None,
+                    hash: None,
+                };
+                self.append_chained(&mut file, checkpoint, Some(&written))?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Link an entry to `previous`, append it and advance the sidecar head
+    fn append_chained(&self, file: &mut fs::File, mut entry: AuditEntry, previous: Option<&ChainHead>) -> Result<ChainHead> {
+        entry.seq = previous.map(|p| p.seq + 1).unwrap_or(0);
+        entry.prev_hash = previous.map(|p| p.hash.clone());
+        entry.hash = None;
+        let hash = entry_hash(&entry)?;
+        entry.hash = Some(hash.clone());
+
+        // Serialize entry
+        let json = serde_json::to_string(&entry)
+            .map_err(|e| CkpError::SerializationError(format!("Failed to serialize audit entry: {}", e)))?;
+
+        // Append to log file (JSONL format)
+        use std::io::Write;
         writeln!(file, "{}", json)
             .map_err(|e| CkpError::IoError(format!("Failed to write log entry: {}", e)))?;
 
-        Ok(())
+        let head = ChainHead { seq: entry.seq, hash };
+        self.store_chain_head(&head)?;
+
+        for sink in &self.sinks {
+            if let Err(e) = sink.send(&entry) {
+                tracing::warn!(sink = sink.name(), error = %e, "audit sink failed");
+            }
+        }
+
+        Ok(head)
     }
 }
 
@@ -198,51 +408,73 @@ impl GdprChecker {
     pub fn new() -> Self {
         Self {
             consent_records: HashMap::new(),
+            redaction: None,
         }
     }
 
+    /// Redact data-portability exports with the given rules
+    pub fn with_redaction_rules(mut self, rules: RedactionRules) -> Self {
+        self.redaction = Some(rules);
+        self
+    }
+
     /// Record user consent
-    pub fn record_consent(&mut self, user_id: &str, consented: bool) {
-        let record = ConsentRecord {
-            user_id: user_id.to_string(),
+    pub fn record_consent(&mut self, user_id: &str, purpose: ConsentPurpose, consented: bool) {
+        self.record_purpose(user_id, purpose, PurposeConsent {
             consented,
+            lawful_basis: LawfulBasis::Consent,
             timestamp: Utc::now(),
             expiry: None,
-        };
-        self.consent_records.insert(user_id.to_string(), record);
+        });
     }
 
-    /// Record consent with expiry
-    pub fn record_consent_with_expiry(&mut self, user_id: &str, consented: bool, expiry_days: i64) {
-        let record = ConsentRecord {
-            user_id: user_id.to_string(),
+    /// Record consent for a purpose with expiry
+    pub fn record_consent_with_expiry(&mut self, user_id: &str, purpose: ConsentPurpose, consented: bool, expiry_days: i64) {
+        self.record_purpose(user_id, purpose, PurposeConsent {
             consented,
+            lawful_basis: LawfulBasis::Consent,
             timestamp: Utc::now(),
             expiry: Some(Utc::now() + Duration::days(expiry_days)),
-        };
-        self.consent_records.insert(user_id.to_string(), record);
+        });
+    }
+
+    /// Record a lawful basis other than consent for a purpose (e.g. contract)
+    pub fn record_lawful_basis(&mut self, user_id: &str, purpose: ConsentPurpose, lawful_basis: LawfulBasis) {
+        self.record_purpose(user_id, purpose, PurposeConsent {
+            consented: false,
+            lawful_basis,
+            timestamp: Utc::now(),
+            expiry: None,
+        });
+    }
+
+    /// Consent record of a user, if any
+    pub fn consent_record(&self, user_id: &str) -> Option<&ConsentRecord> {
+        self.consent_records.get(user_id)
     }
 
-    /// Check if user has given consent
-    pub fn check_consent(&self, user_id: &str) -> Result<bool> {
+    /// Check if processing for a purpose is permitted
+    ///
+    /// A purpose the user was never asked about is not permitted.
+    pub fn check_consent(&self, user_id: &str, purpose: ConsentPurpose) -> Result<bool> {
         match self.consent_records.get(user_id) {
-            Some(record) => {
-                // Check if consent has expired
-                if let Some(expiry) = record.expiry {
-                    if Utc::now() > expiry {
-                        return Ok(false);
-                    }
-                }
-                Ok(record.consented)
-            }
+            Some(record) => Ok(record.purposes.get(&purpose).map(|p| p.permits()).unwrap_or(false)),
             None => Err(CkpError::ValidationError(format!("No consent record for user: {}", user_id))),
         }
     }
 
+    fn record_purpose(&mut self, user_id: &str, purpose: ConsentPurpose, consent: PurposeConsent) {
+        self.consent_records
+            .entry(user_id.to_string())
+            .or_insert_with(|| ConsentRecord { user_id: user_id.to_string(), purposes: BTreeMap::new() })
+            .purposes
+            .insert(purpose, consent);
+    }
+
     /// GDPR Right of Access - retrieve all user data
     pub fn data_access_request(&self, user_id: &str, data_sources: Vec<JsonValue>) -> Result<DataAccessResult> {
         // Verify consent
-        if !self.check_consent(user_id)? {
+        if !self.check_consent(user_id, ConsentPurpose::Processing)? {
             return Err(CkpError::ValidationError(format!("User {} has not consented to data processing", user_id)));
         }
 
@@ -258,18 +490,31 @@ impl GdprChecker {
         // Remove consent record
         self.consent_records.remove(user_id);
 
-        // In production, this would trigger cascading deletion
-        // across all kernels that store user data
+        // Stored data is left untouched; see right_to_erasure_cascade
         Ok(())
     }
 
+    /// GDPR Right to Erasure - remove consent and erase the user's artifacts
+    ///
+    /// # Returns
+    /// Erasure report; its `urn` identifies the persisted proof instance
+    pub fn right_to_erasure_cascade(&mut self, user_id: &str, engine: &ErasureEngine) -> Result<ErasureReport> {
+        let report = engine.erase_subject(user_id)?;
+        self.right_to_erasure(user_id)?;
+        Ok(report)
+    }
+
     /// GDPR Data Portability - export user data in portable format
-    pub fn data_portability(&self, user_id: &str, data: JsonValue, format: &str) -> Result<DataPortabilityExport> {
+    pub fn data_portability(&self, user_id: &str, mut data: JsonValue, format: &str) -> Result<DataPortabilityExport> {
         // Verify consent
-        if !self.check_consent(user_id)? {
+        if !self.check_consent(user_id, ConsentPurpose::Processing)? {
             return Err(CkpError::ValidationError(format!("User {} has not consented to data processing", user_id)));
         }
 
+        if let Some(rules) = &self.redaction {
+            rules.apply(&mut data);
+        }
+
         Ok(DataPortabilityExport {
             user_id: user_id.to_string(),
             export_format: format.to_string(),
@@ -277,6 +522,30 @@ impl GdprChecker {
             timestamp: Utc::now(),
         })
     }
+
+    /// GDPR Right of Access - open and run a DSAR across the project's kernels
+    ///
+    /// Access does not depend on consent; the consent record, if any, is
+    /// included in the package and configured redaction rules are applied.
+    pub fn data_subject_access_request(&self, user_id: &str, workflow: &DsarWorkflow) -> Result<DsarPackage> {
+        let mut request = workflow.open(user_id)?;
+        workflow.execute(&mut request, self.consent_records.get(user_id).cloned(), self.redaction.as_ref())
+    }
+
+    /// GDPR Data Portability - gather the user's data across kernels into a zip bundle
+    pub fn data_portability_bundle(
+        &self,
+        user_id: &str,
+        exporter: &PortabilityExporter,
+        output_dir: &Path,
+    ) -> Result<ExportBundle> {
+        // Verify consent
+        if !self.check_consent(user_id, ConsentPurpose::Processing)? {
+            return Err(CkpError::ValidationError(format!("User {} has not consented to data processing", user_id)));
+        }
+
+        exporter.export(user_id, output_dir)
+    }
 }
 
 impl Default for GdprChecker {
@@ -295,55 +564,52 @@ impl RetentionPolicy {
         }
     }
 
+    /// Create a policy from project configuration
+    pub fn from_config(config: &RetentionConfig, project_root: &Path) -> Self {
+        let archive_path = match &config.archive_path {
+            Some(path) => project_root.join(path),
+            None => project_root.join("concepts/.archive/retention"),
+        };
+
+        let mut policy = Self::new(config.days, archive_path);
+        for kernel in &config.exceptions {
+            policy.add_exception(kernel.clone());
+        }
+        policy
+    }
+
     /// Add kernel exception (exempt from retention)
     pub fn add_exception(&mut self, kernel_name: String) {
         self.exceptions.push(kernel_name);
     }
 
     /// Check for expired data
+    ///
+    /// Kernels are discovered recursively below `concepts_path` (any directory
+    /// with `conceptkernel.yaml`, `storage/` or `queue/`), so nested layouts such
+    /// as `concepts/{Domain}/{Kernel}` are covered. A kernel's
+    /// `spec.retention_contract` replaces the policy's retention days and adds
+    /// its own exceptions.
     pub fn check_expired_data(&self, concepts_path: PathBuf) -> Result<RetentionCheckResult> {
-        let cutoff_date = Utc::now() - Duration::days(self.retention_days);
-        let mut expired_files = Vec::new();
-        let mut total_size = 0u64;
-
-        if !concepts_path.exists() {
-            return Ok(RetentionCheckResult {
-                expired_files,
-                total_size,
-            });
-        }
+        let mut result = RetentionCheckResult {
+            expired_files: Vec::new(),
+            total_size: 0,
+        };
 
-        // Walk through concepts directory
-        for entry in fs::read_dir(&concepts_path)
-            .map_err(|e| CkpError::IoError(format!("Failed to read concepts directory: {}", e)))?
-        {
-            let entry = entry.map_err(|e| CkpError::IoError(format!("Failed to read entry: {}", e)))?;
-            let path = entry.path();
+        if concepts_path.exists() {
+            let mut kernels = Vec::new();
+            self.find_kernels(&concepts_path, &mut kernels)?;
 
-            // Skip exceptions
-            if let Some(name) = path.file_name() {
-                if self.exceptions.iter().any(|e| name.to_string_lossy().contains(e)) {
-                    continue;
+            // Kernels are checked in parallel; results keep kernel order
+            for expired in par_map(kernels, |kernel| self.expired_in_kernel(&kernel)) {
+                for (path, size) in expired? {
+                    result.expired_files.push(path);
+                    result.total_size += size;
                 }
             }
-
-            // Check storage directory
-            let storage_path = path.join("storage");
-            if storage_path.exists() {
-                self.scan_directory(&storage_path, cutoff_date, &mut expired_files, &mut total_size)?;
-            }
-
-            // Check archive directory
-            let archive_path = path.join("queue/archive");
-            if archive_path.exists() {
-                self.scan_directory(&archive_path, cutoff_date, &mut expired_files, &mut total_size)?;
-            }
         }
 
-        Ok(RetentionCheckResult {
-            expired_files,
-            total_size,
-        })
+        Ok(result)
     }
 
     /// Archive old data
@@ -353,14 +619,22 @@ impl RetentionPolicy {
         }
 
         // Create archive directory
-        fs::create_dir_all(&self.archive_path)
+        let archive_dir = self.archive_dir_for(&source_path);
+        fs::create_dir_all(&archive_dir)
             .map_err(|e| CkpError::IoError(format!("Failed to create archive directory: {}", e)))?;
 
-        // Generate archive filename with timestamp
+        // Generate archive filename with timestamp; instances share file names
+        // (payload.json), so never overwrite an earlier archive
         let timestamp = Utc::now().format("%Y%m%d-%H%M%S");
         let filename = source_path.file_name()
-            .ok_or_else(|| CkpError::Path("Invalid source path".to_string()))?;
-        let archive_file = self.archive_path.join(format!("{}.{}", filename.to_string_lossy(), timestamp));
+            .ok_or_else(|| CkpError::Path("Invalid source path".to_string()))?
+            .to_string_lossy();
+        let mut archive_file = archive_dir.join(format!("{}.{}", filename, timestamp));
+        let mut suffix = 1;
+        while archive_file.exists() {
+            archive_file = archive_dir.join(format!("{}.{}.{}", filename, timestamp, suffix));
+            suffix += 1;
+        }
 
         // Copy file to archive
         fs::copy(&source_path, &archive_file)
@@ -383,41 +657,108 @@ impl RetentionPolicy {
         Ok(())
     }
 
-    fn scan_directory(
-        &self,
-        dir_path: &PathBuf,
-        cutoff_date: DateTime<Utc>,
-        expired_files: &mut Vec<PathBuf>,
-        total_size: &mut u64,
-    ) -> Result<()> {
-        if !dir_path.exists() {
-            return Ok(());
-        }
+    /// Archive directory for a file: the policy archive root, or the
+    /// `retention_contract.archive_path` of the kernel holding the file
+    /// (relative to the archive root unless absolute)
+    fn archive_dir_for(&self, source_path: &Path) -> PathBuf {
+        source_path
+            .ancestors()
+            .skip(1)
+            .find(|dir| dir.join("conceptkernel.yaml").exists())
+            .and_then(kernel_retention_contract)
+            .and_then(|contract| contract.archive_path)
+            .map(|path| self.archive_path.join(path))
+            .unwrap_or_else(|| self.archive_path.clone())
+    }
 
-        for entry in fs::read_dir(dir_path)
-            .map_err(|e| CkpError::IoError(format!("Failed to read directory: {}", e)))?
+    /// Kernel directories under `dir`, including nested `{Domain}/{Kernel}` ones
+    fn find_kernels(&self, dir: &Path, kernels: &mut Vec<PathBuf>) -> Result<()> {
+        for entry in fs::read_dir(dir)
+            .map_err(|e| CkpError::IoError(format!("Failed to read concepts directory: {}", e)))?
         {
             let entry = entry.map_err(|e| CkpError::IoError(format!("Failed to read entry: {}", e)))?;
             let path = entry.path();
+            if !path.is_dir() {
+                continue;
+            }
+            let name = entry.file_name().to_string_lossy().to_string();
+
+            // Skip exceptions
+            if self.exceptions.iter().any(|e| name.contains(e)) {
+                continue;
+            }
+
+            let is_kernel = ["conceptkernel.yaml", "storage", "queue"].iter().any(|p| path.join(p).exists());
+            if is_kernel {
+                kernels.push(path);
+            } else if !name.starts_with('.') {
+                // Hidden dirs (.archive, .continuants) never hold kernels
+                self.find_kernels(&path, kernels)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Expired files of one kernel, with their sizes
+    fn expired_in_kernel(&self, path: &Path) -> Result<Vec<(PathBuf, u64)>> {
+        let contract = kernel_retention_contract(path);
+        let days = contract.as_ref().map(|c| c.days).unwrap_or(self.retention_days);
+        let exceptions = contract.map(|c| c.exceptions).unwrap_or_default();
+        let cutoff_date = Utc::now() - Duration::days(days);
+
+        let mut expired = Vec::new();
+
+        // Check storage and archive directories
+        for sub in KERNEL_DATA_DIRS {
+            let dir = path.join(sub);
+            if !dir.exists() {
+                continue;
+            }
+
+            expired.extend(walk(&dir, &|entry| {
+                // Kernel-declared exceptions are entry name prefixes
+                let name = entry.file_name().to_string_lossy().to_string();
+                if exceptions.iter().any(|prefix| name.starts_with(prefix.as_str())) {
+                    return Ok(Visit::Skip);
+                }
+
+                let path = entry.path();
+                if path.is_dir() {
+                    return Ok(Visit::Descend);
+                }
+                if !path.is_file() {
+                    return Ok(Visit::Skip);
+                }
 
-            if path.is_file() {
                 let metadata = fs::metadata(&path)
                     .map_err(|e| CkpError::IoError(format!("Failed to read metadata: {}", e)))?;
-
-                if let Ok(modified) = metadata.modified() {
-                    let modified_dt: DateTime<Utc> = modified.into();
-                    if modified_dt < cutoff_date {
-                        expired_files.push(path);
-                        *total_size += metadata.len();
+                match metadata.modified() {
+                    Ok(modified) if DateTime::<Utc>::from(modified) < cutoff_date => {
+                        Ok(Visit::Yield((path, metadata.len())))
                     }
+                    _ => Ok(Visit::Skip),
                 }
-            } else if path.is_dir() {
-                // Recursively scan subdirectories
-                self.scan_directory(&path, cutoff_date, expired_files, total_size)?;
-            }
+            })?);
         }
 
-        Ok(())
+        Ok(expired)
+    }
+}
+
+/// `spec.retention_contract` declared in a kernel's conceptkernel.yaml
+fn kernel_retention_contract(kernel_path: &Path) -> Option<RetentionContract> {
+    let ontology_path = kernel_path.join("conceptkernel.yaml");
+    if !ontology_path.exists() {
+        return None;
+    }
+
+    match OntologyReader::new(kernel_path.to_path_buf()).read(&ontology_path) {
+        Ok(ontology) => ontology.spec.and_then(|spec| spec.retention_contract),
+        Err(e) => {
+            tracing::info!(kernel = %kernel_path.display(), error = %e, "unreadable ontology, using project retention policy");
+            None
+        }
     }
 }
 
@@ -551,21 +892,21 @@ mod tests {
     fn test_gdpr_consent_verification() {
         let mut checker = GdprChecker::new();
 
-        checker.record_consent("user123", true);
-        assert!(checker.check_consent("user123").unwrap());
+        checker.record_consent("user123", ConsentPurpose::Processing, true);
+        assert!(checker.check_consent("user123", ConsentPurpose::Processing).unwrap());
 
-        checker.record_consent("user456", false);
-        assert!(!checker.check_consent("user456").unwrap());
+        checker.record_consent("user456", ConsentPurpose::Processing, false);
+        assert!(!checker.check_consent("user456", ConsentPurpose::Processing).unwrap());
 
         // No consent record
-        let result = checker.check_consent("user789");
+        let result = checker.check_consent("user789", ConsentPurpose::Processing);
         assert!(result.is_err());
     }
 
     #[test]
     fn test_gdpr_data_access_request() {
         let mut checker = GdprChecker::new();
-        checker.record_consent("alice", true);
+        checker.record_consent("alice", ConsentPurpose::Processing, true);
 
         let data_sources = vec![
             serde_json::json!({"kernel": "Profile", "data": {"name": "Alice"}}),
@@ -583,23 +924,23 @@ mod tests {
     #[test]
     fn test_gdpr_right_to_erasure() {
         let mut checker = GdprChecker::new();
-        checker.record_consent("user123", true);
+        checker.record_consent("user123", ConsentPurpose::Processing, true);
 
-        assert!(checker.check_consent("user123").is_ok());
+        assert!(checker.check_consent("user123", ConsentPurpose::Processing).is_ok());
 
         // Exercise right to erasure
         let result = checker.right_to_erasure("user123");
         assert!(result.is_ok());
 
         // Consent record should be removed
-        let consent_check = checker.check_consent("user123");
+        let consent_check = checker.check_consent("user123", ConsentPurpose::Processing);
         assert!(consent_check.is_err());
     }
 
     #[test]
     fn test_gdpr_data_portability() {
         let mut checker = GdprChecker::new();
-        checker.record_consent("bob", true);
+        checker.record_consent("bob", ConsentPurpose::Processing, true);
 
         let user_data = serde_json::json!({
             "profile": {"name": "Bob", "email": "bob@example.com"},
@@ -615,27 +956,67 @@ mod tests {
         assert_eq!(export.data, user_data);
     }
 
+    #[test]
+    fn test_gdpr_data_portability_redaction() {
+        let rules = RedactionRules::new().with_field("internal_notes");
+        let mut checker = GdprChecker::new().with_redaction_rules(rules);
+        checker.record_consent("bob", ConsentPurpose::Processing, true);
+
+        let user_data = serde_json::json!({
+            "orders": [{"id": "123", "internal_notes": "flagged"}]
+        });
+
+        let export = checker.data_portability("bob", user_data, "json").unwrap();
+        assert_eq!(export.data["orders"][0]["internal_notes"], "[REDACTED]");
+        assert_eq!(export.data["orders"][0]["id"], "123");
+    }
+
     #[test]
     fn test_gdpr_consent_expiry() {
         let mut checker = GdprChecker::new();
 
         // Record consent with short expiry
-        checker.record_consent_with_expiry("temp_user", true, -1); // Expired yesterday
+        checker.record_consent_with_expiry("temp_user", ConsentPurpose::Processing, true, -1); // Expired yesterday
 
         // Consent should be expired
-        let result = checker.check_consent("temp_user");
+        let result = checker.check_consent("temp_user", ConsentPurpose::Processing);
         assert!(result.is_ok());
         assert!(!result.unwrap()); // Should be false due to expiry
 
         // Record valid consent
-        checker.record_consent_with_expiry("valid_user", true, 30); // Expires in 30 days
-        let result = checker.check_consent("valid_user");
+        checker.record_consent_with_expiry("valid_user", ConsentPurpose::Processing, true, 30); // Expires in 30 days
+        let result = checker.check_consent("valid_user", ConsentPurpose::Processing);
         assert!(result.is_ok());
         assert!(result.unwrap());
     }
 
+    #[test]
+    fn test_gdpr_consent_per_purpose() {
+        let mut checker = GdprChecker::new();
+        checker.record_consent("carol", ConsentPurpose::Analytics, true);
+        checker.record_consent("carol", ConsentPurpose::ModelTraining, false);
+        checker.record_lawful_basis("carol", ConsentPurpose::Processing, LawfulBasis::Contract);
+
+        assert!(checker.check_consent("carol", ConsentPurpose::Analytics).unwrap());
+        assert!(!checker.check_consent("carol", ConsentPurpose::ModelTraining).unwrap());
+        // Never asked
+        assert!(!checker.check_consent("carol", ConsentPurpose::Communication).unwrap());
+        // Contract basis permits processing without consent
+        assert!(checker.check_consent("carol", ConsentPurpose::Processing).unwrap());
+
+        // Withdrawing one purpose leaves the others
+        checker.record_consent("carol", ConsentPurpose::Analytics, false);
+        assert!(!checker.check_consent("carol", ConsentPurpose::Analytics).unwrap());
+        assert!(checker.data_access_request("carol", vec![]).is_ok());
+
+        let record = checker.consent_record("carol").unwrap();
+        assert_eq!(record.purposes.len(), 3);
+        let json = serde_json::to_value(record).unwrap();
+        assert_eq!(json["purposes"]["processing"]["lawful_basis"], "contract");
+    }
+
     // ========================================
-    // Data Retention Policy Tests (5 tests)
+    // Data Retention Policy Tests (6 tests)
     // ========================================
 
     #[test]
@@ -748,4 +1129,33 @@ mod tests {
         assert!(expired_paths.iter().any(|p| p.contains("Normal.Kernel")));
         assert!(!expired_paths.iter().any(|p| p.contains("System.Audit")));
     }
+
+    #[test]
+    fn test_retention_kernel_contract_in_nested_layout() {
+        let temp_dir = TempDir::new().unwrap();
+        let concepts_path = temp_dir.path().join("concepts");
+        let archive_path = temp_dir.path().join("archive");
+
+        // concepts/{Domain}/{Kernel}; Orders keeps data for a year, except pinned-*
+        let orders = concepts_path.join("Shop/Shop.Orders");
+        let carts = concepts_path.join("Shop/Shop.Carts");
+        fs::create_dir_all(orders.join("storage")).unwrap();
+        fs::create_dir_all(carts.join("storage/tx1.inst")).unwrap();
+        fs::write(orders.join("storage/order.json"), "{}").unwrap();
+        fs::write(carts.join("storage/tx1.inst/payload.json"), "{}").unwrap();
+        fs::write(carts.join("storage/pinned-cart.json"), "{}").unwrap();
+        fs::write(orders.join("conceptkernel.yaml"), "apiVersion: conceptkernel/v1\nkind: Ontology\nmetadata:\n  name: Shop.Orders\n  type: node:cold\nspec:\n  retention_contract:\n    days: 365\n").unwrap();
+        fs::write(carts.join("conceptkernel.yaml"), "apiVersion: conceptkernel/v1\nkind: Ontology\nmetadata:\n  name: Shop.Carts\n  type: node:cold\nspec:\n  retention_contract:\n    days: 0\n    archive_path: carts\n    exceptions: [pinned-]\n").unwrap();
+
+        let policy = RetentionPolicy::new(0, archive_path.clone());
+        let result = policy.check_expired_data(concepts_path).unwrap();
+        assert_eq!(result.expired_files, vec![carts.join("storage/tx1.inst/payload.json")]);
+
+        let archived = policy.archive_data(result.expired_files[0].clone()).unwrap();
+        assert_eq!(archived.parent().unwrap(), archive_path.join("carts"));
+
+        // Same file name archived in the same second gets a distinct name
+        let again = policy.archive_data(result.expired_files[0].clone()).unwrap();
+        assert_ne!(archived, again);
+    }
 }
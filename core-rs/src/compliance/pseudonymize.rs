@@ -0,0 +1,455 @@
+//! Pseudonymization and anonymization of identifier fields
+//!
+//! Rewrites configured identifier fields in instance files (`payload.json`,
+//! `receipt.bin`) and archived jobs and instances so production data can be
+//! used for analytics:
+//! - pseudonymize: HMAC-SHA256 under a secret key, so the same identifier
+//!   always maps to the same pseudonym and records stay joinable
+//! - anonymize: a random replacement per value, with nothing retained
+//!
+//! Pseudonyms can be mapped back to identifiers only through a
+//! [`PseudonymVault`], which is encrypted under its own key and should be
+//! stored apart from both the pseudonymized data and the hashing key.
+
+use super::erasure::{COMPLIANCE_KERNEL, DEFAULT_SUBJECT_FIELDS, KERNEL_DATA_DIRS};
+use crate::errors::{CkpError, Result};
+use chacha20poly1305::aead::{Aead, AeadCore, KeyInit, OsRng};
+use chacha20poly1305::{ChaCha20Poly1305, Nonce};
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use serde_json::Value as JsonValue;
+use sha2::Sha256;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Prefix of keyed-hash pseudonyms
+pub const PSEUDONYM_PREFIX: &str = "psn_";
+
+/// Prefix of anonymized values
+pub const ANONYMIZED_PREFIX: &str = "anon_";
+
+/// How identifier values are replaced
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum PseudonymMode {
+    /// Stable keyed-hash pseudonym, reversible only through the vault
+    #[default]
+    Pseudonymize,
+
+    /// Random value, irreversible
+    Anonymize,
+}
+
+/// Files rewritten by a pseudonymization run
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PseudonymizationReport {
+    pub mode: PseudonymMode,
+
+    /// Files containing at least one replaced value, in scan order
+    pub files: Vec<PathBuf>,
+
+    /// Total number of values replaced
+    pub values: usize,
+}
+
+/// Encrypted pseudonym → identifier mapping
+///
+/// Stored as JSON holding a ChaCha20-Poly1305 nonce and ciphertext; the
+/// plaintext mapping never touches disk.
+pub struct PseudonymVault {
+    path: PathBuf,
+    key: [u8; 32],
+    mappings: BTreeMap<String, String>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct SealedVault {
+    algorithm: String,
+    nonce: String,
+    ciphertext: String,
+}
+
+impl PseudonymVault {
+    /// Generate a random vault key
+    pub fn generate_key() -> [u8; 32] {
+        ChaCha20Poly1305::generate_key(&mut OsRng).into()
+    }
+
+    /// Open a vault, loading existing mappings if the file exists
+    ///
+    /// # Errors
+    /// `CkpError::AuthenticationFailed` if the key does not decrypt the vault
+    pub fn open(path: PathBuf, key: [u8; 32]) -> Result<Self> {
+        let mut vault = Self { path, key, mappings: BTreeMap::new() };
+
+        if vault.path.exists() {
+            let sealed: SealedVault = serde_json::from_str(&fs::read_to_string(&vault.path)?)?;
+            let nonce = hex::decode(&sealed.nonce)
+                .map_err(|e| CkpError::ParseError(format!("Invalid vault nonce: {}", e)))?;
+            let ciphertext = hex::decode(&sealed.ciphertext)
+                .map_err(|e| CkpError::ParseError(format!("Invalid vault ciphertext: {}", e)))?;
+            if nonce.len() != 12 {
+                return Err(CkpError::ParseError("Invalid vault nonce length".to_string()));
+            }
+
+            let plaintext = vault.cipher()
+                .decrypt(Nonce::from_slice(&nonce), ciphertext.as_slice())
+                .map_err(|_| CkpError::AuthenticationFailed("Pseudonym vault key is incorrect".to_string()))?;
+            vault.mappings = serde_json::from_slice(&plaintext)?;
+        }
+
+        Ok(vault)
+    }
+
+    /// Record a pseudonym mapping
+    pub fn record(&mut self, pseudonym: &str, identifier: &str) {
+        self.mappings.insert(pseudonym.to_string(), identifier.to_string());
+    }
+
+    /// Identifier behind a pseudonym
+    pub fn reidentify(&self, pseudonym: &str) -> Option<&str> {
+        self.mappings.get(pseudonym).map(|s| s.as_str())
+    }
+
+    pub fn len(&self) -> usize {
+        self.mappings.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.mappings.is_empty()
+    }
+
+    /// Encrypt and write the vault
+    pub fn save(&self) -> Result<()> {
+        let nonce = ChaCha20Poly1305::generate_nonce(&mut OsRng);
+        let ciphertext = self.cipher()
+            .encrypt(&nonce, serde_json::to_vec(&self.mappings)?.as_slice())
+            .map_err(|e| CkpError::IoError(format!("Failed to seal pseudonym vault: {}", e)))?;
+
+        let sealed = SealedVault {
+            algorithm: "chacha20poly1305".to_string(),
+            nonce: hex::encode(nonce),
+            ciphertext: hex::encode(ciphertext),
+        };
+
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)
+                .map_err(|e| CkpError::IoError(format!("Failed to create vault directory: {}", e)))?;
+        }
+        fs::write(&self.path, serde_json::to_string_pretty(&sealed)?)
+            .map_err(|e| CkpError::IoError(format!("Failed to write pseudonym vault: {}", e)))?;
+
+        Ok(())
+    }
+
+    fn cipher(&self) -> ChaCha20Poly1305 {
+        ChaCha20Poly1305::new(&self.key.into())
+    }
+}
+
+/// Identifier field rewriter
+///
+/// # Examples
+///
+/// ```no_run
+/// use ckp_core::compliance::{PseudonymVault, Pseudonymizer};
+/// use std::path::{Path, PathBuf};
+///
+/// let mut vault = PseudonymVault::open(PathBuf::from("/secure/vault.json"), [7u8; 32]).unwrap();
+/// let pseudonymizer = Pseudonymizer::new(b"pseudonym-key")
+///     .with_fields(vec!["data.email".to_string()]);
+///
+/// let report = pseudonymizer
+///     .pseudonymize_to(Path::new("/project/concepts"), Path::new("/analytics/concepts"), Some(&mut vault))
+///     .unwrap();
+/// vault.save().unwrap();
+/// println!("{} values replaced", report.values);
+/// ```
+pub struct Pseudonymizer {
+    key: Vec<u8>,
+    fields: Vec<String>,
+    mode: PseudonymMode,
+    exceptions: Vec<String>,
+}
+
+impl Pseudonymizer {
+    /// Create a pseudonymizer keyed for HMAC with the default subject fields
+    pub fn new(key: &[u8]) -> Self {
+        Self {
+            key: key.to_vec(),
+            fields: DEFAULT_SUBJECT_FIELDS.iter().map(|s| s.to_string()).collect(),
+            mode: PseudonymMode::default(),
+            exceptions: Vec::new(),
+        }
+    }
+
+    /// Replace the dotted field paths treated as identifiers
+    pub fn with_fields(mut self, fields: Vec<String>) -> Self {
+        self.fields = fields;
+        self
+    }
+
+    /// Set replacement mode
+    pub fn with_mode(mut self, mode: PseudonymMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Add kernel exception (never rewritten or copied)
+    pub fn add_exception(&mut self, kernel_name: String) {
+        self.exceptions.push(kernel_name);
+    }
+
+    /// Stable pseudonym for an identifier
+    pub fn pseudonym(&self, identifier: &str) -> String {
+        let mut mac = <Hmac<Sha256> as Mac>::new_from_slice(&self.key).expect("HMAC accepts keys of any length");
+        mac.update(identifier.as_bytes());
+        let digest = mac.finalize().into_bytes();
+
+        format!("{}{}", PSEUDONYM_PREFIX, hex::encode(&digest[..16]))
+    }
+
+    /// Rewrite identifier fields of a JSON value in place
+    ///
+    /// Values that are already pseudonyms or anonymized are left as they are,
+    /// so repeated runs are idempotent.
+    ///
+    /// # Returns
+    /// Number of values replaced
+    pub fn apply(&self, value: &mut JsonValue, mut vault: Option<&mut PseudonymVault>) -> usize {
+        let mut count = 0;
+
+        for field in &self.fields {
+            if let Some(target) = lookup_mut(value, field) {
+                count += self.replace(target, &mut vault);
+            }
+        }
+
+        count
+    }
+
+    /// Rewrite storage and archived jobs of every kernel in place
+    pub fn pseudonymize_in_place(
+        &self,
+        concepts_root: &Path,
+        vault: Option<&mut PseudonymVault>,
+    ) -> Result<PseudonymizationReport> {
+        self.process(concepts_root, concepts_root, vault)
+    }
+
+    /// Write rewritten copies of storage and archived jobs under `output_root`
+    ///
+    /// The source tree is left untouched; the output mirrors its layout.
+    pub fn pseudonymize_to(
+        &self,
+        concepts_root: &Path,
+        output_root: &Path,
+        vault: Option<&mut PseudonymVault>,
+    ) -> Result<PseudonymizationReport> {
+        self.process(concepts_root, output_root, vault)
+    }
+
+    fn process(
+        &self,
+        source: &Path,
+        dest: &Path,
+        mut vault: Option<&mut PseudonymVault>,
+    ) -> Result<PseudonymizationReport> {
+        let mut report = PseudonymizationReport { mode: self.mode, files: Vec::new(), values: 0 };
+
+        if !source.exists() {
+            return Ok(report);
+        }
+
+        let mut kernels: Vec<PathBuf> = fs::read_dir(source)
+            .map_err(|e| CkpError::IoError(format!("Failed to read concepts directory: {}", e)))?
+            .filter_map(|entry| entry.ok().map(|e| e.path()))
+            .filter(|path| path.is_dir())
+            .collect();
+        kernels.sort();
+
+        for kernel_path in kernels {
+            let kernel = kernel_path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+            if kernel.starts_with('.') || kernel == COMPLIANCE_KERNEL || self.exceptions.contains(&kernel) {
+                continue;
+            }
+
+            for dir in KERNEL_DATA_DIRS {
+                let mut files = Vec::new();
+                collect_files(&kernel_path.join(dir), &mut files)?;
+
+                for file in files {
+                    let relative = file.strip_prefix(source).unwrap_or(&file);
+                    let target = dest.join(relative);
+                    let replaced = self.process_file(&file, &target, &mut vault)?;
+                    if replaced > 0 {
+                        report.values += replaced;
+                        report.files.push(target);
+                    }
+                }
+            }
+        }
+
+        Ok(report)
+    }
+
+    /// Rewrite one file to `target`; non-JSON files are copied unchanged
+    fn process_file(&self, file: &Path, target: &Path, vault: &mut Option<&mut PseudonymVault>) -> Result<usize> {
+        let content = fs::read(file)
+            .map_err(|e| CkpError::IoError(format!("Failed to read {}: {}", file.display(), e)))?;
+
+        let mut replaced = 0;
+        let mut output = content.clone();
+        if let Ok(mut value) = serde_json::from_slice::<JsonValue>(&content) {
+            replaced = self.apply(&mut value, vault.as_deref_mut());
+            if replaced > 0 {
+                output = serde_json::to_vec_pretty(&value)?;
+            }
+        }
+
+        if replaced > 0 || file != target {
+            if let Some(parent) = target.parent() {
+                fs::create_dir_all(parent)
+                    .map_err(|e| CkpError::IoError(format!("Failed to create directory: {}", e)))?;
+            }
+            fs::write(target, output)
+                .map_err(|e| CkpError::IoError(format!("Failed to write {}: {}", target.display(), e)))?;
+        }
+
+        Ok(replaced)
+    }
+
+    fn replace(&self, value: &mut JsonValue, vault: &mut Option<&mut PseudonymVault>) -> usize {
+        let identifier = match value {
+            JsonValue::Array(items) => return items.iter_mut().map(|item| self.replace(item, vault)).sum(),
+            JsonValue::String(s) if s.starts_with(PSEUDONYM_PREFIX) || s.starts_with(ANONYMIZED_PREFIX) => return 0,
+            JsonValue::String(s) => s.clone(),
+            JsonValue::Number(n) => n.to_string(),
+            _ => return 0,
+        };
+
+        let replacement = match self.mode {
+            PseudonymMode::Pseudonymize => {
+                let pseudonym = self.pseudonym(&identifier);
+                if let Some(vault) = vault.as_deref_mut() {
+                    vault.record(&pseudonym, &identifier);
+                }
+                pseudonym
+            }
+            PseudonymMode::Anonymize => format!("{}{}", ANONYMIZED_PREFIX, uuid::Uuid::new_v4().simple()),
+        };
+
+        *value = JsonValue::String(replacement);
+        1
+    }
+}
+
+/// Resolve a dotted path mutably
+fn lookup_mut<'a>(value: &'a mut JsonValue, path: &str) -> Option<&'a mut JsonValue> {
+    path.split('.').try_fold(value, |current, key| current.get_mut(key))
+}
+
+/// Files under a directory, recursively, sorted
+fn collect_files(dir: &Path, files: &mut Vec<PathBuf>) -> Result<()> {
+    if !dir.exists() {
+        return Ok(());
+    }
+
+    let mut entries: Vec<PathBuf> = fs::read_dir(dir)
+        .map_err(|e| CkpError::IoError(format!("Failed to read directory: {}", e)))?
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .collect();
+    entries.sort();
+
+    for path in entries {
+        if path.is_dir() {
+            collect_files(&path, files)?;
+        } else {
+            files.push(path);
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn setup(concepts: &Path) {
+        let inst = concepts.join("Orders/storage/tx1.inst");
+        fs::create_dir_all(&inst).unwrap();
+        fs::write(inst.join("payload.json"), r#"{"data": {"user_id": "alice", "total": 10}}"#).unwrap();
+        fs::write(inst.join("receipt.bin"), r#"{"id": "tx1", "user_id": "alice"}"#).unwrap();
+
+        let archive = concepts.join("Profile/queue/archive");
+        fs::create_dir_all(&archive).unwrap();
+        fs::write(archive.join("tx2.job"), r#"{"payload": {"userId": ["alice", "bob"]}}"#).unwrap();
+    }
+
+    #[test]
+    fn test_pseudonymize_to_with_vault() {
+        let temp_dir = TempDir::new().unwrap();
+        let concepts = temp_dir.path().join("concepts");
+        let output = temp_dir.path().join("analytics");
+        setup(&concepts);
+
+        let vault_path = temp_dir.path().join("vault/mappings.json");
+        let vault_key = PseudonymVault::generate_key();
+        let mut vault = PseudonymVault::open(vault_path.clone(), vault_key).unwrap();
+
+        let pseudonymizer = Pseudonymizer::new(b"secret");
+        let report = pseudonymizer.pseudonymize_to(&concepts, &output, Some(&mut vault)).unwrap();
+        vault.save().unwrap();
+
+        assert_eq!(report.values, 4);
+        assert_eq!(report.files.len(), 3);
+
+        // Source untouched, copies rewritten with stable pseudonyms
+        assert!(fs::read_to_string(concepts.join("Orders/storage/tx1.inst/payload.json")).unwrap().contains("alice"));
+        let alice = pseudonymizer.pseudonym("alice");
+        let payload: JsonValue = serde_json::from_str(
+            &fs::read_to_string(output.join("Orders/storage/tx1.inst/payload.json")).unwrap(),
+        ).unwrap();
+        assert_eq!(payload["data"]["user_id"], alice.as_str());
+        let job = fs::read_to_string(output.join("Profile/queue/archive/tx2.job")).unwrap();
+        assert!(job.contains(&alice) && !job.contains("bob"));
+
+        // Vault is sealed on disk and reopens only with its key
+        assert!(!fs::read_to_string(&vault_path).unwrap().contains("alice"));
+        let reopened = PseudonymVault::open(vault_path.clone(), vault_key).unwrap();
+        assert_eq!(reopened.len(), 2);
+        assert_eq!(reopened.reidentify(&alice), Some("alice"));
+        assert!(matches!(
+            PseudonymVault::open(vault_path, [0u8; 32]),
+            Err(CkpError::AuthenticationFailed(_))
+        ));
+
+        // Re-running over the output replaces nothing
+        let rerun = pseudonymizer.pseudonymize_in_place(&output, None).unwrap();
+        assert_eq!(rerun.values, 0);
+    }
+
+    #[test]
+    fn test_anonymize_in_place() {
+        let temp_dir = TempDir::new().unwrap();
+        let concepts = temp_dir.path().join("concepts");
+        setup(&concepts);
+
+        let report = Pseudonymizer::new(b"unused")
+            .with_fields(vec!["user_id".to_string()])
+            .with_mode(PseudonymMode::Anonymize)
+            .pseudonymize_in_place(&concepts, None)
+            .unwrap();
+
+        assert_eq!(report.values, 1);
+        let receipt: JsonValue = serde_json::from_str(
+            &fs::read_to_string(concepts.join("Orders/storage/tx1.inst/receipt.bin")).unwrap(),
+        ).unwrap();
+        assert!(receipt["user_id"].as_str().unwrap().starts_with(ANONYMIZED_PREFIX));
+        // Field not configured
+        assert!(fs::read_to_string(concepts.join("Orders/storage/tx1.inst/payload.json")).unwrap().contains("alice"));
+    }
+}
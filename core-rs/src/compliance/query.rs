@@ -0,0 +1,206 @@
+//! Audit log queries
+//!
+//! Reads the active log and every rotated segment in chronological order,
+//! so callers never have to parse JSONL or know about rotation and
+//! compression. Indexed segments that cannot match are skipped unread.
+
+use super::integrity::read_entries;
+use super::{AuditEntry, AuditLogger};
+use crate::errors::Result;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Default page size when `AuditFilters::limit` is unset
+pub const DEFAULT_AUDIT_PAGE_SIZE: usize = 100;
+
+/// Filters for `AuditLogger::query`
+#[derive(Debug, Clone, Default)]
+pub struct AuditFilters {
+    /// Only entries at or after this time
+    pub since: Option<DateTime<Utc>>,
+
+    /// Only entries before this time
+    pub until: Option<DateTime<Utc>>,
+
+    /// Only operations starting with this prefix (e.g. "gdpr.")
+    pub operation_prefix: Option<String>,
+
+    /// Only entries for this user
+    pub user_id: Option<String>,
+
+    /// Only redacted (true) or unredacted (false) entries
+    pub redacted: Option<bool>,
+
+    /// Number of matching entries to skip
+    pub offset: usize,
+
+    /// Maximum entries per page (default: DEFAULT_AUDIT_PAGE_SIZE)
+    pub limit: Option<usize>,
+}
+
+impl AuditFilters {
+    /// Check whether an entry satisfies the filters
+    ///
+    /// `offset` and `limit` only affect pagination and are ignored here.
+    pub fn matches(&self, entry: &AuditEntry) -> bool {
+        if let Some(since) = self.since {
+            if entry.timestamp < since {
+                return false;
+            }
+        }
+
+        if let Some(until) = self.until {
+            if entry.timestamp >= until {
+                return false;
+            }
+        }
+
+        if let Some(prefix) = &self.operation_prefix {
+            if !entry.operation.starts_with(prefix.as_str()) {
+                return false;
+            }
+        }
+
+        if let Some(user_id) = &self.user_id {
+            if entry.user_id.as_deref() != Some(user_id.as_str()) {
+                return false;
+            }
+        }
+
+        if let Some(redacted) = self.redacted {
+            if entry.redacted != redacted {
+                return false;
+            }
+        }
+
+        true
+    }
+}
+
+/// One page of audit query results
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditPage {
+    /// Matching entries, oldest first
+    pub entries: Vec<AuditEntry>,
+
+    /// Total number of matching entries across all segments
+    pub total: usize,
+
+    /// Offset of this page
+    pub offset: usize,
+
+    /// Offset of the next page, if there is one
+    #[serde(rename = "nextOffset")]
+    pub next_offset: Option<usize>,
+}
+
+impl AuditLogger {
+    /// Query audit entries across the active log and rotated segments
+    ///
+    /// Unparseable lines are skipped; use `verify_integrity` to report them.
+    /// Segments whose index summary rules out every filter match are not read.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use ckp_core::compliance::{AuditFilters, AuditLogger};
+    /// use std::path::PathBuf;
+    ///
+    /// let logger = AuditLogger::new(PathBuf::from("/concepts/audit.log"));
+    /// let mut filters = AuditFilters {
+    ///     operation_prefix: Some("gdpr.".to_string()),
+    ///     limit: Some(50),
+    ///     ..Default::default()
+    /// };
+    ///
+    /// loop {
+    ///     let page = logger.query(&filters).unwrap();
+    ///     for entry in &page.entries {
+    ///         println!("{} {}", entry.timestamp, entry.operation);
+    ///     }
+    ///     match page.next_offset {
+    ///         Some(next) => filters.offset = next,
+    ///         None => break,
+    ///     }
+    /// }
+    /// ```
+    pub fn query(&self, filters: &AuditFilters) -> Result<AuditPage> {
+        let limit = filters.limit.unwrap_or(DEFAULT_AUDIT_PAGE_SIZE);
+        let mut entries = Vec::new();
+        let mut total = 0;
+        let index = self.segment_index()?;
+
+        for segment in self.segments()? {
+            let file_name = segment.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+            if index.iter().any(|summary| summary.segment == file_name && !summary.may_match(filters)) {
+                continue;
+            }
+
+            for entry in read_entries(&segment)?.into_iter().flatten() {
+                if !filters.matches(&entry) {
+                    continue;
+                }
+
+                if total >= filters.offset && entries.len() < limit {
+                    entries.push(entry);
+                }
+                total += 1;
+            }
+        }
+
+        let next = filters.offset + entries.len();
+        Ok(AuditPage {
+            entries,
+            total,
+            offset: filters.offset,
+            next_offset: if next < total { Some(next) } else { None },
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_query_filters_and_pages_across_rotation() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut logger = AuditLogger::new(temp_dir.path().join("audit.log"));
+        logger.max_log_size = 100;
+
+        let start = Utc::now();
+        for i in 0..3 {
+            logger.log_with_context("gdpr.access", "alice", serde_json::json!({"i": i})).unwrap();
+        }
+        logger.log_operation("kernel.emit", None, serde_json::json!({})).unwrap();
+        assert!(logger.rotate_if_needed().unwrap());
+        logger.log_with_redaction("gdpr.erasure", Some("alice"), serde_json::json!({"token": "t"})).unwrap();
+        logger.log_with_context("gdpr.access", "bob", serde_json::json!({})).unwrap();
+
+        let gdpr = AuditFilters {
+            operation_prefix: Some("gdpr.".to_string()),
+            limit: Some(2),
+            ..Default::default()
+        };
+        let first = logger.query(&gdpr).unwrap();
+        assert_eq!(first.total, 5);
+        assert_eq!(first.entries.len(), 2);
+        assert_eq!(first.next_offset, Some(2));
+
+        let last = logger.query(&AuditFilters { offset: 4, ..gdpr.clone() }).unwrap();
+        assert_eq!(last.entries[0].user_id.as_deref(), Some("bob"));
+        assert_eq!(last.next_offset, None);
+
+        let redacted = logger.query(&AuditFilters {
+            user_id: Some("alice".to_string()),
+            redacted: Some(true),
+            ..Default::default()
+        }).unwrap();
+        assert_eq!(redacted.total, 1);
+        assert_eq!(redacted.entries[0].operation, "gdpr.erasure");
+
+        let none = logger.query(&AuditFilters { until: Some(start), ..Default::default() }).unwrap();
+        assert_eq!(none.total, 0);
+    }
+}
@@ -0,0 +1,400 @@
+//! Redaction rules for audit logging and data-portability export
+//!
+//! A ruleset combines three kinds of rule, applied recursively through nested
+//! objects and arrays:
+//! - field rules: a bare key (`password`) matches at any depth, a dotted path
+//!   (`profile.ssn`) matches from the root; the whole value is replaced
+//! - pattern rules: named regexes (credit cards, SSNs, ...) replaced inside
+//!   string values
+//! - a deny-list: project-specific terms replaced case-insensitively inside
+//!   string values
+//!
+//! Fields can also be hashed (replaced by a SHA-256 digest, so records stay
+//! joinable on them) or stripped (removed with their key); kernels use these
+//! in `spec.tx_redaction` for what they record in tx.jsonl.
+
+use crate::errors::{CkpError, Result};
+use crate::ontology::OntologyReader;
+use crate::project::ProjectConfig;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use serde_json::Value as JsonValue;
+use std::collections::BTreeMap;
+use std::path::Path;
+
+/// Replacement for redacted content
+pub const REDACTED: &str = "[REDACTED]";
+
+/// Prefix of the digest replacing a hashed field
+pub const HASHED_PREFIX: &str = "sha256:";
+
+/// Fields redacted by the built-in ruleset
+pub const DEFAULT_REDACTED_FIELDS: &[&str] = &["password", "token", "secret", "api_key", "credit_card"];
+
+/// Detectors in the built-in ruleset (name, regex)
+pub const DEFAULT_PATTERNS: &[(&str, &str)] = &[
+    ("credit-card", r"\b(?:\d{4}[- ]?){3}\d{4}\b|\b3[47]\d{2}[- ]?\d{6}[- ]?\d{5}\b"),
+    ("ssn", r"\b\d{3}-\d{2}-\d{4}\b"),
+];
+
+fn default_true() -> bool {
+    true
+}
+
+/// Serializable ruleset, e.g. `spec.redaction` in `.ckproject`
+///
+/// ```yaml
+/// redaction:
+///   fields: [profile.ssn, dateOfBirth]
+///   patterns:
+///     iban: "\\b[A-Z]{2}\\d{2}[A-Z0-9]{11,30}\\b"
+///   denyList: [Project Falcon]
+///   hash: [customer.email]
+///   strip: [payload.notes]
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct RedactionConfig {
+    /// Include the built-in fields and detectors
+    #[serde(default = "default_true")]
+    pub builtins: bool,
+
+    /// Field names or dotted paths
+    #[serde(default)]
+    pub fields: Vec<String>,
+
+    /// Named regex detectors
+    #[serde(default)]
+    pub patterns: BTreeMap<String, String>,
+
+    /// Terms redacted wherever they appear in string values
+    #[serde(default)]
+    pub deny_list: Vec<String>,
+
+    /// Field names or dotted paths replaced by a SHA-256 digest
+    #[serde(default)]
+    pub hash: Vec<String>,
+
+    /// Field names or dotted paths removed together with their key
+    #[serde(default)]
+    pub strip: Vec<String>,
+}
+
+impl Default for RedactionConfig {
+    fn default() -> Self {
+        Self {
+            builtins: true,
+            fields: Vec::new(),
+            patterns: BTreeMap::new(),
+            deny_list: Vec::new(),
+            hash: Vec::new(),
+            strip: Vec::new(),
+        }
+    }
+}
+
+/// Compiled redaction ruleset
+///
+/// `Default` is the built-in ruleset; `new()` is empty.
+#[derive(Debug, Clone)]
+pub struct RedactionRules {
+    fields: Vec<String>,
+    patterns: Vec<(String, Regex)>,
+    deny_terms: Vec<String>,
+    deny_list: Option<Regex>,
+    hashed: Vec<String>,
+    stripped: Vec<String>,
+}
+
+impl Default for RedactionRules {
+    fn default() -> Self {
+        let mut rules = Self::new();
+        rules.fields = DEFAULT_REDACTED_FIELDS.iter().map(|f| f.to_string()).collect();
+        for (name, pattern) in DEFAULT_PATTERNS {
+            rules.patterns.push((name.to_string(), Regex::new(pattern).expect("built-in pattern is valid")));
+        }
+        rules
+    }
+}
+
+impl RedactionRules {
+    /// Create an empty ruleset
+    pub fn new() -> Self {
+        Self {
+            fields: Vec::new(),
+            patterns: Vec::new(),
+            deny_terms: Vec::new(),
+            deny_list: None,
+            hashed: Vec::new(),
+            stripped: Vec::new(),
+        }
+    }
+
+    /// Compile a ruleset from configuration
+    ///
+    /// # Errors
+    /// `CkpError::ValidationError` if a pattern is not a valid regex
+    pub fn from_config(config: &RedactionConfig) -> Result<Self> {
+        let mut rules = if config.builtins { Self::default() } else { Self::new() };
+
+        for field in &config.fields {
+            rules = rules.with_field(field);
+        }
+        for (name, pattern) in &config.patterns {
+            rules = rules.with_pattern(name, pattern)?;
+        }
+        for field in &config.hash {
+            rules = rules.with_hashed_field(field);
+        }
+        for field in &config.strip {
+            rules = rules.with_stripped_field(field);
+        }
+
+        Ok(rules.with_deny_list(&config.deny_list))
+    }
+
+    /// Ruleset from a project's `.ckproject` `spec.redaction`
+    ///
+    /// Falls back to the built-in ruleset when the project has no `.ckproject`
+    /// or no redaction section.
+    pub fn for_project(project_root: &Path) -> Result<Self> {
+        if !project_root.join(".ckproject").exists() {
+            return Ok(Self::default());
+        }
+
+        match ProjectConfig::load_from_project(project_root)?.spec.redaction {
+            Some(config) => Self::from_config(&config),
+            None => Ok(Self::default()),
+        }
+    }
+
+    /// Add a field name or dotted path
+    pub fn with_field(mut self, field: &str) -> Self {
+        if !self.fields.iter().any(|f| f == field) {
+            self.fields.push(field.to_string());
+        }
+        self
+    }
+
+    /// Add a field name or dotted path whose value is replaced by its SHA-256 digest
+    pub fn with_hashed_field(mut self, field: &str) -> Self {
+        if !self.hashed.iter().any(|f| f == field) {
+            self.hashed.push(field.to_string());
+        }
+        self
+    }
+
+    /// Add a field name or dotted path removed together with its key
+    pub fn with_stripped_field(mut self, field: &str) -> Self {
+        if !self.stripped.iter().any(|f| f == field) {
+            self.stripped.push(field.to_string());
+        }
+        self
+    }
+
+    /// Ruleset for a kernel's transaction log, from `spec.tx_redaction`
+    ///
+    /// `None` when the kernel declares no `tx_redaction` or its ontology
+    /// cannot be read.
+    ///
+    /// # Errors
+    /// `CkpError::ValidationError` if a pattern is not a valid regex
+    pub fn for_tx_log(project_root: &Path, kernel: &str) -> Result<Option<Self>> {
+        let config = OntologyReader::new(project_root.to_path_buf())
+            .read_by_kernel_name(kernel)
+            .ok()
+            .and_then(|ontology| ontology.spec)
+            .and_then(|spec| spec.tx_redaction);
+        config.map(|config| Self::from_config(&config)).transpose()
+    }
+
+    /// Add a named regex detector
+    pub fn with_pattern(mut self, name: &str, pattern: &str) -> Result<Self> {
+        let regex = Regex::new(pattern).map_err(|e| {
+            CkpError::ValidationError(format!("Invalid redaction pattern '{}': {}", name, e))
+        })?;
+        self.patterns.push((name.to_string(), regex));
+        Ok(self)
+    }
+
+    /// Add deny-listed terms (matched case-insensitively)
+    pub fn with_deny_list<S: AsRef<str>>(mut self, terms: &[S]) -> Self {
+        self.deny_terms.extend(terms.iter().map(|t| t.as_ref().to_string()).filter(|t| !t.is_empty()));
+
+        if !self.deny_terms.is_empty() {
+            let alternatives: Vec<String> = self.deny_terms.iter().map(|t| regex::escape(t)).collect();
+            self.deny_list = Some(
+                Regex::new(&format!("(?i){}", alternatives.join("|"))).expect("escaped terms form a valid regex"),
+            );
+        }
+        self
+    }
+
+    /// Names of the configured pattern detectors
+    pub fn pattern_names(&self) -> Vec<&str> {
+        self.patterns.iter().map(|(name, _)| name.as_str()).collect()
+    }
+
+    /// Redact a JSON value in place
+    ///
+    /// # Returns
+    /// Number of values that were changed
+    pub fn apply(&self, value: &mut JsonValue) -> usize {
+        let mut path = Vec::new();
+        self.redact(value, &mut path)
+    }
+
+    fn redact(&self, value: &mut JsonValue, path: &mut Vec<String>) -> usize {
+        match value {
+            JsonValue::Object(map) => {
+                let before = map.len();
+                map.retain(|key, _| {
+                    path.push(key.clone());
+                    let stripped = Self::matches(&self.stripped, path);
+                    path.pop();
+                    !stripped
+                });
+                let mut count = before - map.len();
+
+                for (key, child) in map.iter_mut() {
+                    path.push(key.clone());
+                    if Self::matches(&self.hashed, path) {
+                        if !child.as_str().is_some_and(|s| s.starts_with(HASHED_PREFIX)) {
+                            *child = JsonValue::String(digest(child));
+                            count += 1;
+                        }
+                    } else if Self::matches(&self.fields, path) {
+                        if *child != JsonValue::String(REDACTED.to_string()) {
+                            *child = JsonValue::String(REDACTED.to_string());
+                            count += 1;
+                        }
+                    } else {
+                        count += self.redact(child, path);
+                    }
+                    path.pop();
+                }
+                count
+            }
+            // Array indices are transparent to field paths
+            JsonValue::Array(items) => items.iter_mut().map(|item| self.redact(item, path)).sum(),
+            JsonValue::String(s) => match self.redact_str(s) {
+                Some(redacted) => {
+                    *s = redacted;
+                    1
+                }
+                None => 0,
+            },
+            _ => 0,
+        }
+    }
+
+    fn matches(fields: &[String], path: &[String]) -> bool {
+        let key = match path.last() {
+            Some(key) => key,
+            None => return false,
+        };
+
+        fields.iter().any(|field| {
+            if field.contains('.') {
+                field.split('.').eq(path.iter().map(|s| s.as_str()))
+            } else {
+                field == key
+            }
+        })
+    }
+
+    fn redact_str(&self, s: &str) -> Option<String> {
+        let mut current = s.to_string();
+
+        for (_, regex) in &self.patterns {
+            if regex.is_match(&current) {
+                current = regex.replace_all(&current, REDACTED).into_owned();
+            }
+        }
+        if let Some(regex) = &self.deny_list {
+            if regex.is_match(&current) {
+                current = regex.replace_all(&current, REDACTED).into_owned();
+            }
+        }
+
+        (current != s).then_some(current)
+    }
+}
+
+/// SHA-256 digest of a value (strings hashed as-is, anything else as JSON)
+fn digest(value: &JsonValue) -> String {
+    let bytes = match value {
+        JsonValue::String(s) => s.clone().into_bytes(),
+        other => other.to_string().into_bytes(),
+    };
+    format!("{}{}", HASHED_PREFIX, hex::encode(Sha256::digest(&bytes)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_nested_fields_and_detectors() {
+        let rules = RedactionRules::default().with_field("profile.dob");
+        let mut data = serde_json::json!({
+            "profile": {"dob": "1990-01-01", "name": "Alice", "password": "pw"},
+            "dob": "kept at top level",
+            "notes": ["card 4111 1111 1111 1111 on file", "ssn 123-45-6789"],
+            "orders": [{"token": "t1"}, {"id": 7}]
+        });
+
+        assert_eq!(rules.apply(&mut data), 5);
+        assert_eq!(data["profile"]["dob"], REDACTED);
+        assert_eq!(data["profile"]["password"], REDACTED);
+        assert_eq!(data["profile"]["name"], "Alice");
+        assert_eq!(data["dob"], "kept at top level");
+        assert_eq!(data["notes"][0], "card [REDACTED] on file");
+        assert_eq!(data["notes"][1], "ssn [REDACTED]");
+        assert_eq!(data["orders"][0]["token"], REDACTED);
+    }
+
+    #[test]
+    fn test_from_config_with_deny_list() {
+        let config: RedactionConfig = serde_yaml::from_str(
+            "builtins: false\npatterns:\n  ticket: 'TKT-\\d+'\ndenyList: [Project Falcon]\n",
+        ).unwrap();
+        let rules = RedactionRules::from_config(&config).unwrap().with_deny_list(&["orion"]);
+        assert_eq!(rules.pattern_names(), vec!["ticket"]);
+
+        let mut data = serde_json::json!({"msg": "project falcon / ORION: TKT-42", "password": "kept"});
+        rules.apply(&mut data);
+        assert_eq!(data["msg"], "[REDACTED] / [REDACTED]: [REDACTED]");
+        assert_eq!(data["password"], "kept");
+
+        let invalid = RedactionConfig {
+            patterns: BTreeMap::from([("bad".to_string(), "(".to_string())]),
+            ..Default::default()
+        };
+        assert!(matches!(RedactionRules::from_config(&invalid), Err(CkpError::ValidationError(_))));
+    }
+
+    #[test]
+    fn test_hash_and_strip_fields() {
+        let config: RedactionConfig =
+            serde_yaml::from_str("builtins: false
+hash: [customer.email]
+strip: [notes]
+").unwrap();
+        let rules = RedactionRules::from_config(&config).unwrap();
+
+        let mut first = serde_json::json!({"customer": {"email": "a@example.com", "id": 7}, "notes": "call back"});
+        let mut second = serde_json::json!({"customer": {"email": "a@example.com"}, "items": [{"notes": "gift"}]});
+        assert_eq!(rules.apply(&mut first), 2);
+        assert_eq!(rules.apply(&mut second), 2);
+
+        // Hashed values stay joinable; hashing twice changes nothing
+        let email = first["customer"]["email"].clone();
+        assert!(email.as_str().unwrap().starts_with(HASHED_PREFIX));
+        assert_eq!(second["customer"]["email"], email);
+        assert_eq!(rules.apply(&mut first), 0);
+        assert_eq!(first, serde_json::json!({"customer": {"email": email, "id": 7}}));
+        assert_eq!(second["items"][0], serde_json::json!({}));
+    }
+}
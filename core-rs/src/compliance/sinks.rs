@@ -0,0 +1,552 @@
+//! Audit log sinks for forwarding entries to external collectors
+//!
+//! Sinks run after an entry has been appended to the local log, so the local
+//! chain stays authoritative and a failing collector never blocks auditing.
+//! - [`SyslogSink`]: RFC 5424 or CEF messages over UDP or TCP
+//! - [`WebhookSink`]: batched JSON POSTs from a background worker, buffering
+//!   entries and retrying with backoff while the endpoint is unavailable
+
+use super::AuditEntry;
+use crate::daemon::sleep_until_shutdown;
+use crate::errors::{CkpError, Result};
+use std::collections::VecDeque;
+use std::io::Write;
+use std::net::{TcpStream, ToSocketAddrs, UdpSocket};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Condvar, Mutex};
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+
+/// Default maximum number of entries buffered by a webhook sink
+pub const DEFAULT_WEBHOOK_BUFFER: usize = 10_000;
+
+/// Destination that receives every audit entry written locally
+pub trait AuditSink: Send + Sync {
+    /// Sink name used in error reports
+    fn name(&self) -> &str;
+
+    /// Forward one entry
+    fn send(&self, entry: &AuditEntry) -> Result<()>;
+}
+
+/// Syslog transport
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SyslogTransport {
+    Udp,
+
+    /// TCP with octet-counting framing (RFC 6587)
+    Tcp,
+}
+
+/// Syslog message body format
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SyslogFormat {
+    /// Entry as JSON
+    Rfc5424,
+
+    /// ArcSight Common Event Format
+    Cef,
+}
+
+/// Syslog / CEF forwarder
+pub struct SyslogSink {
+    name: String,
+    address: String,
+    transport: SyslogTransport,
+    format: SyslogFormat,
+    facility: u8,
+    hostname: String,
+    tcp: Mutex<Option<TcpStream>>,
+}
+
+impl SyslogSink {
+    /// Syslog facility `local0`
+    pub const FACILITY_LOCAL0: u8 = 16;
+
+    /// Create a UDP sink sending RFC 5424 messages to `address` (host:port)
+    pub fn new(address: &str) -> Self {
+        Self {
+            name: format!("syslog:{}", address),
+            address: address.to_string(),
+            transport: SyslogTransport::Udp,
+            format: SyslogFormat::Rfc5424,
+            facility: Self::FACILITY_LOCAL0,
+            hostname: sysinfo::System::host_name().unwrap_or_else(|| "-".to_string()),
+            tcp: Mutex::new(None),
+        }
+    }
+
+    /// Create a UDP sink sending CEF messages to `address` (host:port)
+    pub fn cef(address: &str) -> Self {
+        let mut sink = Self::new(address).with_format(SyslogFormat::Cef);
+        sink.name = format!("cef:{}", address);
+        sink
+    }
+
+    /// Set transport
+    pub fn with_transport(mut self, transport: SyslogTransport) -> Self {
+        self.transport = transport;
+        self
+    }
+
+    /// Set message format
+    pub fn with_format(mut self, format: SyslogFormat) -> Self {
+        self.format = format;
+        self
+    }
+
+    /// Set syslog facility (0-23)
+    pub fn with_facility(mut self, facility: u8) -> Self {
+        self.facility = facility.min(23);
+        self
+    }
+
+    /// Render the full syslog message for an entry
+    pub fn format_message(&self, entry: &AuditEntry) -> Result<String> {
+        // Severity: notice for audit-chain checkpoints, informational otherwise
+        let severity = if entry.operation == super::CHECKPOINT_OPERATION { 5 } else { 6 };
+        let pri = u16::from(self.facility) * 8 + severity;
+
+        let body = match self.format {
+            SyslogFormat::Rfc5424 => serde_json::to_string(entry)?,
+            SyslogFormat::Cef => cef_message(entry)?,
+        };
+
+        Ok(format!(
+            "<{}>1 {} {} ckp {} {} - {}",
+            pri,
+            entry.timestamp.to_rfc3339_opts(chrono::SecondsFormat::Millis, true),
+            self.hostname,
+            std::process::id(),
+            msg_id(&entry.operation),
+            body
+        ))
+    }
+
+    fn send_tcp(&self, message: &str) -> Result<()> {
+        let mut stream = self.tcp.lock()
+            .map_err(|_| CkpError::IoError("Syslog connection lock poisoned".to_string()))?;
+
+        if stream.is_none() {
+            let addr = self.address.to_socket_addrs()?
+                .next()
+                .ok_or_else(|| CkpError::IoError(format!("Cannot resolve {}", self.address)))?;
+            *stream = Some(TcpStream::connect_timeout(&addr, Duration::from_secs(5))?);
+        }
+
+        let framed = format!("{} {}", message.len(), message);
+        let result = stream.as_mut().map(|s| s.write_all(framed.as_bytes())).unwrap_or(Ok(()));
+        if result.is_err() {
+            // Reconnect on the next entry
+            *stream = None;
+        }
+        Ok(result?)
+    }
+}
+
+impl AuditSink for SyslogSink {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn send(&self, entry: &AuditEntry) -> Result<()> {
+        let message = self.format_message(entry)?;
+
+        match self.transport {
+            SyslogTransport::Udp => {
+                let socket = UdpSocket::bind("0.0.0.0:0")?;
+                socket.send_to(message.as_bytes(), &self.address)?;
+                Ok(())
+            }
+            SyslogTransport::Tcp => self.send_tcp(&message),
+        }
+    }
+}
+
+/// RFC 5424 MSGID: printable ASCII, at most 32 characters
+fn msg_id(operation: &str) -> String {
+    let id: String = operation.chars().filter(|c| c.is_ascii_graphic()).take(32).collect();
+    if id.is_empty() { "-".to_string() } else { id }
+}
+
+fn cef_message(entry: &AuditEntry) -> Result<String> {
+    let severity = if entry.operation == super::CHECKPOINT_OPERATION { 5 } else { 3 };
+
+    let mut extension = vec![
+        format!("rt={}", entry.timestamp.timestamp_millis()),
+        format!("cn1Label=seq cn1={}", entry.seq),
+    ];
+    if let Some(user_id) = &entry.user_id {
+        extension.push(format!("suser={}", cef_escape_extension(user_id)));
+    }
+    if let Some(hash) = &entry.hash {
+        extension.push(format!("cs1Label=hash cs1={}", hash));
+    }
+    extension.push(format!("cs2Label=redacted cs2={}", entry.redacted));
+    extension.push(format!("msg={}", cef_escape_extension(&serde_json::to_string(&entry.data)?)));
+
+    Ok(format!(
+        "CEF:0|ConceptKernel|ckp|{}|{}|{}|{}|{}",
+        env!("CARGO_PKG_VERSION"),
+        cef_escape_header(&entry.operation),
+        cef_escape_header(&entry.operation),
+        severity,
+        extension.join(" ")
+    ))
+}
+
+fn cef_escape_header(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('|', "\\|")
+}
+
+fn cef_escape_extension(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('=', "\\=")
+        .replace('\r', "\\r")
+        .replace('\n', "\\n")
+}
+
+/// Webhook delivery settings
+#[derive(Debug, Clone)]
+struct WebhookConfig {
+    url: String,
+    headers: Vec<(String, String)>,
+    batch_size: usize,
+    max_buffer: usize,
+    initial_backoff: Duration,
+    max_backoff: Duration,
+    timeout: Duration,
+}
+
+/// Entries awaiting delivery, tagged with an increasing id so acknowledged
+/// batches can be removed even if overflow dropped entries meanwhile
+struct WebhookQueue {
+    entries: Mutex<VecDeque<(u64, AuditEntry)>>,
+    ready: Condvar,
+    next_id: AtomicU64,
+    dropped: AtomicU64,
+    shutdown: AtomicBool,
+}
+
+/// HTTPS webhook forwarder with retry buffering
+///
+/// Entries are queued and POSTed as a JSON array by a worker thread. While
+/// the endpoint fails, entries stay buffered (oldest dropped beyond the
+/// buffer limit) and delivery is retried with exponential backoff.
+pub struct WebhookSink {
+    name: String,
+    config: WebhookConfig,
+    queue: Arc<WebhookQueue>,
+    worker: Mutex<Option<JoinHandle<()>>>,
+}
+
+impl WebhookSink {
+    /// Create a sink for an `https://` URL
+    ///
+    /// Plain `http://` is accepted only for loopback hosts.
+    ///
+    /// # Errors
+    /// `CkpError::ValidationError` if the URL is invalid or not HTTPS
+    pub fn new(url: &str) -> Result<Self> {
+        let parsed = reqwest::Url::parse(url)
+            .map_err(|e| CkpError::ValidationError(format!("Invalid webhook URL '{}': {}", url, e)))?;
+
+        let loopback = matches!(parsed.host_str(), Some("localhost") | Some("127.0.0.1") | Some("[::1]"));
+        if parsed.scheme() != "https" && !(parsed.scheme() == "http" && loopback) {
+            return Err(CkpError::ValidationError(format!("Webhook URL must use https: {}", url)));
+        }
+
+        Ok(Self {
+            name: format!("webhook:{}", parsed.host_str().unwrap_or_default()),
+            config: WebhookConfig {
+                url: url.to_string(),
+                headers: Vec::new(),
+                batch_size: 100,
+                max_buffer: DEFAULT_WEBHOOK_BUFFER,
+                initial_backoff: Duration::from_millis(500),
+                max_backoff: Duration::from_secs(60),
+                timeout: Duration::from_secs(10),
+            },
+            queue: Arc::new(WebhookQueue {
+                entries: Mutex::new(VecDeque::new()),
+                ready: Condvar::new(),
+                next_id: AtomicU64::new(0),
+                dropped: AtomicU64::new(0),
+                shutdown: AtomicBool::new(false),
+            }),
+            worker: Mutex::new(None),
+        })
+    }
+
+    /// Add a request header (e.g. a collector token)
+    pub fn with_header(mut self, name: &str, value: &str) -> Self {
+        self.config.headers.push((name.to_string(), value.to_string()));
+        self
+    }
+
+    /// Send `Authorization: Bearer {token}`
+    pub fn with_bearer_token(self, token: &str) -> Self {
+        self.with_header("Authorization", &format!("Bearer {}", token))
+    }
+
+    /// Set the maximum number of entries per request
+    pub fn with_batch_size(mut self, batch_size: usize) -> Self {
+        self.config.batch_size = batch_size.max(1);
+        self
+    }
+
+    /// Set the maximum number of buffered entries
+    pub fn with_max_buffer(mut self, max_buffer: usize) -> Self {
+        self.config.max_buffer = max_buffer.max(1);
+        self
+    }
+
+    /// Set retry backoff bounds
+    pub fn with_backoff(mut self, initial: Duration, max: Duration) -> Self {
+        self.config.initial_backoff = initial;
+        self.config.max_backoff = max.max(initial);
+        self
+    }
+
+    /// Set per-request timeout
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.config.timeout = timeout;
+        self
+    }
+
+    /// Entries buffered but not yet acknowledged
+    pub fn pending(&self) -> usize {
+        self.queue.entries.lock().map(|entries| entries.len()).unwrap_or(0)
+    }
+
+    /// Entries dropped because the buffer was full
+    pub fn dropped(&self) -> u64 {
+        self.queue.dropped.load(Ordering::SeqCst)
+    }
+
+    /// Wait until the buffer is empty
+    ///
+    /// # Returns
+    /// `false` if entries are still pending after `timeout`
+    pub fn wait_drained(&self, timeout: Duration) -> bool {
+        let deadline = Instant::now() + timeout;
+        while Instant::now() < deadline {
+            if self.pending() == 0 {
+                return true;
+            }
+            std::thread::sleep(Duration::from_millis(10));
+        }
+        self.pending() == 0
+    }
+
+    fn ensure_worker(&self) -> Result<()> {
+        let mut worker = self.worker.lock()
+            .map_err(|_| CkpError::IoError("Webhook worker lock poisoned".to_string()))?;
+
+        if worker.is_none() {
+            let config = self.config.clone();
+            let queue = self.queue.clone();
+            *worker = Some(
+                std::thread::Builder::new()
+                    .name("ckp-audit-webhook".to_string())
+                    .spawn(move || deliver(config, queue))?,
+            );
+        }
+
+        Ok(())
+    }
+}
+
+impl AuditSink for WebhookSink {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn send(&self, entry: &AuditEntry) -> Result<()> {
+        self.ensure_worker()?;
+
+        let mut entries = self.queue.entries.lock()
+            .map_err(|_| CkpError::IoError("Webhook buffer lock poisoned".to_string()))?;
+        while entries.len() >= self.config.max_buffer {
+            entries.pop_front();
+            self.queue.dropped.fetch_add(1, Ordering::SeqCst);
+        }
+        entries.push_back((self.queue.next_id.fetch_add(1, Ordering::SeqCst), entry.clone()));
+        self.queue.ready.notify_one();
+
+        Ok(())
+    }
+}
+
+impl Drop for WebhookSink {
+    fn drop(&mut self) {
+        self.queue.shutdown.store(true, Ordering::SeqCst);
+        self.queue.ready.notify_all();
+
+        if let Some(worker) = self.worker.get_mut().ok().and_then(|w| w.take()) {
+            let _ = worker.join();
+        }
+    }
+}
+
+/// Worker loop: deliver batches until shutdown, retrying failures with backoff
+///
+/// On shutdown, buffered entries get one final delivery attempt.
+fn deliver(config: WebhookConfig, queue: Arc<WebhookQueue>) {
+    let client = match reqwest::blocking::Client::builder().timeout(config.timeout).build() {
+        Ok(client) => client,
+        Err(e) => {
+            tracing::error!(error = %e, "failed to create webhook HTTP client");
+            return;
+        }
+    };
+    let mut backoff = config.initial_backoff;
+
+    loop {
+        let batch: Vec<(u64, AuditEntry)> = {
+            let mut entries = match queue.entries.lock() {
+                Ok(entries) => entries,
+                Err(_) => return,
+            };
+            while entries.is_empty() && !queue.shutdown.load(Ordering::SeqCst) {
+                entries = match queue.ready.wait(entries) {
+                    Ok(entries) => entries,
+                    Err(_) => return,
+                };
+            }
+            if entries.is_empty() {
+                return;
+            }
+            entries.iter().take(config.batch_size).cloned().collect()
+        };
+
+        let last_id = batch.last().map(|(id, _)| *id).unwrap_or_default();
+        let body: Vec<&AuditEntry> = batch.iter().map(|(_, entry)| entry).collect();
+
+        let mut request = client.post(&config.url).json(&body);
+        for (name, value) in &config.headers {
+            request = request.header(name, value);
+        }
+
+        match request.send().and_then(|response| response.error_for_status()) {
+            Ok(_) => {
+                if let Ok(mut entries) = queue.entries.lock() {
+                    while entries.front().map(|(id, _)| *id <= last_id).unwrap_or(false) {
+                        entries.pop_front();
+                    }
+                }
+                backoff = config.initial_backoff;
+            }
+            Err(e) => {
+                if queue.shutdown.load(Ordering::SeqCst) {
+                    tracing::warn!(error = %e, "webhook sink shutting down with undelivered entries");
+                    return;
+                }
+                tracing::warn!(backoff = ?backoff, error = %e, "webhook delivery failed, retrying");
+
+                sleep_until_shutdown(backoff, &queue.shutdown);
+                backoff = (backoff * 2).min(config.max_backoff);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::compliance::AuditLogger;
+    use std::io::{BufRead, BufReader, Read};
+    use std::net::TcpListener;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_syslog_and_cef_over_udp() {
+        let temp_dir = TempDir::new().unwrap();
+        let collector = UdpSocket::bind("127.0.0.1:0").unwrap();
+        collector.set_read_timeout(Some(Duration::from_secs(5))).unwrap();
+        let address = collector.local_addr().unwrap().to_string();
+
+        let logger = AuditLogger::new(temp_dir.path().join("audit.log"))
+            .with_sink(Arc::new(SyslogSink::new(&address)))
+            .with_sink(Arc::new(SyslogSink::cef(&address)));
+        logger.log_operation("kernel.emit", Some("alice"), serde_json::json!({"note": "a=b|c"})).unwrap();
+
+        let mut buf = [0u8; 4096];
+        let mut messages = Vec::new();
+        for _ in 0..2 {
+            let n = collector.recv(&mut buf).unwrap();
+            messages.push(String::from_utf8_lossy(&buf[..n]).to_string());
+        }
+
+        // local0.info = 16 * 8 + 6
+        assert!(messages[0].starts_with("<134>1 "));
+        assert!(messages[0].contains(" kernel.emit - {"));
+        assert!(messages[1].contains("CEF:0|ConceptKernel|ckp|"));
+        assert!(messages[1].contains("|kernel.emit|kernel.emit|3|"));
+        assert!(messages[1].contains("suser=alice"));
+        assert!(messages[1].contains(r#"msg={"note":"a\=b|c"}"#));
+    }
+
+    /// Minimal HTTP collector answering with the given statuses, one per request
+    fn collector(statuses: Vec<u16>) -> (String, std::sync::mpsc::Receiver<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/audit", listener.local_addr().unwrap());
+        let (tx, rx) = std::sync::mpsc::channel();
+
+        std::thread::spawn(move || {
+            for status in statuses {
+                let (stream, _) = listener.accept().unwrap();
+                let mut reader = BufReader::new(stream);
+                let mut content_length = 0;
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    if line == "\r\n" {
+                        break;
+                    }
+                    if let Some(value) = line.to_ascii_lowercase().strip_prefix("content-length:") {
+                        content_length = value.trim().parse().unwrap();
+                    }
+                }
+                let mut body = vec![0u8; content_length];
+                reader.read_exact(&mut body).unwrap();
+                tx.send(String::from_utf8(body).unwrap()).unwrap();
+
+                let response = format!("HTTP/1.1 {} X\r\nContent-Length: 0\r\nConnection: close\r\n\r\n", status);
+                reader.get_mut().write_all(response.as_bytes()).unwrap();
+            }
+        });
+
+        (url, rx)
+    }
+
+    #[test]
+    fn test_webhook_retries_buffered_entries() {
+        let temp_dir = TempDir::new().unwrap();
+        let (url, bodies) = collector(vec![503, 200]);
+
+        let sink = Arc::new(
+            WebhookSink::new(&url)
+                .unwrap()
+                .with_bearer_token("t0ken")
+                .with_backoff(Duration::from_millis(20), Duration::from_millis(100)),
+        );
+        let logger = AuditLogger::new(temp_dir.path().join("audit.log")).with_sink(sink.clone());
+        logger.log_operation("kernel.emit", None, serde_json::json!({})).unwrap();
+
+        assert!(sink.wait_drained(Duration::from_secs(10)));
+        let first: Vec<AuditEntry> = serde_json::from_str(&bodies.recv().unwrap()).unwrap();
+        let retried: Vec<AuditEntry> = serde_json::from_str(&bodies.recv().unwrap()).unwrap();
+        assert_eq!(first[0].hash, retried[0].hash);
+        assert_eq!(retried[0].operation, "kernel.emit");
+        assert_eq!(sink.dropped(), 0);
+    }
+
+    #[test]
+    fn test_webhook_requires_https() {
+        assert!(WebhookSink::new("https://siem.example.com/ingest").is_ok());
+        assert!(matches!(WebhookSink::new("http://siem.example.com/ingest"), Err(CkpError::ValidationError(_))));
+        assert!(matches!(WebhookSink::new("not a url"), Err(CkpError::ValidationError(_))));
+    }
+}
@@ -0,0 +1,322 @@
+//! Agent authentication material
+//!
+//! Agents carry hashed API tokens and Ed25519 public keys so HTTP and daemon
+//! front-ends can map an external caller to an Agent URN before RBAC
+//! evaluation. Plaintext tokens are never persisted: only their SHA-256 hex
+//! digest is stored on the Agent, and bearer tokens are looked up by that
+//! digest.
+//!
+//! A signature credential signs `signed_message(agent, timestamp, nonce)`.
+//! It is accepted only within `SIGNATURE_SKEW` of the server clock, and each
+//! nonce only once per agent within that window, so a captured signature
+//! cannot be replayed. Accepted nonces are remembered by the authenticating
+//! process.
+
+use super::{Agent, ContinuantTracker};
+use crate::errors::{CkpError, Result};
+use ed25519_dalek::{Signature, Verifier, VerifyingKey};
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+use std::time::Duration;
+
+/// Largest accepted difference between a signed timestamp and the server clock
+pub const SIGNATURE_SKEW: Duration = Duration::from_secs(300);
+
+/// Nonces of accepted signature credentials, by (agent URN, nonce), with
+/// their signed timestamp
+static SEEN_NONCES: Mutex<BTreeMap<(String, String), i64>> = Mutex::new(BTreeMap::new());
+
+/// Authentication material attached to an Agent
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AgentCredentials {
+    /// SHA-256 hex digests of issued API tokens
+    #[serde(rename = "apiTokenHashes", default)]
+    pub api_token_hashes: Vec<String>,
+
+    /// Ed25519 public keys (hex-encoded, 32 bytes)
+    #[serde(rename = "publicKeys", default)]
+    pub public_keys: Vec<String>,
+}
+
+impl AgentCredentials {
+    /// Whether no authentication material is registered
+    pub fn is_empty(&self) -> bool {
+        self.api_token_hashes.is_empty() && self.public_keys.is_empty()
+    }
+}
+
+/// Credential presented by an external caller
+#[derive(Debug, Clone)]
+pub enum Credential {
+    /// Bearer API token (plaintext, as presented)
+    ApiToken(String),
+
+    /// Ed25519 signature over `signed_message(agent, timestamp, nonce)`
+    Signature {
+        /// Unix time (seconds) at which the caller signed
+        timestamp: i64,
+        /// Caller-chosen value, accepted once per agent within `SIGNATURE_SKEW`
+        nonce: String,
+        /// 64-byte signature
+        signature: Vec<u8>,
+    },
+}
+
+/// SHA-256 hex digest of an API token
+pub fn hash_api_token(token: &str) -> String {
+    hex::encode(Sha256::digest(token.as_bytes()))
+}
+
+/// Message a caller signs to authenticate as `agent_urn`
+pub fn signed_message(agent_urn: &str, timestamp: i64, nonce: &str) -> Vec<u8> {
+    format!("ckp-auth\n{}\n{}\n{}", agent_urn, timestamp, nonce).into_bytes()
+}
+
+/// Whether a signed timestamp is within `SIGNATURE_SKEW` of now
+fn within_skew(timestamp: i64) -> bool {
+    (chrono::Utc::now().timestamp() - timestamp).unsigned_abs() <= SIGNATURE_SKEW.as_secs()
+}
+
+/// Remember a nonce for an agent
+///
+/// # Returns
+/// false if the nonce was already accepted for the agent
+fn remember_nonce(agent_urn: &str, nonce: &str, timestamp: i64) -> bool {
+    let mut seen = SEEN_NONCES.lock().unwrap_or_else(|e| e.into_inner());
+
+    // Nonces past the skew window are rejected by timestamp already
+    seen.retain(|_, signed_at| within_skew(*signed_at));
+    seen.insert((agent_urn.to_string(), nonce.to_string()), timestamp).is_none()
+}
+
+/// Constant-time string comparison (avoids leaking match length via timing)
+fn constant_time_eq(a: &str, b: &str) -> bool {
+    a.len() == b.len() && a.bytes().zip(b.bytes()).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+fn parse_public_key(public_key_hex: &str) -> Result<VerifyingKey> {
+    let bytes: [u8; 32] = hex::decode(public_key_hex)
+        .ok()
+        .and_then(|b| b.try_into().ok())
+        .ok_or_else(|| CkpError::ValidationError(format!(
+            "Invalid Ed25519 public key (expected 32 hex-encoded bytes): {}",
+            public_key_hex
+        )))?;
+
+    VerifyingKey::from_bytes(&bytes)
+        .map_err(|e| CkpError::ValidationError(format!("Invalid Ed25519 public key: {}", e)))
+}
+
+impl Credential {
+    /// Check this credential against the registered material of `agent_urn`
+    ///
+    /// Signatures must also be within `SIGNATURE_SKEW`; whether their nonce
+    /// was used before is checked by `ContinuantTracker::authenticate`.
+    pub fn verify(&self, agent_urn: &str, credentials: &AgentCredentials) -> bool {
+        match self {
+            Credential::ApiToken(token) => {
+                let hash = hash_api_token(token);
+                credentials.api_token_hashes.iter().any(|h| constant_time_eq(h, &hash))
+            }
+            Credential::Signature { timestamp, nonce, signature } => {
+                if nonce.is_empty() || !within_skew(*timestamp) {
+                    return false;
+                }
+                let signature = match Signature::from_slice(signature) {
+                    Ok(signature) => signature,
+                    Err(_) => return false,
+                };
+
+                let message = signed_message(agent_urn, *timestamp, nonce);
+                credentials.public_keys.iter()
+                    .filter_map(|key| parse_public_key(key).ok())
+                    .any(|key| key.verify(&message, &signature).is_ok())
+            }
+        }
+    }
+}
+
+impl ContinuantTracker {
+    /// Issue a new API token for an agent
+    ///
+    /// Only the token's hash is stored; the returned plaintext must be handed
+    /// to the caller now, it cannot be recovered later.
+    ///
+    /// # Returns
+    /// The plaintext token (`ckp_` + 64 hex chars)
+    pub fn issue_api_token(&self, agent_urn: &str) -> Result<String> {
+        let mut bytes = [0u8; 32];
+        rand::thread_rng().fill_bytes(&mut bytes);
+        let token = format!("ckp_{}", hex::encode(bytes));
+
+        let mut agent = self.load_agent_by_urn(agent_urn)?;
+        agent.credentials.api_token_hashes.push(hash_api_token(&token));
+        self.store_agent(&agent)?;
+
+        Ok(token)
+    }
+
+    /// Revoke an API token
+    ///
+    /// # Returns
+    /// true if the token was registered and has been removed
+    pub fn revoke_api_token(&self, agent_urn: &str, token: &str) -> Result<bool> {
+        let hash = hash_api_token(token);
+        let mut agent = self.load_agent_by_urn(agent_urn)?;
+
+        let before = agent.credentials.api_token_hashes.len();
+        agent.credentials.api_token_hashes.retain(|h| !constant_time_eq(h, &hash));
+        let revoked = agent.credentials.api_token_hashes.len() != before;
+
+        if revoked {
+            self.store_agent(&agent)?;
+        }
+
+        Ok(revoked)
+    }
+
+    /// Register an Ed25519 public key (hex-encoded) for an agent
+    pub fn add_public_key(&self, agent_urn: &str, public_key_hex: &str) -> Result<()> {
+        parse_public_key(public_key_hex)?;
+        let public_key_hex = public_key_hex.to_lowercase();
+
+        let mut agent = self.load_agent_by_urn(agent_urn)?;
+        if !agent.credentials.public_keys.contains(&public_key_hex) {
+            agent.credentials.public_keys.push(public_key_hex);
+            self.store_agent(&agent)?;
+        }
+
+        Ok(())
+    }
+
+    /// Authenticate a caller claiming to be `agent_urn`
+    ///
+    /// # Returns
+    /// The authenticated Agent, ready for RBAC evaluation
+    ///
+    /// # Errors
+    /// `CkpError::AuthenticationFailed` if the agent is unknown, the
+    /// credential does not match its registered material, or a signature's
+    /// nonce was already used
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use ckp_core::ContinuantTracker;
+    /// use ckp_core::continuant_tracker::Credential;
+    /// use std::collections::HashMap;
+    /// use std::path::PathBuf;
+    ///
+    /// let tracker = ContinuantTracker::new(PathBuf::from("/project/concepts"));
+    /// let agent = tracker.create_agent("User", "alice", Vec::new(), HashMap::new()).unwrap();
+    /// let token = tracker.issue_api_token(&agent.urn).unwrap();
+    ///
+    /// let caller = tracker.authenticate(&agent.urn, &Credential::ApiToken(token)).unwrap();
+    /// assert_eq!(caller.urn, agent.urn);
+    /// ```
+    pub fn authenticate(&self, agent_urn: &str, credential: &Credential) -> Result<Agent> {
+        let agent = self.load_agent_by_urn(agent_urn)
+            .map_err(|_| CkpError::AuthenticationFailed(format!("Unknown agent: {}", agent_urn)))?;
+
+        if !credential.verify(agent_urn, &agent.credentials) {
+            return Err(CkpError::AuthenticationFailed(format!("Invalid credential for {}", agent_urn)));
+        }
+        if let Credential::Signature { timestamp, nonce, .. } = credential {
+            if !remember_nonce(agent_urn, nonce, *timestamp) {
+                return Err(CkpError::AuthenticationFailed(format!("Replayed signature for {}", agent_urn)));
+            }
+        }
+
+        Ok(agent)
+    }
+
+    /// Resolve a bare API token to the agent it was issued to
+    ///
+    /// For front-ends that receive only a bearer token and need the Agent URN.
+    /// The token is looked up by its hash, not checked against every agent.
+    pub fn authenticate_token(&self, token: &str) -> Result<Agent> {
+        let credential = Credential::ApiToken(token.to_string());
+
+        self.state()?
+            .agent_by_token_hash(&hash_api_token(token))
+            .filter(|agent| credential.verify(&agent.urn, &agent.credentials))
+            .cloned()
+            .ok_or_else(|| CkpError::AuthenticationFailed("Unknown API token".to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ed25519_dalek::{Signer, SigningKey};
+    use std::collections::HashMap;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_api_token_lifecycle() {
+        let temp_dir = TempDir::new().unwrap();
+        let tracker = ContinuantTracker::new(temp_dir.path().to_path_buf());
+        let agent = tracker.create_agent("User", "alice", Vec::new(), HashMap::new()).unwrap();
+
+        let token = tracker.issue_api_token(&agent.urn).unwrap();
+        assert!(token.starts_with("ckp_"));
+
+        // Plaintext never persisted
+        let stored = tracker.load_agent_by_urn(&agent.urn).unwrap();
+        assert_eq!(stored.credentials.api_token_hashes, vec![hash_api_token(&token)]);
+
+        assert!(tracker.authenticate(&agent.urn, &Credential::ApiToken(token.clone())).is_ok());
+        assert_eq!(tracker.authenticate_token(&token).unwrap().urn, agent.urn);
+        assert!(matches!(
+            tracker.authenticate(&agent.urn, &Credential::ApiToken("wrong".to_string())),
+            Err(CkpError::AuthenticationFailed(_))
+        ));
+
+        assert!(tracker.revoke_api_token(&agent.urn, &token).unwrap());
+        assert!(tracker.authenticate(&agent.urn, &Credential::ApiToken(token.clone())).is_err());
+        assert!(tracker.authenticate_token(&token).is_err());
+
+        // The token index is rebuilt when the store is read again
+        let second = tracker.issue_api_token(&agent.urn).unwrap();
+        let reloaded = ContinuantTracker::new(temp_dir.path().to_path_buf());
+        assert_eq!(reloaded.authenticate_token(&second).unwrap().urn, agent.urn);
+    }
+
+    #[test]
+    fn test_signature_authentication() {
+        let temp_dir = TempDir::new().unwrap();
+        let tracker = ContinuantTracker::new(temp_dir.path().to_path_buf());
+        let agent = tracker.create_agent("System", "system:gateway", Vec::new(), HashMap::new()).unwrap();
+
+        let signing_key = SigningKey::from_bytes(&[7u8; 32]);
+        tracker.add_public_key(&agent.urn, &hex::encode(signing_key.verifying_key().to_bytes())).unwrap();
+        assert!(tracker.add_public_key(&agent.urn, "not-a-key").is_err());
+
+        let sign = |timestamp: i64, nonce: &str| Credential::Signature {
+            timestamp,
+            nonce: nonce.to_string(),
+            signature: signing_key.sign(&signed_message(&agent.urn, timestamp, nonce)).to_bytes().to_vec(),
+        };
+        let now = chrono::Utc::now().timestamp();
+
+        let valid = sign(now, "nonce-123");
+        assert!(tracker.authenticate("ckp://Continuant#Agent-nobody", &valid).is_err());
+        assert!(tracker.authenticate(&agent.urn, &valid).is_ok());
+
+        // The same signature cannot be used again, even by a fresh tracker
+        assert!(matches!(tracker.authenticate(&agent.urn, &valid), Err(CkpError::AuthenticationFailed(_))));
+        let reloaded = ContinuantTracker::new(temp_dir.path().to_path_buf());
+        assert!(reloaded.authenticate(&agent.urn, &valid).is_err());
+        assert!(tracker.authenticate(&agent.urn, &sign(now, "nonce-124")).is_ok());
+
+        // Tampered or stale signatures are rejected
+        let Credential::Signature { signature, .. } = sign(now, "nonce-125") else { unreachable!() };
+        let tampered = Credential::Signature { timestamp: now, nonce: "nonce-126".to_string(), signature };
+        assert!(tracker.authenticate(&agent.urn, &tampered).is_err());
+        let stale = now - SIGNATURE_SKEW.as_secs() as i64 - 60;
+        assert!(tracker.authenticate(&agent.urn, &sign(stale, "nonce-127")).is_err());
+    }
+}
@@ -0,0 +1,974 @@
+//! BFO Continuant Tracking (Phase 4 Stage 2)
+//!
+//! Tracks entities that persist through time (BFO Continuants):
+//! - Material Entities (Kernels, Agents)
+//! - Realizable Entities (Roles, Functions, Dispositions)
+//! - Temporal Participation (when Continuants participate in Processes)
+//!
+//! This complements ProcessTracker (Occurrents) with persistent entity tracking.
+
+pub mod auth;
+pub mod participation;
+pub mod store;
+
+pub use auth::{hash_api_token, signed_message, AgentCredentials, Credential, SIGNATURE_SKEW};
+pub use participation::{ArtifactProvenance, FunctionRealization, ParticipationQuery, ResolvedParticipation};
+pub use store::{
+    ContinuantRecord, ContinuantSnapshot, ContinuantStore, JsonFileStore, JsonlLogStore,
+    CONTINUANTS_KERNEL, DEFAULT_COMPACTION_THRESHOLD,
+};
+
+use crate::errors::{CkpError, Result};
+use once_cell::sync::OnceCell;
+use serde::{Serialize, Deserialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::{Mutex, MutexGuard};
+
+/// ContinuantTracker - Tracks BFO Continuants (persistent entities)
+///
+/// Entities are held in memory and persisted through a pluggable
+/// `ContinuantStore`. The store is read once, on first use (or explicitly via
+/// `load()`), and every mutation is appended to it.
+pub struct ContinuantTracker {
+    /// Persistence backend
+    store: Box<dyn ContinuantStore>,
+
+    /// In-memory state (loaded lazily from the store)
+    state: OnceCell<Mutex<ContinuantSnapshot>>,
+}
+
+impl ContinuantTracker {
+    /// Create new tracker backed by the `.continuants/` JSON file layout
+    pub fn new(concepts_root: PathBuf) -> Self {
+        Self::with_store(Box::new(JsonFileStore::new(&concepts_root)))
+    }
+
+    /// Create new tracker with a custom persistence backend
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use ckp_core::continuant_tracker::{ContinuantTracker, JsonlLogStore};
+    /// use std::path::PathBuf;
+    ///
+    /// let root = PathBuf::from("/project/concepts");
+    /// let store = JsonlLogStore::new(&root).with_compaction_threshold(500);
+    /// let tracker = ContinuantTracker::with_store(Box::new(store));
+    /// tracker.load().unwrap();
+    /// ```
+    pub fn with_store(store: Box<dyn ContinuantStore>) -> Self {
+        Self {
+            store,
+            state: OnceCell::new(),
+        }
+    }
+
+    /// Load persisted continuants into memory
+    ///
+    /// Called implicitly on first use; call explicitly at startup to surface
+    /// storage errors early.
+    pub fn load(&self) -> Result<()> {
+        self.state().map(|_| ())
+    }
+
+    /// Compact the backing store from the current in-memory state
+    pub fn compact(&self) -> Result<()> {
+        let state = self.state()?;
+        self.store.compact(&state)
+    }
+
+    /// Generate Continuant URN
+    ///
+    /// Format: `ckp://Continuant#{type}-{identifier}`
+    ///
+    /// Examples:
+    /// - `ckp://Continuant#Kernel-System.Gateway`
+    /// - `ckp://Continuant#Agent-user:alice`
+    /// - `ckp://Continuant#Role-admin`
+    pub fn generate_continuant_urn(&self, continuant_type: &str, identifier: &str) -> String {
+        format!("ckp://Continuant#{}-{}", continuant_type, identifier)
+    }
+
+    /// Create a Material Entity (Kernel)
+    ///
+    /// BFO Material Entity (bfo:0000040) - Physical or digital entity
+    ///
+    /// Examples:
+    /// - Kernel instance
+    /// - File system entity
+    /// - Storage artifact
+    pub fn create_kernel_entity(
+        &self,
+        kernel_name: &str,
+        version: &str,
+        kernel_type: &str,
+        metadata: HashMap<String, serde_json::Value>,
+    ) -> Result<KernelEntity> {
+        self.create_kernel_entity_as(kernel_name, version, kernel_type, metadata, None)
+    }
+
+    /// Create a Material Entity (Kernel) on behalf of an agent
+    ///
+    /// Same as `create_kernel_entity`, but records `agent` as responsible for
+    /// the `Created` lifecycle transition.
+    pub fn create_kernel_entity_as(
+        &self,
+        kernel_name: &str,
+        version: &str,
+        kernel_type: &str,
+        metadata: HashMap<String, serde_json::Value>,
+        agent: Option<&str>,
+    ) -> Result<KernelEntity> {
+        let urn = self.generate_continuant_urn("Kernel", kernel_name);
+        let created_at = chrono::Utc::now().to_rfc3339();
+
+        let mut entity = KernelEntity {
+            urn: urn.clone(),
+            kernel_name: kernel_name.to_string(),
+            version: version.to_string(),
+            kernel_type: kernel_type.to_string(),
+            created_at: created_at.clone(),
+            bfo_type: crate::ontology::BfoEntityType::MaterialEntity,
+            roles: Vec::new(),
+            functions: Vec::new(),
+            participations: Vec::new(),
+            dispositions: Vec::new(),
+            lifecycle: Vec::new(),
+            metadata,
+        };
+
+        // Keep history across re-creation of the same kernel
+        if let Ok(previous) = self.get_kernel_entity(kernel_name) {
+            entity.lifecycle = previous.lifecycle;
+        }
+        entity.lifecycle.push(LifecycleTransition {
+            event: LifecycleEvent::Created,
+            timestamp: created_at,
+            agent: agent.map(|a| a.to_string()),
+            details: HashMap::new(),
+        });
+
+        // Persist
+        self.store_kernel_entity(&entity)?;
+
+        Ok(entity)
+    }
+
+    /// Create an Agent (User or System)
+    ///
+    /// BFO Material Entity → ckp:Agent
+    ///
+    /// Examples:
+    /// - User: alice, bob
+    /// - System: ConceptKernel runtime
+    pub fn create_agent(
+        &self,
+        agent_type: &str,  // "User" or "System"
+        identifier: &str,
+        roles: Vec<Role>,
+        metadata: HashMap<String, serde_json::Value>,
+    ) -> Result<Agent> {
+        let urn = self.generate_continuant_urn("Agent", identifier);
+        let created_at = chrono::Utc::now().to_rfc3339();
+
+        let agent = Agent {
+            urn: urn.clone(),
+            agent_type: agent_type.to_string(),
+            identifier: identifier.to_string(),
+            created_at: created_at.clone(),
+            roles,
+            participations: Vec::new(),
+            credentials: AgentCredentials::default(),
+            metadata,
+        };
+
+        // Persist
+        self.store_agent(&agent)?;
+
+        Ok(agent)
+    }
+
+    /// Assign Role to Kernel or Agent
+    ///
+    /// BFO Realizable Entity → Role
+    ///
+    /// Roles are things entities can *bear* (e.g., "admin", "voter", "gateway")
+    pub fn assign_role(
+        &self,
+        continuant_urn: &str,
+        role: Role,
+    ) -> Result<()> {
+        // Load continuant
+        if continuant_urn.contains("Kernel-") {
+            let mut entity = self.load_kernel_entity_by_urn(continuant_urn)?;
+            entity.roles.push(role);
+            self.store_kernel_entity(&entity)?;
+        } else if continuant_urn.contains("Agent-") {
+            let mut agent = self.load_agent_by_urn(continuant_urn)?;
+            agent.roles.push(role);
+            self.store_agent(&agent)?;
+        } else {
+            return Err(CkpError::ValidationError(format!(
+                "Unknown continuant type: {}",
+                continuant_urn
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// Assign Function to Kernel
+    ///
+    /// BFO Realizable Entity → Function
+    ///
+    /// Functions are what entities are *designed to do* (e.g., "consensus", "gateway", "storage")
+    pub fn assign_function(
+        &self,
+        kernel_urn: &str,
+        function: Function,
+    ) -> Result<()> {
+        let mut entity = self.load_kernel_entity_by_urn(kernel_urn)?;
+        entity.functions.push(function);
+        self.store_kernel_entity(&entity)?;
+
+        Ok(())
+    }
+
+    /// Assign Disposition to Kernel
+    ///
+    /// BFO Realizable Entity → Disposition
+    ///
+    /// Dispositions with a trigger are evaluated by `DispositionEvaluatorDaemon`.
+    /// Re-assigning a disposition with the same name replaces it.
+    pub fn assign_disposition(
+        &self,
+        kernel_urn: &str,
+        disposition: Disposition,
+    ) -> Result<()> {
+        let mut entity = self.load_kernel_entity_by_urn(kernel_urn)?;
+        entity.dispositions.retain(|d| d.name != disposition.name);
+        entity.dispositions.push(disposition);
+        self.store_kernel_entity(&entity)?;
+
+        Ok(())
+    }
+
+    /// Update the realization state of a kernel's disposition
+    ///
+    /// # Arguments
+    /// * `kernel_name` - Bearer kernel name
+    /// * `disposition_name` - Disposition name
+    /// * `realized_at` - Realization timestamp, or None when the condition cleared
+    pub fn set_disposition_realized(
+        &self,
+        kernel_name: &str,
+        disposition_name: &str,
+        realized_at: Option<String>,
+    ) -> Result<()> {
+        let mut entity = self.get_kernel_entity(kernel_name)?;
+        let disposition = entity.dispositions.iter_mut()
+            .find(|d| d.name == disposition_name)
+            .ok_or_else(|| CkpError::ValidationError(format!(
+                "Disposition '{}' not found on kernel {}",
+                disposition_name,
+                kernel_name
+            )))?;
+
+        disposition.realized_at = realized_at;
+        self.store_kernel_entity(&entity)
+    }
+
+    /// Record a lifecycle transition for a kernel
+    ///
+    /// # Arguments
+    /// * `kernel_name` - Kernel name
+    /// * `event` - Transition that occurred
+    /// * `agent` - Responsible agent URN, if known
+    /// * `details` - Transition-specific data (e.g. `from`/`to` for Versioned)
+    pub fn record_lifecycle(
+        &self,
+        kernel_name: &str,
+        event: LifecycleEvent,
+        agent: Option<&str>,
+        details: HashMap<String, serde_json::Value>,
+    ) -> Result<()> {
+        let mut entity = self.get_kernel_entity(kernel_name)?;
+        entity.lifecycle.push(LifecycleTransition {
+            event,
+            timestamp: chrono::Utc::now().to_rfc3339(),
+            agent: agent.map(|a| a.to_string()),
+            details,
+        });
+        self.store_kernel_entity(&entity)
+    }
+
+    /// Lifecycle history of a kernel, oldest first
+    ///
+    /// # Arguments
+    /// * `kernel_urn` - `ckp://Continuant#Kernel-{name}`
+    pub fn history(&self, kernel_urn: &str) -> Result<Vec<LifecycleTransition>> {
+        Ok(self.load_kernel_entity_by_urn(kernel_urn)?.lifecycle)
+    }
+
+    /// Record participation in a Process
+    ///
+    /// BFO Relation: Continuant participates_in Occurrent
+    ///
+    /// Links persistent entities (Kernels, Agents) to temporal processes
+    pub fn record_participation(
+        &self,
+        continuant_urn: &str,
+        process_urn: &str,
+        role_in_process: &str,
+        metadata: HashMap<String, serde_json::Value>,
+    ) -> Result<()> {
+        let timestamp = chrono::Utc::now().to_rfc3339();
+
+        let participation = Participation {
+            process_urn: process_urn.to_string(),
+            role_in_process: role_in_process.to_string(),
+            timestamp: timestamp.clone(),
+            metadata,
+        };
+
+        // Add to continuant
+        if continuant_urn.contains("Kernel-") {
+            let mut entity = self.load_kernel_entity_by_urn(continuant_urn)?;
+            entity.participations.push(participation);
+            self.store_kernel_entity(&entity)?;
+        } else if continuant_urn.contains("Agent-") {
+            let mut agent = self.load_agent_by_urn(continuant_urn)?;
+            agent.participations.push(participation);
+            self.store_agent(&agent)?;
+        }
+
+        Ok(())
+    }
+
+    /// Query kernel entities by role
+    pub fn query_kernels_by_role(&self, role_name: &str) -> Result<Vec<KernelEntity>> {
+        let state = self.state()?;
+
+        Ok(state.kernels.values()
+            .filter(|entity| entity.roles.iter().any(|r| r.name == role_name))
+            .cloned()
+            .collect())
+    }
+
+    /// Query agents by role
+    pub fn query_agents_by_role(&self, role_name: &str) -> Result<Vec<Agent>> {
+        let state = self.state()?;
+
+        Ok(state.agents.values()
+            .filter(|agent| agent.roles.iter().any(|r| r.name == role_name))
+            .cloned()
+            .collect())
+    }
+
+    /// Query participations for a process
+    pub fn query_participants(&self, process_urn: &str) -> Result<Vec<String>> {
+        let state = self.state()?;
+        let mut participants = Vec::new();
+
+        // Check kernels
+        for entity in state.kernels.values() {
+            if entity.participations.iter().any(|p| p.process_urn == process_urn) {
+                participants.push(entity.urn.clone());
+            }
+        }
+
+        // Check agents
+        for agent in state.agents.values() {
+            if agent.participations.iter().any(|p| p.process_urn == process_urn) {
+                participants.push(agent.urn.clone());
+            }
+        }
+
+        Ok(participants)
+    }
+
+    /// Get roles assigned to a specific kernel (Phase 4 Stage 3 - RBAC integration)
+    ///
+    /// # Arguments
+    /// * `kernel_name` - Kernel name (e.g., "System.Gateway.HTTP")
+    ///
+    /// # Returns
+    /// Vector of Role entities assigned to the kernel
+    pub fn get_kernel_roles(&self, kernel_name: &str) -> Result<Vec<Role>> {
+        let state = self.state()?;
+
+        // Kernel entity not found - return empty roles
+        Ok(state.kernels.get(kernel_name)
+            .map(|entity| entity.roles.clone())
+            .unwrap_or_default())
+    }
+
+    // ========================================================================
+    // PRIVATE STORAGE METHODS
+    // ========================================================================
+
+    fn state(&self) -> Result<MutexGuard<'_, ContinuantSnapshot>> {
+        let state = self.state.get_or_try_init(|| self.store.load().map(Mutex::new))?;
+        Ok(state.lock().unwrap())
+    }
+
+    fn persist(&self, record: ContinuantRecord) -> Result<()> {
+        let mut state = self.state()?;
+        self.store.append(&record)?;
+        state.apply(record);
+
+        if self.store.needs_compaction() {
+            self.store.compact(&state)?;
+        }
+
+        Ok(())
+    }
+
+    fn store_kernel_entity(&self, entity: &KernelEntity) -> Result<()> {
+        self.persist(ContinuantRecord::Kernel(entity.clone()))
+    }
+
+    fn store_agent(&self, agent: &Agent) -> Result<()> {
+        self.persist(ContinuantRecord::Agent(agent.clone()))
+    }
+
+    fn load_agent_by_urn(&self, urn: &str) -> Result<Agent> {
+        let identifier = urn.split("Agent-").nth(1)
+            .ok_or_else(|| CkpError::ParseError(format!("Invalid agent URN: {}", urn)))?;
+
+        self.state()?.agents.get(identifier)
+            .cloned()
+            .ok_or_else(|| CkpError::ValidationError(format!("Agent not found: {}", identifier)))
+    }
+
+    /// List all kernel entities
+    ///
+    /// Returns all KernelEntity records known to the tracker's store.
+    /// If no kernels have been recorded yet, returns empty vec.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use ckp_core::ContinuantTracker;
+    /// use std::path::PathBuf;
+    ///
+    /// let tracker = ContinuantTracker::new(PathBuf::from("/project/concepts"));
+    /// let entities = tracker.list_kernel_entities().unwrap();
+    ///
+    /// for entity in entities {
+    ///     println!("{}: {} ({})",
+    ///         entity.kernel_name,
+    ///         entity.version,
+    ///         entity.bfo_type.label()
+    ///     );
+    /// }
+    /// ```
+    pub fn list_kernel_entities(&self) -> Result<Vec<KernelEntity>> {
+        let mut results: Vec<KernelEntity> = self.state()?.kernels.values().cloned().collect();
+
+        // Sort by kernel_name for consistent ordering
+        results.sort_by(|a, b| a.kernel_name.cmp(&b.kernel_name));
+
+        Ok(results)
+    }
+
+    /// List all agents
+    ///
+    /// Returns all Agent records known to the tracker's store, sorted by identifier.
+    pub fn list_agents(&self) -> Result<Vec<Agent>> {
+        let mut results: Vec<Agent> = self.state()?.agents.values().cloned().collect();
+        results.sort_by(|a, b| a.identifier.cmp(&b.identifier));
+
+        Ok(results)
+    }
+
+    /// Get kernel entity by name
+    ///
+    /// Returns a specific KernelEntity by kernel name using URN resolution.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use ckp_core::ContinuantTracker;
+    /// use std::path::PathBuf;
+    ///
+    /// let tracker = ContinuantTracker::new(PathBuf::from("/project/concepts"));
+    /// let entity = tracker.get_kernel_entity("System.Gateway").unwrap();
+    /// println!("Found: {}", entity.kernel_name);
+    /// ```
+    pub fn get_kernel_entity(&self, kernel_name: &str) -> Result<KernelEntity> {
+        // Generate the Continuant URN
+        let urn = self.generate_continuant_urn("Kernel", kernel_name);
+
+        // Load using the internal helper that uses URN resolution
+        self.load_kernel_entity_by_urn(&urn)
+    }
+
+    /// Internal helper: Load kernel entity by URN
+    fn load_kernel_entity_by_urn(&self, urn: &str) -> Result<KernelEntity> {
+        // Extract kernel name from URN (format: ckp://Continuant#Kernel-{name})
+        let kernel_name = urn.split("Kernel-").nth(1)
+            .ok_or_else(|| CkpError::ParseError(format!("Invalid kernel URN: {}", urn)))?;
+
+        self.state()?.kernels.get(kernel_name)
+            .cloned()
+            .ok_or_else(|| CkpError::ValidationError(format!(
+                "Kernel entity not found: {}",
+                kernel_name
+            )))
+    }
+}
+
+// ============================================================================
+// DATA STRUCTURES
+// ============================================================================
+
+/// Kernel Entity (BFO Material Entity → ckp:Kernel)
+///
+/// A persistent entity that processes jobs and maintains state
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KernelEntity {
+    /// Continuant URN (ckp://Continuant#Kernel-{name})
+    pub urn: String,
+
+    /// Kernel name (e.g., "System.Gateway")
+    pub kernel_name: String,
+
+    /// Version (from ontology or git)
+    pub version: String,
+
+    /// Kernel type (rust:hot, rust:cold, node:hot, node:cold)
+    pub kernel_type: String,
+
+    /// Creation timestamp
+    pub created_at: String,
+
+    /// BFO classification (always MaterialEntity for kernels)
+    #[serde(default = "default_kernel_bfo_type")]
+    pub bfo_type: crate::ontology::BfoEntityType,
+
+    /// Roles this kernel bears (BFO Roles)
+    pub roles: Vec<Role>,
+
+    /// Functions this kernel realizes (BFO Functions)
+    pub functions: Vec<Function>,
+
+    /// Processes this kernel has participated in
+    pub participations: Vec<Participation>,
+
+    /// Dispositions this kernel bears (BFO Dispositions)
+    #[serde(default)]
+    pub dispositions: Vec<Disposition>,
+
+    /// Lifecycle transitions, oldest first
+    #[serde(default)]
+    pub lifecycle: Vec<LifecycleTransition>,
+
+    /// Additional metadata
+    pub metadata: HashMap<String, serde_json::Value>,
+}
+
+/// Default BFO type for kernels (Material Entity)
+fn default_kernel_bfo_type() -> crate::ontology::BfoEntityType {
+    crate::ontology::BfoEntityType::MaterialEntity
+}
+
+impl crate::ontology::BfoAligned for KernelEntity {
+    fn bfo_type(&self) -> crate::ontology::BfoEntityType {
+        self.bfo_type
+    }
+}
+
+/// Agent (BFO Material Entity → ckp:Agent)
+///
+/// A user or system entity that can initiate actions
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Agent {
+    /// Continuant URN (ckp://Continuant#Agent-{identifier})
+    pub urn: String,
+
+    /// Agent type: "User" or "System"
+    pub agent_type: String,
+
+    /// Identifier (e.g., "alice", "system:runtime")
+    pub identifier: String,
+
+    /// Creation timestamp
+    pub created_at: String,
+
+    /// Roles this agent bears
+    pub roles: Vec<Role>,
+
+    /// Processes this agent has participated in
+    pub participations: Vec<Participation>,
+
+    /// Authentication material (API token hashes, public keys)
+    #[serde(default, skip_serializing_if = "AgentCredentials::is_empty")]
+    pub credentials: AgentCredentials,
+
+    /// Additional metadata (email, permissions, etc.)
+    pub metadata: HashMap<String, serde_json::Value>,
+}
+
+/// Role (BFO Realizable Entity)
+///
+/// Something an entity can *bear* (e.g., "admin", "voter", "gateway-operator")
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Role {
+    /// Role name (e.g., "admin", "consensus-voter")
+    pub name: String,
+
+    /// Role description
+    pub description: String,
+
+    /// When this role was assigned
+    pub assigned_at: String,
+
+    /// Role-specific metadata (permissions, scope, etc.)
+    pub metadata: HashMap<String, serde_json::Value>,
+}
+
+/// Function (BFO Realizable Entity)
+///
+/// What an entity is *designed to do* (e.g., "consensus", "gateway", "storage")
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Function {
+    /// Function name (e.g., "gateway", "consensus")
+    pub name: String,
+
+    /// Function description
+    pub description: String,
+
+    /// When this function was assigned/recognized
+    pub assigned_at: String,
+
+    /// Function-specific metadata (capabilities, contracts, etc.)
+    pub metadata: HashMap<String, serde_json::Value>,
+}
+
+/// Participation (BFO Relation: participates_in)
+///
+/// Records when a Continuant participates in an Occurrent (Process)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Participation {
+    /// Process URN this entity participated in
+    pub process_urn: String,
+
+    /// Role in this specific process (e.g., "source", "target", "validator")
+    pub role_in_process: String,
+
+    /// When participation occurred
+    pub timestamp: String,
+
+    /// Participation-specific metadata
+    pub metadata: HashMap<String, serde_json::Value>,
+}
+
+/// Disposition (BFO Realizable Entity)
+///
+/// Tendency of an entity to behave in a certain way
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Disposition {
+    /// Disposition name (e.g., "always-validates", "caches-results")
+    pub name: String,
+
+    /// Description
+    pub description: String,
+
+    /// When recognized
+    pub recognized_at: String,
+
+    /// Condition under which the disposition is realized (None = descriptive only)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub trigger: Option<DispositionTrigger>,
+
+    /// When the trigger condition last became true (None = not currently realized)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub realized_at: Option<String>,
+
+    /// Disposition metadata
+    pub metadata: HashMap<String, serde_json::Value>,
+}
+
+/// Kernel lifecycle transition kind
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LifecycleEvent {
+    Created,
+    Bootstrapped,
+    Versioned,
+    Archived,
+    Deleted,
+}
+
+/// A single lifecycle transition of a KernelEntity
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LifecycleTransition {
+    /// What happened
+    pub event: LifecycleEvent,
+
+    /// When it happened
+    pub timestamp: String,
+
+    /// Agent URN responsible for the transition (None = system/unknown)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub agent: Option<String>,
+
+    /// Transition-specific details
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub details: HashMap<String, serde_json::Value>,
+}
+
+/// Observable kernel metric a disposition trigger watches
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum TriggerMetric {
+    /// Jobs waiting in `queue/inbox`
+    InboxDepth,
+
+    /// Jobs in `queue/staging`
+    StagingDepth,
+
+    /// Jobs in `queue/ready`
+    ReadyDepth,
+
+    /// Instances in `storage/`
+    StorageCount,
+}
+
+/// Comparison between the observed metric and the threshold
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TriggerComparison {
+    Gt,
+    Gte,
+    Lt,
+    Lte,
+    Eq,
+}
+
+impl TriggerComparison {
+    /// Apply comparison: `value <op> threshold`
+    pub fn holds(&self, value: u64, threshold: u64) -> bool {
+        match self {
+            Self::Gt => value > threshold,
+            Self::Gte => value >= threshold,
+            Self::Lt => value < threshold,
+            Self::Lte => value <= threshold,
+            Self::Eq => value == threshold,
+        }
+    }
+}
+
+/// Trigger condition for a Disposition
+///
+/// Example: "when inbox > 1000, emit ScaleOut to System.Scaler"
+///
+/// ```
+/// use ckp_core::continuant_tracker::{DispositionTrigger, TriggerMetric, TriggerComparison};
+///
+/// let trigger = DispositionTrigger {
+///     metric: TriggerMetric::InboxDepth,
+///     comparison: TriggerComparison::Gt,
+///     threshold: 1000,
+///     target_kernel: "System.Scaler".to_string(),
+///     payload: serde_json::json!({"action": "scale-out"}),
+/// };
+/// assert!(trigger.is_met(1001));
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DispositionTrigger {
+    /// Metric to observe on the bearer kernel
+    pub metric: TriggerMetric,
+
+    /// Comparison operator
+    #[serde(rename = "op")]
+    pub comparison: TriggerComparison,
+
+    /// Threshold value
+    pub threshold: u64,
+
+    /// Kernel that receives a job when the disposition is realized
+    #[serde(rename = "targetKernel")]
+    pub target_kernel: String,
+
+    /// Extra payload included in the emitted job
+    #[serde(default)]
+    pub payload: serde_json::Value,
+}
+
+impl DispositionTrigger {
+    /// Whether the trigger condition holds for an observed metric value
+    pub fn is_met(&self, value: u64) -> bool {
+        self.comparison.holds(value, self.threshold)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_create_kernel_entity() {
+        let temp_dir = TempDir::new().unwrap();
+        let tracker = ContinuantTracker::new(temp_dir.path().to_path_buf());
+
+        let mut metadata = HashMap::new();
+        metadata.insert("port".to_string(), serde_json::json!(56000));
+
+        let entity = tracker.create_kernel_entity(
+            "System.Gateway",
+            "v1.3.16",
+            "rust:hot",
+            metadata,
+        ).unwrap();
+
+        assert_eq!(entity.kernel_name, "System.Gateway");
+        assert_eq!(entity.version, "v1.3.16");
+        assert!(entity.urn.contains("Continuant#Kernel-System.Gateway"));
+    }
+
+    #[test]
+    fn test_assign_role() {
+        let temp_dir = TempDir::new().unwrap();
+        let tracker = ContinuantTracker::new(temp_dir.path().to_path_buf());
+
+        let entity = tracker.create_kernel_entity(
+            "System.Consensus",
+            "v1.0",
+            "rust:hot",
+            HashMap::new(),
+        ).unwrap();
+
+        let role = Role {
+            name: "consensus-voter".to_string(),
+            description: "Can vote on proposals".to_string(),
+            assigned_at: chrono::Utc::now().to_rfc3339(),
+            metadata: HashMap::new(),
+        };
+
+        tracker.assign_role(&entity.urn, role).unwrap();
+
+        // Reload and verify
+        let reloaded = tracker.load_kernel_entity_by_urn(&entity.urn).unwrap();
+        assert_eq!(reloaded.roles.len(), 1);
+        assert_eq!(reloaded.roles[0].name, "consensus-voter");
+    }
+
+    #[test]
+    fn test_query_by_role() {
+        let temp_dir = TempDir::new().unwrap();
+        let tracker = ContinuantTracker::new(temp_dir.path().to_path_buf());
+
+        // Create kernel with role
+        let entity = tracker.create_kernel_entity(
+            "System.Gateway",
+            "v1.0",
+            "rust:hot",
+            HashMap::new(),
+        ).unwrap();
+
+        let role = Role {
+            name: "gateway".to_string(),
+            description: "HTTP gateway".to_string(),
+            assigned_at: chrono::Utc::now().to_rfc3339(),
+            metadata: HashMap::new(),
+        };
+
+        tracker.assign_role(&entity.urn, role).unwrap();
+
+        // Query
+        let results = tracker.query_kernels_by_role("gateway").unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].kernel_name, "System.Gateway");
+    }
+
+    #[test]
+    fn test_assign_disposition_with_trigger() {
+        let temp_dir = TempDir::new().unwrap();
+        let tracker = ContinuantTracker::new(temp_dir.path().to_path_buf());
+
+        let entity = tracker.create_kernel_entity("System.Worker", "v1.0", "rust:cold", HashMap::new()).unwrap();
+
+        let disposition = Disposition {
+            name: "ScaleOut".to_string(),
+            description: "Scale out under load".to_string(),
+            recognized_at: chrono::Utc::now().to_rfc3339(),
+            trigger: Some(DispositionTrigger {
+                metric: TriggerMetric::InboxDepth,
+                comparison: TriggerComparison::Gt,
+                threshold: 1000,
+                target_kernel: "System.Scaler".to_string(),
+                payload: serde_json::Value::Null,
+            }),
+            realized_at: None,
+            metadata: HashMap::new(),
+        };
+
+        tracker.assign_disposition(&entity.urn, disposition.clone()).unwrap();
+        tracker.assign_disposition(&entity.urn, disposition).unwrap();
+
+        let reloaded = tracker.get_kernel_entity("System.Worker").unwrap();
+        assert_eq!(reloaded.dispositions.len(), 1);
+        assert!(reloaded.dispositions[0].trigger.as_ref().unwrap().is_met(1001));
+        assert!(!reloaded.dispositions[0].trigger.as_ref().unwrap().is_met(1000));
+
+        tracker.set_disposition_realized("System.Worker", "ScaleOut", Some("now".to_string())).unwrap();
+        let reloaded = tracker.get_kernel_entity("System.Worker").unwrap();
+        assert_eq!(reloaded.dispositions[0].realized_at.as_deref(), Some("now"));
+    }
+
+    #[test]
+    fn test_lifecycle_history() {
+        let temp_dir = TempDir::new().unwrap();
+        let tracker = ContinuantTracker::new(temp_dir.path().to_path_buf());
+
+        let entity = tracker.create_kernel_entity("System.Gateway", "v1.0", "rust:hot", HashMap::new()).unwrap();
+
+        let mut details = HashMap::new();
+        details.insert("from".to_string(), serde_json::json!("v1.0"));
+        details.insert("to".to_string(), serde_json::json!("v1.1"));
+        tracker.record_lifecycle("System.Gateway", LifecycleEvent::Versioned, Some("ckp://Continuant#Agent-alice"), details).unwrap();
+
+        // Re-creating keeps earlier history
+        tracker.create_kernel_entity("System.Gateway", "v1.1", "rust:hot", HashMap::new()).unwrap();
+
+        let history = tracker.history(&entity.urn).unwrap();
+        let events: Vec<LifecycleEvent> = history.iter().map(|t| t.event).collect();
+        assert_eq!(events, vec![LifecycleEvent::Created, LifecycleEvent::Versioned, LifecycleEvent::Created]);
+        assert_eq!(history[1].agent.as_deref(), Some("ckp://Continuant#Agent-alice"));
+        assert_eq!(history[1].details["to"], "v1.1");
+    }
+
+    #[test]
+    fn test_jsonl_store_reload_and_compaction() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path().to_path_buf();
+        let new_tracker = || {
+            let store = JsonlLogStore::new(&root).with_compaction_threshold(3);
+            ContinuantTracker::with_store(Box::new(store))
+        };
+
+        let tracker = new_tracker();
+        let entity = tracker.create_kernel_entity("System.Gateway", "v1.0", "rust:hot", HashMap::new()).unwrap();
+        tracker.create_agent("User", "user:alice", Vec::new(), HashMap::new()).unwrap();
+
+        let log_path = root.join(CONTINUANTS_KERNEL).join("storage").join("continuants.log.jsonl");
+        assert_eq!(std::fs::read_to_string(&log_path).unwrap().lines().count(), 2);
+
+        // Third mutation crosses the threshold and compacts
+        tracker.record_participation(&entity.urn, "ckp://Process#invoke-tx1", "source", HashMap::new()).unwrap();
+        assert_eq!(std::fs::read_to_string(&log_path).unwrap(), "");
+
+        tracker.record_participation(&entity.urn, "ckp://Process#invoke-tx2", "source", HashMap::new()).unwrap();
+
+        // A fresh tracker replays snapshot + log
+        let reloaded = new_tracker();
+        reloaded.load().unwrap();
+        assert_eq!(reloaded.get_kernel_entity("System.Gateway").unwrap().participations.len(), 2);
+        assert_eq!(reloaded.query_participants("ckp://Process#invoke-tx2").unwrap(), vec![entity.urn]);
+        assert!(reloaded.load_agent_by_urn("ckp://Continuant#Agent-user:alice").is_ok());
+    }
+}
@@ -0,0 +1,233 @@
+//! Participation queries joining Continuants and Processes
+//!
+//! ContinuantTracker records *that* an entity participated in a process;
+//! ProcessTracker records *what* the process was. `ParticipationQuery` joins
+//! the two so provenance questions have direct answers, e.g. "which agent
+//! participated in the emit that produced this artifact?".
+
+use super::{Agent, ContinuantTracker, Function, KernelEntity, Participation};
+use crate::errors::Result;
+use crate::process_tracker::{Process, ProcessTracker, QueryFilters};
+
+/// A participation record resolved against its process
+#[derive(Debug, Clone)]
+pub struct ResolvedParticipation {
+    /// Participation as recorded on the continuant
+    pub participation: Participation,
+
+    /// The process, if it is still known to the ProcessTracker
+    pub process: Option<Process>,
+}
+
+/// A kernel function together with the processes that realized it
+#[derive(Debug, Clone)]
+pub struct FunctionRealization {
+    /// Function borne by the kernel
+    pub function: Function,
+
+    /// Processes in which the kernel realized this function
+    pub processes: Vec<Process>,
+}
+
+/// Agents that participated in a process producing an artifact
+#[derive(Debug, Clone)]
+pub struct ArtifactProvenance {
+    /// Process that produced the artifact
+    pub process: Process,
+
+    /// Agents that participated in that process
+    pub agents: Vec<Agent>,
+}
+
+/// Join view over ContinuantTracker and ProcessTracker
+///
+/// # Examples
+///
+/// ```no_run
+/// use ckp_core::{ContinuantTracker, ProcessTracker};
+/// use ckp_core::continuant_tracker::ParticipationQuery;
+/// use std::path::PathBuf;
+///
+/// let root = PathBuf::from("/project/concepts");
+/// let continuants = ContinuantTracker::new(root.clone());
+/// let processes = ProcessTracker::new(root).unwrap();
+/// let query = ParticipationQuery::new(&continuants, &processes);
+///
+/// for agent in query.agents_in("tx_123").unwrap() {
+///     println!("{}", agent.identifier);
+/// }
+/// ```
+pub struct ParticipationQuery<'a> {
+    continuants: &'a ContinuantTracker,
+    processes: &'a ProcessTracker,
+}
+
+impl<'a> ParticipationQuery<'a> {
+    /// Create a join view over both trackers
+    pub fn new(continuants: &'a ContinuantTracker, processes: &'a ProcessTracker) -> Self {
+        Self { continuants, processes }
+    }
+
+    /// All participations of a kernel or agent, resolved to their processes
+    ///
+    /// # Arguments
+    /// * `continuant_urn` - `ckp://Continuant#Agent-{id}` or `ckp://Continuant#Kernel-{name}`
+    ///
+    /// # Returns
+    /// Participations sorted by timestamp ascending
+    pub fn participations_of(&self, continuant_urn: &str) -> Result<Vec<ResolvedParticipation>> {
+        let participations = if continuant_urn.contains("Kernel-") {
+            self.continuants.load_kernel_entity_by_urn(continuant_urn)?.participations
+        } else {
+            self.continuants.load_agent_by_urn(continuant_urn)?.participations
+        };
+
+        let mut resolved: Vec<ResolvedParticipation> = participations
+            .into_iter()
+            .map(|participation| {
+                let process = self.processes.load_process(&participation.process_urn);
+                ResolvedParticipation { participation, process }
+            })
+            .collect();
+
+        resolved.sort_by(|a, b| a.participation.timestamp.cmp(&b.participation.timestamp));
+
+        Ok(resolved)
+    }
+
+    /// Agents that participated in a process
+    ///
+    /// # Arguments
+    /// * `process_id` - Process URN (`ckp://Process#...`) or transaction ID
+    pub fn agents_in(&self, process_id: &str) -> Result<Vec<Agent>> {
+        let process_urns = self.resolve_process_urns(process_id)?;
+
+        Ok(self.continuants.list_agents()?
+            .into_iter()
+            .filter(|agent| agent.participations.iter().any(|p| process_urns.contains(&p.process_urn)))
+            .collect())
+    }
+
+    /// Kernels that participated in a process
+    ///
+    /// # Arguments
+    /// * `process_id` - Process URN (`ckp://Process#...`) or transaction ID
+    pub fn kernels_in(&self, process_id: &str) -> Result<Vec<KernelEntity>> {
+        let process_urns = self.resolve_process_urns(process_id)?;
+
+        Ok(self.continuants.list_kernel_entities()?
+            .into_iter()
+            .filter(|entity| entity.participations.iter().any(|p| process_urns.contains(&p.process_urn)))
+            .collect())
+    }
+
+    /// Functions a kernel bears, with the processes that realized each one
+    ///
+    /// A participation realizes a function when its `role_in_process` or its
+    /// `function` metadata names that function.
+    ///
+    /// # Arguments
+    /// * `kernel_name` - Kernel name (e.g., "System.Gateway")
+    pub fn functions_realized_by(&self, kernel_name: &str) -> Result<Vec<FunctionRealization>> {
+        let entity = self.continuants.get_kernel_entity(kernel_name)?;
+
+        Ok(entity.functions
+            .iter()
+            .map(|function| {
+                let processes = entity.participations
+                    .iter()
+                    .filter(|p| {
+                        p.role_in_process == function.name
+                            || p.metadata.get("function").and_then(|v| v.as_str()) == Some(function.name.as_str())
+                    })
+                    .filter_map(|p| self.processes.load_process(&p.process_urn))
+                    .collect();
+
+                FunctionRealization { function: function.clone(), processes }
+            })
+            .collect())
+    }
+
+    /// Agents that participated in the processes that produced an artifact
+    ///
+    /// Uses `ProcessTracker::get_provenance_chain`, so results are ordered
+    /// oldest process first.
+    ///
+    /// # Arguments
+    /// * `instance_urn` - Output instance URN
+    pub fn provenance_agents(&self, instance_urn: &str) -> Result<Vec<ArtifactProvenance>> {
+        self.processes.get_provenance_chain(instance_urn)?
+            .into_iter()
+            .map(|process| {
+                let agents = self.agents_in(&process.urn)?;
+                Ok(ArtifactProvenance { process, agents })
+            })
+            .collect()
+    }
+
+    /// Process URN as-is, or all process URNs sharing a transaction ID
+    fn resolve_process_urns(&self, process_id: &str) -> Result<Vec<String>> {
+        if process_id.starts_with("ckp://Process#") {
+            return Ok(vec![process_id.to_string()]);
+        }
+
+        Ok(self.processes.query_processes(QueryFilters {
+            limit: Some(usize::MAX),
+            ..Default::default()
+        })?
+            .into_iter()
+            .filter(|p| p.tx_id == process_id)
+            .map(|p| p.urn)
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_participation_joins() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path().to_path_buf();
+        let continuants = ContinuantTracker::new(root.clone());
+        let processes = ProcessTracker::new(root).unwrap();
+
+        let mut participants = HashMap::new();
+        participants.insert("outputInstance".to_string(), serde_json::json!("ckp://Instance#out-1"));
+        let process = processes.create_process("emit", "tx_1", participants, HashMap::new()).unwrap();
+
+        let agent = continuants.create_agent("User", "alice", Vec::new(), HashMap::new()).unwrap();
+        continuants.create_agent("User", "bob", Vec::new(), HashMap::new()).unwrap();
+        continuants.record_participation(&agent.urn, &process.urn, "initiator", HashMap::new()).unwrap();
+
+        let kernel = continuants.create_kernel_entity("System.Gateway", "v1.0", "rust:hot", HashMap::new()).unwrap();
+        continuants.assign_function(&kernel.urn, Function {
+            name: "gateway".to_string(),
+            description: "HTTP gateway".to_string(),
+            assigned_at: chrono::Utc::now().to_rfc3339(),
+            metadata: HashMap::new(),
+        }).unwrap();
+        continuants.record_participation(&kernel.urn, &process.urn, "gateway", HashMap::new()).unwrap();
+
+        let query = ParticipationQuery::new(&continuants, &processes);
+
+        let resolved = query.participations_of(&agent.urn).unwrap();
+        assert_eq!(resolved.len(), 1);
+        assert_eq!(resolved[0].process.as_ref().unwrap().process_type, "emit");
+
+        let by_tx: Vec<String> = query.agents_in("tx_1").unwrap().into_iter().map(|a| a.identifier).collect();
+        assert_eq!(by_tx, vec!["alice"]);
+        assert_eq!(query.kernels_in(&process.urn).unwrap().len(), 1);
+
+        let realized = query.functions_realized_by("System.Gateway").unwrap();
+        assert_eq!(realized.len(), 1);
+        assert_eq!(realized[0].processes.len(), 1);
+
+        let provenance = query.provenance_agents("ckp://Instance#out-1").unwrap();
+        assert_eq!(provenance.len(), 1);
+        assert_eq!(provenance[0].agents[0].identifier, "alice");
+    }
+}
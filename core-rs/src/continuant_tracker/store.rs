@@ -0,0 +1,316 @@
+//! Continuant persistence backends
+//!
+//! ContinuantTracker keeps its entities in memory and delegates durability to a
+//! `ContinuantStore`:
+//! - `JsonFileStore`: one pretty JSON file per entity under `.continuants/`
+//!   (the original layout, still the default)
+//! - `JsonlLogStore`: snapshot + append-only log under the
+//!   `System.Continuants` kernel, with compaction
+
+use super::{Agent, KernelEntity};
+use crate::errors::{CkpError, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// Kernel that owns the JSONL continuant log
+pub const CONTINUANTS_KERNEL: &str = "System.Continuants";
+
+/// Default number of log entries before the tracker compacts
+pub const DEFAULT_COMPACTION_THRESHOLD: usize = 1000;
+
+/// A single continuant mutation (upsert of the full entity)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", content = "entity", rename_all = "lowercase")]
+pub enum ContinuantRecord {
+    Kernel(KernelEntity),
+    Agent(Agent),
+}
+
+/// Full in-memory view of all continuants
+#[derive(Debug, Clone, Default)]
+pub struct ContinuantSnapshot {
+    /// Kernel entities by kernel name
+    pub kernels: HashMap<String, KernelEntity>,
+
+    /// Agents by identifier
+    pub agents: HashMap<String, Agent>,
+
+    /// Agent identifier by API token hash, kept in step with `agents`
+    token_index: HashMap<String, String>,
+}
+
+impl ContinuantSnapshot {
+    /// Apply a record (last write wins)
+    pub fn apply(&mut self, record: ContinuantRecord) {
+        match record {
+            ContinuantRecord::Kernel(entity) => {
+                self.kernels.insert(entity.kernel_name.clone(), entity);
+            }
+            ContinuantRecord::Agent(agent) => {
+                if let Some(previous) = self.agents.get(&agent.identifier) {
+                    for hash in &previous.credentials.api_token_hashes {
+                        self.token_index.remove(hash);
+                    }
+                }
+                for hash in &agent.credentials.api_token_hashes {
+                    self.token_index.insert(hash.clone(), agent.identifier.clone());
+                }
+                self.agents.insert(agent.identifier.clone(), agent);
+            }
+        }
+    }
+
+    /// Agent an API token hash was issued to
+    pub fn agent_by_token_hash(&self, hash: &str) -> Option<&Agent> {
+        self.token_index.get(hash).and_then(|identifier| self.agents.get(identifier))
+    }
+
+    /// All entities as records, sorted for deterministic output
+    pub fn records(&self) -> Vec<ContinuantRecord> {
+        let mut kernels: Vec<&KernelEntity> = self.kernels.values().collect();
+        kernels.sort_by(|a, b| a.kernel_name.cmp(&b.kernel_name));
+        let mut agents: Vec<&Agent> = self.agents.values().collect();
+        agents.sort_by(|a, b| a.identifier.cmp(&b.identifier));
+
+        kernels.into_iter().cloned().map(ContinuantRecord::Kernel)
+            .chain(agents.into_iter().cloned().map(ContinuantRecord::Agent))
+            .collect()
+    }
+}
+
+/// Persistence backend for ContinuantTracker
+pub trait ContinuantStore: Send + Sync {
+    /// Load all persisted continuants (called once, on tracker start)
+    fn load(&self) -> Result<ContinuantSnapshot>;
+
+    /// Persist a single mutation
+    fn append(&self, record: &ContinuantRecord) -> Result<()>;
+
+    /// Rewrite storage from a full snapshot
+    fn compact(&self, snapshot: &ContinuantSnapshot) -> Result<()>;
+
+    /// Whether the backend would benefit from compaction now
+    fn needs_compaction(&self) -> bool {
+        false
+    }
+}
+
+// ============================================================================
+// JSON FILE STORE (legacy layout)
+// ============================================================================
+
+/// One JSON file per entity under `.continuants/{kernels,agents}/`
+pub struct JsonFileStore {
+    root: PathBuf,
+}
+
+impl JsonFileStore {
+    /// Create store rooted at `{concepts_root}/.continuants`
+    pub fn new(concepts_root: &Path) -> Self {
+        Self { root: concepts_root.join(".continuants") }
+    }
+
+    fn kernel_path(&self, kernel_name: &str) -> PathBuf {
+        self.root.join("kernels").join(format!("{}.json", kernel_name.replace("/", "_")))
+    }
+
+    fn agent_path(&self, identifier: &str) -> PathBuf {
+        self.root.join("agents").join(format!("{}.json", identifier.replace("/", "_").replace(":", "_")))
+    }
+
+    fn read_dir_json<T: for<'de> Deserialize<'de>>(dir: &Path) -> Result<Vec<T>> {
+        if !dir.exists() {
+            return Ok(Vec::new());
+        }
+
+        let mut results = Vec::new();
+        for entry in fs::read_dir(dir)? {
+            let path = entry?.path();
+            if path.extension().and_then(|s| s.to_str()) == Some("json") {
+                let content = fs::read_to_string(&path)?;
+                results.push(serde_json::from_str(&content)?);
+            }
+        }
+
+        Ok(results)
+    }
+}
+
+impl ContinuantStore for JsonFileStore {
+    fn load(&self) -> Result<ContinuantSnapshot> {
+        let mut snapshot = ContinuantSnapshot::default();
+
+        for entity in Self::read_dir_json::<KernelEntity>(&self.root.join("kernels"))? {
+            snapshot.apply(ContinuantRecord::Kernel(entity));
+        }
+        for agent in Self::read_dir_json::<Agent>(&self.root.join("agents"))? {
+            snapshot.apply(ContinuantRecord::Agent(agent));
+        }
+
+        Ok(snapshot)
+    }
+
+    fn append(&self, record: &ContinuantRecord) -> Result<()> {
+        let (path, json) = match record {
+            ContinuantRecord::Kernel(entity) => {
+                (self.kernel_path(&entity.kernel_name), serde_json::to_string_pretty(entity)?)
+            }
+            ContinuantRecord::Agent(agent) => {
+                (self.agent_path(&agent.identifier), serde_json::to_string_pretty(agent)?)
+            }
+        };
+
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, json)?;
+
+        Ok(())
+    }
+
+    fn compact(&self, snapshot: &ContinuantSnapshot) -> Result<()> {
+        // Files are already one-per-entity; rewriting keeps them in sync
+        for record in snapshot.records() {
+            self.append(&record)?;
+        }
+
+        Ok(())
+    }
+}
+
+// ============================================================================
+// JSONL LOG STORE
+// ============================================================================
+
+/// Snapshot + append log under `concepts/System.Continuants/storage/`
+///
+/// - `continuants.snapshot.jsonl`: one record per entity, written on compaction
+/// - `continuants.log.jsonl`: one record per mutation since the last snapshot
+///
+/// Loading replays the log over the snapshot; compaction writes a fresh
+/// snapshot (tmp + rename) and truncates the log.
+pub struct JsonlLogStore {
+    dir: PathBuf,
+    compaction_threshold: usize,
+    log_entries: AtomicUsize,
+}
+
+impl JsonlLogStore {
+    /// Create store under `{concepts_root}/System.Continuants/storage`
+    pub fn new(concepts_root: &Path) -> Self {
+        Self {
+            dir: concepts_root.join(CONTINUANTS_KERNEL).join("storage"),
+            compaction_threshold: DEFAULT_COMPACTION_THRESHOLD,
+            log_entries: AtomicUsize::new(0),
+        }
+    }
+
+    /// Set the number of log entries that triggers compaction
+    pub fn with_compaction_threshold(mut self, threshold: usize) -> Self {
+        self.compaction_threshold = threshold;
+        self
+    }
+
+    /// Snapshot file path
+    pub fn snapshot_path(&self) -> PathBuf {
+        self.dir.join("continuants.snapshot.jsonl")
+    }
+
+    /// Append log file path
+    pub fn log_path(&self) -> PathBuf {
+        self.dir.join("continuants.log.jsonl")
+    }
+
+    fn read_records(path: &Path) -> Result<Vec<ContinuantRecord>> {
+        if !path.exists() {
+            return Ok(Vec::new());
+        }
+
+        let content = fs::read_to_string(path)?;
+        let lines: Vec<&str> = content.lines().collect();
+        let mut records = Vec::new();
+
+        for (index, line) in lines.iter().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            match serde_json::from_str(line) {
+                Ok(record) => records.push(record),
+                // A torn final line is expected after a crash mid-append
+                Err(e) if index + 1 == lines.len() => {
+                    tracing::warn!(log = %path.display(), error = %e, "skipping truncated log entry");
+                }
+                Err(e) => {
+                    return Err(CkpError::ParseError(format!(
+                        "Invalid continuant record at {}:{}: {}",
+                        path.display(),
+                        index + 1,
+                        e
+                    )));
+                }
+            }
+        }
+
+        Ok(records)
+    }
+}
+
+impl ContinuantStore for JsonlLogStore {
+    fn load(&self) -> Result<ContinuantSnapshot> {
+        let mut snapshot = ContinuantSnapshot::default();
+
+        for record in Self::read_records(&self.snapshot_path())? {
+            snapshot.apply(record);
+        }
+
+        let log = Self::read_records(&self.log_path())?;
+        self.log_entries.store(log.len(), Ordering::SeqCst);
+        for record in log {
+            snapshot.apply(record);
+        }
+
+        Ok(snapshot)
+    }
+
+    fn append(&self, record: &ContinuantRecord) -> Result<()> {
+        fs::create_dir_all(&self.dir)?;
+
+        let line = serde_json::to_string(record)?;
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(self.log_path())?;
+        writeln!(file, "{}", line)?;
+
+        self.log_entries.fetch_add(1, Ordering::SeqCst);
+        Ok(())
+    }
+
+    fn compact(&self, snapshot: &ContinuantSnapshot) -> Result<()> {
+        fs::create_dir_all(&self.dir)?;
+
+        let mut content = String::new();
+        for record in snapshot.records() {
+            content.push_str(&serde_json::to_string(&record)?);
+            content.push('\n');
+        }
+
+        let tmp_path = self.dir.join("continuants.snapshot.jsonl.tmp");
+        fs::write(&tmp_path, content)?;
+        fs::rename(&tmp_path, self.snapshot_path())?;
+
+        // Snapshot now covers everything in the log
+        fs::write(self.log_path(), "")?;
+        self.log_entries.store(0, Ordering::SeqCst);
+
+        Ok(())
+    }
+
+    fn needs_compaction(&self) -> bool {
+        self.log_entries.load(Ordering::SeqCst) >= self.compaction_threshold
+    }
+}
@@ -0,0 +1,388 @@
+// Control socket - Runtime administration of running daemons
+//
+// A daemon started with a control socket accepts one JSON command per
+// connection on a Unix-domain socket and answers with one JSON line:
+//
+//   {"command":"pause","kernel":"Shop.Orders"}      -> {"ok":true,"result":...}
+//   {"command":"resume","kernel":"Shop.Orders"}
+//   {"command":"drain","kernel":"Shop.Orders","queue":"inbox"}
+//   {"command":"dump"}
+//   {"command":"flush"}
+//   {"command":"log-level","level":"debug"}
+//
+// The server thread only parses and forwards commands: each one is handed to
+// the daemon's own loop as a ControlRequest, so daemons apply commands
+// between units of work without sharing their state across threads.
+//
+// Draining moves every entry of a kernel queue to
+// queue/drained/<timestamp>/<queue>/, out of reach of governors and routers
+// but still on disk for inspection or replay.
+
+use crate::errors::{CkpError, Result};
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::sync::mpsc;
+use std::time::Duration;
+
+/// Time a daemon has to answer a command before the client gets an error
+pub const CONTROL_REPLY_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Daemon log verbosity
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LogLevel {
+    /// Errors only
+    Error,
+
+    /// Lifecycle and routing messages
+    #[default]
+    Info,
+
+    /// Everything, as with --verbose
+    Debug,
+}
+
+impl LogLevel {
+    /// Level for a daemon's `verbose` flag
+    pub fn from_verbose(verbose: bool) -> Self {
+        if verbose { LogLevel::Debug } else { LogLevel::Info }
+    }
+}
+
+impl std::str::FromStr for LogLevel {
+    type Err = CkpError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "error" => Ok(LogLevel::Error),
+            "info" => Ok(LogLevel::Info),
+            "debug" => Ok(LogLevel::Debug),
+            other => Err(CkpError::ValidationError(format!(
+                "Invalid log level '{}' (expected error, info or debug)",
+                other
+            ))),
+        }
+    }
+}
+
+/// LogLevel that can be changed while a daemon runs
+#[derive(Debug, Default)]
+pub struct SharedLogLevel(AtomicU8);
+
+impl SharedLogLevel {
+    pub fn new(level: LogLevel) -> Self {
+        Self(AtomicU8::new(level as u8))
+    }
+
+    pub fn get(&self) -> LogLevel {
+        match self.0.load(Ordering::Relaxed) {
+            0 => LogLevel::Error,
+            1 => LogLevel::Info,
+            _ => LogLevel::Debug,
+        }
+    }
+
+    pub fn set(&self, level: LogLevel) {
+        self.0.store(level as u8, Ordering::Relaxed);
+    }
+
+    /// Whether messages at `level` are logged
+    pub fn enabled(&self, level: LogLevel) -> bool {
+        level <= self.get()
+    }
+}
+
+/// Command accepted on a control socket
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "command", rename_all = "kebab-case")]
+pub enum ControlCommand {
+    /// Stop routing instances from or to a kernel
+    Pause { kernel: String },
+
+    /// Resume routing for a kernel and release held instances
+    Resume { kernel: String },
+
+    /// Move a kernel queue's entries aside (default queue: inbox)
+    Drain {
+        kernel: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        queue: Option<String>,
+    },
+
+    /// Report the daemon's internal state
+    Dump,
+
+    /// Drop cached routes so they are resolved afresh
+    Flush,
+
+    /// Change log verbosity
+    LogLevel { level: LogLevel },
+}
+
+/// Answer to a ControlCommand
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ControlResponse {
+    pub ok: bool,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub result: Option<Value>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl From<Result<Value>> for ControlResponse {
+    fn from(result: Result<Value>) -> Self {
+        match result {
+            Ok(value) => Self { ok: true, result: Some(value), error: None },
+            Err(e) => Self { ok: false, result: None, error: Some(e.to_string()) },
+        }
+    }
+}
+
+/// A command waiting for the daemon to apply it
+pub struct ControlRequest {
+    pub command: ControlCommand,
+    reply: mpsc::Sender<ControlResponse>,
+}
+
+impl ControlRequest {
+    /// Send the daemon's answer back to the client
+    pub fn respond(self, result: Result<Value>) {
+        // The client may have given up waiting
+        let _ = self.reply.send(result.into());
+    }
+}
+
+/// Move every entry of `concepts/<kernel>/queue/<queue>` aside
+///
+/// # Arguments
+/// * `queue` - `inbox`, `staging`, `ready` or `edges/<PREDICATE>.<Source>`
+///
+/// # Returns
+/// Number of entries moved
+pub fn drain_queue(root: &Path, kernel: &str, queue: &str) -> Result<usize> {
+    let relative = Path::new(queue);
+    let valid = matches!(queue, "inbox" | "staging" | "ready")
+        || (queue.starts_with("edges/")
+            && relative.components().count() == 2
+            && relative.components().all(|c| matches!(c, Component::Normal(_))));
+    if !valid || kernel.is_empty() || kernel.contains(['/', '\\']) || kernel.starts_with('.') {
+        return Err(CkpError::ValidationError(format!("Cannot drain queue '{}' of '{}'", queue, kernel)));
+    }
+
+    let queue_root = root.join("concepts").join(kernel).join("queue");
+    let source = queue_root.join(relative);
+    if !source.is_dir() {
+        return Err(CkpError::FileNotFound(format!("Queue not found: {}", source.display())));
+    }
+
+    let destination = queue_root
+        .join("drained")
+        .join(Utc::now().format("%Y%m%dT%H%M%S%.3fZ").to_string())
+        .join(relative);
+
+    let mut moved = 0;
+    for entry in fs::read_dir(&source)? {
+        let entry = entry?;
+        if entry.file_name() == ".gitkeep" {
+            continue;
+        }
+
+        if moved == 0 {
+            fs::create_dir_all(&destination)?;
+        }
+        fs::rename(entry.path(), destination.join(entry.file_name()))?;
+        moved += 1;
+    }
+
+    Ok(moved)
+}
+
+#[cfg(unix)]
+pub use unix::{send_command, ControlServer};
+
+#[cfg(unix)]
+mod unix {
+    use super::*;
+    use std::io::{BufRead, BufReader, Write};
+    use std::os::unix::net::{UnixListener, UnixStream};
+    use std::sync::atomic::AtomicBool;
+    use std::sync::Arc;
+    use std::thread::JoinHandle;
+
+    /// Unix-domain socket forwarding commands to a daemon
+    pub struct ControlServer {
+        path: PathBuf,
+        shutdown: Arc<AtomicBool>,
+        handle: Option<JoinHandle<()>>,
+    }
+
+    impl ControlServer {
+        /// Listen on `path`, replacing a stale socket left by a previous run
+        ///
+        /// # Returns
+        /// The server, and the receiver the daemon polls for requests
+        pub fn start(path: PathBuf) -> Result<(Self, mpsc::Receiver<ControlRequest>)> {
+            if path.exists() {
+                if UnixStream::connect(&path).is_ok() {
+                    return Err(CkpError::ValidationError(format!(
+                        "Control socket {} is in use by another daemon",
+                        path.display()
+                    )));
+                }
+                fs::remove_file(&path)?;
+            }
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+
+            let listener = UnixListener::bind(&path)?;
+            listener.set_nonblocking(true)?;
+
+            let (tx, rx) = mpsc::channel();
+            let shutdown = Arc::new(AtomicBool::new(false));
+            let stop = shutdown.clone();
+
+            let handle = std::thread::spawn(move || {
+                while !stop.load(Ordering::SeqCst) {
+                    match listener.accept() {
+                        Ok((stream, _)) => {
+                            if let Err(e) = serve_client(stream, &tx) {
+                                tracing::warn!(error = %e, "control request failed");
+                            }
+                        }
+                        Err(e) if e.kind() == std::io::ErrorKind::WouldBlock => {
+                            std::thread::sleep(Duration::from_millis(50));
+                        }
+                        Err(e) => tracing::warn!(error = %e, "control accept failed"),
+                    }
+                }
+            });
+
+            Ok((Self { path, shutdown, handle: Some(handle) }, rx))
+        }
+
+        /// Socket path
+        pub fn path(&self) -> &Path {
+            &self.path
+        }
+    }
+
+    impl Drop for ControlServer {
+        fn drop(&mut self) {
+            self.shutdown.store(true, Ordering::SeqCst);
+            if let Some(handle) = self.handle.take() {
+                let _ = handle.join();
+            }
+            let _ = fs::remove_file(&self.path);
+        }
+    }
+
+    fn serve_client(stream: UnixStream, requests: &mpsc::Sender<ControlRequest>) -> Result<()> {
+        stream.set_nonblocking(false)?;
+        stream.set_read_timeout(Some(CONTROL_REPLY_TIMEOUT))?;
+
+        let mut line = String::new();
+        BufReader::new(&stream).read_line(&mut line)?;
+
+        let response = match serde_json::from_str::<ControlCommand>(&line) {
+            Ok(command) => {
+                let (reply, answer) = mpsc::channel();
+                if requests.send(ControlRequest { command, reply }).is_err() {
+                    ControlResponse::from(Err(CkpError::Process("Daemon is shutting down".to_string())))
+                } else {
+                    answer.recv_timeout(CONTROL_REPLY_TIMEOUT).unwrap_or_else(|_| {
+                        ControlResponse::from(Err(CkpError::Process("Daemon did not answer".to_string())))
+                    })
+                }
+            }
+            Err(e) => ControlResponse::from(Err(CkpError::InvalidJson(e.to_string()))),
+        };
+
+        let mut stream = stream;
+        writeln!(stream, "{}", serde_json::to_string(&response)?)?;
+        Ok(())
+    }
+
+    /// Send a command to a daemon's control socket and wait for the answer
+    pub fn send_command(path: &Path, command: &ControlCommand) -> Result<ControlResponse> {
+        let mut stream = UnixStream::connect(path).map_err(|e| {
+            CkpError::IoError(format!("Cannot connect to control socket {}: {}", path.display(), e))
+        })?;
+        stream.set_read_timeout(Some(CONTROL_REPLY_TIMEOUT + Duration::from_secs(1)))?;
+
+        writeln!(stream, "{}", serde_json::to_string(command)?)?;
+
+        let mut line = String::new();
+        BufReader::new(&stream).read_line(&mut line)?;
+        Ok(serde_json::from_str(&line)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_drain_queue() {
+        let temp = TempDir::new().unwrap();
+        let inbox = temp.path().join("concepts/Shop.Orders/queue/inbox");
+        fs::create_dir_all(&inbox).unwrap();
+        fs::write(inbox.join(".gitkeep"), "").unwrap();
+        fs::write(inbox.join("a.job"), "{}").unwrap();
+        fs::write(inbox.join("b.job"), "{}").unwrap();
+
+        assert_eq!(drain_queue(temp.path(), "Shop.Orders", "inbox").unwrap(), 2);
+        assert_eq!(fs::read_dir(&inbox).unwrap().count(), 1);
+
+        let drained = temp.path().join("concepts/Shop.Orders/queue/drained");
+        let batch = fs::read_dir(&drained).unwrap().next().unwrap().unwrap().path();
+        assert!(batch.join("inbox/a.job").exists());
+
+        assert_eq!(drain_queue(temp.path(), "Shop.Orders", "inbox").unwrap(), 0);
+        assert!(drain_queue(temp.path(), "Shop.Orders", "edges/../../x").is_err());
+        assert!(drain_queue(temp.path(), "../Shop", "inbox").is_err());
+        assert!(matches!(drain_queue(temp.path(), "Shop.Orders", "ready"), Err(CkpError::FileNotFound(_))));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_control_socket_round_trip() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join("run/router.sock");
+        let (server, requests) = ControlServer::start(path.clone()).unwrap();
+        assert!(ControlServer::start(path.clone()).is_err());
+
+        let daemon = std::thread::spawn(move || {
+            for _ in 0..2 {
+                let request = requests.recv().unwrap();
+                let result = match &request.command {
+                    ControlCommand::Pause { kernel } => Ok(serde_json::json!({ "paused": kernel })),
+                    _ => Err(CkpError::ValidationError("unsupported".to_string())),
+                };
+                request.respond(result);
+            }
+        });
+
+        let response = send_command(&path, &ControlCommand::Pause { kernel: "Shop.Orders".to_string() }).unwrap();
+        assert!(response.ok);
+        assert_eq!(response.result.unwrap()["paused"], "Shop.Orders");
+
+        let response = send_command(&path, &ControlCommand::Dump).unwrap();
+        assert!(!response.ok);
+        assert!(response.error.unwrap().contains("unsupported"));
+        daemon.join().unwrap();
+
+        let level: ControlCommand = serde_json::from_str(r#"{"command":"log-level","level":"debug"}"#).unwrap();
+        assert_eq!(level, ControlCommand::LogLevel { level: LogLevel::Debug });
+
+        drop(server);
+        assert!(!path.exists());
+    }
+}
@@ -0,0 +1,229 @@
+// Edge Router Deduplication - Recently routed tx_ids
+//
+// A kernel that crashes after writing an instance may write it again when it
+// retries the emit, and the router would deliver the same tx twice. The
+// router keeps the last N routed (source kernel, tx_id) pairs in a window
+// and applies a DuplicatePolicy to instances already in it.
+//
+// The window is persisted to a ring file under .ckrouter/ (one key per line,
+// appended as instances are routed) so it survives router restarts and is
+// picked up by the router that takes over a partition. The file is rewritten
+// with only the current window once it holds twice the window size.
+//
+// Duplicates delivered under DuplicatePolicy::Flag get a marker file next to
+// each queue entry ({tx_id}.inst.duplicate); queue scans only pick up .inst
+// and .job entries, so the marker is invisible to them.
+
+use super::partition::{Partition, LEASE_DIR};
+use crate::errors::{CkpError, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashSet, VecDeque};
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+/// Default number of routed tx_ids remembered
+pub const DEFAULT_DEDUP_CAPACITY: usize = 10_000;
+
+/// Extension of the marker written next to a flagged duplicate queue entry
+pub const DUPLICATE_MARKER_EXT: &str = "duplicate";
+
+/// What the router does with an instance whose tx was already routed
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DuplicatePolicy {
+    /// Do not deliver it again
+    #[default]
+    Drop,
+    /// Deliver it, with a duplicate marker next to each queue entry
+    Flag,
+}
+
+impl std::str::FromStr for DuplicatePolicy {
+    type Err = CkpError;
+
+    /// Parse "drop" or "flag"
+    fn from_str(s: &str) -> Result<Self> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "drop" => Ok(Self::Drop),
+            "flag" => Ok(Self::Flag),
+            _ => Err(CkpError::ParseError(format!("Invalid duplicate policy '{}' (expected drop or flag)", s))),
+        }
+    }
+}
+
+/// Window of recently routed (source kernel, tx_id) pairs, backed by a ring file
+#[derive(Debug)]
+pub struct DedupWindow {
+    path: PathBuf,
+    capacity: usize,
+    order: VecDeque<String>,
+    keys: HashSet<String>,
+    // Lines in the ring file, compacted at twice the capacity
+    file_lines: usize,
+}
+
+impl DedupWindow {
+    /// Window persisted at `path`, loading the tx_ids already recorded there
+    ///
+    /// An unreadable ring file starts an empty window.
+    pub fn new(path: PathBuf, capacity: usize) -> Self {
+        let mut window = Self {
+            path,
+            capacity: capacity.max(1),
+            order: VecDeque::new(),
+            keys: HashSet::new(),
+            file_lines: 0,
+        };
+        window.reload();
+        window
+    }
+
+    /// Ring file of a router: one per partition, shared by its lease holders
+    pub fn ring_path(root: &Path, partition: Option<Partition>) -> PathBuf {
+        let name = match partition {
+            Some(partition) => format!("dedup-partition-{}-of-{}.ring", partition.index, partition.count),
+            None => "dedup.ring".to_string(),
+        };
+        root.join(LEASE_DIR).join(name)
+    }
+
+    /// Re-read the ring file, e.g. after taking over a partition from another router
+    pub fn reload(&mut self) {
+        self.order.clear();
+        self.keys.clear();
+        self.file_lines = 0;
+
+        let content = match fs::read_to_string(&self.path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return,
+            Err(e) => {
+                tracing::warn!(path = %self.path.display(), error = %e, "cannot read dedup window");
+                return;
+            }
+        };
+
+        for key in content.lines().filter(|line| !line.is_empty()) {
+            self.file_lines += 1;
+            self.remember(key.to_string());
+        }
+    }
+
+    /// Whether a tx from `source` is in the window
+    pub fn contains(&self, source: &str, tx_id: &str) -> bool {
+        self.keys.contains(&Self::key(source, tx_id))
+    }
+
+    /// Add a routed tx to the window and the ring file
+    pub fn record(&mut self, source: &str, tx_id: &str) -> Result<()> {
+        let key = Self::key(source, tx_id);
+        if self.keys.contains(&key) {
+            return Ok(());
+        }
+        self.remember(key.clone());
+
+        if self.file_lines + 1 >= self.capacity * 2 {
+            return self.compact();
+        }
+
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let mut file = OpenOptions::new().create(true).append(true).open(&self.path)?;
+        writeln!(file, "{}", key)?;
+        self.file_lines += 1;
+        Ok(())
+    }
+
+    /// Number of tx_ids in the window
+    pub fn len(&self) -> usize {
+        self.order.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.order.is_empty()
+    }
+
+    /// Whether a queue entry was delivered as a flagged duplicate
+    pub fn is_flagged(entry: &Path) -> bool {
+        Self::marker_path(entry).exists()
+    }
+
+    /// Marker flagging a queue entry as a duplicate delivery
+    pub fn marker_path(entry: &Path) -> PathBuf {
+        let mut name = entry.file_name().unwrap_or_default().to_os_string();
+        name.push(".");
+        name.push(DUPLICATE_MARKER_EXT);
+        entry.with_file_name(name)
+    }
+
+    fn key(source: &str, tx_id: &str) -> String {
+        format!("{}/{}", source, tx_id)
+    }
+
+    fn remember(&mut self, key: String) {
+        if !self.keys.insert(key.clone()) {
+            return;
+        }
+        self.order.push_back(key);
+        while self.order.len() > self.capacity {
+            if let Some(oldest) = self.order.pop_front() {
+                self.keys.remove(&oldest);
+            }
+        }
+    }
+
+    // Rewrite the ring file with only the current window
+    fn compact(&mut self) -> Result<()> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let mut content = String::new();
+        for key in &self.order {
+            content.push_str(key);
+            content.push('\n');
+        }
+
+        let tmp = self.path.with_extension("ring.tmp");
+        fs::write(&tmp, content)?;
+        fs::rename(&tmp, &self.path)?;
+        self.file_lines = self.order.len();
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_window_persists_and_evicts_oldest() {
+        let temp = TempDir::new().unwrap();
+        let path = DedupWindow::ring_path(temp.path(), None);
+
+        let mut window = DedupWindow::new(path.clone(), 3);
+        for tx in ["tx-1", "tx-2", "tx-3", "tx-2", "tx-4"] {
+            window.record("Source", tx).unwrap();
+        }
+        assert_eq!(window.len(), 3);
+        assert!(!window.contains("Source", "tx-1"));
+        assert!(window.contains("Source", "tx-4"));
+        assert!(!window.contains("Other", "tx-4"));
+
+        // A restarted router sees the same window; the file stays bounded
+        for tx in ["tx-5", "tx-6"] {
+            window.record("Source", tx).unwrap();
+        }
+        let reloaded = DedupWindow::new(path.clone(), 3);
+        assert!(reloaded.contains("Source", "tx-6"));
+        assert!(!reloaded.contains("Source", "tx-3"));
+        assert!(fs::read_to_string(&path).unwrap().lines().count() < 6);
+
+        let entry = temp.path().join("tx-6.inst");
+        assert_eq!(DedupWindow::marker_path(&entry), temp.path().join("tx-6.inst.duplicate"));
+        assert!(!DedupWindow::is_flagged(&entry));
+        assert_eq!("Flag".parse::<DuplicatePolicy>().unwrap(), DuplicatePolicy::Flag);
+        assert!("deliver".parse::<DuplicatePolicy>().is_err());
+    }
+}
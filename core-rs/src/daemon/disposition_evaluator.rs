@@ -0,0 +1,248 @@
+// DispositionEvaluatorDaemon - Realizes BFO dispositions
+//
+// Responsibilities:
+// - Periodically read KernelEntity dispositions from ContinuantTracker
+// - Observe the trigger metric on each bearer kernel (queue depths, storage)
+// - When a trigger condition becomes true, emit a job to the trigger's
+//   target kernel and mark the disposition realized
+// - When the condition clears, reset the realization so it can fire again
+//
+// Realization is edge-triggered: a disposition that stays realized emits
+// exactly one job until its condition clears.
+
+use super::sleep_until_shutdown;
+use crate::continuant_tracker::{ContinuantTracker, Disposition, TriggerMetric};
+use crate::drivers::{FileSystemDriver, JobFileBuilder, StorageDriver};
+use crate::errors::Result;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Default polling interval
+pub const DEFAULT_EVALUATION_INTERVAL: Duration = Duration::from_secs(5);
+
+/// A disposition that was realized during an evaluation pass
+#[derive(Debug, Clone)]
+pub struct RealizedDisposition {
+    /// Bearer kernel name
+    pub kernel: String,
+
+    /// Disposition name
+    pub disposition: String,
+
+    /// Observed metric value
+    pub value: u64,
+
+    /// Kernel the job was emitted to
+    pub target_kernel: String,
+
+    /// Transaction ID of the emitted job
+    pub tx_id: String,
+}
+
+pub struct DispositionEvaluatorDaemon {
+    root: PathBuf,
+    tracker: Arc<ContinuantTracker>,
+    interval: Duration,
+    verbose: bool,
+}
+
+impl DispositionEvaluatorDaemon {
+    /// Create evaluator for a project root
+    ///
+    /// Uses the same ContinuantTracker location as KernelManager.
+    pub fn new(root: PathBuf, verbose: bool) -> Self {
+        let tracker = Arc::new(ContinuantTracker::new(root.clone()));
+        Self::with_tracker(root, tracker, verbose)
+    }
+
+    /// Create evaluator with an existing tracker (e.g. a custom store)
+    pub fn with_tracker(root: PathBuf, tracker: Arc<ContinuantTracker>, verbose: bool) -> Self {
+        Self {
+            root,
+            tracker,
+            interval: DEFAULT_EVALUATION_INTERVAL,
+            verbose,
+        }
+    }
+
+    /// Set polling interval
+    pub fn with_interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    pub fn start(&self, shutdown: Arc<AtomicBool>) -> Result<()> {
+        if self.verbose {
+            tracing::info!(project = %self.root.display(), "disposition evaluator starting");
+        }
+
+        while !shutdown.load(Ordering::SeqCst) {
+            if let Err(e) = self.evaluate_once() {
+                tracing::error!(error = %e, "disposition evaluation failed");
+            }
+
+            sleep_until_shutdown(self.interval, &shutdown);
+        }
+
+        if self.verbose {
+            tracing::info!("disposition evaluator shutting down");
+        }
+        Ok(())
+    }
+
+    /// Run a single evaluation pass over all kernel dispositions
+    ///
+    /// # Returns
+    /// Dispositions that became realized during this pass
+    pub fn evaluate_once(&self) -> Result<Vec<RealizedDisposition>> {
+        let mut realized = Vec::new();
+
+        for entity in self.tracker.list_kernel_entities()? {
+            for disposition in &entity.dispositions {
+                if let Some(event) = self.evaluate(&entity.kernel_name, disposition)? {
+                    realized.push(event);
+                }
+            }
+        }
+
+        Ok(realized)
+    }
+
+    fn evaluate(&self, kernel_name: &str, disposition: &Disposition) -> Result<Option<RealizedDisposition>> {
+        let trigger = match &disposition.trigger {
+            Some(trigger) => trigger,
+            None => return Ok(None),
+        };
+
+        let value = self.observe(kernel_name, trigger.metric)?;
+        let is_met = trigger.is_met(value);
+
+        if !is_met {
+            if disposition.realized_at.is_some() {
+                // Condition cleared - re-arm
+                self.tracker.set_disposition_realized(kernel_name, &disposition.name, None)?;
+                if self.verbose {
+                    tracing::info!(kernel = kernel_name, disposition = %disposition.name, value, "disposition cleared");
+                }
+            }
+            return Ok(None);
+        }
+
+        if disposition.realized_at.is_some() {
+            // Already realized - wait for the condition to clear
+            return Ok(None);
+        }
+
+        let driver = FileSystemDriver::new(self.root.clone(), kernel_name.to_string());
+        let tx_id = driver.generate_tx_id();
+        let timestamp = chrono::Utc::now().to_rfc3339();
+
+        let job = JobFileBuilder::new(
+            trigger.target_kernel.clone(),
+            serde_json::json!({
+                "disposition": disposition.name,
+                "bearer": kernel_name,
+                "metric": trigger.metric,
+                "value": value,
+                "threshold": trigger.threshold,
+                "payload": trigger.payload,
+            }),
+        )
+        .with_tx_id(tx_id.clone())
+        .with_timestamp(timestamp.clone())
+        .with_source(kernel_name, format!("ckp://{}", kernel_name))
+        .build()?;
+        driver.write_job(&trigger.target_kernel, job)?;
+
+        self.tracker.set_disposition_realized(kernel_name, &disposition.name, Some(timestamp))?;
+        if self.verbose {
+            tracing::info!(
+                kernel = kernel_name,
+                disposition = %disposition.name,
+                value,
+                target = %trigger.target_kernel,
+                tx_id = %tx_id,
+                "disposition realized"
+            );
+        }
+
+        Ok(Some(RealizedDisposition {
+            kernel: kernel_name.to_string(),
+            disposition: disposition.name.clone(),
+            value,
+            target_kernel: trigger.target_kernel.clone(),
+            tx_id,
+        }))
+    }
+
+    fn observe(&self, kernel_name: &str, metric: TriggerMetric) -> Result<u64> {
+        let driver = FileSystemDriver::new(self.root.clone(), kernel_name.to_string());
+
+        let count = match metric {
+            TriggerMetric::InboxDepth => driver.count_queue_files(&driver.get_queue_inbox())?,
+            TriggerMetric::StagingDepth => driver.count_queue_files(&driver.get_queue_staging())?,
+            TriggerMetric::ReadyDepth => driver.count_queue_files(&driver.get_queue_ready())?,
+            TriggerMetric::StorageCount => driver.count_queue_files(&driver.get_storage())?,
+        };
+
+        Ok(count as u64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::continuant_tracker::{DispositionTrigger, TriggerComparison};
+    use std::collections::HashMap;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_disposition_realized_once_then_rearmed() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path().to_path_buf();
+        let daemon = DispositionEvaluatorDaemon::new(root.clone(), false);
+
+        let entity = daemon.tracker.create_kernel_entity("System.Worker", "v1.0", "rust:cold", HashMap::new()).unwrap();
+        daemon.tracker.assign_disposition(&entity.urn, Disposition {
+            name: "ScaleOut".to_string(),
+            description: "Scale out under load".to_string(),
+            recognized_at: chrono::Utc::now().to_rfc3339(),
+            trigger: Some(DispositionTrigger {
+                metric: TriggerMetric::InboxDepth,
+                comparison: TriggerComparison::Gt,
+                threshold: 2,
+                target_kernel: "System.Scaler".to_string(),
+                payload: serde_json::json!({"replicas": 2}),
+            }),
+            realized_at: None,
+            metadata: HashMap::new(),
+        }).unwrap();
+
+        let inbox = root.join("concepts/System.Worker/queue/inbox");
+        fs::create_dir_all(&inbox).unwrap();
+        for i in 0..3 {
+            fs::write(inbox.join(format!("job{}.job", i)), "{}").unwrap();
+        }
+
+        let realized = daemon.evaluate_once().unwrap();
+        assert_eq!(realized.len(), 1);
+        assert_eq!(realized[0].value, 3);
+
+        let job_path = root.join("concepts/System.Scaler/queue/inbox").join(format!("{}.job", realized[0].tx_id));
+        let job: serde_json::Value = serde_json::from_str(&fs::read_to_string(job_path).unwrap()).unwrap();
+        assert_eq!(job["payload"]["disposition"], "ScaleOut");
+        assert_eq!(job["payload"]["payload"]["replicas"], 2);
+
+        // Still above threshold - no second job
+        assert!(daemon.evaluate_once().unwrap().is_empty());
+
+        // Condition clears, then fires again
+        fs::remove_file(inbox.join("job0.job")).unwrap();
+        assert!(daemon.evaluate_once().unwrap().is_empty());
+        fs::write(inbox.join("job3.job"), "{}").unwrap();
+        assert_eq!(daemon.evaluate_once().unwrap().len(), 1);
+    }
+}
@@ -9,28 +9,105 @@
 //
 // Architecture:
 // - Storage Watcher (notify crate) - Detects *.inst creation
+// - Event Batcher - Collects instances seen within the debounce window
+// - Reconciliation Sweep - Periodically scans storage/ for instances whose
+//   events were dropped (watcher overflow, atomic renames, network mounts)
 // - Notification Contract Resolver - Reads targets from ontology
 // - Edge Lifecycle Manager - Auto-creates edges on first instance
 // - Routing Engine - Wraps EdgeKernel::route_instance()
+//
+// Like the Node.js router, instances already in storage/ at startup are not
+// routed; only instances created while the daemon runs are. Each instance is
+// routed at most once, whether it arrives by event or by sweep.
+//
+// Edge request batches scheduled with `EdgeRequestChain::schedule` are
+// written to their edge inboxes once their deliver-after time passes.
+//
+// With a control socket, operators can pause routing for a kernel (instances
+// from or to it are held until it resumes), drain queues, dump state and
+// change the log level without restarting the router.
+//
+// Large projects can run several routers, each started with a partition
+// (see `daemon::partition`). A router only routes kernels hashed into its
+// partition, and only while it holds the partition's lease. On takeover,
+// instances older than the previous holder's last renewal count as routed.
+//
+// Routed tx_ids are remembered per source kernel in a dedup window persisted
+// under .ckrouter/ (see `daemon::dedup`), so an instance re-emitted after a
+// kernel crash is dropped, or delivered flagged as a duplicate, instead of
+// being delivered twice.
+//
+// Warm kernels have no governor until they receive their first job: after
+// delivering to a warm target, the router wakes it (`KernelManager::wake`).
+//
+// Routes and notification contracts are resolved once per source kernel and
+// cached (see `daemon::routing_table`) until ontology or edge metadata
+// changes, or an operator flushes them through the control socket.
 
-use crate::edge::EdgeKernel;
+use super::control::{self, ControlCommand, LogLevel, SharedLogLevel};
+use super::dedup::{DedupWindow, DuplicatePolicy, DEFAULT_DEDUP_CAPACITY};
+use super::metrics::DaemonMetrics;
+use super::partition::{LeaseState, Partition, PartitionLease, PartitionLeaseHolder, DEFAULT_LEASE_TTL};
+use super::routing_table::{Invalidation, RoutingTable};
+use crate::edge::{EdgeKernel, EdgeRequestBuilder, EdgeRoute};
+use crate::errors::CkpError;
+use crate::kernel::{CircuitBreaker, CircuitBreakerConfig, KernelManager};
 use crate::ontology::{OntologyReader, OntologyLibrary};
-use crate::process_tracker::ProcessTracker;
+use crate::process_tracker::{ProcessTracker, ProcessTracking};
+use notify::event::{ModifyKind, RenameMode};
 use notify::{Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher, Config as NotifyConfig};
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::sync::atomic::{AtomicBool, Ordering};
-use std::collections::HashMap;
+use std::sync::mpsc::RecvTimeoutError;
+use std::collections::{HashMap, HashSet};
+use std::time::{Duration, Instant};
+
+/// Default window for batching filesystem events
+pub const DEFAULT_DEBOUNCE: Duration = Duration::from_millis(50);
+
+/// Default interval between reconciliation sweeps
+pub const DEFAULT_RECONCILE_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Default maximum instances per batch
+pub const DEFAULT_MAX_BATCH: usize = 256;
+
+/// How often scheduled edge request batches are checked
+pub const SCHEDULED_POLL_INTERVAL: Duration = Duration::from_secs(1);
 
 pub struct EdgeRouterDaemon {
     root: PathBuf,
     edge_kernel: Arc<std::sync::Mutex<EdgeKernel>>,
+    edge_requests: EdgeRequestBuilder,
     ontology_reader: OntologyReader,
     _ontology_library: Option<Arc<OntologyLibrary>>,
     _process_tracker: Arc<ProcessTracker>,
-    verbose: bool,
+    log_level: SharedLogLevel,
     // Cache: kernel_name -> List<(target, predicate)>
     notification_cache: Arc<std::sync::Mutex<HashMap<String, Vec<(String, String)>>>>,
+    // Cache: kernel_name -> resolved edge routes, dropped by invalidation events
+    routing_table: std::sync::Mutex<RoutingTable>,
+    // Instances already routed (or present at startup)
+    seen: std::sync::Mutex<HashSet<PathBuf>>,
+    debounce: Duration,
+    reconcile_interval: Duration,
+    max_batch: usize,
+    // Metrics sink and this project's label in it
+    metrics: Option<(Arc<DaemonMetrics>, String)>,
+    // Kernels paused through the control socket, and instances held for them
+    paused: std::sync::Mutex<HashSet<String>>,
+    held: std::sync::Mutex<Vec<PathBuf>>,
+    control_socket: Option<PathBuf>,
+    // Share of kernels routed by this instance, and whether its lease is held
+    partition: Option<Partition>,
+    lease_ttl: Duration,
+    routing_active: AtomicBool,
+    // Fails fast on targets that keep failing, redirecting to their fallbacks
+    circuit_breaker: Option<CircuitBreaker>,
+    // Recently routed tx_ids, and what to do with instances already among them
+    dedup: std::sync::Mutex<DedupWindow>,
+    dedup_capacity: usize,
+    duplicate_policy: DuplicatePolicy,
 }
 
 impl EdgeRouterDaemon {
@@ -39,26 +116,112 @@ impl EdgeRouterDaemon {
         let ontology_library = OntologyLibrary::new(root.clone()).ok().map(Arc::new);
         let process_tracker = Arc::new(ProcessTracker::new(root.clone())?);
 
-        let edge_kernel = EdgeKernel::with_ontology(
-            root.clone(),
-            ontology_library.clone(),
-            Some(process_tracker.clone()),
-        )?;
+        // Routes are tracked with all phases unless CKP_PROCESS_TRACKING says otherwise
+        let mut edge_kernel = EdgeKernel::with_ontology(root.clone(), ontology_library.clone(), None)?;
+        edge_kernel.set_process_tracking(Some(ProcessTracking::new(process_tracker.clone()).with_env()));
 
         Ok(Self {
             root: root.clone(),
             edge_kernel: Arc::new(std::sync::Mutex::new(edge_kernel)),
+            edge_requests: EdgeRequestBuilder::new(root.clone()),
             ontology_reader: OntologyReader::new(root.clone()),
             _ontology_library: ontology_library,
             _process_tracker: process_tracker,
-            verbose,
+            log_level: SharedLogLevel::new(LogLevel::from_verbose(verbose)),
             notification_cache: Arc::new(std::sync::Mutex::new(HashMap::new())),
+            routing_table: std::sync::Mutex::new(RoutingTable::new()),
+            seen: std::sync::Mutex::new(HashSet::new()),
+            debounce: DEFAULT_DEBOUNCE,
+            reconcile_interval: DEFAULT_RECONCILE_INTERVAL,
+            max_batch: DEFAULT_MAX_BATCH,
+            metrics: None,
+            paused: std::sync::Mutex::new(HashSet::new()),
+            held: std::sync::Mutex::new(Vec::new()),
+            control_socket: None,
+            partition: None,
+            lease_ttl: DEFAULT_LEASE_TTL,
+            routing_active: AtomicBool::new(true),
+            circuit_breaker: None,
+            dedup: std::sync::Mutex::new(DedupWindow::new(DedupWindow::ring_path(&root, None), DEFAULT_DEDUP_CAPACITY)),
+            dedup_capacity: DEFAULT_DEDUP_CAPACITY,
+            duplicate_policy: DuplicatePolicy::default(),
         })
     }
 
+    /// Set how routed instances are tracked as processes (sampling and verbosity)
+    pub fn with_process_tracking(self, tracking: ProcessTracking) -> Self {
+        self.edge_kernel.lock().unwrap().set_process_tracking(Some(tracking));
+        self
+    }
+
+    /// Set how long events are collected before a batch is routed
+    pub fn with_debounce(mut self, debounce: Duration) -> Self {
+        self.debounce = debounce;
+        self
+    }
+
+    /// Set the interval between reconciliation sweeps
+    pub fn with_reconcile_interval(mut self, interval: Duration) -> Self {
+        self.reconcile_interval = interval;
+        self
+    }
+
+    /// Record routing counts, errors and latencies, and report this project's gauges
+    pub fn with_metrics(mut self, metrics: Arc<DaemonMetrics>) -> Self {
+        let label = metrics.register_project(&self.root);
+        self.metrics = Some((metrics, label));
+        self
+    }
+
+    /// Accept control commands on a Unix-domain socket (see `daemon::control`)
+    pub fn with_control_socket(mut self, path: PathBuf) -> Self {
+        self.control_socket = Some(path);
+        self
+    }
+
+    /// Route only the kernels in one partition of a router cluster
+    ///
+    /// Routing starts once this router holds the partition's lease.
+    pub fn with_partition(mut self, partition: Partition) -> Self {
+        self.partition = Some(partition);
+        self.routing_active = AtomicBool::new(false);
+        self.dedup = std::sync::Mutex::new(DedupWindow::new(DedupWindow::ring_path(&self.root, Some(partition)), self.dedup_capacity));
+        self
+    }
+
+    /// Set how long a partition lease lasts without renewal
+    pub fn with_lease_ttl(mut self, ttl: Duration) -> Self {
+        self.lease_ttl = ttl;
+        self
+    }
+
+    /// Stop routing to targets that keep failing, for a cooldown
+    ///
+    /// While a target's circuit is open its instances go to the configured
+    /// fallback kernel, or are dropped with an error if there is none.
+    pub fn with_circuit_breaker(mut self, config: CircuitBreakerConfig) -> Self {
+        self.circuit_breaker = Some(CircuitBreaker::new(config));
+        self
+    }
+
+    /// Set how many routed tx_ids are remembered, and what to do with duplicates
+    pub fn with_dedup(mut self, capacity: usize, policy: DuplicatePolicy) -> Self {
+        self.dedup_capacity = capacity;
+        self.duplicate_policy = policy;
+        self.dedup = std::sync::Mutex::new(DedupWindow::new(DedupWindow::ring_path(&self.root, self.partition), capacity));
+        self
+    }
+
+    /// Set the batch size that triggers routing before the debounce window ends
+    pub fn with_max_batch(mut self, max_batch: usize) -> Self {
+        self.max_batch = max_batch.max(1);
+        self
+    }
+
     pub fn start(&self, shutdown: Arc<AtomicBool>) -> Result<(), Box<dyn std::error::Error>> {
-        self.log("[EdgeRouter] Starting daemon...");
-        self.log(&format!("[EdgeRouter] Project: {}", self.root.display()));
+        if self.info_enabled() {
+            tracing::info!(project = %self.root.display(), "edge router starting");
+        }
 
         // Set up filesystem watcher
         let (tx, rx) = std::sync::mpsc::channel();
@@ -70,93 +233,570 @@ impl EdgeRouterDaemon {
             return Err(format!("Concepts directory not found: {}", concepts_path.display()).into());
         }
 
-        self.log(&format!("[EdgeRouter] Watching: {}", concepts_path.display()));
+        if self.info_enabled() {
+            tracing::info!(path = %concepts_path.display(), "watching concepts");
+        }
         watcher.watch(&concepts_path, RecursiveMode::Recursive)?;
 
-        self.log("[EdgeRouter] Ready - Waiting for instance creation events");
+        #[cfg(unix)]
+        let (_control_server, control_rx) = match &self.control_socket {
+            Some(path) => {
+                let (server, rx) = control::ControlServer::start(path.clone())?;
+                if self.info_enabled() {
+                    tracing::info!(socket = %server.path().display(), "control socket listening");
+                }
+                (Some(server), Some(rx))
+            }
+            None => (None, None),
+        };
+        #[cfg(not(unix))]
+        let control_rx: Option<std::sync::mpsc::Receiver<control::ControlRequest>> = None;
+
+        // Instances created before startup are not routed
+        let existing = self.seed_existing();
+        if self.verbose() {
+            tracing::info!(instances = existing, "ignoring existing instances");
+        }
+
+        // Leases are renewed at a third of their lifetime, released on exit
+        let mut lease = self.partition.map(|partition| PartitionLeaseHolder::new(&self.root, partition, self.lease_ttl));
+        let renew_interval = self.lease_ttl / 3;
+        let mut last_renewal = Instant::now();
+        if let Some(lease) = lease.as_mut() {
+            if self.info_enabled() {
+                tracing::info!(partition = %lease.partition(), owner = %lease.owner(), "routing partition");
+            }
+            self.refresh_lease(lease);
+        }
+
+        if self.info_enabled() {
+            tracing::info!(
+                debounce_ms = self.debounce.as_millis() as u64,
+                sweep_secs = self.reconcile_interval.as_secs(),
+                "edge router ready"
+            );
+        }
+
+        let mut batch: Vec<PathBuf> = Vec::new();
+        let mut batch_started = Instant::now();
+        let mut last_sweep = Instant::now();
+        let mut last_scheduled = Instant::now();
+        let mut sweep_requested = false;
 
         // Event loop
         loop {
             if shutdown.load(Ordering::SeqCst) {
-                self.log("[EdgeRouter] Shutdown signal received, exiting...");
+                if self.info_enabled() {
+                    tracing::info!("edge router shutting down");
+                }
                 break;
             }
 
-            match rx.recv_timeout(std::time::Duration::from_millis(1000)) {
+            // Wake for the end of the debounce window, the next sweep, or a shutdown check
+            let timeout = if batch.is_empty() {
+                self.reconcile_interval
+                    .saturating_sub(last_sweep.elapsed())
+                    .min(Duration::from_millis(1000))
+            } else {
+                self.debounce.saturating_sub(batch_started.elapsed())
+            };
+            let timeout = if lease.is_some() {
+                timeout.min(renew_interval.saturating_sub(last_renewal.elapsed()))
+            } else {
+                timeout
+            };
+
+            match rx.recv_timeout(timeout) {
                 Ok(Ok(event)) => {
-                    self.handle_filesystem_event(event);
+                    if event.need_rescan() {
+                        sweep_requested = true;
+                    }
+                    if batch.is_empty() {
+                        batch_started = Instant::now();
+                    }
+                    self.collect_event(event, &mut batch);
                 }
                 Ok(Err(e)) => {
-                    eprintln!("[EdgeRouter] Watcher error: {}", e);
+                    // Events may have been lost - catch up with a sweep
+                    tracing::warn!(error = %e, "watcher error, sweeping for missed instances");
+                    sweep_requested = true;
+                }
+                Err(RecvTimeoutError::Timeout) => {}
+                Err(RecvTimeoutError::Disconnected) => {
+                    return Err("Filesystem watcher stopped unexpectedly".into());
+                }
+            }
+
+            if let Some(lease) = lease.as_mut() {
+                if last_renewal.elapsed() >= renew_interval {
+                    self.refresh_lease(lease);
+                    last_renewal = Instant::now();
                 }
-                Err(_) => {
-                    // Timeout - continue
+            }
+
+            // Apply operator commands between batches
+            if let Some(rx) = &control_rx {
+                while let Ok(request) = rx.try_recv() {
+                    let result = self.control(&request.command);
+                    request.respond(result);
                 }
             }
+
+            if !batch.is_empty() && (batch.len() >= self.max_batch || batch_started.elapsed() >= self.debounce) {
+                self.route_batch(std::mem::take(&mut batch));
+            }
+
+            if last_scheduled.elapsed() >= SCHEDULED_POLL_INTERVAL {
+                self.deliver_scheduled();
+                last_scheduled = Instant::now();
+            }
+
+            if sweep_requested || last_sweep.elapsed() >= self.reconcile_interval {
+                // Route pending events first so the sweep only finds missed instances
+                self.route_batch(std::mem::take(&mut batch));
+                match self.reconcile_once() {
+                    Ok(0) => {}
+                    Ok(count) if self.info_enabled() => tracing::info!(instances = count, "sweep routed missed instances"),
+                    Ok(_) => {}
+                    Err(e) => tracing::error!(error = %e, "sweep failed"),
+                }
+                last_sweep = Instant::now();
+                sweep_requested = false;
+            }
         }
 
         Ok(())
     }
 
-    fn handle_filesystem_event(&self, event: Event) {
-        // Only care about Create events
-        if !matches!(event.kind, EventKind::Create(_)) {
-            return;
+    /// Mark every instance currently in storage/ as routed
+    ///
+    /// # Returns
+    /// Number of instances found
+    pub fn seed_existing(&self) -> usize {
+        let instances = self.scan_instances();
+        let count = instances.len();
+        self.seen.lock().unwrap().extend(instances);
+        count
+    }
+
+    /// Take or renew this router's partition lease, starting or stopping routing
+    ///
+    /// # Returns
+    /// Whether routing is active afterwards
+    pub fn refresh_lease(&self, lease: &mut PartitionLeaseHolder) -> bool {
+        let partition = lease.partition();
+
+        match lease.refresh() {
+            Ok(LeaseState::Acquired { previous }) => {
+                let seeded = self.take_over(previous.as_ref());
+                if self.info_enabled() {
+                    tracing::info!(partition = %partition, handled = seeded, "acquired partition lease");
+                }
+                self.activate_routing();
+            }
+            Ok(LeaseState::Renewed) => {
+                if !self.routing_active.load(Ordering::SeqCst) {
+                    if self.info_enabled() {
+                        tracing::info!(partition = %partition, "partition lease renewed, resuming");
+                    }
+                    self.activate_routing();
+                }
+            }
+            Ok(LeaseState::Standby { holder }) => {
+                if self.routing_active.swap(false, Ordering::SeqCst) {
+                    tracing::warn!(partition = %partition, holder = %holder, "lost partition lease");
+                } else if self.verbose() {
+                    tracing::info!(partition = %partition, holder = %holder, "standby for partition");
+                }
+            }
+            Err(e) => {
+                // Without a confirmed lease another router may take over
+                self.routing_active.store(false, Ordering::SeqCst);
+                tracing::error!(partition = %partition, error = %e, "failed to refresh partition lease");
+            }
         }
 
-        for path in &event.paths {
-            // Check if this is a storage instance: concepts/{Kernel}/storage/{tx-id}.inst
-            let path_str = path.to_string_lossy();
+        self.routing_active.load(Ordering::SeqCst)
+    }
+
+    /// Mark owned instances handled by a previous lease holder as routed
+    ///
+    /// # Returns
+    /// Number of instances marked
+    fn take_over(&self, previous: Option<&PartitionLease>) -> usize {
+        let previous = match previous {
+            Some(lease) => lease,
+            None => return 0,
+        };
 
-            if !path_str.contains("/storage/") {
-                continue;
+        let handled: Vec<PathBuf> = self
+            .scan_instances()
+            .into_iter()
+            .filter(|path| self.owns_instance(path))
+            .filter(|path| {
+                std::fs::metadata(path)
+                    .and_then(|m| m.modified())
+                    .is_ok_and(|modified| chrono::DateTime::<chrono::Utc>::from(modified) <= previous.renewed_at)
+            })
+            .collect();
+
+        let count = handled.len();
+        self.seen.lock().unwrap().extend(handled);
+
+        // Pick up the tx_ids the previous holder routed
+        self.dedup.lock().unwrap().reload();
+        count
+    }
+
+    // Start routing and catch up on instances created while inactive
+    fn activate_routing(&self) {
+        self.routing_active.store(true, Ordering::SeqCst);
+        match self.reconcile_once() {
+            Ok(0) => {}
+            Ok(count) if self.info_enabled() => tracing::info!(instances = count, "routed instances on takeover"),
+            Ok(_) => {}
+            Err(e) => tracing::error!(error = %e, "takeover sweep failed"),
+        }
+    }
+
+    /// Deliver scheduled edge request batches that are due
+    ///
+    /// Routers in standby leave them to the active router.
+    ///
+    /// # Returns
+    /// Number of edge requests delivered
+    pub fn deliver_scheduled(&self) -> usize {
+        if !self.routing_active.load(Ordering::SeqCst) {
+            return 0;
+        }
+
+        match self.edge_requests.deliver_scheduled(chrono::Utc::now()) {
+            Ok(0) => 0,
+            Ok(count) => {
+                if self.info_enabled() {
+                    tracing::info!(requests = count, "delivered scheduled edge requests");
+                }
+                count
+            }
+            Err(e) => {
+                tracing::error!(error = %e, "scheduled delivery failed");
+                0
+            }
+        }
+    }
+
+    /// Route every instance in storage/ that has not been routed yet
+    ///
+    /// Also forgets instances that have been removed, so the routed set
+    /// tracks what is on disk.
+    ///
+    /// # Returns
+    /// Number of instances routed
+    pub fn reconcile_once(&self) -> Result<usize, Box<dyn std::error::Error>> {
+        let concepts_path = self.root.join("concepts");
+        if !concepts_path.is_dir() {
+            return Err(format!("Concepts directory not found: {}", concepts_path.display()).into());
+        }
+
+        let instances = self.scan_instances();
+        let present: HashSet<PathBuf> = instances.iter().cloned().collect();
+        self.seen.lock().unwrap().retain(|path| present.contains(path));
+
+        Ok(self.route_batch(instances))
+    }
+
+    /// Apply a control command
+    ///
+    /// # Returns
+    /// JSON describing the outcome, sent back to the control client
+    pub fn control(&self, command: &ControlCommand) -> crate::errors::Result<serde_json::Value> {
+        match command {
+            ControlCommand::Pause { kernel } => {
+                self.paused.lock().unwrap().insert(kernel.clone());
+                if self.info_enabled() {
+                    tracing::info!(kernel = %kernel, "paused routing");
+                }
+                Ok(serde_json::json!({ "paused": kernel }))
+            }
+            ControlCommand::Resume { kernel } => {
+                if !self.paused.lock().unwrap().remove(kernel) {
+                    return Err(crate::errors::CkpError::ValidationError(format!("{} is not paused", kernel)));
+                }
+                if self.info_enabled() {
+                    tracing::info!(kernel = %kernel, "resumed routing");
+                }
+
+                // Held instances are re-checked; those still blocked are held again
+                let held = std::mem::take(&mut *self.held.lock().unwrap());
+                let before = held.len();
+                for path in held {
+                    self.handle_instance(&path);
+                }
+                let released = before - self.held.lock().unwrap().len();
+                Ok(serde_json::json!({ "resumed": kernel, "released": released }))
+            }
+            ControlCommand::Drain { kernel, queue } => {
+                let queue = queue.as_deref().unwrap_or("inbox");
+                let drained = control::drain_queue(&self.root, kernel, queue)?;
+                if self.info_enabled() {
+                    tracing::info!(kernel = %kernel, queue, drained, "drained queue");
+                }
+                Ok(serde_json::json!({ "kernel": kernel, "queue": queue, "drained": drained }))
+            }
+            ControlCommand::Dump => {
+                let mut paused: Vec<String> = self.paused.lock().unwrap().iter().cloned().collect();
+                paused.sort();
+                let held: Vec<String> = self.held.lock().unwrap().iter().map(|p| p.display().to_string()).collect();
+                let contracts: std::collections::BTreeMap<String, Vec<String>> = self
+                    .notification_cache
+                    .lock()
+                    .unwrap()
+                    .iter()
+                    .map(|(kernel, targets)| (kernel.clone(), targets.iter().map(|(t, p)| format!("{}:{}", p, t)).collect()))
+                    .collect();
+
+                Ok(serde_json::json!({
+                    "project": self.root.display().to_string(),
+                    "logLevel": self.log_level.get(),
+                    "debounceMs": self.debounce.as_millis() as u64,
+                    "reconcileIntervalSecs": self.reconcile_interval.as_secs(),
+                    "maxBatch": self.max_batch,
+                    "partition": self.partition.map(|p| p.to_string()),
+                    "routingActive": self.routing_active.load(Ordering::SeqCst),
+                    "seenInstances": self.seen.lock().unwrap().len(),
+                    "dedupWindow": self.dedup.lock().unwrap().len(),
+                    "duplicatePolicy": self.duplicate_policy,
+                    "paused": paused,
+                    "held": held,
+                    "notificationContracts": contracts,
+                    "routingTable": self.routing_table.lock().unwrap().stats(),
+                }))
+            }
+            ControlCommand::Flush => {
+                let routes = self.routing_table.lock().unwrap().flush();
+                let contracts = std::mem::take(&mut *self.notification_cache.lock().unwrap()).len();
+                if self.info_enabled() {
+                    tracing::info!(kernels = routes.max(contracts), "flushed cached routes");
+                }
+                Ok(serde_json::json!({ "flushedRoutes": routes, "flushedContracts": contracts }))
+            }
+            ControlCommand::LogLevel { level } => {
+                self.log_level.set(*level);
+                tracing::info!(level = ?level, "log level changed");
+                Ok(serde_json::json!({ "logLevel": level }))
             }
+        }
+    }
+
+    /// Add the storage instances referenced by an event to a batch
+    fn collect_event(&self, event: Event, batch: &mut Vec<PathBuf>) {
+        self.invalidate_routes(&event);
+
+        // Only care about Create events, and instances moved into storage/
+        let paths = match event.kind {
+            EventKind::Create(_) => event.paths,
+            EventKind::Modify(ModifyKind::Name(RenameMode::To)) => event.paths,
+            EventKind::Modify(ModifyKind::Name(RenameMode::Both)) => event.paths.into_iter().skip(1).collect(),
+            _ => return,
+        };
+
+        for path in paths {
+            if Self::is_instance_path(&path) && !batch.contains(&path) {
+                batch.push(path);
+            }
+        }
+    }
+
+    /// Drop the cached routes and contracts an ontology or edge change affects
+    fn invalidate_routes(&self, event: &Event) {
+        for invalidation in Invalidation::for_event(&self.root.join("concepts"), event) {
+            if let Invalidation::Kernel(kernel) = &invalidation {
+                self.notification_cache.lock().unwrap().remove(kernel);
+            }
+            let dropped = self.routing_table.lock().unwrap().invalidate(&invalidation);
+            if dropped > 0 && self.verbose() {
+                tracing::info!(kernels = dropped, invalidation = ?invalidation, "invalidated cached routes");
+            }
+        }
+    }
+
+    /// Routes of a source kernel, from the routing table or resolved afresh
+    fn routes_for(&self, edge_kernel: &mut EdgeKernel, source: &str) -> crate::errors::Result<Vec<EdgeRoute>> {
+        if let Some(routes) = self.routing_table.lock().unwrap().get(source) {
+            return Ok(routes);
+        }
 
-            // Check if it's an .inst directory
-            if !path_str.ends_with(".inst") {
+        let routes = edge_kernel.resolve_routes(source)?;
+        self.routing_table.lock().unwrap().insert(source, routes.clone());
+        Ok(routes)
+    }
+
+    /// Route a batch of instances, skipping any already routed
+    ///
+    /// # Returns
+    /// Number of instances routed
+    fn route_batch(&self, batch: Vec<PathBuf>) -> usize {
+        if batch.len() > 1 && self.verbose() {
+            tracing::info!(instances = batch.len(), "processing batch");
+        }
+
+        if !self.routing_active.load(Ordering::SeqCst) {
+            return 0;
+        }
+
+        let mut routed = 0;
+        for path in batch {
+            if !self.owns_instance(&path) {
+                continue;
+            }
+            if !self.seen.lock().unwrap().insert(path.clone()) {
                 continue;
             }
 
-            // Extract kernel name from path
-            let kernel_name = match self.extract_kernel_from_path(path) {
-                Some(name) => name,
-                None => {
-                    if self.verbose {
-                        eprintln!("[EdgeRouter] Could not extract kernel name from: {}", path.display());
-                    }
-                    continue;
+            self.handle_instance(&path);
+            routed += 1;
+        }
+        routed
+    }
+
+    fn handle_instance(&self, path: &Path) {
+        // Extract kernel name from path
+        let kernel_name = match self.extract_kernel_from_path(path) {
+            Some(name) => name,
+            None => {
+                if self.verbose() {
+                    tracing::warn!(path = %path.display(), "could not extract kernel name");
                 }
-            };
+                return;
+            }
+        };
 
-            self.log(&format!("[EdgeRouter] Instance created: {} (kernel: {})", path.display(), kernel_name));
+        let tx_id = path.file_stem().map(|stem| stem.to_string_lossy().to_string()).unwrap_or_default();
+        let _span = tracing::info_span!("route", kernel = %kernel_name, tx_id = %tx_id).entered();
+        if self.info_enabled() {
+            tracing::info!(path = %path.display(), "instance created");
+        }
 
-            // Get notification contract
-            let targets = match self.get_notification_targets(&kernel_name) {
-                Ok(targets) => targets,
-                Err(e) => {
-                    if self.verbose {
-                        eprintln!("[EdgeRouter] Error reading notification contract for {}: {}", kernel_name, e);
-                    }
-                    continue;
+        // Get notification contract
+        let targets = match self.get_notification_targets(&kernel_name) {
+            Ok(targets) => targets,
+            Err(e) => {
+                if self.verbose() {
+                    tracing::warn!(error = %e, "error reading notification contract");
                 }
-            };
+                return;
+            }
+        };
+
+        if targets.is_empty() {
+            if self.verbose() {
+                tracing::info!("no notification targets");
+            }
+            return;
+        }
 
-            if targets.is_empty() {
-                if self.verbose {
-                    self.log(&format!("[EdgeRouter] No notification targets for {}", kernel_name));
+        // Routing goes through every outgoing edge, so a paused source or target holds the instance
+        {
+            let paused = self.paused.lock().unwrap();
+            if let Some(kernel) = std::iter::once(&kernel_name)
+                .chain(targets.iter().map(|(target, _)| target))
+                .find(|kernel| paused.contains(*kernel))
+            {
+                if self.info_enabled() {
+                    tracing::info!(path = %path.display(), paused = %kernel, "holding instance");
                 }
-                continue;
+                self.held.lock().unwrap().push(path.to_path_buf());
+                return;
             }
+        }
 
-            self.log(&format!("[EdgeRouter] Routing to {} target(s)", targets.len()));
+        // A tx already routed from this kernel is a re-emit
+        let duplicate = self.dedup.lock().unwrap().contains(&kernel_name, &tx_id);
+        if duplicate {
+            match self.duplicate_policy {
+                DuplicatePolicy::Drop => {
+                    tracing::warn!("dropping duplicate");
+                    return;
+                }
+                DuplicatePolicy::Flag => {
+                    tracing::warn!("delivering duplicate, flagged");
+                }
+            }
+        }
 
-            // Route to each target
-            for (target, predicate) in targets {
-                if let Err(e) = self.route_to_target(path, &kernel_name, &target, &predicate) {
-                    eprintln!("[EdgeRouter] Failed to route to {}: {}", target, e);
+        if self.info_enabled() {
+            tracing::info!(targets = targets.len(), "routing instance");
+        }
+
+        // Route to each target
+        let mut delivered = false;
+        for (target, predicate) in targets {
+            let result = match &self.circuit_breaker {
+                Some(breaker) => breaker
+                    .call(&target, |target| {
+                        if !self.root.join("concepts").join(target).is_dir() {
+                            return Err(CkpError::KernelNotFound(target.to_string()));
+                        }
+                        self.route_to_target(path, &kernel_name, target, &predicate, duplicate)
+                            .map_err(|e| CkpError::EdgeRouting(e.to_string()))
+                    })
+                    .map_err(Into::into),
+                None => self.route_to_target(path, &kernel_name, &target, &predicate, duplicate),
+            };
+            delivered |= result.is_ok();
+            if let Err(e) = result {
+                tracing::error!(
+                    edge = %format!("{}.{}", predicate, kernel_name),
+                    target = %target,
+                    error = %e,
+                    "failed to route instance"
+                );
+                if let Some((metrics, project)) = &self.metrics {
+                    metrics.record_error(project, &predicate, &kernel_name, &target);
                 }
             }
         }
+
+        if delivered {
+            if let Err(e) = self.dedup.lock().unwrap().record(&kernel_name, &tx_id) {
+                tracing::warn!(error = %e, "failed to record tx in dedup window");
+            }
+        }
+    }
+
+    /// Whether a path is a storage instance: concepts/{Kernel}/storage/{tx-id}.inst
+    fn is_instance_path(path: &Path) -> bool {
+        let path_str = path.to_string_lossy();
+        path_str.contains("/storage/") && path_str.ends_with(".inst")
+    }
+
+    /// All instances in every kernel's storage/, in a stable order
+    fn scan_instances(&self) -> Vec<PathBuf> {
+        let mut instances = Vec::new();
+
+        let kernels = match std::fs::read_dir(self.root.join("concepts")) {
+            Ok(entries) => entries,
+            Err(_) => return instances,
+        };
+
+        // Kernels are listed in parallel on the shared scan pool
+        let kernels: Vec<PathBuf> = kernels.filter_map(|e| e.ok()).map(|e| e.path()).collect();
+        for storage in crate::storage::walk::par_map(kernels, |kernel| crate::storage::shard::list_entries(&kernel.join("storage"))) {
+            instances.extend(storage.into_iter().filter(|path| Self::is_instance_path(path)));
+        }
+
+        instances.sort();
+        instances
+    }
+
+    // Whether this router's partition covers the instance's kernel
+    fn owns_instance(&self, path: &Path) -> bool {
+        match self.partition {
+            Some(partition) => self
+                .extract_kernel_from_path(path)
+                .is_some_and(|kernel| partition.owns(&kernel)),
+            None => true,
+        }
     }
 
     fn extract_kernel_from_path(&self, path: &Path) -> Option<String> {
@@ -177,15 +817,15 @@ impl EdgeRouterDaemon {
         {
             let cache = self.notification_cache.lock().unwrap();
             if let Some(targets) = cache.get(kernel_name) {
-                if self.verbose {
-                    self.log(&format!("[EdgeRouter] Cache hit for {}", kernel_name));
+                if self.verbose() {
+                    tracing::info!(kernel = kernel_name, "notification contract cache hit");
                 }
                 return Ok(targets.clone());
             }
         }
 
-        if self.verbose {
-            self.log(&format!("[EdgeRouter] Reading notification_contract for {}", kernel_name));
+        if self.verbose() {
+            tracing::info!(kernel = kernel_name, "reading notification contract");
         }
 
         // Read from ontology
@@ -209,35 +849,295 @@ impl EdgeRouterDaemon {
         Ok(targets)
     }
 
-    fn route_to_target(&self, instance_path: &Path, source: &str, target: &str, predicate: &str) -> Result<(), Box<dyn std::error::Error>> {
+    fn route_to_target(
+        &self,
+        instance_path: &Path,
+        source: &str,
+        target: &str,
+        predicate: &str,
+        duplicate: bool,
+    ) -> Result<(), Box<dyn std::error::Error>> {
         let mut edge_kernel = self.edge_kernel.lock().unwrap();
 
         // Check if edge exists, create if not
         let edge_urn = format!("ckp://Edge.{}.{}-to-{}:v1.3.16", predicate, source, target);
 
         if edge_kernel.get_edge(&edge_urn)?.is_none() {
-            self.log(&format!("[EdgeRouter] Creating edge: {} -> {} ({})", source, target, predicate));
+            if self.info_enabled() {
+                tracing::info!(source, target, predicate, "creating edge");
+            }
             edge_kernel.create_edge(predicate, source, target)?;
+            self.routing_table.lock().unwrap().invalidate(&Invalidation::Kernel(source.to_string()));
         }
 
         // Route instance
-        let routed_paths = edge_kernel.route_instance(instance_path, source)?;
+        let routes = self.routes_for(&mut edge_kernel, source)?;
+        let routed_paths = edge_kernel.route_instance_along(instance_path, source, &routes)?;
+        if duplicate {
+            for path in &routed_paths {
+                std::fs::write(DedupWindow::marker_path(path), "")?;
+            }
+        }
 
-        self.log(&format!("[EdgeRouter] Routed {} to {} (created {} symlink(s))",
-                  instance_path.file_name().unwrap().to_string_lossy(),
-                  target,
-                  routed_paths.len()));
+        if let Some((metrics, project)) = &self.metrics {
+            // Latency from instance creation, approximated by its directory mtime
+            let latency = std::fs::metadata(instance_path)
+                .and_then(|m| m.modified())
+                .ok()
+                .and_then(|created| created.elapsed().ok())
+                .unwrap_or_default();
+            metrics.record_routed(project, predicate, source, target, latency);
+        }
 
-        if self.verbose {
+        // Warm targets start on their first inbound job
+        match KernelManager::new(self.root.clone()).and_then(|manager| manager.wake(target)) {
+            Ok(Some(pid)) if self.info_enabled() => tracing::info!(target, pid, "woke target kernel"),
+            Ok(_) => {}
+            Err(e) => tracing::warn!(target, error = %e, "failed to wake target kernel"),
+        }
+
+        if self.info_enabled() {
+            tracing::info!(target, symlinks = routed_paths.len(), "routed instance");
+        }
+
+        if self.verbose() {
             for path in &routed_paths {
-                self.log(&format!("[EdgeRouter]   -> {}", path.display()));
+                tracing::info!(path = %path.display(), "created queue symlink");
             }
         }
 
         Ok(())
     }
 
-    fn log(&self, message: &str) {
-        eprintln!("{}", message);
+    fn verbose(&self) -> bool {
+        self.log_level.enabled(LogLevel::Debug)
+    }
+
+    fn info_enabled(&self) -> bool {
+        self.log_level.enabled(LogLevel::Info)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use notify::event::CreateKind;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn write_source_kernel(root: &Path) {
+        let kernel_dir = root.join("concepts/Source");
+        fs::create_dir_all(kernel_dir.join("storage")).unwrap();
+        fs::write(
+            kernel_dir.join("conceptkernel.yaml"),
+            "apiVersion: conceptkernel/v1\nkind: Ontology\nmetadata:\n  name: ckp://Source:v0.1\n  type: node:cold\n  version: v0.1\nspec:\n  notification_contract:\n    - target_kernel: Target\n      queue: inbox\n",
+        )
+        .unwrap();
+    }
+
+    fn write_instance(root: &Path, tx_id: &str) -> PathBuf {
+        let instance = root.join("concepts/Source/storage").join(format!("{}.inst", tx_id));
+        fs::create_dir_all(&instance).unwrap();
+        fs::write(instance.join("receipt.json"), "{}").unwrap();
+        instance
+    }
+
+    fn routed(root: &Path) -> usize {
+        fs::read_dir(root.join("concepts/Target/queue/edges/PRODUCES.Source"))
+            .map(|entries| entries.filter_map(|e| e.ok()).filter(|e| e.file_name() != ".gitkeep").count())
+            .unwrap_or(0)
+    }
+
+    #[test]
+    fn test_batched_events_and_reconciliation_sweep() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path().to_path_buf();
+        write_source_kernel(&root);
+        fs::create_dir_all(root.join("concepts/Target/queue/inbox")).unwrap();
+
+        let metrics = Arc::new(DaemonMetrics::new());
+        let daemon = EdgeRouterDaemon::new(root.clone(), false).unwrap()
+            .with_debounce(Duration::from_millis(10))
+            .with_max_batch(0)
+            .with_metrics(metrics.clone());
+        assert_eq!(daemon.max_batch, 1);
+
+        // Existing instances are not routed
+        write_instance(&root, "tx-existing");
+        assert_eq!(daemon.seed_existing(), 1);
+        assert_eq!(daemon.reconcile_once().unwrap(), 0);
+        assert_eq!(routed(&root), 0);
+
+        // Duplicate and non-instance events collapse into one batch entry
+        let instance = write_instance(&root, "tx-event");
+        let mut batch = Vec::new();
+        for _ in 0..2 {
+            daemon.collect_event(
+                Event::new(EventKind::Create(CreateKind::Folder)).add_path(instance.clone()),
+                &mut batch,
+            );
+        }
+        daemon.collect_event(
+            Event::new(EventKind::Create(CreateKind::File)).add_path(instance.join("receipt.json")),
+            &mut batch,
+        );
+        daemon.collect_event(
+            Event::new(EventKind::Remove(notify::event::RemoveKind::Folder)).add_path(instance.clone()),
+            &mut batch,
+        );
+        assert_eq!(batch, vec![instance.clone()]);
+        assert_eq!(daemon.route_batch(batch), 1);
+        assert_eq!(routed(&root), 1);
+
+        // The sweep picks up an instance whose event was missed, once
+        write_instance(&root, "tx-missed");
+        assert_eq!(daemon.reconcile_once().unwrap(), 1);
+        assert_eq!(daemon.reconcile_once().unwrap(), 0);
+        assert_eq!(daemon.route_batch(vec![instance]), 0);
+        assert_eq!(routed(&root), 2);
+
+        let project = root.file_name().unwrap().to_string_lossy().to_string();
+        assert_eq!(metrics.routed_count(&project, "PRODUCES", "Source", "Target"), 2);
+    }
+
+    #[test]
+    fn test_pause_holds_instances_until_resume() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path().to_path_buf();
+        write_source_kernel(&root);
+        fs::create_dir_all(root.join("concepts/Target/queue/inbox")).unwrap();
+
+        let daemon = EdgeRouterDaemon::new(root.clone(), false).unwrap();
+        daemon.seed_existing();
+
+        // Pausing the target holds instances from its sources
+        daemon.control(&ControlCommand::Pause { kernel: "Target".to_string() }).unwrap();
+        let instance = write_instance(&root, "tx-held");
+        assert_eq!(daemon.route_batch(vec![instance.clone()]), 1);
+        assert_eq!(routed(&root), 0);
+
+        let dump = daemon.control(&ControlCommand::Dump).unwrap();
+        assert_eq!(dump["paused"], serde_json::json!(["Target"]));
+        assert_eq!(dump["held"].as_array().unwrap().len(), 1);
+
+        // Resuming routes what was held; resuming twice is an error
+        let resumed = daemon.control(&ControlCommand::Resume { kernel: "Target".to_string() }).unwrap();
+        assert_eq!(resumed["released"], 1);
+        assert_eq!(routed(&root), 1);
+        assert!(daemon.control(&ControlCommand::Resume { kernel: "Target".to_string() }).is_err());
+
+        daemon.control(&ControlCommand::LogLevel { level: LogLevel::Error }).unwrap();
+        assert_eq!(daemon.log_level.get(), LogLevel::Error);
+    }
+
+    #[test]
+    fn test_routing_table_invalidated_by_ontology_change_and_flush() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path().to_path_buf();
+        write_source_kernel(&root);
+        fs::create_dir_all(root.join("concepts/Target/queue/inbox")).unwrap();
+
+        let daemon = EdgeRouterDaemon::new(root.clone(), false).unwrap();
+        daemon.seed_existing();
+        for tx_id in ["tx-1", "tx-2"] {
+            assert_eq!(daemon.route_batch(vec![write_instance(&root, tx_id)]), 1);
+        }
+        assert_eq!(routed(&root), 2);
+        let stats = &daemon.control(&ControlCommand::Dump).unwrap()["routingTable"];
+        assert_eq!((stats["sources"].as_u64(), stats["hits"].as_u64()), (Some(1), Some(1)));
+
+        // The target starts refusing the edge; its ontology event drops the cached verdict
+        let ontology = root.join("concepts/Target/conceptkernel.yaml");
+        fs::write(
+            &ontology,
+            "apiVersion: conceptkernel/v1\nkind: Ontology\nmetadata:\n  name: ckp://Target:v0.1\n  type: node:cold\n  version: v0.1\nspec:\n  rbac:\n    communication:\n      denied: ['*']\n",
+        )
+        .unwrap();
+        let mut batch = Vec::new();
+        daemon.collect_event(Event::new(EventKind::Create(CreateKind::File)).add_path(ontology.clone()), &mut batch);
+        assert!(batch.is_empty());
+        assert_eq!(daemon.route_batch(vec![write_instance(&root, "tx-3")]), 1);
+        assert_eq!(routed(&root), 2);
+
+        // Changes the router cannot see take effect after a flush
+        fs::remove_file(&ontology).unwrap();
+        let flushed = daemon.control(&ControlCommand::Flush).unwrap();
+        assert_eq!(flushed["flushedRoutes"], 1);
+        assert_eq!(daemon.route_batch(vec![write_instance(&root, "tx-4")]), 1);
+        assert_eq!(routed(&root), 3);
+    }
+
+    #[test]
+    fn test_reemitted_tx_dropped_or_flagged() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path().to_path_buf();
+        write_source_kernel(&root);
+        fs::create_dir_all(root.join("concepts/Target/queue/inbox")).unwrap();
+
+        let daemon = EdgeRouterDaemon::new(root.clone(), false).unwrap();
+        daemon.seed_existing();
+        let instance = write_instance(&root, "tx-retry");
+        assert_eq!(daemon.route_batch(vec![instance.clone()]), 1);
+        let queued = root.join("concepts/Target/queue/edges/PRODUCES.Source/tx-retry.inst");
+        assert!(queued.exists());
+
+        // The target consumed it, then the source crashed and wrote it again
+        fs::remove_file(&queued).unwrap();
+        fs::remove_dir_all(&instance).unwrap();
+        assert_eq!(daemon.reconcile_once().unwrap(), 0);
+        write_instance(&root, "tx-retry");
+        assert_eq!(daemon.reconcile_once().unwrap(), 1);
+        assert_eq!(routed(&root), 0);
+
+        // A restarted router still knows the tx, and flags it when delivering
+        let flagging = EdgeRouterDaemon::new(root.clone(), false)
+            .unwrap()
+            .with_dedup(DEFAULT_DEDUP_CAPACITY, DuplicatePolicy::Flag);
+        assert_eq!(flagging.route_batch(vec![instance]), 1);
+        assert!(queued.exists());
+        assert!(DedupWindow::is_flagged(&queued));
+    }
+
+    #[test]
+    fn test_partitioned_routers_and_lease_takeover() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path().to_path_buf();
+        write_source_kernel(&root);
+        fs::create_dir_all(root.join("concepts/Target/queue/inbox")).unwrap();
+
+        let owner = Partition::new(Partition::of("Source", 2), 2).unwrap();
+        let other = Partition::new(1 - owner.index, 2).unwrap();
+        let ttl = Duration::from_secs(30);
+
+        let primary = EdgeRouterDaemon::new(root.clone(), false).unwrap().with_partition(owner);
+        let standby = EdgeRouterDaemon::new(root.clone(), false).unwrap().with_partition(owner);
+        let neighbour = EdgeRouterDaemon::new(root.clone(), false).unwrap().with_partition(other);
+        for daemon in [&primary, &standby, &neighbour] {
+            daemon.seed_existing();
+        }
+
+        // Nothing is routed before the lease is held, or outside the partition
+        let instance = write_instance(&root, "tx-primary");
+        assert_eq!(primary.route_batch(vec![instance.clone()]), 0);
+        let mut neighbour_lease = PartitionLeaseHolder::new(&root, other, ttl);
+        assert!(neighbour.refresh_lease(&mut neighbour_lease));
+        assert_eq!(neighbour.route_batch(vec![instance.clone()]), 0);
+
+        // Acquiring the lease catches up on the instance created meanwhile
+        let mut primary_lease = PartitionLeaseHolder::new(&root, owner, ttl);
+        assert!(primary.refresh_lease(&mut primary_lease));
+        assert_eq!(routed(&root), 1);
+
+        let mut standby_lease = PartitionLeaseHolder::new(&root, owner, ttl);
+        assert!(!standby.refresh_lease(&mut standby_lease));
+        assert_eq!(standby.route_batch(vec![instance.clone()]), 0);
+
+        // After the primary releases, the standby routes only newer instances
+        primary_lease.release().unwrap();
+        std::thread::sleep(Duration::from_millis(20));
+        write_instance(&root, "tx-after-release");
+        assert!(standby.refresh_lease(&mut standby_lease));
+        assert_eq!(routed(&root), 2);
+        assert!(!primary.refresh_lease(&mut primary_lease));
     }
 }
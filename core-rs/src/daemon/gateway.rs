@@ -0,0 +1,786 @@
+// GatewayDaemon - HTTP API for systems outside the project
+//
+// Endpoints:
+// - POST /emit       {"target": URN, "payload": {...}} -> {"txId": ...}
+// - GET  /instances  ?kernel=K[&limit=N][&cursor=C][&order=id][&desc=true]
+// - GET  /status     KernelStatus for every kernel in the project
+//
+// Authentication: `Authorization: Bearer <token>` is resolved to an Agent
+// with ContinuantTracker::authenticate_token. Each endpoint needs a
+// permission granted through the agent's roles (role or agent metadata
+// "permissions"):
+// - POST /emit       gateway.emit, or gateway.emit:<Kernel> for one target
+// - GET  /instances  gateway.read
+// - GET  /status     gateway.status
+// "*" and the admin role grant everything.
+//
+// Jobs are written to the target inbox with the Agent URN as their source,
+// in the same format as `ckp emit`. Each connection is served on its own
+// task, up to MAX_CONNECTIONS at once, with one request per connection over
+// HTTP/1.1 with `Connection: close`.
+//
+// Other paths are served by the project's protocol mappings (see
+// `daemon::protocol_mapper`): a mapped path emits to its target kernel
+// (gateway.emit) and `{path}/{txId}` returns the mapped receipt
+// (gateway.read).
+
+use super::protocol_mapper::ProtocolMapper;
+use crate::continuant_tracker::{Agent, ContinuantTracker};
+use crate::drivers::{FileSystemDriver, JobFileBuilder, StorageDriver};
+use crate::errors::{CkpError, Result};
+use crate::kernel::KernelManager;
+use crate::port::PortManager;
+use crate::project::ProtocolMapping;
+use crate::storage::{shard, InstanceOrder, InstancePageRequest, InstanceScanner};
+use crate::urn::UrnResolver;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::net::{Ipv4Addr, SocketAddr};
+use std::path::{Component, Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::Semaphore;
+use tokio::io::{AsyncBufReadExt, AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt, BufReader};
+use tokio::net::TcpListener;
+
+/// Service name of the gateway's port reservation in .ckports
+pub const GATEWAY_SERVICE: &str = "gateway";
+
+/// Largest accepted request body
+pub const MAX_BODY_BYTES: usize = 1024 * 1024;
+
+/// Time allowed to read a request and write its response
+pub const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Connections served at once; further connections wait to be accepted
+pub const MAX_CONNECTIONS: usize = 64;
+
+/// Longest accepted request or header line, including its line ending
+pub const MAX_HEADER_LINE_BYTES: usize = 8 * 1024;
+
+/// Most header lines accepted in one request
+pub const MAX_HEADERS: usize = 100;
+
+/// Parsed HTTP request
+#[derive(Debug, Clone, Default)]
+pub struct GatewayRequest {
+    pub method: String,
+
+    /// Path without the query string
+    pub path: String,
+
+    /// Decoded query parameters
+    pub query: HashMap<String, String>,
+
+    /// Headers, keyed by lower-case name
+    pub headers: HashMap<String, String>,
+
+    pub body: Vec<u8>,
+}
+
+impl GatewayRequest {
+    /// Bearer token from the Authorization header
+    pub fn bearer_token(&self) -> Option<&str> {
+        self.headers
+            .get("authorization")
+            .and_then(|value| value.strip_prefix("Bearer "))
+            .map(str::trim)
+    }
+}
+
+/// JSON response
+#[derive(Debug, Clone)]
+pub struct GatewayResponse {
+    pub status: u16,
+    pub body: Value,
+}
+
+impl GatewayResponse {
+    fn ok(body: Value) -> Self {
+        Self { status: 200, body }
+    }
+
+    fn error(status: u16, message: impl Into<String>) -> Self {
+        Self { status, body: json!({ "error": message.into() }) }
+    }
+
+    fn from_error(error: &CkpError) -> Self {
+        let status = match error {
+            CkpError::AuthenticationFailed(_) => 401,
+            CkpError::Rbac(_) => 403,
+            CkpError::KernelNotFound(_) => 404,
+            CkpError::ValidationError(_)
+            | CkpError::UrnParse(_)
+            | CkpError::InvalidUrnFormat(_)
+            | CkpError::InvalidJson(_)
+            | CkpError::Json(_) => 400,
+            _ => 500,
+        };
+        Self::error(status, error.to_string())
+    }
+
+    fn reason(&self) -> &'static str {
+        match self.status {
+            200 => "OK",
+            202 => "Accepted",
+            400 => "Bad Request",
+            401 => "Unauthorized",
+            403 => "Forbidden",
+            404 => "Not Found",
+            405 => "Method Not Allowed",
+            413 => "Payload Too Large",
+            414 => "URI Too Long",
+            431 => "Request Header Fields Too Large",
+            502 => "Bad Gateway",
+            _ => "Internal Server Error",
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct EmitRequest {
+    target: String,
+    #[serde(default)]
+    payload: Value,
+}
+
+#[derive(Clone)]
+pub struct GatewayDaemon {
+    root: PathBuf,
+    addr: Option<SocketAddr>,
+    verbose: bool,
+}
+
+impl GatewayDaemon {
+    /// Create a gateway for a project, listening on its reserved gateway port
+    pub fn new(root: PathBuf, verbose: bool) -> Self {
+        Self { root, addr: None, verbose }
+    }
+
+    /// Listen on an explicit address instead of the reserved port
+    pub fn with_addr(mut self, addr: SocketAddr) -> Self {
+        self.addr = Some(addr);
+        self
+    }
+
+    /// Address to listen on, reserving the gateway port on first use
+    pub fn listen_addr(&self) -> Result<SocketAddr> {
+        if let Some(addr) = self.addr {
+            return Ok(addr);
+        }
+
+        let port = PortManager::new(&self.root)?.reserve(GATEWAY_SERVICE)?;
+        Ok(SocketAddr::from((Ipv4Addr::LOCALHOST, port)))
+    }
+
+    pub async fn start(&self, shutdown: Arc<AtomicBool>) -> Result<()> {
+        tracing::info!(project = %self.root.display(), "gateway starting");
+
+        self.tracker().load()?;
+        let addr = self.listen_addr()?;
+        let listener = TcpListener::bind(addr).await?;
+
+        tracing::info!(addr = %addr, "gateway listening");
+
+        let connections = Arc::new(Semaphore::new(MAX_CONNECTIONS));
+        while !shutdown.load(Ordering::SeqCst) {
+            // Wait for a free connection slot, then accept, both in short
+            // slices so shutdown is observed promptly
+            let permit = match tokio::time::timeout(Duration::from_secs(1), Arc::clone(&connections).acquire_owned()).await {
+                Ok(Ok(permit)) => permit,
+                Ok(Err(_)) => break,
+                Err(_) => continue,
+            };
+            let (stream, peer) = match tokio::time::timeout(Duration::from_secs(1), listener.accept()).await {
+                Ok(Ok(connection)) => connection,
+                Ok(Err(e)) => {
+                    tracing::warn!(error = %e, "gateway accept failed");
+                    continue;
+                }
+                Err(_) => continue,
+            };
+
+            let gateway = self.clone();
+            tokio::spawn(async move {
+                match tokio::time::timeout(REQUEST_TIMEOUT, gateway.serve_connection(stream)).await {
+                    Ok(Ok(())) => {}
+                    Ok(Err(e)) => tracing::warn!(peer = %peer, error = %e, "gateway connection failed"),
+                    Err(_) => tracing::warn!(peer = %peer, "gateway connection timed out"),
+                }
+                drop(permit);
+            });
+        }
+
+        tracing::info!("gateway shutting down");
+        Ok(())
+    }
+
+    /// Read one request from a connection and write its response
+    pub async fn serve_connection<S>(&self, stream: S) -> Result<()>
+    where
+        S: AsyncRead + AsyncWrite + Unpin,
+    {
+        let mut reader = BufReader::new(stream);
+
+        let response = match read_request(&mut reader).await? {
+            Ok(request) => {
+                let response = self.handle(&request).await;
+                if self.verbose {
+                    tracing::info!(method = %request.method, path = %request.path, status = response.status, "gateway request");
+                }
+                response
+            }
+            Err(response) => response,
+        };
+
+        let body = serde_json::to_vec(&response.body)?;
+        let head = format!(
+            "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+            response.status,
+            response.reason(),
+            body.len()
+        );
+
+        let stream = reader.get_mut();
+        stream.write_all(head.as_bytes()).await?;
+        stream.write_all(&body).await?;
+        stream.flush().await?;
+        Ok(())
+    }
+
+    /// Authenticate, authorize and dispatch a request
+    pub async fn handle(&self, request: &GatewayRequest) -> GatewayResponse {
+        let agent = match request.bearer_token() {
+            Some(token) => match self.tracker().authenticate_token(token) {
+                Ok(agent) => agent,
+                Err(e) => return GatewayResponse::from_error(&e),
+            },
+            None => return GatewayResponse::error(401, "Missing bearer token"),
+        };
+
+        let result = match (request.method.as_str(), request.path.as_str()) {
+            ("POST", "/emit") => self.emit(&agent, &request.body),
+            ("GET", "/instances") => self.instances(&agent, &request.query),
+            ("GET", "/status") => self.status(&agent).await,
+            (_, "/emit") | (_, "/instances") | (_, "/status") => {
+                return GatewayResponse::error(405, format!("{} not allowed on {}", request.method, request.path));
+            }
+            _ => return self.handle_mapped(&agent, request),
+        };
+
+        match result {
+            Ok(body) => GatewayResponse::ok(body),
+            Err(e) => GatewayResponse::from_error(&e),
+        }
+    }
+
+    fn emit(&self, agent: &Agent, body: &[u8]) -> Result<Value> {
+        let request: EmitRequest = serde_json::from_slice(body)?;
+        self.emit_to(agent, &request.target, request.payload)
+    }
+
+    fn emit_to(&self, agent: &Agent, target: &str, payload: Value) -> Result<Value> {
+        let kernel = target_kernel(target)?;
+
+        authorize(agent, &["gateway.emit".to_string(), format!("gateway.emit:{}", kernel)])?;
+
+        if !KernelManager::new(self.root.clone())?.exists(&kernel) {
+            return Err(CkpError::KernelNotFound(kernel));
+        }
+
+        let job = JobFileBuilder::new(target.to_string(), payload)
+            .with_source(agent.urn.clone(), agent.urn.clone())
+            .build()?;
+
+        let tx_id = FileSystemDriver::new(self.root.clone(), String::new()).write_job(target, job)?;
+        tracing::info!(
+            kernel = %kernel,
+            tx_id = %tx_id,
+            agent = %agent.urn,
+            target,
+            "gateway emitted job"
+        );
+
+        Ok(json!({ "txId": tx_id, "target": target }))
+    }
+
+    /// Serve a path declared by the project's protocol mappings
+    fn handle_mapped(&self, agent: &Agent, request: &GatewayRequest) -> GatewayResponse {
+        let mapper = match ProtocolMapper::for_project(&self.root) {
+            Ok(mapper) => mapper,
+            Err(e) => return GatewayResponse::from_error(&e),
+        };
+
+        if let Some(mapping) = mapper.inbound(&request.method, &request.path) {
+            let target = mapping.target.as_deref().unwrap_or_default();
+            let payload = ProtocolMapper::inbound_payload(mapping, request);
+            return match self.emit_to(agent, target, payload) {
+                Ok(mut body) => {
+                    let receipt = format!("{}/{}", request.path, body["txId"].as_str().unwrap_or_default());
+                    body["receipt"] = json!(receipt);
+                    GatewayResponse { status: 202, body }
+                }
+                Err(e) => GatewayResponse::from_error(&e),
+            };
+        }
+
+        if let Some((mapping, tx_id)) = mapper.receipt(&request.method, &request.path) {
+            return self
+                .mapped_receipt(agent, mapping, tx_id)
+                .unwrap_or_else(|e| GatewayResponse::from_error(&e));
+        }
+
+        if mapper.serves(&request.path) {
+            return GatewayResponse::error(405, format!("{} not allowed on {}", request.method, request.path));
+        }
+        GatewayResponse::error(404, format!("No route for {}", request.path))
+    }
+
+    /// Receipt of a mapped target's transaction, as the mapping's response
+    fn mapped_receipt(&self, agent: &Agent, mapping: &ProtocolMapping, tx_id: &str) -> Result<GatewayResponse> {
+        authorize(agent, &["gateway.read".to_string()])?;
+
+        let kernel = target_kernel(mapping.target.as_deref().unwrap_or_default())?;
+        let manager = KernelManager::new(self.root.clone())?;
+        if !manager.exists(&kernel) {
+            return Err(CkpError::KernelNotFound(kernel));
+        }
+
+        let kernel_dir = manager.get_kernel_dir(&kernel);
+        let inst_dir = shard::locate(&kernel_dir.join("storage"), &format!("{}.inst", tx_id));
+        let receipt = if inst_dir.is_dir() {
+            let receipt = InstanceScanner::new(kernel_dir, kernel).read_receipt(&inst_dir)?;
+            Some(serde_json::to_value(receipt)?)
+        } else {
+            None
+        };
+
+        Ok(ProtocolMapper::receipt_response(mapping, tx_id, receipt.as_ref()))
+    }
+
+    fn instances(&self, agent: &Agent, query: &HashMap<String, String>) -> Result<Value> {
+        authorize(agent, &["gateway.read".to_string()])?;
+
+        let kernel = query
+            .get("kernel")
+            .ok_or_else(|| CkpError::ValidationError("Missing query parameter: kernel".to_string()))?;
+        let kernel = &target_kernel(kernel)?;
+
+        let manager = KernelManager::new(self.root.clone())?;
+        if !manager.exists(kernel) {
+            return Err(CkpError::KernelNotFound(kernel.clone()));
+        }
+
+        let limit = query
+            .get("limit")
+            .map(|limit| {
+                limit.parse::<usize>().map_err(|_| CkpError::ValidationError(format!("Invalid limit: {}", limit)))
+            })
+            .transpose()?;
+        let order = match query.get("order").map(String::as_str) {
+            None | Some("timestamp") => InstanceOrder::Timestamp,
+            Some("id") => InstanceOrder::Id,
+            Some(other) => return Err(CkpError::ValidationError(format!("Invalid order: {}", other))),
+        };
+
+        let request = InstancePageRequest {
+            order,
+            descending: query.get("desc").is_some_and(|desc| desc == "true"),
+            cursor: query.get("cursor").cloned(),
+            limit,
+        };
+
+        let scanner = InstanceScanner::new(manager.get_kernel_dir(kernel), kernel.clone());
+        Ok(serde_json::to_value(scanner.list_page(&request)?)?)
+    }
+
+    async fn status(&self, agent: &Agent) -> Result<Value> {
+        authorize(agent, &["gateway.status".to_string()])?;
+
+        let kernels = KernelManager::new(self.root.clone())?.status().await?;
+        Ok(json!({ "project": self.root.to_string_lossy(), "kernels": kernels }))
+    }
+
+    /// Agent store, read afresh so issued and revoked tokens apply immediately
+    fn tracker(&self) -> ContinuantTracker {
+        ContinuantTracker::new(self.root.join("concepts"))
+    }
+}
+
+/// Kernel name of an emit target (`Kernel` or a kernel URN)
+///
+/// The name must be a single plain path component, so it cannot reach
+/// outside the project's `concepts/` directory.
+fn target_kernel(target: &str) -> Result<String> {
+    let kernel = if target.starts_with("ckp://") {
+        UrnResolver::parse(target)?.kernel
+    } else {
+        target.to_string()
+    };
+
+    let mut components = Path::new(&kernel).components();
+    match (components.next(), components.next()) {
+        (Some(Component::Normal(_)), None) => Ok(kernel),
+        _ => Err(CkpError::ValidationError(format!("Invalid target kernel: {}", target))),
+    }
+}
+
+/// Permissions granted to an agent directly and through its roles
+fn agent_permissions(agent: &Agent) -> Vec<String> {
+    let mut permissions = Vec::new();
+
+    let metadata = std::iter::once(&agent.metadata).chain(agent.roles.iter().map(|role| &role.metadata));
+    for metadata in metadata {
+        if let Some(Value::Array(granted)) = metadata.get("permissions") {
+            permissions.extend(granted.iter().filter_map(Value::as_str).map(str::to_string));
+        }
+    }
+
+    if agent.roles.iter().any(|role| role.name == "admin") {
+        permissions.push("*".to_string());
+    }
+
+    permissions
+}
+
+/// Require any one of the given permissions
+fn authorize(agent: &Agent, any_of: &[String]) -> Result<()> {
+    let permissions = agent_permissions(agent);
+    if permissions.iter().any(|p| p == "*" || any_of.contains(p)) {
+        return Ok(());
+    }
+
+    Err(CkpError::Rbac(format!("{} lacks permission {}", agent.urn, any_of[0])))
+}
+
+/// Read a request, or the error response to send instead
+async fn read_request<R>(reader: &mut R) -> Result<std::result::Result<GatewayRequest, GatewayResponse>>
+where
+    R: AsyncBufReadExt + AsyncReadExt + Unpin,
+{
+    let mut line = String::new();
+    if !read_line_capped(reader, &mut line).await? {
+        return Ok(Err(GatewayResponse::error(414, "Request line too long")));
+    }
+
+    let mut parts = line.split_whitespace();
+    let (method, target) = match (parts.next(), parts.next()) {
+        (Some(method), Some(target)) => (method.to_string(), target.to_string()),
+        _ => return Ok(Err(GatewayResponse::error(400, "Malformed request line"))),
+    };
+
+    let mut headers = HashMap::new();
+    let mut header_count = 0;
+    loop {
+        line.clear();
+        if !read_line_capped(reader, &mut line).await? {
+            return Ok(Err(GatewayResponse::error(431, "Header line too long")));
+        }
+        if line.is_empty() {
+            break;
+        }
+
+        let header = line.trim_end();
+        if header.is_empty() {
+            break;
+        }
+        header_count += 1;
+        if header_count > MAX_HEADERS {
+            return Ok(Err(GatewayResponse::error(431, "Too many headers")));
+        }
+        if let Some((name, value)) = header.split_once(':') {
+            headers.insert(name.trim().to_lowercase(), value.trim().to_string());
+        }
+    }
+
+    let length = match headers.get("content-length").map(|value| value.parse::<usize>()) {
+        None => 0,
+        Some(Ok(length)) if length <= MAX_BODY_BYTES => length,
+        Some(Ok(_)) => return Ok(Err(GatewayResponse::error(413, "Request body too large"))),
+        Some(Err(_)) => return Ok(Err(GatewayResponse::error(400, "Invalid Content-Length"))),
+    };
+
+    let mut body = vec![0u8; length];
+    reader.read_exact(&mut body).await?;
+
+    let (path, query) = match target.split_once('?') {
+        Some((path, query)) => (path.to_string(), parse_query(query)),
+        None => (target, HashMap::new()),
+    };
+
+    Ok(Ok(GatewayRequest { method, path, query, headers, body }))
+}
+
+/// Read one line of at most MAX_HEADER_LINE_BYTES
+///
+/// # Returns
+/// false if the line was longer (nothing more of it is read)
+async fn read_line_capped<R>(reader: &mut R, line: &mut String) -> Result<bool>
+where
+    R: AsyncBufReadExt + AsyncReadExt + Unpin,
+{
+    let read = (&mut *reader).take(MAX_HEADER_LINE_BYTES as u64).read_line(line).await?;
+    Ok(read < MAX_HEADER_LINE_BYTES || line.ends_with('\n'))
+}
+
+fn parse_query(query: &str) -> HashMap<String, String> {
+    query
+        .split('&')
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| match pair.split_once('=') {
+            Some((key, value)) => (percent_decode(key), percent_decode(value)),
+            None => (percent_decode(pair), String::new()),
+        })
+        .collect()
+}
+
+fn percent_decode(input: &str) -> String {
+    let bytes = input.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'+' => decoded.push(b' '),
+            b'%' if i + 2 < bytes.len() => {
+                let high = (bytes[i + 1] as char).to_digit(16);
+                let low = (bytes[i + 2] as char).to_digit(16);
+                match (high, low) {
+                    (Some(high), Some(low)) => {
+                        decoded.push((high * 16 + low) as u8);
+                        i += 2;
+                    }
+                    _ => decoded.push(b'%'),
+                }
+            }
+            byte => decoded.push(byte),
+        }
+        i += 1;
+    }
+
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::continuant_tracker::Role;
+    use std::fs;
+    use std::path::Path;
+    use tempfile::TempDir;
+
+    fn write_kernel(root: &Path, name: &str) {
+        let kernel_dir = root.join("concepts").join(name);
+        fs::create_dir_all(kernel_dir.join("queue/inbox")).unwrap();
+        fs::write(
+            kernel_dir.join("conceptkernel.yaml"),
+            format!(
+                "apiVersion: conceptkernel/v1\nkind: Ontology\nmetadata:\n  name: ckp://{}:v0.1\n  type: node:cold\n  version: v0.1\nspec: {{}}\n",
+                name
+            ),
+        )
+        .unwrap();
+    }
+
+    /// Create an agent holding `permissions` and return its API token
+    fn agent_with(root: &Path, identifier: &str, permissions: &[&str]) -> String {
+        let tracker = ContinuantTracker::new(root.join("concepts"));
+        let agent = tracker.create_agent("System", identifier, Vec::new(), HashMap::new()).unwrap();
+
+        let mut metadata = HashMap::new();
+        metadata.insert("permissions".to_string(), json!(permissions));
+        tracker.assign_role(&agent.urn, Role {
+            name: "integration".to_string(),
+            description: "External integration".to_string(),
+            assigned_at: chrono::Utc::now().to_rfc3339(),
+            metadata,
+        }).unwrap();
+
+        tracker.issue_api_token(&agent.urn).unwrap()
+    }
+
+    fn request(method: &str, target: &str, token: Option<&str>, body: Value) -> GatewayRequest {
+        let mut headers = HashMap::new();
+        if let Some(token) = token {
+            headers.insert("authorization".to_string(), format!("Bearer {}", token));
+        }
+
+        let (path, query) = match target.split_once('?') {
+            Some((path, query)) => (path.to_string(), parse_query(query)),
+            None => (target.to_string(), HashMap::new()),
+        };
+
+        GatewayRequest {
+            method: method.to_string(),
+            path,
+            query,
+            headers,
+            body: if body.is_null() { Vec::new() } else { serde_json::to_vec(&body).unwrap() },
+        }
+    }
+
+    #[tokio::test]
+    async fn test_gateway_authorizes_agents() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path().to_path_buf();
+        write_kernel(&root, "Demo.Worker");
+        write_kernel(&root, "Demo.Private");
+
+        let storage = root.join("concepts/Demo.Worker/storage/tx-1.inst");
+        fs::create_dir_all(&storage).unwrap();
+        fs::write(
+            storage.join("receipt.bin"),
+            json!({"id": "tx-1", "name": "first", "timestamp": "2025-11-01T10:00:00Z"}).to_string(),
+        )
+        .unwrap();
+
+        let writer = agent_with(&root, "writer", &["gateway.emit:Demo.Worker", "gateway.read"]);
+        let viewer = agent_with(&root, "viewer", &["gateway.status"]);
+        let gateway = GatewayDaemon::new(root.clone(), false);
+
+        let emit = json!({"target": "Demo.Worker", "payload": {"order": 42}});
+        assert_eq!(gateway.handle(&request("POST", "/emit", None, emit.clone())).await.status, 401);
+        assert_eq!(gateway.handle(&request("POST", "/emit", Some("ckp_wrong"), emit.clone())).await.status, 401);
+        assert_eq!(gateway.handle(&request("POST", "/emit", Some(&viewer), emit.clone())).await.status, 403);
+
+        let response = gateway.handle(&request("POST", "/emit", Some(&writer), emit)).await;
+        assert_eq!(response.status, 200);
+        let tx_id = response.body["txId"].as_str().unwrap();
+        let job: Value = serde_json::from_str(
+            &fs::read_to_string(root.join("concepts/Demo.Worker/queue/inbox").join(format!("{}.job", tx_id))).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(job["payload"]["order"], 42);
+        assert!(job["source"].as_str().unwrap().contains("Agent-writer"));
+
+        // Permission is scoped to Demo.Worker
+        let private = json!({"target": "ckp://Demo.Private:v0.1", "payload": {}});
+        assert_eq!(gateway.handle(&request("POST", "/emit", Some(&writer), private)).await.status, 403);
+
+        let response = gateway.handle(&request("GET", "/instances?kernel=Demo.Worker&limit=10", Some(&writer), Value::Null)).await;
+        assert_eq!(response.status, 200);
+        assert_eq!(response.body["instances"][0]["id"], "tx-1");
+        assert_eq!(gateway.handle(&request("GET", "/instances", Some(&writer), Value::Null)).await.status, 400);
+        assert_eq!(gateway.handle(&request("GET", "/instances?kernel=Demo.Missing", Some(&writer), Value::Null)).await.status, 404);
+
+        let response = gateway.handle(&request("GET", "/status", Some(&viewer), Value::Null)).await;
+        assert_eq!(response.status, 200);
+        assert_eq!(response.body["kernels"].as_array().unwrap().len(), 2);
+        assert_eq!(gateway.handle(&request("GET", "/status", Some(&writer), Value::Null)).await.status, 403);
+
+        assert_eq!(gateway.handle(&request("GET", "/emit", Some(&writer), Value::Null)).await.status, 405);
+        assert_eq!(gateway.handle(&request("GET", "/unknown", Some(&writer), Value::Null)).await.status, 404);
+    }
+
+    #[tokio::test]
+    async fn test_protocol_mappings_emit_and_answer_receipts() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path().to_path_buf();
+        write_kernel(&root, "Demo.Worker");
+        fs::write(
+            root.join(".ckproject"),
+            "apiVersion: conceptkernel/v1\nkind: Project\nmetadata:\n  name: demo\n  id: demo-1\nspec:\n  domain: Org.Demo\n  version: 1.0.0\n  protocol:\n    - path: /hooks/deploy\n      target: ckp://Demo.Worker:v0.1\n      payload:\n        service: /body/service\n        env: /query/env\n      response:\n        state: /data/state\n",
+        )
+        .unwrap();
+        let token = agent_with(&root, "ci", &["gateway.emit:Demo.Worker", "gateway.read"]);
+        let gateway = GatewayDaemon::new(root.clone(), false);
+
+        let response = gateway
+            .handle(&request("POST", "/hooks/deploy?env=prod", Some(&token), json!({"service": "api", "extra": 1})))
+            .await;
+        assert_eq!(response.status, 202);
+        let tx_id = response.body["txId"].as_str().unwrap().to_string();
+        assert_eq!(response.body["receipt"], format!("/hooks/deploy/{}", tx_id));
+        let job: Value = serde_json::from_str(
+            &fs::read_to_string(root.join("concepts/Demo.Worker/queue/inbox").join(format!("{}.job", tx_id))).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(job["payload"], json!({"service": "api", "env": "prod"}));
+
+        let receipt_path = format!("/hooks/deploy/{}", tx_id);
+        assert_eq!(gateway.handle(&request("GET", &receipt_path, Some(&token), Value::Null)).await.status, 202);
+
+        let inst_dir = root.join("concepts/Demo.Worker/storage").join(format!("{}.inst", tx_id));
+        fs::create_dir_all(&inst_dir).unwrap();
+        fs::write(
+            inst_dir.join("receipt.bin"),
+            json!({"id": tx_id, "name": "deploy", "timestamp": "2025-11-01T10:00:00Z", "success": true, "data": {"state": "live"}}).to_string(),
+        )
+        .unwrap();
+        let response = gateway.handle(&request("GET", &receipt_path, Some(&token), Value::Null)).await;
+        assert_eq!((response.status, response.body), (200, json!({"state": "live"})));
+
+        assert_eq!(gateway.handle(&request("GET", "/hooks/deploy", Some(&token), Value::Null)).await.status, 405);
+    }
+
+    #[tokio::test]
+    async fn test_serve_connection_over_http() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path().to_path_buf();
+        write_kernel(&root, "Demo.Worker");
+        let token = agent_with(&root, "operator", &["*"]);
+        let gateway = GatewayDaemon::new(root.clone(), false);
+
+        let body = json!({"target": "Demo.Worker", "payload": {"note": "a b"}}).to_string();
+        let raw = format!(
+            "POST /emit HTTP/1.1\r\nHost: localhost\r\nauthorization: Bearer {}\r\nContent-Length: {}\r\n\r\n{}",
+            token,
+            body.len(),
+            body
+        );
+
+        let (mut client, server) = tokio::io::duplex(64 * 1024);
+        client.write_all(raw.as_bytes()).await.unwrap();
+        gateway.serve_connection(server).await.unwrap();
+
+        let mut response = String::new();
+        client.read_to_string(&mut response).await.unwrap();
+        assert!(response.starts_with("HTTP/1.1 200 OK\r\n"));
+        assert!(response.contains("\"txId\""));
+
+        assert_eq!(parse_query("kernel=Demo.Worker&cursor=a%2Fb+c&desc")["cursor"], "a/b c");
+        assert_eq!(percent_decode("100%"), "100%");
+    }
+
+    #[tokio::test]
+    async fn test_gateway_rejects_oversized_headers_and_escaping_targets() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path().to_path_buf();
+        write_kernel(&root, "Demo.Worker");
+        let token = agent_with(&root, "operator", &["*"]);
+        let gateway = GatewayDaemon::new(root.clone(), false);
+
+        let serve = |raw: String| {
+            let gateway = gateway.clone();
+            async move {
+                let (mut client, server) = tokio::io::duplex(64 * 1024);
+                client.write_all(raw.as_bytes()).await.unwrap();
+                gateway.serve_connection(server).await.unwrap();
+                let mut response = String::new();
+                client.read_to_string(&mut response).await.unwrap();
+                response
+            }
+        };
+
+        let long = format!("GET /status HTTP/1.1\r\nX-Filler: {}\r\n\r\n", "a".repeat(MAX_HEADER_LINE_BYTES));
+        assert!(serve(long).await.starts_with("HTTP/1.1 431 "));
+        let many = format!("GET /status HTTP/1.1\r\n{}\r\n", "X-Filler: a\r\n".repeat(MAX_HEADERS + 1));
+        assert!(serve(many).await.starts_with("HTTP/1.1 431 "));
+
+        for target in ["../System.Compliance", "Demo.Worker/../../x", "/etc", "ckp://../System.Compliance:v1"] {
+            let emit = json!({"target": target, "payload": {}});
+            let response = gateway.handle(&request("POST", "/emit", Some(&token), emit)).await;
+            assert_eq!(response.status, 400, "{}", target);
+        }
+        assert!(!root.join("concepts/System.Compliance").exists());
+
+        fs::create_dir_all(root.join("outside/storage/tx-1.inst")).unwrap();
+        for kernel in ["..", "../outside", "Demo.Worker%2F..%2F..%2Foutside", "%2Fetc"] {
+            let target = format!("/instances?kernel={}", kernel);
+            let response = gateway.handle(&request("GET", &target, Some(&token), Value::Null)).await;
+            assert_eq!(response.status, 400, "{}", kernel);
+        }
+    }
+}
@@ -0,0 +1,459 @@
+// DaemonMetrics - Prometheus metrics for daemons
+//
+// Counters and histograms are recorded by daemons as they work; queue and
+// storage gauges are read from disk for every registered project when
+// /metrics is scraped. All series carry a `project` label.
+//
+// Exported series:
+// - ckp_routed_jobs_total{project,predicate,source,target}      counter
+// - ckp_routing_errors_total{project,predicate,source,target}    counter
+// - ckp_edge_latency_seconds{project,predicate,source,target}   histogram
+//   (instance creation to routed)
+// - ckp_queue_depth{project,kernel,queue}                       gauge
+//   (queue: inbox, staging, ready, edges)
+// - ckp_storage_instances{project,kernel}                       gauge
+// - ckp_storage_bytes{project,kernel}                           gauge
+// - ckp_authorization_cache_hits_total{project,kernel}          counter
+// - ckp_authorization_cache_misses_total{project,kernel}        counter
+// - ckp_authorization_cache_invalidations_total{project,kernel} counter
+//   (kernel: source of the cached decisions, see rbac::DecisionCache)
+//
+// Jobs/sec, storage growth and cache hit rate are derived by Prometheus, e.g.
+// `rate(ckp_routed_jobs_total[1m])`, `deriv(ckp_storage_bytes[1h])` and
+// `rate(ckp_authorization_cache_hits_total[5m]) / (rate(..._hits_total[5m]) + rate(..._misses_total[5m]))`.
+//
+// MetricsServer serves GET /metrics on a background thread so any daemon
+// can enable it without an async runtime.
+
+use crate::errors::Result;
+use crate::project::ProjectConfig;
+use crate::rbac::{DecisionCache, DecisionCacheStats};
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::fs;
+use std::io::{BufRead, BufReader, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread::JoinHandle;
+use std::time::Duration;
+
+/// Upper bounds (seconds) of the edge latency histogram buckets
+pub const LATENCY_BUCKETS: [f64; 11] = [0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0];
+
+/// Queues reported by ckp_queue_depth
+const QUEUES: [&str; 3] = ["inbox", "staging", "ready"];
+
+/// Labels identifying an edge: project, predicate, source, target
+type EdgeKey = (String, String, String, String);
+
+#[derive(Debug, Clone, Default)]
+struct Histogram {
+    /// Observations per bucket (not cumulative)
+    buckets: [u64; LATENCY_BUCKETS.len()],
+    count: u64,
+    sum: f64,
+}
+
+impl Histogram {
+    fn observe(&mut self, seconds: f64) {
+        if let Some(i) = LATENCY_BUCKETS.iter().position(|bound| seconds <= *bound) {
+            self.buckets[i] += 1;
+        }
+        self.count += 1;
+        self.sum += seconds;
+    }
+}
+
+#[derive(Debug, Default)]
+struct MetricsState {
+    /// Project label -> project root
+    projects: BTreeMap<String, PathBuf>,
+    routed: BTreeMap<EdgeKey, u64>,
+    errors: BTreeMap<EdgeKey, u64>,
+    latency: BTreeMap<EdgeKey, Histogram>,
+}
+
+/// Metrics shared by the daemons of one process
+#[derive(Debug, Default)]
+pub struct DaemonMetrics {
+    state: Mutex<MetricsState>,
+}
+
+impl DaemonMetrics {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Report gauges for a project
+    ///
+    /// # Returns
+    /// The project label: `metadata.name` from .ckproject, or the directory name
+    pub fn register_project(&self, root: &Path) -> String {
+        let label = ProjectConfig::load_raw(root.join(".ckproject"))
+            .map(|config| config.metadata.name)
+            .unwrap_or_else(|_| {
+                root.file_name()
+                    .map(|name| name.to_string_lossy().to_string())
+                    .unwrap_or_else(|| root.display().to_string())
+            });
+
+        self.lock().projects.insert(label.clone(), root.to_path_buf());
+        label
+    }
+
+    /// Record an instance routed along an edge
+    pub fn record_routed(&self, project: &str, predicate: &str, source: &str, target: &str, latency: Duration) {
+        let key = edge_key(project, predicate, source, target);
+        let mut state = self.lock();
+        *state.routed.entry(key.clone()).or_default() += 1;
+        state.latency.entry(key).or_default().observe(latency.as_secs_f64());
+    }
+
+    /// Record a failed routing attempt
+    pub fn record_error(&self, project: &str, predicate: &str, source: &str, target: &str) {
+        *self.lock().errors.entry(edge_key(project, predicate, source, target)).or_default() += 1;
+    }
+
+    /// Jobs routed along an edge so far
+    pub fn routed_count(&self, project: &str, predicate: &str, source: &str, target: &str) -> u64 {
+        self.lock().routed.get(&edge_key(project, predicate, source, target)).copied().unwrap_or(0)
+    }
+
+    /// Render all metrics in the Prometheus text exposition format
+    pub fn render(&self) -> String {
+        let state = self.lock();
+        let mut out = String::new();
+
+        write_header(&mut out, "ckp_routed_jobs_total", "counter", "Instances routed to a target kernel");
+        for ((project, predicate, source, target), value) in &state.routed {
+            let labels = edge_labels(project, predicate, source, target);
+            let _ = writeln!(out, "ckp_routed_jobs_total{{{}}} {}", labels, value);
+        }
+
+        write_header(&mut out, "ckp_routing_errors_total", "counter", "Failed routing attempts");
+        for ((project, predicate, source, target), value) in &state.errors {
+            let labels = edge_labels(project, predicate, source, target);
+            let _ = writeln!(out, "ckp_routing_errors_total{{{}}} {}", labels, value);
+        }
+
+        write_header(&mut out, "ckp_edge_latency_seconds", "histogram", "Time from instance creation to routing");
+        for ((project, predicate, source, target), histogram) in &state.latency {
+            let labels = edge_labels(project, predicate, source, target);
+            let mut cumulative = 0;
+            for (bound, count) in LATENCY_BUCKETS.iter().zip(histogram.buckets) {
+                cumulative += count;
+                let _ = writeln!(out, "ckp_edge_latency_seconds_bucket{{{},le=\"{}\"}} {}", labels, bound, cumulative);
+            }
+            let _ = writeln!(out, "ckp_edge_latency_seconds_bucket{{{},le=\"+Inf\"}} {}", labels, histogram.count);
+            let _ = writeln!(out, "ckp_edge_latency_seconds_sum{{{}}} {}", labels, histogram.sum);
+            let _ = writeln!(out, "ckp_edge_latency_seconds_count{{{}}} {}", labels, histogram.count);
+        }
+
+        let kernels: Vec<(String, String, PathBuf)> = state
+            .projects
+            .iter()
+            .flat_map(|(project, root)| {
+                list_kernel_dirs(root).into_iter().map(move |(kernel, dir)| (project.clone(), kernel, dir))
+            })
+            .collect();
+        let decisions: Vec<(String, BTreeMap<String, DecisionCacheStats>)> = state
+            .projects
+            .iter()
+            .map(|(project, root)| (project.clone(), DecisionCache::for_project(root).all_stats()))
+            .collect();
+        drop(state);
+
+        write_header(&mut out, "ckp_queue_depth", "gauge", "Jobs waiting in a kernel queue");
+        for (project, kernel, dir) in &kernels {
+            let queue_dir = dir.join("queue");
+            for queue in QUEUES {
+                let _ = writeln!(
+                    out,
+                    "ckp_queue_depth{{project=\"{}\",kernel=\"{}\",queue=\"{}\"}} {}",
+                    escape(project),
+                    escape(kernel),
+                    queue,
+                    count_entries(&queue_dir.join(queue))
+                );
+            }
+
+            let edges: usize = read_dirs(&queue_dir.join("edges")).iter().map(|edge| count_entries(edge)).sum();
+            let _ = writeln!(
+                out,
+                "ckp_queue_depth{{project=\"{}\",kernel=\"{}\",queue=\"edges\"}} {}",
+                escape(project),
+                escape(kernel),
+                edges
+            );
+        }
+
+        write_header(&mut out, "ckp_storage_instances", "gauge", "Instances in kernel storage");
+        let storage: Vec<(usize, u64)> = kernels.iter().map(|(_, _, dir)| storage_usage(&dir.join("storage"))).collect();
+        for ((project, kernel, _), (instances, _)) in kernels.iter().zip(&storage) {
+            let _ = writeln!(
+                out,
+                "ckp_storage_instances{{project=\"{}\",kernel=\"{}\"}} {}",
+                escape(project),
+                escape(kernel),
+                instances
+            );
+        }
+
+        write_header(&mut out, "ckp_storage_bytes", "gauge", "Bytes used by kernel storage");
+        for ((project, kernel, _), (_, bytes)) in kernels.iter().zip(&storage) {
+            let _ = writeln!(
+                out,
+                "ckp_storage_bytes{{project=\"{}\",kernel=\"{}\"}} {}",
+                escape(project),
+                escape(kernel),
+                bytes
+            );
+        }
+
+        let counters: [(&str, &str, fn(&DecisionCacheStats) -> u64); 3] = [
+            ("ckp_authorization_cache_hits_total", "Authorization decisions answered from cache", |s| s.hits),
+            ("ckp_authorization_cache_misses_total", "Authorization decisions evaluated from ontology", |s| s.misses),
+            ("ckp_authorization_cache_invalidations_total", "Cached authorization decisions dropped after an ontology change", |s| s.invalidations),
+        ];
+        for (name, help, value) in counters {
+            write_header(&mut out, name, "counter", help);
+            for (project, kernels) in &decisions {
+                for (kernel, stats) in kernels {
+                    let _ = writeln!(
+                        out,
+                        "{}{{project=\"{}\",kernel=\"{}\"}} {}",
+                        name,
+                        escape(project),
+                        escape(kernel),
+                        value(stats)
+                    );
+                }
+            }
+        }
+
+        out
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, MetricsState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// HTTP server exposing DaemonMetrics at GET /metrics
+pub struct MetricsServer {
+    addr: SocketAddr,
+    shutdown: Arc<AtomicBool>,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl MetricsServer {
+    /// Bind `addr` and serve metrics on a background thread
+    pub fn start(addr: SocketAddr, metrics: Arc<DaemonMetrics>) -> Result<Self> {
+        let listener = TcpListener::bind(addr)?;
+        listener.set_nonblocking(true)?;
+        let addr = listener.local_addr()?;
+
+        let shutdown = Arc::new(AtomicBool::new(false));
+        let stop = shutdown.clone();
+
+        let handle = std::thread::spawn(move || {
+            while !stop.load(Ordering::SeqCst) {
+                match listener.accept() {
+                    Ok((stream, _)) => {
+                        if let Err(e) = serve_scrape(stream, &metrics) {
+                            tracing::warn!(error = %e, "metrics scrape failed");
+                        }
+                    }
+                    Err(e) if e.kind() == std::io::ErrorKind::WouldBlock => {
+                        std::thread::sleep(Duration::from_millis(100));
+                    }
+                    Err(e) => tracing::warn!(error = %e, "metrics accept failed"),
+                }
+            }
+        });
+
+        Ok(Self { addr, shutdown, handle: Some(handle) })
+    }
+
+    /// Address the server is listening on
+    pub fn local_addr(&self) -> SocketAddr {
+        self.addr
+    }
+
+    /// Stop serving and wait for the server thread
+    pub fn stop(mut self) {
+        self.shutdown_thread();
+    }
+
+    fn shutdown_thread(&mut self) {
+        self.shutdown.store(true, Ordering::SeqCst);
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+impl Drop for MetricsServer {
+    fn drop(&mut self) {
+        self.shutdown_thread();
+    }
+}
+
+fn serve_scrape(stream: TcpStream, metrics: &DaemonMetrics) -> std::io::Result<()> {
+    stream.set_nonblocking(false)?;
+    stream.set_read_timeout(Some(Duration::from_secs(5)))?;
+
+    let mut reader = BufReader::new(stream);
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+
+    // Drain headers
+    let mut line = String::new();
+    while reader.read_line(&mut line)? > 0 && !line.trim_end().is_empty() {
+        line.clear();
+    }
+
+    let mut parts = request_line.split_whitespace();
+    let (status, content_type, body) = match (parts.next(), parts.next()) {
+        (Some("GET"), Some("/metrics")) => ("200 OK", "text/plain; version=0.0.4", metrics.render()),
+        _ => ("404 Not Found", "text/plain", "Not found\n".to_string()),
+    };
+
+    let mut stream = reader.into_inner();
+    write!(
+        stream,
+        "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        content_type,
+        body.len(),
+        body
+    )?;
+    stream.flush()
+}
+
+fn edge_key(project: &str, predicate: &str, source: &str, target: &str) -> EdgeKey {
+    (project.to_string(), predicate.to_string(), source.to_string(), target.to_string())
+}
+
+fn edge_labels(project: &str, predicate: &str, source: &str, target: &str) -> String {
+    format!(
+        "project=\"{}\",predicate=\"{}\",source=\"{}\",target=\"{}\"",
+        escape(project),
+        escape(predicate),
+        escape(source),
+        escape(target)
+    )
+}
+
+fn write_header(out: &mut String, name: &str, kind: &str, help: &str) {
+    let _ = writeln!(out, "# HELP {} {}", name, help);
+    let _ = writeln!(out, "# TYPE {} {}", name, kind);
+}
+
+/// Escape a label value
+fn escape(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n")
+}
+
+/// Kernel directories (those with a conceptkernel.yaml), sorted by name
+fn list_kernel_dirs(root: &Path) -> Vec<(String, PathBuf)> {
+    let mut kernels: Vec<(String, PathBuf)> = read_dirs(&root.join("concepts"))
+        .into_iter()
+        .filter(|dir| dir.join("conceptkernel.yaml").exists())
+        .filter_map(|dir| Some((dir.file_name()?.to_string_lossy().to_string(), dir)))
+        .filter(|(name, _)| !name.starts_with('.'))
+        .collect();
+    kernels.sort();
+    kernels
+}
+
+fn read_dirs(dir: &Path) -> Vec<PathBuf> {
+    fs::read_dir(dir)
+        .map(|entries| entries.filter_map(|e| e.ok()).map(|e| e.path()).filter(|p| p.is_dir()).collect())
+        .unwrap_or_default()
+}
+
+fn count_entries(dir: &Path) -> usize {
+    fs::read_dir(dir)
+        .map(|entries| entries.filter_map(|e| e.ok()).filter(|e| e.file_name() != ".gitkeep").count())
+        .unwrap_or(0)
+}
+
+/// Instance count and total bytes of a storage directory
+fn storage_usage(storage: &Path) -> (usize, u64) {
+    let instances = read_dirs(storage)
+        .iter()
+        .filter(|dir| dir.extension().is_some_and(|ext| ext == "inst"))
+        .count();
+
+    let bytes = walkdir::WalkDir::new(storage)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file())
+        .filter_map(|e| e.metadata().ok())
+        .map(|m| m.len())
+        .sum();
+
+    (instances, bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Read;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_render_and_serve_metrics() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path().join("shop");
+        let kernel_dir = root.join("concepts/Shop.Orders");
+        fs::create_dir_all(kernel_dir.join("queue/inbox")).unwrap();
+        fs::create_dir_all(kernel_dir.join("queue/edges/PRODUCES.Shop.Cart/tx-1.inst")).unwrap();
+        fs::create_dir_all(kernel_dir.join("storage/tx-2.inst")).unwrap();
+        fs::write(kernel_dir.join("conceptkernel.yaml"), "kind: Ontology\n").unwrap();
+        fs::write(kernel_dir.join("queue/inbox/job-1.job"), "{}").unwrap();
+        fs::write(kernel_dir.join("storage/tx-2.inst/receipt.bin"), "0123456789").unwrap();
+
+        let metrics = Arc::new(DaemonMetrics::new());
+        assert_eq!(metrics.register_project(&root), "shop");
+
+        metrics.record_routed("shop", "PRODUCES", "Shop.Cart", "Shop.Orders", Duration::from_millis(20));
+        metrics.record_routed("shop", "PRODUCES", "Shop.Cart", "Shop.Orders", Duration::from_secs(30));
+        metrics.record_error("shop", "PRODUCES", "Shop.Cart", "Shop.\"Quoted\"");
+        assert_eq!(metrics.routed_count("shop", "PRODUCES", "Shop.Cart", "Shop.Orders"), 2);
+
+        let decisions = DecisionCache::for_project(&root);
+        for _ in 0..2 {
+            decisions
+                .decide("Shop.Cart", "ckp://Shop.Orders", "emit", &[kernel_dir.join("conceptkernel.yaml")], || Ok(true))
+                .unwrap();
+        }
+
+        let edge = "project=\"shop\",predicate=\"PRODUCES\",source=\"Shop.Cart\",target=\"Shop.Orders\"";
+        let text = metrics.render();
+        assert!(text.contains(&format!("ckp_routed_jobs_total{{{}}} 2", edge)));
+        assert!(text.contains("target=\"Shop.\\\"Quoted\\\"\"} 1"));
+        assert!(text.contains(&format!("ckp_edge_latency_seconds_bucket{{{},le=\"0.01\"}} 0", edge)));
+        assert!(text.contains(&format!("ckp_edge_latency_seconds_bucket{{{},le=\"0.025\"}} 1", edge)));
+        assert!(text.contains(&format!("ckp_edge_latency_seconds_bucket{{{},le=\"10\"}} 1", edge)));
+        assert!(text.contains(&format!("ckp_edge_latency_seconds_bucket{{{},le=\"+Inf\"}} 2", edge)));
+        assert!(text.contains("ckp_queue_depth{project=\"shop\",kernel=\"Shop.Orders\",queue=\"inbox\"} 1"));
+        assert!(text.contains("ckp_queue_depth{project=\"shop\",kernel=\"Shop.Orders\",queue=\"edges\"} 1"));
+        assert!(text.contains("ckp_storage_instances{project=\"shop\",kernel=\"Shop.Orders\"} 1"));
+        assert!(text.contains("ckp_storage_bytes{project=\"shop\",kernel=\"Shop.Orders\"} 10"));
+        assert!(text.contains("ckp_authorization_cache_hits_total{project=\"shop\",kernel=\"Shop.Cart\"} 1"));
+        assert!(text.contains("ckp_authorization_cache_misses_total{project=\"shop\",kernel=\"Shop.Cart\"} 1"));
+
+        let server = MetricsServer::start("127.0.0.1:0".parse().unwrap(), metrics.clone()).unwrap();
+        let mut stream = TcpStream::connect(server.local_addr()).unwrap();
+        stream.write_all(b"GET /metrics HTTP/1.1\r\nHost: localhost\r\n\r\n").unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        assert!(response.starts_with("HTTP/1.1 200 OK\r\n"));
+        assert!(response.contains("# TYPE ckp_edge_latency_seconds histogram"));
+        server.stop();
+    }
+}
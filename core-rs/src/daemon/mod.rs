@@ -7,6 +7,77 @@
 // By extracting to library, we enable shared dependencies in single binary
 // for reduced container size (21MB → 7-10MB target).
 
+pub mod control;
+pub mod dedup;
+pub mod disposition_evaluator;
 pub mod edge_router;
+pub mod gateway;
+pub mod metrics;
+pub mod partition;
+pub mod protocol_mapper;
+pub mod queue_alarms;
+pub mod replication;
+pub mod retention;
+pub mod routing_table;
+pub mod supervisor;
 
+pub use control::{drain_queue, ControlCommand, ControlResponse, LogLevel};
+#[cfg(unix)]
+pub use control::{send_command, ControlServer};
+pub use dedup::{DedupWindow, DuplicatePolicy};
+pub use disposition_evaluator::{DispositionEvaluatorDaemon, RealizedDisposition};
 pub use edge_router::EdgeRouterDaemon;
+pub use gateway::{GatewayDaemon, GatewayRequest, GatewayResponse};
+pub use metrics::{DaemonMetrics, MetricsServer};
+pub use partition::{LeaseState, Partition, PartitionLease, PartitionLeaseHolder};
+pub use protocol_mapper::ProtocolMapper;
+pub use queue_alarms::{AlarmState, QueueAlarmDaemon, QueueAlarmEvent};
+pub use replication::{promote, ReplicatedKernel, ReplicationDaemon, Promotion};
+pub use retention::{RetentionAction, RetentionDaemon, RetentionMode, RetentionOutcome};
+pub use routing_table::{Invalidation, RoutingTable, RoutingTableStats};
+pub use supervisor::{DaemonSpec, DaemonSupervisor, SupervisedProject, SupervisorConfig, TaskStatus};
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+
+/// How often a sleeping daemon checks its shutdown flag
+const SHUTDOWN_POLL: Duration = Duration::from_millis(50);
+
+/// Sleep for `duration`, waking early once `shutdown` is set
+///
+/// Sleeps in short slices so polling daemons and worker threads observe
+/// shutdown promptly even with long intervals.
+///
+/// # Returns
+/// true if shutdown was requested
+pub fn sleep_until_shutdown(duration: Duration, shutdown: &AtomicBool) -> bool {
+    let deadline = Instant::now() + duration;
+    loop {
+        if shutdown.load(Ordering::SeqCst) {
+            return true;
+        }
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        if remaining.is_zero() {
+            return false;
+        }
+        std::thread::sleep(remaining.min(SHUTDOWN_POLL));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sleep_until_shutdown_wakes_on_shutdown() {
+        let shutdown = AtomicBool::new(false);
+        let started = Instant::now();
+        assert!(!sleep_until_shutdown(Duration::from_millis(120), &shutdown));
+        assert!(started.elapsed() >= Duration::from_millis(120));
+
+        shutdown.store(true, Ordering::SeqCst);
+        let started = Instant::now();
+        assert!(sleep_until_shutdown(Duration::from_secs(60), &shutdown));
+        assert!(started.elapsed() < Duration::from_secs(1));
+    }
+}
@@ -0,0 +1,336 @@
+// Edge Router Partitioning - Split kernels across router instances
+//
+// Responsibilities:
+// - Assign every kernel to one partition by a stable hash of its name
+// - Hold a lease file per partition so only one router routes it at a time
+// - Let standby routers take over a partition once its lease expires
+//
+// Lease files live under .ckrouter/ in the project root. The holder renews
+// its lease while running; every read-modify-write of a lease happens under
+// an exclusive .lock file so two routers cannot both take the same lease.
+
+use crate::errors::{CkpError, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// Directory holding partition lease files, relative to the project root
+pub const LEASE_DIR: &str = ".ckrouter";
+
+/// Default lifetime of a partition lease
+pub const DEFAULT_LEASE_TTL: Duration = Duration::from_secs(30);
+
+// Lock files older than this were left behind by a crashed router
+const LOCK_STALE_AFTER: Duration = Duration::from_secs(5);
+const LOCK_WAIT: Duration = Duration::from_secs(2);
+
+/// Share of the kernels routed by one router instance
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Partition {
+    pub index: u32,
+    pub count: u32,
+}
+
+impl Partition {
+    /// Create a partition
+    ///
+    /// # Errors
+    /// - Count is zero or index is not below count
+    pub fn new(index: u32, count: u32) -> Result<Self> {
+        if count == 0 || index >= count {
+            return Err(CkpError::ValidationError(format!(
+                "Invalid partition {}/{}: index must be below a non-zero count",
+                index, count
+            )));
+        }
+        Ok(Self { index, count })
+    }
+
+    /// Partition index a kernel belongs to out of `count`
+    ///
+    /// Uses FNV-1a so every router computes the same assignment regardless
+    /// of platform or toolchain.
+    pub fn of(kernel: &str, count: u32) -> u32 {
+        let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+        for byte in kernel.as_bytes() {
+            hash ^= u64::from(*byte);
+            hash = hash.wrapping_mul(0x0000_0100_0000_01b3);
+        }
+        (hash % u64::from(count.max(1))) as u32
+    }
+
+    /// Whether this partition routes the kernel
+    pub fn owns(&self, kernel: &str) -> bool {
+        Self::of(kernel, self.count) == self.index
+    }
+
+    /// Lease file for this partition
+    pub fn lease_path(&self, root: &Path) -> PathBuf {
+        root.join(LEASE_DIR)
+            .join(format!("partition-{}-of-{}.lease", self.index, self.count))
+    }
+}
+
+impl std::fmt::Display for Partition {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}/{}", self.index, self.count)
+    }
+}
+
+impl std::str::FromStr for Partition {
+    type Err = CkpError;
+
+    /// Parse "<index>/<count>", e.g. "0/3"
+    fn from_str(s: &str) -> Result<Self> {
+        let invalid = || CkpError::ParseError(format!("Invalid partition '{}' (expected <index>/<count>)", s));
+        let (index, count) = s.split_once('/').ok_or_else(invalid)?;
+        let index = index.trim().parse().map_err(|_| invalid())?;
+        let count = count.trim().parse().map_err(|_| invalid())?;
+        Self::new(index, count)
+    }
+}
+
+/// Contents of a partition lease file
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PartitionLease {
+    pub owner: String,
+    pub acquired_at: DateTime<Utc>,
+    /// Last renewal; instances older than this were handled by the holder
+    pub renewed_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+}
+
+impl PartitionLease {
+    /// Whether the lease has run out
+    pub fn is_expired(&self) -> bool {
+        self.expires_at <= Utc::now()
+    }
+}
+
+/// Outcome of refreshing a partition lease
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LeaseState {
+    /// Lease newly taken, replacing the expired lease of another router if any
+    Acquired { previous: Option<PartitionLease> },
+    /// Lease already held and extended
+    Renewed,
+    /// Another router holds the lease
+    Standby { holder: String },
+}
+
+/// A router's claim on one partition
+pub struct PartitionLeaseHolder {
+    partition: Partition,
+    path: PathBuf,
+    owner: String,
+    ttl: Duration,
+    held: bool,
+}
+
+impl PartitionLeaseHolder {
+    /// Create a holder for a partition of a project; nothing is written yet
+    pub fn new(root: &Path, partition: Partition, ttl: Duration) -> Self {
+        let host = sysinfo::System::host_name().unwrap_or_else(|| "localhost".to_string());
+        Self {
+            partition,
+            path: partition.lease_path(root),
+            owner: format!("{}:{}:{}", host, std::process::id(), &uuid::Uuid::new_v4().simple().to_string()[..8]),
+            ttl,
+            held: false,
+        }
+    }
+
+    /// Partition the lease covers
+    pub fn partition(&self) -> Partition {
+        self.partition
+    }
+
+    /// Identity written to the lease file
+    pub fn owner(&self) -> &str {
+        &self.owner
+    }
+
+    /// Lease lifetime
+    pub fn ttl(&self) -> Duration {
+        self.ttl
+    }
+
+    /// Whether the last refresh left this router holding the lease
+    pub fn is_held(&self) -> bool {
+        self.held
+    }
+
+    /// Take or renew the lease
+    ///
+    /// The lease is taken when the file is missing, expired or already ours.
+    ///
+    /// # Errors
+    /// - Lease file cannot be read, parsed or written
+    /// - Lock could not be taken in time
+    pub fn refresh(&mut self) -> Result<LeaseState> {
+        let path = self.path.clone();
+        let state = with_lock(&path, || {
+            let now = Utc::now();
+            let current = read_lease(&path)?;
+
+            let (acquired_at, state) = match current {
+                Some(lease) if lease.owner == self.owner => (lease.acquired_at, LeaseState::Renewed),
+                Some(lease) if !lease.is_expired() => {
+                    return Ok(LeaseState::Standby { holder: lease.owner });
+                }
+                previous => (now, LeaseState::Acquired { previous }),
+            };
+
+            let ttl = chrono::Duration::from_std(self.ttl)
+                .map_err(|e| CkpError::ValidationError(format!("Invalid lease TTL: {}", e)))?;
+            write_lease(&path, &PartitionLease {
+                owner: self.owner.clone(),
+                acquired_at,
+                renewed_at: now,
+                expires_at: now + ttl,
+            })?;
+            Ok(state)
+        })?;
+
+        self.held = !matches!(state, LeaseState::Standby { .. });
+        Ok(state)
+    }
+
+    /// Give up the lease so a standby router can take over immediately
+    ///
+    /// The lease is expired rather than removed, so the next holder still
+    /// knows up to when instances were handled.
+    pub fn release(&mut self) -> Result<()> {
+        if !self.held {
+            return Ok(());
+        }
+        self.held = false;
+
+        let path = self.path.clone();
+        with_lock(&path, || {
+            if let Some(mut lease) = read_lease(&path)?.filter(|lease| lease.owner == self.owner) {
+                let now = Utc::now();
+                lease.renewed_at = now;
+                lease.expires_at = now;
+                write_lease(&path, &lease)?;
+            }
+            Ok(())
+        })
+    }
+}
+
+impl Drop for PartitionLeaseHolder {
+    fn drop(&mut self) {
+        if let Err(e) = self.release() {
+            tracing::warn!(path = %self.path.display(), error = %e, "failed to release partition lease");
+        }
+    }
+}
+
+/// Read a partition lease file, if present
+pub fn read_lease(path: &Path) -> Result<Option<PartitionLease>> {
+    match fs::read_to_string(path) {
+        Ok(content) => Ok(Some(serde_json::from_str(&content)?)),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(e.into()),
+    }
+}
+
+fn write_lease(path: &Path, lease: &PartitionLease) -> Result<()> {
+    let tmp = path.with_extension("lease.tmp");
+    fs::write(&tmp, serde_json::to_string_pretty(lease)?)?;
+    fs::rename(&tmp, path)?;
+    Ok(())
+}
+
+// Run `f` while holding an exclusive lock file next to the lease
+fn with_lock<T>(lease_path: &Path, f: impl FnOnce() -> Result<T>) -> Result<T> {
+    if let Some(parent) = lease_path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let lock_path = lease_path.with_extension("lock");
+    let started = std::time::Instant::now();
+
+    loop {
+        match fs::OpenOptions::new().write(true).create_new(true).open(&lock_path) {
+            Ok(_) => break,
+            Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => {
+                let stale = fs::metadata(&lock_path)
+                    .and_then(|m| m.modified())
+                    .ok()
+                    .and_then(|modified| modified.elapsed().ok())
+                    .is_some_and(|age| age > LOCK_STALE_AFTER);
+                if stale {
+                    let _ = fs::remove_file(&lock_path);
+                    continue;
+                }
+                if started.elapsed() > LOCK_WAIT {
+                    return Err(CkpError::IoError(format!(
+                        "Timed out waiting for lease lock {}",
+                        lock_path.display()
+                    )));
+                }
+                std::thread::sleep(Duration::from_millis(10));
+            }
+            Err(e) => return Err(e.into()),
+        }
+    }
+
+    let result = f();
+    let _ = fs::remove_file(&lock_path);
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_partition_assignment() {
+        assert!("0/3".parse::<Partition>().is_ok());
+        assert!("3/3".parse::<Partition>().is_err());
+        assert!("1-3".parse::<Partition>().is_err());
+
+        // Every kernel belongs to exactly one partition
+        let partitions: Vec<Partition> = (0..3).map(|i| Partition::new(i, 3).unwrap()).collect();
+        for kernel in ["Shop.Orders", "Shop.Payments", "System.Gateway", "Recipes.BakeCake"] {
+            assert_eq!(partitions.iter().filter(|p| p.owns(kernel)).count(), 1);
+            assert_eq!(Partition::of(kernel, 3), Partition::of(kernel, 3));
+        }
+        assert!(Partition::new(0, 1).unwrap().owns("Anything"));
+    }
+
+    #[test]
+    fn test_lease_takeover() {
+        let temp = TempDir::new().unwrap();
+        let partition = Partition::new(1, 2).unwrap();
+
+        let mut first = PartitionLeaseHolder::new(temp.path(), partition, Duration::from_millis(200));
+        let mut second = PartitionLeaseHolder::new(temp.path(), partition, Duration::from_millis(200));
+
+        assert_eq!(first.refresh().unwrap(), LeaseState::Acquired { previous: None });
+        assert_eq!(first.refresh().unwrap(), LeaseState::Renewed);
+        assert_eq!(
+            second.refresh().unwrap(),
+            LeaseState::Standby { holder: first.owner().to_string() }
+        );
+        assert!(!second.is_held());
+
+        // The standby takes over once the holder stops renewing
+        std::thread::sleep(Duration::from_millis(250));
+        match second.refresh().unwrap() {
+            LeaseState::Acquired { previous: Some(previous) } => assert_eq!(previous.owner, first.owner()),
+            other => panic!("Expected takeover, got {:?}", other),
+        }
+        assert!(matches!(first.refresh().unwrap(), LeaseState::Standby { .. }));
+
+        // Releasing expires the lease so the other router can take it at once
+        second.release().unwrap();
+        assert!(read_lease(&partition.lease_path(temp.path())).unwrap().unwrap().is_expired());
+        assert!(matches!(first.refresh().unwrap(), LeaseState::Acquired { previous: Some(_) }));
+    }
+}
@@ -0,0 +1,195 @@
+// ProtocolMapper - Executes the .ckproject protocol mappings for the gateway
+//
+// A mapping with a `path` and `target` turns inbound HTTP/webhook requests
+// into kernel emits and receipts into HTTP responses:
+//
+//   spec:
+//     protocol:
+//       - path: /hooks/github
+//         target: ckp://Ci.Builds:v1
+//         payload:
+//           repo: /body/repository/full_name
+//           event: /headers/x-github-event
+//         response:
+//           status: /data/status
+//
+// - {method} {path}         -> job emitted to `target`, payload built from
+//                              JSON pointers into {"body", "query", "headers"}
+//                              (the body itself without `payload` fields)
+// - GET      {path}/{txId}  -> receipt of the target's {txId}.inst, its body
+//                              built from JSON pointers into the receipt
+//                              (the receipt data without `response` fields)
+//
+// Bodies that are not JSON are passed as a string. A receipt that does not
+// exist yet answers 202; a receipt with `success: false` answers 502.
+
+use super::gateway::{GatewayRequest, GatewayResponse};
+use crate::errors::{CkpError, Result};
+use crate::project::{ProjectConfig, ProtocolMapping};
+use serde_json::{json, Map, Value};
+use std::collections::BTreeMap;
+use std::path::Path;
+
+/// Method of mappings that do not name one
+pub const DEFAULT_MAPPING_METHOD: &str = "POST";
+
+/// Gateway routes declared by a project's protocol mappings
+#[derive(Debug, Clone, Default)]
+pub struct ProtocolMapper {
+    mappings: Vec<ProtocolMapping>,
+}
+
+impl ProtocolMapper {
+    /// Mapper over the mappings that have a path and a target
+    pub fn new(mappings: Vec<ProtocolMapping>) -> Self {
+        let mappings = mappings
+            .into_iter()
+            .filter(|mapping| mapping.path.is_some() && mapping.target.is_some())
+            .collect();
+        Self { mappings }
+    }
+
+    /// Mappings declared in a project's .ckproject (none without one)
+    pub fn for_project(root: &Path) -> Result<Self> {
+        match ProjectConfig::load(root.join(".ckproject")) {
+            Ok(config) => Ok(Self::new(config.spec.protocol.unwrap_or_default())),
+            Err(CkpError::FileNotFound(_)) => Ok(Self::default()),
+            Err(e) => Err(e),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.mappings.is_empty()
+    }
+
+    /// Mapping receiving `method path` as an inbound emit
+    pub fn inbound(&self, method: &str, path: &str) -> Option<&ProtocolMapping> {
+        self.mappings.iter().find(|mapping| {
+            mapping.path.as_deref() == Some(path)
+                && mapping.method.as_deref().unwrap_or(DEFAULT_MAPPING_METHOD).eq_ignore_ascii_case(method)
+        })
+    }
+
+    /// Mapping and transaction of a receipt request (`GET {path}/{txId}`)
+    pub fn receipt<'a>(&self, method: &str, path: &'a str) -> Option<(&ProtocolMapping, &'a str)> {
+        if !method.eq_ignore_ascii_case("GET") {
+            return None;
+        }
+        let (prefix, tx_id) = path.rsplit_once('/')?;
+        if tx_id.is_empty() {
+            return None;
+        }
+        self.mappings
+            .iter()
+            .find(|mapping| mapping.path.as_deref() == Some(prefix))
+            .map(|mapping| (mapping, tx_id))
+    }
+
+    /// Whether any mapping serves `path`, whatever the method
+    pub fn serves(&self, path: &str) -> bool {
+        self.mappings.iter().any(|mapping| mapping.path.as_deref() == Some(path))
+    }
+
+    /// Job payload for an inbound request
+    pub fn inbound_payload(mapping: &ProtocolMapping, request: &GatewayRequest) -> Value {
+        let body = if request.body.is_empty() {
+            Value::Null
+        } else {
+            serde_json::from_slice(&request.body)
+                .unwrap_or_else(|_| Value::String(String::from_utf8_lossy(&request.body).into_owned()))
+        };
+
+        if mapping.payload.is_empty() {
+            return body;
+        }
+
+        let input = json!({
+            "body": body,
+            "query": request.query,
+            "headers": request.headers,
+        });
+        select(&input, &mapping.payload)
+    }
+
+    /// HTTP response for a transaction's receipt (`None`: not minted yet)
+    pub fn receipt_response(mapping: &ProtocolMapping, tx_id: &str, receipt: Option<&Value>) -> GatewayResponse {
+        let Some(receipt) = receipt else {
+            return GatewayResponse {
+                status: 202,
+                body: json!({ "txId": tx_id, "status": "pending" }),
+            };
+        };
+
+        let body = if mapping.response.is_empty() {
+            receipt.get("data").cloned().unwrap_or(Value::Null)
+        } else {
+            select(receipt, &mapping.response)
+        };
+        let status = match receipt.get("success") {
+            Some(Value::Bool(false)) => 502,
+            _ => 200,
+        };
+        GatewayResponse { status, body }
+    }
+}
+
+/// Object of `field -> value at pointer` (null where the pointer finds nothing)
+fn select(input: &Value, fields: &BTreeMap<String, String>) -> Value {
+    let selected: Map<String, Value> = fields
+        .iter()
+        .map(|(field, pointer)| (field.clone(), input.pointer(pointer).cloned().unwrap_or(Value::Null)))
+        .collect();
+    Value::Object(selected)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_mappings_transform_requests_and_receipts() {
+        let mapping = ProtocolMapping {
+            path: Some("/hooks/github".to_string()),
+            target: Some("ckp://Ci.Builds:v1".to_string()),
+            payload: BTreeMap::from([
+                ("repo".to_string(), "/body/repository/full_name".to_string()),
+                ("event".to_string(), "/headers/x-github-event".to_string()),
+            ]),
+            response: BTreeMap::from([("status".to_string(), "/data/status".to_string())]),
+            ..Default::default()
+        };
+        let resolution_only = ProtocolMapping {
+            domain: "ckp.example".to_string(),
+            url: "https://ckp.example".to_string(),
+            ..Default::default()
+        };
+        let mapper = ProtocolMapper::new(vec![mapping.clone(), resolution_only]);
+
+        assert!(mapper.inbound("post", "/hooks/github").is_some());
+        assert!(mapper.inbound("GET", "/hooks/github").is_none());
+        assert!(mapper.serves("/hooks/github"));
+        assert_eq!(mapper.receipt("GET", "/hooks/github/1000-ab12cd34").map(|(_, tx)| tx), Some("1000-ab12cd34"));
+        assert!(mapper.receipt("GET", "/hooks/github/").is_none());
+
+        let request = GatewayRequest {
+            method: "POST".to_string(),
+            path: "/hooks/github".to_string(),
+            query: HashMap::new(),
+            headers: HashMap::from([("x-github-event".to_string(), "push".to_string())]),
+            body: br#"{"repository": {"full_name": "ck/core"}}"#.to_vec(),
+        };
+        assert_eq!(ProtocolMapper::inbound_payload(&mapping, &request), json!({"repo": "ck/core", "event": "push"}));
+
+        // Without payload fields the body is passed through, as a string if not JSON
+        let raw = GatewayRequest { body: b"a=1&b=2".to_vec(), ..request };
+        assert_eq!(ProtocolMapper::inbound_payload(&ProtocolMapping::default(), &raw), json!("a=1&b=2"));
+
+        let pending = ProtocolMapper::receipt_response(&mapping, "tx-1", None);
+        assert_eq!((pending.status, pending.body["status"].as_str()), (202, Some("pending")));
+        let done = ProtocolMapper::receipt_response(&mapping, "tx-1", Some(&json!({"success": true, "data": {"status": "green"}})));
+        assert_eq!((done.status, done.body), (200, json!({"status": "green"})));
+        let failed = ProtocolMapper::receipt_response(&ProtocolMapping::default(), "tx-1", Some(&json!({"success": false, "data": {"error": "x"}})));
+        assert_eq!((failed.status, failed.body), (502, json!({"error": "x"})));
+    }
+}
@@ -0,0 +1,329 @@
+// QueueAlarmDaemon - Edge queue depth alarms
+//
+// Responsibilities:
+// - Periodically read the alarms each kernel declares in
+//   `spec.queue_contract.alarms` of its conceptkernel.yaml
+// - Count the queued entries of every alarmed edge (queue/edges/{edge})
+// - Raise an alarm when the depth reaches its threshold, and recover it
+//   when the depth drops below `recover_below` (default: the threshold)
+// - Emit an alarm job to the alarm's target kernel, or write an entry to
+//   the System.Audit log when the alarm has no target
+//
+// Alarms are edge-triggered like dispositions: a raised alarm notifies once
+// until it recovers. Raised alarms are kept in the kernel's
+// .queue-alarms.json, so a restarted daemon does not raise them again.
+
+use super::retention::AUDIT_KERNEL;
+use super::sleep_until_shutdown;
+use crate::compliance::AuditLogger;
+use crate::drivers::{FileSystemDriver, JobFileBuilder, StorageDriver};
+use crate::errors::Result;
+use crate::ontology::{EdgeAlarm, OntologyReader};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Default polling interval
+pub const DEFAULT_ALARM_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Raised alarms of a kernel (in the kernel directory)
+pub const ALARM_STATE_FILE: &str = ".queue-alarms.json";
+
+/// Alarm transition
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum AlarmState {
+    /// Depth reached the threshold
+    Raised,
+
+    /// Depth dropped below the recovery depth
+    Recovered,
+}
+
+impl AlarmState {
+    /// Audit operation recording the transition
+    pub fn operation(&self) -> &'static str {
+        match self {
+            AlarmState::Raised => "queue.alarm.raised",
+            AlarmState::Recovered => "queue.alarm.recovered",
+        }
+    }
+}
+
+/// An alarm that was raised or recovered during an evaluation pass
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QueueAlarmEvent {
+    /// Kernel owning the edge queue
+    pub kernel: String,
+
+    /// Edge queue name (e.g., "PRODUCES.Orders.Intake")
+    pub edge: String,
+
+    pub alarm: AlarmState,
+
+    /// Observed queue depth
+    pub depth: u64,
+
+    pub threshold: u64,
+
+    /// Kernel the alarm job was emitted to (None: written to the audit log)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub target: Option<String>,
+
+    /// Transaction ID of the alarm job
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tx_id: Option<String>,
+}
+
+/// Raised alarm, as persisted in ALARM_STATE_FILE
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RaisedAlarm {
+    depth: u64,
+    raised_at: String,
+}
+
+pub struct QueueAlarmDaemon {
+    root: PathBuf,
+    reader: OntologyReader,
+    interval: Duration,
+    verbose: bool,
+}
+
+impl QueueAlarmDaemon {
+    /// Create a daemon for a project root
+    pub fn new(root: PathBuf, verbose: bool) -> Self {
+        Self {
+            reader: OntologyReader::new(root.clone()),
+            root,
+            interval: DEFAULT_ALARM_INTERVAL,
+            verbose,
+        }
+    }
+
+    /// Set polling interval
+    pub fn with_interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    pub fn start(&self, shutdown: Arc<AtomicBool>) -> Result<()> {
+        if self.verbose {
+            tracing::info!(project = %self.root.display(), "queue alarms starting");
+        }
+
+        while !shutdown.load(Ordering::SeqCst) {
+            if let Err(e) = self.evaluate_once() {
+                tracing::error!(error = %e, "queue alarm evaluation failed");
+            }
+
+            sleep_until_shutdown(self.interval, &shutdown);
+        }
+
+        if self.verbose {
+            tracing::info!("queue alarms shutting down");
+        }
+        Ok(())
+    }
+
+    /// Run a single evaluation pass over every kernel's edge alarms
+    ///
+    /// Kernels whose conceptkernel.yaml cannot be read are skipped with a warning.
+    ///
+    /// # Returns
+    /// Alarms raised or recovered during this pass
+    pub fn evaluate_once(&self) -> Result<Vec<QueueAlarmEvent>> {
+        let concepts_dir = self.root.join("concepts");
+        let mut kernels: Vec<String> = match fs::read_dir(&concepts_dir) {
+            Ok(entries) => entries
+                .flatten()
+                .filter(|entry| entry.path().join("conceptkernel.yaml").is_file())
+                .filter_map(|entry| entry.file_name().to_str().map(String::from))
+                .collect(),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e.into()),
+        };
+        kernels.sort();
+
+        let mut events = Vec::new();
+        for kernel in kernels {
+            let alarms = match self.reader.read_queue_contract(&kernel) {
+                Ok(contract) => contract.and_then(|contract| contract.alarms).unwrap_or_default(),
+                Err(e) => {
+                    tracing::warn!(kernel = %kernel, error = %e, "skipping kernel with unreadable queue contract");
+                    continue;
+                }
+            };
+            events.extend(self.evaluate_kernel(&kernel, &alarms)?);
+        }
+
+        Ok(events)
+    }
+
+    fn evaluate_kernel(&self, kernel: &str, alarms: &[EdgeAlarm]) -> Result<Vec<QueueAlarmEvent>> {
+        let kernel_dir = self.root.join("concepts").join(kernel);
+        let previous = read_state(&kernel_dir);
+        if alarms.is_empty() && previous.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let driver = FileSystemDriver::new(self.root.clone(), kernel.to_string());
+        let mut raised = BTreeMap::new();
+        let mut events = Vec::new();
+
+        for alarm in alarms {
+            let depth = driver.count_queue_files(&kernel_dir.join("queue/edges").join(&alarm.edge))? as u64;
+            let state = match previous.get(&alarm.edge) {
+                Some(existing) if depth >= alarm.recovery_depth() => {
+                    raised.insert(alarm.edge.clone(), existing.clone());
+                    continue;
+                }
+                Some(_) => AlarmState::Recovered,
+                None if depth >= alarm.threshold => {
+                    raised.insert(alarm.edge.clone(), RaisedAlarm {
+                        depth,
+                        raised_at: chrono::Utc::now().to_rfc3339(),
+                    });
+                    AlarmState::Raised
+                }
+                None => continue,
+            };
+            events.push(self.notify(kernel, alarm, state, depth)?);
+        }
+
+        // Alarms removed from the contract are dropped without notification
+        if raised != previous {
+            write_state(&kernel_dir, &raised)?;
+        }
+        Ok(events)
+    }
+
+    fn notify(&self, kernel: &str, alarm: &EdgeAlarm, state: AlarmState, depth: u64) -> Result<QueueAlarmEvent> {
+        let mut event = QueueAlarmEvent {
+            kernel: kernel.to_string(),
+            edge: alarm.edge.clone(),
+            alarm: state,
+            depth,
+            threshold: alarm.threshold,
+            target: alarm.target.clone(),
+            tx_id: None,
+        };
+
+        match &alarm.target {
+            Some(target) => {
+                let job = JobFileBuilder::new(target.clone(), serde_json::to_value(&event)?)
+                    .with_source(kernel, format!("ckp://{}", kernel))
+                    .build()?;
+                let driver = FileSystemDriver::new(self.root.clone(), kernel.to_string());
+                event.tx_id = Some(driver.write_job(target, job)?);
+            }
+            None => {
+                let audit_log = self.root.join("concepts").join(AUDIT_KERNEL).join("storage/audit.log");
+                AuditLogger::new(audit_log).log_operation(state.operation(), None, serde_json::to_value(&event)?)?;
+            }
+        }
+
+        if self.verbose {
+            tracing::info!(
+                kernel,
+                edge = %alarm.edge,
+                depth,
+                threshold = alarm.threshold,
+                tx_id = event.tx_id.as_deref(),
+                operation = state.operation(),
+                "queue alarm changed state"
+            );
+        }
+        Ok(event)
+    }
+}
+
+/// Raised alarms of a kernel by edge (none if the state is missing or unreadable)
+fn read_state(kernel_dir: &Path) -> BTreeMap<String, RaisedAlarm> {
+    fs::read_to_string(kernel_dir.join(ALARM_STATE_FILE))
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+fn write_state(kernel_dir: &Path, raised: &BTreeMap<String, RaisedAlarm>) -> Result<()> {
+    let path = kernel_dir.join(ALARM_STATE_FILE);
+    if raised.is_empty() {
+        let _ = fs::remove_file(path);
+        return Ok(());
+    }
+    fs::write(path, serde_json::to_string_pretty(raised)?)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_alarm_raised_once_then_recovered() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path().to_path_buf();
+        let kernel_dir = root.join("concepts/Orders.Intake");
+        let edge_dir = kernel_dir.join("queue/edges/PRODUCES.Orders.Source");
+        fs::create_dir_all(&edge_dir).unwrap();
+        fs::write(kernel_dir.join("conceptkernel.yaml"), r#"
+apiVersion: conceptkernel/v1
+kind: Ontology
+metadata:
+  name: Orders.Intake
+  type: node:cold
+spec:
+  queue_contract:
+    alarms:
+      - edge: PRODUCES.Orders.Source
+        threshold: 3
+        recover_below: 2
+        target: Ops.Pager
+      - edge: REQUIRES.Orders.Stock
+        threshold: 1
+"#).unwrap();
+
+        for i in 0..3 {
+            fs::write(edge_dir.join(format!("100{}-ab12cd34.inst", i)), "").unwrap();
+        }
+        // Dedup markers are not queued entries
+        fs::write(edge_dir.join("1000-ab12cd34.inst.duplicate"), "").unwrap();
+
+        let daemon = QueueAlarmDaemon::new(root.clone(), false);
+        let raised = daemon.evaluate_once().unwrap();
+        assert_eq!(raised.len(), 1);
+        assert_eq!((raised[0].alarm, raised[0].depth), (AlarmState::Raised, 3));
+        let job_path = root.join("concepts/Ops.Pager/queue/inbox").join(format!("{}.job", raised[0].tx_id.as_ref().unwrap()));
+        let job: serde_json::Value = serde_json::from_str(&fs::read_to_string(job_path).unwrap()).unwrap();
+        assert_eq!(job["payload"]["alarm"], "raised");
+        assert_eq!(job["payload"]["edge"], "PRODUCES.Orders.Source");
+
+        // Still raised, also for a restarted daemon
+        assert!(QueueAlarmDaemon::new(root.clone(), false).evaluate_once().unwrap().is_empty());
+
+        // Above the recovery depth: stays raised
+        fs::remove_file(edge_dir.join("1000-ab12cd34.inst")).unwrap();
+        assert!(daemon.evaluate_once().unwrap().is_empty());
+
+        fs::remove_file(edge_dir.join("1001-ab12cd34.inst")).unwrap();
+        let recovered = daemon.evaluate_once().unwrap();
+        assert_eq!((recovered[0].alarm, recovered[0].depth), (AlarmState::Recovered, 1));
+        assert!(!kernel_dir.join(ALARM_STATE_FILE).exists());
+
+        // Without a target the alarm goes to the audit log
+        let stock_dir = kernel_dir.join("queue/edges/REQUIRES.Orders.Stock");
+        fs::create_dir_all(&stock_dir).unwrap();
+        fs::write(stock_dir.join("1003-ab12cd34.inst"), "").unwrap();
+        let audited = daemon.evaluate_once().unwrap();
+        assert_eq!((audited[0].edge.as_str(), audited[0].tx_id.as_deref()), ("REQUIRES.Orders.Stock", None));
+        let audit_log = fs::read_to_string(root.join("concepts/System.Audit/storage/audit.log")).unwrap();
+        assert!(audit_log.contains("queue.alarm.raised"));
+    }
+}
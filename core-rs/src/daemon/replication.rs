@@ -0,0 +1,386 @@
+// ReplicationDaemon - Warm-standby replication of a concepts tree
+//
+// Responsibilities:
+// - Tail every kernel's transaction log (segments through tx.jsonl) and its
+//   storage/ instances in the primary project
+// - Apply what is new to a standby tree through a StorageDriver: records
+//   with `record_transaction`, instances with `mint_storage_artifact`
+// - Remember per kernel how far replication got (.ckreplication/state.json
+//   in the primary), so a restarted daemon resumes where it stopped
+//
+// Only protocol data is replicated, not queues, pid files or the edge
+// router's symlinks, and nothing is read through a symlink: symlinked
+// kernels, instances and receipts are skipped, so the standby never receives
+// files from outside the primary tree. The standby is expected to hold the
+// same kernels (ontologies and tools are deployed there as on the primary).
+//
+// Instances are replicated in transaction ID order. IDs start with their
+// mint time; instances up to REORDER_WINDOW older than the newest one
+// replicated are checked again, so an instance a slow writer finished late
+// is not missed.
+//
+// On failover, `promote` marks the standby tree as the primary
+// (.ckreplication/promoted.json). Replication into a promoted tree is
+// refused, so a former primary coming back cannot overwrite it.
+
+use super::sleep_until_shutdown;
+use crate::drivers::{FileSystemDriver, StorageDriver, TxCompactor};
+use crate::errors::{CkpError, Result};
+use crate::kernel::tx_sort_key;
+use crate::storage::shard;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Default polling interval
+pub const DEFAULT_REPLICATION_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Replication state directory (in the project root)
+pub const REPLICATION_DIR: &str = ".ckreplication";
+
+/// How much older than the newest replicated instance an instance is still looked for (ms)
+pub const REORDER_WINDOW: u64 = 60_000;
+
+const STATE_FILE: &str = "state.json";
+const PROMOTED_FILE: &str = "promoted.json";
+
+/// What one pass replicated for a kernel
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReplicatedKernel {
+    pub kernel: String,
+
+    /// Transaction records applied
+    pub records: usize,
+
+    /// Storage instances applied
+    pub instances: usize,
+}
+
+/// A standby tree promoted to primary
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Promotion {
+    /// When the standby was promoted (RFC 3339)
+    pub promoted_at: String,
+
+    /// Kernel -> transaction records it held when promoted
+    pub kernels: BTreeMap<String, u64>,
+}
+
+/// How far a kernel was replicated
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct KernelCursor {
+    /// Transaction records applied (offset into the full log)
+    records: u64,
+
+    /// Newest instance applied
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    last_instance: Option<String>,
+
+    /// Instances applied within REORDER_WINDOW of `last_instance`
+    #[serde(default, skip_serializing_if = "BTreeSet::is_empty")]
+    recent_instances: BTreeSet<String>,
+}
+
+pub struct ReplicationDaemon {
+    root: PathBuf,
+    standby_root: PathBuf,
+    standby: Box<dyn StorageDriver>,
+    interval: Duration,
+    verbose: bool,
+}
+
+impl ReplicationDaemon {
+    /// Create a daemon replicating the project at `root` to the tree at `standby_root`
+    ///
+    /// The standby is written with a FileSystemDriver (e.g. a mount of the
+    /// standby host's project); `with_driver` writes through another driver.
+    pub fn new(root: PathBuf, standby_root: PathBuf, verbose: bool) -> Self {
+        Self {
+            standby: Box::new(FileSystemDriver::new(standby_root.clone(), String::new())),
+            root,
+            standby_root,
+            interval: DEFAULT_REPLICATION_INTERVAL,
+            verbose,
+        }
+    }
+
+    /// Set polling interval
+    pub fn with_interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    /// Write to the standby through `driver` (e.g. an HttpDriver to the standby host)
+    pub fn with_driver(mut self, driver: Box<dyn StorageDriver>) -> Self {
+        self.standby = driver;
+        self
+    }
+
+    pub fn start(&self, shutdown: Arc<AtomicBool>) -> Result<()> {
+        if self.verbose {
+            tracing::info!(
+                project = %self.root.display(),
+                standby = %self.standby_root.display(),
+                "replication starting"
+            );
+        }
+
+        while !shutdown.load(Ordering::SeqCst) {
+            if let Err(e) = self.replicate_once() {
+                tracing::error!(error = %e, "replication pass failed");
+            }
+
+            sleep_until_shutdown(self.interval, &shutdown);
+        }
+
+        if self.verbose {
+            tracing::info!("replication shutting down");
+        }
+        Ok(())
+    }
+
+    /// Apply everything new in the primary to the standby
+    ///
+    /// Progress is saved after each kernel, also one that failed part way,
+    /// so the next pass resumes where this one stopped.
+    ///
+    /// # Returns
+    /// Kernels that had something to replicate
+    ///
+    /// # Errors
+    /// `CkpError::ValidationError` if the standby was promoted or is the primary itself
+    pub fn replicate_once(&self) -> Result<Vec<ReplicatedKernel>> {
+        if is_promoted(&self.standby_root) {
+            return Err(CkpError::ValidationError(format!(
+                "Standby {} was promoted; refusing to replicate into it",
+                self.standby_root.display()
+            )));
+        }
+        if let (Ok(root), Ok(standby_root)) = (fs::canonicalize(&self.root), fs::canonicalize(&self.standby_root)) {
+            if root == standby_root {
+                return Err(CkpError::ValidationError("Standby is the primary project".to_string()));
+            }
+        }
+
+        let mut state = read_state(&self.root);
+        let mut replicated = Vec::new();
+        for kernel in kernel_names(&self.root)? {
+            let cursor = state.entry(kernel.clone()).or_default();
+            let result = self.replicate_kernel(&kernel, cursor);
+            write_state(&self.root, &state)?;
+
+            let (records, instances) = result?;
+            if records > 0 || instances > 0 {
+                if self.verbose {
+                    tracing::info!(kernel = %kernel, records, instances, "replicated kernel");
+                }
+                replicated.push(ReplicatedKernel { kernel, records, instances });
+            }
+        }
+        Ok(replicated)
+    }
+
+    fn replicate_kernel(&self, kernel: &str, cursor: &mut KernelCursor) -> Result<(usize, usize)> {
+        let kernel_dir = self.root.join("concepts").join(kernel);
+
+        let records = TxCompactor::new(&kernel_dir).records()?;
+        let mut applied_records = 0;
+        for record in records.into_iter().skip(cursor.records as usize) {
+            self.standby.record_transaction(kernel, record)?;
+            cursor.records += 1;
+            applied_records += 1;
+        }
+
+        let mut instances: Vec<(u64, String)> = shard::list_entries(&kernel_dir.join("storage"))
+            .into_iter()
+            .filter(|path| path.extension().is_some_and(|ext| ext == "inst") && !is_symlink(path))
+            .map(|path| tx_sort_key(&path))
+            .collect();
+        instances.sort();
+
+        let last = cursor.last_instance.as_deref().map(|name| tx_sort_key(Path::new(name)));
+        let mut applied_instances = 0;
+        for (timestamp, name) in instances {
+            let pending = match &last {
+                None => true,
+                Some(last) if (timestamp, name.clone()) > *last => true,
+                Some(last) => timestamp.saturating_add(REORDER_WINDOW) >= last.0 && !cursor.recent_instances.contains(&name),
+            };
+            if !pending {
+                continue;
+            }
+
+            let instance_dir = shard::locate(&kernel_dir.join("storage"), &name);
+            // Not fully written yet: picked up by a later pass
+            let Some(data) = read_instance(&instance_dir) else {
+                continue;
+            };
+            let instance_id = name.trim_end_matches(".inst");
+            self.standby.mint_storage_artifact(kernel, instance_id, data)?;
+            applied_instances += 1;
+
+            let newest = cursor.last_instance.as_deref().map(|newest| tx_sort_key(Path::new(newest)));
+            if newest.map_or(true, |newest| newest < (timestamp, name.clone())) {
+                cursor.last_instance = Some(name.clone());
+            }
+            cursor.recent_instances.insert(name);
+        }
+
+        // Instances older than the window are not looked for again
+        if let Some(newest) = cursor.last_instance.as_deref().map(|name| tx_sort_key(Path::new(name)).0) {
+            cursor
+                .recent_instances
+                .retain(|name| tx_sort_key(Path::new(name)).0.saturating_add(REORDER_WINDOW) >= newest);
+        }
+        Ok((applied_records, applied_instances))
+    }
+}
+
+/// Promote a standby tree to primary for failover
+///
+/// Replication into the tree is refused from then on; its kernels can be
+/// started as on any primary.
+///
+/// # Errors
+/// `CkpError::ValidationError` if the tree was already promoted
+pub fn promote(standby_root: &Path) -> Result<Promotion> {
+    if is_promoted(standby_root) {
+        return Err(CkpError::ValidationError(format!("{} was already promoted", standby_root.display())));
+    }
+
+    let mut kernels = BTreeMap::new();
+    for kernel in kernel_names(standby_root)? {
+        let records = TxCompactor::new(standby_root.join("concepts").join(&kernel)).records()?.len() as u64;
+        kernels.insert(kernel, records);
+    }
+    let promotion = Promotion { promoted_at: chrono::Utc::now().to_rfc3339(), kernels };
+
+    let dir = standby_root.join(REPLICATION_DIR);
+    fs::create_dir_all(&dir)?;
+    fs::write(dir.join(PROMOTED_FILE), serde_json::to_string_pretty(&promotion)?)?;
+    tracing::info!(standby = %standby_root.display(), "promoted standby to primary");
+    Ok(promotion)
+}
+
+/// Whether a tree was promoted to primary
+pub fn is_promoted(root: &Path) -> bool {
+    root.join(REPLICATION_DIR).join(PROMOTED_FILE).is_file()
+}
+
+/// Kernels of a project that are real directories, in name order
+fn kernel_names(root: &Path) -> Result<Vec<String>> {
+    let mut kernels: Vec<String> = match fs::read_dir(root.join("concepts")) {
+        Ok(entries) => entries
+            .flatten()
+            .filter(|entry| entry.file_type().is_ok_and(|file_type| file_type.is_dir()))
+            .filter_map(|entry| entry.file_name().to_str().map(String::from))
+            .filter(|name| !name.starts_with('.'))
+            .collect(),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e.into()),
+    };
+    kernels.sort();
+    Ok(kernels)
+}
+
+/// Receipt of an instance (payload.json, or receipt.json for driver-minted ones)
+fn read_instance(instance_dir: &Path) -> Option<Value> {
+    ["payload.json", "receipt.json"]
+        .iter()
+        .map(|file| instance_dir.join(file))
+        .find(|path| fs::symlink_metadata(path).is_ok_and(|metadata| metadata.is_file()))
+        .and_then(|path| fs::read(path).ok())
+        .and_then(|bytes| serde_json::from_slice(&bytes).ok())
+}
+
+fn is_symlink(path: &Path) -> bool {
+    fs::symlink_metadata(path).is_ok_and(|metadata| metadata.file_type().is_symlink())
+}
+
+fn read_state(root: &Path) -> BTreeMap<String, KernelCursor> {
+    fs::read_to_string(root.join(REPLICATION_DIR).join(STATE_FILE))
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+fn write_state(root: &Path, state: &BTreeMap<String, KernelCursor>) -> Result<()> {
+    let dir = root.join(REPLICATION_DIR);
+    fs::create_dir_all(&dir)?;
+    let tmp = dir.join(format!("{}.tmp", STATE_FILE));
+    fs::write(&tmp, serde_json::to_string_pretty(state)?)?;
+    fs::rename(&tmp, dir.join(STATE_FILE))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn write_kernel(root: &Path) -> PathBuf {
+        let kernel_dir = root.join("concepts/Orders.Intake");
+        fs::create_dir_all(kernel_dir.join("storage")).unwrap();
+        fs::write(
+            kernel_dir.join("conceptkernel.yaml"),
+            "apiVersion: conceptkernel/v1\nkind: Ontology\nmetadata:\n  name: ckp://Orders.Intake:v0.1\n  type: node:cold\n  version: v0.1\n",
+        )
+        .unwrap();
+        kernel_dir
+    }
+
+    fn write_instance(kernel_dir: &Path, tx_id: &str) {
+        let instance = kernel_dir.join("storage").join(format!("{}.inst", tx_id));
+        fs::create_dir_all(&instance).unwrap();
+        fs::write(instance.join("receipt.json"), format!("{{\"txId\": \"{}\"}}", tx_id)).unwrap();
+    }
+
+    #[test]
+    fn test_replicates_new_records_and_instances_until_promoted() {
+        let primary = TempDir::new().unwrap();
+        let standby = TempDir::new().unwrap();
+        let kernel_dir = write_kernel(primary.path());
+        let standby_kernel = write_kernel(standby.path());
+
+        fs::write(kernel_dir.join("tx.jsonl"), "{\"txId\":\"1000-aa\"}\n{\"txId\":\"1001-bb\"}\n").unwrap();
+        write_instance(&kernel_dir, "1700000000000-ab12cd34");
+        write_instance(&kernel_dir, "1700000001000-cd34ef56");
+        #[cfg(unix)]
+        {
+            let outside = primary.path().join("outside.inst");
+            fs::create_dir_all(&outside).unwrap();
+            fs::write(outside.join("receipt.json"), "{}").unwrap();
+            std::os::unix::fs::symlink(&outside, kernel_dir.join("storage/1700000002000-00000000.inst")).unwrap();
+        }
+
+        let daemon = ReplicationDaemon::new(primary.path().to_path_buf(), standby.path().to_path_buf(), false);
+        let first = daemon.replicate_once().unwrap();
+        assert_eq!(first, vec![ReplicatedKernel { kernel: "Orders.Intake".to_string(), records: 2, instances: 2 }]);
+        assert_eq!(TxCompactor::new(&standby_kernel).records().unwrap().len(), 2);
+        let payload = fs::read_to_string(standby_kernel.join("storage/1700000001000-cd34ef56.inst/payload.json")).unwrap();
+        assert!(payload.contains("1700000001000-cd34ef56"));
+        assert!(!standby_kernel.join("storage/1700000002000-00000000.inst").exists());
+
+        // Nothing new; then a record and an instance minted late, within the window
+        assert!(daemon.replicate_once().unwrap().is_empty());
+        fs::write(kernel_dir.join("tx.jsonl"), "{\"txId\":\"1000-aa\"}\n{\"txId\":\"1001-bb\"}\n{\"txId\":\"1002-cc\"}\n").unwrap();
+        write_instance(&kernel_dir, "1700000000500-ef56ab12");
+        let second = ReplicationDaemon::new(primary.path().to_path_buf(), standby.path().to_path_buf(), false)
+            .replicate_once()
+            .unwrap();
+        assert_eq!((second[0].records, second[0].instances), (1, 1));
+
+        let promotion = promote(standby.path()).unwrap();
+        assert_eq!(promotion.kernels["Orders.Intake"], 3);
+        assert!(matches!(daemon.replicate_once(), Err(CkpError::ValidationError(_))));
+        assert!(promote(standby.path()).is_err());
+    }
+}
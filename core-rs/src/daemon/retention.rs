@@ -0,0 +1,384 @@
+// RetentionDaemon - Scheduled data retention enforcement
+//
+// Responsibilities:
+// - Run each project's RetentionPolicy on a fixed interval
+// - Archive expired files, then delete the originals once the archive exists
+// - Support dry-run (report only) and approval (operator confirms a pending
+//   list before anything is touched) modes
+// - Write an audit entry for every action to the project's System.Audit log
+//
+// Approval flow: each pass writes the expired files to retention.pending.json;
+// `RetentionDaemon::approve` promotes that list to retention.approved.json, and
+// the next pass enforces approved files that are still expired.
+
+use super::sleep_until_shutdown;
+use crate::compliance::{AuditLogger, RetentionPolicy};
+use crate::errors::{CkpError, Result};
+use crate::project::{ProjectConfig, ProjectRegistry};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Default enforcement interval
+pub const DEFAULT_RETENTION_INTERVAL: Duration = Duration::from_secs(3600);
+
+/// Kernel holding the audit log and approval files
+pub const AUDIT_KERNEL: &str = "System.Audit";
+
+/// How the daemon acts on expired files
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum RetentionMode {
+    /// Report what would be archived; change nothing
+    #[default]
+    DryRun,
+
+    /// Only enforce files an operator approved
+    Approval,
+
+    /// Archive and delete immediately
+    Enforce,
+}
+
+impl std::str::FromStr for RetentionMode {
+    type Err = CkpError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "dry-run" => Ok(RetentionMode::DryRun),
+            "approval" => Ok(RetentionMode::Approval),
+            "enforce" => Ok(RetentionMode::Enforce),
+            other => Err(CkpError::ValidationError(format!(
+                "Invalid retention mode '{}' (expected dry-run, approval or enforce)",
+                other
+            ))),
+        }
+    }
+}
+
+/// What happened to an expired file
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "outcome", rename_all = "kebab-case")]
+pub enum RetentionOutcome {
+    /// Dry run: would have been archived and deleted
+    WouldArchive,
+
+    /// Awaiting operator approval
+    PendingApproval,
+
+    /// Archived to `archive` and deleted
+    Archived { archive: PathBuf },
+
+    /// Archive or delete failed
+    Failed { error: String },
+}
+
+/// A single retention action
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RetentionAction {
+    /// Project root
+    pub project: PathBuf,
+
+    /// Expired file
+    pub file: PathBuf,
+
+    pub outcome: RetentionOutcome,
+}
+
+struct ProjectRetention {
+    root: PathBuf,
+    policy: RetentionPolicy,
+    audit: AuditLogger,
+}
+
+pub struct RetentionDaemon {
+    projects: Vec<ProjectRetention>,
+    mode: RetentionMode,
+    interval: Duration,
+    verbose: bool,
+}
+
+impl RetentionDaemon {
+    /// Create a daemon with no projects
+    pub fn new(verbose: bool) -> Self {
+        Self {
+            projects: Vec::new(),
+            mode: RetentionMode::default(),
+            interval: DEFAULT_RETENTION_INTERVAL,
+            verbose,
+        }
+    }
+
+    /// Create a daemon for one project, using its `.ckproject` `spec.retention`
+    ///
+    /// # Errors
+    /// `CkpError::ValidationError` if the project has no retention section
+    pub fn for_project(project_root: PathBuf, verbose: bool) -> Result<Self> {
+        let policy = Self::project_policy(&project_root)?.ok_or_else(|| {
+            CkpError::ValidationError(format!(
+                "No spec.retention configured in {}",
+                project_root.join(".ckproject").display()
+            ))
+        })?;
+
+        Ok(Self::new(verbose).with_project(project_root, policy))
+    }
+
+    /// Create a daemon for every registered project with a retention section
+    pub fn from_registry(verbose: bool) -> Result<Self> {
+        let mut daemon = Self::new(verbose);
+
+        let mut registry = ProjectRegistry::new()?;
+        for entry in registry.list()? {
+            let root = PathBuf::from(&entry.path);
+            match Self::project_policy(&root) {
+                Ok(Some(policy)) => daemon = daemon.with_project(root, policy),
+                Ok(None) => {}
+                Err(e) => tracing::warn!(project = %entry.name, error = %e, "skipping project with unreadable retention policy"),
+            }
+        }
+
+        Ok(daemon)
+    }
+
+    /// Add a project with an explicit policy
+    ///
+    /// System.Audit is always exempt so enforcement never expires its own trail.
+    pub fn with_project(mut self, project_root: PathBuf, mut policy: RetentionPolicy) -> Self {
+        policy.add_exception(AUDIT_KERNEL.to_string());
+        let audit = AuditLogger::new(audit_dir(&project_root).join("audit.log"));
+
+        self.projects.push(ProjectRetention { root: project_root, policy, audit });
+        self
+    }
+
+    /// Set enforcement mode
+    pub fn with_mode(mut self, mode: RetentionMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Set enforcement interval
+    pub fn with_interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    /// Approve the pending list of a project for the next approval-mode pass
+    ///
+    /// # Returns
+    /// Number of files approved
+    pub fn approve(project_root: &Path) -> Result<usize> {
+        let dir = audit_dir(project_root);
+        let pending = read_file_list(&dir.join("retention.pending.json"))?;
+
+        write_file_list(&dir.join("retention.approved.json"), &pending)?;
+        fs::remove_file(dir.join("retention.pending.json")).ok();
+
+        AuditLogger::new(dir.join("audit.log")).log_operation(
+            "retention.approve",
+            None,
+            serde_json::json!({"files": pending.len()}),
+        )?;
+
+        Ok(pending.len())
+    }
+
+    pub fn start(&self, shutdown: Arc<AtomicBool>) -> Result<()> {
+        if self.verbose {
+            tracing::info!(projects = self.projects.len(), mode = ?self.mode, "retention starting");
+        }
+
+        while !shutdown.load(Ordering::SeqCst) {
+            match self.run_once() {
+                Ok(actions) if self.verbose => tracing::info!(actions = actions.len(), "retention pass complete"),
+                Ok(_) => {}
+                Err(e) => tracing::error!(error = %e, "retention pass failed"),
+            }
+
+            sleep_until_shutdown(self.interval, &shutdown);
+        }
+
+        if self.verbose {
+            tracing::info!("retention shutting down");
+        }
+        Ok(())
+    }
+
+    /// Run one enforcement pass over all projects
+    pub fn run_once(&self) -> Result<Vec<RetentionAction>> {
+        let mut actions = Vec::new();
+
+        for project in &self.projects {
+            actions.extend(self.run_project(project)?);
+        }
+
+        Ok(actions)
+    }
+
+    fn run_project(&self, project: &ProjectRetention) -> Result<Vec<RetentionAction>> {
+        let expired = project.policy.check_expired_data(project.root.join("concepts"))?.expired_files;
+        let dir = audit_dir(&project.root);
+
+        let outcomes: Vec<(PathBuf, RetentionOutcome)> = match self.mode {
+            RetentionMode::DryRun => expired.into_iter().map(|f| (f, RetentionOutcome::WouldArchive)).collect(),
+            RetentionMode::Enforce => expired.into_iter().map(|f| {
+                let outcome = enforce(&project.policy, &f);
+                (f, outcome)
+            }).collect(),
+            RetentionMode::Approval => {
+                let approved_path = dir.join("retention.approved.json");
+                let approved = read_file_list(&approved_path)?;
+                fs::remove_file(&approved_path).ok();
+
+                let mut outcomes = Vec::new();
+                let mut pending = Vec::new();
+                for file in expired {
+                    if approved.contains(&file) {
+                        let outcome = enforce(&project.policy, &file);
+                        outcomes.push((file, outcome));
+                    } else {
+                        pending.push(file.clone());
+                        outcomes.push((file, RetentionOutcome::PendingApproval));
+                    }
+                }
+
+                if pending.is_empty() {
+                    fs::remove_file(dir.join("retention.pending.json")).ok();
+                } else {
+                    write_file_list(&dir.join("retention.pending.json"), &pending)?;
+                }
+                outcomes
+            }
+        };
+
+        let mut actions = Vec::new();
+        for (file, outcome) in outcomes {
+            let operation = match &outcome {
+                RetentionOutcome::WouldArchive => "retention.dry-run",
+                RetentionOutcome::PendingApproval => "retention.pending",
+                RetentionOutcome::Archived { .. } => "retention.archive",
+                RetentionOutcome::Failed { .. } => "retention.error",
+            };
+            project.audit.log_operation(operation, None, serde_json::json!({
+                "file": file,
+                "mode": self.mode,
+                "result": outcome,
+            }))?;
+
+            if self.verbose {
+                tracing::info!(project = %project.root.display(), file = %file.display(), operation, "retention action");
+            }
+            actions.push(RetentionAction { project: project.root.clone(), file, outcome });
+        }
+
+        Ok(actions)
+    }
+
+    /// Policy from a project's `.ckproject`, if it configures retention
+    fn project_policy(project_root: &Path) -> Result<Option<RetentionPolicy>> {
+        let config = ProjectConfig::load_from_project(project_root)?;
+        Ok(config.spec.retention.map(|retention| RetentionPolicy::from_config(&retention, project_root)))
+    }
+}
+
+/// Archive then delete one file
+fn enforce(policy: &RetentionPolicy, file: &Path) -> RetentionOutcome {
+    let result = policy.archive_data(file.to_path_buf()).and_then(|archive| {
+        policy.delete_after_archive(file.to_path_buf(), archive.clone())?;
+        Ok(archive)
+    });
+
+    match result {
+        Ok(archive) => RetentionOutcome::Archived { archive },
+        Err(e) => RetentionOutcome::Failed { error: e.to_string() },
+    }
+}
+
+fn audit_dir(project_root: &Path) -> PathBuf {
+    project_root.join("concepts").join(AUDIT_KERNEL).join("storage")
+}
+
+fn read_file_list(path: &Path) -> Result<Vec<PathBuf>> {
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    Ok(serde_json::from_str(&fs::read_to_string(path)?)?)
+}
+
+fn write_file_list(path: &Path, files: &[PathBuf]) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, serde_json::to_string_pretty(files)?)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::compliance::{AuditFilters, RetentionConfig};
+    use tempfile::TempDir;
+
+    fn setup() -> (TempDir, PathBuf, PathBuf) {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path().to_path_buf();
+        let file = root.join("concepts/Orders/storage/old.json");
+        fs::create_dir_all(file.parent().unwrap()).unwrap();
+        fs::write(&file, "{}").unwrap();
+        (temp_dir, root, file)
+    }
+
+    fn policy(root: &Path) -> RetentionPolicy {
+        // 0 days: everything is expired
+        RetentionPolicy::from_config(&RetentionConfig { days: 0, archive_path: None, exceptions: Vec::new() }, root)
+    }
+
+    #[test]
+    fn test_dry_run_and_enforce() {
+        let (_temp, root, file) = setup();
+
+        let dry = RetentionDaemon::new(false).with_project(root.clone(), policy(&root));
+        let actions = dry.run_once().unwrap();
+        assert_eq!(actions.len(), 1);
+        assert_eq!(actions[0].outcome, RetentionOutcome::WouldArchive);
+        assert!(file.exists());
+
+        let enforce = RetentionDaemon::new(false)
+            .with_project(root.clone(), policy(&root))
+            .with_mode(RetentionMode::Enforce);
+        let actions = enforce.run_once().unwrap();
+        match &actions[0].outcome {
+            RetentionOutcome::Archived { archive } => assert!(archive.starts_with(root.join("concepts/.archive/retention"))),
+            other => panic!("unexpected outcome {:?}", other),
+        }
+        assert!(!file.exists());
+
+        // Audit trail itself is never expired
+        let audit = AuditLogger::new(audit_dir(&root).join("audit.log"));
+        let page = audit.query(&AuditFilters { operation_prefix: Some("retention.".to_string()), ..Default::default() }).unwrap();
+        let ops: Vec<&str> = page.entries.iter().map(|e| e.operation.as_str()).collect();
+        assert_eq!(ops, vec!["retention.dry-run", "retention.archive"]);
+    }
+
+    #[test]
+    fn test_approval_mode() {
+        let (_temp, root, file) = setup();
+        let daemon = RetentionDaemon::new(false)
+            .with_project(root.clone(), policy(&root))
+            .with_mode(RetentionMode::Approval);
+
+        let actions = daemon.run_once().unwrap();
+        assert_eq!(actions[0].outcome, RetentionOutcome::PendingApproval);
+        assert!(file.exists());
+
+        assert_eq!(RetentionDaemon::approve(&root).unwrap(), 1);
+        let actions = daemon.run_once().unwrap();
+        assert!(matches!(actions[0].outcome, RetentionOutcome::Archived { .. }));
+        assert!(!file.exists());
+        assert!(daemon.run_once().unwrap().is_empty());
+    }
+}
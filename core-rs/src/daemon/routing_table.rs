@@ -0,0 +1,205 @@
+// RoutingTable - Cached routes of the edge router
+//
+// Resolving where an instance goes reads every edgekernel.yaml under
+// concepts/.edges/, expands wildcard targets and decides RBAC for each
+// target from its ontology. The router resolves a source kernel once and
+// keeps its routes (edge -> target queue, RBAC verdict) until something they
+// were derived from changes.
+//
+// Entries are dropped by explicit invalidation events, derived from the
+// filesystem events the router already watches:
+// - concepts/{Kernel}/conceptkernel.yaml changed: routes from and to Kernel
+// - an edge created, removed or rewritten under concepts/.edges/: all routes
+// - a kernel directory created or removed (wildcard targets): all routes
+//
+// Targets in other projects are outside the watched tree; the `flush`
+// control command drops every entry after changing them.
+
+use crate::edge::EdgeRoute;
+use notify::{Event, EventKind};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::path::{Component, Path};
+
+/// What a change invalidates
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Invalidation {
+    /// Routes from and to one kernel
+    Kernel(String),
+
+    /// Every route
+    All,
+}
+
+impl Invalidation {
+    /// Invalidation caused by a change to `path` under `concepts_dir`
+    ///
+    /// Queue, storage and log churn invalidates nothing.
+    pub fn for_path(concepts_dir: &Path, path: &Path) -> Option<Self> {
+        let relative = path.strip_prefix(concepts_dir).ok()?;
+        let components: Vec<&str> = relative
+            .components()
+            .map(|component| match component {
+                Component::Normal(name) => name.to_str(),
+                _ => None,
+            })
+            .collect::<Option<_>>()?;
+
+        match components.as_slice() {
+            [".edges", _] | [".edges", _, "edgekernel.yaml"] => Some(Invalidation::All),
+            [kernel] if !kernel.starts_with('.') => Some(Invalidation::All),
+            [kernel, "conceptkernel.yaml"] if !kernel.starts_with('.') => Some(Invalidation::Kernel(kernel.to_string())),
+            _ => None,
+        }
+    }
+
+    /// Invalidations caused by a filesystem event
+    pub fn for_event(concepts_dir: &Path, event: &Event) -> Vec<Self> {
+        if !matches!(event.kind, EventKind::Create(_) | EventKind::Modify(_) | EventKind::Remove(_)) {
+            return Vec::new();
+        }
+        event
+            .paths
+            .iter()
+            .filter_map(|path| Self::for_path(concepts_dir, path))
+            .collect()
+    }
+}
+
+/// Lookup counters reported by `dump`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct RoutingTableStats {
+    pub sources: usize,
+    pub routes: usize,
+    pub hits: u64,
+    pub misses: u64,
+    pub invalidations: u64,
+}
+
+/// Resolved routes per source kernel
+#[derive(Debug, Default)]
+pub struct RoutingTable {
+    routes: HashMap<String, Vec<EdgeRoute>>,
+    hits: u64,
+    misses: u64,
+    invalidations: u64,
+}
+
+impl RoutingTable {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Cached routes of a source kernel
+    pub fn get(&mut self, source_kernel: &str) -> Option<Vec<EdgeRoute>> {
+        let routes = self.routes.get(source_kernel).cloned();
+        match routes {
+            Some(_) => self.hits += 1,
+            None => self.misses += 1,
+        }
+        routes
+    }
+
+    pub fn insert(&mut self, source_kernel: &str, routes: Vec<EdgeRoute>) {
+        self.routes.insert(source_kernel.to_string(), routes);
+    }
+
+    /// Drop the entries an invalidation covers
+    ///
+    /// # Returns
+    /// Number of source kernels dropped
+    pub fn invalidate(&mut self, invalidation: &Invalidation) -> usize {
+        let before = self.routes.len();
+        match invalidation {
+            Invalidation::Kernel(kernel) => self.routes.retain(|source, routes| {
+                source != kernel && routes.iter().all(|route| &route.target_kernel != kernel)
+            }),
+            Invalidation::All => self.routes.clear(),
+        }
+        let dropped = before - self.routes.len();
+        if dropped > 0 {
+            self.invalidations += 1;
+        }
+        dropped
+    }
+
+    /// Drop every entry
+    ///
+    /// # Returns
+    /// Number of source kernels dropped
+    pub fn flush(&mut self) -> usize {
+        self.invalidate(&Invalidation::All)
+    }
+
+    pub fn stats(&self) -> RoutingTableStats {
+        RoutingTableStats {
+            sources: self.routes.len(),
+            routes: self.routes.values().map(Vec::len).sum(),
+            hits: self.hits,
+            misses: self.misses,
+            invalidations: self.invalidations,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use notify::event::{AccessKind, CreateKind, ModifyKind};
+    use std::path::PathBuf;
+
+    fn route(target: &str) -> EdgeRoute {
+        EdgeRoute {
+            edge_urn: format!("ckp://Edge.PRODUCES.Source-to-{}:v1.3.16", target),
+            predicate: "PRODUCES".to_string(),
+            target: target.to_string(),
+            target_kernel: target.to_string(),
+            target_root: PathBuf::from("/project"),
+            queue: PathBuf::from(format!("/project/concepts/{}/queue/edges/PRODUCES.Source", target)),
+            authorized: true,
+        }
+    }
+
+    #[test]
+    fn test_invalidation_events_drop_affected_routes() {
+        let concepts = Path::new("/project/concepts");
+        let event = |kind, path: &str| Event::new(kind).add_path(concepts.join(path));
+
+        let modify = EventKind::Modify(ModifyKind::Any);
+        assert_eq!(
+            Invalidation::for_event(concepts, &event(modify, "Target/conceptkernel.yaml")),
+            vec![Invalidation::Kernel("Target".to_string())]
+        );
+        assert_eq!(
+            Invalidation::for_event(concepts, &event(modify, ".edges/PRODUCES.Source-to-Target/edgekernel.yaml")),
+            vec![Invalidation::All]
+        );
+        assert_eq!(
+            Invalidation::for_event(concepts, &event(EventKind::Create(CreateKind::Folder), "NewKernel")),
+            vec![Invalidation::All]
+        );
+        for quiet in [
+            event(EventKind::Create(CreateKind::File), ".edges/PRODUCES.Source-to-Target/queue/inbox/tx.edgereq"),
+            event(EventKind::Create(CreateKind::Folder), "Source/storage/tx.inst"),
+            event(EventKind::Access(AccessKind::Any), "Target/conceptkernel.yaml"),
+        ] {
+            assert!(Invalidation::for_event(concepts, &quiet).is_empty());
+        }
+
+        let mut table = RoutingTable::new();
+        table.insert("Source", vec![route("Target"), route("Audit")]);
+        table.insert("Other", vec![route("Audit")]);
+        table.insert("Target", vec![]);
+        assert!(table.get("Source").is_some());
+        assert!(table.get("Missing").is_none());
+
+        // A target's ontology drops the routes to it, and its own
+        assert_eq!(table.invalidate(&Invalidation::Kernel("Target".to_string())), 2);
+        assert!(table.get("Other").is_some());
+        assert_eq!(table.flush(), 1);
+        assert_eq!(
+            table.stats(),
+            RoutingTableStats { sources: 0, routes: 0, hits: 2, misses: 1, invalidations: 2 }
+        );
+    }
+}
@@ -0,0 +1,628 @@
+// DaemonSupervisor - Runs project daemons as managed tasks in one process
+//
+// Responsibilities:
+// - Start the edge router, governors, disposition evaluator (the scheduled
+//   trigger daemon), retention daemon and queue alarm daemon for every
+//   configured project
+// - Restart failed tasks with exponential backoff; a task that ran for
+//   longer than the maximum backoff before failing starts again from the
+//   initial delay
+// - Reload configuration on SIGHUP, or when the config file changes on disk,
+//   then stop removed tasks, restart changed ones and start new ones; tasks
+//   whose settings are unchanged keep running
+//
+// Configuration (YAML, `${VAR}` references are interpolated):
+//
+//   projects:
+//     - path: /srv/shop
+//       edgeRouter: true          # default
+//       governors: [Shop.Orders]
+//       dispositionsInterval: 5   # seconds, omit to disable
+//       retention: dry-run        # dry-run | approval | enforce, omit to disable
+//       retentionInterval: 3600
+//       queueAlarmsInterval: 10   # seconds, omit to disable
+//   initialBackoff: 1             # seconds
+//   maxBackoff: 60
+//
+// Without a config file every registered project runs an edge router, and
+// SIGHUP re-reads the project registry.
+//
+// Each task runs on its own thread with its own shutdown flag; async daemons
+// (governors) get a current-thread runtime on that thread.
+
+use super::{DispositionEvaluatorDaemon, EdgeRouterDaemon, QueueAlarmDaemon, RetentionDaemon, RetentionMode};
+use crate::errors::{CkpError, Result};
+use crate::interpolation::Interpolator;
+use crate::kernel::ConceptKernelGovernor;
+use crate::project::ProjectRegistry;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant, SystemTime};
+
+/// Interval between supervision passes
+pub const SUPERVISOR_TICK: Duration = Duration::from_millis(200);
+
+/// Set by the SIGHUP handler, cleared when the reload is applied
+static RELOAD_REQUESTED: AtomicBool = AtomicBool::new(false);
+
+fn default_true() -> bool {
+    true
+}
+
+fn default_initial_backoff() -> u64 {
+    1
+}
+
+fn default_max_backoff() -> u64 {
+    60
+}
+
+/// Daemons to run for one project
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SupervisedProject {
+    /// Project root
+    pub path: PathBuf,
+
+    #[serde(default = "default_true")]
+    pub edge_router: bool,
+
+    /// Kernels to run a governor for
+    #[serde(default)]
+    pub governors: Vec<String>,
+
+    /// Disposition evaluation interval in seconds (None = not run)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dispositions_interval: Option<u64>,
+
+    /// Retention mode (None = not run)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub retention: Option<RetentionMode>,
+
+    /// Retention interval in seconds (default: DEFAULT_RETENTION_INTERVAL)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub retention_interval: Option<u64>,
+
+    /// Queue alarm evaluation interval in seconds (None = not run)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub queue_alarms_interval: Option<u64>,
+}
+
+impl SupervisedProject {
+    /// Project running only the edge router
+    pub fn new(path: PathBuf) -> Self {
+        Self {
+            path,
+            edge_router: true,
+            governors: Vec::new(),
+            dispositions_interval: None,
+            retention: None,
+            retention_interval: None,
+            queue_alarms_interval: None,
+        }
+    }
+}
+
+/// Supervisor configuration
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SupervisorConfig {
+    #[serde(default)]
+    pub projects: Vec<SupervisedProject>,
+
+    /// Delay before the first restart of a failed task, in seconds
+    #[serde(default = "default_initial_backoff")]
+    pub initial_backoff: u64,
+
+    /// Upper bound for the restart delay, in seconds
+    #[serde(default = "default_max_backoff")]
+    pub max_backoff: u64,
+}
+
+impl Default for SupervisorConfig {
+    fn default() -> Self {
+        Self {
+            projects: Vec::new(),
+            initial_backoff: default_initial_backoff(),
+            max_backoff: default_max_backoff(),
+        }
+    }
+}
+
+impl SupervisorConfig {
+    /// Load a config file, resolving `${...}` references
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = fs::read_to_string(path).map_err(|e| {
+            CkpError::IoError(format!("Failed to read supervisor config {}: {}", path.display(), e))
+        })?;
+        Interpolator::current().parse_yaml(&content)
+    }
+
+    /// Edge router for every registered project
+    pub fn from_registry() -> Result<Self> {
+        let mut registry = ProjectRegistry::new()?;
+        let projects = registry
+            .list()?
+            .into_iter()
+            .map(|entry| SupervisedProject::new(PathBuf::from(entry.path)))
+            .collect();
+
+        Ok(Self { projects, ..Self::default() })
+    }
+
+    /// Every daemon the configuration asks for, keyed by task name
+    pub fn tasks(&self) -> BTreeMap<String, DaemonSpec> {
+        let mut tasks = BTreeMap::new();
+
+        for project in &self.projects {
+            let root = project.path.clone();
+            let mut specs = Vec::new();
+
+            if project.edge_router {
+                specs.push(DaemonSpec::EdgeRouter { root: root.clone() });
+            }
+            for kernel in &project.governors {
+                specs.push(DaemonSpec::Governor { root: root.clone(), kernel: kernel.clone() });
+            }
+            if let Some(interval) = project.dispositions_interval {
+                specs.push(DaemonSpec::Dispositions {
+                    root: root.clone(),
+                    interval: Duration::from_secs(interval.max(1)),
+                });
+            }
+            if let Some(mode) = project.retention {
+                specs.push(DaemonSpec::Retention {
+                    root: root.clone(),
+                    mode,
+                    interval: project
+                        .retention_interval
+                        .map(Duration::from_secs)
+                        .unwrap_or(super::retention::DEFAULT_RETENTION_INTERVAL),
+                });
+            }
+            if let Some(interval) = project.queue_alarms_interval {
+                specs.push(DaemonSpec::QueueAlarms {
+                    root: root.clone(),
+                    interval: Duration::from_secs(interval.max(1)),
+                });
+            }
+
+            for spec in specs {
+                tasks.insert(spec.name(), spec);
+            }
+        }
+
+        tasks
+    }
+
+    /// Restart delay after `failures` consecutive failures
+    pub fn backoff(&self, failures: u32) -> Duration {
+        let exponent = failures.saturating_sub(1).min(16);
+        let delay = self.initial_backoff.max(1).saturating_mul(1 << exponent);
+        Duration::from_secs(delay.min(self.max_backoff.max(1)))
+    }
+}
+
+/// A daemon managed by the supervisor
+#[derive(Debug, Clone, PartialEq)]
+pub enum DaemonSpec {
+    EdgeRouter { root: PathBuf },
+    Governor { root: PathBuf, kernel: String },
+    Dispositions { root: PathBuf, interval: Duration },
+    Retention { root: PathBuf, mode: RetentionMode, interval: Duration },
+    QueueAlarms { root: PathBuf, interval: Duration },
+}
+
+impl DaemonSpec {
+    /// Task name, unique per project and daemon
+    pub fn name(&self) -> String {
+        match self {
+            DaemonSpec::EdgeRouter { root } => format!("{}:edge-router", root.display()),
+            DaemonSpec::Governor { root, kernel } => format!("{}:governor:{}", root.display(), kernel),
+            DaemonSpec::Dispositions { root, .. } => format!("{}:dispositions", root.display()),
+            DaemonSpec::Retention { root, .. } => format!("{}:retention", root.display()),
+            DaemonSpec::QueueAlarms { root, .. } => format!("{}:queue-alarms", root.display()),
+        }
+    }
+
+    /// Run the daemon until `shutdown` is set or it fails
+    pub fn run(&self, shutdown: Arc<AtomicBool>, verbose: bool) -> std::result::Result<(), String> {
+        match self {
+            DaemonSpec::EdgeRouter { root } => EdgeRouterDaemon::new(root.clone(), verbose)
+                .and_then(|daemon| daemon.start(shutdown))
+                .map_err(|e| e.to_string()),
+            DaemonSpec::Governor { root, kernel } => {
+                let runtime = tokio::runtime::Builder::new_current_thread()
+                    .enable_all()
+                    .build()
+                    .map_err(|e| e.to_string())?;
+                runtime
+                    .block_on(async {
+                        ConceptKernelGovernor::new(kernel, root.clone())?.start(shutdown).await
+                    })
+                    .map_err(|e| e.to_string())
+            }
+            DaemonSpec::Dispositions { root, interval } => DispositionEvaluatorDaemon::new(root.clone(), verbose)
+                .with_interval(*interval)
+                .start(shutdown)
+                .map_err(|e| e.to_string()),
+            DaemonSpec::Retention { root, mode, interval } => RetentionDaemon::for_project(root.clone(), verbose)
+                .and_then(|daemon| daemon.with_mode(*mode).with_interval(*interval).start(shutdown))
+                .map_err(|e| e.to_string()),
+            DaemonSpec::QueueAlarms { root, interval } => QueueAlarmDaemon::new(root.clone(), verbose)
+                .with_interval(*interval)
+                .start(shutdown)
+                .map_err(|e| e.to_string()),
+        }
+    }
+}
+
+/// Runs a daemon spec; replaceable for embedding and tests
+pub type DaemonRunner = Arc<dyn Fn(&DaemonSpec, Arc<AtomicBool>) -> std::result::Result<(), String> + Send + Sync>;
+
+/// State of a supervised task
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskStatus {
+    pub name: String,
+    pub running: bool,
+
+    /// Consecutive failures since the task last ran stably
+    pub failures: u32,
+
+    /// Error from the last failure
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_error: Option<String>,
+}
+
+struct ManagedTask {
+    spec: DaemonSpec,
+    stop: Arc<AtomicBool>,
+    handle: Option<JoinHandle<std::result::Result<(), String>>>,
+    started_at: Instant,
+    failures: u32,
+    last_error: Option<String>,
+    restart_at: Option<Instant>,
+}
+
+pub struct DaemonSupervisor {
+    config_path: Option<PathBuf>,
+    config_modified: Option<SystemTime>,
+    config: SupervisorConfig,
+    runner: DaemonRunner,
+    tasks: BTreeMap<String, ManagedTask>,
+}
+
+impl DaemonSupervisor {
+    /// Supervise the daemons of a fixed configuration
+    pub fn new(config: SupervisorConfig, verbose: bool) -> Self {
+        Self {
+            config_path: None,
+            config_modified: None,
+            config,
+            runner: Arc::new(move |spec: &DaemonSpec, shutdown: Arc<AtomicBool>| spec.run(shutdown, verbose)),
+            tasks: BTreeMap::new(),
+        }
+    }
+
+    /// Supervise the daemons listed in a config file, reloading it when it changes
+    pub fn from_file(path: PathBuf, verbose: bool) -> Result<Self> {
+        let config = SupervisorConfig::load(&path)?;
+        let mut supervisor = Self::new(config, verbose);
+        supervisor.config_modified = modified(&path);
+        supervisor.config_path = Some(path);
+        Ok(supervisor)
+    }
+
+    /// Supervise an edge router for every registered project
+    pub fn from_registry(verbose: bool) -> Result<Self> {
+        Ok(Self::new(SupervisorConfig::from_registry()?, verbose))
+    }
+
+    /// Run tasks with a custom runner instead of the built-in daemons
+    pub fn with_runner(mut self, runner: DaemonRunner) -> Self {
+        self.runner = runner;
+        self
+    }
+
+    /// Current configuration
+    pub fn config(&self) -> &SupervisorConfig {
+        &self.config
+    }
+
+    pub fn start(&mut self, shutdown: Arc<AtomicBool>) -> Result<()> {
+        tracing::info!("supervisor starting");
+        install_reload_handler();
+
+        let config = self.config.clone();
+        self.apply(config);
+        tracing::info!(tasks = self.tasks.len(), "supervising tasks");
+
+        while !shutdown.load(Ordering::SeqCst) {
+            let config_changed = self.config_path.as_deref().is_some_and(|path| modified(path) != self.config_modified);
+
+            if RELOAD_REQUESTED.swap(false, Ordering::SeqCst) || config_changed {
+                if let Err(e) = self.reload() {
+                    tracing::warn!(error = %e, "supervisor reload failed, keeping current configuration");
+                }
+            }
+
+            self.tick();
+            std::thread::sleep(SUPERVISOR_TICK);
+        }
+
+        tracing::info!("supervisor shutting down, stopping tasks");
+        self.stop_all();
+        Ok(())
+    }
+
+    /// Re-read configuration from the config file, or the project registry
+    pub fn reload(&mut self) -> Result<()> {
+        let config = match &self.config_path {
+            Some(path) => {
+                self.config_modified = modified(path);
+                SupervisorConfig::load(path)?
+            }
+            None => SupervisorConfig::from_registry()?,
+        };
+
+        tracing::info!("supervisor configuration reloaded");
+        self.apply(config);
+        Ok(())
+    }
+
+    /// Switch to a new configuration, touching only tasks that changed
+    pub fn apply(&mut self, config: SupervisorConfig) {
+        let desired = config.tasks();
+        self.config = config;
+
+        let obsolete: Vec<String> = self
+            .tasks
+            .iter()
+            .filter(|(name, task)| desired.get(*name) != Some(&task.spec))
+            .map(|(name, _)| name.clone())
+            .collect();
+
+        for name in obsolete {
+            if let Some(mut task) = self.tasks.remove(&name) {
+                tracing::info!(task = %name, "stopping task");
+                stop_task(&mut task);
+            }
+        }
+
+        for (name, spec) in desired {
+            if !self.tasks.contains_key(&name) {
+                tracing::info!(task = %name, "starting task");
+                let task = self.spawn(spec, 0);
+                self.tasks.insert(name, task);
+            }
+        }
+    }
+
+    /// Reap finished tasks and restart those whose backoff has elapsed
+    pub fn tick(&mut self) {
+        let now = Instant::now();
+        let stable_after = Duration::from_secs(self.config.max_backoff.max(1));
+
+        let mut restarts = Vec::new();
+        for (name, task) in self.tasks.iter_mut() {
+            if let Some(restart_at) = task.restart_at {
+                if now >= restart_at {
+                    restarts.push(name.clone());
+                }
+                continue;
+            }
+
+            if task.handle.as_ref().is_some_and(|handle| !handle.is_finished()) {
+                continue;
+            }
+
+            let result = match task.handle.take() {
+                Some(handle) => handle.join().unwrap_or_else(|_| Err("task panicked".to_string())),
+                None => Err("could not spawn thread".to_string()),
+            };
+
+            // A clean return while not stopping is still unexpected for a daemon
+            let error = result.err().unwrap_or_else(|| "exited unexpectedly".to_string());
+            if task.started_at.elapsed() >= stable_after {
+                task.failures = 0;
+            }
+            task.failures += 1;
+
+            let delay = self.config.backoff(task.failures);
+            tracing::warn!(
+                task = %name,
+                error = %error,
+                restart_in_secs = delay.as_secs(),
+                failures = task.failures,
+                "task failed, restarting after backoff"
+            );
+            task.last_error = Some(error);
+            task.restart_at = Some(now + delay);
+        }
+
+        for name in restarts {
+            if let Some(old) = self.tasks.remove(&name) {
+                tracing::info!(task = %name, "restarting task");
+                let mut task = self.spawn(old.spec, old.failures);
+                task.last_error = old.last_error;
+                self.tasks.insert(name, task);
+            }
+        }
+    }
+
+    /// State of every task, sorted by name
+    pub fn status(&self) -> Vec<TaskStatus> {
+        self.tasks
+            .iter()
+            .map(|(name, task)| TaskStatus {
+                name: name.clone(),
+                running: task.restart_at.is_none() && task.handle.as_ref().is_some_and(|h| !h.is_finished()),
+                failures: task.failures,
+                last_error: task.last_error.clone(),
+            })
+            .collect()
+    }
+
+    /// Stop every task and wait for it to exit
+    pub fn stop_all(&mut self) {
+        for (_, mut task) in std::mem::take(&mut self.tasks) {
+            stop_task(&mut task);
+        }
+    }
+
+    fn spawn(&self, spec: DaemonSpec, failures: u32) -> ManagedTask {
+        let stop = Arc::new(AtomicBool::new(false));
+        let runner = self.runner.clone();
+        let (task_spec, task_stop) = (spec.clone(), stop.clone());
+
+        let handle = std::thread::Builder::new()
+            .name(format!("ckp-{}", spec.name()))
+            .spawn(move || runner(&task_spec, task_stop))
+            .ok();
+
+        ManagedTask {
+            spec,
+            stop,
+            handle,
+            started_at: Instant::now(),
+            failures,
+            last_error: None,
+            restart_at: None,
+        }
+    }
+}
+
+impl Drop for DaemonSupervisor {
+    fn drop(&mut self) {
+        self.stop_all();
+    }
+}
+
+fn stop_task(task: &mut ManagedTask) {
+    task.stop.store(true, Ordering::SeqCst);
+    if let Some(handle) = task.handle.take() {
+        let _ = handle.join();
+    }
+}
+
+fn modified(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+#[cfg(unix)]
+extern "C" fn request_reload(_: libc::c_int) {
+    RELOAD_REQUESTED.store(true, Ordering::SeqCst);
+}
+
+/// Request a reload on SIGHUP
+fn install_reload_handler() {
+    #[cfg(unix)]
+    {
+        use nix::sys::signal::{sigaction, SaFlags, SigAction, SigHandler, SigSet, Signal};
+
+        let action = SigAction::new(SigHandler::Handler(request_reload), SaFlags::SA_RESTART, SigSet::empty());
+        // SAFETY: the handler only stores to an atomic, which is async-signal-safe
+        if let Err(e) = unsafe { sigaction(Signal::SIGHUP, &action) } {
+            tracing::warn!(error = %e, "could not install SIGHUP handler");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::AtomicU32;
+    use tempfile::TempDir;
+
+    fn wait_for(mut condition: impl FnMut() -> bool) {
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while !condition() {
+            assert!(Instant::now() < deadline, "condition not reached");
+            std::thread::sleep(Duration::from_millis(10));
+        }
+    }
+
+    #[test]
+    fn test_config_tasks_and_backoff() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join("supervisor.yaml");
+        fs::write(
+            &path,
+            "projects:\n  - path: /srv/shop\n    governors: [Shop.Orders]\n    dispositionsInterval: 5\n    retention: enforce\n  - path: /srv/blog\n    edgeRouter: false\nmaxBackoff: 8\n",
+        )
+        .unwrap();
+
+        let config = SupervisorConfig::load(&path).unwrap();
+        let names: Vec<String> = config.tasks().into_keys().collect();
+        assert_eq!(
+            names,
+            vec![
+                "/srv/shop:dispositions",
+                "/srv/shop:edge-router",
+                "/srv/shop:governor:Shop.Orders",
+                "/srv/shop:retention",
+            ]
+        );
+
+        assert_eq!(config.backoff(1), Duration::from_secs(1));
+        assert_eq!(config.backoff(3), Duration::from_secs(4));
+        assert_eq!(config.backoff(10), Duration::from_secs(8));
+    }
+
+    #[test]
+    fn test_restart_with_backoff_and_apply() {
+        let runs = Arc::new(AtomicU32::new(0));
+        let counter = runs.clone();
+        let runner: DaemonRunner = Arc::new(move |spec: &DaemonSpec, shutdown: Arc<AtomicBool>| {
+            if matches!(spec, DaemonSpec::Governor { .. }) {
+                // Crashes on its first run, then stays up
+                if counter.fetch_add(1, Ordering::SeqCst) == 0 {
+                    return Err("boom".to_string());
+                }
+            }
+            while !shutdown.load(Ordering::SeqCst) {
+                std::thread::sleep(Duration::from_millis(5));
+            }
+            Ok(())
+        });
+
+        let mut project = SupervisedProject::new(PathBuf::from("/srv/shop"));
+        project.governors = vec!["Shop.Orders".to_string()];
+        let config = SupervisorConfig { projects: vec![project], initial_backoff: 1, max_backoff: 1 };
+
+        let mut supervisor = DaemonSupervisor::new(SupervisorConfig::default(), false).with_runner(runner);
+        supervisor.apply(config.clone());
+        assert_eq!(supervisor.status().len(), 2);
+
+        wait_for(|| {
+            supervisor.tick();
+            supervisor.status().iter().any(|t| t.failures == 1)
+        });
+        let governor = supervisor.status().into_iter().find(|t| t.name.contains("governor")).unwrap();
+        assert!(!governor.running);
+        assert_eq!(governor.last_error.as_deref(), Some("boom"));
+
+        wait_for(|| {
+            supervisor.tick();
+            supervisor.status().iter().all(|t| t.running)
+        });
+        assert_eq!(runs.load(Ordering::SeqCst), 2);
+
+        // Dropping the governor leaves the edge router untouched
+        let mut reduced = config;
+        reduced.projects[0].governors.clear();
+        supervisor.apply(reduced);
+        let status = supervisor.status();
+        assert_eq!(status.len(), 1);
+        assert!(status[0].name.ends_with(":edge-router") && status[0].running);
+
+        supervisor.stop_all();
+        assert!(supervisor.status().is_empty());
+    }
+}
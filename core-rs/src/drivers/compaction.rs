@@ -0,0 +1,329 @@
+//! Snapshot-based compaction of transaction logs (tx.jsonl)
+//!
+//! Compaction moves all but the newest records of a kernel's tx.jsonl into
+//! a zstd-compressed segment and records a checkpoint snapshot beside it:
+//!
+//! ```text
+//! concepts/{kernel}/
+//!   tx.jsonl                                  newest records (appended to)
+//!   tx.snapshot.json                          last checkpoint
+//!   tx-segments/000000000000-000000500000.jsonl.zst
+//!   tx-segments/000000500000-000001000000.jsonl.zst
+//! ```
+//!
+//! Segment names are the record offsets they cover, so the full history is
+//! the segments in name order followed by tx.jsonl (see `TxCompactor::records`).
+//! The snapshot holds the offset and last transaction compacted so far and a
+//! caller-maintained state summary, so derived state can start from the
+//! summary and replay only what came after.
+//!
+//! Truncation happens under the log's append lock, so writers never lose a
+//! record. The segment is written as `.pending` first and promoted once the
+//! log is truncated; a compaction interrupted in between is finished or
+//! rolled back the next time the log is compacted or read.
+
+use super::txlog::{lock, unlock};
+use crate::errors::{CkpError, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fs::{self, OpenOptions};
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+
+/// Checkpoint snapshot file, beside tx.jsonl
+pub const TX_SNAPSHOT_FILE: &str = "tx.snapshot.json";
+
+/// Directory of compacted segments, beside tx.jsonl
+pub const TX_SEGMENTS_DIR: &str = "tx-segments";
+
+/// Records left in tx.jsonl by default
+pub const DEFAULT_KEEP_RECORDS: usize = 10_000;
+
+const SEGMENT_EXTENSION: &str = ".jsonl.zst";
+const PENDING_EXTENSION: &str = ".pending";
+const ZSTD_LEVEL: i32 = 9;
+
+/// Checkpoint written by each compaction
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TxSnapshot {
+    /// Records moved out of tx.jsonl so far (offset of its first record)
+    pub offset: u64,
+
+    /// Transaction ID of the last compacted record
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_tx_id: Option<String>,
+
+    /// When the snapshot was taken
+    #[serde(default)]
+    pub created_at: Option<DateTime<Utc>>,
+
+    /// State summary folded over every compacted record
+    #[serde(default)]
+    pub summary: Value,
+}
+
+/// Compacts one kernel's transaction log
+#[derive(Debug, Clone)]
+pub struct TxCompactor {
+    kernel_dir: PathBuf,
+    keep_records: usize,
+}
+
+impl TxCompactor {
+    /// Compactor for a kernel directory (contains tx.jsonl)
+    pub fn new(kernel_dir: impl Into<PathBuf>) -> Self {
+        Self { kernel_dir: kernel_dir.into(), keep_records: DEFAULT_KEEP_RECORDS }
+    }
+
+    /// Leave this many of the newest records in tx.jsonl
+    pub fn with_keep_records(mut self, keep_records: usize) -> Self {
+        self.keep_records = keep_records;
+        self
+    }
+
+    fn log_path(&self) -> PathBuf {
+        self.kernel_dir.join("tx.jsonl")
+    }
+
+    fn segments_dir(&self) -> PathBuf {
+        self.kernel_dir.join(TX_SEGMENTS_DIR)
+    }
+
+    /// Last checkpoint, if the log was ever compacted
+    pub fn snapshot(&self) -> Result<Option<TxSnapshot>> {
+        match fs::read(self.kernel_dir.join(TX_SNAPSHOT_FILE)) {
+            Ok(bytes) => Ok(Some(serde_json::from_slice(&bytes)?)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Compacted segments, oldest first
+    pub fn segments(&self) -> Result<Vec<PathBuf>> {
+        self.recover()?;
+        let mut segments: Vec<PathBuf> = match fs::read_dir(self.segments_dir()) {
+            Ok(entries) => entries
+                .flatten()
+                .map(|entry| entry.path())
+                .filter(|path| path.to_string_lossy().ends_with(SEGMENT_EXTENSION))
+                .collect(),
+            Err(_) => Vec::new(),
+        };
+        segments.sort();
+        Ok(segments)
+    }
+
+    /// Every record, from the oldest segment through tx.jsonl
+    ///
+    /// Lines that aren't JSON are skipped.
+    pub fn records(&self) -> Result<Vec<Value>> {
+        let mut records = Vec::new();
+        for segment in self.segments()? {
+            records.extend(parse_lines(&read_segment(&segment)?));
+        }
+        match fs::read_to_string(self.log_path()) {
+            Ok(content) => records.extend(parse_lines(&content)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+            Err(e) => return Err(e.into()),
+        }
+        Ok(records)
+    }
+
+    /// Compact the log, keeping the previous snapshot's summary
+    pub fn compact(&self) -> Result<Option<TxSnapshot>> {
+        self.compact_with(|_, _| {})
+    }
+
+    /// Compact the log, folding each compacted record into the summary
+    ///
+    /// # Returns
+    /// The new snapshot, or None if the log holds no more than
+    /// `keep_records` records
+    pub fn compact_with<F>(&self, mut summarize: F) -> Result<Option<TxSnapshot>>
+    where
+        F: FnMut(&mut Value, &Value),
+    {
+        self.recover()?;
+        let mut file = match OpenOptions::new().read(true).write(true).open(self.log_path()) {
+            Ok(file) => file,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(e.into()),
+        };
+
+        lock(&file)?;
+        let result = self.compact_locked(&mut file, &mut summarize);
+        unlock(&file);
+        result
+    }
+
+    fn compact_locked<F>(&self, file: &mut fs::File, summarize: &mut F) -> Result<Option<TxSnapshot>>
+    where
+        F: FnMut(&mut Value, &Value),
+    {
+        let mut content = String::new();
+        file.read_to_string(&mut content)?;
+        let lines: Vec<&str> = content.lines().filter(|line| !line.trim().is_empty()).collect();
+        if lines.len() <= self.keep_records {
+            return Ok(None);
+        }
+        let (compacted, kept) = lines.split_at(lines.len() - self.keep_records);
+
+        // 1. Segment, pending until the log no longer holds its records
+        let start = self.segments()?.last().and_then(|path| segment_range(path)).map_or(0, |(_, end)| end);
+        let end = start + compacted.len() as u64;
+        let segment = self.segments_dir().join(format!("{:012}-{:012}{}", start, end, SEGMENT_EXTENSION));
+        let pending = pending_path(&segment);
+        let mut plain = compacted.join("\n");
+        plain.push('\n');
+        let compressed = zstd::encode_all(plain.as_bytes(), ZSTD_LEVEL)
+            .map_err(|e| CkpError::IoError(format!("Failed to compress tx segment: {}", e)))?;
+        fs::create_dir_all(self.segments_dir())?;
+        write_synced(&pending, &compressed)?;
+
+        // 2. Truncate in place; appenders waiting on the lock write after the kept records
+        let mut remaining = kept.join("\n");
+        if !remaining.is_empty() {
+            remaining.push('\n');
+        }
+        file.set_len(0)?;
+        file.seek(SeekFrom::Start(0))?;
+        file.write_all(remaining.as_bytes())?;
+        file.sync_data()?;
+
+        // 3. Checkpoint, then promote the segment
+        let mut snapshot = self.snapshot()?.unwrap_or_default();
+        for record in parse_lines(&plain) {
+            summarize(&mut snapshot.summary, &record);
+            if let Some(tx_id) = record.get("txId").and_then(|v| v.as_str()) {
+                snapshot.last_tx_id = Some(tx_id.to_string());
+            }
+        }
+        snapshot.offset = end;
+        snapshot.created_at = Some(Utc::now());
+        let snapshot_path = self.kernel_dir.join(TX_SNAPSHOT_FILE);
+        let snapshot_tmp = snapshot_path.with_extension("json.tmp");
+        write_synced(&snapshot_tmp, &serde_json::to_vec_pretty(&snapshot)?)?;
+        fs::rename(&snapshot_tmp, &snapshot_path)?;
+        fs::rename(&pending, &segment)?;
+
+        tracing::info!(
+            kernel_dir = %self.kernel_dir.display(),
+            compacted = compacted.len(),
+            kept = kept.len(),
+            "compacted transaction log"
+        );
+        Ok(Some(snapshot))
+    }
+
+    /// Finish or roll back a compaction that was interrupted
+    ///
+    /// A pending segment whose first record still opens tx.jsonl was never
+    /// truncated from it and is dropped; otherwise it is promoted.
+    fn recover(&self) -> Result<()> {
+        let Ok(entries) = fs::read_dir(self.segments_dir()) else {
+            return Ok(());
+        };
+        for pending in entries.flatten().map(|entry| entry.path()) {
+            let Some(segment) = pending.to_str().and_then(|p| p.strip_suffix(PENDING_EXTENSION)).map(PathBuf::from) else {
+                continue;
+            };
+            let first_compacted = read_segment(&pending).ok().and_then(|plain| plain.lines().next().map(str::to_string));
+            let first_live = fs::read_to_string(self.log_path())
+                .ok()
+                .and_then(|content| content.lines().find(|line| !line.trim().is_empty()).map(str::to_string));
+
+            if first_compacted.is_some() && first_compacted == first_live {
+                fs::remove_file(&pending)?;
+            } else {
+                fs::rename(&pending, &segment)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Record offsets a segment covers, from its file name
+fn segment_range(path: &Path) -> Option<(u64, u64)> {
+    let name = path.file_name()?.to_str()?.strip_suffix(SEGMENT_EXTENSION)?;
+    let (start, end) = name.split_once('-')?;
+    Some((start.parse().ok()?, end.parse().ok()?))
+}
+
+fn pending_path(segment: &Path) -> PathBuf {
+    let mut pending = segment.as_os_str().to_os_string();
+    pending.push(PENDING_EXTENSION);
+    PathBuf::from(pending)
+}
+
+fn read_segment(path: &Path) -> Result<String> {
+    let bytes = zstd::decode_all(fs::File::open(path)?)
+        .map_err(|e| CkpError::IoError(format!("Failed to decompress {}: {}", path.display(), e)))?;
+    String::from_utf8(bytes).map_err(|e| CkpError::ParseError(format!("Invalid tx segment {}: {}", path.display(), e)))
+}
+
+fn parse_lines(content: &str) -> impl Iterator<Item = Value> + '_ {
+    content.lines().filter_map(|line| serde_json::from_str(line).ok())
+}
+
+fn write_synced(path: &Path, bytes: &[u8]) -> Result<()> {
+    let mut file = fs::File::create(path)?;
+    file.write_all(bytes)?;
+    file.sync_data()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use tempfile::TempDir;
+
+    fn append(kernel_dir: &Path, from: usize, to: usize) {
+        let mut file = OpenOptions::new().create(true).append(true).open(kernel_dir.join("tx.jsonl")).unwrap();
+        for i in from..to {
+            writeln!(file, "{}", json!({"txId": format!("{}-0000000{}", 1000 + i, i % 10), "amount": i})).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_compaction_keeps_full_history_and_summary() {
+        let temp = TempDir::new().unwrap();
+        let kernel_dir = temp.path();
+        append(kernel_dir, 0, 10);
+
+        let compactor = TxCompactor::new(kernel_dir).with_keep_records(3);
+        let total = |summary: &mut Value, record: &Value| {
+            *summary = json!(summary.as_i64().unwrap_or(0) + record["amount"].as_i64().unwrap());
+        };
+        let snapshot = compactor.compact_with(total).unwrap().unwrap();
+        assert_eq!(snapshot.offset, 7);
+        assert_eq!(snapshot.last_tx_id.as_deref(), Some("1006-00000006"));
+        assert_eq!(snapshot.summary, json!(21));
+        assert_eq!(fs::read_to_string(kernel_dir.join("tx.jsonl")).unwrap().lines().count(), 3);
+
+        // Appends continue; a second compaction starts where the first ended
+        append(kernel_dir, 10, 12);
+        assert!(compactor.clone().with_keep_records(5).compact().unwrap().is_none());
+        let snapshot = compactor.compact_with(total).unwrap().unwrap();
+        assert_eq!((snapshot.offset, snapshot.summary.clone()), (9, json!(36)));
+        let names: Vec<String> = compactor
+            .segments()
+            .unwrap()
+            .iter()
+            .map(|path| path.file_name().unwrap().to_string_lossy().to_string())
+            .collect();
+        assert_eq!(names, ["000000000000-000000000007.jsonl.zst", "000000000007-000000000009.jsonl.zst"]);
+
+        let amounts: Vec<i64> = compactor.records().unwrap().iter().map(|r| r["amount"].as_i64().unwrap()).collect();
+        assert_eq!(amounts, (0..12).collect::<Vec<_>>());
+
+        // A segment left pending before truncation is rolled back
+        let pending = kernel_dir.join(TX_SEGMENTS_DIR).join("000000000009-000000000010.jsonl.zst.pending");
+        let first_live = fs::read_to_string(kernel_dir.join("tx.jsonl")).unwrap().lines().next().unwrap().to_string();
+        fs::write(&pending, zstd::encode_all(format!("{}\n", first_live).as_bytes(), 1).unwrap()).unwrap();
+        assert_eq!(compactor.records().unwrap().len(), 12);
+        assert!(!pending.exists());
+    }
+}
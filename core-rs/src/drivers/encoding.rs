@@ -0,0 +1,237 @@
+//! Job and receipt encodings
+//!
+//! JSON is the protocol default and what every runtime reads. Kernels that
+//! handle large payloads can opt into MessagePack or CBOR: the writer asks
+//! for a binary encoding (`CKP_ENCODING` or `FileSystemDriver::with_encoding`)
+//! and gets it only when every kernel that will read the file advertises the
+//! matching capability in its ontology, e.g.
+//!
+//! ```yaml
+//! capabilities:
+//!   - encoding:msgpack
+//! ```
+//!
+//! Otherwise the file is written as JSON. The encoding is carried in the file
+//! name, so readers never have to sniff content:
+//!
+//! | Encoding    | Job file               | Receipt           |
+//! |-------------|------------------------|-------------------|
+//! | JSON        | `{txId}.job`           | `receipt.bin`     |
+//! | MessagePack | `{txId}.msgpack.job`   | `receipt.msgpack` |
+//! | CBOR        | `{txId}.cbor.job`      | `receipt.cbor`    |
+
+use crate::errors::{CkpError, Result};
+use crate::storage::RECEIPT_FILE;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+
+/// Environment variable selecting the encoding a kernel asks for
+pub const ENCODING_ENV: &str = "CKP_ENCODING";
+
+/// Ontology capability prefix advertising a readable encoding
+pub const ENCODING_CAPABILITY_PREFIX: &str = "encoding:";
+
+/// Serialization format of job and receipt files
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Encoding {
+    /// Pretty-printed JSON, readable by every runtime
+    #[default]
+    Json,
+    /// MessagePack with named fields
+    MessagePack,
+    /// CBOR
+    Cbor,
+}
+
+impl Encoding {
+    /// Every encoding, JSON first
+    pub const ALL: [Encoding; 3] = [Encoding::Json, Encoding::MessagePack, Encoding::Cbor];
+
+    /// Encoding requested through `CKP_ENCODING`, or JSON if unset or invalid
+    pub fn from_env() -> Self {
+        std::env::var(ENCODING_ENV)
+            .ok()
+            .and_then(|value| value.parse().ok())
+            .unwrap_or_default()
+    }
+
+    /// Short name, as used in file names and capabilities
+    pub fn name(&self) -> &'static str {
+        match self {
+            Encoding::Json => "json",
+            Encoding::MessagePack => "msgpack",
+            Encoding::Cbor => "cbor",
+        }
+    }
+
+    /// Capability a kernel advertises to receive this encoding
+    ///
+    /// None for JSON, which every kernel reads.
+    pub fn capability(&self) -> Option<String> {
+        match self {
+            Encoding::Json => None,
+            _ => Some(format!("{}{}", ENCODING_CAPABILITY_PREFIX, self.name())),
+        }
+    }
+
+    /// Whether a kernel with these capabilities reads this encoding
+    pub fn is_supported_by(&self, capabilities: &[String]) -> bool {
+        match self.capability() {
+            Some(capability) => capabilities.contains(&capability),
+            None => true,
+        }
+    }
+
+    /// This encoding if every reader supports it, JSON otherwise
+    ///
+    /// # Arguments
+    /// * `readers` - Capabilities of each kernel that will read the file
+    pub fn negotiate<'a>(&self, readers: impl IntoIterator<Item = &'a [String]>) -> Encoding {
+        if readers.into_iter().all(|capabilities| self.is_supported_by(capabilities)) {
+            *self
+        } else {
+            Encoding::Json
+        }
+    }
+
+    /// Job file name for a transaction
+    pub fn job_file_name(&self, tx_id: &str) -> String {
+        match self {
+            Encoding::Json => format!("{}.job", tx_id),
+            _ => format!("{}.{}.job", tx_id, self.name()),
+        }
+    }
+
+    /// Encoding and transaction ID of a job file name
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ckp_core::drivers::Encoding;
+    ///
+    /// assert_eq!(Encoding::parse_job_file_name("1234-abc.job"), Some((Encoding::Json, "1234-abc")));
+    /// assert_eq!(Encoding::parse_job_file_name("1234-abc.cbor.job"), Some((Encoding::Cbor, "1234-abc")));
+    /// assert_eq!(Encoding::parse_job_file_name("1234-abc.json"), None);
+    /// ```
+    pub fn parse_job_file_name(file_name: &str) -> Option<(Encoding, &str)> {
+        let stem = file_name.strip_suffix(".job")?;
+        for encoding in [Encoding::MessagePack, Encoding::Cbor] {
+            if let Some(tx_id) = stem.strip_suffix(&format!(".{}", encoding.name())) {
+                return Some((encoding, tx_id));
+            }
+        }
+        Some((Encoding::Json, stem))
+    }
+
+    /// Receipt file name inside an instance directory
+    pub fn receipt_file_name(&self) -> &'static str {
+        match self {
+            Encoding::Json => RECEIPT_FILE,
+            Encoding::MessagePack => "receipt.msgpack",
+            Encoding::Cbor => "receipt.cbor",
+        }
+    }
+
+    /// Receipt present in an instance directory, with its encoding
+    pub fn find_receipt(inst_dir: &Path) -> Option<(Encoding, PathBuf)> {
+        Encoding::ALL
+            .into_iter()
+            .map(|encoding| (encoding, inst_dir.join(encoding.receipt_file_name())))
+            .find(|(_, path)| path.is_file())
+    }
+
+    /// Serialize a value
+    pub fn encode<T: Serialize>(&self, value: &T) -> Result<Vec<u8>> {
+        match self {
+            Encoding::Json => Ok(serde_json::to_vec_pretty(value)?),
+            Encoding::MessagePack => rmp_serde::to_vec_named(value)
+                .map_err(|e| CkpError::SerializationError(format!("MessagePack encoding failed: {}", e))),
+            Encoding::Cbor => {
+                let mut bytes = Vec::new();
+                ciborium::into_writer(value, &mut bytes)
+                    .map_err(|e| CkpError::SerializationError(format!("CBOR encoding failed: {}", e)))?;
+                Ok(bytes)
+            }
+        }
+    }
+
+    /// Deserialize a value
+    pub fn decode<T: DeserializeOwned>(&self, bytes: &[u8]) -> Result<T> {
+        match self {
+            Encoding::Json => Ok(serde_json::from_slice(bytes)?),
+            Encoding::MessagePack => rmp_serde::from_slice(bytes)
+                .map_err(|e| CkpError::ParseError(format!("MessagePack decoding failed: {}", e))),
+            Encoding::Cbor => ciborium::from_reader(bytes)
+                .map_err(|e| CkpError::ParseError(format!("CBOR decoding failed: {}", e))),
+        }
+    }
+}
+
+impl std::fmt::Display for Encoding {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl std::str::FromStr for Encoding {
+    type Err = CkpError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_ascii_lowercase().as_str() {
+            "json" => Ok(Encoding::Json),
+            "msgpack" | "messagepack" => Ok(Encoding::MessagePack),
+            "cbor" => Ok(Encoding::Cbor),
+            other => Err(CkpError::ValidationError(format!(
+                "Invalid encoding '{}' (expected json, msgpack or cbor)",
+                other
+            ))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::drivers::JobFile;
+    use serde_json::json;
+
+    #[test]
+    fn test_encodings_round_trip_jobs() {
+        let job = JobFile {
+            target: "Shop.Orders".to_string(),
+            payload: json!({"items": [1, 2, 3], "blob": "x".repeat(1024), "nested": {"ok": true}}),
+            timestamp: "2025-11-29T10:00:00Z".to_string(),
+            tx_id: "1764410400000-abc".to_string(),
+            source: "external".to_string(),
+            traceparent: None,
+            stamp: Default::default(),
+        };
+
+        for encoding in Encoding::ALL {
+            let bytes = encoding.encode(&job).unwrap();
+            let decoded: JobFile = encoding.decode(&bytes).unwrap();
+            assert_eq!(decoded.payload, job.payload, "{} round trip", encoding);
+            assert_eq!(decoded.tx_id, job.tx_id);
+
+            let file_name = encoding.job_file_name(&job.tx_id);
+            assert!(file_name.ends_with(".job"));
+            assert_eq!(Encoding::parse_job_file_name(&file_name), Some((encoding, job.tx_id.as_str())));
+        }
+        assert!(Encoding::Cbor.decode::<JobFile>(b"{}").is_err());
+    }
+
+    #[test]
+    fn test_negotiation_falls_back_to_json() {
+        let msgpack = vec!["encoding:msgpack".to_string()];
+        let none: Vec<String> = Vec::new();
+
+        assert_eq!(Encoding::MessagePack.negotiate([msgpack.as_slice()]), Encoding::MessagePack);
+        assert_eq!(Encoding::MessagePack.negotiate([msgpack.as_slice(), none.as_slice()]), Encoding::Json);
+        assert_eq!(Encoding::Cbor.negotiate([msgpack.as_slice()]), Encoding::Json);
+        assert_eq!(Encoding::Json.negotiate([none.as_slice()]), Encoding::Json);
+
+        assert_eq!("MessagePack".parse::<Encoding>().unwrap(), Encoding::MessagePack);
+        assert!("yaml".parse::<Encoding>().is_err());
+    }
+}
@@ -1,21 +1,25 @@
 //! FileSystemDriver for ConceptKernel event sourcing
 //!
 //! Provides file-based event sourcing operations including:
-//! - Storage artifact minting
+//! - Storage artifact minting and archiving
 //! - Transaction recording
 //! - Job archiving
 //! - Per-edge queue management (v1.3.12)
 //! - Symlink creation with relative paths
 
+use crate::drivers::settings_cache::SettingsCache;
+use crate::drivers::{is_queue_entry, Encoding, MappedFile, QueueCounters, TxCommit, TxWriters, GROUP_COMMIT_CAPABILITY, GROUP_COMMIT_WINDOW};
 use crate::errors::{CkpError, Result};
+use crate::project::QuotaAccountant;
+use crate::storage::{shard, InstanceScanner, Receipt, SHARDING_CAPABILITY};
 use chrono::Utc;
 use serde_json::Value as JsonValue;
 use std::fs;
 use std::path::{Path, PathBuf};
 use uuid::Uuid;
 
-/// Transaction record structure
-#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+/// Transaction record structure (one line of tx.jsonl)
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, schemars::JsonSchema)]
 pub struct Transaction {
     #[serde(rename = "txId")]
     pub tx_id: String,
@@ -30,6 +34,14 @@ pub struct Transaction {
 pub struct FileSystemDriver {
     root: PathBuf,
     concept: String,
+    encoding: Encoding,
+    tx_commit: Option<TxCommit>,
+
+    /// Group-commit writers, shared with clones and stopped with the last one
+    tx_writers: TxWriters,
+
+    /// Ontology and quota settings, resolved once and shared with clones
+    settings: SettingsCache,
 }
 
 impl FileSystemDriver {
@@ -47,7 +59,91 @@ impl FileSystemDriver {
     /// );
     /// ```
     pub fn new(root: PathBuf, concept: String) -> Self {
-        Self { root, concept }
+        let settings = SettingsCache::new(root.clone());
+        Self { root, concept, encoding: Encoding::Json, tx_commit: None, tx_writers: TxWriters::new(), settings }
+    }
+
+    /// Ask for a binary encoding of jobs and receipts
+    ///
+    /// Files are still written as JSON for readers that don't advertise the
+    /// encoding (see `Encoding::negotiate`).
+    pub fn with_encoding(mut self, encoding: Encoding) -> Self {
+        self.encoding = encoding;
+        self
+    }
+
+    /// Commit transaction records with the given mode
+    ///
+    /// Without this, each kernel's mode comes from its ontology (see
+    /// `TxCommit::for_kernel`).
+    pub fn with_tx_commit(mut self, tx_commit: TxCommit) -> Self {
+        self.tx_commit = Some(tx_commit);
+        self
+    }
+
+    /// Encoding requested for jobs and receipts
+    pub fn encoding(&self) -> Encoding {
+        self.encoding
+    }
+
+    /// Encoding to use for a file read by the given kernels
+    ///
+    /// Kernels whose ontology cannot be read are treated as JSON-only.
+    pub fn negotiate_encoding<'a>(&self, readers: impl IntoIterator<Item = &'a str>) -> Encoding {
+        if self.encoding == Encoding::Json {
+            return Encoding::Json;
+        }
+
+        let capabilities: Vec<Vec<String>> = readers
+            .into_iter()
+            .map(|kernel| self.settings.kernel(kernel).map(|s| s.capabilities.clone()).unwrap_or_default())
+            .collect();
+        self.encoding.negotiate(capabilities.iter().map(Vec::as_slice))
+    }
+
+    /// Encoding for receipts of a kernel: it and every notified kernel must read it
+    fn receipt_encoding(&self, kernel_name: &str) -> Encoding {
+        if self.encoding == Encoding::Json {
+            return Encoding::Json;
+        }
+
+        let Some(settings) = self.settings.kernel(kernel_name) else {
+            return Encoding::Json;
+        };
+        let readers = std::iter::once(kernel_name).chain(settings.notified.iter().map(String::as_str));
+        self.negotiate_encoding(readers)
+    }
+
+    /// How a kernel's transaction records are committed
+    fn tx_commit(&self, kernel_name: &str) -> TxCommit {
+        self.tx_commit.unwrap_or_else(|| {
+            let grouped = self.settings.kernel(kernel_name).is_some_and(|s| s.has_capability(GROUP_COMMIT_CAPABILITY));
+            if grouped {
+                TxCommit::Group(GROUP_COMMIT_WINDOW)
+            } else {
+                TxCommit::Immediate
+            }
+        })
+    }
+
+    /// Apply a kernel's `spec.tx_redaction` to a record bound for its tx.jsonl
+    fn redact_tx(&self, kernel_name: &str, record: &mut JsonValue) -> Result<()> {
+        if let Some(settings) = self.settings.kernel(kernel_name) {
+            if let Some(rules) = settings.redaction()? {
+                rules.apply(record);
+            }
+        }
+        Ok(())
+    }
+
+    /// Whether a kernel's storage and inbox are written sharded
+    fn is_sharded(&self, kernel_name: &str) -> bool {
+        self.settings.kernel(kernel_name).is_some_and(|s| s.has_capability(SHARDING_CAPABILITY))
+    }
+
+    /// Quotas of the project, if it has any
+    fn quotas(&self) -> Option<QuotaAccountant> {
+        self.settings.quotas().map(|config| QuotaAccountant::new(self.root.clone(), config))
     }
 
     /// Get kernel directory path
@@ -101,6 +197,10 @@ impl FileSystemDriver {
 
     /// Mint a storage artifact
     ///
+    /// Writes the payload to `receipt.json` and a schema-conformant
+    /// `receipt.bin` carrying its digest. Kernels with the `sharding`
+    /// capability get the instance under a shard directory.
+    ///
     /// # Example
     ///
     /// ```no_run
@@ -117,23 +217,105 @@ impl FileSystemDriver {
     /// let artifact_path = driver.mint_storage_artifact(&data, "tx-123").unwrap();
     /// ```
     pub fn mint_storage_artifact(&self, data: &JsonValue, tx_id: &str) -> Result<PathBuf> {
-        let artifact_path = self.get_storage().join(format!("{}.inst", tx_id));
+        let receipt_data = serde_json::to_string_pretty(data)?;
+
+        // Refuse instances beyond the kernel's storage quota
+        if let Some(quotas) = self.quotas() {
+            quotas.check_mint(&self.concept, receipt_data.len() as u64)?;
+        }
+
+        let artifact_path = shard::entry_path(&self.get_storage(), &format!("{}.inst", tx_id), self.is_sharded(&self.concept));
+        let scanner = self.instance_scanner();
+        let index_update = scanner.begin_index_update(&artifact_path);
 
         // Create artifact directory
         fs::create_dir_all(&artifact_path)?;
 
         // Write receipt.json
         let receipt_path = artifact_path.join("receipt.json");
-        let receipt_data = serde_json::to_string_pretty(data)?;
-        fs::write(&receipt_path, receipt_data)?;
+        fs::write(&receipt_path, &receipt_data)?;
+
+        // Write receipt.bin (typed CKI receipt), or its binary form if negotiated
+        with_current_cause(Receipt::for_payload(tx_id, &self.concept, data))
+            .with_digest("receipt.json", receipt_data.as_bytes())
+            .write_encoded(&artifact_path, self.receipt_encoding(&self.concept))?;
 
+        index_update.commit()?;
+        tracing::debug!(kernel = %self.concept, tx_id, "minted storage artifact");
         Ok(artifact_path)
     }
 
+    /// Archive a storage artifact
+    ///
+    /// Moves `storage/{tx_id}.inst` to `archive/{tx_id}.inst` in the kernel
+    /// directory, out of instance listings.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use ckp_core::drivers::FileSystemDriver;
+    /// use std::path::PathBuf;
+    ///
+    /// let driver = FileSystemDriver::new(
+    ///     PathBuf::from("/test"),
+    ///     "Recipes.BakeCake".to_string()
+    /// );
+    ///
+    /// let archived = driver.archive_storage_artifact("tx-123").unwrap();
+    /// ```
+    pub fn archive_storage_artifact(&self, tx_id: &str) -> Result<PathBuf> {
+        let artifact_path = shard::locate(&self.get_storage(), &format!("{}.inst", tx_id));
+        if !artifact_path.is_dir() {
+            return Err(CkpError::FileNotFound(format!("Storage artifact not found: {}", tx_id)));
+        }
+
+        let archive_dir = self.get_kernel_dir().join("archive");
+        fs::create_dir_all(&archive_dir)?;
+        let archived_path = archive_dir.join(format!("{}.inst", tx_id));
+
+        let scanner = self.instance_scanner();
+        let index_update = scanner.begin_index_update(&artifact_path);
+        fs::rename(&artifact_path, &archived_path)?;
+        index_update.commit()?;
+        tracing::debug!(kernel = %self.concept, tx_id, "archived storage artifact");
+
+        Ok(archived_path)
+    }
+
+    /// Import instances from a bundle written by `InstanceScanner::export`
+    ///
+    /// Instances already in storage are skipped. Nothing is imported if the
+    /// bundle fails its checksums or holds unsafe paths.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use ckp_core::drivers::FileSystemDriver;
+    /// use std::path::{Path, PathBuf};
+    ///
+    /// let driver = FileSystemDriver::new(
+    ///     PathBuf::from("/test"),
+    ///     "Recipes.BakeCake".to_string()
+    /// );
+    ///
+    /// let imported = driver.import(Path::new("/tmp/instances.tar.gz")).unwrap();
+    /// ```
+    pub fn import(&self, bundle: &Path) -> Result<Vec<PathBuf>> {
+        crate::storage::bundle::import_bundle(bundle, &self.instance_scanner(), &self.get_storage(), self.is_sharded(&self.concept))
+    }
+
+    /// Scanner over this kernel's storage, used to keep its index current
+    fn instance_scanner(&self) -> InstanceScanner {
+        InstanceScanner::new(self.get_kernel_dir(), self.concept.clone())
+    }
+
     /// Record transaction metadata with file locking for FIFO integrity
     ///
     /// Uses advisory file locking to prevent concurrent write corruption
-    /// and ensure transaction log integrity for the queue system.
+    /// and ensure transaction log integrity for the queue system. Kernels
+    /// with group commit (see `TxCommit`) share one lock and fsync per batch.
+    /// Metadata fields named in the kernel's `spec.tx_redaction` are hashed,
+    /// stripped or redacted before the record is written.
     ///
     /// # Example
     ///
@@ -150,14 +332,8 @@ impl FileSystemDriver {
     /// let metadata = json!({"event": "minted"});
     /// driver.record_transaction("tx-123", metadata).unwrap();
     /// ```
-    pub fn record_transaction(&self, tx_id: &str, metadata: JsonValue) -> Result<()> {
-        let tx_log = self.get_tx_log();
-
-        // Ensure parent directory exists
-        if let Some(parent) = tx_log.parent() {
-            fs::create_dir_all(parent)?;
-        }
-
+    pub fn record_transaction(&self, tx_id: &str, mut metadata: JsonValue) -> Result<()> {
+        self.redact_tx(&self.concept, &mut metadata)?;
         let transaction = Transaction {
             tx_id: tx_id.to_string(),
             timestamp: Utc::now().to_rfc3339(),
@@ -165,52 +341,9 @@ impl FileSystemDriver {
             metadata,
         };
 
-        let tx_line = serde_json::to_string(&transaction)?;
-
         // Append to tx.jsonl with file locking to ensure FIFO integrity
-        use std::fs::OpenOptions;
-        use std::io::Write;
-
-        #[cfg(unix)]
-        {
-            // Open with append mode
-            let mut file = OpenOptions::new()
-                .create(true)
-                .append(true)
-                .open(&tx_log)?;
-
-            // Acquire exclusive lock (blocks until available)
-            use std::os::unix::io::AsRawFd;
-            let fd = file.as_raw_fd();
-
-            // Use flock for advisory locking (LOCK_EX for exclusive lock)
-            unsafe {
-                if libc::flock(fd, libc::LOCK_EX) != 0 {
-                    return Err(CkpError::Io(std::io::Error::last_os_error()));
-                }
-            }
-
-            // Write transaction (lock is held)
-            writeln!(file, "{}", tx_line)?;
-
-            // Explicitly unlock (though close() will also release the lock)
-            unsafe {
-                libc::flock(fd, libc::LOCK_UN);
-            }
-        }
-
-        #[cfg(not(unix))]
-        {
-            // Windows fallback: write without locking (Windows uses different locking APIs)
-            let mut file = OpenOptions::new()
-                .create(true)
-                .append(true)
-                .open(tx_log)?;
-
-            writeln!(file, "{}", tx_line)?;
-        }
-
-        Ok(())
+        let tx_line = serde_json::to_string(&transaction)?;
+        self.tx_commit(&self.concept).append(&self.tx_writers, &self.get_tx_log(), tx_line)
     }
 
     /// Move job between queue stages
@@ -243,6 +376,14 @@ impl FileSystemDriver {
 
         // Move file
         fs::rename(source_path, &target_path)?;
+        QueueCounters::record_removed(source_path);
+        QueueCounters::record_added(&target_path);
+        tracing::debug!(
+            kernel = %self.concept,
+            tx_id = self.extract_tx_id_from_path(&target_path).as_deref(),
+            target = %target_dir.display(),
+            "moved job"
+        );
 
         Ok(target_path)
     }
@@ -268,15 +409,9 @@ impl FileSystemDriver {
             return Ok(0);
         }
 
-        let count = fs::read_dir(queue_dir)?
-            .filter_map(|e| e.ok())
-            .filter(|e| {
-                e.path()
-                    .extension()
-                    .and_then(|s| s.to_str())
-                    .map(|ext| ext == "job" || ext == "inst")
-                    .unwrap_or(false)
-            })
+        let count = shard::list_entries(queue_dir)
+            .into_iter()
+            .filter(|path| is_queue_entry(path))
             .count();
 
         Ok(count)
@@ -314,6 +449,8 @@ impl FileSystemDriver {
 
         // Delete original job from inbox
         fs::remove_file(job_path)?;
+        QueueCounters::record_removed(job_path);
+        tracing::debug!(kernel = %self.concept, tx_id, "archived job");
 
         Ok(())
     }
@@ -336,7 +473,7 @@ impl FileSystemDriver {
     /// ```
     pub fn extract_tx_id_from_job_path(&self, job_path: &Path) -> Option<String> {
         let filename = job_path.file_name()?.to_str()?;
-        let tx_id = filename.strip_suffix(".job")?;
+        let (_, tx_id) = Encoding::parse_job_file_name(filename)?;
         Some(tx_id.to_string())
     }
 
@@ -434,6 +571,13 @@ impl FileSystemDriver {
             std::os::windows::fs::symlink_dir(&relative_path, &symlink_path)?;
         }
 
+        tracing::debug!(
+            kernel = %self.concept,
+            tx_id = self.extract_tx_id_from_path(&symlink_path).as_deref(),
+            edge = symlink_path.parent().and_then(|p| p.file_name()).map(|n| n.to_string_lossy().to_string()).as_deref(),
+            "created queue symlink"
+        );
+
         Ok(symlink_path)
     }
 
@@ -486,26 +630,24 @@ impl FileSystemDriver {
 
         let mut instances = Vec::new();
 
-        for entry in fs::read_dir(queue_path)? {
-            let entry = entry?;
-            let file_name = entry.file_name();
-            let file_name_str = file_name.to_string_lossy();
+        for file_path in shard::list_entries(queue_path) {
+            let file_name_str = file_path.file_name().unwrap_or_default().to_string_lossy();
 
             // Skip non-instance files
             if file_name_str == ".gitkeep" || !file_name_str.ends_with(".inst") {
                 continue;
             }
 
-            let file_path = entry.path();
-
             // Follow symlink to get actual instance path
             match fs::canonicalize(&file_path) {
                 Ok(real_path) => instances.push(real_path),
                 Err(e) => {
-                    eprintln!(
-                        "[FileSystemDriver] Failed to resolve symlink {}: {}",
-                        file_path.display(),
-                        e
+                    tracing::warn!(
+                        kernel = %self.concept,
+                        tx_id = self.extract_tx_id_from_path(&file_path).as_deref(),
+                        path = %file_path.display(),
+                        error = %e,
+                        "failed to resolve symlink"
                     );
                 }
             }
@@ -636,6 +778,14 @@ impl FileSystemDriver {
     }
 }
 
+/// Link a receipt without a cause to the current one (see `causality::current_cause`)
+fn with_current_cause(receipt: Receipt) -> Receipt {
+    match crate::causality::current_cause() {
+        Some(cause) if receipt.causation_id.is_none() => receipt.caused_by(&cause.tx_id, Some(&cause.correlation_id)),
+        _ => receipt,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -684,6 +834,242 @@ mod tests {
         assert_eq!(parsed["status"], "success");
     }
 
+    #[test]
+    fn test_project_quotas_refuse_jobs_and_instances() {
+        let temp_dir = TempDir::new().unwrap();
+        setup_test_kernel(&temp_dir, "TestKernel");
+        fs::write(
+            temp_dir.path().join(".ckproject"),
+            "apiVersion: conceptkernel/v1\nkind: Project\nmetadata:\n  name: quota\n  id: proj-quota\nspec:\n  domain: Org.Test\n  version: 1.3.14\n  quotas:\n    kernels:\n      TestKernel:\n        instances: 1\n        queueDepth: 1\n",
+        )
+        .unwrap();
+
+        let driver = FileSystemDriver::new(temp_dir.path().to_path_buf(), "TestKernel".to_string());
+        driver.mint_storage_artifact(&json!({"n": 1}), "tx-1").unwrap();
+        let err = driver.mint_storage_artifact(&json!({"n": 2}), "tx-2").unwrap_err();
+        assert!(matches!(err, CkpError::QuotaExceeded { used: 1, limit: 1, .. }));
+        assert!(!temp_dir.path().join("concepts/TestKernel/storage/tx-2.inst").exists());
+
+        let job = |tx_id: &str| TraitJobFile {
+            target: "TestKernel".to_string(),
+            payload: json!({}),
+            timestamp: Utc::now().to_rfc3339(),
+            tx_id: tx_id.to_string(),
+            source: "external".to_string(),
+            traceparent: None,
+            stamp: Default::default(),
+        };
+        driver.write_job("TestKernel", job("1764410400000-a")).unwrap();
+        let err = driver.write_job("TestKernel", job("1764410400000-b")).unwrap_err();
+        assert!(matches!(err, CkpError::QuotaExceeded { ref resource, .. } if resource == "queue depth"));
+    }
+
+    #[test]
+    fn test_mint_storage_artifact_writes_typed_receipt() {
+        let temp_dir = TempDir::new().unwrap();
+        setup_test_kernel(&temp_dir, "TestKernel");
+
+        let driver = FileSystemDriver::new(
+            temp_dir.path().to_path_buf(),
+            "TestKernel".to_string(),
+        );
+
+        let data = json!({"name": "Order-7", "action": "create", "success": true, "inputs": ["tx-6"]});
+        let artifact_path = driver.mint_storage_artifact(&data, "tx-7").unwrap();
+
+        let scanner = InstanceScanner::new(driver.get_kernel_dir(), "TestKernel".to_string());
+        let receipt = scanner.read_receipt(&artifact_path).unwrap();
+        assert_eq!(receipt.schema_version, crate::storage::RECEIPT_SCHEMA_VERSION);
+        receipt.verify_digests(&artifact_path).unwrap();
+
+        let detail = scanner.describe_instance("order-7").unwrap();
+        assert_eq!((detail.id.as_str(), detail.kernel.as_str()), ("tx-7", "TestKernel"));
+        assert_eq!((detail.action.as_deref(), detail.success), (Some("create"), Some(true)));
+        assert_eq!(detail.inputs, vec!["tx-6"]);
+        assert_eq!(detail.data, data);
+    }
+
+    #[test]
+    fn test_binary_encoding_negotiated_with_readers() {
+        let temp_dir = TempDir::new().unwrap();
+        let ontology = |kernel: &str, extra: &str| {
+            setup_test_kernel(&temp_dir, kernel);
+            fs::write(
+                temp_dir.path().join("concepts").join(kernel).join("conceptkernel.yaml"),
+                format!("apiVersion: conceptkernel/v1\nkind: Ontology\nmetadata:\n  name: {}\n  type: node:cold\n{}", kernel, extra),
+            )
+            .unwrap();
+        };
+        ontology("Binary.Reader", "capabilities:\n  - encoding:msgpack\n");
+        ontology("Json.Reader", "");
+
+        let driver = FileSystemDriver::new(temp_dir.path().to_path_buf(), "Binary.Reader".to_string())
+            .with_encoding(Encoding::MessagePack);
+        let job = |target: &str| TraitJobFile {
+            target: target.to_string(),
+            payload: json!({"blob": "x".repeat(4096)}),
+            timestamp: Utc::now().to_rfc3339(),
+            tx_id: format!("1764410400000-{}", target.len()),
+            source: "external".to_string(),
+            traceparent: None,
+            stamp: Default::default(),
+        };
+
+        // Only kernels advertising the capability get binary jobs
+        let tx_id = driver.write_job("Binary.Reader", job("Binary.Reader")).unwrap();
+        let inbox = temp_dir.path().join("concepts/Binary.Reader/queue/inbox");
+        assert!(inbox.join(format!("{}.msgpack.job", tx_id)).exists());
+        let jobs = driver.read_jobs("Binary.Reader").unwrap();
+        assert_eq!(jobs[0].payload()["blob"].as_str().unwrap().len(), 4096);
+
+        let tx_id = driver.write_job("Json.Reader", job("Json.Reader")).unwrap();
+        let inbox = temp_dir.path().join("concepts/Json.Reader/queue/inbox");
+        serde_json::from_slice::<JsonValue>(&fs::read(inbox.join(format!("{}.job", tx_id))).unwrap()).unwrap();
+
+        // Receipts fall back to JSON once a notified kernel can't read them
+        let data = json!({"name": "binary"});
+        let artifact = driver.mint_storage_artifact(&data, "tx-1").unwrap();
+        assert!(artifact.join("receipt.msgpack").exists() && !artifact.join(crate::storage::RECEIPT_FILE).exists());
+        let scanner = InstanceScanner::new(driver.get_kernel_dir(), "Binary.Reader".to_string());
+        assert_eq!(scanner.read_receipt(&artifact).unwrap().data, data);
+
+        ontology(
+            "Binary.Reader",
+            "capabilities:\n  - encoding:msgpack\nspec:\n  notification_contract:\n    - target_kernel: Json.Reader\n      queue: edges\n",
+        );
+        let artifact = driver.mint_storage_artifact(&data, "tx-2").unwrap();
+        assert!(artifact.join(crate::storage::RECEIPT_FILE).exists());
+    }
+
+    #[test]
+    fn test_tx_redaction_applied_before_recording() {
+        let temp_dir = TempDir::new().unwrap();
+        setup_test_kernel(&temp_dir, "Orders");
+        fs::write(
+            temp_dir.path().join("concepts/Orders/conceptkernel.yaml"),
+            "apiVersion: conceptkernel/v1\nkind: Ontology\nmetadata:\n  name: Orders\n  type: node:cold\n\
+             spec:\n  tx_redaction:\n    builtins: false\n    hash: [customer.email]\n    strip: [notes]\n",
+        )
+        .unwrap();
+
+        let driver = FileSystemDriver::new(temp_dir.path().to_path_buf(), "Orders".to_string());
+        driver
+            .record_transaction("tx-1", json!({"customer": {"email": "a@example.com"}, "notes": "call back", "total": 12}))
+            .unwrap();
+        StorageDriver::record_transaction(&driver, "Orders", json!({"txId": "tx-2", "notes": "gift"})).unwrap();
+
+        let content = fs::read_to_string(temp_dir.path().join("concepts/Orders/tx.jsonl")).unwrap();
+        assert!(!content.contains("a@example.com") && !content.contains("notes"));
+        let records: Vec<JsonValue> = content.lines().map(|line| serde_json::from_str(line).unwrap()).collect();
+        assert!(records[0]["customer"]["email"].as_str().unwrap().starts_with(crate::compliance::HASHED_PREFIX));
+        assert_eq!((records[0]["total"].clone(), records[1]["txId"].clone()), (json!(12), json!("tx-2")));
+    }
+
+    #[test]
+    fn test_sharded_kernel_layout() {
+        let temp_dir = TempDir::new().unwrap();
+        setup_test_kernel(&temp_dir, "Sharded.Kernel");
+        let kernel_dir = temp_dir.path().join("concepts/Sharded.Kernel");
+        fs::write(
+            kernel_dir.join("conceptkernel.yaml"),
+            "apiVersion: conceptkernel/v1\nkind: Ontology\nmetadata:\n  name: Sharded.Kernel\n  type: node:cold\ncapabilities:\n  - sharding\n",
+        )
+        .unwrap();
+        let driver = FileSystemDriver::new(temp_dir.path().to_path_buf(), "Sharded.Kernel".to_string());
+
+        let artifact = driver.mint_storage_artifact(&json!({"n": 1}), "1764410400000-ab12cd34").unwrap();
+        assert_eq!(artifact, kernel_dir.join("storage/ab/1764410400000-ab12cd34.inst"));
+        let scanner = InstanceScanner::new(kernel_dir.clone(), "Sharded.Kernel".to_string());
+        assert_eq!(scanner.count_instances().unwrap(), 1);
+        assert_eq!(scanner.list_instances(0).unwrap()[0].id, "1764410400000-ab12cd34");
+
+        let job = TraitJobFile {
+            target: "Sharded.Kernel".to_string(),
+            payload: json!({}),
+            timestamp: Utc::now().to_rfc3339(),
+            tx_id: "1764410400000-cd34ef56".to_string(),
+            source: "external".to_string(),
+            traceparent: None,
+            stamp: Default::default(),
+        };
+        driver.write_job("Sharded.Kernel", job).unwrap();
+        assert!(kernel_dir.join("queue/inbox/cd/1764410400000-cd34ef56.job").exists());
+        assert_eq!(driver.read_jobs("Sharded.Kernel").unwrap().len(), 1);
+
+        let archived = driver.archive_storage_artifact("1764410400000-ab12cd34").unwrap();
+        assert_eq!(archived, kernel_dir.join("archive/1764410400000-ab12cd34.inst"));
+    }
+
+    #[test]
+    fn test_archive_storage_artifact_updates_instance_count() {
+        let temp_dir = TempDir::new().unwrap();
+        setup_test_kernel(&temp_dir, "TestKernel");
+
+        let driver = FileSystemDriver::new(
+            temp_dir.path().to_path_buf(),
+            "TestKernel".to_string(),
+        );
+        let scanner = InstanceScanner::new(driver.get_kernel_dir(), "TestKernel".to_string());
+
+        driver.mint_storage_artifact(&json!({"n": 1}), "tx-1").unwrap();
+        assert_eq!(scanner.count_instances().unwrap(), 1);
+        driver.mint_storage_artifact(&json!({"n": 2}), "tx-2").unwrap();
+        assert_eq!(scanner.count_instances().unwrap(), 2);
+
+        let archived = driver.archive_storage_artifact("tx-1").unwrap();
+        assert_eq!(archived, driver.get_kernel_dir().join("archive/tx-1.inst"));
+        assert!(archived.join("receipt.json").exists());
+        assert_eq!(scanner.count_instances().unwrap(), 1);
+
+        assert!(matches!(driver.archive_storage_artifact("tx-1"), Err(CkpError::FileNotFound(_))));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_import_exported_instances() {
+        use std::os::unix::fs::symlink;
+
+        let source_dir = TempDir::new().unwrap();
+        setup_test_kernel(&source_dir, "Source");
+        let source = FileSystemDriver::new(source_dir.path().to_path_buf(), "Source".to_string());
+        let shared = source_dir.path().join("shared.json");
+        fs::write(&shared, "{\"shared\": true}").unwrap();
+
+        for id in ["tx-1", "tx-2"] {
+            let inst_dir = source.get_storage().join(format!("{}.inst", id));
+            fs::create_dir_all(inst_dir.join("data")).unwrap();
+            let receipt = json!({"id": id, "name": id, "timestamp": "2025-11-29T10:00:00Z"});
+            fs::write(inst_dir.join("receipt.bin"), receipt.to_string()).unwrap();
+            fs::write(inst_dir.join("data/payload.json"), "{}").unwrap();
+            symlink("data/payload.json", inst_dir.join("latest")).unwrap();
+            symlink(&shared, inst_dir.join("shared.json")).unwrap();
+            symlink("missing", inst_dir.join("dangling")).unwrap();
+        }
+
+        let scanner = InstanceScanner::new(source.get_kernel_dir(), "Source".to_string());
+        let bundle = source_dir.path().join("out/bundle.tar.gz");
+        let manifest = scanner.export(&["tx-2"], &bundle).unwrap();
+        assert_eq!(manifest.instances.len(), 1);
+        assert_eq!(manifest.instances[0].skipped_links, vec!["dangling"]);
+        assert!(matches!(scanner.export(&["tx-404"], &bundle), Err(CkpError::FileNotFound(_))));
+
+        let target_dir = TempDir::new().unwrap();
+        setup_test_kernel(&target_dir, "Target");
+        let target = FileSystemDriver::new(target_dir.path().to_path_buf(), "Target".to_string());
+        let imported = target.import(&bundle).unwrap();
+
+        let inst_dir = target.get_storage().join("tx-2.inst");
+        assert_eq!(imported, vec![inst_dir.clone()]);
+        assert_eq!(fs::read_link(inst_dir.join("latest")).unwrap(), PathBuf::from("data/payload.json"));
+        assert!(!inst_dir.join("shared.json").is_symlink());
+        assert_eq!(fs::read_to_string(inst_dir.join("shared.json")).unwrap(), "{\"shared\": true}");
+        let target_scanner = InstanceScanner::new(target.get_kernel_dir(), "Target".to_string());
+        assert_eq!(target_scanner.describe_instance("tx-2").unwrap().id, "tx-2");
+
+        // Importing again leaves the existing instance alone
+        assert!(target.import(&bundle).unwrap().is_empty());
+    }
+
     #[test]
     fn test_record_transaction() {
         let temp_dir = TempDir::new().unwrap();
@@ -2997,33 +3383,62 @@ use crate::urn::UrnResolver;
 impl StorageDriver for FileSystemDriver {
     fn write_job(&self, target_urn: &str, job: TraitJobFile) -> Result<String> {
         // Resolve target to queue path (inbox by default, or specified stage)
-        let queue_path = if target_urn.starts_with("ckp://") {
+        let (target_kernel, queue_path) = if target_urn.starts_with("ckp://") {
             // Parse URN
             let parsed = UrnResolver::parse(target_urn)?;
+            UrnResolver::ensure_contained(&parsed.kernel, target_urn)?;
             let kernel_path = self.root.join("concepts").join(&parsed.kernel);
 
             // Use stage if specified, otherwise default to inbox
-            if let Some(stage) = parsed.stage {
+            let queue_path = if let Some(stage) = parsed.stage {
+                UrnResolver::ensure_contained(&stage, target_urn)?;
                 kernel_path.join("queue").join(&stage)
             } else {
                 kernel_path.join("queue/inbox")
-            }
+            };
+            (parsed.kernel, queue_path)
         } else {
             // Simple kernel name - default to inbox
-            self.root.join("concepts").join(target_urn).join("queue/inbox")
+            UrnResolver::ensure_contained(target_urn, target_urn)?;
+            (target_urn.to_string(), self.root.join("concepts").join(target_urn).join("queue/inbox"))
         };
 
-        // Ensure queue directory exists
-        fs::create_dir_all(&queue_path)
-            .map_err(|e| CkpError::IoError(format!("Failed to create queue directory: {}", e)))?;
+        // Carry the caller's trace into the job unless it names one already
+        let mut job = job;
+        if job.traceparent.is_none() {
+            job.traceparent = crate::telemetry::current_traceparent();
+        }
 
-        // Write job file
-        let job_path = queue_path.join(format!("{}.job", job.tx_id));
-        let job_json = serde_json::to_string_pretty(&job)
-            .map_err(|e| CkpError::Json(e))?;
+        // Likewise its cause; a job caused by nothing starts its own chain
+        if job.stamp.causation_id.is_none() {
+            if let Some(cause) = crate::causality::current_cause() {
+                job.stamp = job.stamp.caused_by(&cause.tx_id, Some(&cause.correlation_id));
+            }
+        }
+        if job.stamp.correlation_id.is_none() {
+            job.stamp.correlation_id = Some(job.tx_id.clone());
+        }
+
+        // Refuse jobs beyond the target's queue quota
+        if let Some(quotas) = self.quotas() {
+            quotas.check_emit(&target_kernel)?;
+        }
+
+        // Write job file, binary only if the target kernel reads it
+        let encoding = self.negotiate_encoding([target_kernel.as_str()]);
+        let sharded = queue_path.ends_with("queue/inbox") && self.is_sharded(&target_kernel);
+        let job_path = shard::entry_path(&queue_path, &encoding.job_file_name(&job.tx_id), sharded);
+        let job_bytes = encoding.encode(&job)?;
+
+        // Ensure queue (and shard) directory exists
+        if let Some(job_dir) = job_path.parent() {
+            fs::create_dir_all(job_dir)
+                .map_err(|e| CkpError::IoError(format!("Failed to create queue directory: {}", e)))?;
+        }
 
-        fs::write(&job_path, job_json)
+        fs::write(&job_path, job_bytes)
             .map_err(|e| CkpError::IoError(format!("Failed to write job: {}", e)))?;
+        QueueCounters::record_added(&job_path);
 
         Ok(job.tx_id.clone())
     }
@@ -3037,18 +3452,17 @@ impl StorageDriver for FileSystemDriver {
 
         let mut jobs = Vec::new();
 
-        let entries = fs::read_dir(&inbox_path)
-            .map_err(|e| CkpError::IoError(format!("Failed to read inbox: {}", e)))?;
-
-        for entry in entries.filter_map(|e| e.ok()) {
-            let path = entry.path();
-            if path.extension().and_then(|s| s.to_str()) == Some("job") {
-                // Read job content
-                let content_str = fs::read_to_string(&path)
-                    .map_err(|e| CkpError::IoError(format!("Failed to read job: {}", e)))?;
-
-                let content: TraitJobFile = serde_json::from_str(&content_str)
-                    .map_err(|e| CkpError::Json(e))?;
+        for path in shard::list_entries(&inbox_path) {
+            let encoding = path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .and_then(Encoding::parse_job_file_name)
+                .map(|(encoding, _)| encoding);
+            if let Some(encoding) = encoding {
+                // Read job content, mapped if large
+                let content: TraitJobFile = MappedFile::open(&path)
+                    .map_err(|e| CkpError::IoError(format!("Failed to read job: {}", e)))?
+                    .decode(encoding)?;
 
                 jobs.push(JobHandle {
                     tx_id: content.tx_id.clone(),
@@ -3072,6 +3486,7 @@ impl StorageDriver for FileSystemDriver {
 
         fs::rename(&job_path, &archive_path)
             .map_err(|e| CkpError::IoError(format!("Failed to archive job: {}", e)))?;
+        QueueCounters::record_removed(&job_path);
 
         Ok(())
     }
@@ -3086,7 +3501,9 @@ impl StorageDriver for FileSystemDriver {
         fs::create_dir_all(&storage_dir)
             .map_err(|e| CkpError::IoError(format!("Failed to create storage: {}", e)))?;
 
-        let instance_dir = storage_dir.join(format!("{}.inst", instance_id));
+        let instance_dir = shard::entry_path(&storage_dir, &format!("{}.inst", instance_id), self.is_sharded(kernel_name));
+        let scanner = InstanceScanner::new(self.root.join("concepts").join(kernel_name), kernel_name.to_string());
+        let index_update = scanner.begin_index_update(&instance_dir);
         fs::create_dir_all(&instance_dir)
             .map_err(|e| CkpError::IoError(format!("Failed to create instance dir: {}", e)))?;
 
@@ -3095,37 +3512,30 @@ impl StorageDriver for FileSystemDriver {
         let payload_json = serde_json::to_string_pretty(&data)
             .map_err(|e| CkpError::Json(e))?;
 
-        fs::write(&payload_path, payload_json)
+        fs::write(&payload_path, &payload_json)
             .map_err(|e| CkpError::IoError(format!("Failed to write payload: {}", e)))?;
 
+        // Write receipt.bin (typed CKI receipt), or its binary form if negotiated
+        with_current_cause(Receipt::for_payload(instance_id, kernel_name, &data))
+            .with_digest("payload.json", payload_json.as_bytes())
+            .write_encoded(&instance_dir, self.receipt_encoding(kernel_name))?;
+        index_update.commit()?;
+
         // Return URN
         Ok(format!("ckp://{}#storage/{}", kernel_name, instance_id))
     }
 
-    fn record_transaction(&self, kernel_name: &str, transaction: JsonValue) -> Result<()> {
+    fn record_transaction(&self, kernel_name: &str, mut transaction: JsonValue) -> Result<()> {
         let tx_log = self.root.join("concepts").join(kernel_name).join("tx.jsonl");
-
-        // Ensure parent exists
-        if let Some(parent) = tx_log.parent() {
-            fs::create_dir_all(parent)
-                .map_err(|e| CkpError::IoError(format!("Failed to create tx dir: {}", e)))?;
-        }
+        self.redact_tx(kernel_name, &mut transaction)?;
 
         // Append transaction as single JSON line
         let tx_line = serde_json::to_string(&transaction)
             .map_err(|e| CkpError::Json(e))?;
 
-        let mut file = fs::OpenOptions::new()
-            .create(true)
-            .append(true)
-            .open(&tx_log)
-            .map_err(|e| CkpError::IoError(format!("Failed to open tx log: {}", e)))?;
-
-        use std::io::Write;
-        writeln!(file, "{}", tx_line)
-            .map_err(|e| CkpError::IoError(format!("Failed to write tx: {}", e)))?;
-
-        Ok(())
+        self.tx_commit(kernel_name)
+            .append(&self.tx_writers, &tx_log, tx_line)
+            .map_err(|e| CkpError::IoError(format!("Failed to write tx: {}", e)))
     }
 
     fn resolve_urn(&self, urn: &str) -> Result<StorageLocation> {
@@ -3144,9 +3554,9 @@ impl StorageDriver for FileSystemDriver {
                     _ => kernel_path.join(&stage),
                 };
 
-                // If there's a path component (e.g., instance ID), append it
+                // If there's a path component (e.g., instance ID), resolve it in either layout
                 if let Some(subpath) = parsed.path {
-                    base_path.join(&subpath)
+                    shard::locate(&base_path, &subpath)
                 } else {
                     base_path
                 }
@@ -353,9 +353,11 @@ impl GitDriver {
         let new_version = self.increment_version(bump)?;
         self.tag(&new_version, Some(message))?;
 
-        eprintln!(
-            "[GitDriver] [{}] Committed and tagged: {} ({})",
-            self.kernel_name, new_version, &commit_hash[..8]
+        tracing::info!(
+            kernel = %self.kernel_name,
+            version = %new_version,
+            commit = &commit_hash[..8],
+            "committed and tagged"
         );
 
         Ok(new_version)
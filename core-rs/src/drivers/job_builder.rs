@@ -0,0 +1,220 @@
+//! Typed construction of job files
+//!
+//! `JobFileBuilder` fills in the fields every job needs (transaction ID,
+//! timestamp, source) and stamps the protocol envelope (`JobStamp`): the
+//! protocol version that wrote the job, the source kernel's URN, the
+//! correlation and causation IDs tying it to the jobs that led to it, and
+//! an optional payload schema reference. Envelope fields are optional on
+//! disk, so jobs written by older runtimes still parse and runtimes that
+//! don't know them ignore them.
+
+use crate::drivers::JobFile;
+use crate::errors::{CkpError, Result};
+use chrono::Utc;
+use serde_json::Value as JsonValue;
+
+/// Protocol envelope of a job or edge request
+#[derive(Debug, Clone, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize, schemars::JsonSchema)]
+pub struct JobStamp {
+    /// Protocol version of the runtime that wrote the file
+    #[serde(rename = "protocolVersion", default, skip_serializing_if = "Option::is_none")]
+    pub protocol_version: Option<String>,
+
+    /// URN of the source kernel (e.g. ckp://Recipes.MixIngredients:v0.1)
+    #[serde(rename = "sourceUrn", default, skip_serializing_if = "Option::is_none")]
+    pub source_urn: Option<String>,
+
+    /// Transaction ID of the job that started the chain this job belongs to
+    #[serde(rename = "correlationId", default, skip_serializing_if = "Option::is_none")]
+    pub correlation_id: Option<String>,
+
+    /// Transaction ID of the job or instance that directly caused this one
+    #[serde(rename = "causationId", default, skip_serializing_if = "Option::is_none")]
+    pub causation_id: Option<String>,
+
+    /// URN or URL of the payload's schema (also read from `$schema`)
+    #[serde(default, alias = "$schema", skip_serializing_if = "Option::is_none")]
+    pub schema: Option<String>,
+
+    /// Processing priority, higher first (used by the priority processing order)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub priority: Option<i64>,
+}
+
+impl JobStamp {
+    /// Envelope written by this runtime on behalf of a source kernel URN
+    pub fn from_source(source_urn: impl Into<String>) -> Self {
+        Self {
+            protocol_version: Some(crate::VERSION.to_string()),
+            source_urn: Some(source_urn.into()),
+            ..Self::default()
+        }
+    }
+
+    /// Link to the transaction that caused this one
+    ///
+    /// The correlation ID is carried over from the cause, or starts at the
+    /// cause if it had none.
+    pub fn caused_by(mut self, tx_id: &str, correlation_id: Option<&str>) -> Self {
+        self.causation_id = Some(tx_id.to_string());
+        self.correlation_id = Some(correlation_id.unwrap_or(tx_id).to_string());
+        self
+    }
+}
+
+/// Builder for `JobFile`
+///
+/// # Example
+/// ```
+/// use ckp_core::drivers::JobFileBuilder;
+/// use serde_json::json;
+///
+/// let job = JobFileBuilder::new("Recipes.BakeCake", json!({"flour": 2}))
+///     .with_source("Recipes.MixIngredients", "ckp://Recipes.MixIngredients:v0.1")
+///     .caused_by("1764410400000-ab12cd34", None)
+///     .build()
+///     .unwrap();
+///
+/// assert_eq!(job.source, "Recipes.MixIngredients");
+/// assert_eq!(job.stamp.protocol_version.as_deref(), Some(ckp_core::VERSION));
+/// assert_eq!(job.stamp.correlation_id.as_deref(), Some("1764410400000-ab12cd34"));
+/// ```
+#[derive(Debug, Clone)]
+pub struct JobFileBuilder {
+    target: String,
+    payload: JsonValue,
+    tx_id: Option<String>,
+    timestamp: Option<String>,
+    source: Option<String>,
+    traceparent: Option<String>,
+    stamp: JobStamp,
+}
+
+impl JobFileBuilder {
+    /// Job for a target kernel name or URN
+    pub fn new(target: impl Into<String>, payload: JsonValue) -> Self {
+        Self {
+            target: target.into(),
+            payload,
+            tx_id: None,
+            timestamp: None,
+            source: None,
+            traceparent: None,
+            stamp: JobStamp { protocol_version: Some(crate::VERSION.to_string()), ..JobStamp::default() },
+        }
+    }
+
+    /// Use this transaction ID instead of generating one
+    pub fn with_tx_id(mut self, tx_id: impl Into<String>) -> Self {
+        self.tx_id = Some(tx_id.into());
+        self
+    }
+
+    /// Use this timestamp instead of the time of `build`
+    pub fn with_timestamp(mut self, timestamp: impl Into<String>) -> Self {
+        self.timestamp = Some(timestamp.into());
+        self
+    }
+
+    /// Source kernel name and URN
+    ///
+    /// Without a source the job is from 'external'.
+    pub fn with_source(mut self, kernel: impl Into<String>, urn: impl Into<String>) -> Self {
+        self.source = Some(kernel.into());
+        self.stamp.source_urn = Some(urn.into());
+        self
+    }
+
+    /// Link the job to the transaction that caused it (see `JobStamp::caused_by`)
+    pub fn caused_by(mut self, tx_id: &str, correlation_id: Option<&str>) -> Self {
+        self.stamp = self.stamp.caused_by(tx_id, correlation_id);
+        self
+    }
+
+    /// Start or continue a correlation chain explicitly
+    pub fn with_correlation_id(mut self, correlation_id: impl Into<String>) -> Self {
+        self.stamp.correlation_id = Some(correlation_id.into());
+        self
+    }
+
+    /// Reference the payload's schema
+    pub fn with_schema(mut self, schema: impl Into<String>) -> Self {
+        self.stamp.schema = Some(schema.into());
+        self
+    }
+
+    /// Processing priority for kernels processing their inbox priority-first
+    pub fn with_priority(mut self, priority: i64) -> Self {
+        self.stamp.priority = Some(priority);
+        self
+    }
+
+    /// W3C trace context of the request producing the job
+    pub fn with_traceparent(mut self, traceparent: impl Into<String>) -> Self {
+        self.traceparent = Some(traceparent.into());
+        self
+    }
+
+    /// Build the job file
+    ///
+    /// # Errors
+    /// `CkpError::ValidationError` if the target is empty
+    pub fn build(self) -> Result<JobFile> {
+        if self.target.trim().is_empty() {
+            return Err(CkpError::ValidationError("Job target must not be empty".to_string()));
+        }
+
+        Ok(JobFile {
+            target: self.target,
+            payload: self.payload,
+            timestamp: self.timestamp.unwrap_or_else(|| Utc::now().to_rfc3339()),
+            tx_id: self.tx_id.unwrap_or_else(generate_tx_id),
+            source: self.source.unwrap_or_else(|| "external".to_string()),
+            traceparent: self.traceparent,
+            stamp: self.stamp,
+        })
+    }
+}
+
+/// Transaction ID in the format {timestamp}-{8 hex chars}
+fn generate_tx_id() -> String {
+    format!("{}-{:08x}", Utc::now().timestamp_millis(), rand::random::<u32>())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_builder_stamps_envelope() {
+        let job = JobFileBuilder::new("Recipes.BakeCake", json!({"flour": 2})).build().unwrap();
+        assert_eq!(job.source, "external");
+        assert_eq!(job.stamp.source_urn, None);
+        let (millis, short_id) = job.tx_id.split_once('-').unwrap();
+        assert!(millis.parse::<i64>().is_ok() && short_id.len() == 8);
+
+        // A follow-up keeps the chain's correlation ID
+        let follow_up = JobFileBuilder::new("Recipes.Serve", json!({}))
+            .with_source("Recipes.BakeCake", "ckp://Recipes.BakeCake:v0.1")
+            .caused_by("1764410400001-00000002", Some("1764410400000-00000001"))
+            .with_schema("ckp://Recipes.Serve:v0.1#schema/order")
+            .build()
+            .unwrap();
+        let value = serde_json::to_value(&follow_up).unwrap();
+        assert_eq!(value["protocolVersion"], crate::VERSION);
+        assert_eq!(value["sourceUrn"], "ckp://Recipes.BakeCake:v0.1");
+        assert_eq!(value["correlationId"], "1764410400000-00000001");
+        assert_eq!(value["causationId"], "1764410400001-00000002");
+
+        // Jobs without an envelope still parse
+        let bare: JobFile = serde_json::from_value(json!({
+            "target": "Recipes.Serve", "payload": {}, "timestamp": "2025-11-29T10:00:00Z",
+            "txId": "tx-1", "source": "external"
+        }))
+        .unwrap();
+        assert_eq!(bare.stamp, JobStamp::default());
+
+        assert!(JobFileBuilder::new(" ", json!({})).build().is_err());
+    }
+}
@@ -0,0 +1,244 @@
+//! Memory-mapped reads of job and receipt files
+//!
+//! Reading a large job with `fs::read` and then deserializing it keeps two
+//! copies of the payload alive: the file buffer and the parsed value.
+//! `MappedFile` maps files of `MMAP_THRESHOLD` bytes or more instead, so
+//! decoders read straight from the page cache, and `JobView` parses a JSON
+//! job into views borrowed from those bytes, leaving the payload as raw JSON
+//! until it is needed.
+//!
+//! Mapping is only used on Unix. A mapped file must not be truncated while
+//! the mapping lives; job and receipt files are written once and afterwards
+//! only renamed or removed, which is safe. Elsewhere, and for small files,
+//! reads are buffered.
+
+use crate::drivers::{Encoding, JobFile, JobStamp};
+use crate::errors::{CkpError, Result};
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use serde_json::value::RawValue;
+use std::borrow::Cow;
+use std::fs::File;
+use std::io::Read;
+use std::ops::Deref;
+use std::path::Path;
+
+/// Files at least this large are memory-mapped
+pub const MMAP_THRESHOLD: u64 = 64 * 1024;
+
+/// Contents of a file, mapped or read into memory
+#[derive(Debug)]
+pub struct MappedFile {
+    bytes: Bytes,
+}
+
+#[derive(Debug)]
+enum Bytes {
+    #[cfg(unix)]
+    Mapped(memmap2::Mmap),
+    Buffered(Vec<u8>),
+}
+
+impl MappedFile {
+    /// Open a file, mapping it if it is large enough
+    pub fn open(path: &Path) -> std::io::Result<Self> {
+        let mut file = File::open(path)?;
+        let len = file.metadata()?.len();
+        if let Some(bytes) = map(&file, len)? {
+            return Ok(Self { bytes });
+        }
+
+        let mut buffer = Vec::with_capacity(len as usize);
+        file.read_to_end(&mut buffer)?;
+        Ok(Self { bytes: Bytes::Buffered(buffer) })
+    }
+
+    /// Whether the contents are memory-mapped rather than buffered
+    pub fn is_mapped(&self) -> bool {
+        match self.bytes {
+            #[cfg(unix)]
+            Bytes::Mapped(_) => true,
+            Bytes::Buffered(_) => false,
+        }
+    }
+
+    /// Decode the contents in the given encoding
+    pub fn decode<T: DeserializeOwned>(&self, encoding: Encoding) -> Result<T> {
+        encoding.decode(self)
+    }
+}
+
+#[cfg(unix)]
+fn map(file: &File, len: u64) -> std::io::Result<Option<Bytes>> {
+    if len < MMAP_THRESHOLD {
+        return Ok(None);
+    }
+    // SAFETY: the file is not truncated while mapped (see module docs)
+    let map = unsafe { memmap2::Mmap::map(file)? };
+    Ok(Some(Bytes::Mapped(map)))
+}
+
+#[cfg(not(unix))]
+fn map(_file: &File, _len: u64) -> std::io::Result<Option<Bytes>> {
+    Ok(None)
+}
+
+impl Deref for MappedFile {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        match &self.bytes {
+            #[cfg(unix)]
+            Bytes::Mapped(map) => map,
+            Bytes::Buffered(buffer) => buffer,
+        }
+    }
+}
+
+impl AsRef<[u8]> for MappedFile {
+    fn as_ref(&self) -> &[u8] {
+        self
+    }
+}
+
+/// A JSON job borrowed from the bytes it was parsed from
+///
+/// # Example
+/// ```
+/// use ckp_core::drivers::JobView;
+///
+/// let bytes = br#"{"target":"Shop.Orders","payload":{"order":42},"timestamp":"2025-11-29T10:00:00Z","txId":"tx-1","source":"external"}"#;
+/// let job = JobView::parse(bytes).unwrap();
+/// assert_eq!(job.tx_id, "tx-1");
+/// assert_eq!(job.payload.get(), r#"{"order":42}"#);
+/// ```
+#[derive(Debug, Deserialize)]
+pub struct JobView<'a> {
+    /// Target kernel name or URN
+    #[serde(borrow)]
+    pub target: Cow<'a, str>,
+
+    /// Job payload, not yet parsed
+    #[serde(borrow)]
+    pub payload: &'a RawValue,
+
+    /// ISO 8601 timestamp
+    #[serde(borrow)]
+    pub timestamp: Cow<'a, str>,
+
+    /// Transaction ID
+    #[serde(borrow, rename = "txId")]
+    pub tx_id: Cow<'a, str>,
+
+    /// Source kernel name or 'external'
+    #[serde(borrow)]
+    pub source: Cow<'a, str>,
+
+    /// W3C trace context of the request that produced the job
+    #[serde(borrow, default)]
+    pub traceparent: Option<Cow<'a, str>>,
+
+    /// Protocol version of the runtime that wrote the job
+    #[serde(borrow, default, rename = "protocolVersion")]
+    pub protocol_version: Option<Cow<'a, str>>,
+
+    /// URN of the source kernel
+    #[serde(borrow, default, rename = "sourceUrn")]
+    pub source_urn: Option<Cow<'a, str>>,
+
+    /// Transaction ID the job's chain started with
+    #[serde(borrow, default, rename = "correlationId")]
+    pub correlation_id: Option<Cow<'a, str>>,
+
+    /// Transaction ID that caused the job
+    #[serde(borrow, default, rename = "causationId")]
+    pub causation_id: Option<Cow<'a, str>>,
+
+    /// Payload schema reference
+    #[serde(borrow, default)]
+    pub schema: Option<Cow<'a, str>>,
+
+    /// Processing priority
+    #[serde(default)]
+    pub priority: Option<i64>,
+}
+
+impl<'a> JobView<'a> {
+    /// Parse a JSON job without copying its payload
+    pub fn parse(bytes: &'a [u8]) -> Result<Self> {
+        serde_json::from_slice(bytes).map_err(|e| CkpError::ParseError(format!("Failed to parse job: {}", e)))
+    }
+
+    /// Deserialize the payload, borrowing from the job bytes where possible
+    pub fn payload_as<T: Deserialize<'a>>(&self) -> Result<T> {
+        serde_json::from_str(self.payload.get())
+            .map_err(|e| CkpError::ParseError(format!("Failed to parse job payload: {}", e)))
+    }
+
+    /// Owned job file, parsing the payload
+    pub fn to_job_file(&self) -> Result<JobFile> {
+        Ok(JobFile {
+            target: self.target.to_string(),
+            payload: self.payload_as()?,
+            timestamp: self.timestamp.to_string(),
+            tx_id: self.tx_id.to_string(),
+            source: self.source.to_string(),
+            traceparent: self.traceparent.as_ref().map(|t| t.to_string()),
+            stamp: JobStamp {
+                protocol_version: self.protocol_version.as_ref().map(|v| v.to_string()),
+                source_urn: self.source_urn.as_ref().map(|v| v.to_string()),
+                correlation_id: self.correlation_id.as_ref().map(|v| v.to_string()),
+                causation_id: self.causation_id.as_ref().map(|v| v.to_string()),
+                schema: self.schema.as_ref().map(|v| v.to_string()),
+                priority: self.priority,
+            },
+        })
+    }
+}
+
+/// Read and decode a job or receipt file
+///
+/// # Errors
+/// `CkpError::IoError` naming the file if it cannot be read
+pub fn read_encoded<T: DeserializeOwned>(path: &Path, encoding: Encoding) -> Result<T> {
+    let file = MappedFile::open(path)
+        .map_err(|e| CkpError::IoError(format!("Failed to read {}: {}", path.display(), e)))?;
+    file.decode(encoding)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_large_job_is_mapped_and_borrowed() {
+        let temp = TempDir::new().unwrap();
+        let job = JobFile {
+            target: "Test.Kernel".to_string(),
+            payload: json!({"blob": "x".repeat(MMAP_THRESHOLD as usize)}),
+            timestamp: "2025-11-29T10:00:00Z".to_string(),
+            tx_id: "1764410400000-ab12cd34".to_string(),
+            source: "external".to_string(),
+            traceparent: None,
+            stamp: Default::default(),
+        };
+        let path = temp.path().join("1764410400000-ab12cd34.job");
+        fs::write(&path, serde_json::to_vec(&job).unwrap()).unwrap();
+
+        let file = MappedFile::open(&path).unwrap();
+        assert_eq!(file.is_mapped(), cfg!(unix));
+        let view = JobView::parse(&file).unwrap();
+        assert!(matches!(view.tx_id, Cow::Borrowed("1764410400000-ab12cd34")));
+        assert_eq!(view.to_job_file().unwrap().payload, job.payload);
+
+        let decoded: JobFile = read_encoded(&path, Encoding::Json).unwrap();
+        assert_eq!(decoded.tx_id, job.tx_id);
+
+        // Small files are read into memory
+        fs::write(&path, b"{}").unwrap();
+        assert!(!MappedFile::open(&path).unwrap().is_mapped());
+    }
+}
@@ -1,24 +1,62 @@
 //! Drivers module for storage operations
 //!
 //! Provides abstract storage interface (StorageDriver trait) and implementations:
-//! - FileSystemDriver: Local filesystem storage
+//! - FileSystemDriver: Local filesystem storage (tx.jsonl via TxCommit,
+//!   compacted into segments by TxCompactor)
 //! - HttpDriver: Remote HTTP storage
 //! - GitDriver: Git versioning for concept kernels
 //! - VersionDriver: Unified versioning abstraction (git, s3, postgres, filesystem)
 //! - Future: S3Driver, RedisDriver, PostgresDriver, IpfsDriver
 
 mod traits;
+mod job_builder;
+mod encoding;
+mod mapped;
+mod txlog;
+mod compaction;
+mod queue_counts;
+mod settings_cache;
 mod filesystem;
 mod http;
 mod git;
 pub mod version;
 
 pub use traits::{StorageDriver, StorageDriverFactory, StorageLocation, JobFile, JobHandle};
-pub use filesystem::FileSystemDriver;
+pub use job_builder::{JobFileBuilder, JobStamp};
+pub use encoding::{Encoding, ENCODING_CAPABILITY_PREFIX, ENCODING_ENV};
+pub use mapped::{read_encoded, JobView, MappedFile, MMAP_THRESHOLD};
+pub use txlog::{TxCommit, TxWriters, GROUP_COMMIT_CAPABILITY, GROUP_COMMIT_WINDOW};
+pub use compaction::{TxCompactor, TxSnapshot, DEFAULT_KEEP_RECORDS, TX_SEGMENTS_DIR, TX_SNAPSHOT_FILE};
+pub use queue_counts::{QueueCounters, QueueStats, ESTIMATE_MAX_AGE, QUEUE_COUNTS_FILE, RECONCILE_INTERVAL};
+pub(crate) use queue_counts::is_queue_entry;
+pub use filesystem::{FileSystemDriver, Transaction};
 pub use http::HttpDriver;
 pub use git::{GitDriver, VersionBump};
 pub use version::{VersionDriver, VersionInfo, VersionBackend, VersionDriverFactory, VersionedKernel};
 
+use crate::errors::{CkpError, Result};
+
+/// Run blocking storage I/O off the async executor
+///
+/// Inside a tokio runtime the closure runs on the blocking pool, in the
+/// caller's tracing span; without a runtime it runs inline.
+pub(crate) async fn run_blocking<T, F>(f: F) -> Result<T>
+where
+    F: FnOnce() -> Result<T> + Send + 'static,
+    T: Send + 'static,
+{
+    let Ok(handle) = tokio::runtime::Handle::try_current() else {
+        return f();
+    };
+
+    let span = tracing::Span::current();
+    match handle.spawn_blocking(move || span.in_scope(f)).await {
+        Ok(result) => result,
+        Err(e) if e.is_panic() => std::panic::resume_unwind(e.into_panic()),
+        Err(e) => Err(CkpError::IoError(format!("Blocking task failed: {}", e))),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -63,6 +101,8 @@ mod tests {
             timestamp: Utc::now().to_rfc3339(),
             tx_id: "20251129-abc123".to_string(),
             source: "external".to_string(),
+            traceparent: None,
+            stamp: Default::default(),
         };
 
         accepts_job_file(job_file.clone());
@@ -0,0 +1,276 @@
+//! Incrementally maintained queue statistics
+//!
+//! Counting a kernel's queues means listing inbox, staging and ready (and
+//! every inbox shard) on each call. Instead, the counts are kept in
+//! `queue/.counts.json`: the driver adjusts them as it writes, moves and
+//! archives jobs, and the governor reconciles them with a full recount every
+//! `RECONCILE_INTERVAL`, which picks up jobs added or removed by anything
+//! else (tool runtimes, control-socket drains, files moved by hand).
+//!
+//! `QueueCounters::estimate` reads the counters; `QueueCounters::recount`
+//! lists the directories and resets them. Counters are only adjusted once a
+//! recount has created them, and are ignored once they are older than
+//! `ESTIMATE_MAX_AGE`, so a kernel without a governor falls back to exact
+//! counts.
+
+use super::txlog::{lock, unlock};
+use crate::errors::{CkpError, Result};
+use crate::storage::shard;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fs::{File, OpenOptions};
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// Counters file, relative to the kernel's queue directory
+pub const QUEUE_COUNTS_FILE: &str = ".counts.json";
+
+/// How often the governor reconciles the counters with a recount
+pub const RECONCILE_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Age after which counters are no longer trusted as an estimate
+pub const ESTIMATE_MAX_AGE: Duration = Duration::from_secs(300);
+
+/// Queue statistics
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct QueueStats {
+    /// Count of jobs in inbox
+    pub inbox: usize,
+
+    /// Count of jobs in staging
+    pub staging: usize,
+
+    /// Count of jobs in ready
+    pub ready: usize,
+}
+
+impl QueueStats {
+    /// Counter for a queue directory name (inbox, staging or ready)
+    fn stage_mut(&mut self, stage: &str) -> Option<&mut usize> {
+        match stage {
+            "inbox" => Some(&mut self.inbox),
+            "staging" => Some(&mut self.staging),
+            "ready" => Some(&mut self.ready),
+            _ => None,
+        }
+    }
+}
+
+/// Contents of the counters file
+#[derive(Debug, Serialize, Deserialize)]
+struct Counters {
+    #[serde(flatten)]
+    stats: QueueStats,
+
+    /// Time of the last full recount
+    #[serde(rename = "reconciledAt")]
+    reconciled_at: DateTime<Utc>,
+}
+
+/// Queue counters of one kernel
+#[derive(Debug, Clone)]
+pub struct QueueCounters {
+    queue_dir: PathBuf,
+}
+
+impl QueueCounters {
+    /// Counters for a kernel directory (concepts/{kernel})
+    pub fn new(kernel_dir: &Path) -> Self {
+        Self { queue_dir: kernel_dir.join("queue") }
+    }
+
+    fn counts_path(&self) -> PathBuf {
+        self.queue_dir.join(QUEUE_COUNTS_FILE)
+    }
+
+    /// Counted statistics, if counters exist and were reconciled recently
+    ///
+    /// Jobs added or removed outside the driver since the last recount are
+    /// not reflected.
+    pub fn estimate(&self) -> Option<QueueStats> {
+        let mut file = File::open(self.counts_path()).ok()?;
+        lock(&file).ok()?;
+        let counters = read_counters(&mut file);
+        unlock(&file);
+
+        let counters = counters.ok()?;
+        let age = (Utc::now() - counters.reconciled_at).to_std().unwrap_or_default();
+        (age <= ESTIMATE_MAX_AGE).then_some(counters.stats)
+    }
+
+    /// Count the queue directories and reset the counters to the result
+    ///
+    /// # Errors
+    /// `CkpError::IoError` if the counters cannot be written
+    pub fn recount(&self) -> Result<QueueStats> {
+        let mut stats = QueueStats::default();
+        if !self.queue_dir.is_dir() {
+            return Ok(stats);
+        }
+
+        let mut file = OpenOptions::new()
+            .create(true)
+            .truncate(false)
+            .read(true)
+            .write(true)
+            .open(self.counts_path())
+            .map_err(|e| CkpError::IoError(format!("Failed to open queue counters: {}", e)))?;
+
+        // Hold the lock while counting so adjustments wait for the new counts
+        lock(&file)?;
+        for stage in ["inbox", "staging", "ready"] {
+            if let Some(count) = stats.stage_mut(stage) {
+                *count = count_jobs(&self.queue_dir.join(stage));
+            }
+        }
+        let result = write_counters(&mut file, &Counters { stats, reconciled_at: Utc::now() });
+        unlock(&file);
+
+        result?;
+        Ok(stats)
+    }
+
+    /// Recount if the last recount is older than `interval`
+    ///
+    /// # Returns
+    /// The new statistics if a recount was due
+    pub fn reconcile_if_due(&self, interval: Duration) -> Result<Option<QueueStats>> {
+        let due = File::open(self.counts_path())
+            .ok()
+            .and_then(|mut file| read_counters(&mut file).ok())
+            .map(|counters| (Utc::now() - counters.reconciled_at).to_std().unwrap_or_default() >= interval)
+            .unwrap_or(true);
+
+        if due {
+            self.recount().map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Count a job added to a queue
+    ///
+    /// Best effort: failures are left to the next reconciliation.
+    pub fn record_added(job_path: &Path) {
+        adjust(job_path, 1);
+    }
+
+    /// Count a job removed from a queue
+    pub fn record_removed(job_path: &Path) {
+        adjust(job_path, -1);
+    }
+}
+
+/// Whether a queue entry is a job (`.job`, or `.inst` for instance queues)
+pub(crate) fn is_queue_entry(path: &Path) -> bool {
+    path.extension()
+        .and_then(|s| s.to_str())
+        .map(|ext| ext == "job" || ext == "inst")
+        .unwrap_or(false)
+}
+
+/// Jobs in a queue directory, including its shard directories
+fn count_jobs(dir: &Path) -> usize {
+    shard::list_entries(dir)
+        .into_iter()
+        .filter(|path| is_queue_entry(path))
+        .count()
+}
+
+fn read_counters(file: &mut File) -> Result<Counters> {
+    let mut content = String::new();
+    file.read_to_string(&mut content)?;
+    serde_json::from_str(&content)
+        .map_err(|e| CkpError::ParseError(format!("Failed to parse queue counters: {}", e)))
+}
+
+fn write_counters(file: &mut File, counters: &Counters) -> Result<()> {
+    let content = serde_json::to_vec(counters)?;
+    file.seek(SeekFrom::Start(0))?;
+    file.set_len(0)?;
+    file.write_all(&content)?;
+    Ok(())
+}
+
+/// Apply a change to the counter of the queue a job path is in
+fn adjust(job_path: &Path, delta: isize) {
+    // queue/{stage}/job or queue/{stage}/{shard}/job
+    let Some(stage_dir) = shard::entry_dir(job_path) else {
+        return;
+    };
+    let (Some(stage), Some(queue_dir)) = (stage_dir.file_name().and_then(|n| n.to_str()), stage_dir.parent()) else {
+        return;
+    };
+    if queue_dir.file_name().is_none_or(|name| name != "queue") {
+        return;
+    }
+
+    // Counters that don't exist yet are created by the first recount
+    let Ok(mut file) = OpenOptions::new().read(true).write(true).open(queue_dir.join(QUEUE_COUNTS_FILE)) else {
+        return;
+    };
+    if lock(&file).is_err() {
+        return;
+    }
+    let result = read_counters(&mut file).and_then(|mut counters| {
+        if let Some(count) = counters.stats.stage_mut(stage) {
+            *count = count.saturating_add_signed(delta);
+            write_counters(&mut file, &counters)?;
+        }
+        Ok(())
+    });
+    unlock(&file);
+
+    if let Err(e) = result {
+        tracing::debug!(path = %job_path.display(), error = %e, "failed to adjust counters");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_counters_follow_driver_operations() {
+        let temp = TempDir::new().unwrap();
+        let kernel_dir = temp.path().join("concepts/Test.Kernel");
+        for stage in ["inbox", "staging", "ready"] {
+            fs::create_dir_all(kernel_dir.join("queue").join(stage)).unwrap();
+        }
+        let counters = QueueCounters::new(&kernel_dir);
+        let inbox = kernel_dir.join("queue/inbox");
+
+        // Nothing is counted until the first recount
+        QueueCounters::record_added(&inbox.join("a.job"));
+        assert_eq!(counters.estimate(), None);
+
+        fs::write(inbox.join("a.job"), "{}").unwrap();
+        fs::create_dir_all(inbox.join("cd")).unwrap();
+        fs::write(inbox.join("cd/1764410400000-cd12.job"), "{}").unwrap();
+        fs::write(inbox.join(".gitkeep"), "").unwrap();
+        fs::write(inbox.join("a.job.tmp"), "{}").unwrap();
+        fs::write(inbox.join(".DS_Store"), "").unwrap();
+        let stats = counters.recount().unwrap();
+        assert_eq!(stats, QueueStats { inbox: 2, staging: 0, ready: 0 });
+        assert_eq!(counters.estimate(), Some(stats));
+
+        // A move from inbox to staging and a new sharded job
+        QueueCounters::record_removed(&inbox.join("a.job"));
+        QueueCounters::record_added(&kernel_dir.join("queue/staging/a.job"));
+        QueueCounters::record_added(&inbox.join("ef/1764410400000-ef34.job"));
+        assert_eq!(counters.estimate(), Some(QueueStats { inbox: 2, staging: 1, ready: 0 }));
+
+        // Paths outside the counted queues are ignored
+        QueueCounters::record_added(&kernel_dir.join("queue/archive/a.job"));
+        QueueCounters::record_added(&kernel_dir.join("storage/a.inst"));
+        assert_eq!(counters.estimate(), Some(QueueStats { inbox: 2, staging: 1, ready: 0 }));
+
+        // Reconciliation resets drift, but only once it is due
+        assert_eq!(counters.reconcile_if_due(RECONCILE_INTERVAL).unwrap(), None);
+        assert_eq!(counters.reconcile_if_due(Duration::ZERO).unwrap(), Some(stats));
+        assert_eq!(counters.estimate(), Some(stats));
+    }
+}
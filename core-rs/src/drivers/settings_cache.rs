@@ -0,0 +1,159 @@
+//! Ontology settings cached per driver
+//!
+//! Encoding negotiation, sharding, group commit and tx redaction each need a
+//! kernel's conceptkernel.yaml, and quota checks the project's .ckproject.
+//! Instead of parsing them on every job, mint and transaction record, a
+//! driver resolves them once and keeps the result until the file's
+//! modification time or length changes.
+
+use crate::compliance::RedactionRules;
+use crate::errors::{CkpError, Result};
+use crate::ontology::OntologyReader;
+use crate::project::{ProjectConfig, QuotaConfig};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::SystemTime;
+
+/// Modification time and length a cached value was read at
+type FileStamp = Option<(Option<SystemTime>, u64)>;
+
+fn stamp(path: &Path) -> FileStamp {
+    fs::metadata(path).ok().map(|meta| (meta.modified().ok(), meta.len()))
+}
+
+/// Settings the driver reads from one kernel's ontology
+#[derive(Debug)]
+pub(crate) struct KernelSettings {
+    pub capabilities: Vec<String>,
+
+    /// Kernels named in the notification contract
+    pub notified: Vec<String>,
+
+    /// Compiled `spec.tx_redaction`, or why it does not compile
+    redaction: std::result::Result<Option<RedactionRules>, String>,
+}
+
+impl KernelSettings {
+    pub fn has_capability(&self, capability: &str) -> bool {
+        self.capabilities.iter().any(|c| c == capability)
+    }
+
+    pub fn redaction(&self) -> Result<Option<&RedactionRules>> {
+        match &self.redaction {
+            Ok(rules) => Ok(rules.as_ref()),
+            Err(e) => Err(CkpError::ValidationError(e.clone())),
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct Entries {
+    /// Per kernel; `None` if its ontology cannot be read
+    kernels: HashMap<String, (FileStamp, Option<Arc<KernelSettings>>)>,
+    quotas: Option<(FileStamp, Option<QuotaConfig>)>,
+}
+
+/// Settings cache shared by a driver and its clones
+#[derive(Debug, Clone)]
+pub(crate) struct SettingsCache {
+    root: PathBuf,
+    entries: Arc<Mutex<Entries>>,
+}
+
+impl SettingsCache {
+    pub fn new(root: PathBuf) -> Self {
+        Self { root, entries: Arc::default() }
+    }
+
+    /// Settings of a kernel, `None` if its ontology cannot be read
+    pub fn kernel(&self, kernel_name: &str) -> Option<Arc<KernelSettings>> {
+        let path = self.root.join("concepts").join(kernel_name).join("conceptkernel.yaml");
+        let current = stamp(&path);
+
+        let mut entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some((cached, settings)) = entries.kernels.get(kernel_name) {
+            if *cached == current {
+                return settings.clone();
+            }
+        }
+
+        let settings = OntologyReader::new(self.root.clone())
+            .read_by_kernel_name(kernel_name)
+            .ok()
+            .map(|ontology| {
+                let (contract, redaction) = match ontology.spec {
+                    Some(spec) => (spec.notification_contract.unwrap_or_default(), spec.tx_redaction),
+                    None => (Vec::new(), None),
+                };
+                Arc::new(KernelSettings {
+                    capabilities: ontology.capabilities,
+                    notified: contract.into_iter().map(|entry| entry.target_kernel).collect(),
+                    redaction: redaction
+                        .map(|config| RedactionRules::from_config(&config))
+                        .transpose()
+                        .map_err(|e| e.to_string()),
+                })
+            });
+        entries.kernels.insert(kernel_name.to_string(), (current, settings.clone()));
+        settings
+    }
+
+    /// The project's quotas, `None` without any (or a readable .ckproject)
+    pub fn quotas(&self) -> Option<QuotaConfig> {
+        let path = self.root.join(".ckproject");
+        let current = stamp(&path);
+
+        let mut entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some((cached, quotas)) = &entries.quotas {
+            if *cached == current {
+                return quotas.clone();
+            }
+        }
+
+        let quotas = ProjectConfig::load_raw(&path).ok().and_then(|config| config.spec.quotas);
+        entries.quotas = Some((current, quotas.clone()));
+        quotas
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn write_ontology(root: &Path, capabilities: &str) {
+        let kernel_dir = root.join("concepts/Orders");
+        fs::create_dir_all(&kernel_dir).unwrap();
+        fs::write(
+            kernel_dir.join("conceptkernel.yaml"),
+            format!(
+                "apiVersion: conceptkernel/v1\nkind: Ontology\nmetadata:\n  name: ckp://Orders:v0.1\n  type: node:cold\n  version: v0.1\ncapabilities: [{}]\nspec: {{}}\n",
+                capabilities
+            ),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_settings_are_reread_only_when_the_ontology_changes() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path();
+        write_ontology(root, "sharding");
+        let cache = SettingsCache::new(root.to_path_buf());
+
+        let first = cache.kernel("Orders").unwrap();
+        assert!(first.has_capability("sharding"));
+        assert!(Arc::ptr_eq(&first, &cache.kernel("Orders").unwrap()));
+        assert!(Arc::ptr_eq(&first, &cache.clone().kernel("Orders").unwrap()));
+
+        write_ontology(root, "sharding, tx:group-commit");
+        let second = cache.kernel("Orders").unwrap();
+        assert!(!Arc::ptr_eq(&first, &second));
+        assert!(second.has_capability("tx:group-commit"));
+
+        assert!(cache.kernel("Missing").is_none());
+        assert!(cache.quotas().is_none());
+    }
+}
@@ -6,6 +6,7 @@
 //! - HttpDriver (remote HTTP)
 //! - Future: S3Driver, RedisDriver, PostgresDriver, IpfsDriver
 
+use crate::drivers::JobStamp;
 use crate::errors::Result;
 use serde_json::Value as JsonValue;
 use std::path::PathBuf;
@@ -28,7 +29,7 @@ pub enum StorageLocation {
 /// Job file structure
 ///
 /// Standard format for jobs written to inbox queues
-#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, schemars::JsonSchema)]
 pub struct JobFile {
     /// Target kernel name or URN
     pub target: String,
@@ -45,6 +46,14 @@ pub struct JobFile {
 
     /// Source kernel name or 'external'
     pub source: String,
+
+    /// W3C trace context of the request that produced the job
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub traceparent: Option<String>,
+
+    /// Protocol envelope (version, source URN, correlation and causation)
+    #[serde(flatten)]
+    pub stamp: JobStamp,
 }
 
 /// Job handle returned when reading jobs
@@ -78,6 +87,11 @@ impl JobHandle {
         &self.content.source
     }
 
+    /// Get W3C trace context the job was emitted under
+    pub fn traceparent(&self) -> Option<&str> {
+        self.content.traceparent.as_deref()
+    }
+
     /// Get full job content
     pub fn content(&self) -> &JobFile {
         &self.content
@@ -128,6 +142,7 @@ pub trait StorageDriver: Send + Sync {
     /// # Protocol Semantics
     ///
     /// - Job appears atomically in target's inbox
+    /// - Jobs beyond the target's queue quota fail with `CkpError::QuotaExceeded`
     /// - Governor watching inbox will detect immediately (if event-driven)
     /// - Job format must be protocol-compliant JSON
     fn write_job(&self, target_urn: &str, job: JobFile) -> Result<String>;
@@ -366,6 +381,8 @@ mod tests {
             timestamp: "2025-11-29T10:00:00Z".to_string(),
             tx_id: "tx_20251129_100000_abc".to_string(),
             source: "Test.Source".to_string(),
+            traceparent: None,
+            stamp: Default::default(),
         };
 
         // Serialize to JSON
@@ -380,6 +397,26 @@ mod tests {
         assert_eq!(job2.source, "Test.Source");
     }
 
+    #[test]
+    fn test_job_file_traceparent_optional() {
+        // Jobs written before trace propagation still parse
+        let job: JobFile = serde_json::from_value(json!({
+            "target": "Test.Target",
+            "payload": {},
+            "timestamp": "2025-11-29T10:00:00Z",
+            "txId": "tx_123",
+            "source": "external"
+        }))
+        .unwrap();
+        assert!(job.traceparent.is_none());
+        assert!(!serde_json::to_string(&job).unwrap().contains("traceparent"));
+
+        let traceparent = "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01";
+        let traced = JobFile { traceparent: Some(traceparent.to_string()), ..job };
+        let json = serde_json::to_value(&traced).unwrap();
+        assert_eq!(json["traceparent"], traceparent);
+    }
+
     #[test]
     fn test_job_handle_getters() {
         let job_content = JobFile {
@@ -388,6 +425,8 @@ mod tests {
             timestamp: "2025-11-29T10:00:00Z".to_string(),
             tx_id: "tx_test_123".to_string(),
             source: "Test.Source".to_string(),
+            traceparent: None,
+            stamp: Default::default(),
         };
 
         let handle = JobHandle {
@@ -450,6 +489,8 @@ mod tests {
             timestamp: "2025-11-29T10:00:00Z".to_string(),
             tx_id: "tx_123".to_string(),
             source: "Source".to_string(),
+            traceparent: None,
+            stamp: Default::default(),
         };
 
         let cloned = job.clone();
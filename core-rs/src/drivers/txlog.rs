@@ -0,0 +1,292 @@
+//! Transaction log (tx.jsonl) writers
+//!
+//! By default every record is appended under its own exclusive `flock` and
+//! synced to disk, which dominates under high emit rates. Kernels can opt
+//! into group commit in their ontology:
+//!
+//! ```yaml
+//! capabilities:
+//!   - tx:group-commit
+//! ```
+//!
+//! Records are then handed to one writer thread per log file, which collects
+//! everything arriving within `GROUP_COMMIT_WINDOW` and appends the batch
+//! under a single lock followed by a single fsync. Records keep the order in
+//! which they were submitted, and `record_transaction` returns only once its
+//! batch is on disk, so a record that was acknowledged survives a crash.
+//!
+//! Writer threads belong to a `TxWriters` registry held by the driver (and
+//! shared with its clones); they commit what they hold and stop when the
+//! last clone is dropped or the registry is shut down.
+
+use crate::errors::{CkpError, Result};
+use crate::ontology::OntologyReader;
+use std::collections::HashMap;
+use std::fs::{File, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+
+/// Ontology capability enabling group commit of the kernel's tx.jsonl
+pub const GROUP_COMMIT_CAPABILITY: &str = "tx:group-commit";
+
+/// Default time a batch stays open for more records
+pub const GROUP_COMMIT_WINDOW: Duration = Duration::from_millis(2);
+
+/// Most records written in one batch
+const MAX_BATCH: usize = 1024;
+
+/// How records are committed to a transaction log
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TxCommit {
+    /// One locked, synced append per record
+    #[default]
+    Immediate,
+    /// Records within the window share one locked append and fsync
+    Group(Duration),
+}
+
+impl TxCommit {
+    /// Commit mode a kernel asks for in its ontology
+    ///
+    /// Kernels whose ontology cannot be read commit immediately.
+    pub fn for_kernel(project_root: &Path, kernel: &str) -> Self {
+        let grouped = OntologyReader::new(project_root.to_path_buf())
+            .read_capabilities(kernel)
+            .map(|capabilities| capabilities.iter().any(|c| c == GROUP_COMMIT_CAPABILITY))
+            .unwrap_or(false);
+        if grouped {
+            TxCommit::Group(GROUP_COMMIT_WINDOW)
+        } else {
+            TxCommit::Immediate
+        }
+    }
+
+    /// Append one JSON line to a transaction log, returning once it is on disk
+    ///
+    /// Group commits go through the log's writer in `writers`, started on
+    /// first use.
+    pub fn append(&self, writers: &TxWriters, tx_log: &Path, line: String) -> Result<()> {
+        match self {
+            TxCommit::Immediate => append_locked(tx_log, &[line]),
+            TxCommit::Group(window) => writers.for_log(tx_log, *window)?.append(line),
+        }
+    }
+}
+
+/// Group-commit writers, one per log file
+///
+/// Shared by clones; the writers stop once the last clone is dropped.
+#[derive(Debug, Clone, Default)]
+pub struct TxWriters {
+    writers: Arc<Mutex<HashMap<PathBuf, Arc<GroupCommit>>>>,
+}
+
+impl TxWriters {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Writer for a log file, started on first use
+    fn for_log(&self, tx_log: &Path, window: Duration) -> Result<Arc<GroupCommit>> {
+        let mut writers = self.writers.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(writer) = writers.get(tx_log) {
+            return Ok(Arc::clone(writer));
+        }
+
+        let writer = Arc::new(GroupCommit::start(tx_log, window)?);
+        writers.insert(tx_log.to_path_buf(), Arc::clone(&writer));
+        Ok(writer)
+    }
+
+    /// Number of running writers
+    pub fn len(&self) -> usize {
+        self.writers.lock().unwrap_or_else(|e| e.into_inner()).len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Stop every writer once the records it holds are committed
+    ///
+    /// Later group commits start new writers.
+    pub fn shutdown(&self) {
+        let writers: Vec<Arc<GroupCommit>> =
+            self.writers.lock().unwrap_or_else(|e| e.into_inner()).drain().map(|(_, writer)| writer).collect();
+        drop(writers);
+    }
+}
+
+/// Append lines under an exclusive lock and sync them to disk
+fn append_locked(tx_log: &Path, lines: &[String]) -> Result<()> {
+    if let Some(parent) = tx_log.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let mut file = OpenOptions::new().create(true).append(true).open(tx_log)?;
+
+    let mut buffer = String::with_capacity(lines.iter().map(|line| line.len() + 1).sum());
+    for line in lines {
+        buffer.push_str(line);
+        buffer.push('\n');
+    }
+
+    lock(&file)?;
+    let result = file.write_all(buffer.as_bytes()).and_then(|_| file.sync_data());
+    unlock(&file);
+    result.map_err(CkpError::from)
+}
+
+/// Take the advisory lock shared with other writers of the log
+#[cfg(unix)]
+pub(super) fn lock(file: &File) -> Result<()> {
+    use std::os::unix::io::AsRawFd;
+
+    // Use flock for advisory locking (LOCK_EX for exclusive lock)
+    if unsafe { libc::flock(file.as_raw_fd(), libc::LOCK_EX) } != 0 {
+        return Err(CkpError::Io(std::io::Error::last_os_error()));
+    }
+    Ok(())
+}
+
+#[cfg(unix)]
+pub(super) fn unlock(file: &File) {
+    use std::os::unix::io::AsRawFd;
+
+    // Explicit unlock (close() would also release it)
+    unsafe {
+        libc::flock(file.as_raw_fd(), libc::LOCK_UN);
+    }
+}
+
+// Windows fallback: write without locking (Windows uses different locking APIs)
+#[cfg(not(unix))]
+pub(super) fn lock(_file: &File) -> Result<()> {
+    Ok(())
+}
+
+#[cfg(not(unix))]
+pub(super) fn unlock(_file: &File) {}
+
+/// A record waiting for its batch, with where to report the outcome
+type Pending = (String, Sender<std::result::Result<(), String>>);
+
+/// Writer thread batching the records of one log file
+///
+/// Dropping it closes the queue and waits for the thread to commit the rest.
+#[derive(Debug)]
+struct GroupCommit {
+    records: Option<Sender<Pending>>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl GroupCommit {
+    fn start(tx_log: &Path, window: Duration) -> Result<Self> {
+        let (tx, rx) = mpsc::channel();
+        let path = tx_log.to_path_buf();
+        let thread = std::thread::Builder::new()
+            .name("ckp-txlog".to_string())
+            .spawn(move || run_group_commit(&path, window, rx))
+            .map_err(|e| CkpError::IoError(format!("Failed to start transaction log writer: {}", e)))?;
+
+        Ok(Self { records: Some(tx), thread: Some(thread) })
+    }
+
+    /// Queue a line and wait until its batch is durable
+    fn append(&self, line: String) -> Result<()> {
+        let (done, outcome) = mpsc::channel();
+        self.records
+            .as_ref()
+            .ok_or_else(|| CkpError::IoError("Transaction log writer stopped".to_string()))?
+            .send((line, done))
+            .map_err(|_| CkpError::IoError("Transaction log writer stopped".to_string()))?;
+
+        match outcome.recv() {
+            Ok(Ok(())) => Ok(()),
+            Ok(Err(message)) => Err(CkpError::IoError(format!("Failed to commit transaction: {}", message))),
+            Err(_) => Err(CkpError::IoError("Transaction log writer stopped".to_string())),
+        }
+    }
+}
+
+impl Drop for GroupCommit {
+    fn drop(&mut self) {
+        drop(self.records.take());
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+fn run_group_commit(tx_log: &Path, window: Duration, records: Receiver<Pending>) {
+    while let Ok(first) = records.recv() {
+        let mut batch = vec![first];
+        let deadline = Instant::now() + window;
+
+        while batch.len() < MAX_BATCH {
+            match records.recv_timeout(deadline.saturating_duration_since(Instant::now())) {
+                Ok(record) => batch.push(record),
+                Err(RecvTimeoutError::Timeout) | Err(RecvTimeoutError::Disconnected) => break,
+            }
+        }
+
+        let (lines, waiters): (Vec<String>, Vec<_>) = batch.into_iter().unzip();
+        let outcome = append_locked(tx_log, &lines).map_err(|e| e.to_string());
+        tracing::trace!(records = lines.len(), ok = outcome.is_ok(), "committed batch");
+        for waiter in waiters {
+            let _ = waiter.send(outcome.clone());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_group_commit_batches_in_order() {
+        let temp = TempDir::new().unwrap();
+        let tx_log = temp.path().join("concepts/Test.Kernel/tx.jsonl");
+        let commit = TxCommit::Group(Duration::from_millis(20));
+        let writers = TxWriters::new();
+
+        // Each thread's records stay in submission order
+        let threads: Vec<_> = (0..4)
+            .map(|t| {
+                let tx_log = tx_log.clone();
+                let writers = writers.clone();
+                std::thread::spawn(move || {
+                    for i in 0..25 {
+                        commit.append(&writers, &tx_log, format!("{{\"t\":{},\"i\":{}}}", t, i)).unwrap();
+                    }
+                })
+            })
+            .collect();
+        for thread in threads {
+            thread.join().unwrap();
+        }
+
+        let content = std::fs::read_to_string(&tx_log).unwrap();
+        let records: Vec<serde_json::Value> = content.lines().map(|line| serde_json::from_str(line).unwrap()).collect();
+        assert_eq!(records.len(), 100);
+        for t in 0..4 {
+            let order: Vec<u64> = records.iter().filter(|r| r["t"] == t).map(|r| r["i"].as_u64().unwrap()).collect();
+            assert_eq!(order, (0..25).collect::<Vec<_>>());
+        }
+
+        // Immediate commits append to the same file format
+        TxCommit::Immediate.append(&writers, &tx_log, "{\"t\":9}".to_string()).unwrap();
+        assert_eq!(std::fs::read_to_string(&tx_log).unwrap().lines().count(), 101);
+
+        // Writers stop on shutdown and restart on the next group commit
+        assert_eq!(writers.len(), 1);
+        writers.shutdown();
+        assert!(writers.is_empty());
+        commit.append(&writers, &tx_log, "{\"t\":10}".to_string()).unwrap();
+        assert_eq!(std::fs::read_to_string(&tx_log).unwrap().lines().count(), 102);
+    }
+}
@@ -111,14 +111,14 @@ impl VersionDriverFactory {
         // Check for S3 marker
         if kernel_path.join(".s3-versioned").exists() {
             // TODO: Implement S3Driver when S3 backend is ready
-            eprintln!("[VersionDriver] S3 versioning detected but not yet implemented");
+            tracing::warn!("S3 versioning detected but not yet implemented");
             return None;
         }
 
         // Check for filesystem versioning
         if kernel_path.join(".version").exists() {
             // TODO: Implement FilesystemDriver
-            eprintln!("[VersionDriver] Filesystem versioning detected but not yet implemented");
+            tracing::warn!("filesystem versioning detected but not yet implemented");
             return None;
         }
 
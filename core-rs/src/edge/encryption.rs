@@ -0,0 +1,365 @@
+//! Edge payload encryption
+//!
+//! Kernels share one filesystem, so a payload waiting in an edge queue can
+//! be read by every other kernel of the project. A target kernel that
+//! declares `payload_encryption` in its ontology has the payloads of its
+//! inbound edges sealed to its X25519 public key: the producer encrypts the
+//! message before writing it (`KernelContext::send_edge_message`), the edge
+//! router moves the ciphertext like any other entry, and the target's
+//! governor opens the queue's entries in memory with the kernel's private
+//! key when it runs the tool (`open_queue`). Entries stay sealed on disk,
+//! in the queue and once archived; the tool gets the opened payloads on its
+//! stdin (`OPENED_PAYLOADS_ENV`, `read_opened_payloads`).
+//!
+//! Sealing uses an ephemeral X25519 key per payload; the shared secret is
+//! hashed with both public keys into a ChaCha20-Poly1305 key. The sender
+//! and recipient kernel names are bound to the ciphertext, so the envelope
+//! cannot be relabelled in transit, but the sender is not authenticated:
+//! anyone holding the target's public key can seal a payload naming any
+//! sender. `sender` is informational; who may send to a kernel is decided by
+//! RBAC, not by the envelope.
+
+use crate::errors::{CkpError, Result};
+use crate::ontology::OntologyReader;
+use crate::storage::shard;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use chacha20poly1305::aead::{Aead, AeadCore, KeyInit, OsRng, Payload};
+use chacha20poly1305::{ChaCha20Poly1305, Key, Nonce};
+use curve25519_dalek::montgomery::MontgomeryPoint;
+use serde::{Deserialize, Serialize};
+use serde_json::Value as JsonValue;
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+/// Key under which a sealed message carries its envelope
+pub const SEALED_KEY: &str = "sealed";
+
+/// Algorithm recorded in every envelope
+pub const SEALED_ALGORITHM: &str = "x25519-chacha20poly1305";
+
+/// Private key file, relative to the kernel directory
+pub const PAYLOAD_KEY_FILE: &str = ".keys/payload.key";
+
+/// Environment variable set on a tool whose sealed entries were opened for it
+///
+/// The tool's stdin then carries one JSON object mapping each sealed entry
+/// (path relative to the queue directory) to its opened payload.
+pub const OPENED_PAYLOADS_ENV: &str = "CK_OPENED_PAYLOADS";
+
+/// Opened payloads of a queue, by entry path relative to the queue directory
+pub type OpenedPayloads = BTreeMap<String, JsonValue>;
+
+/// Domain separation for the derived payload key
+const KEY_CONTEXT: &[u8] = b"ckp-edge-payload-v1";
+
+/// X25519 key pair a kernel receives sealed payloads with
+pub struct PayloadKeyPair {
+    secret: [u8; 32],
+    public: [u8; 32],
+}
+
+impl std::fmt::Debug for PayloadKeyPair {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PayloadKeyPair")
+            .field("public", &self.public_key_hex())
+            .finish()
+    }
+}
+
+impl PayloadKeyPair {
+    /// Generate a random key pair
+    pub fn generate() -> Self {
+        Self::from_secret(rand::random())
+    }
+
+    fn from_secret(secret: [u8; 32]) -> Self {
+        let public = MontgomeryPoint::mul_base_clamped(secret).to_bytes();
+        Self { secret, public }
+    }
+
+    /// Public key as hex, for the ontology's `payload_encryption.public_key`
+    pub fn public_key_hex(&self) -> String {
+        hex::encode(self.public)
+    }
+
+    /// Load the key pair stored in a kernel directory
+    pub fn load(kernel_dir: &Path) -> Result<Self> {
+        let path = kernel_dir.join(PAYLOAD_KEY_FILE);
+        let content = fs::read_to_string(&path)
+            .map_err(|e| CkpError::IoError(format!("Failed to read payload key {}: {}", path.display(), e)))?;
+        Ok(Self::from_secret(decode_key(content.trim(), "payload key")?))
+    }
+
+    /// Load the kernel's key pair, generating and storing one if it has none
+    ///
+    /// The key file is only readable by its owner.
+    pub fn load_or_generate(kernel_dir: &Path) -> Result<Self> {
+        let path = kernel_dir.join(PAYLOAD_KEY_FILE);
+        if path.exists() {
+            return Self::load(kernel_dir);
+        }
+
+        let keys = Self::generate();
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .map_err(|e| CkpError::IoError(format!("Failed to create {}: {}", parent.display(), e)))?;
+        }
+        write_private(&path, hex::encode(keys.secret).as_bytes())?;
+        Ok(keys)
+    }
+}
+
+/// A payload sealed to a target kernel's public key
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SealedPayload {
+    pub algorithm: String,
+
+    /// Kernel the payload claims to be sent from (bound to the ciphertext,
+    /// not authenticated)
+    pub sender: String,
+
+    /// Kernel the payload is sealed to
+    pub recipient: String,
+
+    /// Ephemeral X25519 public key (hex)
+    pub ephemeral_key: String,
+
+    /// ChaCha20-Poly1305 nonce (hex)
+    pub nonce: String,
+
+    /// Encrypted JSON payload (base64)
+    pub ciphertext: String,
+}
+
+impl SealedPayload {
+    /// Seal a payload from `sender` to `recipient`'s public key (hex)
+    pub fn seal(payload: &JsonValue, sender: &str, recipient: &str, public_key: &str) -> Result<Self> {
+        let recipient_key = decode_key(public_key, "public key")?;
+        let ephemeral = PayloadKeyPair::generate();
+        let cipher = payload_cipher(
+            MontgomeryPoint(recipient_key).mul_clamped(ephemeral.secret),
+            &ephemeral.public,
+            &recipient_key,
+        )?;
+
+        let plaintext = serde_json::to_vec(payload)?;
+        let nonce = ChaCha20Poly1305::generate_nonce(&mut OsRng);
+        let aad = associated_data(sender, recipient);
+        let ciphertext = cipher
+            .encrypt(&nonce, Payload { msg: &plaintext, aad: &aad })
+            .map_err(|_| CkpError::ValidationError("Failed to seal edge payload".to_string()))?;
+
+        Ok(Self {
+            algorithm: SEALED_ALGORITHM.to_string(),
+            sender: sender.to_string(),
+            recipient: recipient.to_string(),
+            ephemeral_key: ephemeral.public_key_hex(),
+            nonce: hex::encode(nonce),
+            ciphertext: BASE64.encode(ciphertext),
+        })
+    }
+
+    /// Open the payload with the recipient's key pair
+    ///
+    /// Fails if the payload was sealed to another key, or if its ciphertext,
+    /// sender or recipient was tampered with.
+    pub fn open(&self, keys: &PayloadKeyPair) -> Result<JsonValue> {
+        if self.algorithm != SEALED_ALGORITHM {
+            return Err(CkpError::ValidationError(format!(
+                "Unsupported sealed payload algorithm: {}",
+                self.algorithm
+            )));
+        }
+
+        let ephemeral_key = decode_key(&self.ephemeral_key, "ephemeral key")?;
+        let nonce = hex::decode(&self.nonce)
+            .ok()
+            .filter(|nonce| nonce.len() == 12)
+            .ok_or_else(|| CkpError::ParseError("Invalid sealed payload nonce".to_string()))?;
+        let ciphertext = BASE64
+            .decode(&self.ciphertext)
+            .map_err(|e| CkpError::ParseError(format!("Invalid sealed payload ciphertext: {}", e)))?;
+
+        let cipher = payload_cipher(
+            MontgomeryPoint(ephemeral_key).mul_clamped(keys.secret),
+            &ephemeral_key,
+            &keys.public,
+        )?;
+        let aad = associated_data(&self.sender, &self.recipient);
+        let plaintext = cipher
+            .decrypt(Nonce::from_slice(&nonce), Payload { msg: &ciphertext, aad: &aad })
+            .map_err(|_| {
+                CkpError::ValidationError(format!(
+                    "Failed to open payload sealed by {} for {}",
+                    self.sender, self.recipient
+                ))
+            })?;
+
+        Ok(serde_json::from_slice(&plaintext)?)
+    }
+
+    /// Wrap the envelope as a queue message (`{"sealed": {...}}`)
+    pub fn to_message(&self) -> Result<JsonValue> {
+        let mut message = serde_json::Map::new();
+        message.insert(SEALED_KEY.to_string(), serde_json::to_value(self)?);
+        Ok(JsonValue::Object(message))
+    }
+
+    /// The envelope of a sealed queue message, if it is one
+    pub fn from_message(message: &JsonValue) -> Option<Self> {
+        let object = message.as_object().filter(|object| object.len() == 1)?;
+        serde_json::from_value(object.get(SEALED_KEY)?.clone()).ok()
+    }
+}
+
+/// Public key payloads from `source_kernel` to `target_kernel` are sealed to
+///
+/// None if the target does not declare payload encryption for that edge.
+pub fn recipient_key(root: &Path, source_kernel: &str, target_kernel: &str) -> Result<Option<String>> {
+    let ontology_path = root.join("concepts").join(target_kernel).join("conceptkernel.yaml");
+    if !ontology_path.exists() {
+        return Ok(None);
+    }
+
+    let encryption = OntologyReader::new(root.to_path_buf()).read_payload_encryption(target_kernel)?;
+    Ok(encryption
+        .filter(|encryption| encryption.covers(source_kernel))
+        .map(|encryption| encryption.public_key))
+}
+
+/// Open the sealed messages in one of a kernel's queues, in memory
+///
+/// The entries on disk are not changed. Symlinked entries belong to another
+/// kernel and are left alone, as are entries that are not JSON. Entries
+/// that cannot be opened are logged and left out.
+pub fn open_queue(kernel_dir: &Path, queue_dir: &Path) -> Result<OpenedPayloads> {
+    let sealed: Vec<_> = shard::list_entries(queue_dir)
+        .into_iter()
+        .filter(|path| fs::symlink_metadata(path).is_ok_and(|meta| meta.is_file()))
+        .filter_map(|path| {
+            let message: JsonValue = serde_json::from_slice(&fs::read(&path).ok()?).ok()?;
+            Some((SealedPayload::from_message(&message)?, path))
+        })
+        .collect();
+    if sealed.is_empty() {
+        return Ok(OpenedPayloads::new());
+    }
+
+    let keys = PayloadKeyPair::load(kernel_dir)?;
+    let mut opened = OpenedPayloads::new();
+    for (envelope, path) in sealed {
+        match envelope.open(&keys) {
+            Ok(message) => {
+                let entry = path.strip_prefix(queue_dir).unwrap_or(&path);
+                opened.insert(entry.to_string_lossy().replace('\\', "/"), message);
+            }
+            Err(e) => tracing::warn!(entry = %path.display(), error = %e, "could not open sealed payload"),
+        }
+    }
+
+    Ok(opened)
+}
+
+/// Opened payloads the governor handed to this tool process
+///
+/// Reads stdin if `OPENED_PAYLOADS_ENV` is set; empty otherwise.
+pub fn read_opened_payloads() -> Result<OpenedPayloads> {
+    if std::env::var_os(OPENED_PAYLOADS_ENV).is_none() {
+        return Ok(OpenedPayloads::new());
+    }
+    Ok(serde_json::from_reader(std::io::stdin().lock())?)
+}
+
+/// ChaCha20-Poly1305 keyed by the shared secret and both public keys
+fn payload_cipher(shared: MontgomeryPoint, ephemeral_key: &[u8; 32], recipient_key: &[u8; 32]) -> Result<ChaCha20Poly1305> {
+    // A low-order public key yields the all-zero secret
+    if shared.to_bytes() == [0u8; 32] {
+        return Err(CkpError::ValidationError("Invalid X25519 public key".to_string()));
+    }
+
+    let mut hasher = Sha256::new();
+    hasher.update(KEY_CONTEXT);
+    hasher.update(shared.to_bytes());
+    hasher.update(ephemeral_key);
+    hasher.update(recipient_key);
+    Ok(ChaCha20Poly1305::new(Key::from_slice(&hasher.finalize())))
+}
+
+fn associated_data(sender: &str, recipient: &str) -> Vec<u8> {
+    format!("{}->{}", sender, recipient).into_bytes()
+}
+
+fn decode_key(key: &str, what: &str) -> Result<[u8; 32]> {
+    hex::decode(key)
+        .ok()
+        .and_then(|bytes| <[u8; 32]>::try_from(bytes).ok())
+        .ok_or_else(|| CkpError::ParseError(format!("Invalid {}: expected 32 hex-encoded bytes", what)))
+}
+
+/// Write a file only its owner can read
+fn write_private(path: &Path, content: &[u8]) -> Result<()> {
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+
+    use std::io::Write;
+    options
+        .open(path)
+        .and_then(|mut file| file.write_all(content))
+        .map_err(|e| CkpError::IoError(format!("Failed to write {}: {}", path.display(), e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_sealed_edge_message_is_opened_by_target_only() {
+        let temp = TempDir::new().unwrap();
+        let target_dir = temp.path().join("concepts/Orders.Billing");
+        let keys = PayloadKeyPair::load_or_generate(&target_dir).unwrap();
+        fs::write(
+            target_dir.join("conceptkernel.yaml"),
+            format!(
+                "apiVersion: conceptkernel/v1\nkind: Ontology\nmetadata:\n  name: ckp://Orders.Billing:v0.1\n  type: node:cold\n  version: v0.1\nspec:\n  payload_encryption:\n    public_key: {}\n    sources:\n      - ckp://Orders.Intake:v0.1\n",
+                keys.public_key_hex()
+            ),
+        )
+        .unwrap();
+
+        let public_key = recipient_key(temp.path(), "Orders.Intake", "Orders.Billing").unwrap().unwrap();
+        assert_eq!(public_key, keys.public_key_hex());
+        assert_eq!(recipient_key(temp.path(), "Orders.Audit", "Orders.Billing").unwrap(), None);
+
+        let message = json!({"task": "charge", "context": {"card": "4111 1111 1111 1111"}});
+        let sealed = SealedPayload::seal(&message, "Orders.Intake", "Orders.Billing", &public_key).unwrap();
+        assert!(!sealed.ciphertext.contains("4111"));
+
+        // Another kernel's key cannot open it, nor can a relabelled envelope
+        assert!(sealed.open(&PayloadKeyPair::generate()).is_err());
+        let relabelled = SealedPayload { sender: "Orders.Audit".to_string(), ..sealed.clone() };
+        assert!(relabelled.open(&keys).is_err());
+
+        // The governor opens the queue in memory; the entry stays sealed
+        let queue = target_dir.join("queue/edges/Orders.Intake");
+        fs::create_dir_all(&queue).unwrap();
+        let entry = queue.join("message-1.json");
+        let on_disk = serde_json::to_vec(&sealed.to_message().unwrap()).unwrap();
+        fs::write(&entry, &on_disk).unwrap();
+        fs::write(queue.join("message-2.json"), r#"{"task": "plain"}"#).unwrap();
+
+        let opened = open_queue(&target_dir, &queue).unwrap();
+        assert_eq!(opened, OpenedPayloads::from([("message-1.json".to_string(), message)]));
+        assert_eq!(fs::read(&entry).unwrap(), on_disk);
+    }
+}
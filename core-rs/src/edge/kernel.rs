@@ -8,13 +8,42 @@ use crate::drivers::FileSystemDriver;
 use crate::edge::EdgeMetadata;
 use crate::errors::{CkpError, Result};
 use crate::ontology::{OntologyLibrary, OntologyReader};
-use crate::process_tracker::ProcessTracker;
+use crate::process_tracker::{ProcessTracker, ProcessTracking, TrackedProcess};
+use crate::project::ProjectRegistry;
+use crate::rbac::{DecisionCache, PermissionChecker};
+use crate::storage::walk::{walk, Visit};
+use crate::urn::UrnResolver;
 use crate::continuant_tracker::ContinuantTracker;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::fs;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
+/// Where an edge delivers one source kernel's instances
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EdgeRoute {
+    /// Edge URN
+    pub edge_urn: String,
+
+    /// Edge predicate
+    pub predicate: String,
+
+    /// Target as routed (`Kernel`, or `project/Kernel` in another project)
+    pub target: String,
+
+    /// Target kernel name
+    pub target_kernel: String,
+
+    /// Root of the project the target lives in
+    pub target_root: PathBuf,
+
+    /// Target's per-edge queue
+    pub queue: PathBuf,
+
+    /// Whether the target (and the source's project scope) allows the edge
+    pub authorized: bool,
+}
+
 /// EdgeKernel - manages edge metadata and instance routing
 pub struct EdgeKernel {
     /// Root directory (concepts/)
@@ -29,8 +58,11 @@ pub struct EdgeKernel {
     /// Ontology library for semantic edge validation (Phase 4 Stage 1)
     ontology_library: Option<Arc<OntologyLibrary>>,
 
-    /// Process tracker for Process URN tracking (Phase 4 Stage 1)
-    process_tracker: Option<Arc<ProcessTracker>>,
+    /// Process tracking for routed instances (Phase 4 Stage 1)
+    process_tracking: Option<ProcessTracking>,
+
+    /// Project registry for cross-project targets (loaded on first use)
+    project_registry: Option<ProjectRegistry>,
 }
 
 impl EdgeKernel {
@@ -60,7 +92,8 @@ impl EdgeKernel {
             edges_dir,
             metadata_cache: HashMap::new(),
             ontology_library: None,
-            process_tracker: None,
+            process_tracking: None,
+            project_registry: None,
         })
     }
 
@@ -105,10 +138,41 @@ impl EdgeKernel {
             edges_dir,
             metadata_cache: HashMap::new(),
             ontology_library,
-            process_tracker,
+            process_tracking: process_tracker.map(ProcessTracking::new),
+            project_registry: None,
         })
     }
 
+    /// Use a specific ProjectRegistry for cross-project targets
+    ///
+    /// Without one, the user's registry (~/.config/conceptkernel/projects)
+    /// is loaded the first time an edge targets another project.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use ckp_core::edge::EdgeKernel;
+    /// use ckp_core::project::ProjectRegistry;
+    /// use std::path::PathBuf;
+    ///
+    /// let registry = ProjectRegistry::from_dir(PathBuf::from("/registry")).unwrap();
+    /// let kernel = EdgeKernel::new(PathBuf::from("/project"))
+    ///     .unwrap()
+    ///     .with_project_registry(registry);
+    /// ```
+    pub fn with_project_registry(mut self, registry: ProjectRegistry) -> Self {
+        self.project_registry = Some(registry);
+        self
+    }
+
+    /// Set how routed instances are tracked as processes
+    ///
+    /// `None` stops tracking; a tracker passed to `with_ontology` tracks
+    /// every route with all of its phases.
+    pub fn set_process_tracking(&mut self, tracking: Option<ProcessTracking>) {
+        self.process_tracking = tracking;
+    }
+
     /// Create a new edge between two kernels
     ///
     /// # Arguments
@@ -178,18 +242,12 @@ impl EdgeKernel {
             match ontology.get_edge_predicate(predicate) {
                 Ok(rdf_predicate) => {
                     // Semantic validation succeeded
-                    eprintln!(
-                        "[EdgeKernel] Semantic validation: {} → {}",
-                        predicate, rdf_predicate
-                    );
+                    tracing::debug!(predicate, rdf_predicate = %rdf_predicate, "predicate validated against ontology");
                     return Ok(());
                 }
                 Err(e) => {
                     // Predicate not found in ontology, fall back to hardcoded list
-                    eprintln!(
-                        "[EdgeKernel] Predicate '{}' not in ontology ({}), trying fallback",
-                        predicate, e
-                    );
+                    tracing::debug!(predicate, error = %e, "predicate not in ontology, trying fallback");
                     // Don't return error - continue to fallback validation below
                 }
             }
@@ -248,37 +306,51 @@ impl EdgeKernel {
             return Ok(Vec::new());
         }
 
-        let mut edges = Vec::new();
-
-        for entry in fs::read_dir(&self.edges_dir)? {
-            let entry = entry?;
-            let path = entry.path();
-
-            if !path.is_dir() {
-                continue;
-            }
-
-            // Load edgekernel.yaml (YAML-only format)
-            let yaml_path = path.join("edgekernel.yaml");
-
+        // Load edgekernel.yaml (YAML-only format) of every edge in parallel
+        let edges = walk(&self.edges_dir, &|entry| {
+            let yaml_path = entry.path().join("edgekernel.yaml");
             if !yaml_path.exists() {
-                continue;
+                return Ok(Visit::Skip);
             }
 
-            let content = fs::read_to_string(&yaml_path)?;
-            let metadata = EdgeMetadata::from_yaml(&content)
-                .map_err(|e| CkpError::IoError(format!("Failed to parse edge metadata from YAML: {}", e)))?;
+            Ok(Visit::Yield(Self::read_edge_metadata(&yaml_path)?))
+        })?;
 
-            // Update cache
+        // Update cache
+        for metadata in &edges {
             self.metadata_cache
                 .insert(metadata.urn.clone(), metadata.clone());
-
-            edges.push(metadata);
         }
 
         Ok(edges)
     }
 
+    /// Read an edgekernel.yaml, migrating it if an older release wrote it
+    ///
+    /// The migrated file is written aside and renamed over the original, so
+    /// a Node.js runtime reading it concurrently sees one layout or the other.
+    fn read_edge_metadata(yaml_path: &Path) -> Result<EdgeMetadata> {
+        let content = fs::read_to_string(yaml_path)?;
+        let mut metadata = EdgeMetadata::from_yaml(&content)
+            .map_err(|e| CkpError::IoError(format!("Failed to parse edge metadata from YAML: {}", e)))?;
+
+        let from_version = metadata.format_version;
+        if metadata.migrate() {
+            let yaml = metadata.to_yaml()
+                .map_err(|e| CkpError::IoError(format!("Failed to serialize metadata: {}", e)))?;
+            let tmp = yaml_path.with_extension("yaml.tmp");
+            fs::write(&tmp, yaml)?;
+            fs::rename(&tmp, yaml_path)?;
+            tracing::info!(
+                edge = %metadata.urn,
+                from = from_version,
+                to = metadata.format_version,
+                "migrated edge metadata format"
+            );
+        }
+        Ok(metadata)
+    }
+
     /// Remove edge by URN
     ///
     /// # Arguments
@@ -333,8 +405,11 @@ impl EdgeKernel {
     /// # Example
     /// Returns: /concepts/BakeCake/queue/edges/PRODUCES.MixIngredients/
     pub fn get_target_queue_path(&self, target: &str, predicate: &str, source: &str) -> PathBuf {
-        self.root
-            .join("concepts")
+        Self::queue_path_in(&self.root, target, predicate, source)
+    }
+
+    fn queue_path_in(root: &Path, target: &str, predicate: &str, source: &str) -> PathBuf {
+        root.join("concepts")
             .join(target)
             .join("queue")
             .join("edges")
@@ -355,6 +430,83 @@ impl EdgeKernel {
         &mut self,
         instance_path: &Path,
         source_kernel: &str,
+    ) -> Result<Vec<PathBuf>> {
+        let routes = self.resolve_routes(source_kernel)?;
+        self.route_instance_along(instance_path, source_kernel, &routes)
+    }
+
+    /// Resolve where instances of a source kernel go
+    ///
+    /// Expands wildcard targets, resolves cross-project targets and decides
+    /// authorization for each target. The result stays valid until an edge,
+    /// the source's ontology or a target's ontology changes, so callers
+    /// routing many instances can keep it (see `daemon::routing_table`).
+    pub fn resolve_routes(&mut self, source_kernel: &str) -> Result<Vec<EdgeRoute>> {
+        let mut routes = Vec::new();
+
+        for edge in self.get_outgoing_edges(source_kernel)? {
+            // Expand wildcard targets to all kernels
+            let actual_targets: Vec<String> = if edge.target == "*" {
+                // List all kernel directories
+                let concepts_dir = self.root.join("concepts");
+                if let Ok(entries) = fs::read_dir(&concepts_dir) {
+                    entries
+                        .filter_map(|entry| entry.ok())
+                        .filter(|entry| entry.path().is_dir())
+                        .filter_map(|entry| entry.file_name().to_str().map(String::from))
+                        .filter(|name| name != "*") // Skip the literal * directory
+                        .collect()
+                } else {
+                    vec![edge.target.clone()]
+                }
+            } else {
+                vec![edge.target.clone()]
+            };
+
+            for actual_target in actual_targets {
+                // Targets in another project (`project/Kernel`) live under that project's root
+                let (target_project, target_kernel) = UrnResolver::split_project(&actual_target);
+                let target_root = match target_project {
+                    Some(project) => match self.project_root(project) {
+                        Ok(root) => root,
+                        Err(e) => {
+                            tracing::warn!(edge = %edge.urn, target = %actual_target, error = %e, "cannot resolve edge target");
+                            continue;
+                        }
+                    },
+                    None => self.root.clone(),
+                };
+
+                // Cross-project emission must be allowed by the source's RBAC project scope
+                let in_scope = target_project.is_none()
+                    || PermissionChecker::new(self.root.clone())
+                        .can_emit_to(source_kernel, &format!("ckp://{}", actual_target))?;
+                let authorized = in_scope && Self::is_edge_authorized_in(&target_root, target_kernel, &edge.urn)?;
+
+                routes.push(EdgeRoute {
+                    edge_urn: edge.urn.clone(),
+                    predicate: edge.predicate.clone(),
+                    queue: Self::queue_path_in(&target_root, target_kernel, &edge.predicate, &edge.source),
+                    target_kernel: target_kernel.to_string(),
+                    target: actual_target,
+                    target_root,
+                    authorized,
+                });
+            }
+        }
+
+        Ok(routes)
+    }
+
+    /// Route instance along routes resolved earlier by `resolve_routes`
+    ///
+    /// # Returns
+    /// Vector of created symlink paths
+    pub fn route_instance_along(
+        &self,
+        instance_path: &Path,
+        source_kernel: &str,
+        routes: &[EdgeRoute],
     ) -> Result<Vec<PathBuf>> {
         // Extract txId from instance path
         let tx_id = instance_path
@@ -362,112 +514,116 @@ impl EdgeKernel {
             .and_then(|s| s.to_str())
             .unwrap_or("unknown");
 
-        // Get outgoing edges
-        let edges = self.get_outgoing_edges(source_kernel)?;
+        let _span = tracing::info_span!("route_instance", kernel = source_kernel, tx_id).entered();
 
-        if edges.is_empty() {
+        if routes.is_empty() {
             return Ok(Vec::new());
         }
 
-        // Phase 4 Stage 1: Create Process URN for edge routing
-        if let Some(tracker) = &self.process_tracker {
-            let process_urn = tracker.generate_process_urn("EdgeRoute", tx_id);
-
+        // Phase 4 Stage 1: Create Process URN for edge routing (sampled)
+        let process = self.process_tracking.as_ref().and_then(|tracking| {
             let mut participants = HashMap::new();
             participants.insert("source_kernel".to_string(), serde_json::json!(source_kernel));
             participants.insert("tx_id".to_string(), serde_json::json!(tx_id));
             participants.insert("instance_path".to_string(), serde_json::json!(instance_path.display().to_string()));
 
+            let edge_count = routes.iter().map(|route| &route.edge_urn).collect::<HashSet<_>>().len();
             let mut metadata = HashMap::new();
-            metadata.insert("edge_count".to_string(), serde_json::json!(edges.len()));
+            metadata.insert("edge_count".to_string(), serde_json::json!(edge_count));
+
+            tracking.begin("EdgeRoute", tx_id, participants, metadata)
+        });
+
+        if let Some(process) = &process {
+            tracing::debug!(process = %process.urn(), "created Process URN");
+
+            // Add temporal part: routing started
+            let mut routing_data = HashMap::new();
+            routing_data.insert("phase".to_string(), serde_json::json!("routing_started"));
+            routing_data.insert("source".to_string(), serde_json::json!(source_kernel));
+            process.phase("routing", routing_data);
+
+            // Phase 4 Stage 2: Record Continuant participation (BFO participates_in relation)
+            let continuant_tracker = ContinuantTracker::new(self.root.clone());
+            let source_urn = continuant_tracker.generate_continuant_urn("Kernel", source_kernel);
+            let mut participation_metadata = HashMap::new();
+            participation_metadata.insert("tx_id".to_string(), serde_json::json!(tx_id));
+            let _ = continuant_tracker.record_participation(
+                &source_urn,
+                process.urn(),
+                "source",
+                participation_metadata,
+            );
+        }
 
-            if let Err(e) = tracker.create_process("EdgeRoute", tx_id, participants, metadata) {
-                eprintln!("[EdgeKernel] Failed to create process tracking: {}", e);
-            } else {
-                eprintln!("[EdgeKernel] Created Process URN: {}", process_urn);
-
-                // Add temporal part: routing started
-                let mut routing_data = HashMap::new();
-                routing_data.insert("phase".to_string(), serde_json::json!("routing_started"));
-                routing_data.insert("source".to_string(), serde_json::json!(source_kernel));
-                let _ = tracker.add_temporal_part(&process_urn, "routing", routing_data);
-
-                // Phase 4 Stage 2: Record Continuant participation (BFO participates_in relation)
-                let continuant_tracker = ContinuantTracker::new(self.root.clone());
-                let source_urn = continuant_tracker.generate_continuant_urn("Kernel", source_kernel);
-                let mut participation_metadata = HashMap::new();
-                participation_metadata.insert("tx_id".to_string(), serde_json::json!(tx_id));
-                let _ = continuant_tracker.record_participation(
-                    &source_urn,
-                    &process_urn,
-                    "source",
-                    participation_metadata,
-                );
+        let mut denied = 0;
+        let result = Self::deliver_along(routes, instance_path, source_kernel, process.as_ref(), &mut denied);
+
+        if let Some(process) = &process {
+            match &result {
+                Ok(paths) if paths.is_empty() && denied > 0 => process.fail("No target authorized the edge"),
+                Ok(paths) => {
+                    let mut summary = HashMap::new();
+                    summary.insert("delivered".to_string(), serde_json::json!(paths.len()));
+                    summary.insert("denied".to_string(), serde_json::json!(denied));
+                    process.complete(summary);
+                }
+                Err(e) => process.fail(&e.to_string()),
             }
         }
 
+        result
+    }
+
+    /// Deliver an instance along routes, counting targets that refused it
+    fn deliver_along(
+        routes: &[EdgeRoute],
+        instance_path: &Path,
+        source_kernel: &str,
+        process: Option<&TrackedProcess>,
+        denied: &mut usize,
+    ) -> Result<Vec<PathBuf>> {
         let mut routed_paths = Vec::new();
 
-        for edge in edges {
-            // Expand wildcard targets to all kernels
-            let actual_targets: Vec<String> = if edge.target == "*" {
-                // List all kernel directories
-                let concepts_dir = self.root.join("concepts");
-                if let Ok(entries) = fs::read_dir(&concepts_dir) {
-                    entries
-                        .filter_map(|entry| entry.ok())
-                        .filter(|entry| entry.path().is_dir())
-                        .filter_map(|entry| entry.file_name().to_str().map(String::from))
-                        .filter(|name| name != "*") // Skip the literal * directory
-                        .collect()
-                } else {
-                    vec![edge.target.clone()]
-                }
-            } else {
-                vec![edge.target.clone()]
-            };
+        for route in routes {
+            // Check authorization
+            if !route.authorized {
+                tracing::warn!(
+                    edge = %route.edge_urn,
+                    source = %source_kernel,
+                    target = %route.target,
+                    "edge not authorized"
+                );
 
-            for actual_target in actual_targets {
-                // Check authorization
-                if !self.is_edge_authorized(&actual_target, &edge.urn)? {
-                    eprintln!(
-                        "[EdgeKernel] Edge not authorized: {} -> {}",
-                        source_kernel, actual_target
-                    );
-
-                    // Track authorization failure
-                    if let Some(tracker) = &self.process_tracker {
-                        let process_urn = tracker.generate_process_urn("EdgeRoute", tx_id);
-                        let mut failed_data = HashMap::new();
-                        failed_data.insert("target".to_string(), serde_json::json!(actual_target));
-                        failed_data.insert("reason".to_string(), serde_json::json!("not_authorized"));
-                        let _ = tracker.add_temporal_part(&process_urn, "failed", failed_data);
-                    }
-
-                    continue;
+                // Track authorization failure
+                *denied += 1;
+                if let Some(process) = process {
+                    let mut denied_data = HashMap::new();
+                    denied_data.insert("target".to_string(), serde_json::json!(route.target));
+                    denied_data.insert("reason".to_string(), serde_json::json!("not_authorized"));
+                    process.phase("denied", denied_data);
                 }
 
-                // Get target queue path
-                let target_queue = self.get_target_queue_path(&actual_target, &edge.predicate, &edge.source);
+                continue;
+            }
 
-                // Create per-edge queue if not exists
-                fs::create_dir_all(&target_queue)?;
+            // Create per-edge queue if not exists
+            fs::create_dir_all(&route.queue)?;
 
-                // Create symlink using FileSystemDriver
-                let driver = FileSystemDriver::new(self.root.clone(), actual_target.clone());
-                let symlink_path = driver.create_symlink(instance_path, &target_queue, None)?;
+            // Create symlink using FileSystemDriver
+            let driver = FileSystemDriver::new(route.target_root.clone(), route.target_kernel.clone());
+            let symlink_path = driver.create_symlink(instance_path, &route.queue, None)?;
 
-                routed_paths.push(symlink_path.clone());
+            tracing::debug!(edge = %route.edge_urn, target = %route.target, "delivered instance");
+            routed_paths.push(symlink_path.clone());
 
-                // Track successful delivery
-                if let Some(tracker) = &self.process_tracker {
-                    let process_urn = tracker.generate_process_urn("EdgeRoute", tx_id);
-                    let mut delivered_data = HashMap::new();
-                    delivered_data.insert("target".to_string(), serde_json::json!(actual_target));
-                    delivered_data.insert("predicate".to_string(), serde_json::json!(edge.predicate));
-                    delivered_data.insert("symlink_path".to_string(), serde_json::json!(symlink_path.display().to_string()));
-                    let _ = tracker.add_temporal_part(&process_urn, "delivered", delivered_data);
-                }
+            // Track successful delivery
+            if let Some(process) = process {
+                let mut delivered_data = HashMap::new();
+                delivered_data.insert("target".to_string(), serde_json::json!(route.target));
+                delivered_data.insert("predicate".to_string(), serde_json::json!(route.predicate));
+                delivered_data.insert("symlink_path".to_string(), serde_json::json!(symlink_path.display().to_string()));
+                process.phase("delivered", delivered_data);
             }
         }
 
@@ -476,7 +632,8 @@ impl EdgeKernel {
 
     /// Check if edge is authorized by target kernel
     ///
-    /// Reads target kernel's conceptkernel.yaml and checks if edge is in allowed list
+    /// Reads target kernel's conceptkernel.yaml and checks if edge is in allowed list.
+    /// Decisions are cached until that file changes.
     ///
     /// # Arguments
     /// * `target_kernel` - Target kernel name
@@ -485,8 +642,11 @@ impl EdgeKernel {
     /// # Returns
     /// true if authorized, false otherwise
     pub fn is_edge_authorized(&self, target_kernel: &str, edge_urn: &str) -> Result<bool> {
-        let ontology_path = self
-            .root
+        Self::is_edge_authorized_in(&self.root, target_kernel, edge_urn)
+    }
+
+    fn is_edge_authorized_in(root: &Path, target_kernel: &str, edge_urn: &str) -> Result<bool> {
+        let ontology_path = root
             .join("concepts")
             .join(target_kernel)
             .join("conceptkernel.yaml");
@@ -496,10 +656,31 @@ impl EdgeKernel {
             return Ok(true);
         }
 
-        let reader = OntologyReader::new(self.root.clone());
-        let is_authorized = reader.is_edge_authorized(target_kernel, edge_urn)?;
+        // Cached per source kernel until the target's ontology changes
+        let (source, predicate) = match UrnResolver::parse_edge_urn(edge_urn) {
+            Ok(edge) => match edge.version {
+                Some(version) => (edge.source, format!("{}:{}", edge.predicate, version)),
+                None => (edge.source, edge.predicate),
+            },
+            Err(_) => (String::new(), edge_urn.to_string()),
+        };
+        DecisionCache::for_project(root).decide(&source, target_kernel, &predicate, &[ontology_path], || {
+            OntologyReader::new(root.to_path_buf()).is_edge_authorized(target_kernel, edge_urn)
+        })
+    }
+
+    /// Root directory of a registered project
+    fn project_root(&mut self, project: &str) -> Result<PathBuf> {
+        let registry = match self.project_registry.take() {
+            Some(registry) => registry,
+            None => ProjectRegistry::new()?,
+        };
 
-        Ok(is_authorized)
+        let entry = self.project_registry.insert(registry).get(project)?.ok_or_else(|| {
+            CkpError::ProjectError(format!("Project \"{}\" is not registered", project))
+        })?;
+
+        Ok(PathBuf::from(entry.path))
     }
 
     /// Load edge contracts from kernel's ontology
@@ -1484,6 +1665,59 @@ spec:
         assert!(link_str.contains("../"), "Relative path should have ../ components");
     }
 
+    /// Test: Routing into a kernel of another registered project
+    #[test]
+    fn test_route_instance_cross_project() {
+        let (temp, kernel) = setup_test_env();
+
+        let workers = TempDir::new().unwrap();
+        let mut registry = ProjectRegistry::from_dir(temp.path().join("registry")).unwrap();
+        registry
+            .register(crate::project::ProjectInfo {
+                name: "workers".to_string(),
+                id: "proj-workers-20250125".to_string(),
+                path: workers.path().to_string_lossy().to_string(),
+                version: "1.3.14".to_string(),
+                preferred_slot: None,
+            })
+            .unwrap();
+        let mut kernel = kernel.with_project_registry(registry);
+
+        kernel.create_edge("PRODUCES", "Gateway", "workers/Worker.Process").unwrap();
+        kernel.create_edge("NOTIFIES", "Gateway", "billing/Invoice").unwrap();
+
+        // Gateway may only feed the workers project
+        let gateway_dir = temp.path().join("concepts/Gateway");
+        fs::create_dir_all(gateway_dir.join("storage/tx-1.inst")).unwrap();
+        fs::write(
+            gateway_dir.join("conceptkernel.yaml"),
+            r#"apiVersion: conceptkernel/v1
+kind: Ontology
+metadata:
+  name: ckp://Gateway:v0.1
+  type: node:cold
+  version: v0.1
+spec:
+  rbac:
+    communication:
+      projects:
+        - workers
+"#,
+        )
+        .unwrap();
+
+        let instance = gateway_dir.join("storage/tx-1.inst");
+        let routed = kernel.route_instance(&instance, "Gateway").unwrap();
+
+        assert_eq!(routed.len(), 1);
+        let expected = workers
+            .path()
+            .join("concepts/Worker.Process/queue/edges/PRODUCES.Gateway/tx-1.inst");
+        assert_eq!(routed[0], expected);
+        assert!(expected.symlink_metadata().unwrap().is_symlink());
+        assert!(expected.exists(), "Symlink should resolve to the source instance");
+    }
+
     // ==================== PHASE 2.3: EDGE KERNEL POLISH TESTS (+4 TESTS) ====================
 
     /// Test: Advanced circular edge detection (A -> B -> C -> A)
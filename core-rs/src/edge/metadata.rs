@@ -4,6 +4,7 @@
 //!
 //! Reference: Node.js v1.3.14 - EdgeKernel.js
 
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 /// Default API version for EdgeMetadata
@@ -16,6 +17,14 @@ fn default_kind() -> String {
     "Edge".to_string()
 }
 
+/// Current edgekernel.yaml format version
+///
+/// - 0: unversioned; Node.js releases before v1.3.14 wrote the edge's fields
+///   flat (`predicate`, `source`, `target`, `version`, `created_at`) without
+///   a URN, later ones wrote the URN-only layout without `formatVersion`
+/// - 1: URN-only layout with `formatVersion`
+pub const EDGE_FORMAT_VERSION: u32 = 1;
+
 /// Edge metadata representing a relationship between two kernels
 ///
 /// File format: edgekernel.yaml (Kubernetes-style resource definition)
@@ -31,15 +40,19 @@ fn default_kind() -> String {
 /// ```yaml
 /// apiVersion: conceptkernel/v1
 /// kind: Edge
+/// formatVersion: 1
 /// urn: "ckp://Edge.PRODUCES.System.Consensus-to-System.Proof:v1.3.16"
 /// createdAt: "2025-11-29T21:27:59.255335+00:00"
 /// ```
 ///
+/// Files of older layouts (see `EDGE_FORMAT_VERSION`) still load; `migrate`
+/// upgrades them to the current format.
+///
 /// ## Design Rationale
 ///
 /// URNs are self-describing - we don't duplicate parseable information.
 /// However, we keep fields in the struct for performance (avoid repeated parsing).
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct EdgeMetadata {
     /// API version (Kubernetes-style)
@@ -50,6 +63,10 @@ pub struct EdgeMetadata {
     #[serde(default = "default_kind")]
     pub kind: String,
 
+    /// Format version of the file this was read from (0 when unversioned)
+    #[serde(default)]
+    pub format_version: u32,
+
     /// Full edge URN (e.g., "ckp://Edge.PRODUCES.Source-to-Target:v1.3.16")
     pub urn: String,
 
@@ -112,6 +129,7 @@ impl EdgeMetadata {
         EdgeMetadata {
             api_version: default_api_version(),
             kind: default_kind(),
+            format_version: EDGE_FORMAT_VERSION,
             urn,
             created_at,
             predicate: predicate.to_string(),
@@ -199,6 +217,7 @@ impl EdgeMetadata {
         Ok(EdgeMetadata {
             api_version: default_api_version(),
             kind: default_kind(),
+            format_version: EDGE_FORMAT_VERSION,
             urn,
             created_at,
             predicate,
@@ -249,6 +268,8 @@ impl EdgeMetadata {
     /// Load EdgeMetadata from YAML string
     ///
     /// Deserializes minimal YAML (apiVersion, kind, urn + createdAt) and parses URN to populate fields.
+    /// Unversioned layouts are accepted (`format_version` is then 0); files
+    /// of a newer format version than this release knows are refused.
     ///
     /// # Arguments
     /// * `yaml_str` - YAML string containing edge metadata
@@ -274,12 +295,67 @@ impl EdgeMetadata {
     /// assert_eq!(metadata.target, "BakeCake");
     /// ```
     pub fn from_yaml(yaml_str: &str) -> Result<Self, String> {
-        // Deserialize minimal YAML
-        let minimal: Self = serde_yaml::from_str(yaml_str)
+        let document: serde_yaml::Value = serde_yaml::from_str(yaml_str)
             .map_err(|e| format!("Failed to parse YAML: {}", e))?;
+        let field = |key: &str| document.get(key).and_then(|value| value.as_str()).map(str::to_string);
+
+        let format_version = match document.get("formatVersion") {
+            Some(version) => version
+                .as_u64()
+                .and_then(|version| u32::try_from(version).ok())
+                .ok_or("Invalid formatVersion")?,
+            None => 0,
+        };
+        if format_version > EDGE_FORMAT_VERSION {
+            return Err(format!(
+                "Edge metadata format version {} is newer than supported ({})",
+                format_version, EDGE_FORMAT_VERSION
+            ));
+        }
+
+        // Pre-v1.3.14 Node.js releases wrote snake_case timestamps
+        let created_at = field("createdAt").or_else(|| field("created_at")).unwrap_or_default();
+
+        // Parse URN to populate other fields, or build it from the flat layout
+        let urn = match field("urn") {
+            Some(urn) => urn,
+            None => match (field("predicate"), field("source"), field("target"), field("version")) {
+                (Some(predicate), Some(source), Some(target), Some(version)) => {
+                    Self::generate_urn(&predicate, &source, &target, &version)
+                }
+                _ => return Err("Edge metadata has neither urn nor predicate/source/target/version".to_string()),
+            },
+        };
+
+        let mut metadata = Self::from_urn(urn, created_at)?;
+        metadata.format_version = format_version;
+        if let Some(api_version) = field("apiVersion") {
+            metadata.api_version = api_version;
+        }
+        Ok(metadata)
+    }
+
+    /// Whether this was read from an older format than the current one
+    pub fn needs_migration(&self) -> bool {
+        self.format_version < EDGE_FORMAT_VERSION
+    }
 
-        // Parse URN to populate other fields
-        Self::from_urn(minimal.urn, minimal.created_at)
+    /// Upgrade metadata read from an older format to the current one
+    ///
+    /// Layouts without a creation time get the migration time.
+    ///
+    /// # Returns
+    /// true if anything changed (the file should be rewritten)
+    pub fn migrate(&mut self) -> bool {
+        if !self.needs_migration() {
+            return false;
+        }
+        self.format_version = EDGE_FORMAT_VERSION;
+        self.kind = default_kind();
+        if self.created_at.is_empty() {
+            self.created_at = chrono::Utc::now().to_rfc3339();
+        }
+        true
     }
 
 
@@ -388,6 +464,29 @@ createdAt: "2025-11-29T21:27:59Z"
     }
 
 
+    #[test]
+    fn test_from_yaml_migrates_older_layouts() {
+        // Flat layout of pre-v1.3.14 Node.js releases
+        let flat = "predicate: PRODUCES\nsource: MixIngredients\ntarget: BakeCake\nversion: v1.3.12\ncreated_at: \"2024-03-01T10:00:00Z\"\n";
+        let mut metadata = EdgeMetadata::from_yaml(flat).unwrap();
+        assert_eq!(metadata.urn, "ckp://Edge.PRODUCES.MixIngredients-to-BakeCake:v1.3.12");
+        assert_eq!((metadata.format_version, metadata.created_at.as_str()), (0, "2024-03-01T10:00:00Z"));
+        assert!(metadata.migrate());
+        assert!(!metadata.migrate());
+
+        let migrated = EdgeMetadata::from_yaml(&metadata.to_yaml().unwrap()).unwrap();
+        assert!(!migrated.needs_migration());
+        assert_eq!(migrated, metadata);
+
+        // Unversioned URN layout
+        let unversioned = "apiVersion: conceptkernel/v1\nkind: Edge\nurn: ckp://Edge.NOTIFIES.A-to-B:v1.3.14\ncreatedAt: \"2025-01-01T00:00:00Z\"\n";
+        assert!(EdgeMetadata::from_yaml(unversioned).unwrap().needs_migration());
+
+        assert!(EdgeMetadata::from_yaml("formatVersion: 9\nurn: ckp://Edge.NOTIFIES.A-to-B:v1.3.14\n").is_err());
+        // Would wrap to version 1 if truncated to u32
+        assert!(EdgeMetadata::from_yaml("formatVersion: 4294967297\nurn: ckp://Edge.NOTIFIES.A-to-B:v1.3.14\n").is_err());
+    }
+
     #[test]
     fn test_get_edge_name() {
         let metadata = EdgeMetadata::new("PRODUCES", "MixIngredients", "BakeCake", "v1.3.14");
@@ -5,13 +5,15 @@
 //!
 //! Reference: Node.js v1.3.14 - EdgeKernel.js
 
+pub mod encryption;
 pub mod kernel;
 pub mod metadata;
 pub mod request_builder;
 
-pub use kernel::EdgeKernel;
-pub use metadata::EdgeMetadata;
-pub use request_builder::{EdgeRequestBuilder, EdgeRequest, EdgeSource, EdgeTarget, NotificationEntry};
+pub use encryption::{read_opened_payloads, OpenedPayloads, PayloadKeyPair, SealedPayload, OPENED_PAYLOADS_ENV, PAYLOAD_KEY_FILE};
+pub use kernel::{EdgeKernel, EdgeRoute};
+pub use metadata::{EdgeMetadata, EDGE_FORMAT_VERSION};
+pub use request_builder::{EdgeRequestBuilder, EdgeRequest, EdgeRequestBatch, EdgeRequestChain, EdgeSource, EdgeTarget, NotificationEntry, SCHEDULED_DIR, SCHEDULED_EXTENSION};
 
 #[cfg(test)]
 mod tests {
@@ -5,16 +5,27 @@
 //!
 //! Reference: Node.js v1.3.14 - EdgeRequestBuilder.js
 
+use crate::causality::Cause;
+use crate::drivers::JobStamp;
 use crate::errors::{CkpError, Result};
-use chrono::{Datelike, Local, Timelike};
+use chrono::{DateTime, Datelike, Local, Timelike, Utc};
 use regex::Regex;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::collections::HashMap;
 use std::fs;
 use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// Directory under concepts/.edges/ holding scheduled request batches
+pub const SCHEDULED_DIR: &str = ".scheduled";
+
+/// Extension of scheduled request batch files
+pub const SCHEDULED_EXTENSION: &str = "edgebatch";
 
 /// Edge request builder for creating routing requests
+#[derive(Debug)]
 pub struct EdgeRequestBuilder {
     /// Project root directory
     root: PathBuf,
@@ -42,6 +53,10 @@ pub struct EdgeRequest {
 
     /// Additional properties
     pub properties: HashMap<String, Value>,
+
+    /// Protocol envelope; causation is the source instance's transaction
+    #[serde(flatten)]
+    pub stamp: JobStamp,
 }
 
 /// Source kernel and instance information
@@ -65,7 +80,7 @@ pub struct EdgeTarget {
 }
 
 /// Notification contract entry from ontology
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct NotificationEntry {
     /// Target kernel URN
     pub target: String,
@@ -78,6 +93,168 @@ pub struct NotificationEntry {
     pub properties: Option<HashMap<String, Value>>,
 }
 
+/// Edge requests for one instance, scheduled for delivery by the edge router
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EdgeRequestBatch {
+    /// Batch ID (transaction ID, also the file name)
+    #[serde(rename = "batchId")]
+    pub batch_id: String,
+
+    /// Earliest delivery time (deliver on the next pass if unset)
+    #[serde(rename = "deliverAfter", default, skip_serializing_if = "Option::is_none")]
+    pub deliver_after: Option<DateTime<Utc>>,
+
+    /// Requests written to their edge inboxes on delivery
+    pub requests: Vec<EdgeRequest>,
+}
+
+impl EdgeRequestBatch {
+    /// Whether the batch may be delivered at `now`
+    pub fn is_due(&self, now: DateTime<Utc>) -> bool {
+        self.deliver_after.is_none_or(|at| at <= now)
+    }
+}
+
+/// Fluent chain of edge requests for one source instance
+///
+/// Created by `EdgeRequestBuilder::requests`; finish with `build`, `emit`
+/// or `schedule`.
+#[derive(Debug, Clone)]
+pub struct EdgeRequestChain<'a> {
+    builder: &'a EdgeRequestBuilder,
+    source_kernel: String,
+    source_instance_path: PathBuf,
+    notifications: Vec<NotificationEntry>,
+    properties: HashMap<String, Value>,
+    deliver_after: Option<DateTime<Utc>>,
+}
+
+impl EdgeRequestChain<'_> {
+    /// Route to a target kernel over a PRODUCES edge
+    pub fn to(self, target: &str) -> Self {
+        self.edge("PRODUCES", target)
+    }
+
+    /// Route to a target kernel over an edge with the given relationship type
+    pub fn edge(mut self, relationship_type: &str, target: &str) -> Self {
+        self.notifications.push(NotificationEntry {
+            target: target.to_string(),
+            relationship_type: Some(relationship_type.to_string()),
+            properties: None,
+        });
+        self
+    }
+
+    /// Route to every entry of a notification contract
+    pub fn notify(mut self, notification_contract: &[NotificationEntry]) -> Self {
+        self.notifications.extend_from_slice(notification_contract);
+        self
+    }
+
+    /// Add a property to every request
+    pub fn with_property(mut self, key: &str, value: Value) -> Self {
+        self.properties.insert(key.to_string(), value);
+        self
+    }
+
+    /// Hold the requests until this time (see `schedule`)
+    pub fn deliver_after(mut self, at: DateTime<Utc>) -> Self {
+        self.deliver_after = Some(at);
+        self
+    }
+
+    /// Hold the requests for this long from now (see `schedule`)
+    pub fn deliver_in(self, delay: Duration) -> Self {
+        let delay = chrono::Duration::from_std(delay).unwrap_or(chrono::Duration::MAX);
+        self.deliver_after(Utc::now().checked_add_signed(delay).unwrap_or(DateTime::<Utc>::MAX_UTC))
+    }
+
+    /// Build the requests without writing them
+    ///
+    /// # Errors
+    ///
+    /// `CkpError::ValidationError` if no target was added, or
+    /// `CkpError::UrnParse` for an invalid target URI
+    pub fn build(self) -> Result<EdgeRequestBatch> {
+        if self.notifications.is_empty() {
+            return Err(CkpError::ValidationError("Edge request chain has no targets".to_string()));
+        }
+
+        let requests = self
+            .notifications
+            .iter()
+            .map(|notif| {
+                self.builder
+                    .build_request(&self.source_kernel, &self.source_instance_path, notif, &self.properties)
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(EdgeRequestBatch {
+            batch_id: self.builder.generate_tx_id(),
+            deliver_after: self.deliver_after,
+            requests,
+        })
+    }
+
+    /// Write the requests into their edge inboxes now
+    ///
+    /// Requests for edges without an inbox are skipped, as in
+    /// `create_edge_requests`.
+    ///
+    /// # Returns
+    ///
+    /// Paths of the written `.edgereq` files
+    ///
+    /// # Errors
+    ///
+    /// `CkpError::ValidationError` if delivery was deferred to a later time;
+    /// use `schedule` for those
+    pub fn emit(self) -> Result<Vec<PathBuf>> {
+        let builder = self.builder;
+        let batch = self.build()?;
+        if !batch.is_due(Utc::now()) {
+            return Err(CkpError::ValidationError(format!(
+                "Edge requests are held until {}; schedule them instead",
+                batch.deliver_after.map(|at| at.to_rfc3339()).unwrap_or_default()
+            )));
+        }
+
+        let mut written = Vec::new();
+        for request in &batch.requests {
+            if let Some(path) = builder.write_request(request)? {
+                written.push(path);
+            }
+        }
+        Ok(written)
+    }
+
+    /// Serialize the requests as a batch for the edge router to deliver
+    ///
+    /// The batch is written to `concepts/.edges/.scheduled/` and delivered
+    /// once its deliver-after time has passed.
+    ///
+    /// # Returns
+    ///
+    /// Path of the batch file
+    pub fn schedule(self) -> Result<PathBuf> {
+        let dir = self.builder.scheduled_dir();
+        let batch = self.build()?;
+
+        fs::create_dir_all(&dir)
+            .map_err(|e| CkpError::IoError(format!("Failed to create scheduled edge directory: {}", e)))?;
+
+        // Write under a temporary name so routers never read a partial batch
+        let path = dir.join(format!("{}.{}", batch.batch_id, SCHEDULED_EXTENSION));
+        let tmp = path.with_extension("tmp");
+        fs::write(&tmp, serde_json::to_vec_pretty(&batch)?)
+            .map_err(|e| CkpError::IoError(format!("Failed to write edge request batch: {}", e)))?;
+        fs::rename(&tmp, &path)?;
+
+        tracing::debug!(batch = %batch.batch_id, requests = batch.requests.len(), "scheduled edge requests");
+        Ok(path)
+    }
+}
+
 impl EdgeRequestBuilder {
     /// Create a new EdgeRequestBuilder
     ///
@@ -235,7 +412,7 @@ impl EdgeRequestBuilder {
                 .create_single_edge_request(source_kernel, source_instance_path, notif)
                 .await
             {
-                eprintln!("[EdgeRequestBuilder] Error creating edge request: {}", e);
+                tracing::warn!(kernel = source_kernel, error = %e, "error creating edge request");
                 // Continue processing remaining notifications
             }
         }
@@ -243,6 +420,107 @@ impl EdgeRequestBuilder {
         Ok(())
     }
 
+    /// Start a chain of edge requests for a minted instance
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use ckp_core::edge::EdgeRequestBuilder;
+    /// # use std::path::{Path, PathBuf};
+    /// # use std::time::Duration;
+    /// let builder = EdgeRequestBuilder::new(PathBuf::from("/my-project"));
+    /// let instance = Path::new("/my-project/concepts/MixIngredients/storage/2511211530456-c8788f41.inst");
+    ///
+    /// // Route to two edges now
+    /// builder
+    ///     .requests("MixIngredients", instance)
+    ///     .to("ckp://BakeCake:v0.1")
+    ///     .edge("NOTIFIES", "ckp://Audit:v0.1")
+    ///     .emit()
+    ///     .unwrap();
+    ///
+    /// // Leave one for the edge router to deliver in an hour
+    /// builder
+    ///     .requests("MixIngredients", instance)
+    ///     .to("ckp://Reports:v0.1")
+    ///     .deliver_in(Duration::from_secs(3600))
+    ///     .schedule()
+    ///     .unwrap();
+    /// ```
+    pub fn requests(&self, source_kernel: &str, source_instance_path: &Path) -> EdgeRequestChain<'_> {
+        EdgeRequestChain {
+            builder: self,
+            source_kernel: source_kernel.to_string(),
+            source_instance_path: source_instance_path.to_path_buf(),
+            notifications: Vec::new(),
+            properties: HashMap::new(),
+            deliver_after: None,
+        }
+    }
+
+    /// Directory holding scheduled edge request batches
+    pub fn scheduled_dir(&self) -> PathBuf {
+        self.root.join("concepts/.edges").join(SCHEDULED_DIR)
+    }
+
+    /// Deliver scheduled batches that are due
+    ///
+    /// Each batch is claimed by renaming it before its requests are written,
+    /// so several routers can poll the same project without delivering a
+    /// batch twice.
+    ///
+    /// # Returns
+    ///
+    /// Number of edge requests written
+    pub fn deliver_scheduled(&self, now: DateTime<Utc>) -> Result<usize> {
+        let dir = self.scheduled_dir();
+        if !dir.is_dir() {
+            return Ok(0);
+        }
+
+        let mut delivered = 0;
+        for entry in fs::read_dir(&dir)?.flatten() {
+            let path = entry.path();
+            if path.extension().and_then(|e| e.to_str()) != Some(SCHEDULED_EXTENSION) {
+                continue;
+            }
+
+            let batch: EdgeRequestBatch = match fs::read(&path).map_err(CkpError::from).and_then(|bytes| {
+                serde_json::from_slice(&bytes).map_err(|e| CkpError::ParseError(e.to_string()))
+            }) {
+                Ok(batch) => batch,
+                Err(e) => {
+                    tracing::warn!(batch = %path.display(), error = %e, "skipping unreadable batch");
+                    continue;
+                }
+            };
+            if !batch.is_due(now) {
+                continue;
+            }
+
+            // Another router claimed it first
+            let claimed = path.with_extension("delivering");
+            if fs::rename(&path, &claimed).is_err() {
+                continue;
+            }
+            for request in &batch.requests {
+                match self.write_request(request) {
+                    Ok(Some(_)) => delivered += 1,
+                    Ok(None) => {}
+                    Err(e) => tracing::warn!(
+                        batch = %batch.batch_id,
+                        request = %request.request_id,
+                        error = %e,
+                        "failed to deliver scheduled request"
+                    ),
+                }
+            }
+            fs::remove_file(&claimed)?;
+        }
+
+        Ok(delivered)
+    }
+
     // ===== PRIVATE HELPER METHODS =====
 
     /// Create a single edge request
@@ -252,6 +530,19 @@ impl EdgeRequestBuilder {
         source_instance_path: &Path,
         notif: &NotificationEntry,
     ) -> Result<()> {
+        let request = self.build_request(source_kernel, source_instance_path, notif, &HashMap::new())?;
+        self.write_request(&request)?;
+        Ok(())
+    }
+
+    /// Build the edge request for one notification entry
+    fn build_request(
+        &self,
+        source_kernel: &str,
+        source_instance_path: &Path,
+        notif: &NotificationEntry,
+        extra_properties: &HashMap<String, Value>,
+    ) -> Result<EdgeRequest> {
         // Extract relationship type (default: PRODUCES)
         let relationship_type = notif
             .relationship_type
@@ -262,24 +553,6 @@ impl EdgeRequestBuilder {
         // Parse target URN to extract kernel name
         let target_kernel = self.extract_target_kernel(&notif.target)?;
 
-        // Construct edge kernel name: {type}.{source}-to-{target}
-        let edge_kernel_name = format!("{}.{}-to-{}", relationship_type, source_kernel, target_kernel);
-
-        // Validate edge kernel inbox exists
-        let edge_inbox = self
-            .root
-            .join("concepts/.edges")
-            .join(&edge_kernel_name)
-            .join("queue/inbox");
-
-        if !edge_inbox.exists() {
-            eprintln!(
-                "[EdgeRequestBuilder] Warning: Edge kernel inbox not found: {}",
-                edge_inbox.display()
-            );
-            return Ok(()); // Skip this notification
-        }
-
         // Generate transaction ID
         let tx_id = self.generate_tx_id();
         let request_id = format!("{}.edgereq", tx_id);
@@ -302,16 +575,22 @@ impl EdgeRequestBuilder {
 
         let target_queue_urn = self.build_ckp_uri(&target_kernel, None, Some("inbox"), None);
 
+        // The instance is named after the transaction that minted it; the
+        // request continues the chain recorded in its receipt
+        let instance_tx_id = instance_filename.strip_suffix(".inst").unwrap_or(instance_filename);
+        let correlation_id = Cause::of_instance(source_instance_path).map(|cause| cause.correlation_id);
+        let stamp = JobStamp::from_source(source_kernel_urn.clone()).caused_by(instance_tx_id, correlation_id.as_deref());
+
         // Build properties
         let mut properties = notif.properties.clone().unwrap_or_default();
+        properties.extend(extra_properties.iter().map(|(k, v)| (k.clone(), v.clone())));
         properties.insert(
             "timestamp".to_string(),
             Value::String(chrono::Utc::now().to_rfc3339()),
         );
 
-        // Build edge request
-        let request = EdgeRequest {
-            request_id: request_id.clone(),
+        Ok(EdgeRequest {
+            request_id,
             source: EdgeSource {
                 kernel: source_kernel_urn,
                 instance: source_instance_urn,
@@ -322,22 +601,47 @@ impl EdgeRequestBuilder {
             },
             relationship_type,
             properties,
-        };
+            stamp,
+        })
+    }
+
+    /// Write an edge request into its edge kernel's inbox
+    ///
+    /// # Returns
+    ///
+    /// Path of the `.edgereq` file, or None if the edge kernel has no inbox
+    fn write_request(&self, request: &EdgeRequest) -> Result<Option<PathBuf>> {
+        // Construct edge kernel name: {type}.{source}-to-{target}
+        let edge_kernel_name = format!(
+            "{}.{}-to-{}",
+            request.relationship_type,
+            self.extract_target_kernel(&request.source.kernel)?,
+            self.extract_target_kernel(&request.target.kernel)?
+        );
+
+        // Validate edge kernel inbox exists
+        let edge_inbox = self
+            .root
+            .join("concepts/.edges")
+            .join(&edge_kernel_name)
+            .join("queue/inbox");
+
+        if !edge_inbox.exists() {
+            tracing::warn!(inbox = %edge_inbox.display(), "edge kernel inbox not found");
+            return Ok(None); // Skip this notification
+        }
 
         // Write .edgereq file
-        let request_path = edge_inbox.join(&request_id);
-        let request_json = serde_json::to_string_pretty(&request)
+        let request_path = edge_inbox.join(&request.request_id);
+        let request_json = serde_json::to_string_pretty(request)
             .map_err(|e| CkpError::Json(e))?;
 
         fs::write(&request_path, request_json)
             .map_err(|e| CkpError::IoError(format!("Failed to write edge request: {}", e)))?;
 
-        println!(
-            "[EdgeRequestBuilder] Created edge request: {}",
-            request_path.display()
-        );
+        tracing::debug!(request = %request_path.display(), "created edge request");
 
-        Ok(())
+        Ok(Some(request_path))
     }
 
     /// Extract target kernel name from CKP URI
@@ -520,6 +824,10 @@ mod tests {
         assert_eq!(request.target.kernel, "ckp://Target:v0.1");
         assert_eq!(request.source.instance, "ckp://Source:v0.1#storage/tx-123.inst");
         assert!(request.properties.contains_key("timestamp"));
+        assert_eq!(request.stamp.protocol_version.as_deref(), Some(crate::VERSION));
+        assert_eq!(request.stamp.source_urn.as_deref(), Some("ckp://Source:v0.1"));
+        assert_eq!(request.stamp.causation_id.as_deref(), Some("tx-123"));
+        assert_eq!(request.stamp.correlation_id.as_deref(), Some("tx-123"));
     }
 
     #[tokio::test]
@@ -670,4 +978,49 @@ mod tests {
         let result6 = builder.extract_target_kernel("ckp://Recipes.Baking:BakeCake:v1.3.12");
         assert_eq!(result6.unwrap(), "BakeCake");
     }
+
+    #[test]
+    fn test_request_chain_emit_and_schedule() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        let inbox = |edge: &str| root.join("concepts/.edges").join(edge).join("queue/inbox");
+        for edge in ["PRODUCES.Source-to-Target", "NOTIFIES.Source-to-Audit"] {
+            fs::create_dir_all(inbox(edge)).unwrap();
+        }
+        let builder = EdgeRequestBuilder::new(root.to_path_buf());
+        let instance_path = root.join("concepts/Source/storage/tx-321.inst");
+
+        // One chain, two edges, shared properties
+        let written = builder
+            .requests("Source", &instance_path)
+            .to("ckp://Target:v0.1")
+            .edge("NOTIFIES", "ckp://Audit:v0.1")
+            .with_property("priority", Value::String("high".to_string()))
+            .emit()
+            .unwrap();
+        assert_eq!(written.len(), 2);
+        let request: EdgeRequest = serde_json::from_slice(&fs::read(&written[1]).unwrap()).unwrap();
+        assert!(written[1].starts_with(inbox("NOTIFIES.Source-to-Audit")));
+        assert_eq!(request.properties["priority"], "high");
+        assert!(builder.requests("Source", &instance_path).emit().is_err());
+
+        // Deferred requests must be scheduled and wait for their time
+        let later = builder
+            .requests("Source", &instance_path)
+            .to("ckp://Target:v0.1")
+            .deliver_in(Duration::from_secs(3600));
+        assert!(later.clone().emit().is_err());
+        let batch_path = later.schedule().unwrap();
+        assert!(batch_path.starts_with(builder.scheduled_dir()));
+
+        let target_inbox = inbox("PRODUCES.Source-to-Target");
+        let count = || fs::read_dir(&target_inbox).unwrap().count();
+        assert_eq!(builder.deliver_scheduled(Utc::now()).unwrap(), 0);
+        assert_eq!(count(), 1);
+        let due = Utc::now() + chrono::Duration::hours(2);
+        assert_eq!(builder.deliver_scheduled(due).unwrap(), 1);
+        assert_eq!(count(), 2);
+        assert!(!batch_path.exists());
+        assert_eq!(builder.deliver_scheduled(due).unwrap(), 0);
+    }
 }
@@ -49,6 +49,9 @@ pub enum CkpError {
     #[error("RBAC error: {0}")]
     Rbac(String),
 
+    #[error("Authentication failed: {0}")]
+    AuthenticationFailed(String),
+
     #[error("Edge routing error: {0}")]
     EdgeRouting(String),
 
@@ -73,6 +76,9 @@ pub enum CkpError {
     #[error("Validation error: {0}")]
     ValidationError(String),
 
+    #[error("Interpolation error: {0}")]
+    Interpolation(String),
+
     #[error("Serialization error: {0}")]
     SerializationError(String),
 
@@ -94,9 +100,37 @@ pub enum CkpError {
     #[error("Project not found")]
     ProjectNotFound,
 
+    #[error("Registry conflict: project \"{project}\" is at revision {found}, expected {expected}")]
+    RegistryConflict {
+        project: String,
+        expected: u64,
+        found: u64,
+    },
+
     #[error("Kernel not found: {0}")]
     KernelNotFound(String),
 
+    #[error("Quota exceeded for {scope}: {resource} at {used} of {limit}")]
+    QuotaExceeded {
+        scope: String,
+        resource: String,
+        used: u64,
+        limit: u64,
+    },
+
+    #[error("Circuit open for {target}: failing fast, retry after {retry_after:?}")]
+    CircuitOpen {
+        target: String,
+        retry_after: std::time::Duration,
+    },
+
+    #[error("Invalid payload of job {tx_id} at {path}: {message}")]
+    PayloadDecode {
+        tx_id: String,
+        path: String,
+        message: String,
+    },
+
     #[error("Invalid path: {0}")]
     InvalidPath(String),
 
@@ -108,6 +142,15 @@ pub enum CkpError {
 
     #[error("Build error: {0}")]
     BuildError(String),
+
+    #[error("Dependency conflict: {0}")]
+    DependencyConflict(String),
+
+    #[error("No compatible package: {0}")]
+    NoCompatiblePackage(String),
+
+    #[error("Missing packages: {0}")]
+    MissingPackages(String),
 }
 
 impl From<regex::Error> for CkpError {
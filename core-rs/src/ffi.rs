@@ -0,0 +1,259 @@
+//! C ABI for embedding in other runtimes
+//!
+//! A minimal, stable C surface so Go, Java (JNA/Panama) and other services
+//! can work with the concepts tree through this crate instead of
+//! reimplementing the protocol. The header is generated by cbindgen into
+//! `core-rs/include/ckp_core.h` when building with the `ffi` feature:
+//!
+//! ```text
+//! cargo rustc --release --lib --features ffi --crate-type cdylib
+//! ```
+//!
+//! Conventions:
+//! - Strings are NUL-terminated UTF-8. Structured results are JSON strings
+//!   with the protocol's camelCase keys.
+//! - Functions return `CKP_OK` (0) on success and `CKP_ERROR` (-1) on
+//!   failure; `ckp_last_error()` then describes the failure.
+//! - Strings returned through `out` parameters are owned by the caller and
+//!   must be released with `ckp_string_free()`.
+//! - Panics never cross the boundary; they are reported as errors.
+//!
+//! ```c
+//! char *tx_id = NULL;
+//! if (ckp_emit("/srv/project", "Shop.Orders", "{\"order\":42}", NULL, &tx_id) != CKP_OK) {
+//!     fprintf(stderr, "emit failed: %s\n", ckp_last_error());
+//! } else {
+//!     ckp_string_free(tx_id);
+//! }
+//! ```
+
+use crate::errors::{CkpError, Result};
+use crate::kernel::Kernel;
+use crate::storage::InstanceScanner;
+use crate::urn::UrnResolver;
+use std::cell::RefCell;
+use std::ffi::{c_char, c_int, CStr, CString};
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::path::PathBuf;
+use std::ptr;
+
+/// Version of this C ABI, bumped on incompatible changes
+pub const CKP_ABI_VERSION: u32 = 1;
+
+/// Success return code
+pub const CKP_OK: c_int = 0;
+
+/// Failure return code; see `ckp_last_error()`
+pub const CKP_ERROR: c_int = -1;
+
+thread_local! {
+    static LAST_ERROR: RefCell<Option<CString>> = const { RefCell::new(None) };
+}
+
+fn set_last_error(message: String) {
+    let message = CString::new(message.replace('\0', "\\0")).unwrap_or_default();
+    LAST_ERROR.with(|last| *last.borrow_mut() = Some(message));
+}
+
+/// Run an FFI call body, recording errors and panics as the last error
+fn ffi_call(body: impl FnOnce() -> Result<()>) -> c_int {
+    LAST_ERROR.with(|last| *last.borrow_mut() = None);
+    match catch_unwind(AssertUnwindSafe(body)) {
+        Ok(Ok(())) => CKP_OK,
+        Ok(Err(e)) => {
+            set_last_error(e.to_string());
+            CKP_ERROR
+        }
+        Err(_) => {
+            set_last_error("Internal panic in ckp_core".to_string());
+            CKP_ERROR
+        }
+    }
+}
+
+/// Borrow a required string argument
+///
+/// # Safety
+/// `value` must be null or a valid NUL-terminated string.
+unsafe fn arg<'a>(name: &str, value: *const c_char) -> Result<&'a str> {
+    if value.is_null() {
+        return Err(CkpError::ValidationError(format!("{} must not be NULL", name)));
+    }
+    CStr::from_ptr(value)
+        .to_str()
+        .map_err(|_| CkpError::ValidationError(format!("{} is not valid UTF-8", name)))
+}
+
+/// Hand a string to the caller through an `out` parameter
+///
+/// # Safety
+/// `out` must be null or valid for writes.
+unsafe fn write_out(out: *mut *mut c_char, value: String) -> Result<()> {
+    if out.is_null() {
+        return Err(CkpError::ValidationError("out must not be NULL".to_string()));
+    }
+    let value = CString::new(value)
+        .map_err(|_| CkpError::SerializationError("Result contains a NUL byte".to_string()))?;
+    *out = value.into_raw();
+    Ok(())
+}
+
+/// Version of the C ABI implemented by this library
+#[no_mangle]
+pub extern "C" fn ckp_abi_version() -> u32 {
+    CKP_ABI_VERSION
+}
+
+/// Message of the last failed call on this thread, or NULL
+///
+/// The string is owned by the library and stays valid until the next
+/// `ckp_*` call on the same thread.
+#[no_mangle]
+pub extern "C" fn ckp_last_error() -> *const c_char {
+    LAST_ERROR.with(|last| last.borrow().as_ref().map_or(ptr::null(), |message| message.as_ptr()))
+}
+
+/// Release a string returned by this library
+///
+/// # Safety
+/// `value` must be null or a string returned through an `out` parameter of
+/// this library that has not been freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn ckp_string_free(value: *mut c_char) {
+    if !value.is_null() {
+        drop(CString::from_raw(value));
+    }
+}
+
+/// Emit a job to a kernel's inbox
+///
+/// `payload_json` is the job payload as JSON. With a non-NULL `source`
+/// kernel the emit is RBAC-checked against its ontology; with NULL the job
+/// is recorded as coming from `external`. On success `tx_id_out` receives
+/// the transaction ID.
+///
+/// # Safety
+/// String arguments must be valid NUL-terminated strings (`source` may be
+/// NULL) and `tx_id_out` must be valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn ckp_emit(
+    root: *const c_char,
+    target: *const c_char,
+    payload_json: *const c_char,
+    source: *const c_char,
+    tx_id_out: *mut *mut c_char,
+) -> c_int {
+    ffi_call(|| {
+        let root = PathBuf::from(arg("root", root)?);
+        let target = arg("target", target)?;
+        let payload = serde_json::from_str(arg("payload_json", payload_json)?)?;
+        let source = if source.is_null() { None } else { Some(arg("source", source)?.to_string()) };
+
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()?;
+        let enable_rbac = source.is_some();
+        let mut kernel = Kernel::new(root, source, enable_rbac);
+        let tx_id = runtime.block_on(kernel.emit(target, payload))?;
+        write_out(tx_id_out, tx_id)
+    })
+}
+
+/// Parse a kernel URN into its components
+///
+/// On success `json_out` receives an object with `kernel`, `version`,
+/// `stage` and `path` (the last two may be null), plus `txId` when the URN
+/// addresses an instance.
+///
+/// # Safety
+/// `urn` must be a valid NUL-terminated string and `json_out` must be valid
+/// for writes.
+#[no_mangle]
+pub unsafe extern "C" fn ckp_parse_urn(urn: *const c_char, json_out: *mut *mut c_char) -> c_int {
+    ffi_call(|| {
+        let urn = arg("urn", urn)?;
+        let parsed = UrnResolver::parse(urn)?;
+        let json = serde_json::json!({
+            "kernel": parsed.kernel,
+            "version": parsed.version,
+            "stage": parsed.stage,
+            "path": parsed.path,
+            "txId": UrnResolver::extract_tx_id(urn),
+        });
+        write_out(json_out, json.to_string())
+    })
+}
+
+/// List a kernel's storage instances, sorted by name
+///
+/// `limit` caps the number of instances (0 = all). On success `json_out`
+/// receives a JSON array of instance summaries.
+///
+/// # Safety
+/// String arguments must be valid NUL-terminated strings and `json_out`
+/// must be valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn ckp_list_instances(
+    kernel_root: *const c_char,
+    kernel_name: *const c_char,
+    limit: u32,
+    json_out: *mut *mut c_char,
+) -> c_int {
+    ffi_call(|| {
+        let scanner = InstanceScanner::new(
+            PathBuf::from(arg("kernel_root", kernel_root)?),
+            arg("kernel_name", kernel_name)?.to_string(),
+        );
+        let instances = scanner.list_instances(limit as usize)?;
+        write_out(json_out, serde_json::to_string(&instances)?)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::Value;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn take(value: *mut c_char) -> String {
+        let text = unsafe { CStr::from_ptr(value) }.to_str().unwrap().to_string();
+        unsafe { ckp_string_free(value) };
+        text
+    }
+
+    #[test]
+    fn test_c_abi_round_trip() {
+        let temp = TempDir::new().unwrap();
+        fs::create_dir_all(temp.path().join("concepts/Shop.Orders/queue/inbox")).unwrap();
+        let root = CString::new(temp.path().to_str().unwrap()).unwrap();
+        let target = CString::new("Shop.Orders").unwrap();
+        let payload = CString::new(r#"{"order":42}"#).unwrap();
+
+        let mut out = ptr::null_mut();
+        let status = unsafe { ckp_emit(root.as_ptr(), target.as_ptr(), payload.as_ptr(), ptr::null(), &mut out) };
+        assert_eq!(status, CKP_OK);
+        assert!(ckp_last_error().is_null());
+        let tx_id = take(out);
+        assert!(temp.path().join(format!("concepts/Shop.Orders/queue/inbox/{}.job", tx_id)).exists());
+
+        let urn = CString::new("ckp://Shop.Orders:v0.1#storage/tx-123.inst").unwrap();
+        let status = unsafe { ckp_parse_urn(urn.as_ptr(), &mut out) };
+        assert_eq!(status, CKP_OK);
+        let parsed: Value = serde_json::from_str(&take(out)).unwrap();
+        assert_eq!(parsed["kernel"], "Shop.Orders");
+        assert_eq!(parsed["txId"], "tx-123");
+
+        // Failures leave a message for the caller and no output
+        let bad = CString::new("{not json").unwrap();
+        let mut untouched = ptr::null_mut();
+        let status = unsafe { ckp_emit(root.as_ptr(), target.as_ptr(), bad.as_ptr(), ptr::null(), &mut untouched) };
+        assert_eq!(status, CKP_ERROR);
+        assert!(untouched.is_null());
+        assert!(!ckp_last_error().is_null());
+        let status = unsafe { ckp_parse_urn(ptr::null(), &mut untouched) };
+        assert_eq!(status, CKP_ERROR);
+        let message = unsafe { CStr::from_ptr(ckp_last_error()) }.to_str().unwrap();
+        assert!(message.contains("urn must not be NULL"));
+    }
+}
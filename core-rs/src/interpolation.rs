@@ -0,0 +1,320 @@
+//! Environment variable and secret interpolation for configuration files
+//!
+//! String values in `.ckproject` and `conceptkernel.yaml` may reference
+//! values that should not be committed with the ontology:
+//!
+//! - `${VAR}` - environment variable, error if unset
+//! - `${VAR:-fallback}` - environment variable with a fallback
+//! - `${secret:name}` - secret looked up through the SecretProviders
+//! - `$${...}` - a literal `${...}`
+//!
+//! Interpolation runs on parsed YAML string scalars, never on raw file text,
+//! so a substituted value cannot inject YAML structure. Mapping keys are left
+//! untouched.
+//!
+//! By default secrets come from `CKP_SECRET_<NAME>` environment variables.
+//! Other backends implement [`SecretProvider`] and are installed for the
+//! whole process with [`Interpolator::install`].
+
+use crate::errors::{CkpError, Result};
+use serde::de::DeserializeOwned;
+use serde_yaml::Value;
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::{Arc, RwLock};
+
+/// Source of values for `${secret:name}` references
+pub trait SecretProvider: Send + Sync {
+    /// Look up a secret, returning `Ok(None)` if this provider does not hold it
+    fn get_secret(&self, name: &str) -> Result<Option<String>>;
+}
+
+/// Secrets from `CKP_SECRET_<NAME>` environment variables
+///
+/// The name is upper-cased and other non-alphanumerics become `_`, so
+/// `${secret:db-password}` reads `CKP_SECRET_DB_PASSWORD`.
+#[derive(Debug, Clone, Default)]
+pub struct EnvSecretProvider;
+
+impl EnvSecretProvider {
+    /// Environment variable holding a secret
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ckp_core::interpolation::EnvSecretProvider;
+    ///
+    /// assert_eq!(EnvSecretProvider::variable_name("db-password"), "CKP_SECRET_DB_PASSWORD");
+    /// ```
+    pub fn variable_name(name: &str) -> String {
+        let name: String = name
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c.to_ascii_uppercase() } else { '_' })
+            .collect();
+        format!("CKP_SECRET_{}", name)
+    }
+}
+
+impl SecretProvider for EnvSecretProvider {
+    fn get_secret(&self, name: &str) -> Result<Option<String>> {
+        Ok(env::var(Self::variable_name(name)).ok())
+    }
+}
+
+/// Secrets stored one per file, as mounted by Docker and Kubernetes
+///
+/// `${secret:db-password}` reads `<dir>/db-password` without its trailing newline.
+#[derive(Debug, Clone)]
+pub struct FileSecretProvider {
+    dir: PathBuf,
+}
+
+impl FileSecretProvider {
+    pub fn new(dir: PathBuf) -> Self {
+        Self { dir }
+    }
+}
+
+impl SecretProvider for FileSecretProvider {
+    fn get_secret(&self, name: &str) -> Result<Option<String>> {
+        // Secret names never address files outside the directory
+        if name.is_empty() || name.starts_with('.') || name.contains(['/', '\\']) {
+            return Ok(None);
+        }
+
+        let path = self.dir.join(name);
+        if !path.is_file() {
+            return Ok(None);
+        }
+
+        let content = fs::read_to_string(&path)
+            .map_err(|e| CkpError::IoError(format!("Failed to read secret {}: {}", name, e)))?;
+        Ok(Some(content.trim_end_matches(['\r', '\n']).to_string()))
+    }
+}
+
+/// Process-wide interpolator, see [`Interpolator::install`]
+static CURRENT: RwLock<Option<Interpolator>> = RwLock::new(None);
+
+/// Resolves `${...}` references in configuration values
+#[derive(Clone)]
+pub struct Interpolator {
+    /// Secret providers, consulted in order
+    providers: Vec<Arc<dyn SecretProvider>>,
+}
+
+impl Default for Interpolator {
+    /// Environment variables, with secrets from `CKP_SECRET_<NAME>`
+    fn default() -> Self {
+        Self::new().with_provider(Arc::new(EnvSecretProvider))
+    }
+}
+
+impl Interpolator {
+    /// Interpolator without secret providers (environment variables only)
+    pub fn new() -> Self {
+        Self { providers: Vec::new() }
+    }
+
+    /// Add a secret provider, consulted after the ones already added
+    pub fn with_provider(mut self, provider: Arc<dyn SecretProvider>) -> Self {
+        self.providers.push(provider);
+        self
+    }
+
+    /// Interpolator used when loading configuration files
+    pub fn current() -> Self {
+        CURRENT
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+            .unwrap_or_default()
+    }
+
+    /// Use this interpolator for every configuration file loaded from now on
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use ckp_core::interpolation::{Interpolator, FileSecretProvider};
+    /// use std::path::PathBuf;
+    /// use std::sync::Arc;
+    ///
+    /// Interpolator::default()
+    ///     .with_provider(Arc::new(FileSecretProvider::new(PathBuf::from("/run/secrets"))))
+    ///     .install();
+    /// ```
+    pub fn install(self) {
+        *CURRENT.write().unwrap_or_else(|e| e.into_inner()) = Some(self);
+    }
+
+    /// Resolve every reference in a string
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ckp_core::interpolation::Interpolator;
+    ///
+    /// let interpolator = Interpolator::new();
+    /// assert_eq!(interpolator.interpolate_str("${CKP_UNSET_EXAMPLE:-8080}").unwrap(), "8080");
+    /// assert_eq!(interpolator.interpolate_str("$${HOME}").unwrap(), "${HOME}");
+    /// ```
+    pub fn interpolate_str(&self, input: &str) -> Result<String> {
+        let mut output = String::with_capacity(input.len());
+        let mut rest = input;
+
+        while let Some(start) = rest.find('$') {
+            output.push_str(&rest[..start]);
+            let tail = &rest[start..];
+
+            if let Some(escaped) = tail.strip_prefix("$${") {
+                output.push_str("${");
+                rest = escaped;
+            } else if let Some(expr) = tail.strip_prefix("${") {
+                let end = expr.find('}').ok_or_else(|| {
+                    CkpError::Interpolation(format!("Unterminated reference in \"{}\"", input))
+                })?;
+                output.push_str(&self.resolve(&expr[..end])?);
+                rest = &expr[end + 1..];
+            } else {
+                output.push('$');
+                rest = &tail[1..];
+            }
+        }
+
+        output.push_str(rest);
+        Ok(output)
+    }
+
+    /// Resolve every reference in the string scalars of a YAML value
+    pub fn interpolate_value(&self, value: &mut Value) -> Result<()> {
+        match value {
+            Value::String(s) => *s = self.interpolate_str(s)?,
+            Value::Sequence(items) => {
+                for item in items {
+                    self.interpolate_value(item)?;
+                }
+            }
+            Value::Mapping(map) => {
+                for item in map.values_mut() {
+                    self.interpolate_value(item)?;
+                }
+            }
+            Value::Tagged(tagged) => self.interpolate_value(&mut tagged.value)?,
+            Value::Null | Value::Bool(_) | Value::Number(_) => {}
+        }
+        Ok(())
+    }
+
+    /// Parse YAML, resolving references before deserializing
+    ///
+    /// YAML syntax and shape errors are returned as `CkpError::Yaml`.
+    pub fn parse_yaml<T: DeserializeOwned>(&self, content: &str) -> Result<T> {
+        if !content.contains("${") {
+            return Ok(serde_yaml::from_str(content)?);
+        }
+
+        let mut value: Value = serde_yaml::from_str(content)?;
+        self.interpolate_value(&mut value)?;
+        Ok(serde_yaml::from_value(value)?)
+    }
+
+    fn resolve(&self, expr: &str) -> Result<String> {
+        if let Some(name) = expr.strip_prefix("secret:") {
+            for provider in &self.providers {
+                if let Some(value) = provider.get_secret(name)? {
+                    return Ok(value);
+                }
+            }
+            return Err(CkpError::Interpolation(format!("Secret \"{}\" not found", name)));
+        }
+
+        let (name, fallback) = match expr.split_once(":-") {
+            Some((name, fallback)) => (name, Some(fallback)),
+            None => (expr, None),
+        };
+
+        let valid = name.chars().next().is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+            && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+        if !valid {
+            return Err(CkpError::Interpolation(format!("Invalid variable name \"{}\"", name)));
+        }
+
+        match (env::var(name), fallback) {
+            (Ok(value), _) => Ok(value),
+            (Err(_), Some(fallback)) => Ok(fallback.to_string()),
+            (Err(_), None) => Err(CkpError::Interpolation(format!(
+                "Environment variable {} is not set",
+                name
+            ))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use tempfile::TempDir;
+
+    struct MapProvider(HashMap<String, String>);
+
+    impl SecretProvider for MapProvider {
+        fn get_secret(&self, name: &str) -> Result<Option<String>> {
+            Ok(self.0.get(name).cloned())
+        }
+    }
+
+    #[test]
+    fn test_interpolate_env_and_fallback() {
+        env::set_var("CKP_TEST_INTERP_HOST", "db.internal");
+        let interpolator = Interpolator::new();
+
+        assert_eq!(
+            interpolator.interpolate_str("postgres://${CKP_TEST_INTERP_HOST}:${CKP_TEST_INTERP_PORT:-5432}/ck").unwrap(),
+            "postgres://db.internal:5432/ck"
+        );
+        assert_eq!(interpolator.interpolate_str("cost: $5, ${}").unwrap_err().to_string(),
+            "Interpolation error: Invalid variable name \"\"");
+        assert_eq!(interpolator.interpolate_str("price $5").unwrap(), "price $5");
+        assert_eq!(interpolator.interpolate_str("$${CKP_TEST_INTERP_HOST}").unwrap(), "${CKP_TEST_INTERP_HOST}");
+
+        assert!(matches!(
+            interpolator.interpolate_str("${CKP_TEST_INTERP_MISSING}"),
+            Err(CkpError::Interpolation(_))
+        ));
+        assert!(interpolator.interpolate_str("${CKP_TEST_INTERP_HOST").is_err());
+    }
+
+    #[test]
+    fn test_secret_providers_in_order() {
+        let temp = TempDir::new().unwrap();
+        fs::write(temp.path().join("api-token"), "from-file\n").unwrap();
+
+        let mut secrets = HashMap::new();
+        secrets.insert("api-token".to_string(), "from-map".to_string());
+
+        let interpolator = Interpolator::new()
+            .with_provider(Arc::new(FileSecretProvider::new(temp.path().to_path_buf())))
+            .with_provider(Arc::new(MapProvider(secrets)));
+        assert_eq!(interpolator.interpolate_str("Bearer ${secret:api-token}").unwrap(), "Bearer from-file");
+        assert!(interpolator.interpolate_str("${secret:missing}").is_err());
+        assert!(interpolator.interpolate_str("${secret:../api-token}").is_err());
+
+        env::set_var("CKP_SECRET_INTERP_ENV_TOKEN", "from-env");
+        assert_eq!(Interpolator::default().interpolate_str("${secret:interp.env-token}").unwrap(), "from-env");
+    }
+
+    #[test]
+    fn test_parse_yaml_only_touches_values() {
+        env::set_var("CKP_TEST_INTERP_ENDPOINT", "https://api.example.com");
+        let yaml = "endpoint: ${CKP_TEST_INTERP_ENDPOINT}\n\"${CKP_TEST_INTERP_ENDPOINT}\": key\nlist:\n  - \"${CKP_TEST_INTERP_ENDPOINT}/v1\"\n  - 3\n";
+
+        let value: Value = Interpolator::new().parse_yaml(yaml).unwrap();
+        assert_eq!(value["endpoint"], Value::from("https://api.example.com"));
+        assert_eq!(value["${CKP_TEST_INTERP_ENDPOINT}"], Value::from("key"));
+        assert_eq!(value["list"][0], Value::from("https://api.example.com/v1"));
+        assert_eq!(value["list"][1], Value::from(3));
+    }
+}
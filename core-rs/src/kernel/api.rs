@@ -4,8 +4,9 @@
 //! - Read jobs from inbox and edge queues
 //! - Mint evidence to storage
 //! - Archive processed jobs
-//! - Send edge messages to other kernels
-//! - Adopt context from source kernels
+//! - Send edge messages to other kernels, sealed if the target asks for it
+//! - Stream edge responses back in chunks (tokens, progress)
+//! - Adopt context from source kernels, including ones created by Node.js
 //!
 //! ## Usage Example
 //!
@@ -35,9 +36,14 @@
 //! }
 //! ```
 
+use crate::drivers::QueueCounters;
+use crate::edge::encryption::{self, SealedPayload};
 use crate::errors::{CkpError, Result};
+use crate::ontology::config_reader::{KernelClass, Spec};
+use crate::project::QuotaAccountant;
+use crate::storage::{shard, InstanceScanner};
 use serde::{Serialize, Deserialize};
-use std::path::{Path, PathBuf};
+use std::path::{Component, Path, PathBuf};
 use std::fs;
 use std::env;
 use chrono::Utc;
@@ -107,10 +113,25 @@ impl KernelContext {
     /// Mint evidence to storage in BFO-compliant format
     ///
     /// Creates a directory: `storage/{tx_id}-{suffix}.inst/payload.json`
-    /// The suffix is extracted from the tx_id or defaults to "analysis"
+    /// The suffix is extracted from the tx_id or defaults to "analysis".
+    /// Kernels with the `sharding` capability get it under a shard directory.
+    /// Fails with `CkpError::QuotaExceeded` if the kernel's storage quota is reached.
     pub fn mint_evidence<T: Serialize>(&self, evidence: &T, tx_id: &str) -> Result<PathBuf> {
-        let storage_dir = self.kernel_root.join("storage");
-        write_evidence_bfo_compliant(&storage_dir, evidence, tx_id)
+        let project_root = self.kernel_root.parent().and_then(Path::parent);
+        let sharded = project_root.is_some_and(|project_root| shard::is_sharded(project_root, &self.kernel_name));
+
+        // Refuse instances beyond the kernel's storage quota
+        if let Some(quotas) = project_root.and_then(QuotaAccountant::for_project) {
+            let bytes = serde_json::to_vec(evidence).map(|json| json.len() as u64).unwrap_or(0);
+            quotas.check_mint(&self.kernel_name, bytes)?;
+        }
+        let inst_dir = shard::entry_path(&self.kernel_root.join("storage"), &format!("{}.inst", tx_id), sharded);
+        let scanner = InstanceScanner::new(self.kernel_root.clone(), self.kernel_name.clone());
+        let index_update = scanner.begin_index_update(&inst_dir);
+
+        let payload_file = write_evidence_bfo_compliant(&inst_dir, evidence)?;
+        index_update.commit()?;
+        Ok(payload_file)
     }
 
     /// Archive processed job to queue/archive/
@@ -133,6 +154,11 @@ impl KernelContext {
     ///
     /// Writes a message to the target kernel's edge queue at:
     /// `{target_kernel}/queue/edges/{this_kernel}/message-{timestamp}.json`
+    ///
+    /// If the target declares `payload_encryption` for this kernel, the
+    /// message is sealed to its public key and only the target's governor
+    /// can open it (see `edge::encryption`). The seal keeps the payload
+    /// confidential; it does not prove to the target who sent it.
     pub fn send_edge_message<T: Serialize>(
         &self,
         target_kernel: &str,
@@ -145,7 +171,15 @@ impl KernelContext {
             .join("edges")
             .join(&self.kernel_name);
 
-        send_edge_message_protocol_compliant(&edge_dir, message)
+        match encryption::recipient_key(&self.project_root, &self.kernel_name, target_kernel)? {
+            Some(public_key) => {
+                let message = serde_json::to_value(message)
+                    .map_err(|e| CkpError::InvalidJson(format!("Failed to serialize message: {}", e)))?;
+                let sealed = SealedPayload::seal(&message, &self.kernel_name, target_kernel, &public_key)?;
+                send_edge_message_protocol_compliant(&edge_dir, &sealed.to_message()?)
+            }
+            None => send_edge_message_protocol_compliant(&edge_dir, message),
+        }
     }
 
     /// Read edge responses from a source kernel
@@ -161,12 +195,71 @@ impl KernelContext {
         read_edge_responses_protocol_compliant(&response_dir)
     }
 
+    /// Start a streamed response to another kernel
+    ///
+    /// Chunks are written to the same place as single responses:
+    /// `{target_kernel}/queue/edges/{this_kernel}/`
+    pub fn stream_edge_response(&self, target_kernel: &str, task: &str, process_urn: &str) -> Result<EdgeResponseStream> {
+        let response_dir = self.project_root
+            .join("concepts")
+            .join(target_kernel)
+            .join("queue")
+            .join("edges")
+            .join(&self.kernel_name);
+
+        fs::create_dir_all(&response_dir)
+            .map_err(|e| CkpError::IoError(format!("Failed to create edge dir: {}", e)))?;
+
+        let stream_id = format!("{}-{}", Utc::now().timestamp_millis(), &uuid::Uuid::new_v4().simple().to_string()[..8]);
+        Ok(EdgeResponseStream {
+            response_dir,
+            stream_id,
+            task: task.to_string(),
+            process_urn: process_urn.to_string(),
+            sequence: 0,
+        })
+    }
+
+    /// Read the chunks of a streamed response from a source kernel
+    ///
+    /// Call again to pick up chunks sent since; the stream is done once
+    /// `complete` is set.
+    pub fn read_edge_stream(&self, source_kernel: &str, stream_id: &str) -> Result<EdgeStream> {
+        let mut chunks: Vec<EdgeResponse> = self
+            .read_edge_responses(source_kernel)?
+            .into_iter()
+            .filter(|response| response.stream_id.as_deref() == Some(stream_id))
+            .collect();
+        chunks.sort_by_key(|chunk| chunk.sequence);
+
+        // Only the contiguous run from 0: later chunks wait for the gap to fill
+        let received = chunks
+            .iter()
+            .enumerate()
+            .take_while(|(expected, chunk)| chunk.sequence == Some(*expected as u64))
+            .count();
+        chunks.truncate(received);
+        let complete = chunks.last().is_some_and(|chunk| chunk.is_final);
+
+        Ok(EdgeStream {
+            stream_id: stream_id.to_string(),
+            chunks,
+            complete,
+        })
+    }
+
     /// Adopt context from another kernel (for edge jobs)
     ///
     /// Returns an AdoptedContext with:
     /// - kernel_name: The source kernel name
     /// - working_directory: {source_kernel}/tool/
     /// - llm_instructions: Concatenated *.md files from {source_kernel}/llm/
+    /// - runtime, entrypoint and spec read from the kernel directory,
+    ///   whichever runtime created it (see `AdoptedContext`)
+    ///
+    /// # Errors
+    /// `CkpError::KernelNotFound` if the kernel does not exist,
+    /// `CkpError::InvalidPath` if its entry point lies outside its directory
     pub fn adopt_context(&self, source_kernel: &str) -> Result<AdoptedContext> {
         let source_dir = self.project_root
             .join("concepts")
@@ -180,25 +273,271 @@ impl KernelContext {
     }
 }
 
+/// Runtime a kernel's tool is written for
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KernelRuntime {
+    Node,
+    Python,
+    Rust,
+    Unknown,
+}
+
+impl KernelRuntime {
+    /// Runtime of a kernel type (`node:cold` -> Node)
+    pub fn from_kernel_type(kernel_type: &str) -> Self {
+        match kernel_type.split(':').next().unwrap_or_default().trim().to_ascii_lowercase().as_str() {
+            "node" | "nodejs" | "javascript" | "js" => KernelRuntime::Node,
+            "python" | "py" => KernelRuntime::Python,
+            "rust" | "rs" => KernelRuntime::Rust,
+            _ => KernelRuntime::Unknown,
+        }
+    }
+
+    /// Runtime inferred from the files under a kernel's tool/ directory
+    fn from_tool_dir(tool_dir: &Path) -> Self {
+        if tool_dir.join("package.json").is_file() || tool_dir.join("tool.js").is_file() {
+            KernelRuntime::Node
+        } else if tool_dir.join("tool.py").is_file() {
+            KernelRuntime::Python
+        } else if tool_dir.join("rs").is_dir() || tool_dir.join("Cargo.toml").is_file() {
+            KernelRuntime::Rust
+        } else {
+            KernelRuntime::Unknown
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            KernelRuntime::Node => "node",
+            KernelRuntime::Python => "python",
+            KernelRuntime::Rust => "rust",
+            KernelRuntime::Unknown => "unknown",
+        }
+    }
+}
+
 /// Adopted context from another kernel
+///
+/// A kernel directory may have been created by the Node.js runtime, which
+/// writes camelCase spec keys (`queueContract`) and declares its tool in
+/// `tool/package.json`. Those are mapped to what the Rust runtime reads;
+/// anything it cannot use is reported in `warnings` (and logged) instead of
+/// failing the adoption.
 #[derive(Debug, Clone)]
 pub struct AdoptedContext {
     pub kernel_name: String,
     pub working_directory: PathBuf,
     pub llm_instructions: String,
+
+    /// Kernel directory (concepts/{kernel})
+    pub kernel_dir: PathBuf,
+
+    /// Runtime from `metadata.type`, or from the tool files if the type names none
+    pub runtime: KernelRuntime,
+
+    /// Tool entry point: `metadata.entrypoint`, package.json `main` or tool/tool.js
+    pub entrypoint: Option<PathBuf>,
+
+    /// Kernel spec with Node.js keys mapped (None if the ontology has none or is unreadable)
+    pub spec: Option<Spec>,
+
+    /// Parts of the kernel directory the Rust runtime does not support
+    pub warnings: Vec<String>,
 }
 
 impl AdoptedContext {
     fn new(kernel_dir: PathBuf, kernel_name: &str) -> Result<Self> {
         let working_directory = kernel_dir.join("tool");
         let llm_instructions = load_llm_instructions(&kernel_dir)?;
+        let mut warnings = Vec::new();
+
+        let ontology = match fs::read_to_string(kernel_dir.join("conceptkernel.yaml")) {
+            Ok(content) => serde_yaml::from_str::<serde_yaml::Value>(&content).unwrap_or_else(|e| {
+                warnings.push(format!("conceptkernel.yaml is not valid YAML: {}", e));
+                serde_yaml::Value::Null
+            }),
+            Err(e) => {
+                warnings.push(format!("conceptkernel.yaml could not be read: {}", e));
+                serde_yaml::Value::Null
+            }
+        };
+        let metadata = |key: &str| ontology.get("metadata").and_then(|m| m.get(key)).and_then(|v| v.as_str());
+
+        let kernel_type = metadata("type").unwrap_or_default();
+        let runtime = match KernelRuntime::from_kernel_type(kernel_type) {
+            KernelRuntime::Unknown => KernelRuntime::from_tool_dir(&working_directory),
+            runtime => runtime,
+        };
+        if !kernel_type.is_empty() && KernelClass::from_kernel_type(kernel_type).is_none() {
+            warnings.push(format!(
+                "kernel type '{}' has no hot, warm or cold class; the Rust runtime treats it as unmanaged",
+                kernel_type
+            ));
+        }
+
+        let package_main = if runtime == KernelRuntime::Node {
+            read_package_main(&working_directory, &mut warnings)
+        } else {
+            None
+        };
+        let declared = match (metadata("entrypoint"), package_main) {
+            (Some(entrypoint), _) => Some(("metadata.entrypoint", PathBuf::from(entrypoint))),
+            (None, Some(main)) => Some(("tool/package.json main", Path::new("tool").join(main))),
+            (None, None) => None,
+        };
+        let entrypoint = match declared {
+            Some((source, relative)) => Some(kernel_dir.join(contained(&relative, source, kernel_name)?)),
+            None => Some(working_directory.join("tool.js")).filter(|path| path.is_file()),
+        };
+
+        let spec = ontology.get("spec").and_then(|spec| adopt_spec(spec, &mut warnings));
+
+        for warning in &warnings {
+            tracing::warn!(kernel = kernel_name, runtime = runtime.as_str(), issue = %warning, "adopted kernel setting ignored");
+        }
 
         Ok(Self {
             kernel_name: kernel_name.to_string(),
             working_directory,
             llm_instructions,
+            kernel_dir,
+            runtime,
+            entrypoint,
+            spec,
+            warnings,
         })
     }
+
+    /// Context for working in the adopted kernel's queues and storage
+    pub fn kernel_context(&self) -> KernelContext {
+        KernelContext {
+            kernel_name: self.kernel_name.clone(),
+            project_root: self.kernel_dir.parent().and_then(Path::parent).map(Path::to_path_buf).unwrap_or_default(),
+            kernel_root: self.kernel_dir.clone(),
+        }
+    }
+}
+
+/// `relative` if it stays inside the kernel directory, `..` included
+fn contained<'a>(relative: &'a Path, source: &str, kernel_name: &str) -> Result<&'a Path> {
+    let mut depth = 0usize;
+    for component in relative.components() {
+        depth = match component {
+            Component::Normal(_) => depth + 1,
+            Component::CurDir => depth,
+            Component::ParentDir if depth > 0 => depth - 1,
+            _ => {
+                return Err(CkpError::InvalidPath(format!(
+                    "{} of {} escapes the kernel directory: {}",
+                    source,
+                    kernel_name,
+                    relative.display()
+                )))
+            }
+        };
+    }
+    Ok(relative)
+}
+
+/// `main` of a Node.js tool's package.json
+fn read_package_main(tool_dir: &Path, warnings: &mut Vec<String>) -> Option<String> {
+    let content = fs::read_to_string(tool_dir.join("package.json")).ok()?;
+    match serde_json::from_str::<serde_json::Value>(&content) {
+        Ok(package) => package.get("main").and_then(|main| main.as_str()).map(str::to_string),
+        Err(e) => {
+            warnings.push(format!("tool/package.json is not valid JSON: {}", e));
+            None
+        }
+    }
+}
+
+/// Spec of an adopted ontology, mapping Node.js camelCase keys to snake_case
+///
+/// Only top-level spec keys are renamed: keys inside contracts may be data
+/// (field names, edge names). Keys that are not fields of `Spec` are
+/// reported rather than silently dropped.
+fn adopt_spec(spec: &serde_yaml::Value, warnings: &mut Vec<String>) -> Option<Spec> {
+    let serde_yaml::Value::Mapping(declared) = spec else {
+        warnings.push("spec is not a mapping; ignored".to_string());
+        return None;
+    };
+
+    let known = struct_fields::<Spec>();
+    let mut mapped = serde_yaml::Mapping::new();
+    for (key, value) in declared {
+        let Some(name) = key.as_str() else {
+            continue;
+        };
+        let snake = snake_case(name);
+        if snake != name && declared.contains_key(snake.as_str()) {
+            warnings.push(format!("spec.{} is shadowed by spec.{}; ignored", name, snake));
+            continue;
+        }
+        if !known.contains(&snake.as_str()) {
+            warnings.push(format!("spec.{} is not supported by the Rust runtime; ignored", name));
+            continue;
+        }
+        mapped.insert(serde_yaml::Value::String(snake), value.clone());
+    }
+
+    match serde_yaml::from_value(serde_yaml::Value::Mapping(mapped)) {
+        Ok(spec) => Some(spec),
+        Err(e) => {
+            warnings.push(format!("spec could not be read by the Rust runtime: {}", e));
+            None
+        }
+    }
+}
+
+/// Field names a derived `Deserialize` struct accepts
+///
+/// Serde hands them to `deserialize_struct`; this deserializer records them
+/// and stops there.
+fn struct_fields<T: serde::de::DeserializeOwned>() -> &'static [&'static str] {
+    struct FieldNames<'a>(&'a mut &'static [&'static str]);
+
+    impl<'de> serde::Deserializer<'de> for FieldNames<'_> {
+        type Error = serde::de::value::Error;
+
+        fn deserialize_any<V: serde::de::Visitor<'de>>(self, _visitor: V) -> std::result::Result<V::Value, Self::Error> {
+            Err(serde::de::Error::custom("not a struct"))
+        }
+
+        fn deserialize_struct<V: serde::de::Visitor<'de>>(
+            self,
+            _name: &'static str,
+            fields: &'static [&'static str],
+            _visitor: V,
+        ) -> std::result::Result<V::Value, Self::Error> {
+            *self.0 = fields;
+            Err(serde::de::Error::custom("field names read"))
+        }
+
+        serde::forward_to_deserialize_any! {
+            bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string bytes byte_buf option unit
+            unit_struct newtype_struct seq tuple tuple_struct map enum identifier ignored_any
+        }
+    }
+
+    let mut fields: &'static [&'static str] = &[];
+    let _ = T::deserialize(FieldNames(&mut fields));
+    fields
+}
+
+/// `queueContract` -> `queue_contract`
+fn snake_case(name: &str) -> String {
+    let mut snake = String::with_capacity(name.len() + 4);
+    for c in name.chars() {
+        if c.is_ascii_uppercase() {
+            if !snake.is_empty() {
+                snake.push('_');
+            }
+            snake.push(c.to_ascii_lowercase());
+        } else {
+            snake.push(c);
+        }
+    }
+    snake
 }
 
 /// Job representation with file path and parsed payload
@@ -236,6 +575,10 @@ fn default_mode() -> String {
 }
 
 /// Edge response representation
+///
+/// A streamed response is sent as partial responses sharing a `streamId`,
+/// numbered by `sequence` from 0, the last one marked `final`. Single
+/// responses carry none of these fields.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct EdgeResponse {
     pub task: String,
@@ -243,6 +586,85 @@ pub struct EdgeResponse {
     pub timestamp: String,
     #[serde(rename = "processUrn")]
     pub process_urn: String,
+    #[serde(rename = "streamId", default, skip_serializing_if = "Option::is_none")]
+    pub stream_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sequence: Option<u64>,
+    #[serde(rename = "final", default, skip_serializing_if = "std::ops::Not::not")]
+    pub is_final: bool,
+}
+
+/// Writer of a streamed edge response
+///
+/// Each chunk is written as `response-{streamId}-{sequence}.json` to the
+/// target's edge queue, so the reader can pick chunks up while the
+/// response is still being produced (tokens, progress).
+pub struct EdgeResponseStream {
+    response_dir: PathBuf,
+    stream_id: String,
+    task: String,
+    process_urn: String,
+    sequence: u64,
+}
+
+impl EdgeResponseStream {
+    pub fn stream_id(&self) -> &str {
+        &self.stream_id
+    }
+
+    /// Send a partial response
+    pub fn send(&mut self, chunk: &str) -> Result<PathBuf> {
+        self.write(chunk, false)
+    }
+
+    /// Send the last chunk, marking the stream complete
+    pub fn finish(mut self, chunk: &str) -> Result<PathBuf> {
+        self.write(chunk, true)
+    }
+
+    fn write(&mut self, chunk: &str, is_final: bool) -> Result<PathBuf> {
+        let response = EdgeResponse {
+            task: self.task.clone(),
+            response: chunk.to_string(),
+            timestamp: Utc::now().to_rfc3339(),
+            process_urn: self.process_urn.clone(),
+            stream_id: Some(self.stream_id.clone()),
+            sequence: Some(self.sequence),
+            is_final,
+        };
+        let response_json = serde_json::to_string_pretty(&response)
+            .map_err(|e| CkpError::InvalidJson(format!("Failed to serialize response: {}", e)))?;
+
+        // Renamed into place so readers never see a partial chunk
+        let response_file = self.response_dir.join(format!("response-{}-{:08}.json", self.stream_id, self.sequence));
+        let tmp_file = response_file.with_extension("json.tmp");
+        fs::write(&tmp_file, response_json)
+            .map_err(|e| CkpError::IoError(format!("Failed to write edge response: {}", e)))?;
+        fs::rename(&tmp_file, &response_file)
+            .map_err(|e| CkpError::IoError(format!("Failed to write edge response: {}", e)))?;
+
+        self.sequence += 1;
+        Ok(response_file)
+    }
+}
+
+/// Chunks of a streamed edge response received so far
+#[derive(Debug, Clone)]
+pub struct EdgeStream {
+    pub stream_id: String,
+
+    /// Chunks in sequence order, up to the first one not received yet
+    pub chunks: Vec<EdgeResponse>,
+
+    /// Whether the final chunk was received (and every chunk before it)
+    pub complete: bool,
+}
+
+impl EdgeStream {
+    /// Received chunks joined in order
+    pub fn text(&self) -> String {
+        self.chunks.iter().map(|chunk| chunk.response.as_str()).collect()
+    }
 }
 
 // ============================================================================
@@ -279,13 +701,9 @@ fn detect_project_root() -> Result<PathBuf> {
 
 /// Scan job files from a queue directory
 fn scan_jobs(queue_dir: &Path) -> Result<Vec<Job>> {
-    let entries = fs::read_dir(queue_dir)
-        .map_err(|e| CkpError::IoError(e.to_string()))?;
-
     let mut jobs = Vec::new();
 
-    for entry in entries.filter_map(|e| e.ok()) {
-        let path = entry.path();
+    for path in crate::storage::shard::list_entries(queue_dir) {
         let filename = path.file_name()
             .and_then(|n| n.to_str())
             .unwrap_or("");
@@ -321,18 +739,12 @@ fn scan_jobs(queue_dir: &Path) -> Result<Vec<Job>> {
 
 /// Write evidence in BFO-compliant format
 fn write_evidence_bfo_compliant<T: Serialize>(
-    storage_dir: &Path,
+    inst_dir: &Path,
     evidence: &T,
-    tx_id: &str
 ) -> Result<PathBuf> {
-    fs::create_dir_all(storage_dir)
-        .map_err(|e| CkpError::IoError(format!("Failed to create storage dir: {}", e)))?;
-
-    // Use tx_id directly as the instance directory name
-    // The tx_id already contains timestamp and unique identifier (e.g., "1764844292952-54a90023")
-    let inst_dir = storage_dir.join(format!("{}.inst", tx_id));
-
-    fs::create_dir_all(&inst_dir)
+    // The instance directory is named after the tx_id, which already contains
+    // timestamp and unique identifier (e.g., "1764844292952-54a90023")
+    fs::create_dir_all(inst_dir)
         .map_err(|e| CkpError::IoError(format!("Failed to create instance dir: {}", e)))?;
 
     // Write payload.json (full evidence)
@@ -348,6 +760,13 @@ fn write_evidence_bfo_compliant<T: Serialize>(
     let evidence_value: serde_json::Value = serde_json::from_str(&evidence_json)
         .map_err(|e| CkpError::InvalidJson(format!("Failed to re-parse evidence: {}", e)))?;
 
+    // Evidence is caused by the job the governor spawned the tool for
+    let cause = crate::causality::current_cause();
+    let causation_id = evidence_value.get("causationId").cloned()
+        .or_else(|| cause.as_ref().map(|c| c.tx_id.clone().into()));
+    let correlation_id = evidence_value.get("correlationId").cloned()
+        .or_else(|| cause.as_ref().map(|c| c.correlation_id.clone().into()));
+
     // Include full evidence in receipt for client delivery
     let receipt = serde_json::json!({
         "type": "event",
@@ -357,6 +776,8 @@ fn write_evidence_bfo_compliant<T: Serialize>(
         "kernel": evidence_value.get("kernel"),
         "timestamp": evidence_value.get("timestamp"),
         "processUrn": evidence_value.get("processUrn").or_else(|| evidence_value.get("process_urn")),
+        "causationId": causation_id,
+        "correlationId": correlation_id,
         // Include actual content fields for client
         "task": evidence_value.get("task"),
         "response": evidence_value.get("response"),
@@ -387,6 +808,7 @@ fn archive_job_file(job_path: &Path, archive_dir: &Path) -> Result<()> {
 
     fs::rename(job_path, &dest)
         .map_err(|e| CkpError::IoError(format!("Failed to archive job: {}", e)))?;
+    QueueCounters::record_removed(job_path);
 
     Ok(())
 }
@@ -403,6 +825,8 @@ fn move_job_file(job_path: &Path, dest_dir: &Path) -> Result<()> {
 
     fs::rename(job_path, &dest)
         .map_err(|e| CkpError::IoError(format!("Failed to move job: {}", e)))?;
+    QueueCounters::record_removed(job_path);
+    QueueCounters::record_added(&dest);
 
     Ok(())
 }
@@ -520,6 +944,86 @@ mod tests {
         assert_eq!(payload.mode, "analyze");
     }
 
+    #[test]
+    fn test_streamed_edge_response_is_read_in_order() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let context = |kernel: &str| KernelContext {
+            kernel_name: kernel.to_string(),
+            project_root: temp.path().to_path_buf(),
+            kernel_root: temp.path().join("concepts").join(kernel),
+        };
+        let (llm, caller) = (context("LLM.Claude"), context("Chat.Session"));
+
+        let mut stream = llm.stream_edge_response("Chat.Session", "summarize", "ckp://Process#1").unwrap();
+        let stream_id = stream.stream_id().to_string();
+        stream.send("Hello").unwrap();
+        let second = stream.send(", ").unwrap();
+
+        let partial = caller.read_edge_stream("LLM.Claude", &stream_id).unwrap();
+        assert_eq!((partial.text(), partial.complete), ("Hello, ".to_string(), false));
+
+        // A chunk after a gap is held back until the gap is filled
+        let held = fs::read_to_string(&second).unwrap();
+        fs::remove_file(&second).unwrap();
+        stream.finish("world").unwrap();
+        let gap = caller.read_edge_stream("LLM.Claude", &stream_id).unwrap();
+        assert_eq!((gap.chunks.len(), gap.complete), (1, false));
+
+        fs::write(&second, held).unwrap();
+        let done = caller.read_edge_stream("LLM.Claude", &stream_id).unwrap();
+        assert_eq!((done.text(), done.complete), ("Hello, world".to_string(), true));
+        assert_eq!(caller.read_edge_responses("LLM.Claude").unwrap().len(), 3);
+    }
+
+    #[test]
+    fn test_adopt_context_of_node_kernel() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let kernel_dir = temp.path().join("concepts/Chat.Session");
+        fs::create_dir_all(kernel_dir.join("tool")).unwrap();
+        fs::create_dir_all(kernel_dir.join("llm")).unwrap();
+        fs::write(
+            kernel_dir.join("conceptkernel.yaml"),
+            "metadata:\n  urn: ckp://Chat.Session:v0.1\n  type: node:manual\nspec:\n  queueContract:\n    processing: lifo\n  sessionStore: redis\n",
+        )
+        .unwrap();
+        fs::write(kernel_dir.join("tool/package.json"), r#"{"name": "chat-session", "main": "src/index.js"}"#).unwrap();
+        fs::write(kernel_dir.join("llm/persona.md"), "Be brief.").unwrap();
+
+        let caller = KernelContext {
+            kernel_name: "LLM.Claude".to_string(),
+            project_root: temp.path().to_path_buf(),
+            kernel_root: temp.path().join("concepts/LLM.Claude"),
+        };
+        let adopted = caller.adopt_context("Chat.Session").unwrap();
+        assert_eq!(adopted.runtime, KernelRuntime::Node);
+        assert_eq!(adopted.entrypoint, Some(kernel_dir.join("tool/src/index.js")));
+        assert!(adopted.llm_instructions.contains("Be brief."));
+
+        let queue_contract = adopted.spec.as_ref().and_then(|spec| spec.queue_contract.as_ref()).unwrap();
+        assert_eq!(queue_contract.processing.as_deref(), Some("lifo"));
+        assert_eq!(adopted.warnings.len(), 2);
+        assert!(adopted.warnings[0].contains("node:manual"));
+        assert!(adopted.warnings[1].contains("spec.sessionStore is not supported"));
+
+        // Spec fields are known whether or not they would serialize back
+        fs::write(
+            kernel_dir.join("conceptkernel.yaml"),
+            "metadata:\n  type: node:hot\nspec:\n  retentionContract: null\n  payloadEncryption: null\n",
+        )
+        .unwrap();
+        assert!(caller.adopt_context("Chat.Session").unwrap().warnings.is_empty());
+
+        // Entry points outside the kernel directory are refused
+        fs::write(kernel_dir.join("tool/package.json"), r#"{"main": "../../../outside.js"}"#).unwrap();
+        assert!(matches!(caller.adopt_context("Chat.Session"), Err(CkpError::InvalidPath(_))));
+        fs::write(kernel_dir.join("conceptkernel.yaml"), "metadata:\n  type: node:hot\n  entrypoint: /usr/bin/env\n").unwrap();
+        assert!(matches!(caller.adopt_context("Chat.Session"), Err(CkpError::InvalidPath(_))));
+
+        let context = adopted.kernel_context();
+        assert_eq!((context.kernel_name(), context.project_root()), ("Chat.Session", temp.path()));
+        assert_eq!(context.kernel_root(), kernel_dir);
+    }
+
     #[test]
     fn test_job_payload_default_mode() {
         let json = r#"{"task": "test"}"#;
@@ -29,7 +29,7 @@ impl KernelBuilder {
     /// 3. Invokes cargo build with proper paths
     /// 4. Returns build artifacts location
     pub fn build_kernel(&self, kernel_name: &str, release: bool) -> Result<PathBuf> {
-        println!("[KernelBuilder] Building kernel: {}", kernel_name);
+        tracing::info!(kernel = %kernel_name, "building kernel");
 
         // Use OntologyReader to get kernel metadata (proper abstraction)
         let ontology = self.ontology_reader.read_by_kernel_name(kernel_name)
@@ -66,9 +66,13 @@ impl KernelBuilder {
             entrypoint_raw.as_str()
         };
 
-        println!("[KernelBuilder] Kernel type: {}", ontology.metadata.kernel_type);
-        println!("[KernelBuilder] Version: {}", ontology.metadata.version.as_ref().unwrap_or(&"unknown".to_string()));
-        println!("[KernelBuilder] Build directory: {}", build_subdir);
+        tracing::debug!(
+            kernel = %kernel_name,
+            kernel_type = %ontology.metadata.kernel_type,
+            version = ontology.metadata.version.as_deref().unwrap_or("unknown"),
+            build_subdir = %build_subdir,
+            "read kernel build metadata"
+        );
 
         // Build path: concepts/{kernel_name}/{build_subdir}
         let build_dir = self.root
@@ -83,8 +87,6 @@ impl KernelBuilder {
             )));
         }
 
-        println!("[KernelBuilder] Build directory: {}", build_dir.display());
-
         // Invoke cargo build
         let mut cmd = Command::new("cargo");
         cmd.arg("build")
@@ -94,7 +96,7 @@ impl KernelBuilder {
             cmd.arg("--release");
         }
 
-        println!("[KernelBuilder] Running: cargo build{}", if release { " --release" } else { "" });
+        tracing::debug!(kernel = %kernel_name, build_dir = %build_dir.display(), release, "running cargo build");
 
         let output = cmd.output()
             .map_err(|e| CkpError::BuildError(format!(
@@ -118,8 +120,7 @@ impl KernelBuilder {
             target_dir.join("debug")
         };
 
-        println!("[KernelBuilder] ✓ Build successful: {}", kernel_name);
-        println!("[KernelBuilder] Binaries at: {}", profile_dir.display());
+        tracing::info!(kernel = %kernel_name, binaries = %profile_dir.display(), "built kernel");
 
         Ok(profile_dir)
     }
@@ -128,7 +129,7 @@ impl KernelBuilder {
     ///
     /// Discovers kernels using OntologyReader, then builds each Rust kernel
     pub fn build_all(&self, release: bool) -> Result<Vec<String>> {
-        println!("[KernelBuilder] Discovering kernels...");
+        tracing::debug!(root = %self.root.display(), "discovering kernels to build");
 
         let concepts_dir = self.root.join("concepts");
         if !concepts_dir.exists() {
@@ -169,7 +170,7 @@ impl KernelBuilder {
             let ontology = match self.ontology_reader.read_by_kernel_name(&kernel_name) {
                 Ok(o) => o,
                 Err(_) => {
-                    println!("[KernelBuilder] ⊘ Skipping {} (no ontology)", kernel_name);
+                    tracing::debug!(kernel = %kernel_name, "skipping kernel without ontology");
                     continue;
                 }
             };
@@ -185,24 +186,20 @@ impl KernelBuilder {
                     built_kernels.push(kernel_name);
                 }
                 Err(e) => {
-                    eprintln!("[KernelBuilder] ✗ Failed to build {}: {}", kernel_name, e);
+                    tracing::error!(kernel = %kernel_name, error = %e, "failed to build kernel");
                     failed_kernels.push(kernel_name);
                 }
             }
         }
 
-        println!("\n[KernelBuilder] ═══════════════════════════════");
-        println!("[KernelBuilder] Build Summary");
-        println!("[KernelBuilder] ═══════════════════════════════");
-        println!("[KernelBuilder] Total:   {} kernels", built_kernels.len() + failed_kernels.len());
-        println!("[KernelBuilder] Success: {} kernels", built_kernels.len());
-        println!("[KernelBuilder] Failed:  {} kernels", failed_kernels.len());
-
+        tracing::info!(
+            total = built_kernels.len() + failed_kernels.len(),
+            succeeded = built_kernels.len(),
+            failed = failed_kernels.len(),
+            "kernel build finished"
+        );
         if !failed_kernels.is_empty() {
-            println!("\n[KernelBuilder] Failed kernels:");
-            for kernel in &failed_kernels {
-                println!("[KernelBuilder]   - {}", kernel);
-            }
+            tracing::warn!(kernels = ?failed_kernels, "kernels failed to build");
         }
 
         Ok(built_kernels)
@@ -252,7 +249,7 @@ impl KernelBuilder {
         match git_driver.get_version() {
             Ok(Some(version_info)) => {
                 if !version_info.is_clean {
-                    println!("[KernelBuilder] Git has uncommitted changes, rebuild needed: {}", kernel_name);
+                    tracing::debug!(kernel = %kernel_name, "uncommitted changes, rebuild needed");
                     return Ok(true);
                 }
             }
@@ -0,0 +1,269 @@
+//! Circuit breaker for emit and routing targets
+//!
+//! Each target has a circuit. It opens after `failure_threshold` consecutive
+//! failures (RBAC denial, missing kernel, I/O error), and while open, calls
+//! to the target fail fast with `CkpError::CircuitOpen` instead of retrying
+//! against a target that keeps failing. After `cooldown`, one call is let
+//! through as a probe: success closes the circuit, failure opens it for
+//! another cooldown.
+//!
+//! A target can have a fallback, which takes over while its circuit is open
+//! and whenever a call to it fails. Fallbacks are not chained.
+//!
+//! ```
+//! use ckp_core::kernel::{CircuitBreaker, CircuitBreakerConfig};
+//! use std::time::Duration;
+//!
+//! let breaker = CircuitBreaker::new(
+//!     CircuitBreakerConfig::default()
+//!         .with_failure_threshold(3)
+//!         .with_cooldown(Duration::from_secs(10))
+//!         .with_fallback("Payments.Primary", "Payments.Backup"),
+//! );
+//! assert_eq!(breaker.fallback("Payments.Primary"), Some("Payments.Backup"));
+//! ```
+
+use crate::errors::{CkpError, Result};
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Consecutive failures that open a circuit by default
+pub const DEFAULT_FAILURE_THRESHOLD: u32 = 5;
+
+/// How long a circuit stays open by default
+pub const DEFAULT_COOLDOWN: Duration = Duration::from_secs(30);
+
+/// Circuit breaker settings
+#[derive(Debug, Clone)]
+pub struct CircuitBreakerConfig {
+    /// Consecutive failures that open a circuit
+    pub failure_threshold: u32,
+
+    /// How long an open circuit fails fast before a probe is let through
+    pub cooldown: Duration,
+
+    /// Fallback target for each target
+    pub fallbacks: HashMap<String, String>,
+}
+
+impl Default for CircuitBreakerConfig {
+    fn default() -> Self {
+        Self { failure_threshold: DEFAULT_FAILURE_THRESHOLD, cooldown: DEFAULT_COOLDOWN, fallbacks: HashMap::new() }
+    }
+}
+
+impl CircuitBreakerConfig {
+    /// Open circuits after this many consecutive failures (at least 1)
+    pub fn with_failure_threshold(mut self, failure_threshold: u32) -> Self {
+        self.failure_threshold = failure_threshold.max(1);
+        self
+    }
+
+    /// Keep open circuits open this long
+    pub fn with_cooldown(mut self, cooldown: Duration) -> Self {
+        self.cooldown = cooldown;
+        self
+    }
+
+    /// Redirect calls to `target` to `fallback` while `target` is failing
+    pub fn with_fallback(mut self, target: &str, fallback: &str) -> Self {
+        self.fallbacks.insert(target.to_string(), fallback.to_string());
+        self
+    }
+}
+
+/// State of a target's circuit
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CircuitState {
+    /// Calls go through
+    Closed,
+    /// Calls fail fast for the remaining cooldown
+    Open { retry_after: Duration },
+    /// Cooldown is over; the next call is a probe
+    HalfOpen,
+}
+
+#[derive(Debug, Default)]
+struct Circuit {
+    failures: u32,
+    opened_at: Option<Instant>,
+    probing: bool,
+}
+
+/// Per-target circuit breaker, shareable between threads
+#[derive(Debug, Default)]
+pub struct CircuitBreaker {
+    config: CircuitBreakerConfig,
+    circuits: Mutex<HashMap<String, Circuit>>,
+}
+
+impl CircuitBreaker {
+    /// Circuit breaker with the given settings
+    pub fn new(config: CircuitBreakerConfig) -> Self {
+        Self { config, circuits: Mutex::new(HashMap::new()) }
+    }
+
+    /// Settings in use
+    pub fn config(&self) -> &CircuitBreakerConfig {
+        &self.config
+    }
+
+    /// Fallback configured for a target
+    pub fn fallback(&self, target: &str) -> Option<&str> {
+        self.config.fallbacks.get(target).map(String::as_str)
+    }
+
+    /// Current state of a target's circuit
+    pub fn state(&self, target: &str) -> CircuitState {
+        let circuits = self.circuits.lock().unwrap();
+        match circuits.get(target).and_then(|circuit| circuit.opened_at) {
+            Some(opened_at) => match self.config.cooldown.checked_sub(opened_at.elapsed()) {
+                Some(retry_after) if !retry_after.is_zero() => CircuitState::Open { retry_after },
+                _ => CircuitState::HalfOpen,
+            },
+            None => CircuitState::Closed,
+        }
+    }
+
+    /// Check whether a call to a target may go ahead
+    ///
+    /// Once the cooldown is over, one caller gets through as the probe and
+    /// others keep failing fast until it reports back.
+    ///
+    /// # Errors
+    /// `CkpError::CircuitOpen` while the target's circuit is open
+    pub fn check(&self, target: &str) -> Result<()> {
+        let mut circuits = self.circuits.lock().unwrap();
+        let Some(circuit) = circuits.get_mut(target) else {
+            return Ok(());
+        };
+        let Some(opened_at) = circuit.opened_at else {
+            return Ok(());
+        };
+
+        let elapsed = opened_at.elapsed();
+        if elapsed >= self.config.cooldown && !circuit.probing {
+            circuit.probing = true;
+            return Ok(());
+        }
+
+        Err(CkpError::CircuitOpen {
+            target: target.to_string(),
+            retry_after: self.config.cooldown.saturating_sub(elapsed),
+        })
+    }
+
+    /// Record a successful call, closing the target's circuit
+    pub fn record_success(&self, target: &str) {
+        if let Some(circuit) = self.circuits.lock().unwrap().remove(target) {
+            if circuit.opened_at.is_some() {
+                tracing::info!(kernel = target, "circuit closed");
+            }
+        }
+    }
+
+    /// Record a failed call, opening the circuit at the threshold or after a failed probe
+    pub fn record_failure(&self, target: &str) {
+        let mut circuits = self.circuits.lock().unwrap();
+        let circuit = circuits.entry(target.to_string()).or_default();
+        circuit.failures = circuit.failures.saturating_add(1);
+
+        if circuit.probing || (circuit.opened_at.is_none() && circuit.failures >= self.config.failure_threshold) {
+            tracing::warn!(
+                kernel = target,
+                failures = circuit.failures,
+                cooldown_ms = self.config.cooldown.as_millis() as u64,
+                "circuit opened"
+            );
+            circuit.opened_at = Some(Instant::now());
+            circuit.probing = false;
+        }
+    }
+
+    /// Run a call against a target through its circuit
+    ///
+    /// The call gets the target to use: the target itself, or its fallback
+    /// if the target's circuit is open or the call to it failed.
+    ///
+    /// # Errors
+    /// The target's error (or `CkpError::CircuitOpen`) if there is no
+    /// fallback, otherwise the fallback's
+    pub fn call<T>(&self, target: &str, mut f: impl FnMut(&str) -> Result<T>) -> Result<T> {
+        let error = match self.call_one(target, &mut f) {
+            Ok(value) => return Ok(value),
+            Err(e) => e,
+        };
+
+        match self.fallback(target) {
+            Some(fallback) => {
+                tracing::warn!(kernel = target, fallback = fallback, error = %error, "redirecting to fallback");
+                self.call_one(fallback, &mut f)
+            }
+            None => Err(error),
+        }
+    }
+
+    fn call_one<T>(&self, target: &str, f: &mut impl FnMut(&str) -> Result<T>) -> Result<T> {
+        self.check(target)?;
+        let result = f(target);
+        match result {
+            Ok(_) => self.record_success(target),
+            Err(_) => self.record_failure(target),
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_circuit_opens_probes_and_falls_back() {
+        let breaker = CircuitBreaker::new(
+            CircuitBreakerConfig::default()
+                .with_failure_threshold(2)
+                .with_cooldown(Duration::from_millis(50)),
+        );
+        let fail = |_: &str| -> Result<()> { Err(CkpError::KernelNotFound("Down".to_string())) };
+
+        // Failures below the threshold pass through
+        assert!(matches!(breaker.call("Down", fail), Err(CkpError::KernelNotFound(_))));
+        assert_eq!(breaker.state("Down"), CircuitState::Closed);
+        assert!(matches!(breaker.call("Down", fail), Err(CkpError::KernelNotFound(_))));
+        assert!(matches!(breaker.state("Down"), CircuitState::Open { .. }));
+
+        // Open: fail fast without calling
+        let mut called = false;
+        let result = breaker.call("Down", |_| {
+            called = true;
+            Ok(())
+        });
+        assert!(matches!(result, Err(CkpError::CircuitOpen { .. })) && !called);
+
+        // After the cooldown, a failed probe reopens and a successful one closes
+        std::thread::sleep(Duration::from_millis(60));
+        assert_eq!(breaker.state("Down"), CircuitState::HalfOpen);
+        assert!(breaker.call("Down", fail).is_err());
+        assert!(matches!(breaker.check("Down"), Err(CkpError::CircuitOpen { .. })));
+        std::thread::sleep(Duration::from_millis(60));
+        breaker.check("Down").unwrap();
+        assert!(matches!(breaker.check("Down"), Err(CkpError::CircuitOpen { .. })), "only one probe at a time");
+        breaker.record_success("Down");
+        assert_eq!(breaker.state("Down"), CircuitState::Closed);
+
+        // Graceful degradation: failures and open circuits go to the fallback
+        let breaker = CircuitBreaker::new(
+            CircuitBreakerConfig::default().with_failure_threshold(1).with_fallback("Primary", "Backup"),
+        );
+        let route = |target: &str| match target {
+            "Primary" => Err(CkpError::IoError("disk full".to_string())),
+            other => Ok(other.to_string()),
+        };
+        assert_eq!(breaker.call("Primary", route).unwrap(), "Backup");
+        assert!(matches!(breaker.state("Primary"), CircuitState::Open { .. }));
+        assert_eq!(breaker.call("Primary", route).unwrap(), "Backup");
+        assert_eq!(breaker.state("Backup"), CircuitState::Closed);
+    }
+}
@@ -2,24 +2,69 @@
 //!
 //! Simplified implementation that:
 //! - Watches inbox directory
-//! - Spawns tool.js or tool.py on file creation
+//! - Spawns tool.js or tool.py on file creation, serving pending queues in
+//!   the kernel's processing order (see `ProcessingOrder`)
+//! - Opens edge payloads sealed to the kernel in memory and hands them to
+//!   the tool on stdin; the queue entries stay sealed
 //! - Prevents concurrent executions
 //! - Logs to kernel logs/
+//! - Times each run's pickup, validation, execution and archive phases into
+//!   its tracked process (see `ProcessTracker::get_phase_breakdown`)
 
+use crate::causality::Cause;
+use crate::edge::encryption;
 use crate::errors::{CkpError, Result};
-use crate::kernel::PidFile;
+use crate::kernel::{KernelLogs, PendingQueue, PidFile, ProcessingOrder, QueueSelector, GOVERNOR_LOG, TOOL_LOG};
 use crate::ontology::{OntologyReader, OntologyLibrary};
+use crate::process_tracker::ProcessTracking;
 use crate::urn::UrnResolver;
-use crate::drivers::{StorageDriver, FileSystemDriver};
+use crate::drivers::{run_blocking, StorageDriver, FileSystemDriver, JobHandle, QueueCounters, RECONCILE_INTERVAL};
+use crate::storage::shard;
+use crate::telemetry;
 use notify::{Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher, Config as NotifyConfig};
+use std::collections::HashMap;
 use std::fs::{self, OpenOptions};
 use std::io::Write;
-use std::path::PathBuf;
-use std::process::{Command, Stdio};
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
 use std::sync::atomic::{AtomicBool, Ordering};
-use std::sync::mpsc::channel;
 use std::sync::{Arc, Mutex};
-use std::time::Duration;
+use std::time::{Duration, Instant};
+use tokio::io::AsyncWriteExt;
+use tokio::process::Command;
+use tokio::sync::mpsc::unbounded_channel;
+use tracing::Instrument;
+
+/// Emit a tracing event and append it to the kernel's log file
+///
+/// Fields are recorded on the event and written as `name=value` after the
+/// message in the log file line, e.g.
+/// `governor_log!(self, warn, "could not watch inbox", error = e)`.
+macro_rules! governor_log {
+    ($governor:expr, $level:ident, $message:literal $(, $field:ident = $value:expr)* $(,)?) => {{
+        let governor = &$governor;
+        tracing::$level!(kernel = %governor.kernel_name, $($field = %$value,)* $message);
+        governor.log(&format!(
+            concat!("[ConceptKernel] [{}] ", $message $(, " ", stringify!($field), "={}")*),
+            governor.kernel_name $(, $value)*
+        ));
+    }};
+}
+
+/// When the governor picked up a queue, for the run's phase timings
+///
+/// A run is tracked in four timed phases: `pickup` (the oldest entry
+/// waiting in its queue), `validation` (checking the queue and reading its
+/// jobs), `execution` (the tool process) and `archive` (post-processing the
+/// instances the tool stored).
+#[derive(Debug, Clone, Copy)]
+struct PickUp {
+    /// When the queue was selected
+    at: Instant,
+
+    /// How long its oldest entry had been waiting
+    waited: Option<Duration>,
+}
 
 /// ConceptKernel Governor for cold kernels
 pub struct ConceptKernelGovernor {
@@ -34,6 +79,10 @@ pub struct ConceptKernelGovernor {
     driver: Arc<dyn StorageDriver>,
     /// RDF ontology library (Phase 4 Stage 0) - loaded on startup
     ontology_library: Option<Arc<OntologyLibrary>>,
+    /// Chooses which pending queue the tool runs for next
+    selector: Mutex<QueueSelector>,
+    /// Process tracking for tool runs (CKP_PROCESS_TRACKING or `with_process_tracking`)
+    process_tracking: Option<ProcessTracking>,
 }
 
 impl std::fmt::Debug for ConceptKernelGovernor {
@@ -47,6 +96,8 @@ impl std::fmt::Debug for ConceptKernelGovernor {
             .field("log_file", &"<File>")
             .field("_pid_file", &self._pid_file)
             .field("ontology_library", &self.ontology_library.as_ref().map(|_| "<OntologyLibrary>"))
+            .field("selector", &self.selector)
+            .field("process_tracking", &self.process_tracking)
             .finish()
     }
 }
@@ -54,32 +105,24 @@ impl std::fmt::Debug for ConceptKernelGovernor {
 impl ConceptKernelGovernor {
     /// Create new governor for a kernel
     pub fn new(kernel_name_or_urn: &str, root: PathBuf) -> Result<Self> {
-        eprintln!("[Governor] Initializing for kernel: {}", kernel_name_or_urn);
-        eprintln!("[Governor] Project root: {}", root.display());
+        tracing::info!(kernel = kernel_name_or_urn, root = %root.display(), "governor initializing");
 
         // Security check: warn if running as root
         #[cfg(unix)]
         {
             let current_uid = unsafe { libc::getuid() };
             if current_uid == 0 {
-                eprintln!("╔═══════════════════════════════════════════════════════════════╗");
-                eprintln!("║ ⚠️  WARNING: GOVERNOR RUNNING AS ROOT                        ║");
-                eprintln!("║                                                               ║");
-                eprintln!("║ This is a SECURITY RISK and should be avoided!               ║");
-                eprintln!("║ Kernel: {:<51} ║", kernel_name_or_urn);
-                eprintln!("║                                                               ║");
-                eprintln!("║ Please run governors as a normal user, not root.             ║");
-                eprintln!("╚═══════════════════════════════════════════════════════════════╝");
+                tracing::warn!(
+                    kernel = kernel_name_or_urn,
+                    "governor running as root is a security risk, run governors as a normal user"
+                );
             }
         }
 
         // Parse URN if provided
         let kernel_name = if kernel_name_or_urn.starts_with("ckp://") {
             let parsed = UrnResolver::parse(kernel_name_or_urn)?;
-            eprintln!(
-                "[Governor] Parsed URN {} -> kernel: {}",
-                kernel_name_or_urn, parsed.kernel
-            );
+            tracing::debug!(urn = kernel_name_or_urn, kernel = %parsed.kernel, "parsed kernel URN");
             parsed.kernel
         } else {
             kernel_name_or_urn.to_string()
@@ -89,12 +132,10 @@ impl ConceptKernelGovernor {
         let kernel_dir = concepts_dir.join(&kernel_name);
         let ontology_path = kernel_dir.join("conceptkernel.yaml");
 
-        eprintln!("[Governor] Kernel directory: {}", kernel_dir.display());
-        eprintln!("[Governor] Ontology path: {}", ontology_path.display());
+        tracing::debug!(kernel = %kernel_name, ontology = %ontology_path.display(), "resolved ontology path");
 
         // Check if kernel exists
         if !ontology_path.exists() {
-            eprintln!("[Governor] ERROR: Ontology not found at {}", ontology_path.display());
             return Err(CkpError::Governor(format!(
                 "Kernel {} not found (no conceptkernel.yaml at {})",
                 kernel_name,
@@ -102,18 +143,16 @@ impl ConceptKernelGovernor {
             )));
         }
 
-        eprintln!("[Governor] Reading ontology...");
         // Read ontology to determine kernel type
         let ontology_reader = OntologyReader::new(root.clone());
         let ontology = ontology_reader.read_by_kernel_name(&kernel_name)?;
         let kernel_type = ontology.metadata.kernel_type.clone();
-        eprintln!("[Governor] Kernel type: {}", kernel_type);
+        let processing = ProcessingOrder::from_ontology(&ontology);
+        tracing::debug!(kernel = %kernel_name, kernel_type = %kernel_type, processing = %processing, "read ontology");
 
         // Determine tool path and command
-        eprintln!("[Governor] Determining tool path for type: {}", kernel_type);
         let (tool_path, tool_command) = if kernel_type.starts_with("python:") {
             let path = kernel_dir.join("tool/tool.py");
-            eprintln!("[Governor] Python tool path: {}", path.display());
             (path, "python3".to_string())
         } else if kernel_type.starts_with("rust:") {
             // For rust kernels, look for the compiled binary in the entrypoint
@@ -124,11 +163,8 @@ impl ConceptKernelGovernor {
                     kernel_name
                 )))?;
 
-            eprintln!("[Governor] Rust entrypoint: {}", entrypoint);
-
             // Find the binary name from the entrypoint (e.g., tool/rs/llm_executor -> llm_executor)
             let binary_name = entrypoint.split('/').last().unwrap_or("tool");
-            eprintln!("[Governor] Binary name: {}", binary_name);
 
             // Priority order for finding the binary:
             // 1. entrypoint path directly (e.g., tool/rs/gateway-http)
@@ -146,71 +182,56 @@ impl ConceptKernelGovernor {
                 kernel_dir.join("tool/rs/target/release").join(binary_name)
             };
 
-            eprintln!("[Governor] Checking entrypoint: {}", entrypoint_path.display());
-            eprintln!("[Governor] entrypoint exists: {}", entrypoint_path.exists());
-            eprintln!("[Governor] Checking tool/rs binary: {}", tool_rs_binary.display());
-            eprintln!("[Governor] tool/rs exists: {}", tool_rs_binary.exists());
-            eprintln!("[Governor] Checking tool binary: {}", tool_binary.display());
-            eprintln!("[Governor] tool exists: {}", tool_binary.exists());
-
             if entrypoint_path.exists() && entrypoint_path.is_file() {
-                eprintln!("[Governor] Using entrypoint path directly");
                 (entrypoint_path, String::new())
             } else if tool_rs_binary.exists() {
-                eprintln!("[Governor] Using tool/rs binary");
                 (tool_rs_binary, String::new())
             } else if tool_binary.exists() {
-                eprintln!("[Governor] Using tool binary");
                 (tool_binary, String::new())
             } else if release_binary.exists() {
-                eprintln!("[Governor] Using target/release binary");
                 (release_binary, String::new())
             } else {
-                eprintln!("[Governor] ERROR: Binary not found");
-                eprintln!("[Governor] Tried:");
-                eprintln!("[Governor]   1. {}", entrypoint_path.display());
-                eprintln!("[Governor]   2. {}", tool_rs_binary.display());
-                eprintln!("[Governor]   3. {}", tool_binary.display());
-                eprintln!("[Governor]   4. {}", release_binary.display());
+                tracing::error!(
+                    kernel = %kernel_name,
+                    tried = ?[&entrypoint_path, &tool_rs_binary, &tool_binary, &release_binary],
+                    entrypoint,
+                    "binary not found for entrypoint"
+                );
                 (entrypoint_path, String::new())  // Return for error message
             }
         } else {
             let path = kernel_dir.join("tool/tool.js");
-            eprintln!("[Governor] Node.js tool path: {}", path.display());
             (path, "node".to_string())
         };
 
-        eprintln!("[Governor] Final tool path: {}", tool_path.display());
-        eprintln!("[Governor] Tool command: {:?}", tool_command);
+        tracing::debug!(
+            kernel = %kernel_name,
+            tool = %tool_path.display(),
+            command = %tool_command,
+            "resolved tool"
+        );
 
         // Check if tool exists
-        eprintln!("[Governor] Checking if tool exists...");
         if !tool_path.exists() {
-            eprintln!("[Governor] ERROR: Tool not found at {}", tool_path.display());
             return Err(CkpError::Governor(format!(
                 "Tool not found: {}",
                 tool_path.display()
             )));
         }
-        eprintln!("[Governor] Tool exists!");
 
         // Check if inbox exists
         let inbox = kernel_dir.join("queue/inbox");
-        eprintln!("[Governor] Checking inbox: {}", inbox.display());
         if !inbox.exists() {
-            eprintln!("[Governor] ERROR: Inbox not found");
             return Err(CkpError::Governor(format!(
                 "Inbox not found: {}",
                 inbox.display()
             )));
         }
-        eprintln!("[Governor] Inbox exists!");
 
         // Create PID file (prevents duplicate governors)
         let pid_path = kernel_dir.join("tool/.governor.pid");
-        eprintln!("[Governor] Creating PID file: {}", pid_path.display());
         let pid_file = PidFile::create(&pid_path)?;
-        eprintln!("[Governor] PID file created!");
+        tracing::debug!(kernel = %kernel_name, pid_file = %pid_path.display(), "created PID file");
 
         // Set up logging
         let logs_dir = kernel_dir.join("logs");
@@ -225,15 +246,14 @@ impl ConceptKernelGovernor {
         let driver = Arc::new(FileSystemDriver::new(root.clone(), kernel_name.clone())) as Arc<dyn StorageDriver>;
 
         // Load ontology library (Phase 4 Stage 0) - load RDF ontologies from .ckproject
-        eprintln!("[Governor] Loading ontology library...");
         let ontology_library = match OntologyLibrary::new(root.clone()) {
-            Ok(lib) => {
-                eprintln!("[Governor] Ontology library loaded successfully");
-                Some(Arc::new(lib))
-            }
+            Ok(lib) => Some(Arc::new(lib)),
             Err(e) => {
-                eprintln!("[Governor] Warning: Could not load ontology library: {}", e);
-                eprintln!("[Governor] Continuing without RDF ontology support");
+                tracing::warn!(
+                    kernel = %kernel_name,
+                    error = %e,
+                    "could not load ontology library, continuing without RDF ontology support"
+                );
                 None
             }
         };
@@ -248,19 +268,31 @@ impl ConceptKernelGovernor {
             _pid_file: pid_file,
             driver,
             ontology_library,
+            selector: Mutex::new(QueueSelector::new(processing)),
+            process_tracking: ProcessTracking::from_env(&root),
         };
 
-        governor.log(&format!(
-            "[ConceptKernel] [{}] Starting governor (PID: {}, Type: {}, Ontology: {})",
-            kernel_name,
-            std::process::id(),
-            kernel_type,
-            if governor.ontology_library.is_some() { "Loaded" } else { "None" }
-        ));
+        governor_log!(
+            governor,
+            info,
+            "starting governor",
+            pid = std::process::id(),
+            kernel_type = kernel_type,
+            ontology = if governor.ontology_library.is_some() { "loaded" } else { "none" },
+            processing = processing,
+        );
 
         Ok(governor)
     }
 
+    /// Track every tool run as a `tool-run` process
+    ///
+    /// Overrides tracking configured through `CKP_PROCESS_TRACKING`.
+    pub fn with_process_tracking(mut self, tracking: ProcessTracking) -> Self {
+        self.process_tracking = Some(tracking);
+        self
+    }
+
     /// Start watching queues (event-driven with notify crate)
     ///
     /// Uses filesystem events for instant detection with fallback polling
@@ -268,57 +300,41 @@ impl ConceptKernelGovernor {
         let inbox_path = self.get_inbox_path();
         let edges_path = self.get_edges_path();
 
-        self.log(&format!(
-            "[ConceptKernel] [{}] Status: GOVERNOR",
-            self.kernel_name
-        ));
-        self.log(&format!(
-            "[ConceptKernel] [{}] Watching inbox: {} (event-driven)",
-            self.kernel_name,
-            inbox_path.display()
-        ));
+        governor_log!(self, info, "watching inbox", inbox = inbox_path.display());
 
         let tool_running = Arc::new(AtomicBool::new(false));
 
         // Check for existing jobs first (important!)
         self.check_and_process_existing_jobs(tool_running.clone()).await;
 
-        // Set up filesystem watcher
-        let (tx, rx) = channel();
-        let mut watcher = match RecommendedWatcher::new(tx, NotifyConfig::default()) {
+        // Set up filesystem watcher; events arrive on an async channel so
+        // waiting for them never blocks the executor
+        let (tx, mut rx) = unbounded_channel();
+        let handler = move |event: notify::Result<Event>| {
+            let _ = tx.send(event);
+        };
+        let mut watcher = match RecommendedWatcher::new(handler, NotifyConfig::default()) {
             Ok(w) => {
-                self.log(&format!(
-                    "[ConceptKernel] [{}] Event-driven watching enabled",
-                    self.kernel_name
-                ));
+                governor_log!(self, info, "event-driven watching enabled");
                 Some(w)
             }
             Err(e) => {
-                self.log(&format!(
-                    "[ConceptKernel] [{}] Warning: Could not create watcher, falling back to polling: {}",
-                    self.kernel_name, e
-                ));
+                governor_log!(self, warn, "could not create watcher, falling back to polling", error = e);
                 None
             }
         };
 
         // Watch directories if watcher available
         if let Some(ref mut w) = watcher {
-            // Watch inbox
-            if let Err(e) = w.watch(&inbox_path, RecursiveMode::NonRecursive) {
-                self.log(&format!(
-                    "[ConceptKernel] [{}] Warning: Could not watch inbox: {}",
-                    self.kernel_name, e
-                ));
+            // Watch inbox (recursively, for shard directories)
+            if let Err(e) = w.watch(&inbox_path, RecursiveMode::Recursive) {
+                governor_log!(self, warn, "could not watch inbox", error = e);
             }
 
             // Watch edges directory (if exists)
             if edges_path.exists() {
                 if let Err(e) = w.watch(&edges_path, RecursiveMode::Recursive) {
-                    self.log(&format!(
-                        "[ConceptKernel] [{}] Warning: Could not watch edges: {}",
-                        self.kernel_name, e
-                    ));
+                    governor_log!(self, warn, "could not watch edges", error = e);
                 }
             }
         }
@@ -326,30 +342,28 @@ impl ConceptKernelGovernor {
         // Event loop
         if watcher.is_some() {
             // Event-driven mode
-            self.log(&format!(
-                "[ConceptKernel] [{}] Ready - Waiting for filesystem events",
-                self.kernel_name
-            ));
+            governor_log!(self, info, "ready, waiting for filesystem events");
 
             loop {
                 // Check shutdown flag
                 if shutdown.load(Ordering::SeqCst) {
-                    self.log(&format!(
-                        "[ConceptKernel] [{}] Received shutdown signal, exiting gracefully",
-                        self.kernel_name
-                    ));
+                    governor_log!(self, info, "received shutdown signal, exiting");
                     break;
                 }
 
-                match rx.recv_timeout(Duration::from_millis(1000)) {
-                    Ok(Ok(event)) => {
+                self.reconcile_queue_counts().await;
+                self.rotate_output_logs().await;
+
+                match tokio::time::timeout(Duration::from_millis(1000), rx.recv()).await {
+                    Ok(Some(Ok(event))) => {
                         self.handle_filesystem_event(event, tool_running.clone()).await;
                     }
-                    Ok(Err(e)) => {
-                        self.log(&format!(
-                            "[ConceptKernel] [{}] Watcher error: {}",
-                            self.kernel_name, e
-                        ));
+                    Ok(Some(Err(e))) => {
+                        governor_log!(self, warn, "watcher error", error = e);
+                    }
+                    Ok(None) => {
+                        governor_log!(self, warn, "watcher stopped, exiting");
+                        break;
                     }
                     Err(_) => {
                         // Timeout - check edges directory in case new edge queues were created
@@ -359,22 +373,18 @@ impl ConceptKernelGovernor {
             }
         } else {
             // Fallback to polling
-            self.log(&format!(
-                "[ConceptKernel] [{}] Using polling mode (500ms interval)",
-                self.kernel_name
-            ));
+            governor_log!(self, info, "using polling mode", interval_ms = 500);
 
             loop {
                 // Check shutdown flag
                 if shutdown.load(Ordering::SeqCst) {
-                    self.log(&format!(
-                        "[ConceptKernel] [{}] Received shutdown signal, exiting gracefully",
-                        self.kernel_name
-                    ));
+                    governor_log!(self, info, "received shutdown signal, exiting");
                     break;
                 }
 
                 self.check_and_process_existing_jobs(tool_running.clone()).await;
+                self.reconcile_queue_counts().await;
+                self.rotate_output_logs().await;
                 tokio::time::sleep(Duration::from_millis(500)).await;
             }
         }
@@ -410,107 +420,174 @@ impl ConceptKernelGovernor {
         let inbox_path = self.get_inbox_path();
         let edges_path = self.get_edges_path();
 
-        for path in &event.paths {
-            // Check if in inbox
-            if path.parent() == Some(&inbox_path) && !tool_running.load(Ordering::SeqCst) {
-                tool_running.store(true, Ordering::SeqCst);
-                self.log(&format!(
-                    "[ConceptKernel] [{}] Event: New job in inbox",
-                    self.kernel_name
-                ));
-                self.spawn_tool(None, tool_running.clone()).await;
-                return;
+        let queue = event.paths.iter().find_map(|path| {
+            if shard::entry_dir(path) == Some(inbox_path.as_path()) {
+                return Some("inbox".to_string());
             }
+            let parent = path.parent()?;
+            (parent.parent()? == edges_path)
+                .then(|| parent.file_name()?.to_str().map(|edge| format!("edge queue {}", edge)))
+                .flatten()
+        });
 
-            // Check if in edge queue
-            if let Some(parent) = path.parent() {
-                if let Some(grandparent) = parent.parent() {
-                    if grandparent == edges_path && !tool_running.load(Ordering::SeqCst) {
-                        if let Some(edge_name) = parent.file_name().and_then(|n| n.to_str()) {
-                            tool_running.store(true, Ordering::SeqCst);
-                            self.log(&format!(
-                                "[ConceptKernel] [{}] Event: New job in edge queue {}",
-                                self.kernel_name, edge_name
-                            ));
-
-                            // Validate edge predicate if ontology library is loaded
-                            let _ = self.validate_edge_predicate(edge_name);
-
-                            self.spawn_tool(
-                                Some(format!("edges/{}", edge_name)),
-                                tool_running.clone(),
-                            )
-                            .await;
-                            return;
-                        }
-                    }
-                }
+        if let Some(queue) = queue {
+            if !tool_running.load(Ordering::SeqCst) {
+                governor_log!(self, info, "new job", queue = queue);
+
+                // The processing order decides which pending queue runs first
+                self.check_and_process_existing_jobs(tool_running).await;
             }
         }
     }
 
-    /// Check for existing jobs (used on startup and polling fallback)
+    /// Check for pending jobs and run the tool for the next queue in processing order
+    ///
+    /// Used on startup, on filesystem events and by the polling fallback.
     async fn check_and_process_existing_jobs(&self, tool_running: Arc<AtomicBool>) {
-        // Check inbox using driver
-        if let Ok(jobs) = self.driver.read_jobs(&self.kernel_name) {
-            if !jobs.is_empty() && !tool_running.load(Ordering::SeqCst) {
-                tool_running.store(true, Ordering::SeqCst);
-                self.log(&format!(
-                    "[ConceptKernel] [{}] Found {} job(s) in inbox",
-                    self.kernel_name,
-                    jobs.len()
-                ));
-                self.spawn_tool(None, tool_running.clone()).await;
-                return;
+        if tool_running.load(Ordering::SeqCst) {
+            return;
+        }
+
+        let kernel_dir = self.root.clone();
+        let order = self.selector.lock().unwrap().order();
+        let pending = match run_blocking(move || Ok(PendingQueue::scan(&kernel_dir, order))).await {
+            Ok(pending) => pending,
+            Err(_) => return,
+        };
+        let Some(queue) = self.selector.lock().unwrap().select(&pending).cloned() else {
+            return;
+        };
+
+        tool_running.store(true, Ordering::SeqCst);
+        let now_ms = u64::try_from(chrono::Utc::now().timestamp_millis()).unwrap_or_default();
+        let pick_up = PickUp {
+            at: Instant::now(),
+            waited: queue.oldest_timestamp_ms().map(|oldest| Duration::from_millis(now_ms.saturating_sub(oldest))),
+        };
+        match queue.source_queue {
+            None => governor_log!(self, info, "found jobs", queue = "inbox", jobs = queue.count),
+            Some(_) => {
+                governor_log!(self, info, "found jobs", edge = queue.name(), jobs = queue.count);
+
+                // Validate edge predicate if ontology library is loaded
+                let _ = self.validate_edge_predicate(queue.name());
             }
         }
 
-        // Check edge queues
-        let edges_path = self.get_edges_path();
-        if edges_path.exists() {
-            if let Ok(edge_dirs) = fs::read_dir(&edges_path) {
-                for edge_dir in edge_dirs.filter_map(|e| e.ok()) {
-                    if !edge_dir.file_type().map(|t| t.is_dir()).unwrap_or(false) {
-                        continue;
-                    }
+        self.spawn_tool(queue.source_queue, pick_up, tool_running).await;
+    }
 
-                    let edge_name = edge_dir.file_name().to_string_lossy().to_string();
-                    let edge_queue_path = edge_dir.path();
-
-                    if let Ok(entries) = fs::read_dir(&edge_queue_path) {
-                        let jobs: Vec<_> = entries
-                            .filter_map(|e| e.ok())
-                            .filter(|e| {
-                                let name = e.file_name().to_string_lossy().to_string();
-                                (name.ends_with(".inst") || name.ends_with(".job"))
-                                    && name != ".gitkeep"
-                            })
-                            .collect();
-
-                        if !jobs.is_empty() && !tool_running.load(Ordering::SeqCst) {
-                            tool_running.store(true, Ordering::SeqCst);
-                            self.log(&format!(
-                                "[ConceptKernel] [{}] Found {} job(s) in edge queue {}",
-                                self.kernel_name,
-                                jobs.len(),
-                                edge_name
-                            ));
-
-                            // Validate edge predicate if ontology library is loaded
-                            let _ = self.validate_edge_predicate(&edge_name);
-
-                            self.spawn_tool(Some(format!("edges/{}", edge_name)), tool_running.clone())
-                                .await;
-                            return;
-                        }
-                    }
+    /// Read inbox jobs through the driver, off the executor
+    async fn read_inbox_jobs(&self) -> Result<Vec<JobHandle>> {
+        let driver = Arc::clone(&self.driver);
+        let kernel_name = self.kernel_name.clone();
+        run_blocking(move || driver.read_jobs(&kernel_name)).await
+    }
+
+    /// Open the payloads sealed to this kernel in an edge queue, in memory
+    async fn open_sealed_payloads(&self, queue: &str) -> encryption::OpenedPayloads {
+        let kernel_dir = self.root.clone();
+        let queue_dir = self.root.join("queue").join(queue);
+        match run_blocking(move || encryption::open_queue(&kernel_dir, &queue_dir)).await {
+            Ok(opened) => {
+                if !opened.is_empty() {
+                    tracing::debug!(kernel = %self.kernel_name, queue, opened = opened.len(), "opened sealed payloads");
                 }
+                opened
+            }
+            Err(e) => {
+                governor_log!(self, warn, "could not open sealed payloads", queue = queue, error = e);
+                encryption::OpenedPayloads::new()
+            }
+        }
+    }
+
+    /// Rotate the kernel's process output logs that have grown too large
+    async fn rotate_output_logs(&self) {
+        let logs = KernelLogs::new(&self.root);
+        let rotated = run_blocking(move || {
+            for log in [GOVERNOR_LOG, TOOL_LOG] {
+                logs.rotate_if_needed(log)?;
             }
+            Ok(())
+        })
+        .await;
+        if let Err(e) = rotated {
+            governor_log!(self, warn, "could not rotate output logs", error = e);
+        }
+    }
+
+    /// Recount the queue counters if the last recount is older than RECONCILE_INTERVAL
+    async fn reconcile_queue_counts(&self) {
+        let counters = QueueCounters::new(&self.root);
+        match run_blocking(move || counters.reconcile_if_due(RECONCILE_INTERVAL)).await {
+            Ok(Some(stats)) => tracing::debug!(
+                kernel = %self.kernel_name,
+                inbox = stats.inbox,
+                staging = stats.staging,
+                ready = stats.ready,
+                "reconciled queue counters"
+            ),
+            Ok(None) => {}
+            Err(e) => governor_log!(self, warn, "could not reconcile queue counters", error = e),
         }
     }
 
     /// Spawn the kernel tool
-    async fn spawn_tool(&self, source_queue: Option<String>, tool_running: Arc<AtomicBool>) {
+    async fn spawn_tool(&self, source_queue: Option<String>, pick_up: PickUp, tool_running: Arc<AtomicBool>) {
+        let span = tracing::info_span!(
+            "spawn_tool",
+            kernel = %self.kernel_name,
+            edge = source_queue.as_deref().and_then(|queue| queue.strip_prefix("edges/")),
+            trace_id = tracing::field::Empty,
+        );
+
+        // The tool gets sealed payloads opened on its stdin, never on disk
+        let opened = match source_queue {
+            Some(ref queue) => self.open_sealed_payloads(queue).await,
+            None => encryption::OpenedPayloads::new(),
+        };
+
+        // A batch run handles the whole inbox; follow the trace of one of its
+        // jobs, and attribute its work to the job only if there is just one
+        let (traceparent, cause) = match source_queue {
+            None => {
+                let jobs = self.read_inbox_jobs().await.unwrap_or_default();
+                let cause = match jobs.as_slice() {
+                    [job] => Some(Cause::of_job(&job.tx_id, &job.content.stamp)),
+                    _ => None,
+                };
+                (jobs.into_iter().next().and_then(|job| job.content.traceparent), cause)
+            }
+            Some(ref queue) => {
+                let instances: Vec<PathBuf> = shard::list_entries(&self.root.join("queue").join(queue))
+                    .into_iter()
+                    .filter(|path| path.extension().is_some_and(|ext| ext == "inst"))
+                    .collect();
+                let cause = match instances.as_slice() {
+                    [instance] => Cause::of_instance(instance),
+                    _ => None,
+                };
+                (None, cause)
+            }
+        };
+        telemetry::set_parent(&span, traceparent.as_deref());
+
+        self.run_tool(source_queue, opened, traceparent, cause, pick_up, tool_running)
+            .instrument(span)
+            .await;
+    }
+
+    /// Run the tool to completion and post-process its output
+    async fn run_tool(
+        &self,
+        source_queue: Option<String>,
+        opened: encryption::OpenedPayloads,
+        traceparent: Option<String>,
+        cause: Option<Cause>,
+        pick_up: PickUp,
+        tool_running: Arc<AtomicBool>,
+    ) {
         let tool_name = self
             .tool_path
             .file_name()
@@ -528,10 +605,22 @@ impl ConceptKernelGovernor {
             c
         };
 
+        // Tool output goes to logs/tool.log, falling back to the governor's own
+        let logs = KernelLogs::new(&self.root);
+        let output = logs.open(TOOL_LOG).and_then(|file| Ok((file.try_clone()?, file)));
+        let (stdout, stderr) = match output {
+            Ok((stdout, stderr)) => (Stdio::from(stdout), Stdio::from(stderr)),
+            Err(e) => {
+                governor_log!(self, warn, "could not open tool log", error = e);
+                (Stdio::inherit(), Stdio::inherit())
+            }
+        };
+
+        let stdin = if opened.is_empty() { Stdio::null() } else { Stdio::piped() };
         cmd.current_dir(&self.root)
-            .stdin(Stdio::null())
-            .stdout(Stdio::inherit())
-            .stderr(Stdio::inherit());
+            .stdin(stdin)
+            .stdout(stdout)
+            .stderr(stderr);
 
         // For Rust tools, add --batch flag for one-time processing
         if self.tool_command.is_empty() {
@@ -542,64 +631,111 @@ impl ConceptKernelGovernor {
         if let Some(ref queue) = source_queue {
             cmd.env("CK_SOURCE_QUEUE", queue);
         }
+        if !opened.is_empty() {
+            cmd.env(encryption::OPENED_PAYLOADS_ENV, opened.len().to_string());
+        }
+
+        // Jobs the tool emits carry the trace on through TRACEPARENT
+        if let Some(traceparent) = telemetry::traceparent_for(&tracing::Span::current(), traceparent.as_deref()) {
+            cmd.env(telemetry::TRACEPARENT_ENV, traceparent);
+        }
+
+        // Jobs and instances from the tool are caused by the job it runs for
+        if let Some(cause) = &cause {
+            cmd.envs(cause.env_vars());
+        }
+
+        // A run for one job or instance is tracked under its transaction
+        let process = self.process_tracking.as_ref().and_then(|tracking| {
+            let tx_id = cause.as_ref().map(|cause| cause.tx_id.clone()).unwrap_or_else(|| {
+                format!("{}-{:08x}", chrono::Utc::now().timestamp_millis(), rand::random::<u32>())
+            });
+
+            let mut participants = HashMap::new();
+            participants.insert("kernel".to_string(), serde_json::json!(self.kernel_name));
+            participants.insert("tool".to_string(), serde_json::json!(tool_name));
+
+            let mut metadata = HashMap::new();
+            metadata.insert("queue".to_string(), serde_json::json!(source_queue.as_deref().unwrap_or("inbox")));
+            if let Some(cause) = &cause {
+                metadata.insert("correlation_id".to_string(), serde_json::json!(cause.correlation_id));
+            }
+
+            tracking.begin("tool-run", &tx_id, participants, metadata)
+        });
+        if let Some(process) = &process {
+            if let Some(waited) = pick_up.waited {
+                process.timed_phase("pickup", waited, HashMap::new());
+            }
+            process.timed_phase("validation", pick_up.at.elapsed(), HashMap::new());
+        }
+        let spawned_at = Instant::now();
 
         // Spawn process
         match cmd.spawn() {
             Ok(mut child) => {
-                let pid = child.id();
-                let queue_info = source_queue
-                    .as_ref()
-                    .map(|s| format!(" for edge {}", s))
-                    .unwrap_or_default();
-
-                self.log(&format!(
-                    "[ConceptKernel] [{}] {} started{} (PID: {})",
-                    self.kernel_name, tool_name, queue_info, pid
-                ));
-
-                // Wait for tool to complete
-                match child.wait() {
+                let pid = child.id().unwrap_or_default();
+                if let Some(mut stdin) = child.stdin.take() {
+                    let kernel = self.kernel_name.clone();
+                    let opened = serde_json::to_vec(&opened).unwrap_or_default();
+                    tokio::spawn(async move {
+                        if let Err(e) = stdin.write_all(&opened).await {
+                            tracing::warn!(kernel = %kernel, error = %e, "could not hand opened payloads to tool");
+                        }
+                    });
+                }
+                let queue = source_queue.as_deref().unwrap_or("inbox");
+                governor_log!(self, info, "tool started", tool = tool_name, queue = queue, pid = pid);
+                if let Some(process) = &process {
+                    process.phase("processing", HashMap::from([("pid".to_string(), serde_json::json!(pid))]));
+                }
+
+                // Wait for tool to complete without holding an executor thread
+                match child.wait().await {
                     Ok(status) => {
                         tool_running.store(false, Ordering::SeqCst);
+                        if let Some(process) = &process {
+                            process.timed_phase(
+                                "execution",
+                                spawned_at.elapsed(),
+                                HashMap::from([("exit_code".to_string(), serde_json::json!(status.code()))]),
+                            );
+                        }
 
                         if status.success() {
-                            self.log(&format!(
-                                "[ConceptKernel] [{}] {} exited successfully (PID: {})",
-                                self.kernel_name, tool_name, pid
-                            ));
+                            governor_log!(self, info, "tool exited successfully", tool = tool_name, pid = pid);
 
                             // Post-processing: Create edge symlinks for notification_contract
+                            let archive_started = Instant::now();
                             if let Err(e) = self.post_process_tool_output() {
-                                self.log(&format!(
-                                    "[ConceptKernel] [{}] Warning: Post-processing failed: {}",
-                                    self.kernel_name, e
-                                ));
+                                governor_log!(self, warn, "post-processing failed", error = e);
+                            }
+                            if let Some(process) = &process {
+                                process.timed_phase("archive", archive_started.elapsed(), HashMap::new());
+                                process.complete(HashMap::from([("exit_code".to_string(), serde_json::json!(0))]));
                             }
                         } else {
-                            self.log(&format!(
-                                "[ConceptKernel] [{}] {} exited with code {:?} (PID: {})",
-                                self.kernel_name,
-                                tool_name,
-                                status.code(),
-                                pid
-                            ));
+                            governor_log!(self, warn, "tool exited unsuccessfully", tool = tool_name, pid = pid, status = status);
+                            if let Some(process) = &process {
+                                process.fail(&format!("{} exited with code {:?}", tool_name, status.code()));
+                            }
                         }
                     }
                     Err(e) => {
                         tool_running.store(false, Ordering::SeqCst);
-                        self.log(&format!(
-                            "[ConceptKernel] [{}] Failed to wait for tool: {}",
-                            self.kernel_name, e
-                        ));
+                        governor_log!(self, error, "failed to wait for tool", tool = tool_name, error = e);
+                        if let Some(process) = &process {
+                            process.fail(&format!("Failed to wait for tool: {}", e));
+                        }
                     }
                 }
             }
             Err(e) => {
                 tool_running.store(false, Ordering::SeqCst);
-                self.log(&format!(
-                    "[ConceptKernel] [{}] Failed to spawn {}: {}",
-                    self.kernel_name, tool_name, e
-                ));
+                governor_log!(self, error, "failed to spawn tool", tool = tool_name, error = e);
+                if let Some(process) = &process {
+                    process.fail(&format!("Failed to spawn {}: {}", tool_name, e));
+                }
             }
         }
     }
@@ -614,9 +750,8 @@ impl ConceptKernelGovernor {
         self.root.join("queue/edges")
     }
 
-    /// Log a message
+    /// Append a line to the kernel's log file (see `governor_log!`)
     fn log(&self, msg: &str) {
-        println!("{}", msg);
         if let Ok(mut file) = self.log_file.lock() {
             writeln!(file, "{}", msg).ok();
         }
@@ -650,19 +785,12 @@ impl ConceptKernelGovernor {
         if let Some(ref library) = self.ontology_library {
             match library.get_edge_predicate(edge_type) {
                 Ok(predicate) => {
-                    self.log(&format!(
-                        "[ConceptKernel] [{}] Edge validation: {} -> {}",
-                        self.kernel_name, edge_type, predicate
-                    ));
+                    governor_log!(self, info, "edge predicate validated", edge = edge_type, predicate = predicate);
                     Ok(predicate)
                 }
                 Err(e) => {
-                    let msg = format!("Edge {} has no predicate mapping: {}", edge_type, e);
-                    self.log(&format!(
-                        "[ConceptKernel] [{}] Warning: {}",
-                        self.kernel_name, msg
-                    ));
-                    Err(msg)
+                    governor_log!(self, warn, "edge has no predicate mapping", edge = edge_type, error = e);
+                    Err(format!("Edge {} has no predicate mapping: {}", edge_type, e))
                 }
             }
         } else {
@@ -677,10 +805,7 @@ impl ConceptKernelGovernor {
     /// 2. For each target kernel, finds the corresponding edge
     /// 3. Creates symlinks in edge inboxes for all storage instances
     fn post_process_tool_output(&self) -> Result<()> {
-        self.log(&format!(
-            "[ConceptKernel] [{}] Starting post-processing...",
-            self.kernel_name
-        ));
+        governor_log!(self, info, "starting post-processing");
 
         // Read notification_contract from ontology
         // self.root is kernel_dir (/project/concepts/KernelName)
@@ -690,10 +815,7 @@ impl ConceptKernelGovernor {
         let notification_contracts = ontology_reader.read_notification_contract(&self.kernel_name)?;
 
         if notification_contracts.is_empty() {
-            self.log(&format!(
-                "[ConceptKernel] [{}] No notification_contract defined, skipping edge notifications",
-                self.kernel_name
-            ));
+            governor_log!(self, info, "no notification contract, skipping edge notifications");
             return Ok(());
         }
 
@@ -707,40 +829,25 @@ impl ConceptKernelGovernor {
         // Get all .inst files from storage/
         let storage_dir = self.root.join("storage");
         if !storage_dir.exists() {
-            self.log(&format!(
-                "[ConceptKernel] [{}] No storage directory, skipping notifications",
-                self.kernel_name
-            ));
+            governor_log!(self, info, "no storage directory, skipping notifications");
             return Ok(());
         }
 
-        let inst_files: Vec<PathBuf> = fs::read_dir(&storage_dir)
-            .map_err(|e| CkpError::IoError(format!("Failed to read storage directory: {}", e)))?
-            .filter_map(|entry| entry.ok())
-            .map(|entry| entry.path())
+        let inst_files: Vec<PathBuf> = shard::list_entries(&storage_dir)
+            .into_iter()
             .filter(|path| path.extension().and_then(|s| s.to_str()) == Some("inst"))
             .collect();
 
         if inst_files.is_empty() {
-            self.log(&format!(
-                "[ConceptKernel] [{}] No instances in storage, skipping notifications",
-                self.kernel_name
-            ));
+            governor_log!(self, info, "no instances in storage, skipping notifications");
             return Ok(());
         }
 
-        self.log(&format!(
-            "[ConceptKernel] [{}] Found {} instance(s) in storage",
-            self.kernel_name,
-            inst_files.len()
-        ));
+        governor_log!(self, info, "found instances in storage", instances = inst_files.len());
 
         // Process each notification target
         for notification in &notification_contracts {
-            self.log(&format!(
-                "[ConceptKernel] [{}] Processing notification to {}",
-                self.kernel_name, notification.target_kernel
-            ));
+            governor_log!(self, info, "processing notification", target = notification.target_kernel);
 
             // Find the edge URN that targets this kernel
             let edge_urn = self.find_edge_for_target(&edges, &notification.target_kernel)?;
@@ -748,18 +855,10 @@ impl ConceptKernelGovernor {
             // Create symlinks for all instances using the full edge URN
             let edge_dir = self.create_edge_symlinks(&inst_files, &edge_urn)?;
 
-            self.log(&format!(
-                "[ConceptKernel] [{}] Created {} symlink(s) in .edges/{}/queue/inbox/",
-                self.kernel_name,
-                inst_files.len(),
-                edge_dir
-            ));
+            governor_log!(self, info, "created edge symlinks", edge = edge_dir, symlinks = inst_files.len());
         }
 
-        self.log(&format!(
-            "[ConceptKernel] [{}] Post-processing completed successfully",
-            self.kernel_name
-        ));
+        governor_log!(self, info, "post-processing completed");
 
         Ok(())
     }
@@ -839,11 +938,7 @@ impl ConceptKernelGovernor {
             CkpError::IoError(format!("Failed to create edge inbox directory: {}", e))
         })?;
 
-        self.log(&format!(
-            "[ConceptKernel] [{}] Creating symlinks in {}",
-            self.kernel_name,
-            edge_inbox.display()
-        ));
+        governor_log!(self, info, "creating edge symlinks", inbox = edge_inbox.display());
 
         // Create symlinks for each instance
         for inst_file in inst_files {
@@ -873,12 +968,13 @@ impl ConceptKernelGovernor {
                 })?;
             }
 
-            self.log(&format!(
-                "[ConceptKernel] [{}] Created symlink: {} -> {}",
-                self.kernel_name,
-                symlink_path.display(),
-                inst_file.display()
-            ));
+            governor_log!(
+                self,
+                debug,
+                "created edge symlink",
+                symlink = symlink_path.display(),
+                instance = inst_file.display(),
+            );
         }
 
         Ok(edge_dir_name)
@@ -1075,7 +1171,7 @@ process.exit(0);
 
         let log_content = fs::read_to_string(&log_path).unwrap();
         assert!(
-            log_content.contains("Starting governor"),
+            log_content.contains("starting governor"),
             "Log should contain startup message"
         );
     }
@@ -1097,7 +1193,7 @@ process.exit(0);
             "Should preserve existing log entries"
         );
         assert!(
-            log_content.contains("Starting governor"),
+            log_content.contains("starting governor"),
             "Should append new entries"
         );
     }
@@ -1204,6 +1300,10 @@ process.exit(0);
             .collect();
 
         assert_eq!(entries.len(), 1, "Should detect 1 instance in edge queue");
+        let pending = PendingQueue::scan(&root.join("concepts/EdgeQueueKernel"), ProcessingOrder::Fifo);
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending[0].source_queue.as_deref(), Some("edges/PRODUCES.SourceKernel"));
+        assert_eq!(pending[0].count, 1);
     }
 
     // === Process Spawning Tests (5 tests) ===
@@ -0,0 +1,301 @@
+//! Point-in-time views of a kernel
+//!
+//! For postmortems of past incidents, `KernelHistory` answers what a kernel
+//! looked like at a time T from what is still on disk, without restoring
+//! backups:
+//!
+//! - instances that existed: minted at or before T (`InstanceScanner::as_of`)
+//! - jobs that were queued: written at or before T and not yet processed
+//! - the transaction log as it stood: its records, from the compacted
+//!   segments through tx.jsonl, up to the first one written after T
+//!
+//! The transaction log holds transactions in the order they were committed.
+//! A job counts as processed at T if its transaction is in the log as it
+//! stood at T. Jobs the log does not mention were processed when they were
+//! archived (the archived file's mtime) and are still pending otherwise.
+//!
+//! ```no_run
+//! use ckp_core::kernel::KernelHistory;
+//! use chrono::{DateTime, Utc};
+//! use std::path::PathBuf;
+//!
+//! # fn example() -> ckp_core::errors::Result<()> {
+//! let history = KernelHistory::new(PathBuf::from("/project"));
+//! let at: DateTime<Utc> = "2025-11-29T03:12:00Z".parse().unwrap();
+//! let state = history.state_at("Orders.Checkout", at)?;
+//! println!("{} jobs queued, {} instances", state.queued.len(), state.instances.len());
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::drivers::{Encoding, JobFile, TxCompactor};
+use crate::errors::{CkpError, Result};
+use crate::kernel::tx_sort_key;
+use crate::storage::{shard, InstanceDetail, InstanceScanner};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Queue stages holding jobs that have not been processed
+const PENDING_STAGES: [&str; 3] = ["queue/inbox", "queue/staging", "queue/ready"];
+
+/// A job as it stood at a point in time
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QueuedJob {
+    pub tx_id: String,
+    pub source: String,
+
+    /// When the job was written
+    pub enqueued_at: DateTime<Utc>,
+
+    /// When the job was processed, if it has been since
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub processed_at: Option<DateTime<Utc>>,
+}
+
+/// A kernel as it stood at a point in time
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct KernelState {
+    pub kernel: String,
+    pub at: DateTime<Utc>,
+
+    /// Instances that existed, oldest first
+    pub instances: Vec<InstanceDetail>,
+
+    /// Jobs queued and not yet processed, oldest first
+    pub queued: Vec<QueuedJob>,
+
+    /// Transaction log records written by then
+    pub transactions: usize,
+
+    /// Last transaction recorded by then
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_tx: Option<String>,
+}
+
+/// Reads kernels' state at past points in time
+pub struct KernelHistory {
+    /// Project root directory (contains concepts/)
+    root: PathBuf,
+}
+
+impl KernelHistory {
+    pub fn new(root: PathBuf) -> Self {
+        Self { root }
+    }
+
+    /// Everything known about a kernel at `at`
+    ///
+    /// # Errors
+    /// `CkpError::KernelNotFound` if the kernel doesn't exist
+    pub fn state_at(&self, kernel: &str, at: DateTime<Utc>) -> Result<KernelState> {
+        let transactions = self.transactions_until(kernel, at)?;
+        let instances = match InstanceScanner::new(self.kernel_dir(kernel)?, kernel.to_string()).as_of(at) {
+            Ok(instances) => instances,
+            Err(CkpError::FileNotFound(_)) => Vec::new(),
+            Err(e) => return Err(e),
+        };
+
+        Ok(KernelState {
+            kernel: kernel.to_string(),
+            at,
+            instances,
+            queued: self.queue_at(kernel, at)?,
+            transactions: transactions.len(),
+            last_tx: transactions.iter().rev().find_map(|record| tx_id(record)).map(str::to_string),
+        })
+    }
+
+    /// Transaction log records as the log stood at `at`, in log order
+    ///
+    /// The log is cut at the first record written after `at`; records
+    /// without a timestamp stay in place.
+    pub fn transactions_until(&self, kernel: &str, at: DateTime<Utc>) -> Result<Vec<Value>> {
+        let mut records = TxCompactor::new(self.kernel_dir(kernel)?).records()?;
+        let cut = records.iter().position(|record| timestamp(record).is_some_and(|t| t > at));
+        records.truncate(cut.unwrap_or(records.len()));
+        Ok(records)
+    }
+
+    /// Jobs that were queued and not yet processed at `at`, oldest first
+    ///
+    /// Jobs are read from the pending queue stages and the queue archive;
+    /// unreadable job files are skipped.
+    pub fn queue_at(&self, kernel: &str, at: DateTime<Utc>) -> Result<Vec<QueuedJob>> {
+        let kernel_dir = self.kernel_dir(kernel)?;
+        let records = TxCompactor::new(&kernel_dir).records()?;
+
+        // First record of each transaction, and those the log held at `at`
+        let mut committed_at: HashMap<&str, Option<DateTime<Utc>>> = HashMap::new();
+        let mut committed_by_then = HashSet::new();
+        for record in &records {
+            let Some(tx_id) = tx_id(record) else {
+                continue;
+            };
+            let recorded = timestamp(record);
+            committed_at.entry(tx_id).or_insert(recorded);
+            if recorded.is_none_or(|t| t <= at) {
+                committed_by_then.insert(tx_id);
+            }
+        }
+
+        let mut jobs: BTreeMap<String, QueuedJob> = BTreeMap::new();
+        let archived = shard::list_entries(&kernel_dir.join("queue/archive")).into_iter().map(|path| (path, true));
+        let pending = PENDING_STAGES
+            .iter()
+            .flat_map(|stage| shard::list_entries(&kernel_dir.join(stage)))
+            .map(|path| (path, false));
+
+        for (path, is_archived) in archived.chain(pending) {
+            let Some((job, file)) = read_job(&path) else {
+                continue;
+            };
+            let Some(enqueued_at) = enqueued_at(&job) else {
+                continue;
+            };
+            let processed_at = match committed_at.get(job.tx_id.as_str()) {
+                Some(recorded) => *recorded,
+                None if is_archived => modified(&file),
+                None => None,
+            };
+
+            let was_queued = enqueued_at <= at
+                && !committed_by_then.contains(job.tx_id.as_str())
+                && processed_at.is_none_or(|t| t > at);
+            if was_queued {
+                jobs.entry(job.tx_id.clone()).or_insert(QueuedJob {
+                    tx_id: job.tx_id,
+                    source: job.source,
+                    enqueued_at,
+                    processed_at,
+                });
+            }
+        }
+
+        let mut queued: Vec<QueuedJob> = jobs.into_values().collect();
+        queued.sort_by(|a, b| a.enqueued_at.cmp(&b.enqueued_at).then_with(|| a.tx_id.cmp(&b.tx_id)));
+        Ok(queued)
+    }
+
+    fn kernel_dir(&self, kernel: &str) -> Result<PathBuf> {
+        let kernel_dir = self.root.join("concepts").join(kernel);
+        if !kernel_dir.is_dir() {
+            return Err(CkpError::KernelNotFound(kernel.to_string()));
+        }
+        Ok(kernel_dir)
+    }
+}
+
+fn tx_id(record: &Value) -> Option<&str> {
+    record.get("txId").and_then(|v| v.as_str())
+}
+
+fn timestamp(record: &Value) -> Option<DateTime<Utc>> {
+    let timestamp = record.get("timestamp").and_then(|v| v.as_str())?;
+    DateTime::parse_from_rfc3339(timestamp).ok().map(|t| t.with_timezone(&Utc))
+}
+
+/// When a job was written: its timestamp, or the one leading its tx ID
+fn enqueued_at(job: &JobFile) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(&job.timestamp)
+        .map(|t| t.with_timezone(&Utc))
+        .ok()
+        .or_else(|| {
+            let (millis, _) = tx_sort_key(Path::new(&job.tx_id));
+            DateTime::from_timestamp_millis(i64::try_from(millis).ok()?)
+        })
+}
+
+/// Job in a queue entry (`{txId}.job`, or `{txId}/job.json` in the archive)
+/// and the file it was read from
+fn read_job(path: &Path) -> Option<(JobFile, PathBuf)> {
+    if path.is_dir() {
+        let file = path.join("job.json");
+        let job = serde_json::from_slice(&fs::read(&file).ok()?).ok()?;
+        return Some((job, file));
+    }
+    let (encoding, _) = path.file_name().and_then(|n| n.to_str()).and_then(Encoding::parse_job_file_name)?;
+    let job = encoding.decode(&fs::read(path).ok()?).ok()?;
+    Some((job, path.to_path_buf()))
+}
+
+fn modified(path: &Path) -> Option<DateTime<Utc>> {
+    fs::metadata(path).and_then(|metadata| metadata.modified()).ok().map(DateTime::<Utc>::from)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::drivers::JobFileBuilder;
+    use serde_json::json;
+    use tempfile::TempDir;
+
+    fn at(time: &str) -> DateTime<Utc> {
+        format!("2025-11-29T{}:00Z", time).parse().unwrap()
+    }
+
+    fn job(tx_id: &str, time: &str) -> Vec<u8> {
+        let job = JobFileBuilder::new("Orders", json!({}))
+            .with_tx_id(tx_id)
+            .with_timestamp(format!("2025-11-29T{}:00Z", time))
+            .with_source("Shop", "ckp://Shop")
+            .build()
+            .unwrap();
+        serde_json::to_vec(&job).unwrap()
+    }
+
+    fn instance(dir: &Path, id: &str, time: &str) {
+        let inst_dir = dir.join(format!("{}.inst", id));
+        fs::create_dir_all(&inst_dir).unwrap();
+        let receipt = json!({"id": id, "name": id, "kernel": "Orders", "timestamp": format!("2025-11-29T{}:00Z", time), "data": {}});
+        fs::write(inst_dir.join("receipt.bin"), receipt.to_string()).unwrap();
+    }
+
+    #[test]
+    fn test_state_at_past_times() {
+        let temp = TempDir::new().unwrap();
+        let kernel_dir = temp.path().join("concepts/Orders");
+        let inbox = kernel_dir.join("queue/inbox");
+        let archive = kernel_dir.join("queue/archive");
+        fs::create_dir_all(&inbox).unwrap();
+        fs::create_dir_all(archive.join("tx-a")).unwrap();
+
+        // tx-a processed at 10:02, tx-b at 10:20, tx-c still pending,
+        // tx-d archived (now) without a record
+        fs::write(archive.join("tx-a/job.json"), job("tx-a", "10:00")).unwrap();
+        fs::write(archive.join("tx-b.job"), job("tx-b", "10:05")).unwrap();
+        fs::write(archive.join("tx-d.job"), job("tx-d", "10:01")).unwrap();
+        fs::write(inbox.join("tx-c.job"), job("tx-c", "10:10")).unwrap();
+        let log: String = [("tx-a", "10:02"), ("tx-b", "10:20")]
+            .iter()
+            .map(|(tx, time)| format!("{}\n", json!({"txId": tx, "timestamp": format!("2025-11-29T{}:00Z", time), "kernel": "Orders"})))
+            .collect();
+        fs::write(kernel_dir.join("tx.jsonl"), log).unwrap();
+
+        instance(&kernel_dir.join("storage"), "tx-a", "10:02");
+        instance(&kernel_dir.join("archive"), "tx-0", "09:00");
+
+        let history = KernelHistory::new(temp.path().to_path_buf());
+        let state = history.state_at("Orders", at("10:06")).unwrap();
+        let queued: Vec<&str> = state.queued.iter().map(|job| job.tx_id.as_str()).collect();
+        assert_eq!(queued, vec!["tx-d", "tx-b"]);
+        assert_eq!(state.queued[1].processed_at, Some(at("10:20")));
+        let instances: Vec<&str> = state.instances.iter().map(|i| i.id.as_str()).collect();
+        assert_eq!(instances, vec!["tx-0", "tx-a"]);
+        assert_eq!((state.transactions, state.last_tx.as_deref()), (1, Some("tx-a")));
+
+        let later = history.state_at("Orders", at("10:30")).unwrap();
+        let queued: Vec<&str> = later.queued.iter().map(|job| job.tx_id.as_str()).collect();
+        assert_eq!(queued, vec!["tx-d", "tx-c"]);
+
+        let earlier = history.state_at("Orders", at("10:01")).unwrap();
+        assert_eq!(earlier.instances.len(), 1);
+        assert_eq!(earlier.transactions, 0);
+        assert!(matches!(history.state_at("Billing", at("10:00")), Err(CkpError::KernelNotFound(_))));
+    }
+}
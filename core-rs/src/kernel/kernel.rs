@@ -5,13 +5,19 @@
 //!
 //! Reference: Node.js v1.3.14 - Kernel.js
 
+use crate::causality::Cause;
 use crate::errors::{CkpError, Result};
 use crate::ontology::{OntologyReader, Ontology};
 use crate::rbac::PermissionChecker;
 use crate::port::PortManager;
-use crate::drivers::{StorageDriver, FileSystemDriver, JobFile as DriverJobFile};
+use crate::process_tracker::{ProcessTracking, TrackedProcess};
+use crate::urn::UrnResolver;
+use crate::kernel::{CircuitBreaker, CircuitBreakerConfig, ProcessingOrder, QuarantineDiagnosis};
+use crate::drivers::{run_blocking, Encoding, MappedFile, QueueCounters, StorageDriver, FileSystemDriver, JobFile as DriverJobFile, JobFileBuilder, JobStamp};
 use chrono::Utc;
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::fs;
 use std::path::PathBuf;
 use std::sync::Arc;
@@ -35,6 +41,12 @@ pub struct Kernel {
 
     /// Storage driver for backend abstraction
     driver: Arc<dyn StorageDriver>,
+
+    /// Circuit breaker for emit targets (disabled unless configured)
+    circuit_breaker: Option<Arc<CircuitBreaker>>,
+
+    /// Process tracking for emits (CKP_PROCESS_TRACKING or `with_process_tracking`)
+    process_tracking: Option<ProcessTracking>,
 }
 
 /// Job file structure written to inbox
@@ -55,6 +67,25 @@ pub struct JobFile {
 
     /// Source kernel name or 'external'
     pub source: String,
+
+    /// W3C trace context of the request that produced the job
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub traceparent: Option<String>,
+
+    /// Protocol envelope (version, source URN, correlation and causation)
+    #[serde(flatten)]
+    pub stamp: JobStamp,
+}
+
+/// Payload type tagged with the schema its jobs carry
+///
+/// Emitters stamp the schema with `JobFileBuilder::with_schema(T::SCHEMA)`;
+/// `Job::tagged_payload_as` rejects jobs tagged with another schema, and the
+/// inbox quarantines jobs tagged with a schema outside the kernel's
+/// `queue_contract.schemas`.
+pub trait TaggedPayload: DeserializeOwned {
+    /// URN or URL of the payload's schema
+    const SCHEMA: &'static str;
 }
 
 /// Job handle for processing inbox jobs
@@ -67,6 +98,9 @@ pub struct Job {
     /// Path to archive directory
     archive_dir: PathBuf,
 
+    /// Kernel whose inbox held the job
+    kernel: String,
+
     /// Transaction ID
     tx_id: String,
 
@@ -85,6 +119,48 @@ impl Job {
         &self.content.payload
     }
 
+    /// Decode the payload into `T`
+    ///
+    /// # Errors
+    ///
+    /// `CkpError::PayloadDecode` with the path of the field that failed
+    /// (e.g. `items[2].quantity`)
+    pub fn payload_as<T: DeserializeOwned>(&self) -> Result<T> {
+        serde_path_to_error::deserialize(&self.content.payload).map_err(|e| {
+            let path = e.path().to_string();
+            CkpError::PayloadDecode {
+                tx_id: self.tx_id.clone(),
+                path,
+                message: e.into_inner().to_string(),
+            }
+        })
+    }
+
+    /// Decode the payload into `T`, checking the job's schema tag first
+    ///
+    /// Untagged jobs (older emitters) are decoded as is.
+    ///
+    /// # Errors
+    ///
+    /// `CkpError::PayloadDecode` at `$schema` if the job is tagged with
+    /// another schema, otherwise as `payload_as`
+    pub fn tagged_payload_as<T: TaggedPayload>(&self) -> Result<T> {
+        match self.schema() {
+            Some(schema) if schema != T::SCHEMA => Err(CkpError::PayloadDecode {
+                tx_id: self.tx_id.clone(),
+                path: "$schema".to_string(),
+                message: format!("expected {}, found {}", T::SCHEMA, schema),
+            }),
+            _ => self.payload_as(),
+        }
+    }
+
+    /// Schema the payload is tagged with: the job's `schema`/`$schema`,
+    /// or a `$schema` inside the payload
+    pub fn schema(&self) -> Option<&str> {
+        job_schema(&self.content)
+    }
+
     /// Get source kernel name
     pub fn source(&self) -> &str {
         &self.content.source
@@ -95,6 +171,39 @@ impl Job {
         &self.content
     }
 
+    /// Get W3C trace context the job was emitted under
+    pub fn traceparent(&self) -> Option<&str> {
+        self.content.traceparent.as_deref()
+    }
+
+    /// Span for processing this job, continuing the emitter's trace
+    ///
+    /// Jobs emitted while the span is entered carry the same trace.
+    pub fn span(&self) -> tracing::Span {
+        crate::telemetry::job_span(&self.kernel, &self.tx_id, self.traceparent())
+    }
+
+    /// Cause for jobs and instances produced while processing this job
+    ///
+    /// Pass it to `JobFileBuilder::caused_by` so follow-up jobs continue
+    /// this job's chain.
+    pub fn cause(&self) -> Cause {
+        Cause::of_job(&self.tx_id, &self.content.stamp)
+    }
+
+    /// Move this job to the kernel's quarantine instead of archiving it
+    ///
+    /// For jobs whose payload the handler rejects (e.g. a `PayloadDecode`
+    /// error from `payload_as`); the error is recorded in the diagnosis.
+    ///
+    /// # Returns
+    ///
+    /// Diagnosis written next to the quarantined job
+    pub fn quarantine(self, error: &CkpError) -> Result<QuarantineDiagnosis> {
+        let kernel_dir = self.archive_dir.parent().unwrap_or(&self.archive_dir);
+        super::quarantine::quarantine(kernel_dir, &self.job_path, error)
+    }
+
     /// Archive this job (move to archive directory)
     ///
     /// This is an atomic operation that moves the job file from inbox to archive.
@@ -103,12 +212,16 @@ impl Job {
     ///
     /// Returns error if file move fails
     pub fn archive(self) -> Result<()> {
-        let archive_path = self.archive_dir.join(format!("{}.job", self.tx_id));
+        // Keep the file name so the archived job stays in its encoding
+        let file_name = self.job_path.file_name().map(|name| name.to_os_string())
+            .unwrap_or_else(|| format!("{}.job", self.tx_id).into());
+        let archive_path = self.archive_dir.join(file_name);
 
         fs::rename(&self.job_path, &archive_path)
             .map_err(|e| CkpError::IoError(format!("Failed to archive job {}: {}", self.tx_id, e)))?;
+        QueueCounters::record_removed(&self.job_path);
 
-        println!("[Job] Archived job {} to {}", self.tx_id, archive_path.display());
+        tracing::debug!(kernel = %self.kernel, tx_id = %self.tx_id, archive = %archive_path.display(), "archived job");
         Ok(())
     }
 }
@@ -118,48 +231,87 @@ pub struct InboxIterator {
     jobs: Vec<PathBuf>,
     index: usize,
     archive_dir: PathBuf,
+    kernel: String,
+
+    /// Schemas from the queue contract (empty: any)
+    schemas: Vec<String>,
+}
+
+/// Schema a job is tagged with (see `Job::schema`)
+fn job_schema(content: &JobFile) -> Option<&str> {
+    content
+        .stamp
+        .schema
+        .as_deref()
+        .or_else(|| content.payload.get("$schema").and_then(|schema| schema.as_str()))
 }
 
 impl Iterator for InboxIterator {
     type Item = Result<Job>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        if self.index >= self.jobs.len() {
-            return None;
-        }
-
-        let job_path = self.jobs[self.index].clone();
-        self.index += 1;
-
-        // Extract tx_id from filename
-        let tx_id = match job_path.file_stem().and_then(|s| s.to_str()) {
-            Some(id) => id.to_string(),
-            None => return Some(Err(CkpError::ParseError(format!(
-                "Invalid job filename: {}",
-                job_path.display()
-            )))),
-        };
-
-        // Read and parse job file
-        let content = match fs::read_to_string(&job_path) {
-            Ok(c) => c,
-            Err(e) => return Some(Err(CkpError::IoError(format!(
-                "Failed to read job {}: {}",
-                tx_id, e
-            )))),
-        };
-
-        let job_content: JobFile = match serde_json::from_str(&content) {
-            Ok(j) => j,
-            Err(e) => return Some(Err(CkpError::Json(e))),
-        };
+        loop {
+            if self.index >= self.jobs.len() {
+                return None;
+            }
 
-        Some(Ok(Job {
-            job_path,
-            archive_dir: self.archive_dir.clone(),
-            tx_id,
-            content: job_content,
-        }))
+            let job_path = self.jobs[self.index].clone();
+            self.index += 1;
+
+            // Extract tx_id and encoding from filename
+            let (encoding, tx_id) = match job_path
+                .file_name()
+                .and_then(|s| s.to_str())
+                .and_then(Encoding::parse_job_file_name)
+            {
+                Some((encoding, id)) => (encoding, id.to_string()),
+                None => return Some(Err(CkpError::ParseError(format!(
+                    "Invalid job filename: {}",
+                    job_path.display()
+                )))),
+            };
+
+            // Read and parse job file, mapped if large
+            let content = match MappedFile::open(&job_path) {
+                Ok(c) => c,
+                Err(e) => return Some(Err(CkpError::IoError(format!(
+                    "Failed to read job {}: {}",
+                    tx_id, e
+                )))),
+            };
+
+            // Unparseable jobs, and jobs tagged with a schema the kernel does not
+            // accept, are quarantined instead of failing every pass
+            let decoded = encoding.decode::<JobFile>(&content).and_then(|job| match job_schema(&job) {
+                Some(schema) if !self.schemas.is_empty() && !self.schemas.iter().any(|s| s == schema) => {
+                    Err(CkpError::PayloadDecode {
+                        tx_id: tx_id.clone(),
+                        path: "$schema".to_string(),
+                        message: format!("expected one of {}, found {}", self.schemas.join(", "), schema),
+                    })
+                }
+                _ => Ok(job),
+            });
+            let job_content = match decoded {
+                Ok(j) => j,
+                Err(e) => {
+                    drop(content);
+                    let kernel_dir = self.archive_dir.parent().unwrap_or(&self.archive_dir);
+                    if let Err(quarantine_error) = super::quarantine::quarantine(kernel_dir, &job_path, &e) {
+                        return Some(Err(quarantine_error));
+                    }
+                    continue;
+                }
+            };
+
+            return Some(Ok(Job {
+                job_path,
+                archive_dir: self.archive_dir.clone(),
+                kernel: self.kernel.clone(),
+                tx_id,
+                content: job_content,
+            }));
+        }
     }
 }
 
@@ -186,9 +338,12 @@ impl Kernel {
     /// ```
     pub fn new(root: PathBuf, concept: Option<String>, enable_rbac: bool) -> Self {
         let permission_checker = PermissionChecker::new(root.clone());
+        let process_tracking = ProcessTracking::from_env(&root);
 
         // Create default filesystem driver (concept-agnostic)
-        let driver = Arc::new(FileSystemDriver::new(root.clone(), String::new())) as Arc<dyn StorageDriver>;
+        // Tools opt into binary job encodings through CKP_ENCODING
+        let driver = Arc::new(FileSystemDriver::new(root.clone(), String::new()).with_encoding(Encoding::from_env()))
+            as Arc<dyn StorageDriver>;
 
         Self {
             root,
@@ -197,6 +352,8 @@ impl Kernel {
             ontology: None,
             permission_checker,
             driver,
+            circuit_breaker: None,
+            process_tracking,
         }
     }
 
@@ -233,6 +390,7 @@ impl Kernel {
         driver: Arc<dyn StorageDriver>,
     ) -> Self {
         let permission_checker = PermissionChecker::new(root.clone());
+        let process_tracking = ProcessTracking::from_env(&root);
 
         Self {
             root,
@@ -241,9 +399,58 @@ impl Kernel {
             ontology: None,
             permission_checker,
             driver,
+            circuit_breaker: None,
+            process_tracking,
         }
     }
 
+    /// Enable a circuit breaker for emits
+    ///
+    /// Emits to a target that keeps failing (RBAC denial, missing kernel,
+    /// I/O error) open its circuit; further emits fail fast with
+    /// `CkpError::CircuitOpen` until the cooldown is over, or go to the
+    /// target's configured fallback.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ckp_core::kernel::{CircuitBreakerConfig, Kernel};
+    /// use std::path::PathBuf;
+    ///
+    /// let kernel = Kernel::new(PathBuf::from("/concepts"), Some("Orders.Checkout".to_string()), true)
+    ///     .with_circuit_breaker(CircuitBreakerConfig::default().with_fallback("Payments.Primary", "Payments.Backup"));
+    /// ```
+    pub fn with_circuit_breaker(self, config: CircuitBreakerConfig) -> Self {
+        self.with_shared_circuit_breaker(Arc::new(CircuitBreaker::new(config)))
+    }
+
+    /// Enable a circuit breaker shared with other kernels or routers
+    pub fn with_shared_circuit_breaker(mut self, breaker: Arc<CircuitBreaker>) -> Self {
+        self.circuit_breaker = Some(breaker);
+        self
+    }
+
+    /// Track every emit as an `emit` process
+    ///
+    /// Overrides tracking configured through `CKP_PROCESS_TRACKING`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ckp_core::kernel::Kernel;
+    /// use ckp_core::process_tracker::{ProcessTracker, ProcessTracking};
+    /// use std::path::PathBuf;
+    /// use std::sync::Arc;
+    ///
+    /// let tracker = Arc::new(ProcessTracker::new(PathBuf::from("/tmp")).unwrap());
+    /// let kernel = Kernel::new(PathBuf::from("/concepts"), Some("Orders.Checkout".to_string()), true)
+    ///     .with_process_tracking(ProcessTracking::new(tracker).with_sample_rate(0.01));
+    /// ```
+    pub fn with_process_tracking(mut self, tracking: ProcessTracking) -> Self {
+        self.process_tracking = Some(tracking);
+        self
+    }
+
     /// Bootstrap kernel by loading ontology
     ///
     /// # Arguments
@@ -268,11 +475,11 @@ impl Kernel {
         match ontology_reader.read_by_kernel_name(kernel_name) {
             Ok(ontology) => {
                 self.ontology = Some(ontology);
-                println!("[Kernel] Bootstrapped {}", kernel_name);
+                tracing::info!(kernel = %kernel_name, "bootstrapped kernel");
                 Ok(())
             }
             Err(e) => {
-                eprintln!("[Kernel] Warning: Failed to load ontology for {}: {}", kernel_name, e);
+                tracing::warn!(kernel = %kernel_name, error = %e, "failed to load ontology");
                 // Don't fail - kernel can still emit without ontology
                 Ok(())
             }
@@ -321,7 +528,7 @@ impl Kernel {
         let exe_path = std::env::current_exe()
             .map_err(|e| CkpError::IoError(format!("Failed to get executable path: {}", e)))?;
 
-        println!("[Kernel] Auto-discovering from binary: {}", exe_path.display());
+        tracing::debug!(binary = %exe_path.display(), "discovering kernel from binary");
 
         // Navigate up directory tree to find concepts directory
         let mut current = exe_path.parent();
@@ -359,7 +566,7 @@ impl Kernel {
             ))
         })?;
 
-        println!("[Kernel] Discovered kernel: {} at root: {}", kernel_name, root.display());
+        tracing::info!(kernel = %kernel_name, root = %root.display(), "discovered kernel");
 
         // Create kernel instance and bootstrap
         let mut kernel = Kernel::new(root, Some(kernel_name.clone()), true);
@@ -404,13 +611,13 @@ impl Kernel {
 
         // Try to get existing allocation first
         if let Some(port) = port_manager.get(kernel_name) {
-            println!("[Kernel] Using existing port allocation: {}", port);
+            tracing::debug!(kernel = %kernel_name, port, "using existing port allocation");
             return Ok(port);
         }
 
         // Allocate new port
         let port = port_manager.allocate(kernel_name, None)?;
-        println!("[Kernel] Allocated new port: {}", port);
+        tracing::info!(kernel = %kernel_name, port, "allocated port");
 
         Ok(port)
     }
@@ -432,7 +639,8 @@ impl Kernel {
 
     /// Iterate over jobs in inbox
     ///
-    /// Returns an iterator over all `.job` files in the kernel's inbox directory.
+    /// Returns an iterator over all `.job` files in the kernel's inbox directory,
+    /// in the processing order of its queue contract (FIFO by default).
     /// Each job can be processed and then archived using `job.archive()`.
     ///
     /// # Returns
@@ -471,6 +679,21 @@ impl Kernel {
             CkpError::ParseError("Kernel concept not set. Call bootstrap() first.".to_string())
         })?;
 
+        let order = match self.ontology {
+            Some(ref ontology) => ProcessingOrder::from_ontology(ontology),
+            None => ProcessingOrder::for_kernel(&self.root, kernel_name),
+        };
+        self.inbox_iter_ordered(order)
+    }
+
+    /// Iterate over inbox jobs in the given processing order
+    ///
+    /// `inbox_iter` uses the order configured in the kernel's queue contract.
+    pub fn inbox_iter_ordered(&self, order: ProcessingOrder) -> Result<InboxIterator> {
+        let kernel_name = self.concept.as_ref().ok_or_else(|| {
+            CkpError::ParseError("Kernel concept not set. Call bootstrap() first.".to_string())
+        })?;
+
         let inbox_dir = self.root.join("concepts").join(kernel_name).join("queue/inbox");
         let archive_dir = self.root.join("concepts").join(kernel_name).join("archive");
 
@@ -486,35 +709,48 @@ impl Kernel {
                 jobs: Vec::new(),
                 index: 0,
                 archive_dir,
+                kernel: kernel_name.clone(),
+                schemas: Vec::new(),
             });
         }
 
-        // Read all .job files from inbox
-        let mut jobs = Vec::new();
-        for entry in fs::read_dir(&inbox_dir)
-            .map_err(|e| CkpError::IoError(format!("Failed to read inbox directory: {}", e)))?
-        {
-            let entry = entry
-                .map_err(|e| CkpError::IoError(format!("Failed to read directory entry: {}", e)))?;
-
-            let path = entry.path();
-            if path.extension().and_then(|s| s.to_str()) == Some("job") {
-                jobs.push(path);
-            }
-        }
-
-        // Sort by filename (which includes timestamp) for deterministic ordering
-        jobs.sort();
+        // Read all .job files from inbox, including shard directories
+        let jobs: Vec<PathBuf> = crate::storage::shard::list_entries(&inbox_dir)
+            .into_iter()
+            .filter(|path| path.extension().and_then(|s| s.to_str()) == Some("job"))
+            .collect();
+        let jobs = order.order_jobs(jobs);
 
-        println!("[Kernel] Found {} jobs in inbox", jobs.len());
+        tracing::debug!(kernel = %kernel_name, jobs = jobs.len(), "read inbox");
 
         Ok(InboxIterator {
             jobs,
             index: 0,
             archive_dir,
+            kernel: kernel_name.clone(),
+            schemas: self.accepted_schemas(kernel_name),
         })
     }
 
+    /// Payload schemas from the kernel's queue contract
+    fn accepted_schemas(&self, kernel_name: &str) -> Vec<String> {
+        let schemas = |ontology: &Ontology| {
+            ontology
+                .spec
+                .as_ref()
+                .and_then(|spec| spec.queue_contract.as_ref())
+                .and_then(|contract| contract.schemas.clone())
+                .unwrap_or_default()
+        };
+        match self.ontology {
+            Some(ref ontology) => schemas(ontology),
+            None => OntologyReader::new(self.root.clone())
+                .read_by_kernel_name(kernel_name)
+                .map(|ontology| schemas(&ontology))
+                .unwrap_or_default(),
+        }
+    }
+
     /// Emit a job to a target kernel with RBAC checks
     ///
     /// # Arguments
@@ -558,38 +794,154 @@ impl Kernel {
     /// # }
     /// ```
     pub async fn emit(&mut self, target: &str, payload: serde_json::Value) -> Result<String> {
+        let job = self.job_builder(target, payload).build()?;
+        self.emit_job(job).await
+    }
+
+    /// Builder for a job from this kernel
+    ///
+    /// Fills in the transaction ID, source name and source URN; add
+    /// causation, correlation or a schema reference and pass the result to
+    /// `emit_job`.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use ckp_core::kernel::Kernel;
+    /// # use std::path::PathBuf;
+    /// # async fn example() -> ckp_core::errors::Result<()> {
+    /// let mut kernel = Kernel::new(PathBuf::from("/concepts"), Some("Recipes.MixIngredients".to_string()), true);
+    /// let job = kernel
+    ///     .job_builder("Recipes.BakeCake", serde_json::json!({"data": "test"}))
+    ///     .caused_by("1764410400000-ab12cd34", None)
+    ///     .build()?;
+    /// let tx_id = kernel.emit_job(job).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn job_builder(&self, target: &str, payload: serde_json::Value) -> JobFileBuilder {
+        let builder = JobFileBuilder::new(target, payload).with_tx_id(self.generate_tx_id());
+        match self.concept {
+            Some(ref concept) => builder.with_source(concept.clone(), self.construct_source_urn()),
+            None => builder,
+        }
+    }
+
+    /// Emit a prepared job to its target kernel with RBAC checks
+    ///
+    /// With a circuit breaker enabled, the target kernel must exist, an
+    /// open circuit fails fast, and a failing or open target is replaced by
+    /// its fallback kernel (delivered to the fallback's inbox).
+    ///
+    /// # Returns
+    ///
+    /// Transaction ID of the written job
+    ///
+    /// # Errors
+    ///
+    /// Same as `emit`, plus `CkpError::CircuitOpen` when the target's
+    /// circuit is open and it has no fallback
+    pub async fn emit_job(&mut self, job: DriverJobFile) -> Result<String> {
+        let process = self.process_tracking.as_ref().and_then(|tracking| {
+            let mut participants = HashMap::new();
+            participants.insert("source".to_string(), serde_json::json!(job.source));
+            participants.insert("target".to_string(), serde_json::json!(job.target));
+
+            let mut metadata = HashMap::new();
+            if let Some(ref causation_id) = job.stamp.causation_id {
+                metadata.insert("causation_id".to_string(), serde_json::json!(causation_id));
+            }
+
+            tracking.begin("emit", &job.tx_id, participants, metadata)
+        });
+
+        let result = self.emit_tracked(job, process.as_ref()).await;
+
+        if let Some(process) = &process {
+            match &result {
+                Ok(tx_id) => {
+                    let mut summary = HashMap::new();
+                    summary.insert("tx_id".to_string(), serde_json::json!(tx_id));
+                    process.complete(summary);
+                }
+                Err(e) => process.fail(&e.to_string()),
+            }
+        }
+        result
+    }
+
+    /// Emit a job, recording circuit breaker redirects on its process
+    async fn emit_tracked(&mut self, mut job: DriverJobFile, process: Option<&TrackedProcess>) -> Result<String> {
+        let Some(breaker) = self.circuit_breaker.clone() else {
+            return self.deliver_job(job).await;
+        };
+
+        let target = target_kernel_name(&job.target);
+        let error = match self.deliver_through(&breaker, &target, job.clone()).await {
+            Ok(tx_id) => return Ok(tx_id),
+            Err(e) => e,
+        };
+
+        match breaker.fallback(&target) {
+            Some(fallback) => {
+                tracing::warn!(target = %target, fallback = %fallback, error = %error, "emit redirected to fallback");
+                if let Some(process) = process {
+                    let mut redirect = HashMap::new();
+                    redirect.insert("from".to_string(), serde_json::json!(target));
+                    redirect.insert("to".to_string(), serde_json::json!(fallback));
+                    redirect.insert("reason".to_string(), serde_json::json!(error.to_string()));
+                    process.phase("redirected", redirect);
+                }
+                job.target = fallback.to_string();
+                self.deliver_through(&breaker, fallback, job).await
+            }
+            None => Err(error),
+        }
+    }
+
+    /// Deliver a job through the target kernel's circuit
+    async fn deliver_through(&mut self, breaker: &CircuitBreaker, target: &str, job: DriverJobFile) -> Result<String> {
+        breaker.check(target)?;
+
+        let result = if self.root.join("concepts").join(target).is_dir() {
+            self.deliver_job(job).await
+        } else {
+            Err(CkpError::KernelNotFound(target.to_string()))
+        };
+
+        match result {
+            Ok(_) => breaker.record_success(target),
+            Err(_) => breaker.record_failure(target),
+        }
+        result
+    }
+
+    /// Check RBAC and write a job through the driver
+    async fn deliver_job(&mut self, job: DriverJobFile) -> Result<String> {
         // ===== STEP 1: RBAC AUTHORIZATION CHECK =====
         if self.enable_rbac && self.concept.is_some() {
             let source_urn = self.construct_source_urn();
-            let target_urn = self.normalize_target_urn(target);
+            let target_urn = self.normalize_target_urn(&job.target);
 
             // Check authorization (throws on denial)
             self.permission_checker.assert_can_emit_to(&source_urn, &target_urn)?;
         }
 
-        // ===== STEP 2: TRANSACTION ID GENERATION =====
-        let tx_id = self.generate_tx_id();
-
-        // ===== STEP 3: JOB CONTENT CREATION =====
-        let timestamp = Utc::now().to_rfc3339();
-        let source = self.concept.as_ref()
-            .map(|s| s.clone())
-            .unwrap_or_else(|| "external".to_string());
-
-        let job = DriverJobFile {
-            target: target.to_string(),
-            payload,
-            timestamp,
-            tx_id: tx_id.clone(),
-            source,
-        };
-
-        // ===== STEP 4: WRITE JOB VIA DRIVER =====
-        // Driver abstracts storage backend (filesystem, S3, Redis, etc.)
-        let returned_tx_id = self.driver.write_job(target, job)?;
-
-        // ===== STEP 5: LOGGING AND RETURN =====
-        println!("[Kernel] Emitted job {} to {}", returned_tx_id, target);
+        // ===== STEP 2: WRITE JOB VIA DRIVER =====
+        // Driver abstracts storage backend (filesystem, S3, Redis, etc.);
+        // its blocking I/O runs off the executor
+        let driver = Arc::clone(&self.driver);
+        let target = job.target.clone();
+        let target_name = target.clone();
+        let returned_tx_id = run_blocking(move || driver.write_job(&target_name, job)).await?;
+
+        // ===== STEP 3: LOGGING AND RETURN =====
+        tracing::debug!(
+            kernel = self.concept.as_deref().unwrap_or_default(),
+            tx_id = %returned_tx_id,
+            target = %target,
+            "emitted job"
+        );
 
         Ok(returned_tx_id)
     }
@@ -754,9 +1106,14 @@ impl Kernel {
             CkpError::ParseError("Kernel concept not set. Call bootstrap() first.".to_string())
         })?;
 
-        // Load current ontology
+        let ontology_path = self.root
+            .join("concepts")
+            .join(kernel_name)
+            .join("conceptkernel.yaml");
+
+        // Load current ontology as written, so resolved secrets are not persisted
         let ontology_reader = OntologyReader::new(self.root.clone());
-        let mut current = ontology_reader.read_by_kernel_name(kernel_name)
+        let mut current = ontology_reader.read_raw(&ontology_path)
             .map_err(|e| CkpError::ParseError(format!(
                 "Failed to load current ontology for {}: {}",
                 kernel_name, e
@@ -774,11 +1131,6 @@ impl Kernel {
             .map_err(|e| CkpError::Json(e))?;
 
         // Write updated ontology to conceptkernel.yaml
-        let ontology_path = self.root
-            .join("concepts")
-            .join(kernel_name)
-            .join("conceptkernel.yaml");
-
         let yaml_content = serde_yaml::to_string(&current)
             .map_err(|e| CkpError::ParseError(format!(
                 "Failed to serialize ontology to YAML: {}",
@@ -791,7 +1143,7 @@ impl Kernel {
                 ontology_path.display(), e
             )))?;
 
-        println!("[Kernel] Updated ontology for {}", kernel_name);
+        tracing::info!(kernel = %kernel_name, "updated ontology");
         Ok(())
     }
 
@@ -938,7 +1290,7 @@ impl Kernel {
                 urn, e
             )))?;
 
-        println!("[Kernel] Saved instance: {}", urn);
+        tracing::debug!(kernel = %kernel_name, urn = %urn, "saved instance");
         Ok(())
     }
 
@@ -1052,6 +1404,19 @@ fn parse_instance_urn(urn: &str) -> Result<(String, String)> {
     Ok((kernel_name, storage_path))
 }
 
+/// Kernel name an emit target resolves to
+///
+/// Accepts a plain kernel name or a `ckp://` URN (version and stage are
+/// dropped); unparseable URNs are returned as written.
+fn target_kernel_name(target: &str) -> String {
+    if target.starts_with("ckp://") {
+        if let Ok(parsed) = UrnResolver::parse(target) {
+            return parsed.kernel;
+        }
+    }
+    target.to_string()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1194,6 +1559,19 @@ mod tests {
         assert!(job_path.exists());
     }
 
+    #[tokio::test]
+    async fn test_emit_rejects_targets_escaping_concepts() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path().to_path_buf();
+        let mut kernel = Kernel::new(root.clone(), Some("SourceKernel".to_string()), false);
+
+        for target in ["../Escaped", "/tmp/Escaped", "ckp://../Escaped:v0.1", "ckp://TargetKernel:v0.1#../../../Escaped"] {
+            let result = kernel.emit(target, serde_json::json!({"data": "test"})).await;
+            assert!(matches!(result, Err(CkpError::InvalidPath(_))), "{} should be rejected", target);
+        }
+        assert!(!root.join("Escaped").exists());
+    }
+
     #[tokio::test]
     async fn test_emit_creates_inbox_directory() {
         let temp_dir = TempDir::new().unwrap();
@@ -1210,6 +1588,26 @@ mod tests {
         assert!(inbox_path.is_dir());
     }
 
+    #[tokio::test]
+    async fn test_emit_circuit_breaker_redirects_to_fallback() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path().to_path_buf();
+        fs::create_dir_all(root.join("concepts/Backup")).unwrap();
+        let mut kernel = Kernel::new(root.clone(), Some("SourceKernel".to_string()), false).with_circuit_breaker(
+            CircuitBreakerConfig::default().with_failure_threshold(1).with_fallback("Primary", "Backup"),
+        );
+
+        // Missing target opens its circuit; the job goes to the fallback
+        let tx_id = kernel.emit("ckp://Primary:v0.1", serde_json::json!({})).await.unwrap();
+        assert!(root.join("concepts/Backup/queue/inbox").join(format!("{}.job", tx_id)).exists());
+        assert!(!root.join("concepts/Primary").exists());
+
+        // Without a fallback, the open circuit fails fast
+        kernel.emit("Other", serde_json::json!({})).await.unwrap_err();
+        let err = kernel.emit("Other", serde_json::json!({})).await.unwrap_err();
+        assert!(matches!(err, CkpError::CircuitOpen { ref target, .. } if target == "Other"));
+    }
+
     #[tokio::test]
     async fn test_emit_external_source() {
         let temp_dir = TempDir::new().unwrap();
@@ -1420,6 +1818,113 @@ spec:
         assert!(job_content.contains("\"source\""));
     }
 
+    #[tokio::test]
+    async fn test_inbox_iter_quarantines_unparseable_jobs() {
+        let (temp, mut kernel) = setup_test_kernel();
+        let tx_id = kernel.emit("TestKernel", serde_json::json!({"ok": true})).await.unwrap();
+        let inbox = temp.path().join("concepts/TestKernel/queue/inbox");
+        fs::write(inbox.join("1000-0badbeef.job"), "{\"target\": ").unwrap();
+
+        let jobs: Vec<Job> = kernel.inbox_iter().unwrap().collect::<Result<_>>().unwrap();
+        assert_eq!(jobs.len(), 1);
+        assert_eq!(jobs[0].tx_id(), tx_id);
+
+        let quarantine = temp.path().join("concepts/TestKernel").join(crate::kernel::QUARANTINE_DIR);
+        assert!(quarantine.join("1000-0badbeef.job").exists());
+        assert!(quarantine.join("1000-0badbeef.job.diagnosis.json").exists());
+        assert!(!inbox.join("1000-0badbeef.job").exists());
+    }
+
+    #[tokio::test]
+    async fn test_inbox_iter_quarantines_jobs_tagged_with_other_schemas() {
+        let (temp, kernel) = setup_test_kernel();
+        let kernel_dir = temp.path().join("concepts/TestKernel");
+        let inbox = kernel_dir.join("queue/inbox");
+        fs::create_dir_all(&inbox).unwrap();
+        fs::write(
+            kernel_dir.join("conceptkernel.yaml"),
+            "apiVersion: conceptkernel/v1\nkind: Ontology\nmetadata:\n  name: ckp://TestKernel:v0.1\n  type: rust:cold\n  version: v0.1\nspec:\n  queue_contract:\n    schemas: [ckp://TestKernel:v0.1#schema/order]\n",
+        )
+        .unwrap();
+
+        let job = |tx_id: &str, schema: Option<&str>| {
+            let mut content = serde_json::json!({
+                "target": "TestKernel",
+                "payload": {"order": 1},
+                "timestamp": "2025-11-29T10:00:00Z",
+                "txId": tx_id,
+                "source": "external",
+            });
+            if let Some(schema) = schema {
+                content["$schema"] = serde_json::json!(schema);
+            }
+            fs::write(inbox.join(format!("{}.job", tx_id)), content.to_string()).unwrap();
+        };
+        job("1000-00000001", Some("ckp://TestKernel:v0.1#schema/order"));
+        job("1000-00000002", Some("ckp://TestKernel:v0.1#schema/refund"));
+        job("1000-00000003", None);
+
+        let jobs: Vec<Job> = kernel.inbox_iter().unwrap().collect::<Result<_>>().unwrap();
+        let mut tx_ids: Vec<&str> = jobs.iter().map(Job::tx_id).collect();
+        tx_ids.sort();
+        assert_eq!(tx_ids, vec!["1000-00000001", "1000-00000003"]);
+
+        let diagnoses = super::super::quarantine::list(&kernel_dir).unwrap();
+        assert_eq!(diagnoses.len(), 1);
+        assert_eq!((diagnoses[0].tx_id.as_str(), diagnoses[0].path.as_deref()), ("1000-00000002", Some("$schema")));
+    }
+
+    #[test]
+    fn test_payload_as_reports_field_path_and_checks_schema() {
+        #[derive(Debug, Deserialize, PartialEq)]
+        struct Item {
+            name: String,
+            quantity: u32,
+        }
+
+        #[derive(Debug, Deserialize, PartialEq)]
+        struct Order {
+            items: Vec<Item>,
+        }
+
+        impl TaggedPayload for Order {
+            const SCHEMA: &'static str = "ckp://Recipes.Serve:v0.1#schema/order";
+        }
+
+        let job_with = |content: serde_json::Value| Job {
+            job_path: PathBuf::from("/tmp/1000-ab12cd34.job"),
+            archive_dir: PathBuf::from("/tmp/archive"),
+            kernel: "Recipes.Serve".to_string(),
+            tx_id: "1000-ab12cd34".to_string(),
+            content: serde_json::from_value(content).unwrap(),
+        };
+        let envelope = |payload: serde_json::Value, schema: &str| serde_json::json!({
+            "target": "Recipes.Serve",
+            "payload": payload,
+            "timestamp": "2025-11-29T10:00:00Z",
+            "txId": "1000-ab12cd34",
+            "source": "external",
+            "$schema": schema,
+        });
+        let items = serde_json::json!({"items": [{"name": "flour", "quantity": 2}, {"name": "eggs", "quantity": "three"}]});
+
+        let job = job_with(envelope(items.clone(), Order::SCHEMA));
+        assert_eq!(job.schema(), Some(Order::SCHEMA));
+        match job.tagged_payload_as::<Order>() {
+            Err(CkpError::PayloadDecode { tx_id, path, .. }) => assert_eq!((tx_id.as_str(), path.as_str()), ("1000-ab12cd34", "items[1].quantity")),
+            other => panic!("expected PayloadDecode, got {:?}", other),
+        }
+
+        let valid = serde_json::json!({"items": [{"name": "flour", "quantity": 2}]});
+        let order: Order = job_with(envelope(valid.clone(), Order::SCHEMA)).tagged_payload_as().unwrap();
+        assert_eq!(order.items[0], Item { name: "flour".to_string(), quantity: 2 });
+
+        // Tagged with another schema: rejected before decoding
+        let other = job_with(envelope(valid, "ckp://Recipes.Serve:v0.1#schema/refund"));
+        assert!(matches!(other.tagged_payload_as::<Order>(), Err(CkpError::PayloadDecode { path, .. }) if path == "$schema"));
+        assert!(other.payload_as::<Order>().is_ok());
+    }
+
     // ==================== PHASE 3: BOOTSTRAP & EMIT ERROR HANDLING (+11 TESTS) ====================
 
     // ----- Bootstrap Failure Scenarios (+6 tests) -----
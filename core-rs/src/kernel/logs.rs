@@ -0,0 +1,195 @@
+//! Kernel process output logs with size-based rotation
+//!
+//! The governor and tool processes of a kernel write their stdout and stderr
+//! to files under `concepts/{kernel}/logs/`:
+//! - `governor.log` - output of `ckr daemon governor`
+//! - `tool.log` - output of the tool (hot tools and every cold tool run)
+//!
+//! Processes keep writing to the files they were started with, so rotation
+//! copies the file to `{log}.1` (shifting older copies up to `{log}.N`) and
+//! truncates it in place. Output written between the copy and the truncate
+//! is lost. Logs are rotated when opened for a new process and by the
+//! governor while it runs.
+
+use crate::errors::{CkpError, Result};
+use std::fs::{self, File, OpenOptions};
+use std::io::{Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+
+/// Output of the kernel's governor process
+pub const GOVERNOR_LOG: &str = "governor.log";
+
+/// Output of the kernel's tool process
+pub const TOOL_LOG: &str = "tool.log";
+
+/// Size at which a log is rotated
+pub const LOG_MAX_BYTES: u64 = 10 * 1024 * 1024;
+
+/// Rotated copies kept of each log
+pub const LOG_ROTATIONS: usize = 3;
+
+/// Bytes read at a time when tailing a log from the end
+const TAIL_CHUNK: u64 = 8 * 1024;
+
+/// Process output logs of one kernel
+#[derive(Debug, Clone)]
+pub struct KernelLogs {
+    dir: PathBuf,
+    max_bytes: u64,
+    rotations: usize,
+}
+
+impl KernelLogs {
+    /// Logs of a kernel directory (concepts/{kernel})
+    pub fn new(kernel_dir: &Path) -> Self {
+        Self { dir: kernel_dir.join("logs"), max_bytes: LOG_MAX_BYTES, rotations: LOG_ROTATIONS }
+    }
+
+    /// Rotate logs at this size instead of `LOG_MAX_BYTES`
+    pub fn with_max_bytes(mut self, max_bytes: u64) -> Self {
+        self.max_bytes = max_bytes;
+        self
+    }
+
+    /// Keep this many rotated copies instead of `LOG_ROTATIONS`
+    pub fn with_rotations(mut self, rotations: usize) -> Self {
+        self.rotations = rotations;
+        self
+    }
+
+    /// Path of a log file (`GOVERNOR_LOG` or `TOOL_LOG`)
+    pub fn path(&self, log: &str) -> PathBuf {
+        self.dir.join(log)
+    }
+
+    fn rotated_path(&self, log: &str, n: usize) -> PathBuf {
+        self.dir.join(format!("{}.{}", log, n))
+    }
+
+    /// Open a log for appending, rotating it first if it is full
+    ///
+    /// The file is meant to be handed to a child process as stdout/stderr.
+    pub fn open(&self, log: &str) -> Result<File> {
+        fs::create_dir_all(&self.dir)
+            .map_err(|e| CkpError::IoError(format!("Failed to create logs directory: {}", e)))?;
+        self.rotate_if_needed(log)?;
+
+        OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(self.path(log))
+            .map_err(|e| CkpError::IoError(format!("Failed to open {}: {}", log, e)))
+    }
+
+    /// Rotate a log that has reached the size limit
+    ///
+    /// # Returns
+    /// Whether the log was rotated
+    pub fn rotate_if_needed(&self, log: &str) -> Result<bool> {
+        let path = self.path(log);
+        let size = match fs::metadata(&path) {
+            Ok(metadata) => metadata.len(),
+            Err(_) => return Ok(false),
+        };
+        if size < self.max_bytes {
+            return Ok(false);
+        }
+
+        if self.rotations > 0 {
+            for n in (1..self.rotations).rev() {
+                let from = self.rotated_path(log, n);
+                if from.exists() {
+                    fs::rename(&from, self.rotated_path(log, n + 1))?;
+                }
+            }
+            fs::copy(&path, self.rotated_path(log, 1))?;
+        }
+        OpenOptions::new().write(true).open(&path)?.set_len(0)?;
+
+        tracing::debug!(log = %path.display(), size, "rotated log");
+        Ok(true)
+    }
+
+    /// Last `n` lines of a log, reaching into rotated copies if needed
+    ///
+    /// Lines come back oldest first. A missing log has no lines.
+    pub fn tail(&self, log: &str, n: usize) -> Result<Vec<String>> {
+        let mut lines = Vec::new();
+        let files = std::iter::once(self.path(log)).chain((1..=self.rotations).map(|i| self.rotated_path(log, i)));
+
+        for path in files {
+            if lines.len() >= n {
+                break;
+            }
+            if !path.exists() {
+                break;
+            }
+            let mut older = tail_file(&path, n - lines.len())?;
+            older.append(&mut lines);
+            lines = older;
+        }
+        Ok(lines)
+    }
+}
+
+/// Last `n` lines of a file, reading backwards from its end
+fn tail_file(path: &Path, n: usize) -> Result<Vec<String>> {
+    let mut file = File::open(path)?;
+    let len = file.metadata()?.len();
+
+    // Read chunks from the end until they hold more than n line breaks
+    let mut start = len;
+    let mut buffer = Vec::new();
+    while start > 0 && buffer.iter().filter(|&&b| b == b'\n').count() <= n {
+        let chunk = TAIL_CHUNK.min(start);
+        start -= chunk;
+        let mut bytes = vec![0; chunk as usize];
+        file.seek(SeekFrom::Start(start))?;
+        file.read_exact(&mut bytes)?;
+        bytes.append(&mut buffer);
+        buffer = bytes;
+    }
+
+    let text = String::from_utf8_lossy(&buffer);
+    let lines: Vec<&str> = text.lines().collect();
+    Ok(lines[lines.len().saturating_sub(n)..].iter().map(|line| line.to_string()).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_rotation_and_tail() {
+        let temp = TempDir::new().unwrap();
+        let logs = KernelLogs::new(temp.path()).with_max_bytes(100).with_rotations(2);
+        assert!(logs.tail(TOOL_LOG, 5).unwrap().is_empty());
+
+        // Fill the log past the limit through a handle that stays open
+        let mut file = logs.open(TOOL_LOG).unwrap();
+        for i in 0..20 {
+            writeln!(file, "line {:02}", i).unwrap();
+        }
+        assert!(logs.rotate_if_needed(TOOL_LOG).unwrap());
+        assert_eq!(fs::metadata(logs.path(TOOL_LOG)).unwrap().len(), 0);
+
+        // The open handle keeps appending to the truncated log
+        writeln!(file, "line 20").unwrap();
+        assert!(!logs.rotate_if_needed(TOOL_LOG).unwrap());
+        assert_eq!(logs.tail(TOOL_LOG, 3).unwrap(), vec!["line 18", "line 19", "line 20"]);
+        assert_eq!(logs.tail(TOOL_LOG, 100).unwrap().len(), 21);
+
+        // Older copies shift up and the oldest is dropped
+        for _ in 0..2 {
+            for i in 0..20 {
+                writeln!(file, "next {:02}", i).unwrap();
+            }
+            logs.rotate_if_needed(TOOL_LOG).unwrap();
+        }
+        assert!(logs.rotated_path(TOOL_LOG, 2).exists());
+        assert!(!logs.rotated_path(TOOL_LOG, 3).exists());
+        assert_eq!(logs.tail(TOOL_LOG, 1).unwrap(), vec!["next 19"]);
+    }
+}
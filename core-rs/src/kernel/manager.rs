@@ -5,15 +5,42 @@
 //!
 //! Reference: Node.js v1.3.14 - KernelManager.js
 
+use super::logs::{KernelLogs, GOVERNOR_LOG, TOOL_LOG};
+use super::quarantine::{self, QuarantineDiagnosis};
+use crate::compliance::AuditLogger;
+use crate::daemon::retention::AUDIT_KERNEL;
+use crate::drivers::{FileSystemDriver, QueueCounters, VersionDriverFactory};
 use crate::errors::{CkpError, Result};
-use crate::ontology::{OntologyReader, Ontology};
-use crate::continuant_tracker::{ContinuantTracker, Function};
+use crate::ontology::{KernelClass, OntologyReader, Ontology};
+use crate::continuant_tracker::{ContinuantTracker, Function, LifecycleEvent};
+use crate::port::PortManager;
+use crate::urn::UrnResolver;
+use crate::workflow::{WorkflowEvent, WORKFLOW_KERNEL};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::fs;
 use std::path::{Path, PathBuf};
+use std::process::{Child, Command, Stdio};
+use std::time::Duration;
 use sysinfo::{Pid, System};
 
+pub use crate::drivers::QueueStats;
+
+/// How long started processes are watched for an early exit
+pub const START_GRACE: Duration = Duration::from_millis(250);
+
+/// Log lines included in a StartResult when a process exits during startup
+pub const START_LOG_LINES: usize = 20;
+
+/// Where `decommission` moves jobs and edge entries still queued (under the kernel directory)
+pub const DEAD_LETTER_DIR: &str = "queue/dead-letter";
+
+/// Queues emptied by `decommission` (under the kernel's queue/ directory)
+const PENDING_QUEUES: &[&str] = &["inbox", "staging", "ready", "edges"];
+
+/// How often queues are checked while draining
+const DRAIN_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
 /// High-level kernel lifecycle manager
 pub struct KernelManager {
     /// Root directory for project
@@ -21,6 +48,12 @@ pub struct KernelManager {
 
     /// Concepts directory ({root}/concepts)
     concepts_dir: PathBuf,
+
+    /// Agent URN recorded as responsible for lifecycle transitions
+    agent: Option<String>,
+
+    /// Tenant namespace this manager is confined to (whole tree when unset)
+    namespace: Option<String>,
 }
 
 /// Status information for a kernel
@@ -33,6 +66,10 @@ pub struct KernelStatus {
     #[serde(rename = "type")]
     pub kernel_type: String,
 
+    /// Temperature class from the kernel type (None for e.g. "node:manual")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub class: Option<KernelClass>,
+
     /// Tool process ID (hot kernels or cold kernel currently processing)
     pub pid: Option<u32>,
 
@@ -51,19 +88,6 @@ pub struct KernelStatus {
     pub port: Option<u16>,
 }
 
-/// Queue statistics
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct QueueStats {
-    /// Count of jobs in inbox
-    pub inbox: usize,
-
-    /// Count of jobs in staging
-    pub staging: usize,
-
-    /// Count of jobs in ready
-    pub ready: usize,
-}
-
 /// Running process IDs
 #[derive(Debug, Clone)]
 pub struct RunningPids {
@@ -88,6 +112,49 @@ pub struct StartResult {
 
     /// Whether kernel was already running
     pub already_running: bool,
+
+    /// Why a started process exited within `START_GRACE`, if one did
+    pub error: Option<String>,
+
+    /// Last lines of that process's output log when it exited
+    pub log_tail: Vec<String>,
+}
+
+/// What `decommission` does with work still queued for the kernel
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QueueDisposition {
+    /// Give the running kernel up to this long to empty its queues, then
+    /// dead-letter what is left
+    Drain(Duration),
+
+    /// Dead-letter everything queued right away
+    DeadLetter,
+}
+
+/// Result of decommissioning a kernel
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DecommissionReport {
+    /// Kernel name
+    pub kernel: String,
+
+    /// Whether any kernel process had to be stopped
+    pub stopped: bool,
+
+    /// Queue entries processed while draining
+    pub drained: usize,
+
+    /// Entries moved to `queue/dead-letter`, relative to it
+    pub dead_lettered: Vec<PathBuf>,
+
+    /// Version recorded by the kernel's version driver, if it has one
+    pub snapshot: Option<String>,
+
+    /// Service port reservations released with the kernel's port
+    pub released_reservations: Vec<String>,
+
+    /// When the ontology was marked archived (RFC 3339)
+    pub archived_at: String,
 }
 
 impl KernelManager {
@@ -118,7 +185,42 @@ impl KernelManager {
                 .map_err(|e| CkpError::IoError(format!("Failed to create concepts directory: {}", e)))?;
         }
 
-        Ok(Self { root, concepts_dir })
+        Ok(Self { root, concepts_dir, agent: None, namespace: None })
+    }
+
+    /// Record `agent_urn` as responsible for lifecycle transitions made by this manager
+    pub fn with_agent(mut self, agent_urn: &str) -> Self {
+        self.agent = Some(agent_urn.to_string());
+        self
+    }
+
+    /// Confine this manager to one tenant namespace
+    ///
+    /// Kernel names resolve into the namespace (`Orders` means
+    /// `{namespace}~Orders`), listings only include its kernels, and kernels
+    /// in other namespaces are rejected with `CkpError::Rbac`.
+    pub fn with_namespace(mut self, namespace: &str) -> Self {
+        self.namespace = Some(namespace.to_string());
+        self
+    }
+
+    /// Tenant namespace this manager is confined to
+    pub fn namespace(&self) -> Option<&str> {
+        self.namespace.as_deref()
+    }
+
+    /// Kernel name qualified with this manager's namespace
+    ///
+    /// Managers without a namespace see the whole tree and accept any name.
+    ///
+    /// # Errors
+    ///
+    /// `CkpError::Rbac` if the kernel belongs to another namespace
+    pub fn scoped_name(&self, name: &str) -> Result<String> {
+        match self.namespace {
+            Some(ref namespace) => UrnResolver::scope_kernel(name, Some(namespace)),
+            None => Ok(name.to_string()),
+        }
     }
 
     /// List all valid kernels in /concepts/
@@ -127,6 +229,7 @@ impl KernelManager {
     /// - Hidden directories (starting with '.')
     /// - Special directories ('bus')
     /// - Directories without conceptkernel.yaml
+    /// - Kernels outside this manager's namespace, if it has one
     ///
     /// # Returns
     ///
@@ -181,6 +284,11 @@ impl KernelManager {
                 }
             }
 
+            // Filter out other tenants' kernels
+            if self.namespace.is_some() && UrnResolver::namespace_of(&name) != self.namespace {
+                continue;
+            }
+
             // Only include if conceptkernel.yaml exists
             if path.join("conceptkernel.yaml").exists() {
                 kernels.push(name);
@@ -199,9 +307,13 @@ impl KernelManager {
     ///
     /// # Returns
     ///
-    /// PathBuf to {root}/concepts/{name}
+    /// PathBuf to {root}/concepts/{name}, with `name` qualified by this
+    /// manager's namespace
     pub fn get_kernel_dir(&self, name: &str) -> PathBuf {
-        self.concepts_dir.join(name)
+        match self.scoped_name(name) {
+            Ok(scoped) => self.concepts_dir.join(scoped),
+            Err(_) => self.concepts_dir.join(name),
+        }
     }
 
     /// Check if kernel exists with valid ontology
@@ -214,8 +326,10 @@ impl KernelManager {
     ///
     /// true if kernel directory and conceptkernel.yaml exist
     pub fn exists(&self, name: &str) -> bool {
-        let kernel_dir = self.get_kernel_dir(name);
-        kernel_dir.exists() && kernel_dir.join("conceptkernel.yaml").exists()
+        self.scoped_name(name).is_ok_and(|name| {
+            let kernel_dir = self.concepts_dir.join(name);
+            kernel_dir.exists() && kernel_dir.join("conceptkernel.yaml").exists()
+        })
     }
 
     /// Create a new kernel from template
@@ -234,6 +348,7 @@ impl KernelManager {
     ///
     /// Returns error if kernel already exists or creation fails
     pub fn create_kernel(&self, name: &str, template: &str, version: &str) -> Result<()> {
+        let name = &self.scoped_name(name)?;
         let kernel_dir = self.get_kernel_dir(name);
 
         // Check if already exists
@@ -332,9 +447,231 @@ process.exit(0);
         fs::write(kernel_dir.join("tx.jsonl"), "")
             .map_err(|e| CkpError::IoError(format!("Failed to create tx.jsonl: {}", e)))?;
 
+        // Record Created transition (non-blocking)
+        ContinuantTracker::new(self.root.clone())
+            .create_kernel_entity_as(name, version, template, HashMap::new(), self.agent.as_deref())
+            .ok();
+
+        self.announce_registration(name, version, template);
+
         Ok(())
     }
 
+    /// Emit a "kernel-registered" event to System.Workflow, if the project has it
+    ///
+    /// Non-blocking: the kernel exists whether or not the event is delivered.
+    fn announce_registration(&self, name: &str, version: &str, template: &str) {
+        if !self.concepts_dir.join(WORKFLOW_KERNEL).join("conceptkernel.yaml").exists() {
+            return;
+        }
+
+        let mut event = WorkflowEvent::kernel_registered(name, version, template);
+        if let Some(ref agent) = self.agent {
+            event = event.with_detail("agent", agent.as_str());
+        }
+        if let Err(e) = event.emit(&self.root, name) {
+            tracing::warn!(kernel = name, workflow_kernel = WORKFLOW_KERNEL, error = %e, "failed to announce kernel registration");
+        }
+    }
+
+    /// Delete a kernel, optionally archiving it first
+    ///
+    /// Archived kernels are moved to `concepts/.archive/{name}.{timestamp}`
+    /// (hidden, so `list_kernels` ignores them). Records an `Archived` or
+    /// `Deleted` lifecycle transition and releases the kernel's port and
+    /// the service ports it reserved.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - Kernel name
+    /// * `archive` - Move to the archive instead of removing
+    ///
+    /// # Returns
+    ///
+    /// Archive location when `archive` is true
+    pub fn delete_kernel(&self, name: &str, archive: bool) -> Result<Option<PathBuf>> {
+        let name = &self.scoped_name(name)?;
+        let kernel_dir = self.get_kernel_dir(name);
+        if !kernel_dir.exists() {
+            return Err(CkpError::FileNotFound(format!("Kernel not found: {}", name)));
+        }
+
+        let mut details = HashMap::new();
+        let archived_to = if archive {
+            let archive_dir = self.concepts_dir.join(".archive");
+            fs::create_dir_all(&archive_dir)
+                .map_err(|e| CkpError::IoError(format!("Failed to create archive directory: {}", e)))?;
+
+            let target = archive_dir.join(format!("{}.{}", name, chrono::Utc::now().format("%Y%m%dT%H%M%S")));
+            fs::rename(&kernel_dir, &target)
+                .map_err(|e| CkpError::IoError(format!("Failed to archive kernel: {}", e)))?;
+
+            details.insert("location".to_string(), serde_json::json!(target.to_string_lossy()));
+            Some(target)
+        } else {
+            fs::remove_dir_all(&kernel_dir)
+                .map_err(|e| CkpError::IoError(format!("Failed to delete kernel: {}", e)))?;
+            None
+        };
+
+        // Free the kernel's port and the service ports it reserved
+        if self.root.join(".ckports").exists() {
+            let mut ports = PortManager::new(&self.root)?;
+            let released = ports.release_kernel(name)?;
+            if !released.is_empty() {
+                details.insert("releasedReservations".to_string(), serde_json::json!(released));
+            }
+        }
+
+        let event = if archive { LifecycleEvent::Archived } else { LifecycleEvent::Deleted };
+        ContinuantTracker::new(self.root.clone())
+            .record_lifecycle(name, event, self.agent.as_deref(), details)
+            .ok(); // Non-blocking (kernel may never have been tracked)
+
+        Ok(archived_to)
+    }
+
+    /// Decommission a kernel, leaving its directory in place as a record
+    ///
+    /// In order: drains its queues (if asked to), stops its processes,
+    /// moves what is still queued to `queue/dead-letter`, snapshots the
+    /// kernel through its version driver, releases its port and service
+    /// reservations, marks its ontology archived (`metadata.archived`, which
+    /// `start_kernel` refuses) and records an `Archived` lifecycle
+    /// transition and a `kernel.decommission` audit entry.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - Kernel name
+    /// * `queues` - Whether to drain or dead-letter queued work
+    ///
+    /// # Errors
+    ///
+    /// Returns error if the kernel does not exist or is already archived,
+    /// or if a step fails; steps already taken are not undone
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use ckp_core::kernel::{KernelManager, QueueDisposition};
+    /// # use std::path::PathBuf;
+    /// # use std::time::Duration;
+    /// # async fn example() -> ckp_core::errors::Result<()> {
+    /// let manager = KernelManager::new(PathBuf::from("/project"))?;
+    /// let report = manager.decommission("Orders.Legacy", QueueDisposition::Drain(Duration::from_secs(30))).await?;
+    /// println!("{} entries dead-lettered", report.dead_lettered.len());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn decommission(&self, name: &str, queues: QueueDisposition) -> Result<DecommissionReport> {
+        let name = &self.scoped_name(name)?;
+        if !self.exists(name) {
+            return Err(CkpError::FileNotFound(format!("Kernel not found: {}", name)));
+        }
+        let ontology = OntologyReader::new(self.root.clone()).read_by_kernel_name(name)?;
+        if let Some(archived) = ontology.metadata.archived {
+            return Err(CkpError::ValidationError(format!("Kernel {} was decommissioned at {}", name, archived)));
+        }
+        let kernel_dir = self.get_kernel_dir(name);
+
+        // Let a running kernel work through its queues first
+        let queued = pending_entries(&kernel_dir).len();
+        if let QueueDisposition::Drain(timeout) = queues {
+            let pids = self.find_running_pids(name)?;
+            if pids.pid.is_some() || pids.watcher_pid.is_some() {
+                let deadline = std::time::Instant::now() + timeout;
+                while !pending_entries(&kernel_dir).is_empty() && std::time::Instant::now() < deadline {
+                    tokio::time::sleep(DRAIN_POLL_INTERVAL).await;
+                }
+            }
+        }
+        let drained = queued.saturating_sub(pending_entries(&kernel_dir).len());
+
+        let stopped = self.stop_kernel(name).await?;
+        let dead_lettered = self.dead_letter(&kernel_dir)?;
+        self.get_queue_stats(&kernel_dir)?;
+
+        // Nothing changed since the last version: that version is the snapshot
+        let snapshot = match VersionDriverFactory::detect(&kernel_dir, name) {
+            Some(driver) => match driver.create_version(&format!("Decommission {}", name)) {
+                Ok(version) => Some(version),
+                Err(e) => Some(driver.get_version()?.map(|info| info.version).ok_or(e)?),
+            },
+            None => None,
+        };
+
+        let released_reservations = if self.root.join(".ckports").exists() {
+            PortManager::new(&self.root)?.release_kernel(name)?
+        } else {
+            Vec::new()
+        };
+
+        let archived_at = chrono::Utc::now().to_rfc3339();
+        mark_archived(&kernel_dir.join("conceptkernel.yaml"), &archived_at)?;
+
+        let report = DecommissionReport {
+            kernel: name.clone(),
+            stopped,
+            drained,
+            dead_lettered,
+            snapshot,
+            released_reservations,
+            archived_at,
+        };
+
+        let mut details = HashMap::new();
+        details.insert("decommissioned".to_string(), serde_json::json!(true));
+        details.insert("deadLettered".to_string(), serde_json::json!(report.dead_lettered.len()));
+        if let Some(ref snapshot) = report.snapshot {
+            details.insert("snapshot".to_string(), serde_json::json!(snapshot));
+        }
+        ContinuantTracker::new(self.root.clone())
+            .record_lifecycle(name, LifecycleEvent::Archived, self.agent.as_deref(), details)
+            .ok(); // Non-blocking (kernel may never have been tracked)
+
+        let audit_log = self.concepts_dir.join(AUDIT_KERNEL).join("storage").join("audit.log");
+        AuditLogger::new(audit_log).log_operation(
+            "kernel.decommission",
+            self.agent.as_deref(),
+            serde_json::to_value(&report)?,
+        )?;
+
+        Ok(report)
+    }
+
+    /// Move everything still queued for a kernel to its dead-letter queue
+    ///
+    /// Keeps each entry's path under queue/; edge entries stay symlinks to
+    /// the instances they point at.
+    fn dead_letter(&self, kernel_dir: &Path) -> Result<Vec<PathBuf>> {
+        let queue_dir = kernel_dir.join("queue");
+        let dead_letter_dir = kernel_dir.join(DEAD_LETTER_DIR);
+        let driver = FileSystemDriver::new(self.root.clone(), String::new());
+
+        let mut moved = Vec::new();
+        for entry in pending_entries(kernel_dir) {
+            let Ok(relative) = entry.strip_prefix(&queue_dir) else {
+                continue;
+            };
+            let target = dead_letter_dir.join(relative);
+            if let Some(parent) = target.parent() {
+                fs::create_dir_all(parent)?;
+            }
+
+            // Relative links would dangle one directory deeper; re-link them
+            match fs::read_link(&entry) {
+                Ok(link) => {
+                    let instance = entry.parent().map(|dir| dir.join(&link)).unwrap_or(link);
+                    driver.create_symlink(&instance, &target, None)?;
+                    fs::remove_file(&entry)?;
+                }
+                Err(_) => fs::rename(&entry, &target)?,
+            }
+            moved.push(relative.to_path_buf());
+        }
+        Ok(moved)
+    }
+
     /// Get comprehensive status of a kernel
     ///
     /// # Arguments
@@ -349,6 +686,7 @@ process.exit(0);
     ///
     /// Returns error if kernel does not exist
     pub async fn get_kernel_status(&self, name: &str) -> Result<KernelStatus> {
+        let name = &self.scoped_name(name)?;
         if !self.exists(name) {
             return Err(CkpError::FileNotFound(format!("Kernel not found: {}", name)));
         }
@@ -363,10 +701,11 @@ process.exit(0);
         let pids = self.find_running_pids(name)?;
 
         // Calculate mode
-        let mode = self.calculate_mode(&ontology.metadata.kernel_type, &pids, &ontology);
+        let class = ontology.metadata.kernel_class();
+        let mode = self.calculate_mode(class, &pids, &ontology);
 
-        // Get queue statistics
-        let queue_stats = self.get_queue_stats(&kernel_dir)?;
+        // Get queue statistics (counted incrementally, see QueueCounters)
+        let queue_stats = self.estimate_queue_stats(&kernel_dir)?;
 
         // Port extraction not supported yet (ontology structure doesn't have annotations map)
         let port = None;
@@ -374,6 +713,7 @@ process.exit(0);
         Ok(KernelStatus {
             name: name.to_string(),
             kernel_type: ontology.metadata.kernel_type.clone(),
+            class,
             pid: pids.pid,
             watcher_pid: pids.watcher_pid,
             mode,
@@ -382,6 +722,124 @@ process.exit(0);
         })
     }
 
+    /// Get queue statistics from the kernel's incrementally updated counters
+    ///
+    /// Fast: reads one small file. Jobs added or removed outside the driver
+    /// since the governor's last reconciliation are not reflected; kernels
+    /// without recent counters are recounted.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - Kernel name
+    ///
+    /// # Errors
+    ///
+    /// Returns error if kernel does not exist
+    pub fn queue_stats(&self, name: &str) -> Result<QueueStats> {
+        let name = &self.scoped_name(name)?;
+        if !self.exists(name) {
+            return Err(CkpError::FileNotFound(format!("Kernel not found: {}", name)));
+        }
+        self.estimate_queue_stats(&self.get_kernel_dir(name))
+    }
+
+    /// Recount queue statistics from the queue directories
+    ///
+    /// Exact, and resets the kernel's counters to the result.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - Kernel name
+    ///
+    /// # Errors
+    ///
+    /// Returns error if kernel does not exist or its queues cannot be read
+    pub fn recount_queue_stats(&self, name: &str) -> Result<QueueStats> {
+        let name = &self.scoped_name(name)?;
+        if !self.exists(name) {
+            return Err(CkpError::FileNotFound(format!("Kernel not found: {}", name)));
+        }
+        self.get_queue_stats(&self.get_kernel_dir(name))
+    }
+
+    /// Diagnoses of a kernel's quarantined jobs, oldest first
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - Kernel name
+    ///
+    /// # Errors
+    ///
+    /// Returns error if kernel does not exist or its quarantine cannot be read
+    pub fn quarantined(&self, name: &str) -> Result<Vec<QuarantineDiagnosis>> {
+        let name = &self.scoped_name(name)?;
+        if !self.exists(name) {
+            return Err(CkpError::FileNotFound(format!("Kernel not found: {}", name)));
+        }
+        quarantine::list(&self.get_kernel_dir(name))
+    }
+
+    /// Move a quarantined job back to the kernel's inbox
+    ///
+    /// For after the operator fixed the job's content: the job must parse
+    /// again, otherwise it stays quarantined. The re-drive is audited.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - Kernel name
+    /// * `tx_id` - Transaction ID of the quarantined job
+    ///
+    /// # Returns
+    ///
+    /// Path of the job in the inbox
+    ///
+    /// # Errors
+    ///
+    /// Returns error if kernel does not exist, no such job is quarantined,
+    /// or the job still does not parse (`CkpError::ValidationError`)
+    pub fn requeue_quarantined(&self, name: &str, tx_id: &str) -> Result<PathBuf> {
+        let name = &self.scoped_name(name)?;
+        if !self.exists(name) {
+            return Err(CkpError::FileNotFound(format!("Kernel not found: {}", name)));
+        }
+        let sharded = crate::storage::shard::is_sharded(&self.root, name);
+        let inbox_path = quarantine::requeue(&self.get_kernel_dir(name), tx_id, sharded)?;
+
+        let audit_log = self.concepts_dir.join(AUDIT_KERNEL).join("storage").join("audit.log");
+        AuditLogger::new(audit_log).log_operation(
+            "kernel.requeue_quarantined",
+            self.agent.as_deref(),
+            serde_json::json!({ "kernel": name, "txId": tx_id }),
+        )?;
+
+        Ok(inbox_path)
+    }
+
+    /// Last lines of a kernel's tool output
+    ///
+    /// Reads `logs/tool.log` and, if it holds fewer lines, its rotated
+    /// copies (see `KernelLogs`).
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - Kernel name
+    /// * `lines` - Number of lines to return
+    ///
+    /// # Returns
+    ///
+    /// Up to `lines` lines, oldest first
+    ///
+    /// # Errors
+    ///
+    /// Returns error if kernel does not exist or its log cannot be read
+    pub fn tail(&self, name: &str, lines: usize) -> Result<Vec<String>> {
+        let name = &self.scoped_name(name)?;
+        if !self.exists(name) {
+            return Err(CkpError::FileNotFound(format!("Kernel not found: {}", name)));
+        }
+        KernelLogs::new(&self.get_kernel_dir(name)).tail(TOOL_LOG, lines)
+    }
+
     /// Find running process IDs for kernel
     ///
     /// Reads PID files and validates processes are actually running.
@@ -395,6 +853,7 @@ process.exit(0);
     ///
     /// RunningPids with validated PIDs (or None if not running)
     pub fn find_running_pids(&self, name: &str) -> Result<RunningPids> {
+        let name = &self.scoped_name(name)?;
         let kernel_dir = self.get_kernel_dir(name);
 
         let tool_pid_file = kernel_dir.join(".tool.pid");
@@ -446,6 +905,7 @@ process.exit(0);
         name: &str,
         _options: &HashMap<String, String>,
     ) -> Result<StartResult> {
+        let name = &self.scoped_name(name)?;
         if !self.exists(name) {
             return Err(CkpError::FileNotFound(format!("Kernel not found: {}", name)));
         }
@@ -465,6 +925,9 @@ process.exit(0);
         let ontology_reader = OntologyReader::new(self.root.clone());
         let ontology = ontology_reader.read_by_kernel_name(name)?;
         let kernel_type = &ontology.metadata.kernel_type;
+        if let Some(ref archived) = ontology.metadata.archived {
+            return Err(CkpError::ValidationError(format!("Kernel {} was decommissioned at {}", name, archived)));
+        }
 
         // Check if already running
         let pids = self.find_running_pids(name)?;
@@ -474,39 +937,62 @@ process.exit(0);
                 watcher_pid: pids.watcher_pid,
                 kernel_type: kernel_type.clone(),
                 already_running: true,
+                error: None,
+                log_tail: Vec::new(),
             });
         }
 
-        let is_hot = kernel_type.contains("hot");
+        let is_hot = ontology.metadata.kernel_class() == Some(KernelClass::Hot);
 
         // Step 1: ALWAYS spawn governor daemon watcher for ALL kernels
-        let watcher_pid = self.spawn_watcher(name)?;
+        let mut watcher = self.spawn_watcher(name)?;
+        let watcher_pid = watcher.id();
 
         // Write watcher PID file
         let watcher_pid_file = kernel_dir.join(".watcher.pid");
         self.write_pid_file(&watcher_pid_file, watcher_pid)?;
 
         // Step 2: For hot kernels, also spawn the tool process
-        let tool_pid = if is_hot {
-            let pid = self.spawn_hot_tool(name, &kernel_type, &ontology)?;
+        let mut tool = if is_hot {
+            let tool = self.spawn_hot_tool(name, &kernel_type, &ontology)?;
 
             // Write tool PID file
             let tool_pid_file = kernel_dir.join(".tool.pid");
-            self.write_pid_file(&tool_pid_file, pid)?;
+            self.write_pid_file(&tool_pid_file, tool.id())?;
 
-            Some(pid)
+            Some(tool)
         } else {
             None
         };
+        let tool_pid = tool.as_ref().map(|tool| tool.id());
 
         // Phase 4 Stage 2: Create KernelEntity (BFO Material Entity)
         self.ensure_kernel_entity(name, &ontology).ok(); // Non-blocking
 
+        // Report processes that die right away, with the end of their output
+        tokio::time::sleep(START_GRACE).await;
+        let logs = KernelLogs::new(&kernel_dir);
+        let mut processes = vec![("Governor", &mut watcher, GOVERNOR_LOG)];
+        if let Some(tool) = tool.as_mut() {
+            processes.push(("Tool", tool, TOOL_LOG));
+        }
+        let mut error = None;
+        let mut log_tail = Vec::new();
+        for (process, child, log) in processes {
+            if let Ok(Some(status)) = child.try_wait() {
+                error = Some(format!("{} exited during startup ({})", process, status));
+                log_tail = logs.tail(log, START_LOG_LINES).unwrap_or_default();
+                break;
+            }
+        }
+
         Ok(StartResult {
             pid: tool_pid,
             watcher_pid: Some(watcher_pid),
             kernel_type: kernel_type.clone(),
             already_running: false,
+            error,
+            log_tail,
         })
     }
 
@@ -529,13 +1015,26 @@ process.exit(0);
             metadata.insert("port".to_string(), serde_json::json!(port));
         }
 
+        // Existing entity: record version change and bootstrap, keep accumulated state
+        if let Ok(existing) = tracker.get_kernel_entity(kernel_name) {
+            if existing.version != version {
+                let mut details = HashMap::new();
+                details.insert("from".to_string(), serde_json::json!(existing.version));
+                details.insert("to".to_string(), serde_json::json!(version));
+                tracker.record_lifecycle(kernel_name, LifecycleEvent::Versioned, self.agent.as_deref(), details)?;
+            }
+            return tracker.record_lifecycle(kernel_name, LifecycleEvent::Bootstrapped, self.agent.as_deref(), HashMap::new());
+        }
+
         // Create kernel entity
-        let entity = tracker.create_kernel_entity(
+        let entity = tracker.create_kernel_entity_as(
             kernel_name,
             &version,
             &kernel_type,
             metadata,
+            self.agent.as_deref(),
         )?;
+        tracker.record_lifecycle(kernel_name, LifecycleEvent::Bootstrapped, self.agent.as_deref(), HashMap::new())?;
 
         // Derive function from kernel name
         if kernel_name.starts_with("System.Gateway") {
@@ -588,6 +1087,7 @@ process.exit(0);
     ///
     /// true if any process was stopped, false otherwise
     pub async fn stop_kernel(&self, name: &str) -> Result<bool> {
+        let name = &self.scoped_name(name)?;
         let kernel_dir = self.get_kernel_dir(name);
         let tool_pid_file = kernel_dir.join(".tool.pid");
         let watcher_pid_file = kernel_dir.join(".watcher.pid");
@@ -678,8 +1178,10 @@ process.exit(0);
 
     /// Start all kernels in the project
     ///
-    /// Attempts to start every kernel found in /concepts/.
-    /// Skips kernels that are already running.
+    /// Attempts to start every kernel found in /concepts/ except warm
+    /// kernels, which the edge router wakes on their first inbound job (see
+    /// `wake`), and cold kernels, which stay process-less until started
+    /// explicitly. Skips kernels that are already running.
     ///
     /// # Returns
     ///
@@ -702,10 +1204,14 @@ process.exit(0);
         let mut results = Vec::new();
 
         for name in kernel_names {
+            if let Some(class @ (KernelClass::Warm | KernelClass::Cold)) = self.kernel_class(&name) {
+                tracing::debug!(kernel = %name, class = class.as_str(), "not starting kernel");
+                continue;
+            }
             match self.start_kernel(&name, &std::collections::HashMap::new()).await {
                 Ok(result) => results.push(result),
                 Err(e) => {
-                    eprintln!("Failed to start {}: {}", name, e);
+                    tracing::warn!(kernel = %name, error = %e, "failed to start kernel");
                 }
             }
         }
@@ -713,6 +1219,45 @@ process.exit(0);
         Ok(results)
     }
 
+    /// Start a warm kernel's governor if it is not running
+    ///
+    /// Called by the edge router when it delivers a job, so warm kernels
+    /// start on their first inbound job. Kernels of other classes, and
+    /// decommissioned ones, are left alone.
+    ///
+    /// # Returns
+    ///
+    /// PID of the governor started, None if nothing was started
+    pub fn wake(&self, name: &str) -> Result<Option<u32>> {
+        let name = &self.scoped_name(name)?;
+        let ontology = OntologyReader::new(self.root.clone()).read_by_kernel_name(name)?;
+        if ontology.metadata.kernel_class() != Some(KernelClass::Warm) || ontology.metadata.archived.is_some() {
+            return Ok(None);
+        }
+
+        let pids = self.find_running_pids(name)?;
+        if pids.pid.is_some() || pids.watcher_pid.is_some() {
+            return Ok(None);
+        }
+
+        let watcher_pid = self.spawn_watcher(name)?.id();
+        self.write_pid_file(&self.get_kernel_dir(name).join(".watcher.pid"), watcher_pid)?;
+        self.ensure_kernel_entity(name, &ontology).ok(); // Non-blocking
+
+        tracing::info!(kernel = %name, pid = watcher_pid, "woke warm kernel");
+        Ok(Some(watcher_pid))
+    }
+
+    /// Temperature class of a kernel (None if its type has none or its ontology is unreadable)
+    pub fn kernel_class(&self, name: &str) -> Option<KernelClass> {
+        let name = self.scoped_name(name).ok()?;
+        OntologyReader::new(self.root.clone())
+            .read_by_kernel_name(&name)
+            .ok()?
+            .metadata
+            .kernel_class()
+    }
+
     /// Stop all running kernels in the project
     ///
     /// Sends SIGTERM to all running kernel processes.
@@ -742,7 +1287,7 @@ process.exit(0);
             match self.stop_kernel(&name).await {
                 Ok(stopped) => results.push((name.clone(), stopped)),
                 Err(e) => {
-                    eprintln!("Failed to stop {}: {}", name, e);
+                    tracing::warn!(kernel = %name, error = %e, "failed to stop kernel");
                     results.push((name.clone(), false));
                 }
             }
@@ -820,72 +1365,54 @@ process.exit(0);
         Ok(None)
     }
 
-    /// Calculate kernel mode based on type and PIDs
+    /// Calculate kernel mode based on class and PIDs
     fn calculate_mode(
         &self,
-        kernel_type: &str,
+        class: Option<KernelClass>,
         pids: &RunningPids,
         _ontology: &Ontology,
     ) -> String {
         // Note: Ontology annotations not yet supported in current structure
         // Would check for "conceptkernel.io/stopped" annotation here
 
-        // Calculate mode based on type and running processes
-        if kernel_type.contains("hot") {
+        match class {
             // Hot services: long-running processes (websockets, APIs, etc.)
-            if pids.pid.is_some() {
-                "ONLINE".to_string()
-            } else {
-                // Hot service not running = DOWN (regardless of watcher state)
-                "DOWN".to_string()
-            }
-        } else if kernel_type.contains("cold") {
-            // Cold services: job processors with governors
-            match (pids.watcher_pid, pids.pid) {
+            // Hot service not running = DOWN (regardless of watcher state)
+            Some(KernelClass::Hot) => match pids.pid {
+                Some(_) => "ONLINE".to_string(),
+                None => "DOWN".to_string(),
+            },
+            // Warm and cold services: job processors with governors
+            Some(class) => match (pids.watcher_pid, pids.pid) {
                 (Some(_), Some(_)) => "PROCESSING".to_string(), // Processing a job
                 (Some(_), None) => "IDLE".to_string(),          // Ready, waiting for jobs
-                _ => "DOWN".to_string(),                         // Governor not running
-            }
-        } else {
+                // No governor yet: woken by the router (warm), or not meant to run (cold)
+                (None, _) if class == KernelClass::Warm => "STANDBY".to_string(),
+                (None, _) => "SLEEP".to_string(),
+            },
             // Unknown type
-            "DOWN".to_string()
+            None => "DOWN".to_string(),
         }
     }
 
-    /// Get queue statistics for kernel
+    /// Get queue statistics for kernel by counting its queue directories
     fn get_queue_stats(&self, kernel_dir: &Path) -> Result<QueueStats> {
-        let count_queue = |subdir: &str| -> Result<usize> {
-            let dir = kernel_dir.join("queue").join(subdir);
-            if !dir.exists() {
-                return Ok(0);
-            }
-
-            let count = fs::read_dir(&dir)
-                .map_err(|e| CkpError::IoError(format!("Failed to read queue: {}", e)))?
-                .filter_map(|e| e.ok())
-                .filter(|e| {
-                    let name = e.file_name().to_string_lossy().to_string();
-                    name != ".gitkeep" && e.path().is_file()
-                })
-                .count();
-
-            Ok(count)
-        };
+        QueueCounters::new(kernel_dir).recount()
+    }
 
-        Ok(QueueStats {
-            inbox: count_queue("inbox")?,
-            staging: count_queue("staging")?,
-            ready: count_queue("ready")?,
-        })
+    /// Queue statistics from the kernel's counters, recounting if there are none
+    fn estimate_queue_stats(&self, kernel_dir: &Path) -> Result<QueueStats> {
+        match QueueCounters::new(kernel_dir).estimate() {
+            Some(stats) => Ok(stats),
+            None => self.get_queue_stats(kernel_dir),
+        }
     }
 
     /// Spawn governor daemon watcher process for a kernel
     ///
     /// This is called for ALL kernels (both hot and cold).
     /// Uses the unified `ckr daemon governor` command.
-    fn spawn_watcher(&self, name: &str) -> Result<u32> {
-        use std::process::{Command, Stdio};
-
+    fn spawn_watcher(&self, name: &str) -> Result<Child> {
         // Find ckr binary - resolve symlinks to get the actual binary
         let current_exe = std::env::current_exe()
             .map_err(|_| CkpError::Process("Failed to get current executable path".to_string()))?;
@@ -903,17 +1430,10 @@ process.exit(0);
         }
 
         // Spawn ckr daemon governor with proper detachment
-        // Redirect stderr to a debug log file for visibility
-        let kernel_dir = self.get_kernel_dir(name);
-        let debug_log = kernel_dir.join("logs").join("governor-debug.log");
-        std::fs::create_dir_all(kernel_dir.join("logs")).ok();
-        let stderr_file = std::fs::OpenOptions::new()
-            .create(true)
-            .append(true)
-            .open(&debug_log)
-            .unwrap_or_else(|_| std::fs::File::create("/dev/null").unwrap());
-
-        let child = Command::new(&ckr)
+        // Its output goes to logs/governor.log for visibility
+        let (stdout, stderr) = self.output_log(name, GOVERNOR_LOG);
+
+        Command::new(&ckr)
             .arg("daemon")
             .arg("governor")
             .arg("--kernel")
@@ -921,19 +1441,30 @@ process.exit(0);
             .arg("--project")
             .arg(&self.root)
             .stdin(Stdio::null())
-            .stdout(Stdio::null())
-            .stderr(Stdio::from(stderr_file))
+            .stdout(stdout)
+            .stderr(stderr)
             .spawn()
-            .map_err(|e| CkpError::Process(format!("Failed to spawn ckr daemon governor: {}", e)))?;
+            .map_err(|e| CkpError::Process(format!("Failed to spawn ckr daemon governor: {}", e)))
+    }
 
-        Ok(child.id())
+    /// Stdout and stderr for a kernel process, appending to one of its output logs
+    ///
+    /// Output is discarded if the log cannot be opened.
+    fn output_log(&self, name: &str, log: &str) -> (Stdio, Stdio) {
+        let file = KernelLogs::new(&self.get_kernel_dir(name)).open(log);
+        match file.and_then(|file| Ok((file.try_clone()?, file))) {
+            Ok((stdout, stderr)) => (Stdio::from(stdout), Stdio::from(stderr)),
+            Err(e) => {
+                tracing::warn!(kernel = name, log, error = %e, "could not open output log");
+                (Stdio::null(), Stdio::null())
+            }
+        }
     }
 
     /// Spawn hot kernel tool process
     ///
     /// Only called for hot kernels. Spawns the actual tool binary/script.
-    fn spawn_hot_tool(&self, name: &str, kernel_type: &str, ontology: &Ontology) -> Result<u32> {
-        use std::process::{Command, Stdio};
+    fn spawn_hot_tool(&self, name: &str, kernel_type: &str, ontology: &Ontology) -> Result<Child> {
 
         let kernel_dir = self.get_kernel_dir(name);
 
@@ -1010,15 +1541,14 @@ process.exit(0);
             command.current_dir(kernel_dir.join("tool"));
         }
 
-        // Spawn with proper detachment
-        let child = command
+        // Spawn with proper detachment, output to logs/tool.log
+        let (stdout, stderr) = self.output_log(name, TOOL_LOG);
+        command
             .stdin(Stdio::null())
-            .stdout(Stdio::null())
-            .stderr(Stdio::null())
+            .stdout(stdout)
+            .stderr(stderr)
             .spawn()
-            .map_err(|e| CkpError::Process(format!("Failed to spawn hot tool: {}", e)))?;
-
-        Ok(child.id())
+            .map_err(|e| CkpError::Process(format!("Failed to spawn hot tool: {}", e)))
     }
 
     /// Send SIGTERM signal to process
@@ -1036,11 +1566,41 @@ process.exit(0);
     #[cfg(not(unix))]
     fn send_sigterm(&self, _pid: u32) -> bool {
         // Windows support would go here
-        eprintln!("[KernelManager] SIGTERM not supported on this platform");
+        tracing::warn!("SIGTERM not supported on this platform");
         false
     }
 }
 
+/// Files and edge links waiting in a kernel's queues
+fn pending_entries(kernel_dir: &Path) -> Vec<PathBuf> {
+    let queue_dir = kernel_dir.join("queue");
+    PENDING_QUEUES
+        .iter()
+        .flat_map(|queue| walkdir::WalkDir::new(queue_dir.join(queue)).follow_links(false).into_iter().flatten())
+        .filter(|entry| !entry.file_type().is_dir())
+        .filter(|entry| !entry.file_name().to_string_lossy().starts_with('.'))
+        .map(|entry| entry.into_path())
+        .collect()
+}
+
+/// Set `metadata.archived` in a kernel's conceptkernel.yaml
+fn mark_archived(ontology_path: &Path, archived_at: &str) -> Result<()> {
+    let content = fs::read_to_string(ontology_path)?;
+    let mut document: serde_yaml::Value = serde_yaml::from_str(&content)
+        .map_err(|e| CkpError::Ontology(format!("Failed to parse {}: {}", ontology_path.display(), e)))?;
+
+    let metadata = document
+        .get_mut("metadata")
+        .and_then(|metadata| metadata.as_mapping_mut())
+        .ok_or_else(|| CkpError::Ontology(format!("No metadata in {}", ontology_path.display())))?;
+    metadata.insert("archived".into(), archived_at.into());
+
+    let content = serde_yaml::to_string(&document)
+        .map_err(|e| CkpError::Ontology(format!("Failed to write {}: {}", ontology_path.display(), e)))?;
+    fs::write(ontology_path, content)?;
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1223,6 +1783,39 @@ ckp: a bfo:0000029 ;  # Site (BFO)
         assert_eq!(stats.ready, 0);
     }
 
+    #[test]
+    fn test_queue_stats_estimate_and_recount() {
+        let (temp, manager) = setup_test_manager();
+        create_test_kernel(temp.path(), "TestKernel", "node:cold");
+        let inbox = manager.get_kernel_dir("TestKernel").join("queue/inbox");
+
+        // Without counters the estimate is a recount
+        fs::write(inbox.join("job1.job"), "{}").unwrap();
+        assert_eq!(manager.queue_stats("TestKernel").unwrap().inbox, 1);
+
+        // Jobs written behind the driver's back wait for reconciliation
+        fs::write(inbox.join("job2.job"), "{}").unwrap();
+        assert_eq!(manager.queue_stats("TestKernel").unwrap().inbox, 1);
+        assert_eq!(manager.recount_queue_stats("TestKernel").unwrap().inbox, 2);
+        assert_eq!(manager.queue_stats("TestKernel").unwrap().inbox, 2);
+
+        assert!(manager.queue_stats("Missing").is_err());
+    }
+
+    #[test]
+    fn test_tail_tool_log() {
+        let (temp, manager) = setup_test_manager();
+        create_test_kernel(temp.path(), "TestKernel", "node:cold");
+        assert!(manager.tail("TestKernel", 5).unwrap().is_empty());
+
+        let logs_dir = manager.get_kernel_dir("TestKernel").join("logs");
+        fs::create_dir_all(&logs_dir).unwrap();
+        fs::write(logs_dir.join(TOOL_LOG), "starting\nlistening on 3000\npanic: boom\n").unwrap();
+        assert_eq!(manager.tail("TestKernel", 2).unwrap(), vec!["listening on 3000", "panic: boom"]);
+
+        assert!(manager.tail("Missing", 5).is_err());
+    }
+
     // ===== LIFECYCLE EDGE CASES =====
 
     #[tokio::test]
@@ -1380,6 +1973,24 @@ ckp: a bfo:0000029 ;  # Site (BFO)
         assert_eq!(status.watcher_pid, None);
     }
 
+    #[tokio::test]
+    async fn test_start_all_leaves_warm_and_cold_kernels_process_less() {
+        let (temp, manager) = setup_test_manager();
+        create_test_kernel(temp.path(), "Test.Warm", "node:warm");
+        create_test_kernel(temp.path(), "Test.Cold", "python:cold");
+
+        assert_eq!(manager.kernel_class("Test.Warm"), Some(KernelClass::Warm));
+        assert!(manager.start_all().await.unwrap().is_empty());
+
+        let warm = manager.get_kernel_status("Test.Warm").await.unwrap();
+        assert_eq!((warm.class, warm.mode.as_str(), warm.watcher_pid), (Some(KernelClass::Warm), "STANDBY", None));
+        let cold = manager.get_kernel_status("Test.Cold").await.unwrap();
+        assert_eq!((cold.class, cold.mode.as_str(), cold.watcher_pid), (Some(KernelClass::Cold), "SLEEP", None));
+
+        // Only warm kernels are woken
+        assert_eq!(manager.wake("Test.Cold").unwrap(), None);
+    }
+
     #[test]
     fn test_list_all_kernels_empty() {
         let (_temp, manager) = setup_test_manager();
@@ -1389,4 +2000,122 @@ ckp: a bfo:0000029 ;  # Site (BFO)
         assert!(kernels.is_empty());
         assert_eq!(kernels.len(), 0);
     }
+
+    #[test]
+    fn test_create_and_delete_record_lifecycle() {
+        let (temp, manager) = setup_test_manager();
+        let manager = manager.with_agent("ckp://Continuant#Agent-alice");
+
+        manager.create_kernel("Test.Archived", "node:cold", "v0.1").unwrap();
+        manager.create_kernel("Test.Deleted", "node:cold", "v0.1").unwrap();
+
+        let archived = manager.delete_kernel("Test.Archived", true).unwrap().unwrap();
+        assert!(archived.exists());
+        assert!(manager.delete_kernel("Test.Deleted", false).unwrap().is_none());
+        assert!(!manager.exists("Test.Deleted"));
+        assert!(manager.list_kernels().unwrap().is_empty());
+
+        let tracker = ContinuantTracker::new(temp.path().to_path_buf());
+        let history = tracker.history("ckp://Continuant#Kernel-Test.Archived").unwrap();
+        let events: Vec<LifecycleEvent> = history.iter().map(|t| t.event).collect();
+        assert_eq!(events, vec![LifecycleEvent::Created, LifecycleEvent::Archived]);
+        assert_eq!(history[0].agent.as_deref(), Some("ckp://Continuant#Agent-alice"));
+
+        let history = tracker.history("ckp://Continuant#Kernel-Test.Deleted").unwrap();
+        assert_eq!(history.last().unwrap().event, LifecycleEvent::Deleted);
+    }
+
+    #[test]
+    fn test_create_kernel_announces_registration_to_workflow() {
+        let (temp, manager) = setup_test_manager();
+        let inbox = temp.path().join("concepts").join(WORKFLOW_KERNEL).join("queue/inbox");
+
+        // Without System.Workflow nothing is announced
+        manager.create_kernel("Orders.Intake", "node:cold", "v0.1").unwrap();
+        assert!(!inbox.exists());
+
+        create_test_kernel(temp.path(), WORKFLOW_KERNEL, "rust:hot");
+        manager.with_agent("ckp://Continuant#Agent-alice").create_kernel("Orders.Billing", "python:cold", "v0.2").unwrap();
+
+        let jobs: Vec<_> = fs::read_dir(&inbox).unwrap().flatten().collect();
+        assert_eq!(jobs.len(), 1);
+        let job: serde_json::Value = serde_json::from_str(&fs::read_to_string(jobs[0].path()).unwrap()).unwrap();
+        let event: WorkflowEvent = serde_json::from_value(job["payload"].clone()).unwrap();
+        assert_eq!((event.event.as_str(), event.kernel.as_str()), (crate::workflow::KERNEL_REGISTERED, "Orders.Billing"));
+        assert_eq!(event.details["template"], "python:cold");
+        assert_eq!(event.details["agent"], "ckp://Continuant#Agent-alice");
+    }
+
+    #[test]
+    fn test_namespace_confines_manager() {
+        let (temp, manager) = setup_test_manager();
+        create_test_kernel(temp.path(), "Shared.Kernel", "node:cold");
+        create_test_kernel(temp.path(), "globex~Orders", "node:cold");
+
+        let acme = KernelManager::new(temp.path().to_path_buf()).unwrap().with_namespace("acme");
+        acme.create_kernel("Orders", "node:cold", "v0.1").unwrap();
+        assert!(temp.path().join("concepts/acme~Orders/conceptkernel.yaml").exists());
+
+        assert_eq!(acme.list_kernels().unwrap(), vec!["acme~Orders".to_string()]);
+        assert!(acme.exists("Orders") && acme.exists("acme~Orders"));
+        assert!(!acme.exists("globex~Orders"));
+        assert!(matches!(acme.delete_kernel("globex~Orders", false), Err(CkpError::Rbac(_))));
+        assert!(matches!(acme.queue_stats("globex~Orders"), Err(CkpError::Rbac(_))));
+
+        // The unconfined manager still sees every tenant
+        assert_eq!(manager.list_kernels().unwrap().len(), 3);
+    }
+
+    #[test]
+    fn test_delete_releases_port_reservations() {
+        let (temp, manager) = setup_test_manager();
+        manager.create_kernel("Test.Gateway", "node:hot", "v0.1").unwrap();
+
+        let mut ports = PortManager::new(temp.path()).unwrap();
+        ports.set_base_port(58600).unwrap();
+        ports.allocate("Test.Gateway", None).unwrap();
+        ports.reserve_with("gateway-http", Some("Test.Gateway"), None).unwrap();
+        ports.reserve("metrics").unwrap();
+
+        manager.delete_kernel("Test.Gateway", false).unwrap();
+
+        let ports = PortManager::new(temp.path()).unwrap();
+        assert_eq!(ports.get("Test.Gateway"), None);
+        assert!(ports.get_reservation("gateway-http").is_none());
+        assert!(ports.get_reservation("metrics").is_some());
+    }
+
+    #[tokio::test]
+    async fn test_decommission_dead_letters_and_archives() {
+        let (temp, manager) = setup_test_manager();
+        create_test_kernel(temp.path(), "Orders.Legacy", "node:cold");
+        create_test_kernel(temp.path(), "Orders.Source", "node:cold");
+        let kernel_dir = temp.path().join("concepts/Orders.Legacy");
+
+        // A queued job and an edge entry pointing at another kernel's instance
+        fs::write(kernel_dir.join("queue/inbox/1000-00000001.job"), "{}").unwrap();
+        let instance = temp.path().join("concepts/Orders.Source/storage/1000-00000002.inst");
+        fs::create_dir_all(&instance).unwrap();
+        let edge_queue = kernel_dir.join("queue/edges/PRODUCES.Orders.Source");
+        fs::create_dir_all(&edge_queue).unwrap();
+        FileSystemDriver::new(temp.path().to_path_buf(), String::new())
+            .create_symlink(&instance, &edge_queue, None)
+            .unwrap();
+
+        let report = manager.decommission("Orders.Legacy", QueueDisposition::DeadLetter).await.unwrap();
+        assert_eq!(report.dead_lettered.len(), 2);
+        assert!(report.snapshot.is_none());
+        let dead_letter = kernel_dir.join(DEAD_LETTER_DIR);
+        assert!(dead_letter.join("inbox/1000-00000001.job").exists());
+        assert!(dead_letter.join("edges/PRODUCES.Orders.Source/1000-00000002.inst").is_dir());
+        assert!(fs::read_dir(kernel_dir.join("queue/inbox")).unwrap().next().is_none());
+
+        // Archived: recorded, refused on start and on a second decommission
+        let ontology = OntologyReader::new(temp.path().to_path_buf()).read_by_kernel_name("Orders.Legacy").unwrap();
+        assert_eq!(ontology.metadata.archived.as_deref(), Some(report.archived_at.as_str()));
+        assert!(manager.start_kernel("Orders.Legacy", &HashMap::new()).await.is_err());
+        assert!(manager.decommission("Orders.Legacy", QueueDisposition::DeadLetter).await.is_err());
+        let audit = fs::read_to_string(temp.path().join("concepts/System.Audit/storage/audit.log")).unwrap();
+        assert!(audit.contains("kernel.decommission"));
+    }
 }
@@ -1,18 +1,32 @@
 //! Kernel module for governor and process management
 
 mod governor;
+mod circuit_breaker;
 mod pid;
+mod logs;
+mod processing;
 mod kernel;
 mod manager;
 mod builder;
+mod replay;
+mod history;
+mod registry;
+mod quarantine;
 pub mod api;
 
 pub use governor::ConceptKernelGovernor;
+pub use circuit_breaker::{CircuitBreaker, CircuitBreakerConfig, CircuitState, DEFAULT_COOLDOWN, DEFAULT_FAILURE_THRESHOLD};
 pub use pid::PidFile;
-pub use kernel::{Kernel, JobFile, Job, InboxIterator};
-pub use manager::{KernelManager, KernelStatus, QueueStats, RunningPids, StartResult};
+pub use logs::{KernelLogs, GOVERNOR_LOG, LOG_MAX_BYTES, LOG_ROTATIONS, TOOL_LOG};
+pub use processing::{tx_sort_key, PendingQueue, ProcessingOrder, QueueSelector};
+pub use kernel::{Kernel, JobFile, Job, InboxIterator, TaggedPayload};
+pub use manager::{DecommissionReport, KernelManager, KernelStatus, QueueDisposition, QueueStats, RunningPids, StartResult, DEAD_LETTER_DIR, START_GRACE, START_LOG_LINES};
 pub use builder::KernelBuilder;
-pub use api::{KernelContext, AdoptedContext, EdgeResponse};
+pub use replay::{ReplayEngine, ReplayEvent, ReplayReport};
+pub use history::{KernelHistory, KernelState, QueuedJob};
+pub use quarantine::{QuarantineDiagnosis, QuarantineReason, DIAGNOSIS_SUFFIX, QUARANTINE_DIR};
+pub use registry::{RegistryClient, RegistryEntry, DEFAULT_REGISTRY_CACHE_TTL, REGISTRY_KERNEL};
+pub use api::{KernelContext, AdoptedContext, KernelRuntime, EdgeResponse, EdgeResponseStream, EdgeStream};
 
 #[cfg(test)]
 mod tests {
@@ -0,0 +1,358 @@
+//! Queue processing order
+//!
+//! A kernel chooses the order its jobs are processed in through its queue
+//! contract:
+//!
+//! ```yaml
+//! spec:
+//!   queue_contract:
+//!     processing: round-robin
+//! ```
+//!
+//! - `fifo` (default) - oldest transaction first, by the timestamp in the tx ID
+//! - `lifo` - newest transaction first
+//! - `round-robin` - take turns: the governor serves the inbox and each edge
+//!   queue in rotation, and the inbox iterator alternates between job sources
+//! - `priority` - highest job `priority` first, oldest first among equals;
+//!   edge queue entries carry no priority and rank as 0
+//!
+//! The governor applies the order when choosing which queue to run the tool
+//! for (`QueueSelector`); `InboxIterator` applies it to the jobs of a run.
+
+use crate::drivers::{Encoding, MappedFile};
+use crate::errors::{CkpError, Result};
+use crate::ontology::{Ontology, OntologyReader};
+use crate::storage::shard;
+use serde::Deserialize;
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, VecDeque};
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+/// Order in which queued jobs are processed
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ProcessingOrder {
+    /// Oldest transaction first
+    #[default]
+    Fifo,
+    /// Newest transaction first
+    Lifo,
+    /// Rotate between queues (governor) and job sources (inbox)
+    RoundRobin,
+    /// Highest priority first, then oldest
+    Priority,
+}
+
+impl ProcessingOrder {
+    /// Name used in the queue contract
+    pub fn name(&self) -> &'static str {
+        match self {
+            ProcessingOrder::Fifo => "fifo",
+            ProcessingOrder::Lifo => "lifo",
+            ProcessingOrder::RoundRobin => "round-robin",
+            ProcessingOrder::Priority => "priority",
+        }
+    }
+
+    /// Order configured in an ontology, FIFO if none or invalid
+    pub fn from_ontology(ontology: &Ontology) -> Self {
+        let configured = ontology
+            .spec
+            .as_ref()
+            .and_then(|spec| spec.queue_contract.as_ref())
+            .and_then(|contract| contract.processing.as_deref());
+
+        match configured.map(str::parse) {
+            Some(Ok(order)) => order,
+            Some(Err(e)) => {
+                tracing::warn!(kernel = %ontology.metadata.get_name(), error = %e, "invalid processing order, using fifo");
+                Self::default()
+            }
+            None => Self::default(),
+        }
+    }
+
+    /// Order configured for a kernel, FIFO if its ontology cannot be read
+    pub fn for_kernel(root: &Path, kernel: &str) -> Self {
+        OntologyReader::new(root.to_path_buf())
+            .read_by_kernel_name(kernel)
+            .map(|ontology| Self::from_ontology(&ontology))
+            .unwrap_or_default()
+    }
+
+    /// Put inbox job files in processing order
+    ///
+    /// Round-robin and priority read each job's source or priority; jobs
+    /// that cannot be read sort as if from an unnamed source with priority 0.
+    pub fn order_jobs(&self, mut jobs: Vec<PathBuf>) -> Vec<PathBuf> {
+        match self {
+            ProcessingOrder::Fifo => {
+                jobs.sort_by_cached_key(|path| tx_sort_key(path));
+                jobs
+            }
+            ProcessingOrder::Lifo => {
+                jobs.sort_by_cached_key(|path| Reverse(tx_sort_key(path)));
+                jobs
+            }
+            ProcessingOrder::Priority => {
+                jobs.sort_by_cached_key(|path| {
+                    (Reverse(read_meta(path).priority.unwrap_or_default()), tx_sort_key(path))
+                });
+                jobs
+            }
+            ProcessingOrder::RoundRobin => {
+                jobs.sort_by_cached_key(|path| tx_sort_key(path));
+                let mut by_source: BTreeMap<String, VecDeque<PathBuf>> = BTreeMap::new();
+                for job in jobs {
+                    by_source.entry(read_meta(&job).source).or_default().push_back(job);
+                }
+
+                // One job from each source per turn, each source oldest first
+                let mut ordered = Vec::new();
+                while !by_source.is_empty() {
+                    by_source.retain(|_, queue| {
+                        ordered.extend(queue.pop_front());
+                        !queue.is_empty()
+                    });
+                }
+                ordered
+            }
+        }
+    }
+}
+
+impl FromStr for ProcessingOrder {
+    type Err = CkpError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "fifo" => Ok(ProcessingOrder::Fifo),
+            "lifo" => Ok(ProcessingOrder::Lifo),
+            "round-robin" | "roundrobin" | "fair" => Ok(ProcessingOrder::RoundRobin),
+            "priority" => Ok(ProcessingOrder::Priority),
+            other => Err(CkpError::ValidationError(format!(
+                "Unknown processing order '{}' (expected fifo, lifo, round-robin or priority)",
+                other
+            ))),
+        }
+    }
+}
+
+impl fmt::Display for ProcessingOrder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// Sort key of a queue entry: the timestamp leading its tx ID, then its name
+///
+/// Entries without a numeric timestamp sort after those with one.
+pub fn tx_sort_key(path: &Path) -> (u64, String) {
+    let name = path.file_name().and_then(|n| n.to_str()).unwrap_or_default();
+    let timestamp = name.split(['-', '.']).next().and_then(|t| t.parse().ok()).unwrap_or(u64::MAX);
+    (timestamp, name.to_string())
+}
+
+/// Fields of a job needed to order it
+#[derive(Debug, Default, Deserialize)]
+struct JobMeta {
+    #[serde(default)]
+    source: String,
+    #[serde(default)]
+    priority: Option<i64>,
+}
+
+fn read_meta(path: &Path) -> JobMeta {
+    let Some((encoding, _)) = path.file_name().and_then(|n| n.to_str()).and_then(Encoding::parse_job_file_name)
+    else {
+        return JobMeta::default();
+    };
+    MappedFile::open(path)
+        .ok()
+        .and_then(|file| file.decode::<JobMeta>(encoding).ok())
+        .unwrap_or_default()
+}
+
+/// A queue with entries waiting for the tool
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PendingQueue {
+    /// Queue passed to the tool: None for the inbox, `edges/{name}` for an edge queue
+    pub source_queue: Option<String>,
+
+    /// Entries waiting
+    pub count: usize,
+
+    /// Sort key of the oldest entry
+    oldest: (u64, String),
+
+    /// Sort key of the newest entry
+    newest: (u64, String),
+
+    /// Highest job priority (0 for edge queues)
+    priority: i64,
+}
+
+impl PendingQueue {
+    /// Queues of a kernel directory (concepts/{kernel}) that have entries waiting
+    ///
+    /// Job priorities are only read for the priority order.
+    pub fn scan(kernel_dir: &Path, order: ProcessingOrder) -> Vec<PendingQueue> {
+        let mut queues = Vec::new();
+
+        let inbox: Vec<PathBuf> = shard::list_entries(&kernel_dir.join("queue/inbox"))
+            .into_iter()
+            .filter(|path| path.file_name().and_then(|n| n.to_str()).and_then(Encoding::parse_job_file_name).is_some())
+            .collect();
+        let priority = match order {
+            ProcessingOrder::Priority => inbox.iter().filter_map(|job| read_meta(job).priority).max().unwrap_or(0),
+            _ => 0,
+        };
+        queues.extend(Self::from_entries(None, inbox, priority));
+
+        let mut edge_dirs: Vec<PathBuf> = fs::read_dir(kernel_dir.join("queue/edges"))
+            .map(|entries| entries.flatten().map(|e| e.path()).filter(|p| p.is_dir()).collect())
+            .unwrap_or_default();
+        edge_dirs.sort();
+        for dir in edge_dirs {
+            let Some(name) = dir.file_name().and_then(|n| n.to_str()).map(String::from) else {
+                continue;
+            };
+            let entries: Vec<PathBuf> = fs::read_dir(&dir)
+                .map(|entries| {
+                    entries
+                        .flatten()
+                        .map(|e| e.path())
+                        .filter(|p| p.extension().is_some_and(|ext| ext == "inst" || ext == "job"))
+                        .collect()
+                })
+                .unwrap_or_default();
+            queues.extend(Self::from_entries(Some(format!("edges/{}", name)), entries, 0));
+        }
+
+        queues
+    }
+
+    fn from_entries(source_queue: Option<String>, entries: Vec<PathBuf>, priority: i64) -> Option<Self> {
+        let oldest = entries.iter().map(|p| tx_sort_key(p)).min()?;
+        let newest = entries.iter().map(|p| tx_sort_key(p)).max()?;
+        Some(Self { source_queue, count: entries.len(), oldest, newest, priority })
+    }
+
+    /// Display name (inbox or the edge queue name)
+    pub fn name(&self) -> &str {
+        self.source_queue.as_deref().and_then(|q| q.strip_prefix("edges/")).unwrap_or("inbox")
+    }
+
+    /// Timestamp (ms since the epoch) leading the oldest entry's tx ID, if it has one
+    pub fn oldest_timestamp_ms(&self) -> Option<u64> {
+        Some(self.oldest.0).filter(|timestamp| *timestamp != u64::MAX)
+    }
+}
+
+/// Chooses the next queue the governor runs the tool for
+#[derive(Debug, Clone)]
+pub struct QueueSelector {
+    order: ProcessingOrder,
+    last: Option<Option<String>>,
+}
+
+impl QueueSelector {
+    /// Selector applying a processing order
+    pub fn new(order: ProcessingOrder) -> Self {
+        Self { order, last: None }
+    }
+
+    /// Processing order applied
+    pub fn order(&self) -> ProcessingOrder {
+        self.order
+    }
+
+    /// Pick the next queue to serve and remember it
+    pub fn select<'a>(&mut self, queues: &'a [PendingQueue]) -> Option<&'a PendingQueue> {
+        let selected = match self.order {
+            ProcessingOrder::Fifo => queues.iter().min_by(|a, b| a.oldest.cmp(&b.oldest)),
+            ProcessingOrder::Lifo => queues.iter().max_by(|a, b| a.newest.cmp(&b.newest)),
+            ProcessingOrder::Priority => queues
+                .iter()
+                .min_by(|a, b| (Reverse(a.priority), &a.oldest).cmp(&(Reverse(b.priority), &b.oldest))),
+            ProcessingOrder::RoundRobin => {
+                // The first queue after the last one served, in inbox-then-name order
+                let position = self
+                    .last
+                    .as_ref()
+                    .and_then(|last| queues.iter().position(|q| rotation_key(&q.source_queue) > rotation_key(last)));
+                match position {
+                    Some(index) => queues.get(index),
+                    None => queues.first(),
+                }
+            }
+        }?;
+
+        self.last = Some(selected.source_queue.clone());
+        Some(selected)
+    }
+}
+
+/// Position in the rotation: the inbox, then edge queues by name
+fn rotation_key(source_queue: &Option<String>) -> (bool, &str) {
+    (source_queue.is_some(), source_queue.as_deref().unwrap_or_default())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_processing_orders() {
+        let temp = TempDir::new().unwrap();
+        let kernel_dir = temp.path().join("concepts/Test.Kernel");
+        let inbox = kernel_dir.join("queue/inbox");
+        fs::create_dir_all(&inbox).unwrap();
+
+        // Millisecond timestamps of different lengths order numerically
+        let job = |tx_id: &str, source: &str, priority: Option<i64>| {
+            let path = inbox.join(format!("{}.job", tx_id));
+            let content = json!({"target": "Test.Kernel", "payload": {}, "timestamp": "2025-11-29T10:00:00Z",
+                "txId": tx_id, "source": source, "priority": priority});
+            fs::write(&path, content.to_string()).unwrap();
+            path
+        };
+        let a1 = job("900-a1", "A", None);
+        let a2 = job("1000-a2", "A", Some(5));
+        let a3 = job("1100-a3", "A", None);
+        let b1 = job("1050-b1", "B", Some(1));
+        let jobs = vec![a3.clone(), b1.clone(), a1.clone(), a2.clone()];
+
+        let order = |order: ProcessingOrder| order.order_jobs(jobs.clone());
+        assert_eq!(order(ProcessingOrder::Fifo), vec![a1.clone(), a2.clone(), b1.clone(), a3.clone()]);
+        assert_eq!(order(ProcessingOrder::Lifo), vec![a3.clone(), b1.clone(), a2.clone(), a1.clone()]);
+        assert_eq!(order(ProcessingOrder::Priority), vec![a2.clone(), b1.clone(), a1.clone(), a3.clone()]);
+        assert_eq!(order(ProcessingOrder::RoundRobin), vec![a1, b1, a2, a3]);
+
+        // Governor queue selection across the inbox and two edge queues
+        for (edge, tx_id) in [("PRODUCES.Early", "800-e"), ("PRODUCES.Late", "2000-l")] {
+            let dir = kernel_dir.join("queue/edges").join(edge);
+            fs::create_dir_all(&dir).unwrap();
+            fs::write(dir.join(format!("{}.inst", tx_id)), "{}").unwrap();
+        }
+        let select = |order: ProcessingOrder, rounds: usize| {
+            let queues = PendingQueue::scan(&kernel_dir, order);
+            let mut selector = QueueSelector::new(order);
+            (0..rounds).map(|_| selector.select(&queues).unwrap().name().to_string()).collect::<Vec<_>>()
+        };
+        assert_eq!(select(ProcessingOrder::Fifo, 1), ["PRODUCES.Early"]);
+        assert_eq!(select(ProcessingOrder::Lifo, 1), ["PRODUCES.Late"]);
+        assert_eq!(select(ProcessingOrder::Priority, 1), ["inbox"]);
+        assert_eq!(
+            select(ProcessingOrder::RoundRobin, 4),
+            ["inbox", "PRODUCES.Early", "PRODUCES.Late", "inbox"]
+        );
+
+        assert_eq!("Round-Robin".parse::<ProcessingOrder>().unwrap(), ProcessingOrder::RoundRobin);
+        assert!("random".parse::<ProcessingOrder>().is_err());
+    }
+}
@@ -0,0 +1,192 @@
+//! Inbox quarantine
+//!
+//! A job that cannot be processed as written would fail on every pass over
+//! the inbox. Unparseable job files are moved out of the inbox by
+//! `InboxIterator`; handlers move jobs whose payload they reject (e.g. with
+//! `CkpError::PayloadDecode`) with `Job::quarantine`. Quarantined jobs keep
+//! their file name under queue/quarantine, next to a diagnosis file
+//! (`{file}.diagnosis.json`) telling the operator what was wrong.
+//!
+//! Once the content is fixed, `KernelManager::requeue_quarantined` checks
+//! that the job parses again and moves it back to the inbox.
+
+use super::kernel::JobFile;
+use crate::drivers::{read_encoded, Encoding, QueueCounters};
+use crate::errors::{CkpError, Result};
+use crate::storage::shard;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Where quarantined jobs are kept (under the kernel directory)
+pub const QUARANTINE_DIR: &str = "queue/quarantine";
+
+/// Suffix of the diagnosis file written next to a quarantined job
+pub const DIAGNOSIS_SUFFIX: &str = ".diagnosis.json";
+
+/// Why a job was quarantined
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum QuarantineReason {
+    /// The job file could not be decoded
+    Unparseable,
+    /// The job parsed but its payload was rejected
+    InvalidPayload,
+}
+
+/// What was wrong with a quarantined job
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QuarantineDiagnosis {
+    /// Transaction ID (the file name when it has none)
+    pub tx_id: String,
+
+    /// Job file name under queue/quarantine
+    pub file: String,
+
+    pub reason: QuarantineReason,
+
+    /// Error the job failed with
+    pub error: String,
+
+    /// Path of the payload field that failed, if known (e.g. "items[1].quantity")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+
+    /// When the job was quarantined (RFC 3339)
+    pub quarantined_at: String,
+}
+
+/// Move a queued job to its kernel's quarantine with a diagnosis
+///
+/// `PayloadDecode` and `ValidationError` errors are diagnosed as invalid
+/// payloads, anything else as an unparseable file.
+pub fn quarantine(kernel_dir: &Path, job_path: &Path, error: &CkpError) -> Result<QuarantineDiagnosis> {
+    let file = job_path
+        .file_name()
+        .and_then(|name| name.to_str())
+        .ok_or_else(|| CkpError::InvalidPath(format!("Not a job file: {}", job_path.display())))?
+        .to_string();
+    let tx_id = Encoding::parse_job_file_name(&file).map(|(_, tx_id)| tx_id).unwrap_or(&file).to_string();
+
+    let (reason, path) = match error {
+        CkpError::PayloadDecode { path, .. } => (QuarantineReason::InvalidPayload, Some(path.clone())),
+        CkpError::ValidationError(_) => (QuarantineReason::InvalidPayload, None),
+        _ => (QuarantineReason::Unparseable, None),
+    };
+    let diagnosis = QuarantineDiagnosis {
+        tx_id,
+        file: file.clone(),
+        reason,
+        error: error.to_string(),
+        path,
+        quarantined_at: chrono::Utc::now().to_rfc3339(),
+    };
+
+    let quarantine_dir = kernel_dir.join(QUARANTINE_DIR);
+    fs::create_dir_all(&quarantine_dir)?;
+    fs::write(diagnosis_path(&quarantine_dir, &file), serde_json::to_string_pretty(&diagnosis)?)?;
+    fs::rename(job_path, quarantine_dir.join(&file))
+        .map_err(|e| CkpError::IoError(format!("Failed to quarantine job {}: {}", diagnosis.tx_id, e)))?;
+    QueueCounters::record_removed(job_path);
+
+    tracing::warn!(job = %file, tx_id = %diagnosis.tx_id, error = %diagnosis.error, "quarantined job");
+    Ok(diagnosis)
+}
+
+/// Diagnoses of a kernel's quarantined jobs, oldest first
+pub fn list(kernel_dir: &Path) -> Result<Vec<QuarantineDiagnosis>> {
+    let entries = match fs::read_dir(kernel_dir.join(QUARANTINE_DIR)) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e.into()),
+    };
+
+    let mut diagnoses = Vec::new();
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if !path.to_str().is_some_and(|p| p.ends_with(DIAGNOSIS_SUFFIX)) {
+            continue;
+        }
+        let diagnosis = fs::read_to_string(&path).ok().and_then(|content| serde_json::from_str(&content).ok());
+        match diagnosis {
+            Some(diagnosis) => diagnoses.push(diagnosis),
+            None => tracing::warn!(path = %path.display(), "skipping unreadable diagnosis"),
+        }
+    }
+    diagnoses.sort_by(|a, b| a.quarantined_at.cmp(&b.quarantined_at).then_with(|| a.file.cmp(&b.file)));
+    Ok(diagnoses)
+}
+
+/// Move a quarantined job back to the inbox once it parses again
+///
+/// # Returns
+/// Path of the job in the inbox
+///
+/// # Errors
+/// - `CkpError::FileNotFound` if no job of `tx_id` is quarantined
+/// - `CkpError::ValidationError` if the job still does not parse; it stays quarantined
+pub fn requeue(kernel_dir: &Path, tx_id: &str, sharded: bool) -> Result<PathBuf> {
+    let quarantine_dir = kernel_dir.join(QUARANTINE_DIR);
+    let (encoding, file) = Encoding::ALL
+        .iter()
+        .map(|encoding| (*encoding, encoding.job_file_name(tx_id)))
+        .find(|(_, file)| quarantine_dir.join(file).is_file())
+        .ok_or_else(|| CkpError::FileNotFound(format!("No quarantined job {} in {}", tx_id, quarantine_dir.display())))?;
+
+    let quarantined = quarantine_dir.join(&file);
+    read_encoded::<JobFile>(&quarantined, encoding)
+        .map_err(|e| CkpError::ValidationError(format!("Quarantined job {} still does not parse: {}", tx_id, e)))?;
+
+    let inbox_path = shard::entry_path(&kernel_dir.join("queue/inbox"), &file, sharded);
+    if let Some(parent) = inbox_path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::rename(&quarantined, &inbox_path)?;
+    QueueCounters::record_added(&inbox_path);
+
+    // The diagnosis described the content that was fixed
+    let _ = fs::remove_file(diagnosis_path(&quarantine_dir, &file));
+    Ok(inbox_path)
+}
+
+fn diagnosis_path(quarantine_dir: &Path, file: &str) -> PathBuf {
+    quarantine_dir.join(format!("{}{}", file, DIAGNOSIS_SUFFIX))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_quarantine_and_requeue_after_fix() {
+        let temp = TempDir::new().unwrap();
+        let kernel_dir = temp.path().join("concepts/Orders.Intake");
+        let inbox = kernel_dir.join("queue/inbox");
+        fs::create_dir_all(&inbox).unwrap();
+        let job_path = inbox.join("1000-ab12cd34.job");
+        fs::write(&job_path, "{\"target\": \"Orders.Intake\", \"payl").unwrap();
+
+        let error = CkpError::ParseError("Failed to parse job: EOF while parsing".to_string());
+        let diagnosis = quarantine(&kernel_dir, &job_path, &error).unwrap();
+        assert_eq!((diagnosis.tx_id.as_str(), diagnosis.reason), ("1000-ab12cd34", QuarantineReason::Unparseable));
+        assert!(!job_path.exists());
+        assert_eq!(list(&kernel_dir).unwrap(), vec![diagnosis]);
+
+        // Still broken: stays quarantined
+        assert!(matches!(requeue(&kernel_dir, "1000-ab12cd34", false), Err(CkpError::ValidationError(_))));
+        assert!(matches!(requeue(&kernel_dir, "1000-00000000", false), Err(CkpError::FileNotFound(_))));
+
+        let fixed = serde_json::json!({
+            "target": "Orders.Intake",
+            "payload": {"order": 1},
+            "timestamp": "2025-11-29T10:00:00Z",
+            "txId": "1000-ab12cd34",
+            "source": "external",
+        });
+        fs::write(kernel_dir.join(QUARANTINE_DIR).join("1000-ab12cd34.job"), fixed.to_string()).unwrap();
+        assert_eq!(requeue(&kernel_dir, "1000-ab12cd34", true).unwrap(), inbox.join("ab/1000-ab12cd34.job"));
+        assert!(list(&kernel_dir).unwrap().is_empty());
+    }
+}
@@ -0,0 +1,254 @@
+//! Typed client for the System.Registry kernel
+//!
+//! Registrations are jobs emitted to System.Registry; the registry mints one
+//! instance per registration, whose receipt data is a `RegistryEntry`.
+//! Lookups read those instances from the registry's storage through a local
+//! read-through cache, refreshed after its TTL or after this client
+//! registers something.
+//!
+//! ```no_run
+//! # use ckp_core::kernel::{Kernel, RegistryClient, RegistryEntry};
+//! # use std::path::PathBuf;
+//! # async fn example() -> ckp_core::errors::Result<()> {
+//! let kernel = Kernel::new(PathBuf::from("/project"), Some("Recipes.BakeCake".to_string()), true);
+//! let mut registry = RegistryClient::new(kernel);
+//!
+//! registry.register(&RegistryEntry::new("Recipes.BakeCake", "v0.2").with_capability("bake")).await?;
+//! let bakers = registry.find_by_capability("bake")?;
+//! let entry = registry.resolve("ckp://Recipes.BakeCake:v0.2")?;
+//! # Ok(())
+//! # }
+//! ```
+
+use super::kernel::Kernel;
+use crate::errors::{CkpError, Result};
+use crate::storage::InstanceScanner;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Name of the registry kernel
+pub const REGISTRY_KERNEL: &str = "System.Registry";
+
+/// Default time registry contents are served from the local cache
+pub const DEFAULT_REGISTRY_CACHE_TTL: Duration = Duration::from_secs(30);
+
+/// A kernel version registered with System.Registry
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RegistryEntry {
+    /// Kernel name (e.g., "Recipes.BakeCake")
+    pub kernel: String,
+
+    /// Registered version (e.g., "v0.2")
+    pub version: String,
+
+    /// Capabilities the kernel offers at this version
+    #[serde(default)]
+    pub capabilities: Vec<String>,
+}
+
+impl RegistryEntry {
+    pub fn new(kernel: impl Into<String>, version: impl Into<String>) -> Self {
+        Self {
+            kernel: kernel.into(),
+            version: version.into(),
+            capabilities: Vec::new(),
+        }
+    }
+
+    pub fn with_capability(mut self, capability: impl Into<String>) -> Self {
+        self.capabilities.push(capability.into());
+        self
+    }
+
+    /// Versioned kernel URN: `ckp://{kernel}:{version}`
+    pub fn urn(&self) -> String {
+        format!("ckp://{}:{}", self.kernel, self.version)
+    }
+}
+
+/// Registered versions by kernel, in registration order
+type Snapshot = BTreeMap<String, Vec<RegistryEntry>>;
+
+/// Client registering and resolving kernels through System.Registry
+pub struct RegistryClient {
+    kernel: Kernel,
+    scanner: InstanceScanner,
+    cache_ttl: Duration,
+    cache: Mutex<Option<(Instant, Arc<Snapshot>)>>,
+}
+
+impl RegistryClient {
+    /// Client emitting registrations as `kernel`
+    pub fn new(kernel: Kernel) -> Self {
+        let registry_root = kernel.root().join("concepts").join(REGISTRY_KERNEL);
+        Self {
+            scanner: InstanceScanner::new(registry_root, REGISTRY_KERNEL.to_string()),
+            kernel,
+            cache_ttl: DEFAULT_REGISTRY_CACHE_TTL,
+            cache: Mutex::new(None),
+        }
+    }
+
+    /// Set how long registry contents are served from the local cache
+    pub fn with_cache_ttl(mut self, ttl: Duration) -> Self {
+        self.cache_ttl = ttl;
+        self
+    }
+
+    /// Register a kernel version with System.Registry
+    ///
+    /// # Returns
+    /// Transaction ID of the registration job
+    pub async fn register(&mut self, entry: &RegistryEntry) -> Result<String> {
+        let mut payload = serde_json::to_value(entry)?;
+        payload["action"] = serde_json::json!("register");
+
+        let tx_id = self.kernel.emit(REGISTRY_KERNEL, payload).await?;
+        self.invalidate();
+        Ok(tx_id)
+    }
+
+    /// Latest registered version of a kernel
+    pub fn lookup(&self, kernel: &str) -> Result<Option<RegistryEntry>> {
+        Ok(self.snapshot()?.get(kernel).and_then(|versions| versions.last()).cloned())
+    }
+
+    /// Every registered version of a kernel, oldest registration first
+    pub fn versions(&self, kernel: &str) -> Result<Vec<RegistryEntry>> {
+        Ok(self.snapshot()?.get(kernel).cloned().unwrap_or_default())
+    }
+
+    /// Latest registered versions of the kernels offering a capability
+    pub fn find_by_capability(&self, capability: &str) -> Result<Vec<RegistryEntry>> {
+        Ok(self
+            .snapshot()?
+            .values()
+            .filter_map(|versions| versions.last())
+            .filter(|entry| entry.capabilities.iter().any(|c| c == capability))
+            .cloned()
+            .collect())
+    }
+
+    /// Resolve a kernel reference (`Kernel`, `ckp://Kernel` or `ckp://Kernel:v1`)
+    ///
+    /// Without a version the latest registration is returned.
+    ///
+    /// # Errors
+    /// `CkpError::KernelNotFound` if the kernel or version is not registered
+    pub fn resolve(&self, reference: &str) -> Result<RegistryEntry> {
+        let reference = reference.trim_start_matches("ckp://");
+        let reference = reference.split('#').next().unwrap_or(reference);
+        let (kernel, version) = match reference.split_once(':') {
+            Some((kernel, version)) => (kernel, Some(version)),
+            None => (reference, None),
+        };
+
+        let snapshot = self.snapshot()?;
+        let versions = snapshot.get(kernel).map(Vec::as_slice).unwrap_or_default();
+        let entry = match version {
+            Some(version) => versions.iter().rev().find(|entry| entry.version == version),
+            None => versions.last(),
+        };
+        entry.cloned().ok_or_else(|| CkpError::KernelNotFound(format!("{} is not registered in {}", reference, REGISTRY_KERNEL)))
+    }
+
+    /// Drop cached registry contents; the next lookup reads storage
+    pub fn invalidate(&self) {
+        *self.cache.lock().unwrap_or_else(|e| e.into_inner()) = None;
+    }
+
+    // Registry contents, read from storage when the cache is empty or stale
+    fn snapshot(&self) -> Result<Arc<Snapshot>> {
+        let mut cache = self.cache.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some((loaded_at, snapshot)) = cache.as_ref() {
+            if loaded_at.elapsed() < self.cache_ttl {
+                return Ok(Arc::clone(snapshot));
+            }
+        }
+
+        let snapshot = Arc::new(self.read_storage()?);
+        *cache = Some((Instant::now(), Arc::clone(&snapshot)));
+        Ok(snapshot)
+    }
+
+    fn read_storage(&self) -> Result<Snapshot> {
+        // A registry without storage has nothing registered yet
+        let details = match self.scanner.list_instance_details() {
+            Ok(details) => details,
+            Err(CkpError::FileNotFound(_)) => return Ok(Snapshot::new()),
+            Err(e) => return Err(e),
+        };
+
+        let mut snapshot = Snapshot::new();
+        for detail in details {
+            let Ok(entry) = serde_json::from_value::<RegistryEntry>(detail.data) else {
+                continue;
+            };
+
+            // Re-registering a version replaces it and makes it the latest
+            let versions = snapshot.entry(entry.kernel.clone()).or_default();
+            versions.retain(|existing| existing.version != entry.version);
+            versions.push(entry);
+        }
+        Ok(snapshot)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::path::Path;
+    use tempfile::TempDir;
+
+    // What System.Registry mints for a registration
+    fn mint_registration(root: &Path, tx_id: &str, timestamp: &str, entry: &RegistryEntry) {
+        let inst_dir = root.join("concepts/System.Registry/storage").join(format!("{}.inst", tx_id));
+        fs::create_dir_all(&inst_dir).unwrap();
+        let receipt = serde_json::json!({
+            "id": tx_id,
+            "name": entry.urn(),
+            "kernel": REGISTRY_KERNEL,
+            "timestamp": timestamp,
+            "action": "register",
+            "data": entry,
+        });
+        fs::write(inst_dir.join("receipt.bin"), receipt.to_string()).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_register_and_resolve_through_cache() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path().to_path_buf();
+        let mut registry = RegistryClient::new(Kernel::new(root.clone(), Some("Recipes.BakeCake".to_string()), false));
+        assert_eq!(registry.lookup("Recipes.BakeCake").unwrap(), None);
+
+        // Registration is a job in the registry's inbox
+        let v1 = RegistryEntry::new("Recipes.BakeCake", "v0.1").with_capability("bake");
+        let tx_id = registry.register(&v1).await.unwrap();
+        let job = fs::read_to_string(root.join("concepts/System.Registry/queue/inbox").join(format!("{}.job", tx_id))).unwrap();
+        let job: serde_json::Value = serde_json::from_str(&job).unwrap();
+        assert_eq!(job["payload"]["action"], "register");
+        assert_eq!(job["payload"]["version"], "v0.1");
+
+        let v2 = RegistryEntry::new("Recipes.BakeCake", "v0.2").with_capability("bake").with_capability("frost");
+        mint_registration(&root, "1000-00000001", "2025-11-29T10:00:00Z", &v1);
+        mint_registration(&root, "1000-00000002", "2025-11-29T10:00:01Z", &v2);
+
+        assert_eq!(registry.lookup("Recipes.BakeCake").unwrap(), Some(v2.clone()));
+        assert_eq!(registry.versions("Recipes.BakeCake").unwrap(), vec![v1.clone(), v2.clone()]);
+        assert_eq!(registry.resolve("ckp://Recipes.BakeCake:v0.1").unwrap(), v1);
+        assert_eq!(registry.find_by_capability("frost").unwrap(), vec![v2.clone()]);
+        assert!(matches!(registry.resolve("Recipes.BakeCake:v9"), Err(CkpError::KernelNotFound(_))));
+
+        // Served from cache until invalidated
+        let mixer = RegistryEntry::new("Recipes.Mix", "v1");
+        mint_registration(&root, "1000-00000003", "2025-11-29T10:00:02Z", &mixer);
+        assert_eq!(registry.lookup("Recipes.Mix").unwrap(), None);
+        registry.invalidate();
+        assert_eq!(registry.resolve("Recipes.Mix").unwrap(), mixer);
+    }
+}
@@ -0,0 +1,348 @@
+//! Replay of a kernel's history
+//!
+//! A kernel's history is its archived jobs (`queue/archive/`) and the
+//! receipts of its instances (`storage/` and `archive/`). `ReplayEngine`
+//! reads them back in the order they happened, by the timestamp leading
+//! their tx IDs, and feeds them to a handler. Derived state (an index, a
+//! projection, a downstream kernel's storage) can be rebuilt from it after
+//! a loss, or from a given transaction onward.
+//!
+//! ```no_run
+//! use ckp_core::kernel::{ReplayEngine, ReplayEvent};
+//! use std::path::PathBuf;
+//!
+//! # fn example() -> ckp_core::errors::Result<()> {
+//! let engine = ReplayEngine::new(PathBuf::from("/project"));
+//! let mut totals = 0;
+//! engine.replay("Orders.Checkout", None, |event| {
+//!     if let ReplayEvent::Job(job) = event {
+//!         totals += job.payload["amount"].as_i64().unwrap_or(0);
+//!     }
+//!     Ok(())
+//! })?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! With `with_transactions`, the records of the kernel's transaction log
+//! are replayed too, from its compacted segments through tx.jsonl, so a
+//! replay crosses compaction boundaries (see `TxCompactor`).
+//! `replay_since_snapshot` starts after the log's last checkpoint instead,
+//! for handlers that restore their state from the snapshot summary.
+//!
+//! `reemit` instead writes the archived jobs again as new jobs, to the
+//! kernel itself or to a downstream kernel, each caused by the job it
+//! replays.
+
+use crate::drivers::{Encoding, FileSystemDriver, JobFile, JobFileBuilder, JobStamp, StorageDriver, TxCompactor, TxSnapshot};
+use crate::errors::{CkpError, Result};
+use crate::kernel::tx_sort_key;
+use crate::storage::{shard, InstanceScanner, Receipt};
+use serde_json::Value;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// One entry of a kernel's history
+#[derive(Debug, Clone)]
+pub enum ReplayEvent {
+    /// Archived job
+    Job(JobFile),
+
+    /// Receipt of an instance the kernel minted
+    Instance(Receipt),
+
+    /// Record of the kernel's transaction log
+    Transaction(Value),
+}
+
+impl ReplayEvent {
+    /// Transaction ID of the job or instance
+    pub fn tx_id(&self) -> &str {
+        match self {
+            ReplayEvent::Job(job) => &job.tx_id,
+            ReplayEvent::Instance(receipt) => &receipt.id,
+            ReplayEvent::Transaction(record) => record.get("txId").and_then(|v| v.as_str()).unwrap_or_default(),
+        }
+    }
+}
+
+/// Outcome of a replay
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ReplayReport {
+    /// Jobs fed to the handler
+    pub jobs: usize,
+
+    /// Instances fed to the handler
+    pub instances: usize,
+
+    /// Transaction log records fed to the handler
+    pub transactions: usize,
+
+    /// Transaction of the last event replayed
+    pub last_tx: Option<String>,
+}
+
+/// Replays kernels' archived jobs and receipts
+pub struct ReplayEngine {
+    /// Project root directory (contains concepts/)
+    root: PathBuf,
+
+    /// Driver re-emitted jobs are written through
+    driver: Arc<dyn StorageDriver>,
+
+    /// Replay instance receipts as well as jobs
+    include_instances: bool,
+
+    /// Replay transaction log records as well
+    include_transactions: bool,
+}
+
+impl ReplayEngine {
+    /// Engine for a project, re-emitting through the filesystem driver
+    pub fn new(root: PathBuf) -> Self {
+        let driver = Arc::new(FileSystemDriver::new(root.clone(), String::new())) as Arc<dyn StorageDriver>;
+        Self { root, driver, include_instances: true, include_transactions: false }
+    }
+
+    /// Re-emit through another storage driver
+    pub fn with_driver(mut self, driver: Arc<dyn StorageDriver>) -> Self {
+        self.driver = driver;
+        self
+    }
+
+    /// Replay archived jobs only, leaving out instance receipts
+    pub fn jobs_only(mut self) -> Self {
+        self.include_instances = false;
+        self
+    }
+
+    /// Replay the kernel's transaction log records as well
+    pub fn with_transactions(mut self) -> Self {
+        self.include_transactions = true;
+        self
+    }
+
+    /// A kernel's history in the order it happened
+    ///
+    /// # Arguments
+    /// * `kernel` - Kernel name
+    /// * `from_tx` - Start at this transaction (inclusive) instead of the beginning
+    ///
+    /// # Errors
+    /// `CkpError::KernelNotFound` if the kernel doesn't exist,
+    /// `CkpError::ValidationError` if `from_tx` is not in its history
+    pub fn events(&self, kernel: &str, from_tx: Option<&str>) -> Result<Vec<ReplayEvent>> {
+        let kernel_dir = self.root.join("concepts").join(kernel);
+        if !kernel_dir.is_dir() {
+            return Err(CkpError::KernelNotFound(kernel.to_string()));
+        }
+
+        let mut events: Vec<ReplayEvent> = shard::list_entries(&kernel_dir.join("queue/archive"))
+            .iter()
+            .filter_map(|path| read_job(path).map(ReplayEvent::Job))
+            .collect();
+
+        if self.include_instances {
+            let scanner = InstanceScanner::new(kernel_dir.clone(), kernel.to_string());
+            for dir in [kernel_dir.join("storage"), kernel_dir.join("archive")] {
+                for inst_dir in shard::list_entries(&dir) {
+                    if inst_dir.extension().is_some_and(|ext| ext == "inst") {
+                        if let Ok(receipt) = scanner.read_receipt(&inst_dir) {
+                            events.push(ReplayEvent::Instance(receipt));
+                        }
+                    }
+                }
+            }
+        }
+
+        if self.include_transactions {
+            let records = TxCompactor::new(&kernel_dir).records()?;
+            events.extend(records.into_iter().map(ReplayEvent::Transaction));
+        }
+
+        // Jobs, then the instances they produced, then their records within
+        // the same millisecond; records keep their log order among equals
+        events.sort_by_cached_key(|event| {
+            let (timestamp, tx_id) = tx_sort_key(Path::new(event.tx_id()));
+            let rank = match event {
+                ReplayEvent::Job(_) => 0,
+                ReplayEvent::Instance(_) => 1,
+                ReplayEvent::Transaction(_) => 2,
+            };
+            (timestamp, rank, tx_id)
+        });
+
+        if let Some(from_tx) = from_tx {
+            let start = events.iter().position(|event| event.tx_id() == from_tx).ok_or_else(|| {
+                CkpError::ValidationError(format!("Transaction {} is not in {}'s history", from_tx, kernel))
+            })?;
+            events.drain(..start);
+        }
+
+        Ok(events)
+    }
+
+    /// Feed a kernel's history to a handler, in order
+    ///
+    /// Stops at the first handler error and returns it.
+    pub fn replay<F>(&self, kernel: &str, from_tx: Option<&str>, handler: F) -> Result<ReplayReport>
+    where
+        F: FnMut(&ReplayEvent) -> Result<()>,
+    {
+        feed(kernel, self.events(kernel, from_tx)?, handler)
+    }
+
+    /// Feed a handler the history after the transaction log's last checkpoint
+    ///
+    /// Returns the checkpoint the handler's state should start from; without
+    /// one, the whole history is replayed.
+    pub fn replay_since_snapshot<F>(&self, kernel: &str, handler: F) -> Result<(Option<TxSnapshot>, ReplayReport)>
+    where
+        F: FnMut(&ReplayEvent) -> Result<()>,
+    {
+        let snapshot = TxCompactor::new(self.root.join("concepts").join(kernel)).snapshot()?;
+        let mut events = self.events(kernel, None)?;
+        if let Some(last_tx) = snapshot.as_ref().and_then(|snapshot| snapshot.last_tx_id.as_deref()) {
+            let checkpoint = tx_sort_key(Path::new(last_tx));
+            events.retain(|event| tx_sort_key(Path::new(event.tx_id())) > checkpoint);
+        }
+        Ok((snapshot, feed(kernel, events, handler)?))
+    }
+
+    /// Write a kernel's archived jobs again as new jobs
+    ///
+    /// Each copy gets a new transaction ID and is caused by the job it
+    /// replays, so the replay shows up in the original chain.
+    ///
+    /// # Arguments
+    /// * `kernel` - Kernel whose archive is replayed
+    /// * `from_tx` - Start at this transaction (inclusive)
+    /// * `target` - Kernel to write to; the kernel itself if None
+    ///
+    /// # Returns
+    /// Transaction IDs of the new jobs, in order
+    pub fn reemit(&self, kernel: &str, from_tx: Option<&str>, target: Option<&str>) -> Result<Vec<String>> {
+        let target = target.unwrap_or(kernel);
+        let mut emitted = Vec::new();
+        for event in self.events(kernel, from_tx)? {
+            let ReplayEvent::Job(job) = event else {
+                continue;
+            };
+
+            // Same source, payload and envelope, under a new transaction
+            let correlation_id = job.stamp.correlation_id.clone();
+            let mut replayed = JobFileBuilder::new(target, job.payload).build()?;
+            replayed.source = job.source;
+            replayed.traceparent = job.traceparent;
+            replayed.stamp = JobStamp { protocol_version: replayed.stamp.protocol_version, ..job.stamp }
+                .caused_by(&job.tx_id, correlation_id.as_deref());
+            emitted.push(self.driver.write_job(target, replayed)?);
+        }
+
+        tracing::info!(kernel, to = target, jobs = emitted.len(), "re-emitted archived jobs");
+        Ok(emitted)
+    }
+}
+
+/// Feed events to a handler, counting them
+fn feed<F>(kernel: &str, events: Vec<ReplayEvent>, mut handler: F) -> Result<ReplayReport>
+where
+    F: FnMut(&ReplayEvent) -> Result<()>,
+{
+    let mut report = ReplayReport::default();
+    for event in events {
+        handler(&event)?;
+        match event {
+            ReplayEvent::Job(_) => report.jobs += 1,
+            ReplayEvent::Instance(_) => report.instances += 1,
+            ReplayEvent::Transaction(_) => report.transactions += 1,
+        }
+        report.last_tx = Some(event.tx_id().to_string());
+    }
+
+    tracing::info!(
+        kernel,
+        jobs = report.jobs,
+        instances = report.instances,
+        transactions = report.transactions,
+        "replayed history"
+    );
+    Ok(report)
+}
+
+/// Archived job file, skipping anything that isn't one
+fn read_job(path: &Path) -> Option<JobFile> {
+    let (encoding, _) = path.file_name().and_then(|n| n.to_str()).and_then(Encoding::parse_job_file_name)?;
+    encoding.decode(&fs::read(path).ok()?).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use tempfile::TempDir;
+
+    fn archive_job(root: &Path, kernel: &str, tx_id: &str, amount: i64) {
+        let job = JobFileBuilder::new(kernel, json!({"amount": amount})).with_tx_id(tx_id).build().unwrap();
+        let archive = root.join("concepts").join(kernel).join("queue/archive");
+        fs::create_dir_all(&archive).unwrap();
+        fs::write(archive.join(format!("{}.job", tx_id)), serde_json::to_vec(&job).unwrap()).unwrap();
+    }
+
+    #[test]
+    fn test_replay_in_order_and_reemit_downstream() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path();
+        archive_job(root, "Orders", "1003-00000003", 30);
+        archive_job(root, "Orders", "1001-00000001", 10);
+        archive_job(root, "Orders", "1002-00000002", 20);
+        let inst_dir = root.join("concepts/Orders/storage/1001-00000009.inst");
+        fs::create_dir_all(&inst_dir).unwrap();
+        Receipt::new("1001-00000009", "order", "Orders", json!({})).write(&inst_dir).unwrap();
+        fs::create_dir_all(root.join("concepts/Ledger/queue/inbox")).unwrap();
+
+        let engine = ReplayEngine::new(root.to_path_buf());
+        let mut seen = Vec::new();
+        let report = engine
+            .replay("Orders", None, |event| {
+                seen.push(event.tx_id().to_string());
+                Ok(())
+            })
+            .unwrap();
+        assert_eq!(seen, ["1001-00000001", "1001-00000009", "1002-00000002", "1003-00000003"]);
+        assert_eq!((report.jobs, report.instances), (3, 1));
+        assert_eq!(report.last_tx.as_deref(), Some("1003-00000003"));
+
+        // From a transaction on, jobs only, into a downstream kernel
+        let emitted = engine.jobs_only().reemit("Orders", Some("1002-00000002"), Some("Ledger")).unwrap();
+        assert_eq!(emitted.len(), 2);
+        let first = fs::read(root.join("concepts/Ledger/queue/inbox").join(format!("{}.job", emitted[0]))).unwrap();
+        let first: JobFile = serde_json::from_slice(&first).unwrap();
+        assert_eq!(first.payload["amount"], 20);
+        assert_eq!(first.stamp.causation_id.as_deref(), Some("1002-00000002"));
+
+        assert!(matches!(
+            ReplayEngine::new(root.to_path_buf()).events("Orders", Some("9999-00000000")),
+            Err(CkpError::ValidationError(_))
+        ));
+    }
+
+    #[test]
+    fn test_replay_across_compaction_boundary() {
+        let temp = TempDir::new().unwrap();
+        let kernel_dir = temp.path().join("concepts/Orders");
+        fs::create_dir_all(&kernel_dir).unwrap();
+        let log: String = (1..=5).map(|i| format!("{}\n", json!({"txId": format!("100{}-0000000{}", i, i)}))).collect();
+        fs::write(kernel_dir.join("tx.jsonl"), log).unwrap();
+        TxCompactor::new(&kernel_dir).with_keep_records(2).compact().unwrap().unwrap();
+
+        let engine = ReplayEngine::new(temp.path().to_path_buf()).with_transactions();
+        let report = engine.replay("Orders", Some("1002-00000002"), |_| Ok(())).unwrap();
+        assert_eq!(report.transactions, 4);
+
+        let (snapshot, report) = engine.replay_since_snapshot("Orders", |_| Ok(())).unwrap();
+        assert_eq!(snapshot.unwrap().last_tx_id.as_deref(), Some("1003-00000003"));
+        assert_eq!((report.transactions, report.last_tx.as_deref()), (2, Some("1005-00000005")));
+    }
+}
@@ -47,24 +47,42 @@ pub mod continuant_tracker;
 pub mod compliance;
 pub mod cache;
 pub mod storage;
+pub mod maintenance;
 pub mod daemon;
+pub mod interpolation;
+pub mod logging;
+pub mod telemetry;
+pub mod causality;
+pub mod protocol;
+pub mod presentation;
+#[cfg(feature = "napi")]
+pub mod node;
+#[cfg(feature = "pyo3")]
+pub mod python;
+#[cfg(feature = "ffi")]
+pub mod ffi;
 
 pub use urn::{UrnResolver, UrnValidator, ParsedUrn, ParsedEdgeUrn, ParsedQueryUrn, ParsedQueryUrnV2};
 pub use errors::CkpError;
-pub use ontology::{OntologyReader, Ontology, OntologyLibrary, OntologyError, BfoEntityType, BfoAligned, RoleMetadata, FunctionMetadata, KernelMetadata};
-pub use workflow::{WorkflowAPI, Workflow, WorkflowPhase, WorkflowEdge, WorkflowTrigger, WorkflowStatus, PhaseStatus, WorkflowCycle, CycleType, WorkflowValidation};
-pub use kernel::{ConceptKernelGovernor, Kernel, JobFile, Job, InboxIterator, KernelManager, KernelStatus, QueueStats, RunningPids, StartResult, KernelContext, AdoptedContext, EdgeResponse, KernelBuilder};
-pub use project::{ProjectConfig, ProjectRegistry, ProjectEntry, ProjectInfo};
+pub use ontology::{OntologyReader, Ontology, OntologyLibrary, OntologyError, BfoEntityType, BfoAligned, RoleMetadata, FunctionMetadata, KernelMetadata, KernelClass};
+pub use workflow::{WorkflowAPI, Workflow, WorkflowPhase, WorkflowEdge, WorkflowTrigger, WorkflowStatus, PhaseStatus, WorkflowCycle, CycleType, WorkflowValidation, WorkflowEvent, WorkflowTriggers, WorkflowRun};
+pub use kernel::{ConceptKernelGovernor, CircuitBreaker, CircuitBreakerConfig, Kernel, JobFile, Job, InboxIterator, TaggedPayload, KernelManager, ProcessingOrder, KernelStatus, QueueStats, RunningPids, StartResult, KernelContext, AdoptedContext, KernelRuntime, EdgeResponse, EdgeResponseStream, EdgeStream, KernelBuilder, RegistryClient, RegistryEntry, ReplayEngine, KernelHistory};
+pub use project::{ProjectConfig, ProjectRegistry, ProjectEntry, ProjectInfo, ProjectArchiveManifest, ProjectHealth};
 pub use port::PortManager;
-pub use edge::{EdgeKernel, EdgeMetadata, EdgeRequestBuilder, EdgeRequest, EdgeSource, EdgeTarget, NotificationEntry};
-pub use rbac::{PermissionChecker, SelfImprovementConfig};
-pub use process_tracker::{ProcessTracker, Process, TemporalPart, TemporalRegion, QueryFilters, Statistics};
-pub use continuant_tracker::{ContinuantTracker, KernelEntity, Agent, Role, Function, Participation, Disposition};
-pub use compliance::{AuditLogger, GdprChecker, RetentionPolicy, AuditEntry, ConsentRecord, RetentionCheckResult, DataAccessResult, DataPortabilityExport};
-pub use cache::{PackageManager, PackageInfo};
-pub use storage::{InstanceScanner, InstanceSummary, InstanceDetail};
+pub use edge::{EdgeKernel, EdgeMetadata, EdgeRequestBuilder, EdgeRequest, EdgeRequestBatch, EdgeRequestChain, EdgeSource, EdgeTarget, NotificationEntry, PayloadKeyPair, SealedPayload};
+pub use rbac::{DecisionCache, DecisionCacheStats, EdgeMatrix, KernelPolicy, PermissionChecker, PolicyBundle, SelfImprovementConfig};
+pub use process_tracker::{ProcessTracker, Process, TemporalPart, TemporalRegion, QueryFilters, Statistics, StatisticsSnapshot, DurationPercentiles, GroupStatistics, ThroughputWindow, ProcessEvent, ProcessEventKind, ProcessEventStream, ProcessTracking, TrackedProcess, TrackingVerbosity, PHASE_DURATION_KEY};
+pub use continuant_tracker::{ContinuantTracker, KernelEntity, Agent, Role, Function, Participation, Disposition, ContinuantStore, ContinuantRecord, ContinuantSnapshot, JsonFileStore, JsonlLogStore, ParticipationQuery, DispositionTrigger, TriggerMetric, TriggerComparison, AgentCredentials, Credential, LifecycleEvent, LifecycleTransition};
+pub use compliance::{AuditLogger, GdprChecker, RetentionPolicy, AuditEntry, ConsentRecord, ConsentPurpose, LawfulBasis, RetentionCheckResult, DataAccessResult, DataPortabilityExport, IntegrityIssue, IntegrityReport, AuditFilters, AuditPage, ErasureEngine, ErasureMode, ErasureReport, DsarWorkflow, DsarRequest, DsarPackage, RedactionConfig, RedactionRules, PortabilityExporter, ExportFormat, ExportBundle, Pseudonymizer, PseudonymMode, PseudonymVault, AuditSink, SyslogSink, WebhookSink, SegmentIndexEntry};
+pub use cache::{PackageManager, PackageInfo, RegistryClient, RegistryPackage, PackageSigner, TrustPolicy, TrustStore, Lockfile, GcPolicy, GcReport, HostPlatform};
+pub use storage::{InstanceScanner, InstanceSummary, InstanceDetail, InstanceFilter, FieldPredicate, FieldComparison, InstanceOrder, InstancePage, InstancePageRequest, InstanceStream, ProjectScanner, ProjectInstance, BundleManifest, Receipt};
 pub use drivers::{GitDriver, VersionBump, VersionDriver, VersionInfo, VersionBackend, VersionDriverFactory, VersionedKernel};
-pub use daemon::EdgeRouterDaemon;
+pub use daemon::{EdgeRouterDaemon, DispositionEvaluatorDaemon, GatewayDaemon, DaemonMetrics, MetricsServer, DaemonSupervisor, QueueAlarmDaemon, ReplicationDaemon, RetentionDaemon, RetentionMode};
+pub use presentation::{OutputFormat, StatusScreen, StatusView};
+pub use interpolation::{Interpolator, SecretProvider, EnvSecretProvider, FileSecretProvider};
+pub use logging::LogFormat;
+pub use telemetry::TraceContext;
+pub use protocol::{validate_protocol_file, ProtocolFile};
 
 /// Version of the CKP protocol (upgrading to 1.3.14 for multi-project support)
 pub const VERSION: &str = "1.3.14";
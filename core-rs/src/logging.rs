@@ -0,0 +1,118 @@
+//! Structured logging setup
+//!
+//! Library modules emit `tracing` events and spans; binaries choose where
+//! they go. Events carry `kernel`, `tx_id` and `edge` fields where known,
+//! so JSON output can be joined with Node.js runtime logs on the same keys.
+//!
+//! Configuration:
+//! - `CKP_LOG` - filter directives (e.g. `info`, `ckp_core::daemon=debug`)
+//! - `CKP_LOG_FORMAT` - `text` (default) or `json`
+//! - `OTEL_EXPORTER_OTLP_ENDPOINT` - export spans over OTLP (`otel` feature)
+
+use crate::errors::{CkpError, Result};
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::util::SubscriberInitExt;
+use tracing_subscriber::{fmt, EnvFilter};
+
+/// Environment variable holding filter directives
+pub const LOG_FILTER_ENV: &str = "CKP_LOG";
+
+/// Environment variable selecting the output format
+pub const LOG_FORMAT_ENV: &str = "CKP_LOG_FORMAT";
+
+/// Output format for log events
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum LogFormat {
+    /// Human-readable lines
+    #[default]
+    Text,
+    /// One JSON object per event, with span fields flattened in
+    Json,
+}
+
+impl LogFormat {
+    /// Format from `CKP_LOG_FORMAT`, or text if unset or invalid
+    pub fn from_env() -> Self {
+        std::env::var(LOG_FORMAT_ENV)
+            .ok()
+            .and_then(|value| value.parse().ok())
+            .unwrap_or_default()
+    }
+}
+
+impl std::str::FromStr for LogFormat {
+    type Err = CkpError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_ascii_lowercase().as_str() {
+            "text" => Ok(LogFormat::Text),
+            "json" => Ok(LogFormat::Json),
+            other => Err(CkpError::ValidationError(format!(
+                "Invalid log format '{}' (expected text or json)",
+                other
+            ))),
+        }
+    }
+}
+
+/// Install a global subscriber writing to stderr
+///
+/// # Arguments
+/// * `format` - Text or JSON output
+/// * `default_filter` - Filter used when `CKP_LOG` is unset
+///
+/// # Returns
+/// false if a subscriber was already installed
+///
+/// # Errors
+/// - `CKP_LOG` or `default_filter` is not a valid filter
+pub fn init(format: LogFormat, default_filter: &str) -> Result<bool> {
+    let filter = match std::env::var(LOG_FILTER_ENV) {
+        Ok(directives) => EnvFilter::try_new(directives),
+        Err(_) => EnvFilter::try_new(default_filter),
+    }
+    .map_err(|e| CkpError::ValidationError(format!("Invalid log filter: {}", e)))?;
+
+    // Spans are also exported when built with `otel` and a collector is set
+    #[cfg(feature = "otel")]
+    let (otel, otel_error) = match crate::telemetry::layer() {
+        Ok(layer) => (layer, None),
+        Err(e) => (None, Some(e)),
+    };
+    #[cfg(not(feature = "otel"))]
+    let (otel, otel_error): (Option<tracing_subscriber::layer::Identity>, Option<CkpError>) = (None, None);
+
+    let registry = tracing_subscriber::registry().with(filter).with(otel);
+    let output = fmt::layer().with_writer(std::io::stderr);
+
+    let installed = match format {
+        LogFormat::Text => registry.with(output.with_target(false)).try_init(),
+        LogFormat::Json => registry
+            .with(
+                output
+                    .json()
+                    .flatten_event(true)
+                    .with_current_span(true)
+                    .with_span_list(false),
+            )
+            .try_init(),
+    };
+
+    if let Some(e) = otel_error {
+        tracing::warn!(error = %e, "OTLP export disabled");
+    }
+    Ok(installed.is_ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_log_format_parsing() {
+        assert_eq!("json".parse::<LogFormat>().unwrap(), LogFormat::Json);
+        assert_eq!("TEXT".parse::<LogFormat>().unwrap(), LogFormat::Text);
+        assert!("yaml".parse::<LogFormat>().is_err());
+        assert_eq!(LogFormat::default(), LogFormat::Text);
+    }
+}
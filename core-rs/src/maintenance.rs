@@ -0,0 +1,352 @@
+//! Project maintenance: garbage collection
+//!
+//! `collect_garbage` sweeps every kernel of a project for:
+//! - **Orphaned instances**: `storage/*.inst` entries whose tx id appears in
+//!   no record of the kernel's transaction log (`tx.jsonl` and its compacted
+//!   segments). Kernels with an empty or missing log are skipped, since nothing can be said about their instances.
+//! - **Dangling symlinks**: links under `queue/` or `storage/` whose target
+//!   is gone, typically edge queue entries for deleted instances.
+//! - **Empty edge queues**: `queue/edges/*` directories with nothing left in
+//!   them (dangling links aside).
+//! - **Stale PID files**: `.tool.pid`, `.watcher.pid` and
+//!   `tool/.governor.pid` naming a process that is no longer running.
+//!
+//! Entries younger than `GcOptions::min_age` are left alone, so instances
+//! minted just before their tx record is written are not collected. With
+//! `dry_run` nothing is removed and the report lists what would be.
+//!
+//! ```no_run
+//! use ckp_core::maintenance::{collect_garbage, GcOptions};
+//! use std::path::Path;
+//!
+//! let report = collect_garbage(Path::new("/my-project"), &GcOptions::default().dry_run()).unwrap();
+//! println!("{}", report);
+//! ```
+
+use crate::drivers::TxCompactor;
+use crate::errors::{CkpError, Result};
+use crate::storage::{shard, InstanceScanner};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+use sysinfo::{Pid, System};
+
+/// Age below which entries are never collected by default
+pub const DEFAULT_MIN_AGE: Duration = Duration::from_secs(3600);
+
+/// PID files checked in each kernel directory
+const PID_FILES: [&str; 3] = [".tool.pid", ".watcher.pid", "tool/.governor.pid"];
+
+/// Garbage collection settings
+#[derive(Debug, Clone)]
+pub struct GcOptions {
+    /// Report what would be removed without removing it
+    pub dry_run: bool,
+
+    /// Leave entries modified more recently than this
+    pub min_age: Duration,
+}
+
+impl Default for GcOptions {
+    fn default() -> Self {
+        Self { dry_run: false, min_age: DEFAULT_MIN_AGE }
+    }
+}
+
+impl GcOptions {
+    /// Only report, removing nothing
+    pub fn dry_run(mut self) -> Self {
+        self.dry_run = true;
+        self
+    }
+
+    /// Leave entries modified more recently than `min_age`
+    pub fn with_min_age(mut self, min_age: Duration) -> Self {
+        self.min_age = min_age;
+        self
+    }
+}
+
+/// What a garbage collection found (and removed, unless a dry run)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GcReport {
+    pub dry_run: bool,
+    pub orphaned_instances: Vec<PathBuf>,
+    pub dangling_symlinks: Vec<PathBuf>,
+    pub empty_edge_queues: Vec<PathBuf>,
+    pub stale_pid_files: Vec<PathBuf>,
+
+    /// Bytes held by the orphaned instances
+    pub reclaimed_bytes: u64,
+
+    /// Entries that could not be inspected or removed, with the reason
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub errors: Vec<String>,
+}
+
+impl GcReport {
+    /// Number of garbage entries found
+    pub fn total(&self) -> usize {
+        self.orphaned_instances.len()
+            + self.dangling_symlinks.len()
+            + self.empty_edge_queues.len()
+            + self.stale_pid_files.len()
+    }
+
+    /// Whether nothing was found
+    pub fn is_clean(&self) -> bool {
+        self.total() == 0
+    }
+}
+
+impl fmt::Display for GcReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let verb = if self.dry_run { "Would remove" } else { "Removed" };
+        writeln!(f, "{} {} entries ({} bytes of instances)", verb, self.total(), self.reclaimed_bytes)?;
+        for (label, paths) in [
+            ("orphaned instance", &self.orphaned_instances),
+            ("dangling symlink", &self.dangling_symlinks),
+            ("empty edge queue", &self.empty_edge_queues),
+            ("stale PID file", &self.stale_pid_files),
+        ] {
+            for path in paths {
+                writeln!(f, "  {}: {}", label, path.display())?;
+            }
+        }
+        for error in &self.errors {
+            writeln!(f, "  error: {}", error)?;
+        }
+        Ok(())
+    }
+}
+
+/// Find and remove garbage in every kernel of a project
+///
+/// # Arguments
+/// * `project` - Project root directory (contains concepts/)
+///
+/// # Errors
+/// `CkpError::FileNotFound` if the project has no concepts directory.
+/// Problems with individual entries are listed in `GcReport::errors`.
+pub fn collect_garbage(project: &Path, options: &GcOptions) -> Result<GcReport> {
+    let concepts = project.join("concepts");
+    let entries = fs::read_dir(&concepts)
+        .map_err(|e| CkpError::FileNotFound(format!("{}: {}", concepts.display(), e)))?;
+
+    let mut kernels: Vec<(String, PathBuf)> = entries
+        .flatten()
+        .filter(|entry| entry.path().is_dir())
+        .map(|entry| (entry.file_name().to_string_lossy().to_string(), entry.path()))
+        .filter(|(name, _)| !name.starts_with('.'))
+        .collect();
+    kernels.sort();
+
+    let mut report = GcReport { dry_run: options.dry_run, ..Default::default() };
+    for (kernel, kernel_dir) in &kernels {
+        collect_kernel(kernel, kernel_dir, options, &mut report);
+    }
+
+    tracing::info!(
+        dry_run = options.dry_run,
+        entries = report.total(),
+        bytes = report.reclaimed_bytes,
+        "garbage collection finished"
+    );
+    Ok(report)
+}
+
+fn collect_kernel(kernel: &str, kernel_dir: &Path, options: &GcOptions, report: &mut GcReport) {
+    let old_enough = |path: &Path| {
+        fs::symlink_metadata(path)
+            .and_then(|m| m.modified())
+            .ok()
+            .and_then(|modified| SystemTime::now().duration_since(modified).ok())
+            .is_some_and(|age| age >= options.min_age)
+    };
+
+    // Orphaned instances
+    let recorded = recorded_tx_ids(kernel_dir);
+    if !recorded.is_empty() {
+        let scanner = InstanceScanner::new(kernel_dir.to_path_buf(), kernel.to_string());
+        for inst in shard::list_entries(&kernel_dir.join("storage")) {
+            let Some(stem) = inst.file_name().and_then(|n| n.to_str()).and_then(|n| n.strip_suffix(".inst")) else {
+                continue;
+            };
+            let referenced = recorded.contains(stem)
+                || stem.match_indices('-').any(|(i, _)| recorded.contains(&stem[..i]));
+            if referenced || !inst.is_dir() || !old_enough(&inst) {
+                continue;
+            }
+
+            let bytes = dir_size(&inst);
+            if !options.dry_run {
+                let index_update = scanner.begin_index_update(&inst);
+                let removed = fs::remove_dir_all(&inst).map_err(CkpError::from).and_then(|_| index_update.commit());
+                if let Err(e) = removed {
+                    report.errors.push(format!("{}: {}", inst.display(), e));
+                    continue;
+                }
+            }
+            report.reclaimed_bytes += bytes;
+            report.orphaned_instances.push(inst);
+        }
+    }
+
+    // Dangling symlinks
+    let mut dangling = HashSet::new();
+    for dir in [kernel_dir.join("queue"), kernel_dir.join("storage")] {
+        for entry in walkdir::WalkDir::new(&dir).follow_links(false).into_iter().flatten() {
+            let path = entry.path();
+            if entry.path_is_symlink() && fs::metadata(path).is_err() && old_enough(path) {
+                dangling.insert(path.to_path_buf());
+            }
+        }
+    }
+    let mut dangling: Vec<PathBuf> = dangling.into_iter().collect();
+    dangling.sort();
+    for link in dangling {
+        if !options.dry_run {
+            if let Err(e) = fs::remove_file(&link) {
+                report.errors.push(format!("{}: {}", link.display(), e));
+                continue;
+            }
+        }
+        report.dangling_symlinks.push(link);
+    }
+
+    // Empty edge queues (counting dangling links as gone, also in dry runs)
+    if let Ok(edges) = fs::read_dir(kernel_dir.join("queue/edges")) {
+        let mut edge_dirs: Vec<PathBuf> = edges.flatten().map(|e| e.path()).filter(|p| p.is_dir()).collect();
+        edge_dirs.sort();
+        for edge_dir in edge_dirs {
+            let empty = fs::read_dir(&edge_dir)
+                .map(|entries| entries.flatten().all(|e| report.dangling_symlinks.contains(&e.path())))
+                .unwrap_or(false);
+            if !empty || !old_enough(&edge_dir) {
+                continue;
+            }
+            if !options.dry_run {
+                if let Err(e) = fs::remove_dir(&edge_dir) {
+                    report.errors.push(format!("{}: {}", edge_dir.display(), e));
+                    continue;
+                }
+            }
+            report.empty_edge_queues.push(edge_dir);
+        }
+    }
+
+    // Stale PID files
+    for name in PID_FILES {
+        let pid_file = kernel_dir.join(name);
+        let Ok(content) = fs::read_to_string(&pid_file) else {
+            continue;
+        };
+        if is_pid_alive(content.trim()) {
+            continue;
+        }
+        if !options.dry_run {
+            if let Err(e) = fs::remove_file(&pid_file) {
+                report.errors.push(format!("{}: {}", pid_file.display(), e));
+                continue;
+            }
+        }
+        report.stale_pid_files.push(pid_file);
+    }
+}
+
+/// Transaction IDs recorded in a kernel's tx.jsonl and its compacted segments
+/// (`txId` or `tx_id` fields)
+fn recorded_tx_ids(kernel_dir: &Path) -> HashSet<String> {
+    TxCompactor::new(kernel_dir)
+        .records()
+        .unwrap_or_default()
+        .iter()
+        .filter_map(|record| {
+            record.get("txId").or_else(|| record.get("tx_id")).and_then(|id| id.as_str()).map(str::to_string)
+        })
+        .collect()
+}
+
+/// Whether a PID file's content (`{pid}` or `{pid}:{start_time}`) names a running process
+fn is_pid_alive(content: &str) -> bool {
+    let (pid, start_time) = match content.split_once(':') {
+        Some((pid, start)) => (pid, start.parse::<u64>().ok()),
+        None => (content, None),
+    };
+    let Ok(pid) = pid.parse::<u32>() else {
+        return false;
+    };
+
+    let mut sys = System::new();
+    sys.refresh_processes(sysinfo::ProcessesToUpdate::All);
+    sys.process(Pid::from_u32(pid))
+        .is_some_and(|process| start_time.is_none_or(|start| process.start_time() == start))
+}
+
+/// Total size of the files under a directory
+fn dir_size(dir: &Path) -> u64 {
+    walkdir::WalkDir::new(dir)
+        .into_iter()
+        .flatten()
+        .filter(|entry| entry.file_type().is_file())
+        .filter_map(|entry| entry.metadata().ok())
+        .map(|metadata| metadata.len())
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_collect_garbage_dry_run_then_collect() {
+        let temp = TempDir::new().unwrap();
+        let kernel_dir = temp.path().join("concepts/Orders");
+        let storage = kernel_dir.join("storage");
+        for inst in ["tx-1.inst", "tx-2-analysis.inst", "tx-3.inst"] {
+            fs::create_dir_all(storage.join(inst)).unwrap();
+            fs::write(storage.join(inst).join("receipt.json"), "{}").unwrap();
+        }
+        fs::write(
+            kernel_dir.join("tx.jsonl"),
+            "{\"txId\":\"tx-1\",\"timestamp\":\"t\",\"kernel\":\"Orders\"}\n{\"txId\":\"tx-2\"}\n",
+        )
+        .unwrap();
+
+        let edges = kernel_dir.join("queue/edges");
+        fs::create_dir_all(edges.join("PRODUCES.Orders")).unwrap();
+        fs::create_dir_all(edges.join("PRODUCES.Billing")).unwrap();
+        fs::write(edges.join("PRODUCES.Billing/tx-9.job"), "{}").unwrap();
+        #[cfg(unix)]
+        std::os::unix::fs::symlink(storage.join("tx-404.inst"), edges.join("PRODUCES.Orders/tx-404.inst")).unwrap();
+
+        fs::create_dir_all(kernel_dir.join("tool")).unwrap();
+        fs::write(kernel_dir.join(".tool.pid"), format!("{}", u32::MAX - 1)).unwrap();
+        fs::write(kernel_dir.join("tool/.governor.pid"), std::process::id().to_string()).unwrap();
+
+        let options = GcOptions::default().with_min_age(Duration::ZERO);
+        let report = collect_garbage(temp.path(), &options.clone().dry_run()).unwrap();
+        assert_eq!(report.orphaned_instances, vec![storage.join("tx-3.inst")]);
+        assert_eq!(report.reclaimed_bytes, 2);
+        assert_eq!(report.empty_edge_queues, vec![edges.join("PRODUCES.Orders")]);
+        assert_eq!(report.stale_pid_files, vec![kernel_dir.join(".tool.pid")]);
+        #[cfg(unix)]
+        assert_eq!(report.dangling_symlinks, vec![edges.join("PRODUCES.Orders/tx-404.inst")]);
+        assert!(storage.join("tx-3.inst").exists() && kernel_dir.join(".tool.pid").exists());
+
+        let report = collect_garbage(temp.path(), &options).unwrap();
+        assert!(!report.dry_run && report.errors.is_empty(), "{}", report);
+        assert!(!storage.join("tx-3.inst").exists() && storage.join("tx-2-analysis.inst").exists());
+        assert!(!edges.join("PRODUCES.Orders").exists() && edges.join("PRODUCES.Billing").exists());
+        assert!(!kernel_dir.join(".tool.pid").exists() && kernel_dir.join("tool/.governor.pid").exists());
+
+        // Recent entries are left alone
+        fs::create_dir_all(storage.join("tx-5.inst")).unwrap();
+        assert!(collect_garbage(temp.path(), &GcOptions::default()).unwrap().is_clean());
+    }
+}
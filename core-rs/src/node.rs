@@ -0,0 +1,303 @@
+//! Node.js bindings (N-API)
+//!
+//! Exposes the hot paths of this crate to the Node.js runtime as a native
+//! module, so `ck` can call into Rust in-process instead of spawning `ckr`:
+//!
+//! - `KernelManager` - list, create, start, stop and inspect kernels
+//! - `emit()` - write a job to a kernel's inbox (`Kernel::emit`)
+//! - `InstanceScanner` - read instances from a kernel's storage
+//! - `parseUrn()`, `parseEdgeUrn()`, `validateUrn()`, ... - `UrnResolver`
+//!
+//! Build with the `napi` feature as a cdylib and load it from Node:
+//!
+//! ```text
+//! cargo rustc --release --lib --features napi --crate-type cdylib
+//! cp target/release/libckp_core.so ckp_core.node
+//! ```
+//!
+//! ```js
+//! const ckp = require('./ckp_core.node');
+//! const txId = await ckp.emit('/path/to/project', 'Shop.Orders', { order: 42 });
+//! const kernels = new ckp.KernelManager('/path/to/project').listKernels();
+//! ```
+//!
+//! Errors surface as JavaScript exceptions carrying the `CkpError` message.
+//! Structured results are returned as plain objects with the same camelCase
+//! keys the protocol files use.
+
+use crate::errors::CkpError;
+use crate::kernel::{Kernel, KernelManager, StartResult};
+use crate::storage::InstanceScanner;
+use crate::urn::{ParsedEdgeUrn, ParsedUrn, UrnResolver, UrnValidator};
+use napi_derive::napi;
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+fn to_napi(error: CkpError) -> napi::Error {
+    napi::Error::from_reason(error.to_string())
+}
+
+fn to_js<T: Serialize>(value: &T) -> napi::Result<Value> {
+    serde_json::to_value(value).map_err(|e| napi::Error::from_reason(e.to_string()))
+}
+
+// ==================== Kernel Manager ====================
+
+/// Result of `KernelManager.startKernel()`
+#[napi(object, js_name = "StartResult")]
+pub struct JsStartResult {
+    pub pid: Option<u32>,
+    pub watcher_pid: Option<u32>,
+    pub kernel_type: String,
+    pub already_running: bool,
+    pub error: Option<String>,
+    pub log_tail: Vec<String>,
+}
+
+impl From<StartResult> for JsStartResult {
+    fn from(result: StartResult) -> Self {
+        Self {
+            pid: result.pid,
+            watcher_pid: result.watcher_pid,
+            kernel_type: result.kernel_type,
+            already_running: result.already_running,
+            error: result.error,
+            log_tail: result.log_tail,
+        }
+    }
+}
+
+/// Kernel lifecycle management for a project
+#[napi(js_name = "KernelManager")]
+pub struct JsKernelManager {
+    inner: Arc<KernelManager>,
+}
+
+#[napi]
+impl JsKernelManager {
+    /// Manager for the project at `root`
+    #[napi(constructor)]
+    pub fn new(root: String) -> napi::Result<Self> {
+        let inner = KernelManager::new(PathBuf::from(root)).map_err(to_napi)?;
+        Ok(Self { inner: Arc::new(inner) })
+    }
+
+    /// Names of all kernels in the project
+    #[napi]
+    pub fn list_kernels(&self) -> napi::Result<Vec<String>> {
+        self.inner.list_kernels().map_err(to_napi)
+    }
+
+    /// Whether a kernel exists
+    #[napi]
+    pub fn exists(&self, name: String) -> bool {
+        self.inner.exists(&name)
+    }
+
+    /// Create a kernel from a template
+    #[napi]
+    pub fn create_kernel(&self, name: String, template: String, version: String) -> napi::Result<()> {
+        self.inner.create_kernel(&name, &template, &version).map_err(to_napi)
+    }
+
+    /// Delete a kernel, optionally archiving it first
+    ///
+    /// Returns the archive path when archived.
+    #[napi]
+    pub fn delete_kernel(&self, name: String, archive: bool) -> napi::Result<Option<String>> {
+        let archived = self.inner.delete_kernel(&name, archive).map_err(to_napi)?;
+        Ok(archived.map(|path| path.to_string_lossy().into_owned()))
+    }
+
+    /// Status of one kernel
+    #[napi]
+    pub async fn get_kernel_status(&self, name: String) -> napi::Result<Value> {
+        let status = self.inner.get_kernel_status(&name).await.map_err(to_napi)?;
+        to_js(&status)
+    }
+
+    /// Status of every kernel in the project
+    #[napi]
+    pub async fn status(&self) -> napi::Result<Value> {
+        let statuses = self.inner.status().await.map_err(to_napi)?;
+        to_js(&statuses)
+    }
+
+    /// Start a kernel
+    #[napi]
+    pub async fn start_kernel(&self, name: String) -> napi::Result<JsStartResult> {
+        let result = self.inner.start_kernel(&name, &HashMap::new()).await.map_err(to_napi)?;
+        Ok(result.into())
+    }
+
+    /// Stop a kernel, returning whether it was running
+    #[napi]
+    pub async fn stop_kernel(&self, name: String) -> napi::Result<bool> {
+        self.inner.stop_kernel(&name).await.map_err(to_napi)
+    }
+}
+
+// ==================== Emit ====================
+
+/// Emit a job to a kernel's inbox
+///
+/// With a `source` kernel the emit is RBAC-checked against its ontology;
+/// without one the job is recorded as coming from `external`.
+///
+/// Returns the transaction ID.
+#[napi]
+pub async fn emit(root: String, target: String, payload: Value, source: Option<String>) -> napi::Result<String> {
+    let enable_rbac = source.is_some();
+    let mut kernel = Kernel::new(PathBuf::from(root), source, enable_rbac);
+    kernel.emit(&target, payload).await.map_err(to_napi)
+}
+
+// ==================== Instance Scanner ====================
+
+/// Read-only access to a kernel's storage instances
+#[napi(js_name = "InstanceScanner")]
+pub struct JsInstanceScanner {
+    inner: InstanceScanner,
+}
+
+#[napi]
+impl JsInstanceScanner {
+    /// Scanner for the kernel at `kernelRoot`
+    #[napi(constructor)]
+    pub fn new(kernel_root: String, kernel_name: String) -> Self {
+        Self {
+            inner: InstanceScanner::new(PathBuf::from(kernel_root), kernel_name),
+        }
+    }
+
+    /// Instances sorted by name, up to `limit` (all when omitted or 0)
+    #[napi]
+    pub fn list_instances(&self, limit: Option<u32>) -> napi::Result<Value> {
+        let instances = self.inner.list_instances(limit.unwrap_or(0) as usize).map_err(to_napi)?;
+        to_js(&instances)
+    }
+
+    /// Full detail of one instance
+    #[napi]
+    pub fn describe_instance(&self, name: String) -> napi::Result<Value> {
+        let detail = self.inner.describe_instance(&name).map_err(to_napi)?;
+        to_js(&detail)
+    }
+
+    /// Full detail of every instance
+    #[napi]
+    pub fn list_instance_details(&self) -> napi::Result<Value> {
+        let details = self.inner.list_instance_details().map_err(to_napi)?;
+        to_js(&details)
+    }
+
+    /// Number of instances in storage
+    #[napi]
+    pub fn count_instances(&self) -> napi::Result<u32> {
+        let count = self.inner.count_instances().map_err(to_napi)?;
+        Ok(count as u32)
+    }
+}
+
+// ==================== URN Resolver ====================
+
+/// Components of a kernel URN
+#[napi(object, js_name = "ParsedUrn")]
+pub struct JsParsedUrn {
+    pub kernel: String,
+    pub version: String,
+    pub stage: Option<String>,
+    pub path: Option<String>,
+}
+
+impl From<ParsedUrn> for JsParsedUrn {
+    fn from(parsed: ParsedUrn) -> Self {
+        Self {
+            kernel: parsed.kernel,
+            version: parsed.version,
+            stage: parsed.stage,
+            path: parsed.path,
+        }
+    }
+}
+
+/// Components of an edge URN
+#[napi(object, js_name = "ParsedEdgeUrn")]
+pub struct JsParsedEdgeUrn {
+    pub predicate: String,
+    pub source: String,
+    pub target: String,
+    pub version: Option<String>,
+    pub queue_path: String,
+    pub edge_dir: String,
+}
+
+impl From<ParsedEdgeUrn> for JsParsedEdgeUrn {
+    fn from(parsed: ParsedEdgeUrn) -> Self {
+        Self {
+            predicate: parsed.predicate,
+            source: parsed.source,
+            target: parsed.target,
+            version: parsed.version,
+            queue_path: parsed.queue_path,
+            edge_dir: parsed.edge_dir,
+        }
+    }
+}
+
+/// Result of `validateUrn()`
+#[napi(object, js_name = "UrnValidation")]
+pub struct JsUrnValidation {
+    pub valid: bool,
+    pub errors: Vec<String>,
+}
+
+/// Parse a kernel URN (`ckp://Kernel:version#stage/path`)
+#[napi]
+pub fn parse_urn(urn: String) -> napi::Result<JsParsedUrn> {
+    UrnResolver::parse(&urn).map(Into::into).map_err(to_napi)
+}
+
+/// Parse an edge URN (`ckp://Edge.PREDICATE.Source-to-Target:version`)
+#[napi]
+pub fn parse_edge_urn(urn: String) -> napi::Result<JsParsedEdgeUrn> {
+    UrnResolver::parse_edge_urn(&urn).map(Into::into).map_err(to_napi)
+}
+
+/// Whether a URN addresses an edge
+#[napi]
+pub fn is_edge_urn(urn: String) -> bool {
+    UrnResolver::is_edge_urn(&urn)
+}
+
+/// Whether a URN addresses a kernel
+#[napi]
+pub fn is_kernel_urn(urn: String) -> bool {
+    UrnResolver::is_kernel_urn(&urn)
+}
+
+/// Filesystem path a URN resolves to under `conceptsRoot`
+#[napi]
+pub fn resolve_urn_to_path(urn: String, concepts_root: String) -> napi::Result<String> {
+    let path = UrnResolver::resolve_to_path(&urn, Path::new(&concepts_root)).map_err(to_napi)?;
+    Ok(path.to_string_lossy().into_owned())
+}
+
+/// Transaction ID embedded in a URN, if any
+#[napi]
+pub fn extract_tx_id(urn: String) -> Option<String> {
+    UrnResolver::extract_tx_id(&urn)
+}
+
+/// Validate a URN, collecting every problem found
+#[napi]
+pub fn validate_urn(urn: String) -> JsUrnValidation {
+    let result = UrnValidator::validate(&urn);
+    JsUrnValidation {
+        valid: result.valid,
+        errors: result.errors,
+    }
+}
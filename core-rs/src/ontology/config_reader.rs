@@ -1,8 +1,13 @@
 //! Ontology reader for parsing kernel conceptkernel.yaml files
 //!
-//! Provides methods to extract contracts, edges, and metadata from ontologies
+//! Provides methods to extract contracts, edges, and metadata from ontologies.
+//! String values may use `${ENV_VAR}` and `${secret:name}` references, resolved
+//! at read time (see crate::interpolation).
 
+use crate::compliance::RedactionConfig;
 use crate::errors::{CkpError, Result};
+use crate::interpolation::Interpolator;
+use crate::urn::UrnResolver;
 use serde::{Deserialize, Serialize};
 use std::fs;
 use std::path::{Path, PathBuf};
@@ -55,7 +60,7 @@ pub struct Metadata {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub name: Option<String>,
 
-    /// Kernel type: node:hot, node:cold, python:hot, python:cold, node:manual
+    /// Kernel type: {runtime}:{class}, e.g. node:hot, python:warm, rust:cold, node:manual
     #[serde(rename = "type")]
     pub kernel_type: String,
 
@@ -82,6 +87,10 @@ pub struct Metadata {
     /// Tags for categorization
     #[serde(skip_serializing_if = "Option::is_none")]
     pub tags: Option<Vec<String>>,
+
+    /// When the kernel was decommissioned (RFC 3339); archived kernels are not started
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub archived: Option<String>,
 }
 
 impl Metadata {
@@ -100,6 +109,11 @@ impl Metadata {
         }
     }
 
+    /// Temperature class from the kernel type (`node:cold` -> Cold)
+    pub fn kernel_class(&self) -> Option<KernelClass> {
+        KernelClass::from_kernel_type(&self.kernel_type)
+    }
+
     /// Get the kernel name (without version)
     pub fn get_name(&self) -> String {
         if let Some(urn) = &self.urn {
@@ -116,6 +130,40 @@ impl Metadata {
     }
 }
 
+/// Temperature class of a kernel, the part of its type after the runtime
+///
+/// - `hot`: long-running tool process, started eagerly with the project
+/// - `warm`: governor started on the first job routed to the kernel
+/// - `cold`: no resident process until explicitly started
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum KernelClass {
+    Hot,
+    Warm,
+    Cold,
+}
+
+impl KernelClass {
+    /// Class of a kernel type (e.g., "python:warm"); None for other classes (e.g., "node:manual")
+    pub fn from_kernel_type(kernel_type: &str) -> Option<Self> {
+        let class = kernel_type.rsplit(':').next().unwrap_or(kernel_type);
+        match class.trim().to_ascii_lowercase().as_str() {
+            "hot" => Some(KernelClass::Hot),
+            "warm" => Some(KernelClass::Warm),
+            "cold" => Some(KernelClass::Cold),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            KernelClass::Hot => "hot",
+            KernelClass::Warm => "warm",
+            KernelClass::Cold => "cold",
+        }
+    }
+}
+
 /// Specification section
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
 pub struct Spec {
@@ -133,6 +181,32 @@ pub struct Spec {
     pub deploy_contract: Option<DeployContract>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub cli: Option<CliContract>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub retention_contract: Option<RetentionContract>,
+    /// Redaction applied to records before they go into tx.jsonl
+    ///
+    /// ```yaml
+    /// tx_redaction:
+    ///   builtins: false
+    ///   hash: [customer.email]   # replaced by a SHA-256 digest
+    ///   strip: [payload]         # removed
+    ///   fields: [cardHolder]     # replaced by [REDACTED]
+    /// ```
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tx_redaction: Option<RedactionConfig>,
+    /// Edge payloads sealed to this kernel's public key in transit
+    ///
+    /// Sealing is for confidentiality only: `sources` chooses which senders
+    /// seal, not who may send, and the sender named in an envelope is not
+    /// authenticated.
+    ///
+    /// ```yaml
+    /// payload_encryption:
+    ///   public_key: 8f2c...e41a        # X25519, hex (`ckp edge keygen`)
+    ///   sources: [ckp://Orders.Intake] # sealed edges (default: all inbound)
+    /// ```
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub payload_encryption: Option<PayloadEncryption>,
 }
 
 /// CLI contract for dynamic command registration
@@ -180,6 +254,42 @@ pub struct QueueContract {
     pub edges: Option<Vec<EdgeEntry>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub manifest: Option<Vec<serde_json::Value>>,
+    /// Processing order: fifo (default), lifo, round-robin or priority
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub processing: Option<String>,
+    /// Depth alarms on the kernel's edge queues
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub alarms: Option<Vec<EdgeAlarm>>,
+    /// Payload schemas the inbox accepts; jobs tagged with another are quarantined
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub schemas: Option<Vec<String>>,
+}
+
+/// Depth alarm on an edge queue, raised and recovered by QueueAlarmDaemon
+///
+/// ```yaml
+/// queue_contract:
+///   alarms:
+///     - edge: PRODUCES.Orders.Intake   # queue/edges/{edge}
+///       threshold: 500                 # raised at this depth
+///       recover_below: 400             # recovered under this depth (default: threshold)
+///       target: Ops.Pager              # alarm jobs go here (default: System.Audit log)
+/// ```
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+pub struct EdgeAlarm {
+    pub edge: String,
+    pub threshold: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub recover_below: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub target: Option<String>,
+}
+
+impl EdgeAlarm {
+    /// Depth under which a raised alarm recovers (never above the threshold)
+    pub fn recovery_depth(&self) -> u64 {
+        self.recover_below.unwrap_or(self.threshold).min(self.threshold)
+    }
 }
 
 /// Edge entry - can be string or object
@@ -206,6 +316,23 @@ pub struct StorageContract {
     pub result: Option<serde_json::Value>,
 }
 
+/// Retention contract, overriding the project retention policy for this kernel
+///
+/// ```yaml
+/// retention_contract:
+///   days: 30
+///   archive_path: orders     # under the policy archive root, or absolute
+///   exceptions: [pinned-]    # entry name prefixes kept indefinitely
+/// ```
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+pub struct RetentionContract {
+    pub days: i64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub archive_path: Option<String>,
+    #[serde(default)]
+    pub exceptions: Vec<String>,
+}
+
 /// Notification contract entry
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
 pub struct NotificationContract {
@@ -235,11 +362,36 @@ pub struct Communication {
     pub allowed: Option<Vec<String>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub denied: Option<Vec<String>>,
+    /// Projects this kernel may emit into (`*` for any registered project)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub projects: Option<Vec<String>>,
+}
+
+/// Payload encryption declared by an edge target
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+pub struct PayloadEncryption {
+    pub public_key: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sources: Option<Vec<String>>,
+}
+
+impl PayloadEncryption {
+    /// Whether payloads from `source_kernel` must be sealed (checked by the sender)
+    pub fn covers(&self, source_kernel: &str) -> bool {
+        self.sources.as_ref().is_none_or(|sources| {
+            sources
+                .iter()
+                .any(|source| UrnResolver::normalize_kernel_name(source) == source_kernel)
+        })
+    }
 }
 
 /// Ontology reader
 pub struct OntologyReader {
     root: PathBuf,
+
+    /// Resolves `${...}` references (process-wide interpolator when unset)
+    interpolator: Option<Interpolator>,
 }
 
 impl OntologyReader {
@@ -254,7 +406,13 @@ impl OntologyReader {
     /// let reader = OntologyReader::new(PathBuf::from("/concepts"));
     /// ```
     pub fn new(root: PathBuf) -> Self {
-        Self { root }
+        Self { root, interpolator: None }
+    }
+
+    /// Resolve `${ENV_VAR}` and `${secret:name}` references with a specific interpolator
+    pub fn with_interpolator(mut self, interpolator: Interpolator) -> Self {
+        self.interpolator = Some(interpolator);
+        self
     }
 
     /// Read and parse conceptkernel.yaml file
@@ -270,6 +428,21 @@ impl OntologyReader {
     /// println!("Kernel: {}", ontology.metadata.name);
     /// ```
     pub fn read(&self, ontology_path: &Path) -> Result<Ontology> {
+        match &self.interpolator {
+            Some(interpolator) => Self::read_inner(ontology_path, Some(interpolator)),
+            None => Self::read_inner(ontology_path, Some(&Interpolator::current())),
+        }
+    }
+
+    /// Read conceptkernel.yaml as written, leaving `${...}` references in place
+    ///
+    /// Use this when the ontology is written back, so resolved secrets never
+    /// end up in the file.
+    pub fn read_raw(&self, ontology_path: &Path) -> Result<Ontology> {
+        Self::read_inner(ontology_path, None)
+    }
+
+    fn read_inner(ontology_path: &Path, interpolator: Option<&Interpolator>) -> Result<Ontology> {
         if !ontology_path.exists() {
             return Err(CkpError::Ontology(format!(
                 "Ontology file not found: {}",
@@ -281,8 +454,13 @@ impl OntologyReader {
             CkpError::Ontology(format!("Failed to read ontology file: {}", e))
         })?;
 
-        let ontology: Ontology = serde_yaml::from_str(&content).map_err(|e| {
-            CkpError::Ontology(format!("Failed to parse ontology YAML: {}", e))
+        let parsed = match interpolator {
+            Some(interpolator) => interpolator.parse_yaml(&content),
+            None => serde_yaml::from_str(&content).map_err(CkpError::from),
+        };
+        let ontology: Ontology = parsed.map_err(|e| match e {
+            CkpError::Yaml(e) => CkpError::Ontology(format!("Failed to parse ontology YAML: {}", e)),
+            other => other,
         })?;
 
         // Validate required fields - must have either urn or name
@@ -553,10 +731,7 @@ impl OntologyReader {
                     });
                 }
                 Err(e) => {
-                    eprintln!(
-                        "[OntologyReader] Failed to read ontology for {}: {}",
-                        kernel_name, e
-                    );
+                    tracing::warn!(kernel = %kernel_name, error = %e, "failed to read ontology");
                 }
             }
         }
@@ -570,6 +745,12 @@ impl OntologyReader {
         Ok(ontology.spec.and_then(|s| s.queue_contract))
     }
 
+    /// Get payload encryption from ontology
+    pub fn read_payload_encryption(&self, kernel_name: &str) -> Result<Option<PayloadEncryption>> {
+        let ontology = self.read_by_kernel_name(kernel_name)?;
+        Ok(ontology.spec.and_then(|s| s.payload_encryption))
+    }
+
     /// Get storage contract from ontology
     pub fn read_storage_contract(&self, kernel_name: &str) -> Result<Option<StorageContract>> {
         let ontology = self.read_by_kernel_name(kernel_name)?;
@@ -635,6 +816,50 @@ capabilities:
         assert_eq!(ontology.capabilities, vec!["baking"]);
     }
 
+    #[test]
+    fn test_read_interpolates_references() {
+        use crate::interpolation::SecretProvider;
+        use std::sync::Arc;
+
+        struct Secrets;
+        impl SecretProvider for Secrets {
+            fn get_secret(&self, name: &str) -> Result<Option<String>> {
+                Ok((name == "webhook").then(|| "s3cr3t".to_string()))
+            }
+        }
+
+        let temp_dir = TempDir::new().unwrap();
+        let ontology_yaml = r#"
+apiVersion: conceptkernel/v1
+kind: Ontology
+metadata:
+  name: ckp://Notify.Webhook:v0.1
+  type: node:cold
+  description: "Posts to ${CKP_TEST_ONTOLOGY_ENDPOINT:-http://localhost} with ${secret:webhook}"
+        "#;
+        create_test_ontology(&temp_dir, "Notify.Webhook", ontology_yaml);
+        let path = temp_dir.path().join("concepts/Notify.Webhook/conceptkernel.yaml");
+
+        let reader = OntologyReader::new(temp_dir.path().to_path_buf())
+            .with_interpolator(Interpolator::new().with_provider(Arc::new(Secrets)));
+        let ontology = reader.read(&path).unwrap();
+        assert_eq!(
+            ontology.metadata.description,
+            Some("Posts to http://localhost with s3cr3t".to_string())
+        );
+
+        // Raw reads keep the references for writing back
+        let raw = reader.read_raw(&path).unwrap();
+        assert_eq!(
+            raw.metadata.description,
+            Some("Posts to ${CKP_TEST_ONTOLOGY_ENDPOINT:-http://localhost} with ${secret:webhook}".to_string())
+        );
+
+        // Unresolvable secrets fail the read
+        let strict = OntologyReader::new(temp_dir.path().to_path_buf()).with_interpolator(Interpolator::new());
+        assert!(matches!(strict.read(&path), Err(CkpError::Interpolation(_))));
+    }
+
     #[test]
     fn test_read_edges() {
         let temp_dir = TempDir::new().unwrap();
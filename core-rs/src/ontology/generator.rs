@@ -84,12 +84,11 @@ impl OntologyGenerator {
         fs::write(&ontology_path, ontology_content)
             .map_err(|e| CkpError::IoError(format!("Failed to write ontology.ttl: {}", e)))?;
 
-        println!("✓ Generated ontology.ttl for {}", new_name);
-        if let Some(src) = source_name {
-            if source_metadata.is_some() {
-                println!("  └─ Inherited semantic metadata from {}", src);
-            }
-        }
+        tracing::info!(
+            kernel = %new_name,
+            inherited_from = source_name.filter(|_| source_metadata.is_some()),
+            "generated ontology.ttl"
+        );
 
         Ok(ontology_path)
     }
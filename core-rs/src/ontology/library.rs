@@ -4,15 +4,17 @@
  */
 
 use oxigraph::store::Store;
-use oxigraph::model::NamedNode;
-use oxigraph::io::RdfFormat;
-use std::collections::HashMap;
+use oxigraph::model::{NamedNode, Quad};
+use oxigraph::io::{RdfFormat, RdfParser};
+use std::collections::{HashMap, HashSet};
 use std::fs;
 use std::path::{Path, PathBuf};
 use thiserror::Error;
 
 use crate::urn::UrnResolver;
 use crate::project::ProjectConfig;
+use super::config_reader::KernelClass;
+use super::manifest::OntologyManifest;
 
 #[derive(Error, Debug)]
 pub enum OntologyError {
@@ -69,10 +71,21 @@ pub struct KernelMetadata {
     pub version: String,
 }
 
+impl KernelMetadata {
+    /// Temperature class from the kernel type
+    pub fn class(&self) -> Option<KernelClass> {
+        KernelClass::from_kernel_type(&self.kernel_type)
+    }
+}
+
 pub struct OntologyLibrary {
     store: Store,
     pub project_root: PathBuf,
     kernel_graphs: HashMap<String, String>,
+    /// Quads loaded from each kernel's ontology.ttl (removed on reload)
+    kernel_quads: HashMap<String, Vec<Quad>>,
+    /// Manifest digest of each kernel's ontology sources when loaded
+    kernel_digests: HashMap<String, String>,
 }
 
 impl OntologyLibrary {
@@ -85,6 +98,8 @@ impl OntologyLibrary {
             store,
             project_root: project_root.clone(),
             kernel_graphs: HashMap::new(),
+            kernel_quads: HashMap::new(),
+            kernel_digests: HashMap::new(),
         };
         
         // Load core ontologies from .ckproject
@@ -98,8 +113,10 @@ impl OntologyLibrary {
         let ckproject_path = project_root.join(".ckproject");
 
         if !ckproject_path.exists() {
-            eprintln!("[OntologyLibrary] ⚠️  WARNING: .ckproject not found at {}", ckproject_path.display());
-            eprintln!("[OntologyLibrary]    Canonical ontologies will not be loaded");
+            tracing::warn!(
+                path = %ckproject_path.display(),
+                ".ckproject not found, canonical ontologies will not be loaded"
+            );
             return Ok(()); // Optional
         }
 
@@ -107,51 +124,51 @@ impl OntologyLibrary {
             .map_err(|e| OntologyError::LoadError(e.to_string()))?;
 
         if let Some(ontology_config) = config.spec.ontology {
-            eprintln!("[OntologyLibrary] Loading canonical ontologies from .ckproject...");
+            tracing::debug!("loading canonical ontologies from .ckproject");
 
             // Load core ontologies - support both file:// and URN formats
             if let Err(e) = self.load_ontology_reference(&ontology_config.core, "https://conceptkernel.org/ontology/core") {
-                eprintln!("[OntologyLibrary] ⚠️  WARNING: Failed to load core ontology: {}", e);
+                tracing::warn!(ontology = "core", error = %e, "failed to load canonical ontology");
             }
 
             if let Err(e) = self.load_ontology_reference(&ontology_config.bfo, "http://purl.obolibrary.org/obo/bfo.owl") {
-                eprintln!("[OntologyLibrary] ⚠️  WARNING: Failed to load BFO ontology: {}", e);
+                tracing::warn!(ontology = "bfo", error = %e, "failed to load canonical ontology");
             }
 
             if let Err(e) = self.load_ontology_reference(&ontology_config.predicates, "https://conceptkernel.org/ontology/predicates") {
-                eprintln!("[OntologyLibrary] ⚠️  WARNING: Failed to load predicates ontology: {}", e);
+                tracing::warn!(ontology = "predicates", error = %e, "failed to load canonical ontology");
             }
 
             // Load optional ontologies
             if let Some(processes) = ontology_config.processes.as_ref() {
                 if let Err(e) = self.load_ontology_reference(processes, "https://conceptkernel.org/ontology/processes") {
-                    eprintln!("[OntologyLibrary] ⚠️  WARNING: Failed to load processes ontology: {}", e);
+                    tracing::warn!(ontology = "processes", error = %e, "failed to load canonical ontology");
                 }
             }
 
             if let Some(rbac) = ontology_config.rbac.as_ref() {
                 if let Err(e) = self.load_ontology_reference(rbac, "https://conceptkernel.org/ontology/rbac") {
-                    eprintln!("[OntologyLibrary] ⚠️  WARNING: Failed to load RBAC ontology: {}", e);
+                    tracing::warn!(ontology = "rbac", error = %e, "failed to load canonical ontology");
                 }
             }
 
             if let Some(improvement) = ontology_config.improvement.as_ref() {
                 if let Err(e) = self.load_ontology_reference(improvement, "https://conceptkernel.org/ontology/improvement") {
-                    eprintln!("[OntologyLibrary] ⚠️  WARNING: Failed to load self-improvement ontology: {}", e);
+                    tracing::warn!(ontology = "self-improvement", error = %e, "failed to load canonical ontology");
                 }
             }
 
             if let Some(workflow) = ontology_config.workflow.as_ref() {
                 if let Err(e) = self.load_ontology_reference(workflow, "https://conceptkernel.org/ontology/workflow") {
-                    eprintln!("[OntologyLibrary] ⚠️  WARNING: Failed to load workflow ontology: {}", e);
+                    tracing::warn!(ontology = "workflow", error = %e, "failed to load canonical ontology");
                 }
             }
 
-            eprintln!("[OntologyLibrary] ✓ Canonical ontologies loaded from .ckproject");
+            tracing::info!("canonical ontologies loaded from .ckproject");
         } else {
-            eprintln!("[OntologyLibrary] ⚠️  WARNING: No spec.ontology section in .ckproject");
-            eprintln!("[OntologyLibrary]    Canonical ontologies from /concepts/.ontology/ will not be loaded");
-            eprintln!("[OntologyLibrary]    Add spec.ontology section referencing canonical ontology files");
+            tracing::warn!(
+                "no spec.ontology section in .ckproject, canonical ontologies will not be loaded"
+            );
         }
 
         Ok(())
@@ -181,7 +198,7 @@ impl OntologyLibrary {
             self.load_ontology_urn(reference, graph_uri)
         } else if reference.starts_with("http://") || reference.starts_with("https://") {
             // HTTP reference - for BFO, just note it (don't try to fetch)
-            eprintln!("[OntologyLibrary]    Skipping remote ontology: {}", reference);
+            tracing::debug!(reference, "skipping remote ontology");
             Ok(())
         } else {
             Err(OntologyError::LoadError(format!(
@@ -196,17 +213,17 @@ impl OntologyLibrary {
     /// URN: ckp://ConceptKernel.Ontology:v1.3.16#storage/ck-predicates
     /// Resolves to: concepts/ConceptKernel.Ontology/storage/ck-predicates.v1.3.16.ttl
     fn load_ontology_urn(&mut self, urn: &str, graph_uri: &str) -> Result<(), OntologyError> {
-        eprintln!("[OntologyLibrary] Resolving URN: {}", urn);
-
         // Parse URN (format: ckp://kernel:version#stage/path)
         let parsed = UrnResolver::parse(urn)
             .map_err(|e| OntologyError::UrnError(e.to_string()))?;
 
-        eprintln!("[OntologyLibrary] Parsed - kernel: {}, stage: {}, path: {:?}, version: {}",
-            parsed.kernel,
-            parsed.stage.as_ref().unwrap_or(&"none".to_string()),
-            parsed.path,
-            parsed.version
+        tracing::debug!(
+            urn,
+            kernel = %parsed.kernel,
+            stage = parsed.stage.as_deref(),
+            path = parsed.path.as_deref(),
+            version = %parsed.version,
+            "resolving ontology URN"
         );
 
         // Build filesystem path from parsed components
@@ -226,7 +243,7 @@ impl OntologyLibrary {
             let filename = format!("{}.v{}.ttl", path_str, version);
             let full_path = parent.join(filename);
 
-            eprintln!("[OntologyLibrary] Resolved path: {:?}", full_path);
+            tracing::debug!(path = %full_path.display(), "resolved ontology path");
 
             if !full_path.exists() {
                 return Err(OntologyError::NotFound(format!(
@@ -268,9 +285,82 @@ impl OntologyLibrary {
             kernel_name.to_lowercase().replace(".", "-")
         );
 
-        self.load_ontology_file(&ontology_path, &graph_uri)?;
+        let content = fs::read_to_string(&ontology_path)?;
+        let quads = RdfParser::from_format(RdfFormat::Turtle)
+            .for_reader(content.as_bytes())
+            .collect::<Result<Vec<Quad>, _>>()
+            .map_err(|e| OntologyError::ParseError(format!("{}: {}", ontology_path.display(), e)))?;
+
+        // Reloading replaces what the previous version of the file added
+        self.unload_kernel_quads(kernel_name)?;
+        self.insert_quads(quads.iter().cloned())?;
+        self.kernel_quads.insert(kernel_name.to_string(), quads);
         self.kernel_graphs.insert(kernel_name.to_string(), graph_uri);
 
+        let kernel_dir = self.project_root.join("concepts").join(kernel_name);
+        match OntologyManifest::refresh(&kernel_dir) {
+            Ok(manifest) => {
+                self.kernel_digests.insert(kernel_name.to_string(), manifest.digest);
+            }
+            Err(e) => tracing::warn!(kernel = kernel_name, error = %e, "cannot record ontology digest"),
+        }
+
+        Ok(())
+    }
+
+    /// Loaded kernels whose ontology sources changed on disk since they were loaded
+    ///
+    /// Compares each kernel's digest manifest (see `OntologyManifest`),
+    /// refreshed from the files, with the digest recorded at load time.
+    /// The manifest is shared with the Node.js runtime, so a change written
+    /// through either runtime is detected.
+    pub fn stale_kernels(&self) -> Result<Vec<String>, OntologyError> {
+        let mut stale = Vec::new();
+        for kernel_name in self.kernel_graphs.keys() {
+            let kernel_dir = self.project_root.join("concepts").join(kernel_name);
+            let manifest = OntologyManifest::refresh(&kernel_dir)
+                .map_err(|e| OntologyError::LoadError(format!("{}: {}", kernel_name, e)))?;
+            if self.kernel_digests.get(kernel_name) != Some(&manifest.digest) {
+                stale.push(kernel_name.clone());
+            }
+        }
+        stale.sort();
+        Ok(stale)
+    }
+
+    /// Reload only the kernels whose ontology changed on disk
+    ///
+    /// Kernels whose ontology.ttl is gone are unloaded.
+    ///
+    /// # Returns
+    /// Names of the kernels reloaded or unloaded
+    pub fn sync(&mut self) -> Result<Vec<String>, OntologyError> {
+        let stale = self.stale_kernels()?;
+        for kernel_name in &stale {
+            let ontology_path = self.project_root.join("concepts").join(kernel_name).join("ontology.ttl");
+            if ontology_path.exists() {
+                self.load_kernel_ontology(kernel_name)?;
+            } else {
+                self.unload_kernel_quads(kernel_name)?;
+                self.kernel_graphs.remove(kernel_name);
+                self.kernel_digests.remove(kernel_name);
+            }
+            tracing::info!(kernel = %kernel_name, "synced ontology changed on disk");
+        }
+        Ok(stale)
+    }
+
+    /// Remove the quads a kernel's ontology added, except those another kernel also holds
+    fn unload_kernel_quads(&mut self, kernel_name: &str) -> Result<(), OntologyError> {
+        let Some(quads) = self.kernel_quads.remove(kernel_name) else {
+            return Ok(());
+        };
+        let shared: HashSet<&Quad> = self.kernel_quads.values().flatten().collect();
+        for quad in quads.iter().filter(|quad| !shared.contains(quad)) {
+            self.store
+                .remove(quad)
+                .map_err(|e| OntologyError::StoreError(e.to_string()))?;
+        }
         Ok(())
     }
 
@@ -314,22 +404,19 @@ impl OntologyLibrary {
 
         // Emit warnings
         if !warnings.is_empty() {
-            eprintln!("[OntologyLibrary] ⚠️  WARNING: Kernel '{}' ontology validation issues:", kernel_name);
-            for warning in &warnings {
-                eprintln!("[OntologyLibrary]    • {}", warning);
-            }
-            eprintln!("[OntologyLibrary]    Location: {}", ontology_path.display());
-            eprintln!("[OntologyLibrary]    Each kernel ontology.ttl should import canonical ontologies:");
-            eprintln!("[OntologyLibrary]      owl:imports <https://conceptkernel.org/ontology/core> ;");
-            eprintln!("[OntologyLibrary]      owl:imports <http://purl.obolibrary.org/obo/bfo.owl> ;");
+            tracing::warn!(
+                kernel = kernel_name,
+                issues = ?warnings,
+                location = %ontology_path.display(),
+                "kernel ontology validation issues; each kernel ontology.ttl should import the canonical core and BFO ontologies"
+            );
         }
 
         Ok(())
     }
     
     fn load_ontology_file(&mut self, path: &Path, graph_uri: &str) -> Result<(), OntologyError> {
-        eprintln!("[OntologyLibrary] Loading ontology file: {:?}", path);
-        eprintln!("[OntologyLibrary] Graph URI: {}", graph_uri);
+        tracing::debug!(graph = graph_uri, path = %path.display(), "loading ontology file");
 
         if !path.exists() {
             return Err(OntologyError::NotFound(format!("File not found: {:?}", path)));
@@ -710,6 +797,31 @@ impl OntologyLibrary {
         }
     }
     
+    /// Insert quads into the store
+    ///
+    /// Used by runtime exporters (e.g. `ProcessTracker::export_rdf`) to make
+    /// occurrent data queryable alongside the loaded ontologies.
+    ///
+    /// # Returns
+    /// Number of quads that were newly inserted
+    pub fn insert_quads<I>(&self, quads: I) -> Result<usize, OntologyError>
+    where
+        I: IntoIterator<Item = Quad>,
+    {
+        let mut inserted = 0;
+
+        for quad in quads {
+            if self.store
+                .insert(&quad)
+                .map_err(|e| OntologyError::StoreError(e.to_string()))?
+            {
+                inserted += 1;
+            }
+        }
+
+        Ok(inserted)
+    }
+
     /// Check if class is BFO Occurrent (temporal entity)
     pub fn is_temporal_entity(&self, class_uri: &str) -> Result<bool, OntologyError> {
         let query = format!(
@@ -1522,4 +1634,40 @@ mod tests {
         let lib = OntologyLibrary::new(temp_dir.path().to_path_buf());
         assert!(lib.is_ok());
     }
+
+    #[test]
+    fn test_sync_reloads_only_changed_kernels() {
+        let temp_dir = TempDir::new().unwrap();
+        let kernel_ttl = |name: &str, label: &str| format!(
+            "@prefix ckp: <https://conceptkernel.org/ontology#> .\n@prefix rdfs: <http://www.w3.org/2000/01/rdf-schema#> .\n<https://conceptkernel.org/kernel/{}> rdfs:label \"{}\" .\n",
+            name, label
+        );
+        for name in ["Orders.Intake", "Orders.Billing"] {
+            let kernel_dir = temp_dir.path().join("concepts").join(name);
+            fs::create_dir_all(&kernel_dir).unwrap();
+            fs::write(kernel_dir.join("ontology.ttl"), kernel_ttl(name, "v1")).unwrap();
+        }
+
+        let mut lib = OntologyLibrary::new(temp_dir.path().to_path_buf()).unwrap();
+        lib.load_kernel_ontology("Orders.Intake").unwrap();
+        lib.load_kernel_ontology("Orders.Billing").unwrap();
+        assert!(lib.stale_kernels().unwrap().is_empty());
+
+        // Another runtime rewrites one kernel's ontology
+        let intake = temp_dir.path().join("concepts/Orders.Intake/ontology.ttl");
+        fs::write(&intake, kernel_ttl("Orders.Intake", "v2 (edited)")).unwrap();
+        assert_eq!(lib.sync().unwrap(), vec!["Orders.Intake".to_string()]);
+
+        let labels = |lib: &OntologyLibrary| {
+            let rows = lib.query_sparql("SELECT ?label WHERE { <https://conceptkernel.org/kernel/Orders.Intake> ?p ?label }").unwrap();
+            rows.into_iter().map(|row| row["label"].clone()).collect::<Vec<_>>()
+        };
+        assert_eq!(labels(&lib), vec!["\"v2 (edited)\"".to_string()]);
+        assert!(lib.sync().unwrap().is_empty());
+
+        fs::remove_file(&intake).unwrap();
+        assert_eq!(lib.sync().unwrap(), vec!["Orders.Intake".to_string()]);
+        assert!(labels(&lib).is_empty());
+        assert_eq!(lib.loaded_kernels(), vec!["Orders.Billing".to_string()]);
+    }
 }
@@ -0,0 +1,172 @@
+/**
+ * manifest.rs
+ * Per-kernel digest manifests of ontology source files
+ *
+ * The Node.js and Rust runtimes both derive RDF stores from the same kernel
+ * ontology files. Each kernel directory holds a manifest (.ontology-digest.json)
+ * with the SHA-256 of every ontology source file, and the size and
+ * modification time it was hashed at. A runtime refreshes the manifest,
+ * re-hashing only files whose size or mtime changed, and compares the
+ * kernel's combined digest with the one its store was loaded from: kernels
+ * whose digest differs are stale and are the only ones reloaded.
+ */
+
+use crate::errors::{CkpError, Result};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+use std::time::UNIX_EPOCH;
+
+/// Manifest file name (in the kernel directory)
+pub const MANIFEST_FILE: &str = ".ontology-digest.json";
+
+/// Files of a kernel directory that ontology stores are derived from
+pub const ONTOLOGY_SOURCES: &[&str] = &["ontology.ttl", "conceptkernel.yaml"];
+
+/// Runtime recorded as the manifest's last writer
+const RUNTIME: &str = "rust";
+
+/// Digest of one ontology source file
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FileDigest {
+    pub sha256: String,
+    pub size: u64,
+    pub modified_ms: u128,
+}
+
+/// Digests of a kernel's ontology source files
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OntologyManifest {
+    pub kernel: String,
+
+    /// Combined digest of every source file (changes when any of them does)
+    pub digest: String,
+
+    /// Source file name -> digest (missing files are absent)
+    pub files: BTreeMap<String, FileDigest>,
+
+    /// When the digests last changed (RFC 3339)
+    pub updated_at: String,
+
+    /// Runtime that last updated the manifest ("rust", "node")
+    pub updated_by: String,
+}
+
+impl OntologyManifest {
+    /// Manifest as last written, if the kernel has one
+    pub fn read(kernel_dir: &Path) -> Result<Option<Self>> {
+        let path = kernel_dir.join(MANIFEST_FILE);
+        match fs::read_to_string(&path) {
+            Ok(content) => Ok(Some(serde_json::from_str(&content).map_err(|e| {
+                CkpError::ParseError(format!("Failed to parse {}: {}", path.display(), e))
+            })?)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Bring the manifest up to date with the files on disk
+    ///
+    /// Files whose size and mtime match the manifest keep their digest; the
+    /// manifest is rewritten only if a digest changed. An unreadable
+    /// manifest is rebuilt.
+    pub fn refresh(kernel_dir: &Path) -> Result<Self> {
+        let previous = Self::read(kernel_dir).unwrap_or_else(|e| {
+            tracing::warn!(kernel_dir = %kernel_dir.display(), error = %e, "rebuilding unreadable ontology manifest");
+            None
+        });
+        let previous_files = previous.as_ref().map(|manifest| &manifest.files);
+
+        let mut files = BTreeMap::new();
+        for source in ONTOLOGY_SOURCES {
+            let path = kernel_dir.join(source);
+            let metadata = match fs::metadata(&path) {
+                Ok(metadata) if metadata.is_file() => metadata,
+                Ok(_) => continue,
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => continue,
+                Err(e) => return Err(e.into()),
+            };
+            let size = metadata.len();
+            let modified_ms = metadata
+                .modified()
+                .ok()
+                .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+                .map(|elapsed| elapsed.as_millis())
+                .unwrap_or(0);
+
+            let unchanged = previous_files
+                .and_then(|files| files.get(*source))
+                .filter(|digest| digest.size == size && digest.modified_ms == modified_ms && modified_ms != 0);
+            let digest = match unchanged {
+                Some(digest) => digest.clone(),
+                None => FileDigest {
+                    sha256: hex::encode(Sha256::digest(fs::read(&path)?)),
+                    size,
+                    modified_ms,
+                },
+            };
+            files.insert(source.to_string(), digest);
+        }
+
+        if let Some(previous) = previous.filter(|previous| previous.files == files) {
+            return Ok(previous);
+        }
+
+        let kernel = kernel_dir.file_name().and_then(|name| name.to_str()).unwrap_or_default().to_string();
+        let manifest = Self {
+            kernel,
+            digest: combined_digest(&files),
+            files,
+            updated_at: chrono::Utc::now().to_rfc3339(),
+            updated_by: RUNTIME.to_string(),
+        };
+
+        // Written aside and renamed: the other runtime may be reading it
+        let path = kernel_dir.join(MANIFEST_FILE);
+        let tmp = kernel_dir.join(format!("{}.tmp", MANIFEST_FILE));
+        fs::write(&tmp, serde_json::to_string_pretty(&manifest)?)?;
+        fs::rename(&tmp, &path)?;
+        Ok(manifest)
+    }
+}
+
+/// SHA-256 over `{file}:{sha256}` lines of every source file, in name order
+fn combined_digest(files: &BTreeMap<String, FileDigest>) -> String {
+    let mut hasher = Sha256::new();
+    for (name, digest) in files {
+        hasher.update(format!("{}:{}\n", name, digest.sha256));
+    }
+    hex::encode(hasher.finalize())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_refresh_tracks_source_changes() {
+        let temp = TempDir::new().unwrap();
+        let kernel_dir = temp.path().join("Orders.Intake");
+        fs::create_dir_all(&kernel_dir).unwrap();
+        fs::write(kernel_dir.join("conceptkernel.yaml"), "kind: Ontology\n").unwrap();
+
+        let first = OntologyManifest::refresh(&kernel_dir).unwrap();
+        assert_eq!(first.kernel, "Orders.Intake");
+        assert_eq!(first.files.keys().collect::<Vec<_>>(), vec!["conceptkernel.yaml"]);
+        assert_eq!(OntologyManifest::read(&kernel_dir).unwrap(), Some(first.clone()));
+
+        // Unchanged files keep the manifest as written
+        assert_eq!(OntologyManifest::refresh(&kernel_dir).unwrap(), first);
+
+        fs::write(kernel_dir.join("ontology.ttl"), "@prefix ckp: <https://conceptkernel.org/ontology#> .\n").unwrap();
+        let second = OntologyManifest::refresh(&kernel_dir).unwrap();
+        assert_ne!(second.digest, first.digest);
+        assert_eq!(second.files["conceptkernel.yaml"], first.files["conceptkernel.yaml"]);
+        assert_eq!(second.updated_by, "rust");
+    }
+}
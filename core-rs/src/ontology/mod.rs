@@ -3,6 +3,7 @@
  *
  * - config_reader: Parses YAML kernel config (conceptkernel.yaml/conceptkernel.yaml)
  * - library: RDF ontology library with Oxigraph (Phase 4 Stage 0)
+ * - manifest: Per-kernel digest manifests for detecting stale stores
  * - query: SPARQL query builders
  * - bfo: BFO 2020 type system with compile-time ontological alignment
  * - generator: Automatic ontology.ttl generation for forked/created kernels
@@ -13,18 +14,22 @@ pub mod config_reader;
 pub mod generator;
 pub mod improvement;
 pub mod library;
+pub mod manifest;
 pub mod query;
 
 // BFO 2020 type system
 pub use bfo::{BfoEntityType, BfoAligned};
 
 // YAML config parser (reads conceptkernel.yaml/conceptkernel.yaml)
-pub use config_reader::{OntologyReader, Ontology};
+pub use config_reader::{EdgeAlarm, KernelClass, OntologyReader, Ontology, PayloadEncryption, RetentionContract};
 
 // RDF ontology library (loads ontology.ttl files with Oxigraph)
 pub use library::{OntologyLibrary, OntologyError, RoleMetadata, FunctionMetadata, KernelMetadata};
 pub use query::{QueryResult, SparqlQuery};
 
+// Per-kernel digest manifests shared with the Node.js runtime
+pub use manifest::{FileDigest, OntologyManifest, MANIFEST_FILE, ONTOLOGY_SOURCES};
+
 // Ontology generator (automatic ontology.ttl generation)
 pub use generator::OntologyGenerator;
 
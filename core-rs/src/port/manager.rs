@@ -21,8 +21,9 @@
  * Reference: Node.js v1.3.14 - PortManager.js
  */
 
+use chrono::{DateTime, Duration, Utc};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::fs;
 use std::net::TcpListener;
 use std::path::{Path, PathBuf};
@@ -35,6 +36,33 @@ use crate::errors::CkpError;
 pub struct PortMap {
     pub base_port: Option<u16>,
     pub allocations: HashMap<String, u16>,
+    /// Named service reservations (e.g. "gateway-http")
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub reservations: HashMap<String, PortReservation>,
+}
+
+/// Port reserved for a named service
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PortReservation {
+    pub port: u16,
+
+    /// Kernel owning the reservation; it is released with the kernel
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub owner: Option<String>,
+
+    pub leased_at: DateTime<Utc>,
+
+    /// End of the lease; without one the reservation is held until released
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+impl PortReservation {
+    /// Whether the lease has run out
+    pub fn is_expired(&self) -> bool {
+        self.expires_at.is_some_and(|expires| expires <= Utc::now())
+    }
 }
 
 /// Port range for a project
@@ -51,6 +79,50 @@ impl PortRange {
     }
 }
 
+/// Difference between the recorded port allocations and the live system
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum PortDrift {
+    /// Base port in .ckports differs from the project registry
+    BasePort { recorded: Option<u16>, expected: u16 },
+    /// Allocation lies outside the project's port range
+    OutOfRange { kernel: String, port: u16 },
+    /// Port is allocated to more than one kernel
+    Duplicate { kernel: String, port: u16, holder: String },
+    /// Port of a stopped kernel is bound by another process
+    Occupied { kernel: String, port: u16 },
+    /// Unallocated port in the range is bound by an external service
+    External { port: u16 },
+}
+
+/// Kernel moved to a new port during reconciliation
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PortReassignment {
+    pub kernel: String,
+    pub from: u16,
+    pub to: u16,
+}
+
+/// Result of `PortManager::reconcile`
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PortReconcileReport {
+    /// Every drift found, in allocation order
+    pub drift: Vec<PortDrift>,
+
+    /// Allocations moved to a free port (repair mode only)
+    pub reassigned: Vec<PortReassignment>,
+
+    /// Kernels that could not be given a free port and keep their old one
+    pub unresolved: Vec<String>,
+}
+
+impl PortReconcileReport {
+    /// True when the allocations match reality
+    pub fn is_clean(&self) -> bool {
+        self.drift.is_empty()
+    }
+}
+
 /// Port Manager - manages .ckports file and dynamic port allocation
 pub struct PortManager {
     _project_path: PathBuf,
@@ -88,6 +160,7 @@ impl PortManager {
             return Ok(PortMap {
                 base_port: None,
                 allocations: HashMap::new(),
+                reservations: HashMap::new(),
             });
         }
 
@@ -96,7 +169,7 @@ impl PortManager {
         })?;
 
         let port_map: PortMap = serde_json::from_str(&content).map_err(|e| {
-            eprintln!("Failed to parse .ckports, resetting: {}", e);
+            tracing::warn!(error = %e, "failed to parse .ckports, resetting");
             // Return empty map if parsing fails
             return CkpError::ParseError(format!("Invalid .ckports JSON: {}", e));
         })?;
@@ -153,7 +226,10 @@ impl PortManager {
     /// # Returns
     /// true if available, false if in use
     pub fn is_port_available(port: u16) -> bool {
-        TcpListener::bind(("127.0.0.1", port)).is_ok()
+        // A service bound to a single interface only blocks that address,
+        // so probe loopback and the wildcard address separately
+        let loopback = TcpListener::bind(("127.0.0.1", port)).is_ok();
+        loopback && TcpListener::bind(("0.0.0.0", port)).is_ok()
     }
 
     /// Allocate port for a kernel
@@ -193,7 +269,7 @@ impl PortManager {
                 let candidate_port = range.start + offset;
 
                 // Check if already allocated
-                if !self.port_map.allocations.values().any(|&p| p == candidate_port) {
+                if !self.is_assigned(candidate_port) {
                     if Self::is_port_available(candidate_port) {
                         self.port_map
                             .allocations
@@ -210,7 +286,7 @@ impl PortManager {
             let candidate_port = range.start + offset;
 
             // Check if already allocated to another kernel
-            if self.port_map.allocations.values().any(|&p| p == candidate_port) {
+            if self.is_assigned(candidate_port) {
                 continue; // Already allocated
             }
 
@@ -371,7 +447,7 @@ impl PortManager {
                 let candidate_port = range.start + offset;
 
                 // Check if already allocated to another kernel
-                if self.port_map.allocations.values().any(|&p| p == candidate_port) {
+                if self.is_assigned(candidate_port) {
                     // Already allocated - skip
                 } else {
                     // Check if occupied by external service
@@ -386,9 +462,10 @@ impl PortManager {
                         return Ok((candidate_port, false));
                     } else {
                         // Occupied - log warning and continue to find alternative
-                        eprintln!(
-                            "⚠️  Preferred port {} is OCCUPIED by external service for {}. Allocating alternative...",
-                            candidate_port, kernel_name
+                        tracing::warn!(
+                            kernel = kernel_name,
+                            port = candidate_port,
+                            "preferred port is occupied by an external service, allocating an alternative"
                         );
                     }
                 }
@@ -400,7 +477,7 @@ impl PortManager {
             let candidate_port = range.start + offset;
 
             // Check if already allocated to another kernel
-            if self.port_map.allocations.values().any(|&p| p == candidate_port) {
+            if self.is_assigned(candidate_port) {
                 continue;
             }
 
@@ -422,6 +499,280 @@ impl PortManager {
             range.start, range.end, kernel_name
         )))
     }
+
+    /// Whether a port is allocated to a kernel or held by an active reservation
+    fn is_assigned(&self, port: u16) -> bool {
+        self.port_map.allocations.values().any(|&p| p == port)
+            || self
+                .port_map
+                .reservations
+                .values()
+                .any(|r| r.port == port && !r.is_expired())
+    }
+
+    /// Reserve a port for a named service
+    ///
+    /// Same as `reserve_with(service, None, None)`: the reservation has no
+    /// owner and no lease, and is held until released.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use ckp_core::PortManager;
+    ///
+    /// let mut ports = PortManager::new("/project").unwrap();
+    /// let port = ports.reserve("gateway-http").unwrap();
+    /// assert_eq!(ports.reserve("gateway-http").unwrap(), port);
+    /// ```
+    pub fn reserve(&mut self, service: &str) -> Result<u16, CkpError> {
+        self.reserve_with(service, None, None)
+    }
+
+    /// Reserve a port for a named service, with an owner and lease
+    ///
+    /// Reserving an existing name returns the same port and renews its
+    /// lease. An expired reservation keeps its port if it is still free.
+    /// New reservations are taken from the top of the range downwards, away
+    /// from kernel allocations that fill it from the bottom.
+    ///
+    /// # Arguments
+    /// * `service` - Service name (e.g. "gateway-http")
+    /// * `owner` - Kernel owning the reservation, released with the kernel
+    /// * `ttl` - Lease duration; `None` holds the port until released
+    ///
+    /// # Errors
+    /// - Base port not set
+    /// - Service reserved by another owner whose lease is still active
+    /// - No free port left in the range
+    pub fn reserve_with(
+        &mut self,
+        service: &str,
+        owner: Option<&str>,
+        ttl: Option<Duration>,
+    ) -> Result<u16, CkpError> {
+        let range = self.get_port_range().ok_or_else(|| {
+            CkpError::PortError(
+                "Base port not set. Initialize project first with ProjectRegistry.".to_string(),
+            )
+        })?;
+
+        let previous = self.port_map.reservations.remove(service);
+        if let Some(existing) = &previous {
+            if !existing.is_expired() && existing.owner.as_deref() != owner {
+                let holder = existing.owner.clone().unwrap_or_else(|| "the project".to_string());
+                self.port_map.reservations.insert(service.to_string(), existing.clone());
+                return Err(CkpError::PortError(format!(
+                    "Service \"{}\" is reserved by {} until its lease expires",
+                    service, holder
+                )));
+            }
+        }
+
+        // Keep the previous port when possible so clients don't need reconfiguring
+        let port = match previous {
+            Some(existing)
+                if range.contains(existing.port)
+                    && !self.is_assigned(existing.port)
+                    && (!existing.is_expired() || Self::is_port_available(existing.port)) =>
+            {
+                existing.port
+            }
+            _ => (range.start..=range.end)
+                .rev()
+                .find(|&port| !self.is_assigned(port) && Self::is_port_available(port))
+                .ok_or_else(|| {
+                    CkpError::PortUnavailable(format!(
+                        "No available ports in range {}-{} for service {}",
+                        range.start, range.end, service
+                    ))
+                })?,
+        };
+
+        let now = Utc::now();
+        self.port_map.reservations.insert(
+            service.to_string(),
+            PortReservation {
+                port,
+                owner: owner.map(str::to_string),
+                leased_at: now,
+                expires_at: ttl.map(|ttl| now + ttl),
+            },
+        );
+        self.save()?;
+
+        Ok(port)
+    }
+
+    /// Get the active reservation for a service
+    pub fn get_reservation(&self, service: &str) -> Option<&PortReservation> {
+        self.port_map
+            .reservations
+            .get(service)
+            .filter(|r| !r.is_expired())
+    }
+
+    /// Release a named reservation
+    ///
+    /// # Returns
+    /// true if released, false if not reserved
+    pub fn release_reservation(&mut self, service: &str) -> Result<bool, CkpError> {
+        if self.port_map.reservations.remove(service).is_some() {
+            self.save()?;
+            Ok(true)
+        } else {
+            Ok(false)
+        }
+    }
+
+    /// Release a kernel's port allocation and every reservation it owns
+    ///
+    /// Called when the kernel is deleted from the project.
+    ///
+    /// # Returns
+    /// Names of the released reservations
+    pub fn release_kernel(&mut self, kernel_name: &str) -> Result<Vec<String>, CkpError> {
+        let mut released: Vec<String> = self
+            .port_map
+            .reservations
+            .iter()
+            .filter(|(_, r)| r.owner.as_deref() == Some(kernel_name))
+            .map(|(service, _)| service.clone())
+            .collect();
+        released.sort();
+
+        for service in &released {
+            self.port_map.reservations.remove(service);
+        }
+        let had_allocation = self.port_map.allocations.remove(kernel_name).is_some();
+
+        if had_allocation || !released.is_empty() {
+            self.save()?;
+        }
+        Ok(released)
+    }
+
+    /// Drop reservations whose lease has expired
+    ///
+    /// # Returns
+    /// Names of the pruned reservations
+    pub fn prune_expired_reservations(&mut self) -> Result<Vec<String>, CkpError> {
+        let mut expired: Vec<String> = self
+            .port_map
+            .reservations
+            .iter()
+            .filter(|(_, r)| r.is_expired())
+            .map(|(service, _)| service.clone())
+            .collect();
+        expired.sort();
+
+        if !expired.is_empty() {
+            for service in &expired {
+                self.port_map.reservations.remove(service);
+            }
+            self.save()?;
+        }
+        Ok(expired)
+    }
+
+    /// Compare allocations against live sockets and optionally repair them
+    ///
+    /// Detects a base port that disagrees with the project registry,
+    /// allocations outside the range, ports shared by several kernels, ports
+    /// of stopped kernels taken by other processes, and unallocated ports in
+    /// the range held by external services.
+    ///
+    /// With `repair`, the base port is reset to `expected_base` and every
+    /// misallocated kernel (out of range, duplicate or occupied) is moved to
+    /// the next free port. External occupants are only reported.
+    ///
+    /// # Arguments
+    /// * `expected_base` - Base port assigned by the project registry, if known
+    /// * `running` - Kernels currently running; their own ports are expected to be bound
+    /// * `repair` - Apply fixes and save .ckports
+    ///
+    /// # Returns
+    /// Report of drift found and reassignments made
+    pub fn reconcile(
+        &mut self,
+        expected_base: Option<u16>,
+        running: &HashSet<String>,
+        repair: bool,
+    ) -> Result<PortReconcileReport, CkpError> {
+        let mut report = PortReconcileReport::default();
+
+        let recorded = self.port_map.base_port;
+        let base = match (recorded, expected_base) {
+            (_, Some(expected)) => {
+                if recorded != Some(expected) {
+                    report.drift.push(PortDrift::BasePort { recorded, expected });
+                }
+                expected
+            }
+            (Some(recorded), None) => recorded,
+            (None, None) => {
+                return Err(CkpError::PortError(
+                    "Base port not set. Cannot reconcile ports.".to_string(),
+                ))
+            }
+        };
+        let range = PortRange { start: base, end: base + 199 };
+
+        // Sorted so the same kernel keeps a contested port on every run
+        let mut allocations: Vec<(String, u16)> = self
+            .port_map
+            .allocations
+            .iter()
+            .map(|(kernel, port)| (kernel.clone(), *port))
+            .collect();
+        allocations.sort();
+
+        let mut holders: HashMap<u16, String> = HashMap::new();
+        let mut misallocated = Vec::new();
+
+        for (kernel, port) in allocations {
+            let drift = if !range.contains(port) {
+                PortDrift::OutOfRange { kernel: kernel.clone(), port }
+            } else if let Some(holder) = holders.get(&port) {
+                PortDrift::Duplicate { kernel: kernel.clone(), port, holder: holder.clone() }
+            } else {
+                holders.insert(port, kernel.clone());
+                if running.contains(&kernel) || Self::is_port_available(port) {
+                    continue;
+                }
+                PortDrift::Occupied { kernel: kernel.clone(), port }
+            };
+
+            report.drift.push(drift);
+            misallocated.push((kernel, port));
+        }
+
+        for port in range.start..=range.end {
+            if !holders.contains_key(&port) && !self.is_assigned(port) && !Self::is_port_available(port) {
+                report.drift.push(PortDrift::External { port });
+            }
+        }
+
+        if !repair {
+            return Ok(report);
+        }
+
+        self.port_map.base_port = Some(base);
+        for (kernel, _) in &misallocated {
+            self.port_map.allocations.remove(kernel);
+        }
+
+        for (kernel, from) in misallocated {
+            match self.allocate(&kernel, None) {
+                Ok(to) => report.reassigned.push(PortReassignment { kernel, from, to }),
+                Err(_) => {
+                    self.port_map.allocations.insert(kernel.clone(), from);
+                    report.unresolved.push(kernel);
+                }
+            }
+        }
+
+        self.save()?;
+        Ok(report)
+    }
 }
 
 #[cfg(test)]
@@ -786,4 +1137,114 @@ mod tests {
             assert_eq!(*port, 58000 + i as u16);
         }
     }
+
+    #[test]
+    fn test_named_reservations() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut port_manager = PortManager::new(temp_dir.path()).unwrap();
+        assert!(port_manager.reserve("gateway-http").is_err(), "Needs a base port");
+        port_manager.set_base_port(58800).unwrap();
+
+        // Reservations come from the top of the range and are stable
+        let http = port_manager.reserve("gateway-http").unwrap();
+        assert!(http > 58800 + 100);
+        assert_eq!(port_manager.reserve("gateway-http").unwrap(), http);
+
+        // Kernel allocations never land on a reserved port
+        let wss = port_manager.reserve_with("gateway-wss", Some("System.Gateway"), None).unwrap();
+        assert_ne!(wss, http);
+        for i in 0..5 {
+            let port = port_manager.allocate(&format!("Kernel.{}", i), Some(http - 58800)).unwrap();
+            assert!(port != http && port != wss);
+        }
+
+        // Another owner can't take an active reservation
+        assert!(port_manager.reserve_with("gateway-wss", Some("Other.Kernel"), None).is_err());
+
+        // Persisted across loads
+        let reloaded = PortManager::new(temp_dir.path()).unwrap();
+        assert_eq!(reloaded.get_reservation("gateway-http").unwrap().port, http);
+        assert_eq!(reloaded.get_reservation("gateway-wss").unwrap().owner.as_deref(), Some("System.Gateway"));
+
+        assert_eq!(port_manager.release_kernel("System.Gateway").unwrap(), vec!["gateway-wss".to_string()]);
+        assert!(port_manager.get_reservation("gateway-wss").is_none());
+        assert!(port_manager.release_reservation("gateway-http").unwrap());
+        assert!(!port_manager.release_reservation("gateway-http").unwrap());
+    }
+
+    #[test]
+    fn test_reservation_lease_expiry() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut port_manager = PortManager::new(temp_dir.path()).unwrap();
+        port_manager.set_base_port(59000).unwrap();
+
+        let port = port_manager
+            .reserve_with("batch-api", Some("Batch.Worker"), Some(Duration::seconds(-1)))
+            .unwrap();
+        assert!(port_manager.get_reservation("batch-api").is_none(), "Lease already ran out");
+
+        // An expired lease can be taken over and keeps its free port
+        let renewed = port_manager
+            .reserve_with("batch-api", Some("Batch.Other"), Some(Duration::hours(1)))
+            .unwrap();
+        assert_eq!(renewed, port);
+        assert_eq!(port_manager.get_reservation("batch-api").unwrap().owner.as_deref(), Some("Batch.Other"));
+
+        port_manager.reserve_with("stale", None, Some(Duration::seconds(-1))).unwrap();
+        assert_eq!(port_manager.prune_expired_reservations().unwrap(), vec!["stale".to_string()]);
+        assert!(port_manager.get_reservation("batch-api").is_some());
+    }
+
+    #[test]
+    fn test_reconcile_reports_and_repairs_drift() {
+        let temp_dir = TempDir::new().unwrap();
+
+        // .ckports still points at an old base; the registry assigned 58400
+        fs::write(
+            temp_dir.path().join(".ckports"),
+            r#"{"basePort": 58200, "allocations": {
+                "Kernel.Moved": 58201,
+                "Kernel.Api": 58405,
+                "Kernel.Copy": 58405,
+                "Kernel.Stopped": 58406
+            }}"#,
+        )
+        .unwrap();
+        let mut port_manager = PortManager::new(temp_dir.path()).unwrap();
+
+        let _api = TcpListener::bind(("127.0.0.1", 58405)).unwrap();
+        let _squatter = TcpListener::bind(("127.0.0.1", 58406)).unwrap();
+        let _external = TcpListener::bind(("127.0.0.1", 58410)).unwrap();
+        let running: HashSet<String> = ["Kernel.Api".to_string()].into_iter().collect();
+
+        let report = port_manager.reconcile(Some(58400), &running, false).unwrap();
+        assert!(!report.is_clean());
+        assert_eq!(report.drift[0], PortDrift::BasePort { recorded: Some(58200), expected: 58400 });
+        for expected in [
+            PortDrift::OutOfRange { kernel: "Kernel.Moved".to_string(), port: 58201 },
+            PortDrift::Duplicate { kernel: "Kernel.Copy".to_string(), port: 58405, holder: "Kernel.Api".to_string() },
+            PortDrift::Occupied { kernel: "Kernel.Stopped".to_string(), port: 58406 },
+            PortDrift::External { port: 58410 },
+        ] {
+            assert!(report.drift.contains(&expected), "missing {:?}", expected);
+        }
+        assert!(report.reassigned.is_empty());
+        assert_eq!(port_manager.get_base_port(), Some(58200), "Report mode changes nothing");
+
+        let report = port_manager.reconcile(Some(58400), &running, true).unwrap();
+        assert_eq!(report.reassigned.len(), 3);
+        assert!(report.unresolved.is_empty());
+
+        let reloaded = PortManager::new(temp_dir.path()).unwrap();
+        assert_eq!(reloaded.get_base_port(), Some(58400));
+        assert_eq!(reloaded.get("Kernel.Api"), Some(58405));
+
+        let range = reloaded.get_port_range().unwrap();
+        let mut ports: Vec<u16> = reloaded.get_all_allocations().values().copied().collect();
+        assert!(ports.iter().all(|port| range.contains(*port)));
+        assert!(!ports.contains(&58406) && !ports.contains(&58410));
+        ports.sort();
+        ports.dedup();
+        assert_eq!(ports.len(), 4, "Every kernel holds its own port");
+    }
 }
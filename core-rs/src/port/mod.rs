@@ -5,7 +5,7 @@
 
 pub mod manager;
 
-pub use manager::{PortManager, PortMap, PortRange};
+pub use manager::{PortDrift, PortManager, PortMap, PortRange, PortReassignment, PortReconcileReport, PortReservation};
 
 #[cfg(test)]
 mod tests {
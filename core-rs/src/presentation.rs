@@ -0,0 +1,456 @@
+//! Status screens shared by the CLIs
+//!
+//! `ck` (Node.js) and `ckr` show the same status screens for kernels, queues,
+//! projects and workflow validations. `StatusView` renders each of them as a
+//! human table or, for `--json`, as a JSON document.
+//!
+//! The JSON field names are written out here instead of derived from the
+//! types' serde attributes: they are the contract with the Node CLI and with
+//! scripts, and must not change when a Rust field is renamed. Names are
+//! camelCase; optional values are `null` rather than omitted, and maps are
+//! always present, so every document of a kind has the same shape.
+
+use crate::kernel::{KernelStatus, QueueStats};
+use crate::project::{KernelHealth, ProjectHealth};
+use crate::workflow::{CycleType, WorkflowCycle, WorkflowValidation};
+use serde_json::{json, Value};
+
+/// How a status screen is printed
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum OutputFormat {
+    /// Summary, table and notes for a terminal
+    #[default]
+    Table,
+
+    /// Pretty-printed JSON (`--json`)
+    Json,
+}
+
+impl OutputFormat {
+    /// Format selected by a `--json` flag
+    pub fn from_json_flag(json: bool) -> Self {
+        if json {
+            OutputFormat::Json
+        } else {
+            OutputFormat::Table
+        }
+    }
+}
+
+/// Column alignment
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Align {
+    Left,
+    Right,
+}
+
+/// Table sized to its contents
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Table {
+    columns: Vec<(String, Align)>,
+    rows: Vec<Vec<String>>,
+}
+
+impl Table {
+    pub fn new(columns: &[(&str, Align)]) -> Self {
+        Self {
+            columns: columns.iter().map(|(name, align)| (name.to_string(), *align)).collect(),
+            rows: Vec::new(),
+        }
+    }
+
+    /// Append a row; missing cells are blank and extra cells are dropped
+    pub fn push_row(&mut self, mut cells: Vec<String>) {
+        cells.resize(self.columns.len(), String::new());
+        self.rows.push(cells);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rows.is_empty()
+    }
+
+    /// Header and rows, columns separated by two spaces, without trailing blanks
+    pub fn render(&self) -> String {
+        let headers: Vec<String> = self.columns.iter().map(|(name, _)| name.clone()).collect();
+        let widths: Vec<usize> = (0..self.columns.len())
+            .map(|i| {
+                std::iter::once(&headers)
+                    .chain(&self.rows)
+                    .map(|row| row[i].chars().count())
+                    .max()
+                    .unwrap_or(0)
+            })
+            .collect();
+
+        let mut out = String::new();
+        for row in std::iter::once(&headers).chain(&self.rows) {
+            let cells: Vec<String> = row
+                .iter()
+                .zip(&self.columns)
+                .zip(&widths)
+                .map(|((cell, (_, align)), width)| match align {
+                    Align::Left => format!("{:<width$}", cell, width = width),
+                    Align::Right => format!("{:>width$}", cell, width = width),
+                })
+                .collect();
+            out.push_str(cells.join("  ").trim_end());
+            out.push('\n');
+        }
+        out
+    }
+}
+
+/// Human form of a status: title, aligned summary lines, a table and notes
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct StatusScreen {
+    pub title: Option<String>,
+    pub summary: Vec<(String, String)>,
+    pub table: Option<Table>,
+    pub notes: Vec<String>,
+}
+
+impl StatusScreen {
+    pub fn render(&self) -> String {
+        let mut sections = Vec::new();
+
+        let mut head = String::new();
+        if let Some(title) = &self.title {
+            head.push_str(title);
+            head.push('\n');
+        }
+        let key_width = self.summary.iter().map(|(key, _)| key.chars().count() + 1).max().unwrap_or(0);
+        for (key, value) in &self.summary {
+            head.push_str(&format!("  {:<width$} {}\n", format!("{}:", key), value, width = key_width));
+        }
+        if !head.is_empty() {
+            sections.push(head);
+        }
+
+        if let Some(table) = self.table.as_ref().filter(|table| !table.is_empty()) {
+            sections.push(table.render());
+        }
+
+        if !self.notes.is_empty() {
+            sections.push(self.notes.iter().map(|note| format!("{}\n", note)).collect());
+        }
+
+        sections.join("\n")
+    }
+}
+
+/// Something the CLIs show a status screen for
+pub trait StatusView {
+    /// Machine form, with the field names shared with the Node CLI
+    fn status_json(&self) -> Value;
+
+    /// Human form
+    fn status_screen(&self) -> StatusScreen;
+
+    /// Render in the given format
+    fn render_status(&self, format: OutputFormat) -> String {
+        match format {
+            OutputFormat::Table => self.status_screen().render(),
+            OutputFormat::Json => {
+                let mut json = serde_json::to_string_pretty(&self.status_json()).unwrap_or_default();
+                json.push('\n');
+                json
+            }
+        }
+    }
+}
+
+impl StatusView for QueueStats {
+    fn status_json(&self) -> Value {
+        json!({
+            "inbox": self.inbox,
+            "staging": self.staging,
+            "ready": self.ready,
+            "total": self.inbox + self.staging + self.ready,
+        })
+    }
+
+    fn status_screen(&self) -> StatusScreen {
+        let mut table = Table::new(&[
+            ("INBOX", Align::Right),
+            ("STAGING", Align::Right),
+            ("READY", Align::Right),
+            ("TOTAL", Align::Right),
+        ]);
+        table.push_row(vec![
+            self.inbox.to_string(),
+            self.staging.to_string(),
+            self.ready.to_string(),
+            (self.inbox + self.staging + self.ready).to_string(),
+        ]);
+        StatusScreen {
+            table: Some(table),
+            ..StatusScreen::default()
+        }
+    }
+}
+
+impl StatusView for KernelStatus {
+    fn status_json(&self) -> Value {
+        json!({
+            "name": self.name,
+            "type": self.kernel_type,
+            "class": self.class.map(|class| class.as_str()),
+            "mode": self.mode,
+            "pid": self.pid,
+            "watcherPid": self.watcher_pid,
+            "port": self.port,
+            "queueStats": self.queue_stats.status_json(),
+        })
+    }
+
+    fn status_screen(&self) -> StatusScreen {
+        StatusScreen {
+            title: Some(format!("Kernel: {}", self.name)),
+            summary: vec![
+                ("Type".to_string(), self.kernel_type.clone()),
+                ("Mode".to_string(), self.mode.clone()),
+                ("PID".to_string(), or_dash(self.pid)),
+                ("Watcher PID".to_string(), or_dash(self.watcher_pid)),
+                ("Port".to_string(), or_dash(self.port)),
+            ],
+            ..self.queue_stats.status_screen()
+        }
+    }
+}
+
+impl StatusView for ProjectHealth {
+    fn status_json(&self) -> Value {
+        json!({
+            "project": self.project,
+            "path": self.path,
+            "checkedAt": self.checked_at.to_rfc3339(),
+            "portRange": {"start": self.port_range.start, "end": self.port_range.end},
+            "healthy": self.is_healthy(),
+            "aliveCount": self.alive_count(),
+            "totalQueueDepth": self.total_queue_depth(),
+            "kernels": self.kernels.iter().map(kernel_health_json).collect::<Vec<_>>(),
+            "errors": self.errors,
+            "stalePorts": self.stale_ports,
+        })
+    }
+
+    fn status_screen(&self) -> StatusScreen {
+        let mut table = Table::new(&[
+            ("KERNEL", Align::Left),
+            ("TYPE", Align::Left),
+            ("MODE", Align::Left),
+            ("QUEUE", Align::Right),
+            ("EDGES", Align::Right),
+            ("PORT", Align::Right),
+        ]);
+        for kernel in &self.kernels {
+            let port = match kernel.status.port {
+                Some(port) if kernel.port_out_of_range => format!("{}!", port),
+                Some(port) => port.to_string(),
+                None => "-".to_string(),
+            };
+            table.push_row(vec![
+                kernel.status.name.clone(),
+                kernel.status.kernel_type.clone(),
+                kernel.status.mode.clone(),
+                (kernel.queue_depth() - kernel.edge_queue_depth).to_string(),
+                kernel.edge_queue_depth.to_string(),
+                port,
+            ]);
+        }
+
+        let mut notes: Vec<String> = self.errors.iter().map(|(kernel, error)| format!("⚠️  {}: {}", kernel, error)).collect();
+        notes.extend(
+            self.stale_ports
+                .iter()
+                .map(|(kernel, port)| format!("⚠️  Port {} still allocated to missing kernel {}", port, kernel)),
+        );
+        notes.push(format!("Status: {}", if self.is_healthy() { "healthy" } else { "degraded" }));
+
+        StatusScreen {
+            title: Some(format!("Project: {} ({})", self.project, self.path)),
+            summary: vec![
+                ("Port Range".to_string(), format!("{}-{}", self.port_range.start, self.port_range.end)),
+                ("Kernels".to_string(), format!("{} alive / {}", self.alive_count(), self.kernels.len())),
+                ("Queued".to_string(), self.total_queue_depth().to_string()),
+            ],
+            table: Some(table),
+            notes,
+        }
+    }
+}
+
+impl StatusView for WorkflowValidation {
+    fn status_json(&self) -> Value {
+        json!({
+            "valid": self.is_valid,
+            "cycles": self.cycles.iter().map(cycle_json).collect::<Vec<_>>(),
+            "missingKernels": self.missing_kernels,
+            "invalidPredicates": self.invalid_predicates,
+            "warnings": self.warnings,
+            "errors": self.errors,
+        })
+    }
+
+    fn status_screen(&self) -> StatusScreen {
+        let mut table = Table::new(&[
+            ("CYCLE", Align::Left),
+            ("TYPE", Align::Left),
+            ("INTENTIONAL", Align::Left),
+            ("EXIT", Align::Left),
+        ]);
+        for cycle in &self.cycles {
+            table.push_row(vec![
+                cycle.kernels.join(" -> "),
+                cycle_type_name(&cycle.cycle_type).to_string(),
+                yes_no(cycle.is_intentional).to_string(),
+                yes_no(cycle.has_exit_condition).to_string(),
+            ]);
+        }
+
+        let mut notes: Vec<String> = self.errors.iter().map(|error| format!("ERROR: {}", error)).collect();
+        notes.extend(self.warnings.iter().map(|warning| format!("WARNING: {}", warning)));
+
+        StatusScreen {
+            title: Some(format!("Workflow: {}", if self.is_valid { "valid" } else { "invalid" })),
+            summary: vec![
+                ("Cycles".to_string(), self.cycles.len().to_string()),
+                ("Missing Kernels".to_string(), list_or_dash(&self.missing_kernels)),
+                ("Invalid Predicates".to_string(), list_or_dash(&self.invalid_predicates)),
+            ],
+            table: Some(table),
+            notes,
+        }
+    }
+}
+
+fn kernel_health_json(kernel: &KernelHealth) -> Value {
+    let mut json = kernel.status.status_json();
+    json["edgeQueueDepth"] = json!(kernel.edge_queue_depth);
+    json["queueDepth"] = json!(kernel.queue_depth());
+    json["alive"] = json!(kernel.alive);
+    json["portOutOfRange"] = json!(kernel.port_out_of_range);
+    json
+}
+
+fn cycle_json(cycle: &WorkflowCycle) -> Value {
+    json!({
+        "kernels": cycle.kernels,
+        "type": cycle_type_name(&cycle.cycle_type),
+        "intentional": cycle.is_intentional,
+        "hasExitCondition": cycle.has_exit_condition,
+    })
+}
+
+fn cycle_type_name(cycle_type: &CycleType) -> &'static str {
+    match cycle_type {
+        CycleType::ClosedLoopVerification => "closed-loop-verification",
+        CycleType::RequestResponse => "request-response",
+        CycleType::Problematic => "problematic",
+    }
+}
+
+fn or_dash<T: ToString>(value: Option<T>) -> String {
+    value.map(|v| v.to_string()).unwrap_or_else(|| "-".to_string())
+}
+
+fn list_or_dash(items: &[String]) -> String {
+    if items.is_empty() {
+        "-".to_string()
+    } else {
+        items.join(", ")
+    }
+}
+
+fn yes_no(value: bool) -> &'static str {
+    if value {
+        "yes"
+    } else {
+        "no"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ontology::KernelClass;
+    use crate::project::registry::PortRange;
+    use chrono::{TimeZone, Utc};
+    use std::collections::BTreeMap;
+
+    fn kernel(name: &str, mode: &str, port: Option<u16>, inbox: usize) -> KernelStatus {
+        KernelStatus {
+            name: name.to_string(),
+            kernel_type: "node:cold".to_string(),
+            class: Some(KernelClass::Cold),
+            pid: None,
+            watcher_pid: Some(4242),
+            mode: mode.to_string(),
+            queue_stats: QueueStats { inbox, staging: 0, ready: 1 },
+            port,
+        }
+    }
+
+    #[test]
+    fn test_project_health_renders_table_and_stable_json() {
+        let health = ProjectHealth {
+            project: "shop".to_string(),
+            path: "/srv/shop".to_string(),
+            checked_at: Utc.with_ymd_and_hms(2026, 1, 2, 3, 4, 5).unwrap(),
+            port_range: PortRange { start: 56000, end: 56009 },
+            kernels: vec![
+                KernelHealth {
+                    status: kernel("Shop.Orders", "SLEEP", Some(56001), 3),
+                    edge_queue_depth: 2,
+                    alive: true,
+                    port_out_of_range: false,
+                },
+                KernelHealth {
+                    status: kernel("Shop.Mailer", "DOWN", Some(57000), 0),
+                    edge_queue_depth: 0,
+                    alive: false,
+                    port_out_of_range: true,
+                },
+            ],
+            errors: BTreeMap::from([("Shop.Broken".to_string(), "missing ontology".to_string())]),
+            stale_ports: BTreeMap::new(),
+        };
+
+        assert_eq!(
+            health.render_status(OutputFormat::Table),
+            "Project: shop (/srv/shop)\n\
+             \x20 Port Range: 56000-56009\n\
+             \x20 Kernels:    1 alive / 2\n\
+             \x20 Queued:     7\n\
+             \n\
+             KERNEL       TYPE       MODE   QUEUE  EDGES    PORT\n\
+             Shop.Orders  node:cold  SLEEP      4      2   56001\n\
+             Shop.Mailer  node:cold  DOWN       1      0  57000!\n\
+             \n\
+             ⚠️  Shop.Broken: missing ontology\n\
+             Status: degraded\n"
+        );
+
+        let json: Value = serde_json::from_str(&health.render_status(OutputFormat::Json)).unwrap();
+        assert_eq!(json["checkedAt"], "2026-01-02T03:04:05+00:00");
+        assert_eq!(json["healthy"], false);
+        assert_eq!(json["stalePorts"], json!({}));
+        assert_eq!(
+            json["kernels"][0],
+            json!({
+                "name": "Shop.Orders",
+                "type": "node:cold",
+                "class": "cold",
+                "mode": "SLEEP",
+                "pid": null,
+                "watcherPid": 4242,
+                "port": 56001,
+                "queueStats": {"inbox": 3, "staging": 0, "ready": 1, "total": 4},
+                "edgeQueueDepth": 2,
+                "queueDepth": 6,
+                "alive": true,
+                "portOutOfRange": false,
+            })
+        );
+    }
+}
@@ -6,17 +6,35 @@
 //! Reference: Node.js v1.3.14 - ProcessTracker.js
 
 use crate::errors::{CkpError, Result};
+use crate::ontology::{BfoEntityType, OntologyLibrary};
 use chrono::{DateTime, Utc};
+use oxigraph::model::vocab::{rdf, xsd};
+use oxigraph::model::{GraphName, Literal, NamedNode, Quad, Term};
 use once_cell::sync::OnceCell;
 use regex::Regex;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::pin::Pin;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::broadcast;
+use tokio_stream::wrappers::BroadcastStream;
+use tokio_stream::{Stream, StreamExt};
 
 static PROCESS_TRACKER_INSTANCE: OnceCell<ProcessTracker> = OnceCell::new();
 
+/// Buffered events per subscriber before slow subscribers start losing events
+const EVENT_CHANNEL_CAPACITY: usize = 1024;
+
+/// Temporal part data key holding how long the phase took, in milliseconds
+///
+/// Set by `TrackedProcess::timed_phase`; `Statistics::by_phase` and
+/// `ProcessTracker::get_phase_breakdown` aggregate it per phase name.
+pub const PHASE_DURATION_KEY: &str = "durationMs";
+
 /// BFO-aligned process tracker
 pub struct ProcessTracker {
     /// Root concepts directory
@@ -24,6 +42,46 @@ pub struct ProcessTracker {
 
     /// Processes storage directory
     processes_dir: PathBuf,
+
+    /// Broadcast channel feeding `subscribe()` streams
+    events: broadcast::Sender<ProcessEvent>,
+}
+
+/// Stream of process events returned by `ProcessTracker::subscribe`
+pub type ProcessEventStream = Pin<Box<dyn Stream<Item = ProcessEvent> + Send>>;
+
+/// Kind of process lifecycle event
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ProcessEventKind {
+    /// Process was created
+    Started,
+
+    /// A temporal part (phase) was added
+    PhaseChanged,
+
+    /// Process completed
+    Completed,
+
+    /// Process failed
+    Failed,
+}
+
+/// Process lifecycle event delivered to subscribers
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProcessEvent {
+    /// Event kind
+    pub kind: ProcessEventKind,
+
+    /// Phase name (for phase-changed, completed and failed events)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub phase: Option<String>,
+
+    /// Event timestamp
+    pub timestamp: String,
+
+    /// Process state after the change
+    pub process: Process,
 }
 
 /// Process (Occurrent) - temporal entity that unfolds over time
@@ -128,6 +186,49 @@ pub struct QueryFilters {
     pub sort_field: Option<String>,
 }
 
+impl QueryFilters {
+    /// Check whether a process satisfies the type, kernel, status and time filters
+    ///
+    /// `limit`, `order` and `sort_field` only affect result sets and are ignored here.
+    pub fn matches(&self, process: &Process) -> bool {
+        if let Some(ref process_type) = self.process_type {
+            if &process.process_type != process_type {
+                return false;
+            }
+        }
+
+        if let Some(ref kernel) = self.kernel {
+            if process.participants.get("kernel").and_then(|v| v.as_str()) != Some(kernel) {
+                return false;
+            }
+        }
+
+        if let Some(ref status) = self.status {
+            if &process.status != status {
+                return false;
+            }
+        }
+
+        if let Some(ref start_after) = self.start_after {
+            if let Ok(start_time) = DateTime::parse_from_rfc3339(&process.temporal_region.start) {
+                if start_time.with_timezone(&Utc) <= *start_after {
+                    return false;
+                }
+            }
+        }
+
+        if let Some(ref start_before) = self.start_before {
+            if let Ok(start_time) = DateTime::parse_from_rfc3339(&process.temporal_region.start) {
+                if start_time.with_timezone(&Utc) >= *start_before {
+                    return false;
+                }
+            }
+        }
+
+        true
+    }
+}
+
 /// Process statistics
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Statistics {
@@ -149,8 +250,154 @@ pub struct Statistics {
     /// Total duration in milliseconds
     #[serde(rename = "totalDuration")]
     pub total_duration: i64,
+
+    /// Share of processes that failed (0.0 - 1.0)
+    #[serde(rename = "failureRatio", default)]
+    pub failure_ratio: f64,
+
+    /// Duration percentiles across all finished processes
+    #[serde(default)]
+    pub durations: DurationPercentiles,
+
+    /// Aggregates per kernel (participant `kernel`, falling back to `source_kernel`)
+    #[serde(rename = "byKernel", default)]
+    pub by_kernel: HashMap<String, GroupStatistics>,
+
+    /// Aggregates per edge (participant `edge`)
+    #[serde(rename = "byEdge", default)]
+    pub by_edge: HashMap<String, GroupStatistics>,
+
+    /// Throughput over sliding windows ending at computation time
+    #[serde(default)]
+    pub throughput: Vec<ThroughputWindow>,
+
+    /// Duration percentiles per timed phase (see `PHASE_DURATION_KEY`)
+    #[serde(rename = "byPhase", default)]
+    pub by_phase: HashMap<String, DurationPercentiles>,
 }
 
+/// Duration percentiles in milliseconds (nearest-rank)
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct DurationPercentiles {
+    /// Number of durations sampled
+    pub count: usize,
+
+    /// Median duration
+    pub p50: i64,
+
+    /// 95th percentile duration
+    pub p95: i64,
+
+    /// 99th percentile duration
+    pub p99: i64,
+}
+
+impl DurationPercentiles {
+    /// Compute percentiles from unsorted duration samples
+    pub fn from_samples(mut samples: Vec<i64>) -> Self {
+        if samples.is_empty() {
+            return Self::default();
+        }
+
+        samples.sort_unstable();
+        let rank = |pct: f64| -> i64 {
+            let index = ((pct / 100.0) * samples.len() as f64).ceil() as usize;
+            samples[index.saturating_sub(1).min(samples.len() - 1)]
+        };
+
+        Self {
+            count: samples.len(),
+            p50: rank(50.0),
+            p95: rank(95.0),
+            p99: rank(99.0),
+        }
+    }
+}
+
+/// Aggregated statistics for a single kernel or edge
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct GroupStatistics {
+    /// Total processes
+    pub total: usize,
+
+    /// Failed processes
+    pub failed: usize,
+
+    /// Share of processes that failed (0.0 - 1.0)
+    #[serde(rename = "failureRatio")]
+    pub failure_ratio: f64,
+
+    /// Duration percentiles of finished processes
+    pub durations: DurationPercentiles,
+}
+
+/// Throughput over a sliding window
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ThroughputWindow {
+    /// Window length in seconds
+    #[serde(rename = "windowSecs")]
+    pub window_secs: i64,
+
+    /// Processes finished (completed or failed) inside the window
+    pub count: usize,
+
+    /// Finished processes per second
+    #[serde(rename = "perSecond")]
+    pub per_second: f64,
+}
+
+/// Compact statistics snapshot for the metrics daemon
+///
+/// Drops the status/type breakdowns and uses sorted maps so consecutive
+/// snapshots serialize deterministically.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StatisticsSnapshot {
+    /// Snapshot timestamp
+    pub timestamp: String,
+
+    /// Total processes
+    pub total: usize,
+
+    /// Overall failure ratio
+    #[serde(rename = "failureRatio")]
+    pub failure_ratio: f64,
+
+    /// Overall duration percentiles
+    pub durations: DurationPercentiles,
+
+    /// Per-kernel aggregates
+    pub kernels: BTreeMap<String, GroupStatistics>,
+
+    /// Per-edge aggregates
+    pub edges: BTreeMap<String, GroupStatistics>,
+
+    /// Sliding-window throughput
+    pub throughput: Vec<ThroughputWindow>,
+
+    /// Per-phase duration percentiles
+    #[serde(default)]
+    pub phases: BTreeMap<String, DurationPercentiles>,
+}
+
+impl Statistics {
+    /// Build a compact, deterministic snapshot of these statistics
+    pub fn snapshot(&self) -> StatisticsSnapshot {
+        StatisticsSnapshot {
+            timestamp: Utc::now().to_rfc3339(),
+            total: self.total,
+            failure_ratio: self.failure_ratio,
+            durations: self.durations.clone(),
+            kernels: self.by_kernel.clone().into_iter().collect(),
+            edges: self.by_edge.clone().into_iter().collect(),
+            throughput: self.throughput.clone(),
+            phases: self.by_phase.clone().into_iter().collect(),
+        }
+    }
+}
+
+/// Default sliding windows for throughput: 1 minute, 5 minutes, 1 hour
+pub const DEFAULT_THROUGHPUT_WINDOWS: [i64; 3] = [60, 300, 3600];
+
 impl ProcessTracker {
     /// Create a new ProcessTracker
     ///
@@ -166,9 +413,12 @@ impl ProcessTracker {
                 .map_err(|e| CkpError::IoError(format!("Failed to create processes directory: {}", e)))?;
         }
 
+        let (events, _) = broadcast::channel(EVENT_CHANNEL_CAPACITY);
+
         Ok(Self {
             _concepts_root: concepts_root,
             processes_dir,
+            events,
         })
     }
 
@@ -253,6 +503,7 @@ impl ProcessTracker {
         };
 
         self.save_process(&process)?;
+        self.publish(ProcessEventKind::Started, None, &process);
         Ok(process)
     }
 
@@ -320,6 +571,13 @@ impl ProcessTracker {
         process.updated_at = now.to_rfc3339();
         self.save_process(&process)?;
 
+        let kind = match phase {
+            "completed" => ProcessEventKind::Completed,
+            "failed" => ProcessEventKind::Failed,
+            _ => ProcessEventKind::PhaseChanged,
+        };
+        self.publish(kind, Some(phase), &process);
+
         Ok(())
     }
 
@@ -358,6 +616,7 @@ impl ProcessTracker {
         process.updated_at = now.to_rfc3339();
 
         self.save_process(&process)?;
+        self.publish(ProcessEventKind::Completed, Some("completed"), &process);
         Ok(())
     }
 
@@ -395,9 +654,56 @@ impl ProcessTracker {
         process.updated_at = now.to_rfc3339();
 
         self.save_process(&process)?;
+        self.publish(ProcessEventKind::Failed, Some("failed"), &process);
         Ok(())
     }
 
+    /// Subscribe to process lifecycle events
+    ///
+    /// Returns a stream of events (started, phase-changed, completed, failed)
+    /// produced by this tracker's writes from now on, restricted to processes
+    /// matching `filters`. A subscriber that falls more than
+    /// `EVENT_CHANNEL_CAPACITY` events behind skips the events it missed.
+    ///
+    /// # Arguments
+    ///
+    /// * `filters` - Filters applied to each event's process (limit and ordering are ignored)
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use ckp_core::{ProcessTracker, QueryFilters};
+    /// # use std::path::PathBuf;
+    /// let tracker = ProcessTracker::new(PathBuf::from("/tmp")).unwrap();
+    /// let _events = tracker.subscribe(QueryFilters {
+    ///     status: Some("failed".to_string()),
+    ///     ..Default::default()
+    /// });
+    /// ```
+    pub fn subscribe(&self, filters: QueryFilters) -> ProcessEventStream {
+        let stream = BroadcastStream::new(self.events.subscribe())
+            .filter_map(move |event| match event {
+                Ok(event) if filters.matches(&event.process) => Some(event),
+                _ => None,
+            });
+
+        Box::pin(stream)
+    }
+
+    /// Publish an event to subscribers (no-op when nobody is listening)
+    fn publish(&self, kind: ProcessEventKind, phase: Option<&str>, process: &Process) {
+        if self.events.receiver_count() == 0 {
+            return;
+        }
+
+        let _ = self.events.send(ProcessEvent {
+            kind,
+            phase: phase.map(|p| p.to_string()),
+            timestamp: Utc::now().to_rfc3339(),
+            process: process.clone(),
+        });
+    }
+
     /// Save process to disk
     ///
     /// # Arguments
@@ -507,32 +813,8 @@ impl ProcessTracker {
             };
 
             // Apply filters
-            if let Some(ref kernel) = filters.kernel {
-                if process.participants.get("kernel").and_then(|v| v.as_str()) != Some(kernel) {
-                    continue;
-                }
-            }
-
-            if let Some(ref status) = filters.status {
-                if &process.status != status {
-                    continue;
-                }
-            }
-
-            if let Some(ref start_after) = filters.start_after {
-                if let Ok(start_time) = DateTime::parse_from_rfc3339(&process.temporal_region.start) {
-                    if start_time.with_timezone(&Utc) <= *start_after {
-                        continue;
-                    }
-                }
-            }
-
-            if let Some(ref start_before) = filters.start_before {
-                if let Ok(start_time) = DateTime::parse_from_rfc3339(&process.temporal_region.start) {
-                    if start_time.with_timezone(&Utc) >= *start_before {
-                        continue;
-                    }
-                }
+            if !filters.matches(&process) {
+                continue;
             }
 
             all_processes.push(process);
@@ -567,6 +849,9 @@ impl ProcessTracker {
 
     /// Get statistics for processes
     ///
+    /// Throughput is reported for `DEFAULT_THROUGHPUT_WINDOWS`; use
+    /// `get_statistics_with_windows` for custom windows.
+    ///
     /// # Arguments
     ///
     /// * `filters` - Query filters
@@ -575,6 +860,24 @@ impl ProcessTracker {
     ///
     /// Statistics summary
     pub fn get_statistics(&self, filters: QueryFilters) -> Result<Statistics> {
+        self.get_statistics_with_windows(filters, &DEFAULT_THROUGHPUT_WINDOWS)
+    }
+
+    /// Get statistics for processes with custom throughput windows
+    ///
+    /// # Arguments
+    ///
+    /// * `filters` - Query filters
+    /// * `windows_secs` - Sliding window lengths in seconds
+    ///
+    /// # Returns
+    ///
+    /// Statistics summary including per-kernel/per-edge percentiles
+    pub fn get_statistics_with_windows(
+        &self,
+        filters: QueryFilters,
+        windows_secs: &[i64],
+    ) -> Result<Statistics> {
         let processes = self.query_processes(QueryFilters {
             limit: Some(10000),
             ..filters
@@ -583,33 +886,175 @@ impl ProcessTracker {
         let mut by_status: HashMap<String, usize> = HashMap::new();
         let mut by_type: HashMap<String, usize> = HashMap::new();
         let mut total_duration: i64 = 0;
-        let mut duration_count: usize = 0;
+        let mut durations: Vec<i64> = Vec::new();
+        let mut failed: usize = 0;
+
+        // (total, failed, durations) per group key
+        let mut kernel_groups: HashMap<String, (usize, usize, Vec<i64>)> = HashMap::new();
+        let mut edge_groups: HashMap<String, (usize, usize, Vec<i64>)> = HashMap::new();
+
+        let mut phase_samples: HashMap<String, Vec<i64>> = HashMap::new();
+
+        let now = Utc::now();
+        let mut window_counts = vec![0usize; windows_secs.len()];
 
         for process in &processes {
+            collect_phase_durations(process, &mut phase_samples);
             *by_status.entry(process.status.clone()).or_insert(0) += 1;
             *by_type.entry(process.process_type.clone()).or_insert(0) += 1;
 
+            let is_failed = process.status == "failed";
+            if is_failed {
+                failed += 1;
+            }
+
             if let Some(duration) = process.temporal_region.duration {
                 total_duration += duration;
-                duration_count += 1;
+                durations.push(duration);
+            }
+
+            let kernel = process.participants.get("kernel")
+                .or_else(|| process.participants.get("source_kernel"))
+                .and_then(|v| v.as_str());
+            let edge = process.participants.get("edge").and_then(|v| v.as_str());
+
+            for (key, groups) in [(kernel, &mut kernel_groups), (edge, &mut edge_groups)] {
+                if let Some(key) = key {
+                    let group = groups.entry(key.to_string()).or_default();
+                    group.0 += 1;
+                    if is_failed {
+                        group.1 += 1;
+                    }
+                    if let Some(duration) = process.temporal_region.duration {
+                        group.2.push(duration);
+                    }
+                }
+            }
+
+            if let Some(end) = process.temporal_region.end.as_deref()
+                .and_then(|e| DateTime::parse_from_rfc3339(e).ok())
+            {
+                let age_secs = now.signed_duration_since(end.with_timezone(&Utc)).num_seconds();
+                for (i, window) in windows_secs.iter().enumerate() {
+                    if age_secs <= *window {
+                        window_counts[i] += 1;
+                    }
+                }
             }
         }
 
-        let avg_duration = if duration_count > 0 {
-            total_duration as f64 / duration_count as f64
+        let avg_duration = if !durations.is_empty() {
+            total_duration as f64 / durations.len() as f64
         } else {
             0.0
         };
 
+        let throughput = windows_secs
+            .iter()
+            .zip(window_counts)
+            .map(|(window, count)| ThroughputWindow {
+                window_secs: *window,
+                count,
+                per_second: if *window > 0 { count as f64 / *window as f64 } else { 0.0 },
+            })
+            .collect();
+
         Ok(Statistics {
             total: processes.len(),
             by_status,
             by_type,
             avg_duration,
             total_duration,
+            failure_ratio: ratio(failed, processes.len()),
+            durations: DurationPercentiles::from_samples(durations),
+            by_kernel: into_group_statistics(kernel_groups),
+            by_edge: into_group_statistics(edge_groups),
+            throughput,
+            by_phase: phase_samples
+                .into_iter()
+                .map(|(phase, samples)| (phase, DurationPercentiles::from_samples(samples)))
+                .collect(),
         })
     }
 
+    /// Duration percentiles of every timed phase, by phase name
+    ///
+    /// Only phases recorded with `TrackedProcess::timed_phase` (for instance
+    /// the governor's pickup, validation, execution and archive phases)
+    /// carry a duration; the slowest stage of a kernel's jobs is the phase
+    /// with the highest percentiles.
+    ///
+    /// # Arguments
+    ///
+    /// * `filters` - Query filters (e.g. `kernel` to break down one kernel)
+    pub fn get_phase_breakdown(&self, filters: QueryFilters) -> Result<BTreeMap<String, DurationPercentiles>> {
+        let processes = self.query_processes(QueryFilters {
+            limit: Some(10000),
+            ..filters
+        })?;
+
+        let mut samples: HashMap<String, Vec<i64>> = HashMap::new();
+        for process in &processes {
+            collect_phase_durations(process, &mut samples);
+        }
+
+        Ok(samples
+            .into_iter()
+            .map(|(phase, samples)| (phase, DurationPercentiles::from_samples(samples)))
+            .collect())
+    }
+
+    /// Export processes as BFO occurrent triples into the ontology store
+    ///
+    /// Each process becomes a `ckp:Process` / BFO process with its temporal
+    /// region (`BFO_0000199` occupies-temporal-region) and temporal parts
+    /// (`BFO_0000117` has-occurrent-part). Participants are linked with
+    /// `RO_0000057` (has-participant); participants named `role` or ending in
+    /// `_role` are additionally linked with `BFO_0000055` (realizes).
+    /// Timestamps are typed `xsd:dateTime`, so the existing temporal queries
+    /// (`process_precedes`, `processes_overlap`, ...) work on exported data.
+    ///
+    /// # Arguments
+    ///
+    /// * `library` - Ontology library to write into
+    ///
+    /// # Returns
+    ///
+    /// Number of processes exported
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use ckp_core::{OntologyLibrary, ProcessTracker};
+    /// # use std::path::PathBuf;
+    /// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let tracker = ProcessTracker::new(PathBuf::from("/project"))?;
+    /// let library = OntologyLibrary::new(PathBuf::from("/project"))?;
+    /// tracker.export_rdf(&library)?;
+    ///
+    /// let rows = library.query_sparql(r#"
+    ///     PREFIX obo: <http://purl.obolibrary.org/obo/>
+    ///     SELECT ?process WHERE { ?process obo:BFO_0000055 <ckp://Role#edge-manager> }
+    /// "#)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn export_rdf(&self, library: &OntologyLibrary) -> Result<usize> {
+        let processes = self.query_processes(QueryFilters {
+            limit: Some(usize::MAX),
+            ..Default::default()
+        })?;
+
+        let mut quads = Vec::new();
+        for process in &processes {
+            process_to_quads(process, &mut quads)?;
+        }
+
+        library.insert_quads(quads)?;
+
+        Ok(processes.len())
+    }
+
     /// Get provenance chain for an instance
     ///
     /// # Arguments
@@ -822,6 +1267,353 @@ impl ProcessTracker {
     }
 }
 
+/// Environment variable selecting process tracking verbosity (off, lifecycle, phases)
+pub const PROCESS_TRACKING_ENV: &str = "CKP_PROCESS_TRACKING";
+
+/// Environment variable with the share of transactions tracked (0.0 to 1.0)
+pub const PROCESS_SAMPLE_RATE_ENV: &str = "CKP_PROCESS_SAMPLE_RATE";
+
+/// How much of a hooked process is recorded
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TrackingVerbosity {
+    /// Nothing is recorded
+    Off,
+
+    /// Process creation and its completion or failure
+    Lifecycle,
+
+    /// Lifecycle plus a temporal part for every phase
+    #[default]
+    Phases,
+}
+
+impl TrackingVerbosity {
+    /// Parse a verbosity name (`off`, `lifecycle` or `phases`)
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "off" | "none" | "0" => Some(Self::Off),
+            "lifecycle" => Some(Self::Lifecycle),
+            "phases" | "on" | "1" => Some(Self::Phases),
+            _ => None,
+        }
+    }
+
+    /// Verbosity set through `CKP_PROCESS_TRACKING`
+    pub fn from_env() -> Option<Self> {
+        std::env::var(PROCESS_TRACKING_ENV).ok().and_then(|value| Self::parse(&value))
+    }
+}
+
+/// Opt-in hook feeding a `ProcessTracker` from the runtime
+///
+/// Kernels record a process per emit, edge kernels per routed instance and
+/// governors per tool run. Only a sampled share of transactions is tracked;
+/// the decision hashes the transaction ID, so every hook in every process
+/// makes the same choice for a given transaction. Tracking errors are
+/// logged and never fail the work being tracked.
+///
+/// # Example
+///
+/// ```
+/// # use ckp_core::process_tracker::{ProcessTracker, ProcessTracking, TrackingVerbosity};
+/// # use std::path::PathBuf;
+/// # use std::sync::Arc;
+/// let tracker = Arc::new(ProcessTracker::new(PathBuf::from("/tmp")).unwrap());
+/// let tracking = ProcessTracking::new(tracker)
+///     .with_verbosity(TrackingVerbosity::Lifecycle)
+///     .with_sample_rate(0.1);
+/// ```
+#[derive(Clone)]
+pub struct ProcessTracking {
+    tracker: Arc<ProcessTracker>,
+    verbosity: TrackingVerbosity,
+    sample_rate: f64,
+}
+
+impl std::fmt::Debug for ProcessTracking {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ProcessTracking")
+            .field("processes_dir", &self.tracker.processes_dir)
+            .field("verbosity", &self.verbosity)
+            .field("sample_rate", &self.sample_rate)
+            .finish()
+    }
+}
+
+impl ProcessTracking {
+    /// Track every transaction with all of its phases
+    pub fn new(tracker: Arc<ProcessTracker>) -> Self {
+        Self {
+            tracker,
+            verbosity: TrackingVerbosity::Phases,
+            sample_rate: 1.0,
+        }
+    }
+
+    /// Tracking configured through `CKP_PROCESS_TRACKING` and `CKP_PROCESS_SAMPLE_RATE`
+    ///
+    /// `None` unless `CKP_PROCESS_TRACKING` names a verbosity other than `off`.
+    pub fn from_env(concepts_root: &Path) -> Option<Self> {
+        match TrackingVerbosity::from_env()? {
+            TrackingVerbosity::Off => None,
+            _ => match ProcessTracker::new(concepts_root.to_path_buf()) {
+                Ok(tracker) => Some(Self::new(Arc::new(tracker)).with_env()),
+                Err(e) => {
+                    tracing::warn!(error = %e, "process tracking disabled");
+                    None
+                }
+            },
+        }
+    }
+
+    /// Apply `CKP_PROCESS_TRACKING` and `CKP_PROCESS_SAMPLE_RATE` where set
+    pub fn with_env(mut self) -> Self {
+        if let Some(verbosity) = TrackingVerbosity::from_env() {
+            self = self.with_verbosity(verbosity);
+        }
+        let sample_rate = std::env::var(PROCESS_SAMPLE_RATE_ENV).ok().and_then(|rate| rate.trim().parse::<f64>().ok());
+        if let Some(sample_rate) = sample_rate {
+            self = self.with_sample_rate(sample_rate);
+        }
+        self
+    }
+
+    /// Set how much of each process is recorded
+    pub fn with_verbosity(mut self, verbosity: TrackingVerbosity) -> Self {
+        self.verbosity = verbosity;
+        self
+    }
+
+    /// Set the share of transactions tracked (clamped to 0.0..=1.0)
+    pub fn with_sample_rate(mut self, sample_rate: f64) -> Self {
+        self.sample_rate = if sample_rate.is_nan() { 0.0 } else { sample_rate.clamp(0.0, 1.0) };
+        self
+    }
+
+    /// Tracker the processes are recorded in
+    pub fn tracker(&self) -> &Arc<ProcessTracker> {
+        &self.tracker
+    }
+
+    pub fn verbosity(&self) -> TrackingVerbosity {
+        self.verbosity
+    }
+
+    /// Whether a transaction's processes are tracked
+    pub fn is_sampled(&self, tx_id: &str) -> bool {
+        if self.verbosity == TrackingVerbosity::Off || self.sample_rate <= 0.0 {
+            return false;
+        }
+        if self.sample_rate >= 1.0 {
+            return true;
+        }
+
+        // FNV-1a: the same on every run and in every process
+        let hash = tx_id
+            .bytes()
+            .fold(0xcbf2_9ce4_8422_2325u64, |hash, byte| (hash ^ byte as u64).wrapping_mul(0x0100_0000_01b3));
+        ((hash % 10_000) as f64) < self.sample_rate * 10_000.0
+    }
+
+    /// Start tracking a process for a transaction, if it is sampled
+    pub fn begin(
+        &self,
+        process_type: &str,
+        tx_id: &str,
+        participants: HashMap<String, Value>,
+        metadata: HashMap<String, Value>,
+    ) -> Option<TrackedProcess> {
+        if !self.is_sampled(tx_id) {
+            return None;
+        }
+
+        match self.tracker.create_process(process_type, tx_id, participants, metadata) {
+            Ok(process) => Some(TrackedProcess {
+                tracker: Arc::clone(&self.tracker),
+                verbosity: self.verbosity,
+                urn: process.urn,
+            }),
+            Err(e) => {
+                tracing::warn!(process_type, tx_id, error = %e, "failed to create process");
+                None
+            }
+        }
+    }
+}
+
+/// Process started by a `ProcessTracking` hook
+#[derive(Clone)]
+pub struct TrackedProcess {
+    tracker: Arc<ProcessTracker>,
+    verbosity: TrackingVerbosity,
+    urn: String,
+}
+
+impl std::fmt::Debug for TrackedProcess {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TrackedProcess")
+            .field("urn", &self.urn)
+            .field("verbosity", &self.verbosity)
+            .finish()
+    }
+}
+
+impl TrackedProcess {
+    /// Process URN
+    pub fn urn(&self) -> &str {
+        &self.urn
+    }
+
+    /// Record a phase (with `TrackingVerbosity::Phases` only)
+    pub fn phase(&self, phase: &str, data: HashMap<String, Value>) {
+        if self.verbosity != TrackingVerbosity::Phases {
+            return;
+        }
+        if let Err(e) = self.tracker.add_temporal_part(&self.urn, phase, data) {
+            tracing::warn!(process = %self.urn, phase, error = %e, "failed to record process phase");
+        }
+    }
+
+    /// Record a phase that has just ended, with how long it took
+    ///
+    /// The duration is stored under `PHASE_DURATION_KEY` in the phase data
+    /// (with `TrackingVerbosity::Phases` only, like `phase`).
+    pub fn timed_phase(&self, phase: &str, elapsed: Duration, mut data: HashMap<String, Value>) {
+        let millis = i64::try_from(elapsed.as_millis()).unwrap_or(i64::MAX);
+        data.insert(PHASE_DURATION_KEY.to_string(), Value::from(millis));
+        self.phase(phase, data);
+    }
+
+    /// Mark the process completed
+    pub fn complete(&self, result: HashMap<String, Value>) {
+        if let Err(e) = self.tracker.complete_process(&self.urn, result) {
+            tracing::warn!(process = %self.urn, error = %e, "failed to complete process");
+        }
+    }
+
+    /// Mark the process failed
+    pub fn fail(&self, error: &str) {
+        if let Err(e) = self.tracker.fail_process(&self.urn, error) {
+            tracing::warn!(process = %self.urn, error = %e, "failed to record process failure");
+        }
+    }
+}
+
+const CKP_NS: &str = "https://conceptkernel.org/ontology#";
+const OBO_NS: &str = "http://purl.obolibrary.org/obo/";
+
+fn ckp(local: &str) -> NamedNode {
+    NamedNode::new_unchecked(format!("{}{}", CKP_NS, local))
+}
+
+fn obo(local: &str) -> NamedNode {
+    NamedNode::new_unchecked(format!("{}{}", OBO_NS, local))
+}
+
+fn iri(value: &str) -> Result<NamedNode> {
+    NamedNode::new(value).map_err(|e| CkpError::Ontology(format!("Invalid IRI '{}': {}", value, e)))
+}
+
+fn date_time(value: &str) -> Literal {
+    Literal::new_typed_literal(value, xsd::DATE_TIME)
+}
+
+/// Map one process (and its region/parts) to BFO-aligned quads
+fn process_to_quads(process: &Process, quads: &mut Vec<Quad>) -> Result<()> {
+    let subject = iri(&process.urn)?;
+    let mut add = |s: &NamedNode, p: NamedNode, o: Term| {
+        quads.push(Quad::new(s.clone(), p, o, GraphName::DefaultGraph));
+    };
+
+    add(&subject, rdf::TYPE.into_owned(), ckp("Process").into());
+    add(&subject, rdf::TYPE.into_owned(), iri(BfoEntityType::Process.uri())?.into());
+    add(&subject, ckp("processType"), Literal::new_simple_literal(&process.process_type).into());
+    add(&subject, ckp("txId"), Literal::new_simple_literal(&process.tx_id).into());
+    add(&subject, ckp("status"), Literal::new_simple_literal(&process.status).into());
+
+    // Temporal region
+    let region = iri(&format!("{}/region", process.urn))?;
+    add(&subject, obo("BFO_0000199"), region.clone().into());
+    add(&region, rdf::TYPE.into_owned(), iri(BfoEntityType::TemporalRegion.uri())?.into());
+    for node in [&subject, &region] {
+        add(node, ckp("startTime"), date_time(&process.temporal_region.start).into());
+        if let Some(end) = &process.temporal_region.end {
+            add(node, ckp("endTime"), date_time(end).into());
+        }
+    }
+    if let Some(duration) = process.temporal_region.duration {
+        add(&region, ckp("duration"), Literal::from(duration).into());
+    }
+
+    // Temporal parts (phases)
+    for (index, part) in process.temporal_parts.iter().enumerate() {
+        let node = iri(&format!("{}/part/{}", process.urn, index))?;
+        add(&subject, obo("BFO_0000117"), node.clone().into());
+        add(&node, rdf::TYPE.into_owned(), iri(BfoEntityType::TemporalPart.uri())?.into());
+        add(&node, ckp("phase"), Literal::new_simple_literal(&part.phase).into());
+        add(&node, ckp("timestamp"), date_time(&part.timestamp).into());
+        add(&node, ckp("sequence"), Literal::from(index as i64).into());
+    }
+
+    // Participants: IRIs when the value is one, plain literals otherwise
+    for (key, value) in &process.participants {
+        let values: Vec<&str> = match value {
+            Value::String(s) => vec![s.as_str()],
+            Value::Array(items) => items.iter().filter_map(|v| v.as_str()).collect(),
+            _ => continue,
+        };
+        let realizes = key == "role" || key.ends_with("_role");
+
+        for value in values {
+            let object: Term = match NamedNode::new(value) {
+                Ok(node) if value.contains("://") => node.into(),
+                _ => Literal::new_simple_literal(value).into(),
+            };
+            add(&subject, obo("RO_0000057"), object.clone());
+            if realizes {
+                add(&subject, obo("BFO_0000055"), object);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Add the durations of a process's timed phases to per-phase samples
+fn collect_phase_durations(process: &Process, samples: &mut HashMap<String, Vec<i64>>) {
+    for part in &process.temporal_parts {
+        if let Some(millis) = part.data.get(PHASE_DURATION_KEY).and_then(Value::as_i64) {
+            samples.entry(part.phase.clone()).or_default().push(millis);
+        }
+    }
+}
+
+/// Failure ratio helper (0.0 when there is nothing to divide)
+fn ratio(part: usize, total: usize) -> f64 {
+    if total > 0 {
+        part as f64 / total as f64
+    } else {
+        0.0
+    }
+}
+
+/// Convert raw (total, failed, durations) accumulators into GroupStatistics
+fn into_group_statistics(
+    groups: HashMap<String, (usize, usize, Vec<i64>)>,
+) -> HashMap<String, GroupStatistics> {
+    groups
+        .into_iter()
+        .map(|(key, (total, failed, durations))| {
+            (key, GroupStatistics {
+                total,
+                failed,
+                failure_ratio: ratio(failed, total),
+                durations: DurationPercentiles::from_samples(durations),
+            })
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -904,6 +1696,32 @@ mod tests {
         assert_eq!(loaded.error, Some("Test error".to_string()));
     }
 
+    #[test]
+    fn test_process_tracking_sampling_and_verbosity() {
+        let (_temp, tracker) = setup_tracker();
+        let tracker = Arc::new(tracker);
+
+        // Lifecycle verbosity skips phases but records completion
+        let lifecycle = ProcessTracking::new(tracker.clone()).with_verbosity(TrackingVerbosity::Lifecycle);
+        let process = lifecycle.begin("emit", "1000-00000001", HashMap::new(), HashMap::new()).unwrap();
+        process.phase("delivered", HashMap::new());
+        process.complete(HashMap::new());
+        let loaded = tracker.load_process(process.urn()).unwrap();
+        assert_eq!(loaded.status, "completed");
+        assert_eq!(loaded.temporal_parts.len(), 1);
+
+        // Sampling is decided by transaction ID alone
+        let sampled = ProcessTracking::new(tracker.clone()).with_sample_rate(0.5);
+        let tx_ids: Vec<String> = (0..200).map(|i| format!("1000-{:08x}", i)).collect();
+        let tracked = tx_ids.iter().filter(|tx_id| sampled.is_sampled(tx_id)).count();
+        assert!(tracked > 50 && tracked < 150);
+        assert!(tx_ids.iter().all(|tx_id| sampled.is_sampled(tx_id) == sampled.clone().is_sampled(tx_id)));
+
+        let off = ProcessTracking::new(tracker.clone()).with_verbosity(TrackingVerbosity::Off);
+        assert!(off.begin("emit", "1000-00000002", HashMap::new(), HashMap::new()).is_none());
+        assert!(ProcessTracking::new(tracker).with_sample_rate(0.0).begin("emit", "1000-00000003", HashMap::new(), HashMap::new()).is_none());
+    }
+
     #[test]
     fn test_load_process() {
         let (_temp, tracker) = setup_tracker();
@@ -1154,4 +1972,160 @@ mod tests {
         assert!(chain1[0].temporal_region.start < chain2[0].temporal_region.start);
         assert!(chain2[0].temporal_region.start < chain3[0].temporal_region.start);
     }
+
+    #[tokio::test]
+    async fn test_subscribe_receives_lifecycle_events() {
+        use tokio_stream::StreamExt;
+
+        let (_temp, tracker) = setup_tracker();
+        let mut events = tracker.subscribe(QueryFilters::default());
+
+        let process = tracker.create_process("invoke", "test-1", HashMap::new(), HashMap::new()).unwrap();
+        tracker.add_temporal_part(&process.urn, "processing", HashMap::new()).unwrap();
+        tracker.complete_process(&process.urn, HashMap::new()).unwrap();
+
+        let started = events.next().await.unwrap();
+        assert_eq!(started.kind, ProcessEventKind::Started);
+        assert_eq!(started.process.tx_id, "test-1");
+
+        let phase = events.next().await.unwrap();
+        assert_eq!(phase.kind, ProcessEventKind::PhaseChanged);
+        assert_eq!(phase.phase.as_deref(), Some("processing"));
+
+        let completed = events.next().await.unwrap();
+        assert_eq!(completed.kind, ProcessEventKind::Completed);
+        assert_eq!(completed.process.status, "completed");
+    }
+
+    #[tokio::test]
+    async fn test_subscribe_applies_filters() {
+        use tokio_stream::StreamExt;
+
+        let (_temp, tracker) = setup_tracker();
+        let mut failures = tracker.subscribe(QueryFilters {
+            status: Some("failed".to_string()),
+            ..Default::default()
+        });
+
+        let ok = tracker.create_process("invoke", "test-ok", HashMap::new(), HashMap::new()).unwrap();
+        tracker.complete_process(&ok.urn, HashMap::new()).unwrap();
+
+        let bad = tracker.create_process("invoke", "test-bad", HashMap::new(), HashMap::new()).unwrap();
+        tracker.fail_process(&bad.urn, "boom").unwrap();
+
+        let event = failures.next().await.unwrap();
+        assert_eq!(event.kind, ProcessEventKind::Failed);
+        assert_eq!(event.process.tx_id, "test-bad");
+        assert_eq!(event.process.error.as_deref(), Some("boom"));
+    }
+
+    #[test]
+    fn test_duration_percentiles() {
+        let samples: Vec<i64> = (1..=100).collect();
+        let pct = DurationPercentiles::from_samples(samples);
+        assert_eq!(pct.count, 100);
+        assert_eq!(pct.p50, 50);
+        assert_eq!(pct.p95, 95);
+        assert_eq!(pct.p99, 99);
+
+        assert_eq!(DurationPercentiles::from_samples(Vec::new()), DurationPercentiles::default());
+        assert_eq!(DurationPercentiles::from_samples(vec![7]).p99, 7);
+    }
+
+    #[test]
+    fn test_statistics_per_kernel_and_edge() {
+        let (_temp, tracker) = setup_tracker();
+
+        let mut participants = HashMap::new();
+        participants.insert("kernel".to_string(), Value::String("ckp://A:v1".to_string()));
+        participants.insert("edge".to_string(), Value::String("PRODUCES.A".to_string()));
+
+        let p1 = tracker.create_process("invoke", "test-1", participants.clone(), HashMap::new()).unwrap();
+        tracker.complete_process(&p1.urn, HashMap::new()).unwrap();
+        let p2 = tracker.create_process("invoke", "test-2", participants, HashMap::new()).unwrap();
+        tracker.fail_process(&p2.urn, "error").unwrap();
+
+        let stats = tracker.get_statistics_with_windows(QueryFilters::default(), &[60]).unwrap();
+        assert_eq!(stats.failure_ratio, 0.5);
+        assert_eq!(stats.durations.count, 2);
+
+        let kernel = stats.by_kernel.get("ckp://A:v1").unwrap();
+        assert_eq!(kernel.total, 2);
+        assert_eq!(kernel.failed, 1);
+        assert_eq!(kernel.failure_ratio, 0.5);
+        assert_eq!(stats.by_edge.get("PRODUCES.A").unwrap().total, 2);
+
+        assert_eq!(stats.throughput.len(), 1);
+        assert_eq!(stats.throughput[0].count, 2);
+
+        let snapshot = stats.snapshot();
+        let json = serde_json::to_value(&snapshot).unwrap();
+        assert_eq!(json["kernels"]["ckp://A:v1"]["failed"], 1);
+        assert!(json.get("byStatus").is_none());
+    }
+
+    #[test]
+    fn test_phase_breakdown_from_timed_phases() {
+        let (_temp, tracker) = setup_tracker();
+        let tracking = ProcessTracking::new(Arc::new(tracker));
+
+        for (tx_id, archive_ms) in [("1000-00000001", 40), ("1000-00000002", 900)] {
+            let mut participants = HashMap::new();
+            participants.insert("kernel".to_string(), serde_json::json!("Shop.Orders"));
+            let process = tracking.begin("tool-run", tx_id, participants, HashMap::new()).unwrap();
+            process.timed_phase("execution", Duration::from_millis(120), HashMap::new());
+            process.timed_phase("archive", Duration::from_millis(archive_ms), HashMap::new());
+            process.phase("processing", HashMap::new());
+            process.complete(HashMap::new());
+        }
+
+        let breakdown = tracking.tracker().get_phase_breakdown(QueryFilters {
+            kernel: Some("Shop.Orders".to_string()),
+            ..Default::default()
+        }).unwrap();
+        assert_eq!(breakdown.keys().collect::<Vec<_>>(), vec!["archive", "execution"]);
+        assert_eq!(breakdown["execution"].p50, 120);
+        assert_eq!((breakdown["archive"].count, breakdown["archive"].p99), (2, 900));
+
+        let stats = tracking.tracker().get_statistics(QueryFilters::default()).unwrap();
+        assert_eq!(stats.by_phase["archive"], breakdown["archive"]);
+        assert_eq!(stats.snapshot().phases, breakdown);
+    }
+
+    #[test]
+    fn test_export_rdf() {
+        let (temp, tracker) = setup_tracker();
+        let library = OntologyLibrary::new(temp.path().to_path_buf()).unwrap();
+
+        let mut participants = HashMap::new();
+        participants.insert("kernel".to_string(), Value::String("ckp://Kernel#A".to_string()));
+        participants.insert("role".to_string(), Value::String("ckp://Role#edge-manager".to_string()));
+
+        let p1 = tracker.create_process("invoke", "test-1", participants, HashMap::new()).unwrap();
+        tracker.add_temporal_part(&p1.urn, "processing", HashMap::new()).unwrap();
+        tracker.complete_process(&p1.urn, HashMap::new()).unwrap();
+        let p2 = tracker.create_process("invoke", "test-2", HashMap::new(), HashMap::new()).unwrap();
+        tracker.complete_process(&p2.urn, HashMap::new()).unwrap();
+
+        assert_eq!(tracker.export_rdf(&library).unwrap(), 2);
+
+        let rows = library.query_sparql(r#"
+            PREFIX obo: <http://purl.obolibrary.org/obo/>
+            SELECT ?process WHERE {
+                ?process a obo:BFO_0000015 ;
+                         obo:BFO_0000055 <ckp://Role#edge-manager> .
+            }
+        "#).unwrap();
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0]["process"], format!("<{}>", p1.urn));
+
+        let rows = library.query_sparql(&format!(r#"
+            PREFIX obo: <http://purl.obolibrary.org/obo/>
+            PREFIX ckp: <https://conceptkernel.org/ontology#>
+            SELECT ?phase WHERE {{ <{}> obo:BFO_0000117 ?part . ?part ckp:phase ?phase }}
+        "#, p1.urn)).unwrap();
+        assert_eq!(rows.len(), 2);
+
+        assert!(library.processes_overlap(&p1.urn, &p1.urn).unwrap());
+    }
 }
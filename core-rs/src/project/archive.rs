@@ -0,0 +1,394 @@
+/**
+ * archive.rs
+ * Project export/import for moving a whole environment between hosts
+ *
+ * An archive is a tar.gz with a `project-archive.json` manifest at its root
+ * and the project under `project/`:
+ * - .ckproject and ckp.lock
+ * - every kernel under concepts/ without runtime state (queues,
+ *   transactions, logs, consensus, archive) or storage index sidecars
+ * - kernel storage, when exported with `include_storage`
+ *
+ * Symlinks resolving to an exported path are stored as relative links, so
+ * edge and storage links survive the move. Links to other files are
+ * replaced by the file; links to directories outside the archive or to
+ * nothing are left out and listed in the manifest.
+ *
+ * Import registers the project under a fresh slot and gives every kernel
+ * the port at its old offset in the new range, or the next free one.
+ */
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::config::{PortConfig, ProjectConfig};
+use super::registry::{ProjectEntry, ProjectInfo, ProjectRegistry};
+use crate::cache::pack::{EXCLUDED_DIRS, RUNTIME_STATE_DIRS};
+use crate::cache::LOCKFILE_NAME;
+use crate::errors::CkpError;
+use crate::port::PortManager;
+use crate::storage::bundle::{self, Content};
+use crate::storage::{BundleFile, INDEX_FILE, INDEX_META_FILE};
+
+/// Manifest file name at the archive root
+pub const PROJECT_ARCHIVE_MANIFEST: &str = "project-archive.json";
+
+/// Current project archive format version
+pub const PROJECT_ARCHIVE_VERSION: u32 = 1;
+
+/// Directory holding the project inside an archive
+const PROJECT_DIR: &str = "project";
+
+/// Project archive manifest (`project-archive.json`)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectArchiveManifest {
+    pub version: u32,
+
+    /// Name of the exported project
+    pub project: String,
+
+    pub exported_at: DateTime<Utc>,
+
+    /// Whether kernel storage was exported
+    pub includes_storage: bool,
+
+    /// Kernel ports as offsets from the project's base port
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub port_offsets: BTreeMap<String, u16>,
+
+    /// Files relative to the project root
+    pub files: Vec<BundleFile>,
+
+    /// Symlinks left out because they point outside the archive to a
+    /// directory or to nothing
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub skipped_links: Vec<String>,
+}
+
+impl ProjectRegistry {
+    /// Export a registered project into a portable archive
+    ///
+    /// # Arguments
+    /// * `project` - Project name
+    /// * `dest` - Path of the tar.gz to write
+    /// * `include_storage` - Also export every kernel's storage instances
+    ///
+    /// # Returns
+    /// Manifest written into the archive
+    ///
+    /// # Errors
+    /// Returns `CkpError::ProjectNotFound` if the project is not registered
+    pub fn export(
+        &mut self,
+        project: &str,
+        dest: &Path,
+        include_storage: bool,
+    ) -> Result<ProjectArchiveManifest, CkpError> {
+        let entry = self.get(project)?.ok_or(CkpError::ProjectNotFound)?;
+        let root = PathBuf::from(&entry.path);
+        ProjectConfig::load_raw(root.join(".ckproject"))?;
+
+        let ports = PortManager::new(&root)?;
+        let port_offsets = match ports.get_base_port() {
+            Some(base) => ports
+                .get_all_allocations()
+                .iter()
+                .filter(|(_, port)| **port >= base)
+                .map(|(kernel, &port)| (kernel.clone(), port - base))
+                .collect(),
+            None => BTreeMap::new(),
+        };
+
+        let mut manifest = ProjectArchiveManifest {
+            version: PROJECT_ARCHIVE_VERSION,
+            project: entry.name,
+            exported_at: Utc::now(),
+            includes_storage: include_storage,
+            port_offsets,
+            files: Vec::new(),
+            skipped_links: Vec::new(),
+        };
+        let contents: Vec<(String, Content)> = collect_project(&root, include_storage, &mut manifest)?
+            .into_iter()
+            .map(|(path, content)| (format!("{}/{}", PROJECT_DIR, path), content))
+            .collect();
+
+        bundle::write_bundle(dest, PROJECT_ARCHIVE_MANIFEST, &manifest, &contents)?;
+        Ok(manifest)
+    }
+
+    /// Import a project archive written by `ProjectRegistry::export`
+    ///
+    /// Unpacks the project into `dest` (which must be missing or empty),
+    /// registers it under a fresh slot, records the new ports in its
+    /// `.ckproject` and allocates kernel ports in the new range. Nothing is
+    /// unpacked into `dest` if the archive fails its checksums.
+    ///
+    /// # Returns
+    /// Registry entry of the imported project
+    ///
+    /// # Errors
+    /// Returns error if:
+    /// - `dest` is not empty
+    /// - A project with the same name is already registered
+    /// - The archive fails its checksums or holds unsafe paths
+    pub fn import(&mut self, archive: &Path, dest: &Path) -> Result<ProjectEntry, CkpError> {
+        if dest.is_file() || fs::read_dir(dest).is_ok_and(|mut entries| entries.next().is_some()) {
+            return Err(CkpError::ProjectError(format!(
+                "Import destination {} is not empty",
+                dest.display()
+            )));
+        }
+
+        let parent = dest.parent().filter(|p| !p.as_os_str().is_empty()).unwrap_or(Path::new("."));
+        let staging = parent.join(format!(".import-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&staging).map_err(|e| {
+            CkpError::IoError(format!("Failed to create import staging directory: {}", e))
+        })?;
+
+        let result = self.import_staged(archive, &staging, dest);
+        let _ = fs::remove_dir_all(&staging);
+        result
+    }
+
+    /// Unpack and verify an archive in `staging`, then move it to `dest`
+    fn import_staged(&mut self, archive: &Path, staging: &Path, dest: &Path) -> Result<ProjectEntry, CkpError> {
+        bundle::unpack(archive, staging, 1)?;
+
+        let manifest: ProjectArchiveManifest =
+            serde_json::from_slice(&bundle::read(&staging.join(PROJECT_ARCHIVE_MANIFEST))?)?;
+        if manifest.version > PROJECT_ARCHIVE_VERSION {
+            return Err(CkpError::ValidationError(format!(
+                "Project archive version {} is newer than supported version {}",
+                manifest.version, PROJECT_ARCHIVE_VERSION
+            )));
+        }
+
+        let unpacked = staging.join(PROJECT_DIR);
+        bundle::verify_files(&unpacked, &manifest.project, &manifest.files)?;
+
+        let mut config = ProjectConfig::load_raw(unpacked.join(".ckproject"))?;
+        if let Some(existing) = self.get(&config.metadata.name)? {
+            return Err(CkpError::ProjectAlreadyRegistered(format!(
+                "Project \"{}\" is already registered at slot {}",
+                existing.name, existing.slot
+            )));
+        }
+
+        if dest.exists() {
+            fs::remove_dir(dest).map_err(|e| {
+                CkpError::IoError(format!("Failed to replace {}: {}", dest.display(), e))
+            })?;
+        }
+        fs::rename(&unpacked, dest).map_err(|e| {
+            CkpError::IoError(format!("Failed to move project into {}: {}", dest.display(), e))
+        })?;
+        let root = fs::canonicalize(dest).map_err(|e| {
+            CkpError::IoError(format!("Failed to resolve {}: {}", dest.display(), e))
+        })?;
+
+        let entry = match self.register(ProjectInfo {
+            name: config.metadata.name.clone(),
+            id: config.metadata.id.clone(),
+            path: root.to_string_lossy().to_string(),
+            version: config.spec.version.clone(),
+            preferred_slot: None,
+        }) {
+            Ok(entry) => entry,
+            Err(e) => {
+                // Hand the files back to staging so they are cleaned up
+                let _ = fs::rename(&root, &unpacked);
+                return Err(e);
+            }
+        };
+
+        config.spec.ports = Some(PortConfig {
+            base_port: entry.discovery_port,
+            slot: entry.slot,
+        });
+        config.save(root.join(".ckproject"))?;
+
+        let mut ports = PortManager::new(&root)?;
+        ports.set_base_port(entry.discovery_port)?;
+        for (kernel, offset) in &manifest.port_offsets {
+            ports.allocate(kernel, Some(*offset))?;
+        }
+
+        Ok(entry)
+    }
+}
+
+/// Files of a project, with symlinks resolved as described above
+fn collect_project(
+    root: &Path,
+    include_storage: bool,
+    manifest: &mut ProjectArchiveManifest,
+) -> Result<Vec<(String, Content)>, CkpError> {
+    let canonical_root = fs::canonicalize(root).map_err(|e| {
+        CkpError::IoError(format!("Failed to resolve {}: {}", root.display(), e))
+    })?;
+    let mut contents = Vec::new();
+
+    let walker = walkdir::WalkDir::new(root)
+        .min_depth(1)
+        .sort_by_file_name()
+        .into_iter()
+        .filter_entry(|entry| {
+            is_exported(entry.path().strip_prefix(root).unwrap_or(entry.path()), include_storage)
+        });
+
+    for entry in walker {
+        let entry = entry.map_err(|e| CkpError::IoError(format!("Failed to walk project: {}", e)))?;
+        if entry.file_type().is_dir() {
+            continue;
+        }
+        let relative = bundle::to_slash(entry.path().strip_prefix(root).unwrap_or(entry.path()));
+
+        let content = if entry.path_is_symlink() {
+            match fs::canonicalize(entry.path()) {
+                Ok(target) if target.strip_prefix(&canonical_root).is_ok_and(|t| is_exported(t, include_storage)) => {
+                    let from = entry.path().parent()
+                        .and_then(|p| fs::canonicalize(p).ok())
+                        .unwrap_or_else(|| canonical_root.clone());
+                    Content::Link(bundle::relative_link(&from, &target))
+                }
+                Ok(target) if target.is_file() => Content::File(bundle::read(&target)?),
+                _ => {
+                    manifest.skipped_links.push(relative);
+                    continue;
+                }
+            }
+        } else {
+            Content::File(bundle::read(entry.path())?)
+        };
+
+        manifest.files.push(match &content {
+            Content::File(bytes) => BundleFile { path: relative.clone(), sha256: Some(hex::encode(Sha256::digest(bytes))), link: None },
+            Content::Link(target) => BundleFile { path: relative.clone(), sha256: None, link: Some(target.clone()) },
+        });
+        contents.push((relative, content));
+    }
+
+    Ok(contents)
+}
+
+/// Whether a path relative to the project root belongs in an archive
+fn is_exported(relative: &Path, include_storage: bool) -> bool {
+    let parts: Vec<String> = relative
+        .components()
+        .map(|c| c.as_os_str().to_string_lossy().to_string())
+        .collect();
+    let Some(name) = parts.last() else {
+        return true;
+    };
+
+    if parts[0] != "concepts" {
+        return parts.len() == 1 && (name == ".ckproject" || name == LOCKFILE_NAME);
+    }
+
+    // Storage instances are kept whole; everything else drops runtime state
+    for part in parts.iter().skip(1) {
+        if part == "storage" {
+            return include_storage;
+        }
+        if RUNTIME_STATE_DIRS.contains(&part.as_str()) || EXCLUDED_DIRS.contains(&part.as_str()) {
+            return false;
+        }
+    }
+    !(name == INDEX_FILE || name == INDEX_META_FILE || name.ends_with(".pid"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::os::unix::fs::symlink;
+    use tempfile::TempDir;
+
+    fn setup_project(temp: &TempDir, registry: &mut ProjectRegistry) -> PathBuf {
+        let root = temp.path().join("shop");
+        let concepts = root.join("concepts");
+        fs::create_dir_all(concepts.join("Shop.Orders/queue/inbox")).unwrap();
+        fs::create_dir_all(concepts.join("Shop.Orders/storage/tx-1.inst")).unwrap();
+        fs::create_dir_all(concepts.join("Shop.Billing/storage")).unwrap();
+
+        ProjectConfig::new("shop".to_string(), "proj-shop".to_string(), "Org.Shop".to_string(), "1.3.14".to_string())
+            .save(root.join(".ckproject"))
+            .unwrap();
+        fs::write(root.join(LOCKFILE_NAME), "version: 1\npackages: []\n").unwrap();
+        fs::write(root.join("notes.txt"), "scratch").unwrap();
+        fs::write(concepts.join("Shop.Orders/conceptkernel.yaml"), "metadata:\n  name: Shop.Orders\n").unwrap();
+        fs::write(concepts.join("Shop.Orders/queue/inbox/tx-2.job"), "{}").unwrap();
+        fs::write(concepts.join("Shop.Orders/tool.pid"), "42").unwrap();
+        fs::write(concepts.join("Shop.Orders/storage/tx-1.inst/receipt.bin"), r#"{"id": "tx-1"}"#).unwrap();
+        fs::write(concepts.join("Shop.Billing/conceptkernel.yaml"), "metadata:\n  name: Shop.Billing\n").unwrap();
+        symlink("../../Shop.Orders/storage/tx-1.inst", concepts.join("Shop.Billing/storage/tx-1.inst")).unwrap();
+        symlink("../../Shop.Orders/queue", concepts.join("Shop.Billing/orders-queue")).unwrap();
+
+        let entry = registry.register(ProjectInfo {
+            name: "shop".to_string(),
+            id: "proj-shop".to_string(),
+            path: root.to_string_lossy().to_string(),
+            version: "1.3.14".to_string(),
+            preferred_slot: None,
+        }).unwrap();
+        let mut ports = PortManager::new(&root).unwrap();
+        ports.set_base_port(entry.discovery_port).unwrap();
+        ports.allocate("Shop.Orders", Some(5)).unwrap();
+
+        root
+    }
+
+    /// Test: Projects round-trip through an archive with links and port offsets intact
+    #[test]
+    fn test_export_import_project() {
+        let source = TempDir::new().unwrap();
+        let mut source_registry = ProjectRegistry::from_dir(source.path().join("registry")).unwrap();
+        let root = setup_project(&source, &mut source_registry);
+        let ports = PortManager::new(&root).unwrap();
+        let orders_offset = ports.get("Shop.Orders").unwrap() - ports.get_base_port().unwrap();
+
+        let archive = source.path().join("out/shop.tar.gz");
+        let manifest = source_registry.export("shop", &archive, true).unwrap();
+        let paths: Vec<&str> = manifest.files.iter().map(|f| f.path.as_str()).collect();
+        assert_eq!(paths, vec![
+            ".ckproject",
+            "ckp.lock",
+            "concepts/Shop.Billing/conceptkernel.yaml",
+            "concepts/Shop.Billing/storage/tx-1.inst",
+            "concepts/Shop.Orders/conceptkernel.yaml",
+            "concepts/Shop.Orders/storage/tx-1.inst/receipt.bin",
+        ]);
+        assert_eq!(manifest.skipped_links, vec!["concepts/Shop.Billing/orders-queue"]);
+        assert_eq!(manifest.port_offsets["Shop.Orders"], orders_offset);
+        assert!(matches!(source_registry.export("missing", &archive, true), Err(CkpError::ProjectNotFound)));
+
+        let target = TempDir::new().unwrap();
+        let mut target_registry = ProjectRegistry::from_dir(target.path().join("registry")).unwrap();
+        let dest = target.path().join("imported");
+        let entry = target_registry.import(&archive, &dest).unwrap();
+        assert_eq!(entry.name, "shop");
+
+        let receipt = dest.join("concepts/Shop.Billing/storage/tx-1.inst/receipt.bin");
+        assert_eq!(fs::read_to_string(receipt).unwrap(), r#"{"id": "tx-1"}"#);
+        assert!(!dest.join("concepts/Shop.Orders/queue").exists());
+        assert!(!dest.join("notes.txt").exists());
+
+        let config = ProjectConfig::load_from_project(&dest).unwrap();
+        assert_eq!(config.spec.ports, Some(PortConfig { base_port: entry.discovery_port, slot: entry.slot }));
+        let ports = PortManager::new(&dest).unwrap();
+        assert!(entry.port_range.start <= ports.get("Shop.Orders").unwrap());
+        assert!(ports.get("Shop.Orders").unwrap() <= entry.port_range.end);
+
+        assert!(matches!(
+            target_registry.import(&archive, &target.path().join("again")),
+            Err(CkpError::ProjectAlreadyRegistered(_))
+        ));
+        assert!(!target.path().join("again").exists());
+        assert!(matches!(target_registry.import(&archive, &dest), Err(CkpError::ProjectError(_))));
+    }
+}
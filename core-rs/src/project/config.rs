@@ -14,14 +14,21 @@
  *   version: 1.3.14
  * ```
  *
+ * String values may use `${ENV_VAR}` and `${secret:name}` references,
+ * resolved at load time (see crate::interpolation).
+ *
  * Reference: Node.js v1.3.14 - MULTI_PROJECT_INFRASTRUCTURE.md section 2
  */
 
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::fs;
 use std::path::Path;
 
+use crate::compliance::{RedactionConfig, RetentionConfig};
 use crate::errors::CkpError;
+use crate::interpolation::Interpolator;
+use super::quota::QuotaConfig;
 
 /// .ckproject file structure
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -56,10 +63,32 @@ pub struct Features {
 }
 
 /// Protocol domain resolution mapping
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+///
+/// Mappings with a `path` and `target` are also served by the gateway: a
+/// request on `path` is emitted to `target`, and `{path}/{txId}` answers with
+/// the receipt of that transaction (see `daemon::ProtocolMapper`).
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
 pub struct ProtocolMapping {
+    #[serde(default)]
     pub domain: String,
+    #[serde(default)]
     pub url: String,
+    /// Inbound HTTP path handled by the gateway (e.g. "/hooks/github")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+    /// HTTP method accepted on `path` (default POST)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub method: Option<String>,
+    /// Kernel URN inbound requests are emitted to
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub target: Option<String>,
+    /// Job payload fields, as JSON pointers into the request's
+    /// `{"body", "query", "headers"}` (default: the request body)
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub payload: BTreeMap<String, String>,
+    /// Response body fields, as JSON pointers into the receipt (default: its data)
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub response: BTreeMap<String, String>,
 }
 
 /// Default user for local development
@@ -124,6 +153,15 @@ pub struct Spec {
     /// Ontology library configuration (Phase 4 Stage 0)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub ontology: Option<OntologyConfig>,
+    /// Redaction rules for audit logs and data exports
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub redaction: Option<RedactionConfig>,
+    /// Data retention policy enforced by the retention daemon
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub retention: Option<RetentionConfig>,
+    /// Storage, instance and queue quotas per kernel and per project
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub quotas: Option<QuotaConfig>,
 }
 
 impl ProjectConfig {
@@ -138,7 +176,27 @@ impl ProjectConfig {
     /// assert_eq!(config.metadata.name, "my-project");
     /// ```
     pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, CkpError> {
-        let path = path.as_ref();
+        Self::load_inner(path.as_ref(), Some(&Interpolator::current()))
+    }
+
+    /// Load .ckproject resolving `${...}` references with a specific interpolator
+    ///
+    /// # Arguments
+    /// * `path` - Path to .ckproject file
+    /// * `interpolator` - Resolves environment variables and secrets
+    pub fn load_with<P: AsRef<Path>>(path: P, interpolator: &Interpolator) -> Result<Self, CkpError> {
+        Self::load_inner(path.as_ref(), Some(interpolator))
+    }
+
+    /// Load .ckproject as written, leaving `${...}` references in place
+    ///
+    /// Use this when the config is saved back, so resolved secrets are
+    /// never written to disk.
+    pub fn load_raw<P: AsRef<Path>>(path: P) -> Result<Self, CkpError> {
+        Self::load_inner(path.as_ref(), None)
+    }
+
+    fn load_inner(path: &Path, interpolator: Option<&Interpolator>) -> Result<Self, CkpError> {
 
         if !path.exists() {
             return Err(CkpError::FileNotFound(
@@ -150,8 +208,13 @@ impl ProjectConfig {
             CkpError::IoError(format!("Failed to read .ckproject: {}", e))
         })?;
 
-        let config: ProjectConfig = serde_yaml::from_str(&content).map_err(|e| {
-            CkpError::ParseError(format!("Invalid .ckproject YAML: {}", e))
+        let parsed = match interpolator {
+            Some(interpolator) => interpolator.parse_yaml(&content),
+            None => serde_yaml::from_str(&content).map_err(CkpError::from),
+        };
+        let config: ProjectConfig = parsed.map_err(|e| match e {
+            CkpError::Yaml(e) => CkpError::ParseError(format!("Invalid .ckproject YAML: {}", e)),
+            other => other,
         })?;
 
         // Validate required fields
@@ -268,6 +331,9 @@ impl ProjectConfig {
                 protocol: None,
                 default_user: None,
                 ontology: None,
+                redaction: None,
+                retention: None,
+                quotas: None,
             },
         }
     }
@@ -387,6 +453,35 @@ spec:
         assert_eq!(config.metadata.name, "test-project");
     }
 
+    #[test]
+    fn test_load_interpolates_environment() {
+        let temp_dir = TempDir::new().unwrap();
+        let ckproject_path = temp_dir.path().join(".ckproject");
+
+        std::env::set_var("CKP_TEST_CKPROJECT_DOMAIN", "Org.FromEnv");
+        fs::write(
+            &ckproject_path,
+            r#"
+apiVersion: conceptkernel/v1
+kind: Project
+metadata:
+  name: test-project
+  id: proj-test-20250125
+spec:
+  domain: ${CKP_TEST_CKPROJECT_DOMAIN}
+  version: ${CKP_TEST_CKPROJECT_VERSION:-1.3.14}
+"#,
+        )
+        .unwrap();
+
+        let config = ProjectConfig::load_with(&ckproject_path, &Interpolator::new()).unwrap();
+        assert_eq!(config.spec.domain, "Org.FromEnv");
+        assert_eq!(config.spec.version, "1.3.14");
+
+        let raw = ProjectConfig::load_raw(&ckproject_path).unwrap();
+        assert_eq!(raw.spec.domain, "${CKP_TEST_CKPROJECT_DOMAIN}");
+    }
+
     #[test]
     fn test_save_and_load() {
         let temp_dir = TempDir::new().unwrap();
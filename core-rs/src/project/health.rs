@@ -0,0 +1,311 @@
+/**
+ * health.rs
+ * Project-level health and status aggregation
+ *
+ * A ProjectHealth report combines, for every kernel in a project:
+ * - KernelStatus (type, PIDs, mode, inbox/staging/ready depths)
+ * - Depth of the per-edge queues (queue/edges/<PREDICATE>.<Source>/)
+ * - Daemon liveness (governor for cold kernels, tool for hot kernels)
+ * - Port allocation from .ckports, checked against the project's range
+ *
+ * Port drift between .ckports, the registry and live sockets is reported
+ * and repaired separately by `reconcile_ports`.
+ *
+ * Used by `ckr project status` and monitoring endpoints.
+ */
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::registry::{PortRange, ProjectRegistry};
+use crate::drivers::is_queue_entry;
+use crate::errors::CkpError;
+use crate::kernel::{KernelManager, KernelStatus};
+use crate::ontology::KernelClass;
+use crate::port::{PortManager, PortReconcileReport};
+
+/// Health of a single kernel in a project
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct KernelHealth {
+    /// Kernel status, with `port` taken from .ckports
+    #[serde(flatten)]
+    pub status: KernelStatus,
+
+    /// Instances waiting in per-edge queues
+    pub edge_queue_depth: usize,
+
+    /// Whether the kernel's daemon is running (the governor, or the tool of a hot kernel)
+    pub alive: bool,
+
+    /// Whether the allocated port lies outside the project's port range
+    pub port_out_of_range: bool,
+}
+
+impl KernelHealth {
+    /// Total instances waiting in inbox, staging, ready and edge queues
+    pub fn queue_depth(&self) -> usize {
+        let stats = &self.status.queue_stats;
+        stats.inbox + stats.staging + stats.ready + self.edge_queue_depth
+    }
+}
+
+/// Aggregated health report for a registered project
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectHealth {
+    /// Project name
+    pub project: String,
+
+    /// Project root directory
+    pub path: String,
+
+    pub checked_at: DateTime<Utc>,
+
+    /// Port range assigned by the registry
+    pub port_range: PortRange,
+
+    /// Health of every readable kernel, sorted by name
+    pub kernels: Vec<KernelHealth>,
+
+    /// Kernels whose status could not be read, with the reason
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub errors: BTreeMap<String, String>,
+
+    /// Port allocations for kernels that no longer exist
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub stale_ports: BTreeMap<String, u16>,
+}
+
+impl ProjectHealth {
+    /// Kernels whose daemon is running
+    pub fn alive_count(&self) -> usize {
+        self.kernels.iter().filter(|k| k.alive).count()
+    }
+
+    /// Total instances waiting across all kernel queues
+    pub fn total_queue_depth(&self) -> usize {
+        self.kernels.iter().map(KernelHealth::queue_depth).sum()
+    }
+
+    /// True when every kernel is readable, running and holds an in-range port
+    pub fn is_healthy(&self) -> bool {
+        self.errors.is_empty()
+            && self.kernels.iter().all(|k| k.alive && !k.port_out_of_range)
+    }
+}
+
+impl ProjectRegistry {
+    /// Aggregate the health of every kernel in a registered project
+    ///
+    /// # Arguments
+    /// * `project` - Project name
+    ///
+    /// # Returns
+    /// ProjectHealth report
+    ///
+    /// # Errors
+    /// Returns `CkpError::ProjectNotFound` if the project is not registered
+    pub async fn status(&mut self, project: &str) -> Result<ProjectHealth, CkpError> {
+        let entry = self.get(project)?.ok_or(CkpError::ProjectNotFound)?;
+        let root = PathBuf::from(&entry.path);
+
+        if !root.is_dir() {
+            return Err(CkpError::ProjectError(format!(
+                "Project directory not found: {}",
+                root.display()
+            )));
+        }
+
+        let manager = KernelManager::new(root.clone())?;
+        let mut allocations: BTreeMap<String, u16> = PortManager::new(&root)?
+            .get_all_allocations()
+            .iter()
+            .map(|(name, port)| (name.clone(), *port))
+            .collect();
+
+        let mut kernels = Vec::new();
+        let mut errors = BTreeMap::new();
+
+        for name in manager.list_kernels()? {
+            let allocated = allocations.remove(&name);
+
+            let mut status = match manager.get_kernel_status(&name).await {
+                Ok(status) => status,
+                Err(e) => {
+                    errors.insert(name, e.to_string());
+                    continue;
+                }
+            };
+            status.port = status.port.or(allocated);
+
+            let port_out_of_range = status
+                .port
+                .is_some_and(|port| port < entry.port_range.start || port > entry.port_range.end);
+
+            kernels.push(KernelHealth {
+                edge_queue_depth: edge_queue_depth(&manager.get_kernel_dir(&name))?,
+                alive: daemon_running(&status),
+                port_out_of_range,
+                status,
+            });
+        }
+
+        Ok(ProjectHealth {
+            project: entry.name,
+            path: entry.path,
+            checked_at: Utc::now(),
+            port_range: entry.port_range,
+            kernels,
+            errors,
+            stale_ports: allocations,
+        })
+    }
+}
+
+impl ProjectRegistry {
+    /// Reconcile a project's .ckports with its registry entry and live sockets
+    ///
+    /// Kernels whose tool process is running are expected to hold their own
+    /// port. See `PortManager::reconcile` for what is detected and repaired.
+    ///
+    /// # Arguments
+    /// * `project` - Project name
+    /// * `repair` - Apply fixes to .ckports
+    ///
+    /// # Errors
+    /// Returns `CkpError::ProjectNotFound` if the project is not registered
+    pub fn reconcile_ports(&mut self, project: &str, repair: bool) -> Result<PortReconcileReport, CkpError> {
+        let entry = self.get(project)?.ok_or(CkpError::ProjectNotFound)?;
+        let root = PathBuf::from(&entry.path);
+
+        let manager = KernelManager::new(root.clone())?;
+        let mut running = HashSet::new();
+        for name in manager.list_kernels()? {
+            if manager.find_running_pids(&name)?.pid.is_some() {
+                running.insert(name);
+            }
+        }
+
+        PortManager::new(&root)?.reconcile(Some(entry.port_range.start), &running, repair)
+    }
+}
+
+/// Whether a kernel's daemon has a live process
+///
+/// Warm and cold kernels without a governor are STANDBY or SLEEP, which is
+/// not DOWN but not running either.
+fn daemon_running(status: &KernelStatus) -> bool {
+    match status.class {
+        Some(KernelClass::Hot) => status.pid.is_some(),
+        Some(_) => status.watcher_pid.is_some(),
+        None => status.pid.is_some() || status.watcher_pid.is_some(),
+    }
+}
+
+/// Count instances waiting in a kernel's per-edge queues
+fn edge_queue_depth(kernel_dir: &Path) -> Result<usize, CkpError> {
+    let edges_dir = kernel_dir.join("queue").join("edges");
+    if !edges_dir.is_dir() {
+        return Ok(0);
+    }
+
+    let mut depth = 0;
+    for edge in fs::read_dir(&edges_dir)? {
+        let edge = edge?.path();
+        if !edge.is_dir() {
+            continue;
+        }
+
+        depth += fs::read_dir(&edge)?
+            .filter_map(|e| e.ok())
+            .filter(|e| is_queue_entry(&e.path()))
+            .count();
+    }
+
+    Ok(depth)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::project::ProjectInfo;
+    use tempfile::TempDir;
+
+    fn write_kernel(root: &Path, name: &str, kernel_type: &str) {
+        let kernel_dir = root.join("concepts").join(name);
+        fs::create_dir_all(kernel_dir.join("queue/inbox")).unwrap();
+        fs::write(
+            kernel_dir.join("conceptkernel.yaml"),
+            format!(
+                "apiVersion: conceptkernel/v1\nkind: Ontology\nmetadata:\n  name: ckp://{}:v0.1\n  type: {}\n  version: v0.1\nspec: {{}}\n",
+                name, kernel_type
+            ),
+        )
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_project_status() {
+        let temp = TempDir::new().unwrap();
+        let project_dir = temp.path().join("demo");
+        fs::create_dir_all(&project_dir).unwrap();
+
+        let mut registry = ProjectRegistry::from_dir(temp.path().join("registry")).unwrap();
+        let entry = registry
+            .register(ProjectInfo {
+                name: "demo".to_string(),
+                id: "proj-demo-20250125".to_string(),
+                path: project_dir.to_string_lossy().to_string(),
+                version: "1.3.14".to_string(),
+                preferred_slot: None,
+            })
+            .unwrap();
+
+        write_kernel(&project_dir, "Demo.Worker", "node:cold");
+        write_kernel(&project_dir, "Demo.Api", "python:hot");
+        write_kernel(&project_dir, "Demo.Batch", "python:warm");
+
+        let worker_dir = project_dir.join("concepts/Demo.Worker");
+        fs::write(worker_dir.join("queue/inbox/job-1.job"), "{}").unwrap();
+        fs::create_dir_all(worker_dir.join("queue/edges/PRODUCES.Demo.Api/tx-1.inst")).unwrap();
+        fs::write(worker_dir.join("queue/edges/PRODUCES.Demo.Api/.gitkeep"), "").unwrap();
+        fs::write(worker_dir.join("queue/edges/PRODUCES.Demo.Api/tx-2.inst.tmp"), "").unwrap();
+
+        let mut ports = PortManager::new(&project_dir).unwrap();
+        ports.set_base_port(entry.port_range.start).unwrap();
+        let api_port = ports.allocate("Demo.Api", Some(1)).unwrap();
+        let removed_port = ports.allocate("Demo.Removed", Some(2)).unwrap();
+
+        let health = registry.status("demo").await.unwrap();
+
+        assert_eq!(health.project, "demo");
+        assert_eq!(health.kernels.len(), 3);
+        assert!(health.errors.is_empty());
+
+        let api = health.kernels.iter().find(|k| k.status.name == "Demo.Api").unwrap();
+        assert_eq!(api.status.port, Some(api_port));
+        assert!(!api.port_out_of_range);
+
+        let worker = health.kernels.iter().find(|k| k.status.name == "Demo.Worker").unwrap();
+        assert_eq!(worker.status.queue_stats.inbox, 1);
+        assert_eq!(worker.edge_queue_depth, 1);
+        assert_eq!(worker.queue_depth(), 2);
+        assert!(!worker.alive);
+
+        // A warm kernel the router has not started yet is on standby, not running
+        let batch = health.kernels.iter().find(|k| k.status.name == "Demo.Batch").unwrap();
+        assert_eq!(batch.status.mode, "STANDBY");
+        assert!(!batch.alive);
+
+        assert_eq!(health.total_queue_depth(), 2);
+        assert_eq!(health.alive_count(), 0);
+        assert!(!health.is_healthy());
+        assert_eq!(health.stale_ports.get("Demo.Removed"), Some(&removed_port));
+
+        assert!(matches!(registry.status("missing").await, Err(CkpError::ProjectNotFound)));
+    }
+}
@@ -3,10 +3,16 @@
  * Multi-project infrastructure components (v1.3.14)
  */
 
+pub mod archive;
 pub mod config;
+pub mod health;
+pub mod quota;
 pub mod registry;
 
+pub use archive::{ProjectArchiveManifest, PROJECT_ARCHIVE_MANIFEST, PROJECT_ARCHIVE_VERSION};
+pub use health::{KernelHealth, ProjectHealth};
 pub use config::{DefaultUser, Features, Metadata, OntologyConfig, PortConfig, ProjectConfig, ProtocolMapping, Spec};
+pub use quota::{KernelUsage, QuotaAccountant, QuotaConfig, QuotaLimits, QuotaResource};
 pub use registry::{ProjectEntry, ProjectInfo, ProjectRegistry};
 
 #[cfg(test)]
@@ -0,0 +1,354 @@
+/**
+ * quota.rs
+ * Storage, instance and queue quotas per kernel and per project
+ *
+ * Quotas are configured in `.ckproject` under `spec.quotas`:
+ * ```yaml
+ * spec:
+ *   quotas:
+ *     project:               # totals across all kernels
+ *       storageBytes: 10737418240
+ *     kernel:                # default for every kernel
+ *       queueDepth: 10000
+ *     kernels:               # per-kernel overrides
+ *       Orders.Checkout:
+ *         instances: 50000
+ *         storageBytes: 1073741824
+ * ```
+ *
+ * Usage is read from ledgers the driver keeps current, not from a walk of
+ * the disk: `.inst` entries under `storage/` and their bytes from the
+ * instance index (see `storage::index`), adjusted on every mint, archive
+ * and delete, and jobs in inbox, staging and ready from the queue counters
+ * (see `drivers::QueueCounters`). Either is rebuilt from the filesystem
+ * only when it has gone stale. The driver checks the target's queue depth
+ * before writing a job and the kernel's storage before minting an instance,
+ * failing with `CkpError::QuotaExceeded`.
+ */
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::config::ProjectConfig;
+use crate::drivers::QueueCounters;
+use crate::errors::CkpError;
+use crate::storage::InstanceScanner;
+
+/// Limits for one kernel, or for a project's totals
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct QuotaLimits {
+    /// Bytes under storage/
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub storage_bytes: Option<u64>,
+
+    /// Instances (`.inst` entries) in storage/
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub instances: Option<u64>,
+
+    /// Jobs waiting in inbox, staging and ready
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub queue_depth: Option<u64>,
+}
+
+impl QuotaLimits {
+    /// Limit for a resource
+    pub fn limit(&self, resource: QuotaResource) -> Option<u64> {
+        match resource {
+            QuotaResource::StorageBytes => self.storage_bytes,
+            QuotaResource::Instances => self.instances,
+            QuotaResource::QueueDepth => self.queue_depth,
+        }
+    }
+}
+
+/// Quota settings, `spec.quotas` in `.ckproject`
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct QuotaConfig {
+    /// Limits on totals across all kernels
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub project: Option<QuotaLimits>,
+
+    /// Limits for kernels without an entry in `kernels`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub kernel: Option<QuotaLimits>,
+
+    /// Per-kernel limits
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub kernels: HashMap<String, QuotaLimits>,
+}
+
+impl QuotaConfig {
+    /// Limits that apply to a kernel
+    pub fn limits_for(&self, kernel: &str) -> Option<&QuotaLimits> {
+        self.kernels.get(kernel).or(self.kernel.as_ref())
+    }
+}
+
+/// Resource counted against a quota
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum QuotaResource {
+    StorageBytes,
+    Instances,
+    QueueDepth,
+}
+
+impl fmt::Display for QuotaResource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::StorageBytes => "storage bytes",
+            Self::Instances => "instances",
+            Self::QueueDepth => "queue depth",
+        })
+    }
+}
+
+/// Measured usage of one kernel
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct KernelUsage {
+    pub kernel: String,
+    pub storage_bytes: u64,
+    pub instances: u64,
+    pub queue_depth: u64,
+}
+
+impl KernelUsage {
+    /// Usage of a resource
+    pub fn get(&self, resource: QuotaResource) -> u64 {
+        match resource {
+            QuotaResource::StorageBytes => self.storage_bytes,
+            QuotaResource::Instances => self.instances,
+            QuotaResource::QueueDepth => self.queue_depth,
+        }
+    }
+}
+
+/// Measures usage and enforces the quotas of one project
+#[derive(Debug, Clone)]
+pub struct QuotaAccountant {
+    /// Project root directory (contains concepts/)
+    root: PathBuf,
+    config: QuotaConfig,
+}
+
+impl QuotaAccountant {
+    /// Accountant for a project with the given quotas
+    pub fn new(root: PathBuf, config: QuotaConfig) -> Self {
+        Self { root, config }
+    }
+
+    /// Accountant for a project's configured quotas, if it has any
+    ///
+    /// Projects without a readable `.ckproject` are unlimited.
+    pub fn for_project(root: &Path) -> Option<Self> {
+        let config = ProjectConfig::load_raw(root.join(".ckproject")).ok()?.spec.quotas?;
+        Some(Self::new(root.to_path_buf(), config))
+    }
+
+    /// Quotas being enforced
+    pub fn config(&self) -> &QuotaConfig {
+        &self.config
+    }
+
+    /// One kernel's usage
+    pub fn usage(&self, kernel: &str) -> Result<KernelUsage, CkpError> {
+        let kernel_dir = self.root.join("concepts").join(kernel);
+
+        let (instances, storage_bytes) = if kernel_dir.join("storage").is_dir() {
+            let scanner = InstanceScanner::new(kernel_dir.clone(), kernel.to_string());
+            (scanner.count_instances()? as u64, scanner.storage_bytes()?)
+        } else {
+            (0, 0)
+        };
+
+        let counters = QueueCounters::new(&kernel_dir);
+        let queue = match counters.estimate() {
+            Some(stats) => stats,
+            None => counters.recount()?,
+        };
+
+        Ok(KernelUsage {
+            kernel: kernel.to_string(),
+            storage_bytes,
+            instances,
+            queue_depth: (queue.inbox + queue.staging + queue.ready) as u64,
+        })
+    }
+
+    /// Usage of every kernel in the project, sorted by name
+    pub fn project_usage(&self) -> Result<Vec<KernelUsage>, CkpError> {
+        let entries = match fs::read_dir(self.root.join("concepts")) {
+            Ok(entries) => entries,
+            Err(_) => return Ok(Vec::new()),
+        };
+
+        let mut kernels: Vec<String> = entries
+            .flatten()
+            .filter(|entry| entry.path().join("conceptkernel.yaml").is_file())
+            .map(|entry| entry.file_name().to_string_lossy().to_string())
+            .filter(|name| !name.starts_with('.'))
+            .collect();
+        kernels.sort();
+
+        kernels.iter().map(|kernel| self.usage(kernel)).collect()
+    }
+
+    /// Kernels using the most of a resource, largest first
+    pub fn top_consumers(&self, resource: QuotaResource, limit: usize) -> Result<Vec<KernelUsage>, CkpError> {
+        let mut usage = self.project_usage()?;
+        usage.sort_by(|a, b| b.get(resource).cmp(&a.get(resource)).then_with(|| a.kernel.cmp(&b.kernel)));
+        usage.truncate(limit);
+        Ok(usage)
+    }
+
+    /// Check that one more job fits in a kernel's queues
+    ///
+    /// # Errors
+    ///
+    /// `CkpError::QuotaExceeded` if the kernel or project queue depth is at its limit
+    pub fn check_emit(&self, kernel: &str) -> Result<(), CkpError> {
+        self.check(kernel, &[(QuotaResource::QueueDepth, 1)])
+    }
+
+    /// Check that a new instance of `bytes` fits in a kernel's storage
+    ///
+    /// # Errors
+    ///
+    /// `CkpError::QuotaExceeded` if the kernel or project would go over its
+    /// storage or instance limit
+    pub fn check_mint(&self, kernel: &str, bytes: u64) -> Result<(), CkpError> {
+        self.check(kernel, &[(QuotaResource::Instances, 1), (QuotaResource::StorageBytes, bytes)])
+    }
+
+    fn check(&self, kernel: &str, additions: &[(QuotaResource, u64)]) -> Result<(), CkpError> {
+        if let Some(limits) = self.config.limits_for(kernel) {
+            if additions.iter().any(|(resource, _)| limits.limit(*resource).is_some()) {
+                check_limits(kernel, limits, &self.usage(kernel)?, additions)?;
+            }
+        }
+
+        if let Some(limits) = &self.config.project {
+            if additions.iter().any(|(resource, _)| limits.limit(*resource).is_some()) {
+                let total = self.project_usage()?.iter().fold(KernelUsage::default(), |mut total, usage| {
+                    total.storage_bytes += usage.storage_bytes;
+                    total.instances += usage.instances;
+                    total.queue_depth += usage.queue_depth;
+                    total
+                });
+                check_limits("project", limits, &total, additions)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn check_limits(scope: &str, limits: &QuotaLimits, usage: &KernelUsage, additions: &[(QuotaResource, u64)]) -> Result<(), CkpError> {
+    for &(resource, added) in additions {
+        let Some(limit) = limits.limit(resource) else {
+            continue;
+        };
+        let used = usage.get(resource);
+        if used.saturating_add(added) > limit {
+            return Err(CkpError::QuotaExceeded {
+                scope: scope.to_string(),
+                resource: resource.to_string(),
+                used,
+                limit,
+            });
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn create_kernel(root: &Path, name: &str, instances: usize, jobs: usize) {
+        let kernel_dir = root.join("concepts").join(name);
+        fs::create_dir_all(kernel_dir.join("queue/inbox")).unwrap();
+        fs::write(kernel_dir.join("conceptkernel.yaml"), "kind: Ontology\n").unwrap();
+        for i in 0..instances {
+            let inst = kernel_dir.join("storage").join(format!("tx-{}.inst", i));
+            fs::create_dir_all(&inst).unwrap();
+            fs::write(inst.join("receipt.json"), "0123456789").unwrap();
+        }
+        for i in 0..jobs {
+            fs::write(kernel_dir.join("queue/inbox").join(format!("tx-{}.job", i)), "{}").unwrap();
+        }
+    }
+
+    #[test]
+    fn test_quota_enforcement_and_top_consumers() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path();
+        create_kernel(root, "Orders", 3, 2);
+        create_kernel(root, "Billing", 1, 0);
+
+        let config: QuotaConfig = serde_yaml::from_str(
+            "project:\n  storageBytes: 45\nkernel:\n  queueDepth: 2\nkernels:\n  Billing:\n    instances: 1\n",
+        )
+        .unwrap();
+        let quotas = QuotaAccountant::new(root.to_path_buf(), config);
+
+        let usage = quotas.usage("Orders").unwrap();
+        assert_eq!((usage.storage_bytes, usage.instances, usage.queue_depth), (30, 3, 2));
+
+        // Kernel default queue depth; Billing's override has no queue limit
+        assert!(matches!(
+            quotas.check_emit("Orders"),
+            Err(CkpError::QuotaExceeded { ref scope, used: 2, limit: 2, .. }) if scope == "Orders"
+        ));
+        quotas.check_emit("Billing").unwrap();
+
+        // Kernel instance limit, then project storage total (40 of 45 bytes used)
+        assert!(matches!(quotas.check_mint("Billing", 1), Err(CkpError::QuotaExceeded { used: 1, .. })));
+        quotas.check_mint("Orders", 5).unwrap();
+        assert!(matches!(
+            quotas.check_mint("Orders", 6),
+            Err(CkpError::QuotaExceeded { ref scope, used: 40, limit: 45, .. }) if scope == "project"
+        ));
+
+        let top = quotas.top_consumers(QuotaResource::StorageBytes, 1).unwrap();
+        assert_eq!(top.len(), 1);
+        assert_eq!(top[0].kernel, "Orders");
+    }
+
+    #[test]
+    fn test_usage_follows_mints_and_archives_without_walking_storage() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path();
+        create_kernel(root, "Orders", 0, 0);
+        let driver = crate::drivers::FileSystemDriver::new(root.to_path_buf(), "Orders".to_string());
+        let first = driver.mint_storage_artifact(&serde_json::json!({"n": 1}), "tx-1").unwrap();
+
+        let quotas = QuotaAccountant::new(root.to_path_buf(), QuotaConfig::default());
+        let usage = quotas.usage("Orders").unwrap();
+        assert_eq!(usage.instances, 1);
+        let one = usage.storage_bytes;
+        assert!(one > 0);
+
+        // Mint and archive adjust the ledger
+        driver.mint_storage_artifact(&serde_json::json!({"n": 2}), "tx-2").unwrap();
+        let usage = quotas.usage("Orders").unwrap();
+        assert_eq!(usage.instances, 2);
+        assert!(usage.storage_bytes > one);
+        driver.archive_storage_artifact("tx-2").unwrap();
+        let usage = quotas.usage("Orders").unwrap();
+        assert_eq!((usage.instances, usage.storage_bytes), (1, one));
+
+        // Changes made behind the driver's back wait for reconciliation
+        fs::write(first.join("extra.bin"), "0123456789").unwrap();
+        assert_eq!(quotas.usage("Orders").unwrap().storage_bytes, one);
+        InstanceScanner::new(root.join("concepts/Orders"), "Orders".to_string()).rebuild_index().unwrap();
+        assert_eq!(quotas.usage("Orders").unwrap().storage_bytes, one + 10);
+    }
+}
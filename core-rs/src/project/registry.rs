@@ -10,19 +10,28 @@
  *
  * Slot allocation:
  * - Auto-detect next available slot (if 4 projects exist, start at slot 5)
+ * - Each candidate range is bind-probed: the base port must be free and
+ *   few other ports may be taken by external services
  * - 3 retry attempts for port conflicts
  * - Fail if all 3 attempts fail
  *
+ * Concurrency:
+ * - Mutations hold an advisory lock on `.lock` in the registry directory,
+ *   so `ck` and `ckr` processes never interleave registry updates
+ * - Entry files are written to `<name>.json.part` and renamed into place
+ * - Each entry carries a revision; `update` rejects stale entries
+ *
  * Reference: Node.js v1.3.14 - ProjectRegistry.js
  */
 
 use serde::{Deserialize, Serialize};
 use std::env;
 use std::fs;
-use std::net::TcpListener;
 use std::path::{Path, PathBuf};
 
 use crate::errors::CkpError;
+use crate::port::PortManager;
+use crate::urn::UrnResolver;
 
 /// Port range for a project
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -43,6 +52,9 @@ pub struct ProjectEntry {
     pub discovery_port: u16,
     pub port_range: PortRange,
     pub registered_at: String,
+    /// Incremented on every update (0 for entries written before revisions)
+    #[serde(default)]
+    pub revision: u64,
 }
 
 /// Project information for registration
@@ -61,6 +73,43 @@ pub struct ProjectRegistry {
     projects_cache: Option<Vec<ProjectEntry>>,
 }
 
+/// Most externally occupied ports a newly assigned range may contain
+pub const MAX_OCCUPIED_IN_RANGE: usize = 20;
+
+/// Lock file guarding registry mutations
+const LOCK_FILE: &str = ".lock";
+
+/// Exclusive advisory lock on the registry, released on drop
+struct RegistryLock {
+    _file: fs::File,
+}
+
+impl RegistryLock {
+    /// Block until no other process or registry holds the lock
+    fn acquire(registry_dir: &Path) -> Result<Self, CkpError> {
+        let file = fs::OpenOptions::new()
+            .create(true)
+            .write(true)
+            .truncate(false)
+            .open(registry_dir.join(LOCK_FILE))
+            .map_err(|e| CkpError::IoError(format!("Failed to open registry lock: {}", e)))?;
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::io::AsRawFd;
+
+            // Closing the file releases the lock
+            unsafe {
+                if libc::flock(file.as_raw_fd(), libc::LOCK_EX) != 0 {
+                    return Err(CkpError::Io(std::io::Error::last_os_error()));
+                }
+            }
+        }
+
+        Ok(RegistryLock { _file: file })
+    }
+}
+
 impl ProjectRegistry {
     /// Create a new ProjectRegistry
     ///
@@ -75,6 +124,11 @@ impl ProjectRegistry {
             .join("conceptkernel")
             .join("projects");
 
+        Self::from_dir(registry_dir)
+    }
+
+    /// Create a ProjectRegistry over a specific registry directory
+    pub fn from_dir(registry_dir: PathBuf) -> Result<Self, CkpError> {
         // Create registry directory if not exists
         if !registry_dir.exists() {
             fs::create_dir_all(&registry_dir).map_err(|e| {
@@ -114,11 +168,11 @@ impl ProjectRegistry {
                     Ok(content) => match serde_json::from_str::<ProjectEntry>(&content) {
                         Ok(project) => projects.push(project),
                         Err(e) => {
-                            eprintln!("Failed to parse project {:?}: {}", path.file_name(), e);
+                            tracing::warn!(path = %path.display(), error = %e, "failed to parse project entry");
                         }
                     },
                     Err(e) => {
-                        eprintln!("Failed to read project {:?}: {}", path.file_name(), e);
+                        tracing::warn!(path = %path.display(), error = %e, "failed to read project entry");
                     }
                 }
             }
@@ -175,7 +229,29 @@ impl ProjectRegistry {
     /// # Returns
     /// true if available, false if in use
     pub fn is_port_available(port: u16) -> bool {
-        TcpListener::bind(("127.0.0.1", port)).is_ok()
+        PortManager::is_port_available(port)
+    }
+
+    /// Probe a candidate port range before assigning it
+    ///
+    /// The discovery (base) port must be free, and at most
+    /// `MAX_OCCUPIED_IN_RANGE` of the other ports may be held by external
+    /// services.
+    ///
+    /// # Arguments
+    /// * `base_port` - First port of the candidate range
+    ///
+    /// # Returns
+    /// true if the range can be assigned to a project
+    pub fn is_range_usable(base_port: u16) -> bool {
+        if !Self::is_port_available(base_port) {
+            return false;
+        }
+
+        let occupied = (1..=199)
+            .filter(|offset| !Self::is_port_available(base_port + offset))
+            .count();
+        occupied <= MAX_OCCUPIED_IN_RANGE
     }
 
     /// Register a new project
@@ -200,6 +276,10 @@ impl ProjectRegistry {
             ));
         }
 
+        // Hold the registry lock and read it fresh so slots stay unique across processes
+        let _lock = RegistryLock::acquire(&self.registry_dir)?;
+        self.clear_cache();
+
         // Check if project already registered
         if let Some(existing) = self.get(&project_info.name)? {
             return Err(CkpError::ProjectAlreadyRegistered(format!(
@@ -219,8 +299,8 @@ impl ProjectRegistry {
             let slot = initial_slot + attempt;
             let base_port = Self::calculate_base_port(slot);
 
-            if Self::is_port_available(base_port) {
-                // Port is available, register project
+            if Self::is_range_usable(base_port) {
+                // Range is available, register project
                 let project = ProjectEntry {
                     name: project_info.name.clone(),
                     id: project_info.id.clone(),
@@ -233,17 +313,11 @@ impl ProjectRegistry {
                         end: base_port + 199,
                     },
                     registered_at: chrono::Utc::now().to_rfc3339(),
+                    revision: 1,
                 };
 
                 // Save to registry
-                let filename = format!("{}.json", project_info.name);
-                let file_path = self.registry_dir.join(filename);
-                let json = serde_json::to_string_pretty(&project).map_err(|e| {
-                    CkpError::SerializationError(format!("Failed to serialize project: {}", e))
-                })?;
-                fs::write(&file_path, json).map_err(|e| {
-                    CkpError::IoError(format!("Failed to write project file: {}", e))
-                })?;
+                self.write_entry(&project)?;
 
                 // Clear cache
                 self.clear_cache();
@@ -271,6 +345,118 @@ impl ProjectRegistry {
         Ok(projects.into_iter().find(|p| p.name == name))
     }
 
+    /// Resolve a cross-project URN to a filesystem path
+    ///
+    /// `ckp://workers/Worker.Process:v1#inbox` resolves against the
+    /// `concepts/` directory of the registered project `workers`.
+    ///
+    /// # Arguments
+    /// * `urn` - Cross-project URN
+    ///
+    /// # Returns
+    /// Path inside the target project
+    pub fn resolve_urn(&mut self, urn: &str) -> Result<PathBuf, CkpError> {
+        let parsed = UrnResolver::parse_project_urn(urn)?;
+        let project = self.get(&parsed.project)?.ok_or_else(|| {
+            CkpError::ProjectError(format!("Project \"{}\" is not registered", parsed.project))
+        })?;
+
+        UrnResolver::resolve_to_path(&parsed.local_urn(), &Path::new(&project.path).join("concepts"))
+    }
+
+    /// Update a registered project with optimistic concurrency
+    ///
+    /// `entry.revision` must match the revision on disk; the stored entry
+    /// gets the next revision. A mismatch means another process updated the
+    /// project after `entry` was read, so re-read it and retry.
+    ///
+    /// # Arguments
+    /// * `entry` - Modified project entry, as read from the registry
+    ///
+    /// # Returns
+    /// The stored entry with its new revision
+    ///
+    /// # Errors
+    /// - `CkpError::ProjectNotFound` if the project is not registered
+    /// - `CkpError::RegistryConflict` if the project changed since it was read
+    pub fn update(&mut self, entry: &ProjectEntry) -> Result<ProjectEntry, CkpError> {
+        let _lock = RegistryLock::acquire(&self.registry_dir)?;
+
+        let current = self.read_entry(&entry.name)?.ok_or(CkpError::ProjectNotFound)?;
+        if current.revision != entry.revision {
+            return Err(CkpError::RegistryConflict {
+                project: entry.name.clone(),
+                expected: entry.revision,
+                found: current.revision,
+            });
+        }
+
+        let mut updated = entry.clone();
+        updated.revision += 1;
+        self.write_entry(&updated)?;
+        self.clear_cache();
+
+        Ok(updated)
+    }
+
+    /// Read one entry straight from disk, bypassing the cache
+    fn read_entry(&self, name: &str) -> Result<Option<ProjectEntry>, CkpError> {
+        let file_path = self.registry_dir.join(format!("{}.json", name));
+        if !file_path.exists() {
+            return Ok(None);
+        }
+
+        let content = fs::read_to_string(&file_path).map_err(|e| {
+            CkpError::IoError(format!("Failed to read project file: {}", e))
+        })?;
+        let entry = serde_json::from_str(&content).map_err(|e| {
+            CkpError::ParseError(format!("Invalid project file {}: {}", file_path.display(), e))
+        })?;
+
+        Ok(Some(entry))
+    }
+
+    /// Write an entry file atomically (write `.part`, then rename)
+    fn write_entry(&self, entry: &ProjectEntry) -> Result<(), CkpError> {
+        let json = serde_json::to_string_pretty(entry).map_err(|e| {
+            CkpError::SerializationError(format!("Failed to serialize project: {}", e))
+        })?;
+
+        Self::write_atomic(&self.registry_dir.join(format!("{}.json", entry.name)), &json)
+            .map_err(|e| CkpError::IoError(format!("Failed to write project file: {}", e)))
+    }
+
+    fn write_atomic(path: &Path, content: &str) -> std::io::Result<()> {
+        let mut part = path.as_os_str().to_owned();
+        part.push(".part");
+        let part = PathBuf::from(part);
+
+        fs::write(&part, content).and_then(|_| fs::rename(&part, path))
+    }
+
+    /// Reserve a port for a named service in a registered project
+    ///
+    /// The reservation is stored in the project's .ckports and returned
+    /// unchanged on later calls, so daemons can look up their port by name
+    /// instead of hardcoding an offset. Use `PortManager::reserve_with` for
+    /// owned or leased reservations.
+    ///
+    /// # Arguments
+    /// * `project` - Project name
+    /// * `service` - Service name (e.g. "gateway-http")
+    ///
+    /// # Errors
+    /// Returns `CkpError::ProjectNotFound` if the project is not registered
+    pub fn reserve(&mut self, project: &str, service: &str) -> Result<u16, CkpError> {
+        let entry = self.get(project)?.ok_or(CkpError::ProjectNotFound)?;
+
+        let mut ports = PortManager::new(&entry.path)?;
+        if ports.get_base_port().is_none() {
+            ports.set_base_port(entry.port_range.start)?;
+        }
+        ports.reserve(service)
+    }
+
     /// Remove project from registry
     ///
     /// Note: This only removes the registry entry, project files remain intact
@@ -281,6 +467,7 @@ impl ProjectRegistry {
     /// # Returns
     /// true if removed, false if not found
     pub fn remove(&mut self, name: &str) -> Result<bool, CkpError> {
+        let _lock = RegistryLock::acquire(&self.registry_dir)?;
         let filename = format!("{}.json", name);
         let file_path = self.registry_dir.join(filename);
 
@@ -344,7 +531,7 @@ impl ProjectRegistry {
     /// Ok if successful
     pub fn set_current(&self, name: &str) -> Result<(), CkpError> {
         let current_file = self.registry_dir.join(".current");
-        fs::write(&current_file, name).map_err(|e| {
+        Self::write_atomic(&current_file, name).map_err(|e| {
             CkpError::IoError(format!("Failed to set current project: {}", e))
         })?;
         Ok(())
@@ -477,6 +664,30 @@ mod tests {
         assert!(not_found.is_none());
     }
 
+    #[test]
+    fn test_resolve_project_urn() {
+        let (mut registry, temp) = create_test_registry();
+
+        let project_info = ProjectInfo {
+            name: "workers".to_string(),
+            id: "proj-workers-20250125".to_string(),
+            path: temp.path().to_string_lossy().to_string(),
+            version: "1.3.14".to_string(),
+            preferred_slot: None,
+        };
+
+        registry.register(project_info).unwrap();
+
+        let path = registry.resolve_urn("ckp://workers/Worker.Process:v1#inbox").unwrap();
+        assert_eq!(path, temp.path().join("concepts/Worker.Process/queue/inbox"));
+
+        let kernel = registry.resolve_urn("ckp://workers/Worker.Process:v1").unwrap();
+        assert_eq!(kernel, temp.path().join("concepts/Worker.Process"));
+
+        assert!(registry.resolve_urn("ckp://billing/Invoice:v1").is_err());
+        assert!(registry.resolve_urn("ckp://Worker.Process:v1").is_err());
+    }
+
     #[test]
     fn test_remove_project() {
         let (mut registry, temp) = create_test_registry();
@@ -680,6 +891,140 @@ mod tests {
         assert_eq!(failures, 2, "Two should fail with duplicate error");
     }
 
+    /// Test: Separate registries over one directory never share a slot
+    #[test]
+    fn test_concurrent_registration_separate_registries() {
+        use std::thread;
+
+        let (registry, temp) = create_test_registry();
+        let registry_dir = registry.get_registry_dir().to_path_buf();
+        let temp_path = temp.path().to_string_lossy().to_string();
+
+        // Each thread stands in for a separate ck/ckr process
+        let handles: Vec<_> = (1..=4)
+            .map(|i| {
+                let registry_dir = registry_dir.clone();
+                let temp_path = temp_path.clone();
+                thread::spawn(move || {
+                    let mut reg = ProjectRegistry::from_dir(registry_dir).unwrap();
+                    reg.register(ProjectInfo {
+                        name: format!("process-project-{}", i),
+                        id: format!("proj-process-{}-20250125", i),
+                        path: temp_path,
+                        version: "1.3.14".to_string(),
+                        preferred_slot: None,
+                    })
+                })
+            })
+            .collect();
+
+        for handle in handles {
+            handle.join().unwrap().unwrap();
+        }
+
+        let mut reg = ProjectRegistry::from_dir(registry_dir.clone()).unwrap();
+        let mut slots: Vec<u32> = reg.list().unwrap().iter().map(|p| p.slot).collect();
+        slots.dedup();
+        assert_eq!(slots.len(), 4, "All slots should be unique");
+
+        // No partial files are left behind
+        let leftovers = fs::read_dir(&registry_dir)
+            .unwrap()
+            .filter_map(|e| e.ok())
+            .filter(|e| e.file_name().to_string_lossy().ends_with(".part"))
+            .count();
+        assert_eq!(leftovers, 0);
+    }
+
+    /// Test: Named reservations live in the project's port map
+    #[test]
+    fn test_reserve_service_port() {
+        let (mut registry, temp) = create_test_registry();
+
+        let entry = registry
+            .register(ProjectInfo {
+                name: "reserving".to_string(),
+                id: "proj-reserving-20250125".to_string(),
+                path: temp.path().to_string_lossy().to_string(),
+                version: "1.3.14".to_string(),
+                preferred_slot: None,
+            })
+            .unwrap();
+
+        let port = registry.reserve("reserving", "gateway-http").unwrap();
+        assert!(port >= entry.port_range.start && port <= entry.port_range.end);
+        assert_eq!(registry.reserve("reserving", "gateway-http").unwrap(), port);
+
+        let ports = PortManager::new(temp.path()).unwrap();
+        assert_eq!(ports.get_reservation("gateway-http").unwrap().port, port);
+
+        assert!(matches!(registry.reserve("missing", "gateway-http"), Err(CkpError::ProjectNotFound)));
+    }
+
+    /// Test: Updates are rejected when the entry was changed by someone else
+    #[test]
+    fn test_update_revision_conflict() {
+        let (mut registry, temp) = create_test_registry();
+
+        let entry = registry
+            .register(ProjectInfo {
+                name: "versioned".to_string(),
+                id: "proj-versioned-20250125".to_string(),
+                path: temp.path().to_string_lossy().to_string(),
+                version: "1.3.14".to_string(),
+                preferred_slot: None,
+            })
+            .unwrap();
+        assert_eq!(entry.revision, 1);
+
+        let mut first = entry.clone();
+        first.version = "1.3.19".to_string();
+        let stored = registry.update(&first).unwrap();
+        assert_eq!(stored.revision, 2);
+        assert_eq!(registry.get("versioned").unwrap().unwrap().version, "1.3.19");
+
+        // A second writer still holding revision 1 loses
+        let mut stale = entry.clone();
+        stale.path = "/elsewhere".to_string();
+        match registry.update(&stale) {
+            Err(CkpError::RegistryConflict { project, expected, found }) => {
+                assert_eq!(project, "versioned");
+                assert_eq!(expected, 1);
+                assert_eq!(found, 2);
+            }
+            other => panic!("Expected RegistryConflict, got {:?}", other),
+        }
+        assert_eq!(registry.get("versioned").unwrap().unwrap().path, entry.path);
+
+        let mut missing = entry;
+        missing.name = "missing".to_string();
+        assert!(matches!(registry.update(&missing), Err(CkpError::ProjectNotFound)));
+    }
+
+    /// Test: Entries written before revisions load as revision 0
+    #[test]
+    fn test_legacy_entry_without_revision() {
+        let (mut registry, _temp) = create_test_registry();
+
+        let legacy = r#"{
+  "name": "legacy",
+  "id": "proj-legacy",
+  "path": "/tmp/legacy",
+  "version": "1.3.14",
+  "slot": 1,
+  "discoveryPort": 56000,
+  "portRange": { "start": 56000, "end": 56199 },
+  "registeredAt": "2025-01-25T00:00:00Z"
+}"#;
+        fs::write(registry.get_registry_dir().join("legacy.json"), legacy).unwrap();
+
+        let mut entry = registry.get("legacy").unwrap().unwrap();
+        assert_eq!(entry.revision, 0);
+
+        entry.version = "1.3.19".to_string();
+        assert_eq!(registry.update(&entry).unwrap().revision, 1);
+    }
+
     /// Test: Registration with slot reuse after removal
     #[test]
     fn test_registration_slot_reuse_after_removal() {
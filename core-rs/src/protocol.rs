@@ -0,0 +1,244 @@
+//! Protocol file schemas
+//!
+//! JSON Schemas for the files kernels exchange on disk, generated from the
+//! Rust types that read and write them, so the Node.js runtime and third
+//! parties can check interop mechanically instead of tracking this crate's
+//! serde attributes by hand.
+//!
+//! `validate_protocol_file` applies the same types plus the protocol rules a
+//! schema cannot express (edge URNs, known receipt versions, trace contexts).
+
+use crate::drivers::{JobFile, Transaction};
+use crate::edge::{EdgeMetadata, NotificationEntry};
+use crate::errors::{CkpError, Result};
+use crate::storage::{Receipt, RECEIPT_FILE, RECEIPT_SCHEMA_VERSION};
+use crate::telemetry::TraceContext;
+use crate::urn::UrnResolver;
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Kind of protocol file
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProtocolFile {
+    /// Queue entry (`queue/inbox/<txId>.job`)
+    Job,
+    /// Instance envelope (`storage/<id>.inst/receipt.bin`)
+    Receipt,
+    /// Edge definition (`edges/<edge>/edgekernel.yaml`, JSON form)
+    Edge,
+    /// Entry of an ontology notification contract
+    Notification,
+    /// Transaction log (`tx.jsonl`), one record per line
+    Transaction,
+}
+
+impl ProtocolFile {
+    /// Every protocol file kind
+    pub const ALL: [ProtocolFile; 5] = [
+        ProtocolFile::Job,
+        ProtocolFile::Receipt,
+        ProtocolFile::Edge,
+        ProtocolFile::Notification,
+        ProtocolFile::Transaction,
+    ];
+
+    /// Short name used on the command line and in schema file names
+    pub fn name(&self) -> &'static str {
+        match self {
+            ProtocolFile::Job => "job",
+            ProtocolFile::Receipt => "receipt",
+            ProtocolFile::Edge => "edge",
+            ProtocolFile::Notification => "notification",
+            ProtocolFile::Transaction => "transaction",
+        }
+    }
+
+    /// Name of files of this kind on disk, where fixed
+    pub fn file_name(&self) -> Option<&'static str> {
+        match self {
+            ProtocolFile::Receipt => Some(RECEIPT_FILE),
+            ProtocolFile::Edge => Some("edgekernel.yaml"),
+            ProtocolFile::Transaction => Some("tx.jsonl"),
+            ProtocolFile::Job | ProtocolFile::Notification => None,
+        }
+    }
+
+    /// JSON Schema (draft-07) of this kind
+    ///
+    /// For `Transaction` the schema describes one line of tx.jsonl.
+    pub fn schema(&self) -> Value {
+        let root = match self {
+            ProtocolFile::Job => schemars::schema_for!(JobFile),
+            ProtocolFile::Receipt => schemars::schema_for!(Receipt),
+            ProtocolFile::Edge => schemars::schema_for!(EdgeMetadata),
+            ProtocolFile::Notification => schemars::schema_for!(NotificationEntry),
+            ProtocolFile::Transaction => schemars::schema_for!(Transaction),
+        };
+
+        let mut schema = serde_json::to_value(root).unwrap_or(Value::Null);
+        if let Some(object) = schema.as_object_mut() {
+            object.insert("$id".to_string(), Value::String(format!("{}.schema.json", self.name())));
+        }
+        schema
+    }
+}
+
+impl std::fmt::Display for ProtocolFile {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl std::str::FromStr for ProtocolFile {
+    type Err = CkpError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        ProtocolFile::ALL
+            .into_iter()
+            .find(|kind| kind.name() == s)
+            .ok_or_else(|| {
+                CkpError::ValidationError(format!(
+                    "Unknown protocol file '{}' (expected job, receipt, edge, notification or transaction)",
+                    s
+                ))
+            })
+    }
+}
+
+/// Write every schema to `<dir>/<name>.schema.json`
+///
+/// # Returns
+/// Paths of the written files
+pub fn write_protocol_schemas(dir: &Path) -> Result<Vec<PathBuf>> {
+    fs::create_dir_all(dir)?;
+
+    let mut written = Vec::new();
+    for kind in ProtocolFile::ALL {
+        let path = dir.join(format!("{}.schema.json", kind.name()));
+        fs::write(&path, serde_json::to_string_pretty(&kind.schema())? + "\n")?;
+        written.push(path);
+    }
+    Ok(written)
+}
+
+/// Check that `bytes` is a valid protocol file of the given kind
+///
+/// # Errors
+/// - Not JSON, or does not match the kind's schema
+/// - Breaks a protocol rule: edges need an edge URN, receipts a known
+///   schema version, and jobs a well-formed traceparent if they carry one
+///
+/// # Example
+/// ```
+/// use ckp_core::protocol::{validate_protocol_file, ProtocolFile};
+///
+/// let job = br#"{"target":"Shop.Orders","payload":{},"timestamp":"2025-11-29T10:00:00Z","txId":"tx_1","source":"external"}"#;
+/// assert!(validate_protocol_file(ProtocolFile::Job, job).is_ok());
+/// assert!(validate_protocol_file(ProtocolFile::Job, b"{}").is_err());
+/// ```
+pub fn validate_protocol_file(kind: ProtocolFile, bytes: &[u8]) -> Result<()> {
+    match kind {
+        ProtocolFile::Job => {
+            let job: JobFile = parse(kind, bytes)?;
+            if let Some(traceparent) = &job.traceparent {
+                if TraceContext::parse(traceparent).is_none() {
+                    return Err(invalid(kind, format!("malformed traceparent '{}'", traceparent)));
+                }
+            }
+        }
+        ProtocolFile::Receipt => {
+            let receipt: Receipt = parse(kind, bytes)?;
+            if receipt.schema_version > RECEIPT_SCHEMA_VERSION {
+                return Err(invalid(kind, format!(
+                    "schema version {} is newer than supported version {}",
+                    receipt.schema_version, RECEIPT_SCHEMA_VERSION
+                )));
+            }
+        }
+        ProtocolFile::Edge => {
+            let edge: EdgeMetadata = parse(kind, bytes)?;
+            if edge.kind != "Edge" {
+                return Err(invalid(kind, format!("kind must be 'Edge', not '{}'", edge.kind)));
+            }
+            if !UrnResolver::is_edge_urn(&edge.urn) {
+                return Err(invalid(kind, format!("'{}' is not an edge URN", edge.urn)));
+            }
+        }
+        ProtocolFile::Notification => {
+            let entry: NotificationEntry = parse(kind, bytes)?;
+            if entry.target.trim().is_empty() {
+                return Err(invalid(kind, "target is empty".to_string()));
+            }
+        }
+        ProtocolFile::Transaction => {
+            let text = std::str::from_utf8(bytes).map_err(|e| invalid(kind, e.to_string()))?;
+            for (index, line) in text.lines().enumerate() {
+                if line.trim().is_empty() {
+                    continue;
+                }
+                serde_json::from_str::<Transaction>(line)
+                    .map_err(|e| invalid(kind, format!("line {}: {}", index + 1, e)))?;
+            }
+        }
+    }
+    Ok(())
+}
+
+fn parse<T: DeserializeOwned>(kind: ProtocolFile, bytes: &[u8]) -> Result<T> {
+    serde_json::from_slice(bytes).map_err(|e| invalid(kind, e.to_string()))
+}
+
+fn invalid(kind: ProtocolFile, reason: String) -> CkpError {
+    CkpError::ValidationError(format!("Invalid {} file: {}", kind, reason))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_schemas_follow_serde_names() {
+        let job = ProtocolFile::Job.schema();
+        assert_eq!(job["$id"], "job.schema.json");
+        assert!(job["properties"]["txId"].is_object());
+        let required: Vec<&str> = job["required"].as_array().unwrap().iter().filter_map(Value::as_str).collect();
+        assert!(required.contains(&"txId"));
+        assert!(!required.contains(&"traceparent"));
+
+        // Fields parsed from the URN are not part of the edge file
+        let edge = ProtocolFile::Edge.schema();
+        assert!(edge["properties"]["createdAt"].is_object());
+        assert!(edge["properties"].get("predicate").is_none());
+
+        let temp = TempDir::new().unwrap();
+        let written = write_protocol_schemas(temp.path()).unwrap();
+        assert_eq!(written.len(), ProtocolFile::ALL.len());
+        assert!(temp.path().join("transaction.schema.json").exists());
+    }
+
+    #[test]
+    fn test_validate_protocol_files() {
+        let edge = br#"{"apiVersion":"conceptkernel/v1","kind":"Edge","urn":"ckp://Edge.PRODUCES.A-to-B:v1.0","createdAt":"2025-11-29T21:27:59Z"}"#;
+        assert!(validate_protocol_file(ProtocolFile::Edge, edge).is_ok());
+        let not_edge = br#"{"urn":"ckp://Shop.Orders","createdAt":"2025-11-29T21:27:59Z"}"#;
+        assert!(validate_protocol_file(ProtocolFile::Edge, not_edge).is_err());
+
+        let receipt = serde_json::to_vec(&Receipt::new("tx_1", "order", "Shop.Orders", serde_json::json!({}))).unwrap();
+        assert!(validate_protocol_file(ProtocolFile::Receipt, &receipt).is_ok());
+        let future = br#"{"schemaVersion":99,"id":"a","name":"a","kernel":"K","timestamp":"2025-11-29T10:00:00Z"}"#;
+        assert!(validate_protocol_file(ProtocolFile::Receipt, future).is_err());
+
+        let job = br#"{"target":"K","payload":{},"timestamp":"t","txId":"tx_1","source":"external","traceparent":"bogus"}"#;
+        assert!(validate_protocol_file(ProtocolFile::Job, job).is_err());
+
+        let tx = b"{\"txId\":\"tx_1\",\"timestamp\":\"t\",\"kernel\":\"K\",\"action\":\"emit\"}\n\n{\"txId\":\"tx_2\"}\n";
+        let error = validate_protocol_file(ProtocolFile::Transaction, tx).unwrap_err();
+        assert!(error.to_string().contains("line 3"));
+
+        assert_eq!("notification".parse::<ProtocolFile>().unwrap(), ProtocolFile::Notification);
+        assert!("yaml".parse::<ProtocolFile>().is_err());
+    }
+}
@@ -0,0 +1,204 @@
+//! Python bindings (PyO3)
+//!
+//! A `ckp_core` extension module for data and ML users who consume pipeline
+//! outputs from Python and want to read receipts or trigger kernels without
+//! shelling out to the CLI:
+//!
+//! ```python
+//! import ckp_core
+//!
+//! tx_id = ckp_core.emit("/path/to/project", "Shop.Orders", {"order": 42})
+//! paid = ckp_core.query_instances(
+//!     "/path/to/project/concepts/Shop.Orders", "Shop.Orders",
+//!     where=[("order.total", "gte", 100)], success=True,
+//! )
+//! rows = ckp_core.sparql("/path/to/project", "SELECT ?s WHERE { ?s ?p ?o } LIMIT 5")
+//! ```
+//!
+//! Build with the `pyo3` feature as a cdylib, e.g. with maturin:
+//!
+//! ```text
+//! maturin build --release --features pyo3
+//! ```
+//!
+//! Values cross the boundary as JSON, so payloads and results are plain
+//! dicts, lists and scalars with the protocol's camelCase keys. Failures
+//! raise `ckp_core.CkpError`.
+
+use crate::kernel::Kernel;
+use crate::ontology::OntologyLibrary;
+use crate::storage::{FieldPredicate, InstanceFilter, InstanceScanner};
+use crate::workflow::WorkflowAPI;
+use chrono::{DateTime, Utc};
+use pyo3::exceptions::PyException;
+use pyo3::prelude::*;
+use serde::Serialize;
+use serde_json::Value;
+use std::path::PathBuf;
+
+pyo3::create_exception!(ckp_core, CkpError, PyException, "Error raised by the ConceptKernel runtime");
+
+fn to_py_err(error: impl std::fmt::Display) -> PyErr {
+    CkpError::new_err(error.to_string())
+}
+
+/// Convert a serializable value to the equivalent Python object
+fn to_python<'py, T: Serialize>(py: Python<'py>, value: &T) -> PyResult<Bound<'py, PyAny>> {
+    let text = serde_json::to_string(value).map_err(to_py_err)?;
+    py.import("json")?.call_method1("loads", (text,))
+}
+
+/// Convert a JSON-compatible Python object to a JSON value
+fn from_python(value: &Bound<'_, PyAny>) -> PyResult<Value> {
+    let text: String = value.py().import("json")?.call_method1("dumps", (value,))?.extract()?;
+    serde_json::from_str(&text).map_err(to_py_err)
+}
+
+fn parse_timestamp(value: Option<&str>) -> PyResult<Option<DateTime<Utc>>> {
+    value
+        .map(|text| {
+            DateTime::parse_from_rfc3339(text)
+                .map(|timestamp| timestamp.with_timezone(&Utc))
+                .map_err(|e| to_py_err(format!("Invalid timestamp '{}': {}", text, e)))
+        })
+        .transpose()
+}
+
+// ==================== Emit ====================
+
+/// Emit a job to a kernel's inbox and return its transaction ID
+///
+/// With a `source` kernel the emit is RBAC-checked against its ontology;
+/// without one the job is recorded as coming from `external`.
+#[pyfunction]
+#[pyo3(signature = (root, target, payload, source = None))]
+fn emit(py: Python<'_>, root: PathBuf, target: String, payload: &Bound<'_, PyAny>, source: Option<String>) -> PyResult<String> {
+    let payload = from_python(payload)?;
+    py.allow_threads(move || {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .map_err(to_py_err)?;
+        let enable_rbac = source.is_some();
+        let mut kernel = Kernel::new(root, source, enable_rbac);
+        runtime.block_on(kernel.emit(&target, payload)).map_err(to_py_err)
+    })
+}
+
+// ==================== Instances ====================
+
+/// Instance summaries sorted by name, up to `limit` (0 = all)
+#[pyfunction]
+#[pyo3(signature = (kernel_root, kernel_name, limit = 0))]
+fn list_instances<'py>(py: Python<'py>, kernel_root: PathBuf, kernel_name: String, limit: usize) -> PyResult<Bound<'py, PyAny>> {
+    let instances = InstanceScanner::new(kernel_root, kernel_name)
+        .list_instances(limit)
+        .map_err(to_py_err)?;
+    to_python(py, &instances)
+}
+
+/// Full detail of one instance, including its receipt data
+#[pyfunction]
+fn describe_instance<'py>(py: Python<'py>, kernel_root: PathBuf, kernel_name: String, name: String) -> PyResult<Bound<'py, PyAny>> {
+    let detail = InstanceScanner::new(kernel_root, kernel_name)
+        .describe_instance(&name)
+        .map_err(to_py_err)?;
+    to_python(py, &detail)
+}
+
+/// Instances matching envelope and payload filters, oldest first
+///
+/// `where` holds `(field, comparison, value)` triples over the data payload,
+/// with comparisons `eq`, `ne`, `gt`, `gte`, `lt` and `lte`. `since` and
+/// `until` are RFC 3339 timestamps.
+#[pyfunction]
+#[pyo3(signature = (kernel_root, kernel_name, *, action = None, success = None, since = None, until = None, r#where = None, limit = None))]
+#[allow(clippy::too_many_arguments)]
+fn query_instances<'py>(
+    py: Python<'py>,
+    kernel_root: PathBuf,
+    kernel_name: String,
+    action: Option<String>,
+    success: Option<bool>,
+    since: Option<String>,
+    until: Option<String>,
+    r#where: Option<Vec<(String, String, Bound<'py, PyAny>)>>,
+    limit: Option<usize>,
+) -> PyResult<Bound<'py, PyAny>> {
+    let mut fields = Vec::new();
+    for (field, comparison, value) in r#where.unwrap_or_default() {
+        let comparison = serde_json::from_value(Value::String(comparison.to_lowercase()))
+            .map_err(|_| to_py_err(format!("Invalid comparison '{}' (expected eq, ne, gt, gte, lt or lte)", comparison)))?;
+        fields.push(FieldPredicate::new(field, comparison, from_python(&value)?));
+    }
+
+    let filter = InstanceFilter {
+        since: parse_timestamp(since.as_deref())?,
+        until: parse_timestamp(until.as_deref())?,
+        action,
+        success,
+        fields,
+        limit,
+    };
+    let details = InstanceScanner::new(kernel_root, kernel_name)
+        .query(&filter)
+        .map_err(to_py_err)?;
+    to_python(py, &details)
+}
+
+// ==================== Ontology ====================
+
+/// Run a SPARQL query over the project ontology
+///
+/// Canonical ontologies from .ckproject are always loaded; `kernels` adds
+/// the ontologies of those kernels. Returns one dict per solution.
+#[pyfunction]
+#[pyo3(signature = (root, query, kernels = None))]
+fn sparql<'py>(py: Python<'py>, root: PathBuf, query: String, kernels: Option<Vec<String>>) -> PyResult<Bound<'py, PyAny>> {
+    let rows = py.allow_threads(move || {
+        let mut library = OntologyLibrary::new(root).map_err(to_py_err)?;
+        for kernel in kernels.unwrap_or_default() {
+            library.load_kernel_ontology(&kernel).map_err(to_py_err)?;
+        }
+        library.query_sparql(&query).map_err(to_py_err)
+    })?;
+    to_python(py, &rows)
+}
+
+/// Workflows with their execution status and phases
+///
+/// Workflows are loaded from the given CKDL files; pass `workflow_urn` to
+/// get a single workflow (None if it is not defined).
+#[pyfunction]
+#[pyo3(signature = (root, ckdl, workflow_urn = None))]
+fn workflow_status<'py>(py: Python<'py>, root: PathBuf, ckdl: Vec<PathBuf>, workflow_urn: Option<String>) -> PyResult<Bound<'py, PyAny>> {
+    let workflows = py.allow_threads(move || {
+        let library = OntologyLibrary::new(root).map_err(to_py_err)?;
+        let mut api = WorkflowAPI::new(library);
+        for path in &ckdl {
+            api.load_workflow_from_ckdl(path).map_err(to_py_err)?;
+        }
+        api.query_all_workflows().map_err(to_py_err)
+    })?;
+
+    match workflow_urn {
+        Some(urn) => {
+            let workflow = workflows.into_iter().find(|workflow| workflow.workflow_urn == urn);
+            to_python(py, &workflow)
+        }
+        None => to_python(py, &workflows),
+    }
+}
+
+/// The `ckp_core` Python module
+#[pymodule]
+fn ckp_core(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add("CkpError", m.py().get_type::<CkpError>())?;
+    m.add_function(wrap_pyfunction!(emit, m)?)?;
+    m.add_function(wrap_pyfunction!(list_instances, m)?)?;
+    m.add_function(wrap_pyfunction!(describe_instance, m)?)?;
+    m.add_function(wrap_pyfunction!(query_instances, m)?)?;
+    m.add_function(wrap_pyfunction!(sparql, m)?)?;
+    m.add_function(wrap_pyfunction!(workflow_status, m)?)?;
+    Ok(())
+}
@@ -0,0 +1,382 @@
+//! RBAC policy bundles
+//!
+//! Change-management approvals review a project's RBAC as a whole.
+//! `PolicyBundle::export` collects the effective policy of every kernel
+//! (communication allow/deny sets, consensus, self-improvement and git rules,
+//! roles) into one document, written as JSON for import or as Turtle for
+//! review. `PolicyBundle::apply` writes a reviewed bundle back to the
+//! `spec.rbac` section of each kernel's conceptkernel.yaml: every file is
+//! updated, or none is.
+//!
+//! Each kernel's policy records the digest of the ontology file it was
+//! exported from, and kernels whose file changed since are refused, so an
+//! approval never overwrites edits it did not cover. Roles are assigned
+//! through the ContinuantTracker, not ontology files: they are exported for
+//! review and left as they are on import.
+
+use crate::continuant_tracker::ContinuantTracker;
+use crate::errors::{CkpError, Result};
+use crate::ontology::config_reader::{Communication, Rbac};
+use crate::rbac::SelfImprovementConfig;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Bundle format version
+pub const BUNDLE_VERSION: u32 = 1;
+
+/// Effective RBAC policy of one kernel
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct KernelPolicy {
+    /// SHA-256 of the kernel's conceptkernel.yaml when exported
+    pub ontology_digest: String,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub communication: Option<Communication>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub consensus: Option<Value>,
+
+    /// Self-improvement settings as enforced (defaults when not declared)
+    #[serde(default)]
+    pub self_improvement: SelfImprovementConfig,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub git: Option<Value>,
+
+    /// Roles assigned to the kernel (for review; not applied on import)
+    #[serde(default)]
+    pub roles: Vec<String>,
+}
+
+/// RBAC policy of every kernel in a project
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PolicyBundle {
+    pub version: u32,
+
+    /// When the bundle was exported (RFC 3339)
+    pub exported_at: String,
+
+    /// Kernel name -> policy
+    pub kernels: BTreeMap<String, KernelPolicy>,
+}
+
+impl PolicyBundle {
+    /// Export the policy of every kernel under `root`/concepts
+    pub fn export(root: &Path) -> Result<Self> {
+        let concepts_dir = root.join("concepts");
+        let tracker = ContinuantTracker::new(concepts_dir.clone());
+
+        let mut kernels = BTreeMap::new();
+        for name in kernel_names(&concepts_dir)? {
+            let content = fs::read_to_string(ontology_path(root, &name))?;
+            let declared = declared_rbac(&parse_ontology(&content, &name)?, &name)?;
+            let roles = tracker.get_kernel_roles(&name)?.into_iter().map(|role| role.name).collect();
+
+            let policy = match declared {
+                Some(rbac) => KernelPolicy {
+                    ontology_digest: digest(&content),
+                    self_improvement: rbac
+                        .self_improvement
+                        .as_ref()
+                        .map(SelfImprovementConfig::from_value)
+                        .unwrap_or_default(),
+                    communication: rbac.communication,
+                    consensus: rbac.consensus,
+                    git: rbac.git,
+                    roles,
+                },
+                None => KernelPolicy {
+                    ontology_digest: digest(&content),
+                    communication: None,
+                    consensus: None,
+                    self_improvement: SelfImprovementConfig::default(),
+                    git: None,
+                    roles,
+                },
+            };
+            kernels.insert(name, policy);
+        }
+
+        Ok(Self {
+            version: BUNDLE_VERSION,
+            exported_at: chrono::Utc::now().to_rfc3339(),
+            kernels,
+        })
+    }
+
+    /// Parse a bundle exported as JSON
+    pub fn from_json(content: &str) -> Result<Self> {
+        let bundle: Self = serde_json::from_str(content)
+            .map_err(|e| CkpError::ParseError(format!("Failed to parse policy bundle: {}", e)))?;
+        if bundle.version != BUNDLE_VERSION {
+            return Err(CkpError::ValidationError(format!(
+                "Unsupported policy bundle version {} (expected {})",
+                bundle.version, BUNDLE_VERSION
+            )));
+        }
+        Ok(bundle)
+    }
+
+    /// Render the bundle as Turtle for review
+    pub fn to_turtle(&self) -> String {
+        let mut out = String::from("@prefix ckp: <https://conceptkernel.org/ontology#> .\n");
+        out.push_str(&format!("# RBAC policy bundle exported {}\n", self.exported_at));
+
+        for (name, policy) in &self.kernels {
+            let mut statements = vec![format!("ckp:ontologyDigest {}", literal(&policy.ontology_digest))];
+            if let Some(communication) = &policy.communication {
+                let lists = [
+                    ("ckp:allowsCommunicationWith", &communication.allowed),
+                    ("ckp:deniesCommunicationWith", &communication.denied),
+                    ("ckp:allowsProject", &communication.projects),
+                ];
+                for (predicate, values) in lists {
+                    for value in values.iter().flatten() {
+                        statements.push(format!("{} {}", predicate, literal(value)));
+                    }
+                }
+            }
+            for role in &policy.roles {
+                statements.push(format!("ckp:hasRole {}", literal(role)));
+            }
+
+            let self_improvement = &policy.self_improvement;
+            statements.push(format!("ckp:selfImprovementEnabled {}", self_improvement.enabled));
+            statements.push(format!("ckp:selfImprovementRequiresConsensus {}", self_improvement.requires_consensus));
+            for action in &self_improvement.allowed_actions {
+                statements.push(format!("ckp:allowsSelfImprovementAction {}", literal(action)));
+            }
+            for action in &self_improvement.forbidden_actions {
+                statements.push(format!("ckp:forbidsSelfImprovementAction {}", literal(action)));
+            }
+
+            // Free-form sections are kept as JSON literals
+            if let Some(consensus) = &policy.consensus {
+                statements.push(format!("ckp:consensusPolicy {}", literal(&consensus.to_string())));
+            }
+            if let Some(git) = &policy.git {
+                statements.push(format!("ckp:gitPolicy {}", literal(&git.to_string())));
+            }
+
+            out.push_str(&format!("\n<ckp://{}>\n    {} .\n", name, statements.join(" ;\n    ")));
+        }
+        out
+    }
+
+    /// Write the bundle's policies to the ontology files of the project at `root`
+    ///
+    /// Kernels of the project missing from the bundle are left unchanged, as
+    /// are kernels whose policy already matches. Nothing is written unless
+    /// every kernel can be updated.
+    ///
+    /// # Returns
+    /// Names of the kernels whose ontology was rewritten
+    ///
+    /// # Errors
+    /// - `CkpError::KernelNotFound` if a kernel of the bundle does not exist
+    /// - `CkpError::Rbac` if a kernel's ontology changed since export (unless `force`)
+    pub fn apply(&self, root: &Path, force: bool) -> Result<Vec<String>> {
+        // Prepare every file before writing any
+        let mut updates = Vec::new();
+        for (name, policy) in &self.kernels {
+            let path = ontology_path(root, name);
+            let content = match fs::read_to_string(&path) {
+                Ok(content) => content,
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                    return Err(CkpError::KernelNotFound(name.clone()))
+                }
+                Err(e) => return Err(e.into()),
+            };
+            if !force && digest(&content) != policy.ontology_digest {
+                return Err(CkpError::Rbac(format!(
+                    "Ontology of {} changed since the bundle was exported; export and review again",
+                    name
+                )));
+            }
+
+            let mut document = parse_ontology(&content, name)?;
+            let declared = declared_rbac(&document, name)?;
+            let rbac = policy.to_rbac(declared.as_ref());
+            if rbac == declared {
+                continue;
+            }
+
+            let spec = document
+                .as_mapping_mut()
+                .ok_or_else(|| CkpError::Ontology(format!("Ontology of {} is not a mapping", name)))?
+                .entry("spec".into())
+                .or_insert_with(|| serde_yaml::Value::Mapping(Default::default()));
+            let spec = spec
+                .as_mapping_mut()
+                .ok_or_else(|| CkpError::Ontology(format!("spec of {} is not a mapping", name)))?;
+            match rbac {
+                Some(rbac) => {
+                    spec.insert("rbac".into(), serde_yaml::to_value(rbac)?);
+                }
+                None => {
+                    spec.remove("rbac");
+                }
+            }
+
+            let tmp = path.with_extension("yaml.policy-tmp");
+            fs::write(&tmp, serde_yaml::to_string(&document)?)?;
+            updates.push((name.clone(), path, tmp, content));
+        }
+
+        // Swap the prepared files in, restoring the originals if one fails
+        for (index, (name, path, tmp, _)) in updates.iter().enumerate() {
+            if let Err(e) = fs::rename(tmp, path) {
+                for (_, path, _, original) in &updates[..index] {
+                    let _ = fs::write(path, original);
+                }
+                for (_, _, tmp, _) in &updates[index..] {
+                    let _ = fs::remove_file(tmp);
+                }
+                return Err(CkpError::IoError(format!("Failed to update policy of {}: {}", name, e)));
+            }
+        }
+
+        let applied: Vec<String> = updates.into_iter().map(|(name, ..)| name).collect();
+        tracing::info!(kernels = ?applied, "applied RBAC policy bundle");
+        Ok(applied)
+    }
+}
+
+impl KernelPolicy {
+    /// `spec.rbac` section for this policy
+    ///
+    /// A declared self-improvement section that already enforces these
+    /// settings is kept as written; undeclared defaults stay undeclared.
+    fn to_rbac(&self, declared: Option<&Rbac>) -> Option<Rbac> {
+        let declared_self_improvement = declared.and_then(|rbac| rbac.self_improvement.as_ref());
+        let self_improvement = match declared_self_improvement {
+            Some(value) if SelfImprovementConfig::from_value(value) == self.self_improvement => Some(value.clone()),
+            None if self.self_improvement == SelfImprovementConfig::default() => None,
+            _ => Some(serde_json::to_value(&self.self_improvement).expect("config serializes")),
+        };
+
+        let rbac = Rbac {
+            communication: self.communication.clone(),
+            consensus: self.consensus.clone(),
+            self_improvement,
+            git: self.git.clone(),
+        };
+        let empty = rbac.communication.is_none()
+            && rbac.consensus.is_none()
+            && rbac.self_improvement.is_none()
+            && rbac.git.is_none();
+        (!empty).then_some(rbac)
+    }
+}
+
+/// Kernels with an ontology under the concepts directory, in name order
+pub(crate) fn kernel_names(concepts_dir: &Path) -> Result<Vec<String>> {
+    let entries = match fs::read_dir(concepts_dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e.into()),
+    };
+
+    let mut names: Vec<String> = entries
+        .flatten()
+        .filter(|entry| entry.path().join("conceptkernel.yaml").is_file())
+        .map(|entry| entry.file_name().to_string_lossy().to_string())
+        .filter(|name| !name.starts_with('.'))
+        .collect();
+    names.sort();
+    Ok(names)
+}
+
+fn ontology_path(root: &Path, kernel: &str) -> PathBuf {
+    root.join("concepts").join(kernel).join("conceptkernel.yaml")
+}
+
+/// Ontology as written (policies are exported and applied without interpolation)
+fn parse_ontology(content: &str, kernel: &str) -> Result<serde_yaml::Value> {
+    serde_yaml::from_str(content)
+        .map_err(|e| CkpError::Ontology(format!("Failed to parse ontology of {}: {}", kernel, e)))
+}
+
+fn declared_rbac(document: &serde_yaml::Value, kernel: &str) -> Result<Option<Rbac>> {
+    match document.get("spec").and_then(|spec| spec.get("rbac")) {
+        Some(rbac) if !rbac.is_null() => serde_yaml::from_value(rbac.clone())
+            .map(Some)
+            .map_err(|e| CkpError::Ontology(format!("Invalid rbac section of {}: {}", kernel, e))),
+        _ => Ok(None),
+    }
+}
+
+fn digest(content: &str) -> String {
+    hex::encode(Sha256::digest(content.as_bytes()))
+}
+
+/// Turtle string literal
+fn literal(value: &str) -> String {
+    let escaped = value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+        .replace('\r', "\\r");
+    format!("\"{}\"", escaped)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn write_kernel(root: &Path, name: &str, spec: &str) {
+        let kernel_dir = root.join("concepts").join(name);
+        fs::create_dir_all(&kernel_dir).unwrap();
+        fs::write(
+            kernel_dir.join("conceptkernel.yaml"),
+            format!(
+                "apiVersion: conceptkernel/v1\nkind: Ontology\nmetadata:\n  name: ckp://{}:v0.1\n  type: node:cold\n  version: v0.1\n{}",
+                name, spec
+            ),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_export_review_and_apply_bundle() {
+        let temp = TempDir::new().unwrap();
+        write_kernel(
+            temp.path(),
+            "Orders.Intake",
+            "spec:\n  rbac:\n    communication:\n      allowed:\n        - ckp://Orders.Billing\n    self_improvement:\n      enabled: true\n",
+        );
+        write_kernel(temp.path(), "Orders.Billing", "");
+
+        let mut bundle = PolicyBundle::export(temp.path()).unwrap();
+        let intake = &bundle.kernels["Orders.Intake"];
+        assert_eq!(intake.communication.as_ref().unwrap().allowed, Some(vec!["ckp://Orders.Billing".to_string()]));
+        assert!(intake.self_improvement.enabled && intake.self_improvement.requires_consensus);
+        let turtle = bundle.to_turtle();
+        assert!(turtle.contains("<ckp://Orders.Intake>\n    ckp:ontologyDigest"));
+        assert!(turtle.contains("ckp:allowsCommunicationWith \"ckp://Orders.Billing\""));
+
+        // Round trip: nothing to change
+        let json = serde_json::to_string_pretty(&bundle).unwrap();
+        assert_eq!(PolicyBundle::from_json(&json).unwrap().apply(temp.path(), false).unwrap(), Vec::<String>::new());
+
+        bundle.kernels.get_mut("Orders.Billing").unwrap().communication = Some(Communication {
+            allowed: None,
+            denied: Some(vec!["ckp://Orders.Intake".to_string()]),
+            projects: None,
+        });
+        assert_eq!(bundle.apply(temp.path(), false).unwrap(), vec!["Orders.Billing"]);
+        let exported = PolicyBundle::export(temp.path()).unwrap();
+        assert_eq!(exported.kernels["Orders.Billing"].communication, bundle.kernels["Orders.Billing"].communication);
+        assert_eq!(exported.kernels["Orders.Intake"].ontology_digest, bundle.kernels["Orders.Intake"].ontology_digest);
+
+        // Billing's ontology changed after this bundle was exported: nothing is written
+        bundle.kernels.get_mut("Orders.Intake").unwrap().git = Some(serde_json::json!({"can_commit": false}));
+        assert!(matches!(bundle.apply(temp.path(), false), Err(CkpError::Rbac(_))));
+        assert_eq!(PolicyBundle::export(temp.path()).unwrap().kernels["Orders.Intake"].git, None);
+    }
+}
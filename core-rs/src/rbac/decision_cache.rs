@@ -0,0 +1,201 @@
+//! Cache of authorization decisions
+//!
+//! Emit and edge checks read and parse ontology YAML on every call, which
+//! dominates hot routing paths. Decisions are cached per source kernel,
+//! keyed by (target, predicate), together with the digests of the ontology
+//! files they were made from. A decision is reused only while every one of
+//! those files still has the same digest, so editing a kernel's
+//! conceptkernel.yaml invalidates what was decided from it.
+//!
+//! Checkers of a project share its cache (`DecisionCache::for_project`)
+//! unless given their own; hits, misses and invalidations are counted per
+//! kernel and exported by DaemonMetrics.
+
+use crate::errors::Result;
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, OnceLock};
+
+/// Predicate recorded for emit decisions (`PermissionChecker::can_emit_to`)
+pub const EMIT_PREDICATE: &str = "emit";
+
+/// Project root -> the project's shared cache
+static PROJECT_CACHES: OnceLock<Mutex<HashMap<PathBuf, Arc<DecisionCache>>>> = OnceLock::new();
+
+/// Lookup counters of one kernel's decisions
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct DecisionCacheStats {
+    /// Decisions answered from the cache
+    pub hits: u64,
+
+    /// Decisions evaluated (not cached, or invalidated)
+    pub misses: u64,
+
+    /// Cached decisions dropped because an ontology file changed
+    pub invalidations: u64,
+
+    /// Decisions currently cached
+    pub entries: usize,
+}
+
+impl DecisionCacheStats {
+    /// Share of lookups answered from the cache (0.0 without lookups)
+    pub fn hit_rate(&self) -> f64 {
+        let lookups = self.hits + self.misses;
+        if lookups == 0 {
+            0.0
+        } else {
+            self.hits as f64 / lookups as f64
+        }
+    }
+}
+
+/// Digests of the ontology files a decision was made from (`None`: missing file)
+type Dependencies = Vec<(PathBuf, Option<String>)>;
+
+#[derive(Debug)]
+struct Decision {
+    allowed: bool,
+    depends_on: Dependencies,
+}
+
+#[derive(Debug, Default)]
+struct KernelDecisions {
+    /// (target, predicate) -> decision
+    decisions: HashMap<(String, String), Decision>,
+    stats: DecisionCacheStats,
+}
+
+/// Per-kernel cache of authorization decisions
+#[derive(Debug, Default)]
+pub struct DecisionCache {
+    kernels: Mutex<HashMap<String, KernelDecisions>>,
+}
+
+impl DecisionCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Cache shared by every checker of the project at `root`
+    pub fn for_project(root: &Path) -> Arc<Self> {
+        let caches = PROJECT_CACHES.get_or_init(|| Mutex::new(HashMap::new()));
+        let mut caches = caches.lock().unwrap_or_else(|e| e.into_inner());
+        Arc::clone(caches.entry(root.to_path_buf()).or_default())
+    }
+
+    /// Cached decision for `source -> target` over `predicate`, evaluating it on a miss
+    ///
+    /// `depends_on` lists the ontology files the decision is read from.
+    /// Errors from `evaluate` are returned and not cached.
+    pub fn decide<F>(&self, source: &str, target: &str, predicate: &str, depends_on: &[PathBuf], evaluate: F) -> Result<bool>
+    where
+        F: FnOnce() -> Result<bool>,
+    {
+        let dependencies: Dependencies = depends_on.iter().map(|path| (path.clone(), digest(path))).collect();
+        let key = (target.to_string(), predicate.to_string());
+
+        {
+            let mut kernels = self.lock();
+            let kernel = kernels.entry(source.to_string()).or_default();
+            match kernel.decisions.get(&key) {
+                Some(decision) if decision.depends_on == dependencies => {
+                    kernel.stats.hits += 1;
+                    return Ok(decision.allowed);
+                }
+                Some(_) => {
+                    kernel.decisions.remove(&key);
+                    kernel.stats.invalidations += 1;
+                }
+                None => {}
+            }
+            kernel.stats.misses += 1;
+        }
+
+        // Evaluated without the lock: it reads ontologies and may be slow
+        let allowed = evaluate()?;
+        let mut kernels = self.lock();
+        let kernel = kernels.entry(source.to_string()).or_default();
+        kernel.decisions.insert(key, Decision { allowed, depends_on: dependencies });
+        Ok(allowed)
+    }
+
+    /// Counters of one kernel's decisions
+    pub fn stats(&self, kernel: &str) -> DecisionCacheStats {
+        self.lock().get(kernel).map(KernelDecisions::snapshot).unwrap_or_default()
+    }
+
+    /// Counters of every kernel with decisions, by kernel name
+    pub fn all_stats(&self) -> BTreeMap<String, DecisionCacheStats> {
+        self.lock().iter().map(|(kernel, decisions)| (kernel.clone(), decisions.snapshot())).collect()
+    }
+
+    /// Drop every cached decision (counters are kept)
+    pub fn clear(&self) {
+        for kernel in self.lock().values_mut() {
+            kernel.decisions.clear();
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<String, KernelDecisions>> {
+        self.kernels.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl KernelDecisions {
+    fn snapshot(&self) -> DecisionCacheStats {
+        DecisionCacheStats {
+            entries: self.decisions.len(),
+            ..self.stats
+        }
+    }
+}
+
+/// SHA-256 of a file's content, or `None` if it cannot be read
+fn digest(path: &Path) -> Option<String> {
+    fs::read(path).ok().map(|content| hex::encode(Sha256::digest(&content)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_decisions_cached_until_ontology_changes() {
+        let temp = TempDir::new().unwrap();
+        let ontology = temp.path().join("conceptkernel.yaml");
+        fs::write(&ontology, "allowed: [Target]").unwrap();
+        let cache = DecisionCache::new();
+        let depends_on = [ontology.clone()];
+        let mut evaluations = 0;
+
+        for _ in 0..3 {
+            let allowed = cache
+                .decide("Source", "Target", "PRODUCES", &depends_on, || {
+                    evaluations += 1;
+                    Ok(true)
+                })
+                .unwrap();
+            assert!(allowed);
+        }
+        assert_eq!(evaluations, 1);
+
+        // Another predicate is another decision
+        cache.decide("Source", "Target", "NOTIFIES", &depends_on, || Ok(false)).unwrap();
+
+        fs::write(&ontology, "allowed: []").unwrap();
+        assert!(!cache.decide("Source", "Target", "PRODUCES", &depends_on, || Ok(false)).unwrap());
+
+        let stats = cache.stats("Source");
+        assert_eq!((stats.hits, stats.misses, stats.invalidations, stats.entries), (2, 3, 1, 2));
+        assert!((stats.hit_rate() - 0.4).abs() < f64::EPSILON);
+
+        // Errors are not cached
+        assert!(cache.decide("Other", "Target", "emit", &depends_on, || Err(crate::errors::CkpError::Rbac("x".into()))).is_err());
+        assert_eq!(cache.stats("Other").entries, 0);
+    }
+}
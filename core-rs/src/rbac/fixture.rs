@@ -0,0 +1,247 @@
+//! Edge authorization fixtures
+//!
+//! A project's communication topology is spread over the
+//! `rbac.communication` rules of every kernel's ontology, so an edit to one
+//! kernel can silently open or close edges of another. `EdgeMatrix::generate`
+//! evaluates `PermissionChecker::can_emit_to` for every ordered pair of the
+//! project's kernels and records which pairs are allowed and which denied.
+//! The matrix is committed as a JSON fixture next to the ontologies;
+//! `assert_edge_matrix` fails a test when the project no longer matches it,
+//! listing every pair that changed.
+
+use crate::errors::{CkpError, Result};
+use crate::rbac::bundle::kernel_names;
+use crate::rbac::{DecisionCache, PermissionChecker};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+use std::fs;
+use std::path::Path;
+use std::sync::Arc;
+
+/// Edge matrix format version
+pub const EDGE_MATRIX_VERSION: u32 = 1;
+
+/// Allowed and denied (source, target) pairs of a project's kernels
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EdgeMatrix {
+    pub version: u32,
+
+    /// Source kernel -> targets it may emit to
+    #[serde(default)]
+    pub allowed: BTreeMap<String, BTreeSet<String>>,
+
+    /// Source kernel -> targets it may not emit to
+    #[serde(default)]
+    pub denied: BTreeMap<String, BTreeSet<String>>,
+}
+
+/// A pair whose decision differs between a fixture and the project
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EdgeChange {
+    pub source: String,
+    pub target: String,
+
+    /// Decision in the fixture (None if the pair is not in it)
+    pub expected: Option<bool>,
+
+    /// Decision now (None if either kernel no longer exists)
+    pub actual: Option<bool>,
+}
+
+impl fmt::Display for EdgeChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let describe = |decision: Option<bool>| match decision {
+            Some(true) => "allowed",
+            Some(false) => "denied",
+            None => "absent",
+        };
+        write!(
+            f,
+            "{} -> {}: {} in fixture, {} now",
+            self.source,
+            self.target,
+            describe(self.expected),
+            describe(self.actual)
+        )
+    }
+}
+
+impl EdgeMatrix {
+    /// Evaluate every ordered pair of distinct kernels under `root`/concepts
+    ///
+    /// Decisions are evaluated afresh, not read from the project's shared
+    /// decision cache.
+    pub fn generate(root: &Path) -> Result<Self> {
+        let kernels = kernel_names(&root.join("concepts"))?;
+        let mut checker =
+            PermissionChecker::new(root.to_path_buf()).with_decision_cache(Arc::new(DecisionCache::new()));
+
+        let mut matrix = Self {
+            version: EDGE_MATRIX_VERSION,
+            allowed: BTreeMap::new(),
+            denied: BTreeMap::new(),
+        };
+        for source in &kernels {
+            for target in kernels.iter().filter(|target| *target != source) {
+                let decisions = if checker.can_emit_to(source, target)? {
+                    &mut matrix.allowed
+                } else {
+                    &mut matrix.denied
+                };
+                decisions.entry(source.clone()).or_default().insert(target.clone());
+            }
+        }
+        Ok(matrix)
+    }
+
+    /// Parse a matrix written as JSON
+    pub fn from_json(content: &str) -> Result<Self> {
+        let matrix: Self = serde_json::from_str(content)
+            .map_err(|e| CkpError::ParseError(format!("Failed to parse edge matrix: {}", e)))?;
+        if matrix.version != EDGE_MATRIX_VERSION {
+            return Err(CkpError::ValidationError(format!(
+                "Unsupported edge matrix version {} (expected {})",
+                matrix.version, EDGE_MATRIX_VERSION
+            )));
+        }
+        Ok(matrix)
+    }
+
+    /// Decision recorded for `source -> target`, if the pair is in the matrix
+    pub fn is_allowed(&self, source: &str, target: &str) -> Option<bool> {
+        let contains = |decisions: &BTreeMap<String, BTreeSet<String>>| {
+            decisions.get(source).is_some_and(|targets| targets.contains(target))
+        };
+        if contains(&self.allowed) {
+            Some(true)
+        } else if contains(&self.denied) {
+            Some(false)
+        } else {
+            None
+        }
+    }
+
+    /// Pairs decided differently in `actual`, in source/target order
+    pub fn diff(&self, actual: &Self) -> Vec<EdgeChange> {
+        let pairs: BTreeSet<(&String, &String)> = [&self.allowed, &self.denied, &actual.allowed, &actual.denied]
+            .into_iter()
+            .flat_map(|decisions| decisions.iter().flat_map(|(source, targets)| targets.iter().map(move |t| (source, t))))
+            .collect();
+
+        pairs
+            .into_iter()
+            .filter_map(|(source, target)| {
+                let expected = self.is_allowed(source, target);
+                let actual = actual.is_allowed(source, target);
+                (expected != actual).then(|| EdgeChange {
+                    source: source.clone(),
+                    target: target.clone(),
+                    expected,
+                    actual,
+                })
+            })
+            .collect()
+    }
+
+    /// Compare the project at `root` with the fixture at `fixture`
+    pub fn verify(root: &Path, fixture: &Path) -> Result<Vec<EdgeChange>> {
+        let content = fs::read_to_string(fixture)
+            .map_err(|e| CkpError::IoError(format!("Failed to read {}: {}", fixture.display(), e)))?;
+        Ok(Self::from_json(&content)?.diff(&Self::generate(root)?))
+    }
+}
+
+/// Assert that the project at `root` still matches its edge matrix fixture
+///
+/// # Panics
+/// If the fixture cannot be read or the project cannot be evaluated, or if
+/// any pair is decided differently, listing the changed pairs.
+///
+/// # Example
+/// ```no_run
+/// #[test]
+/// fn rbac_topology_is_unchanged() {
+///     ckp_core::rbac::assert_edge_matrix(
+///         std::path::Path::new("."),
+///         std::path::Path::new("tests/fixtures/edge-matrix.json"),
+///     );
+/// }
+/// ```
+#[track_caller]
+pub fn assert_edge_matrix(root: &Path, fixture: &Path) {
+    let changes = match EdgeMatrix::verify(root, fixture) {
+        Ok(changes) => changes,
+        Err(e) => panic!("Failed to verify edge matrix {}: {}", fixture.display(), e),
+    };
+    if !changes.is_empty() {
+        let lines: Vec<String> = changes.iter().map(|change| format!("  {}", change)).collect();
+        panic!(
+            "Edge authorization differs from {} ({} pair(s)):\n{}\nRegenerate it with `ckp project edge-matrix --out {}` if the change is intended",
+            fixture.display(),
+            changes.len(),
+            lines.join("\n"),
+            fixture.display()
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn write_kernel(root: &Path, name: &str, spec: &str) {
+        let kernel_dir = root.join("concepts").join(name);
+        fs::create_dir_all(&kernel_dir).unwrap();
+        fs::write(
+            kernel_dir.join("conceptkernel.yaml"),
+            format!(
+                "apiVersion: conceptkernel/v1\nkind: Ontology\nmetadata:\n  name: ckp://{}:v0.1\n  type: node:cold\n  version: v0.1\n{}",
+                name, spec
+            ),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_edge_matrix_detects_topology_changes() {
+        let temp = TempDir::new().unwrap();
+        write_kernel(
+            temp.path(),
+            "Orders.Intake",
+            "spec:\n  rbac:\n    communication:\n      allowed:\n        - ckp://Orders.Billing\n",
+        );
+        write_kernel(temp.path(), "Orders.Billing", "");
+        write_kernel(temp.path(), "Orders.Audit", "");
+
+        let matrix = EdgeMatrix::generate(temp.path()).unwrap();
+        assert_eq!(matrix.is_allowed("Orders.Intake", "Orders.Billing"), Some(true));
+        assert_eq!(matrix.is_allowed("Orders.Intake", "Orders.Audit"), Some(false));
+        assert_eq!(matrix.is_allowed("Orders.Billing", "Orders.Intake"), Some(true));
+        assert_eq!(matrix.is_allowed("Orders.Intake", "Orders.Intake"), None);
+
+        let fixture = temp.path().join("edge-matrix.json");
+        fs::write(&fixture, serde_json::to_string_pretty(&matrix).unwrap()).unwrap();
+        assert_edge_matrix(temp.path(), &fixture);
+
+        // Billing starts refusing to talk to Intake
+        write_kernel(
+            temp.path(),
+            "Orders.Billing",
+            "spec:\n  rbac:\n    communication:\n      denied:\n        - ckp://Orders.Intake\n",
+        );
+        assert_eq!(
+            EdgeMatrix::verify(temp.path(), &fixture).unwrap(),
+            vec![EdgeChange {
+                source: "Orders.Billing".to_string(),
+                target: "Orders.Intake".to_string(),
+                expected: Some(true),
+                actual: Some(false),
+            }]
+        );
+        let panic = std::panic::catch_unwind(|| assert_edge_matrix(temp.path(), &fixture)).unwrap_err();
+        let message = panic.downcast_ref::<String>().unwrap();
+        assert!(message.contains("Orders.Billing -> Orders.Intake: allowed in fixture, denied now"));
+    }
+}
@@ -1,12 +1,19 @@
 //! RBAC (Role-Based Access Control) module
 //!
 //! Provides permission checking, communication authorization,
-//! and consensus validation for ConceptKernel.
+//! consensus validation, project-wide policy bundles and edge authorization
+//! fixtures for ConceptKernel.
 //!
 //! Reference: Node.js v1.3.14 - PermissionChecker.js
 
+pub mod bundle;
+pub mod decision_cache;
+pub mod fixture;
 pub mod permission_checker;
 
+pub use bundle::{KernelPolicy, PolicyBundle, BUNDLE_VERSION};
+pub use decision_cache::{DecisionCache, DecisionCacheStats, EMIT_PREDICATE};
+pub use fixture::{assert_edge_matrix, EdgeChange, EdgeMatrix, EDGE_MATRIX_VERSION};
 pub use permission_checker::{PermissionChecker, SelfImprovementConfig};
 
 #[cfg(test)]
@@ -8,12 +8,15 @@
 use crate::errors::{CkpError, Result};
 use crate::ontology::OntologyReader;
 use crate::ontology::library::OntologyLibrary;
+use crate::rbac::decision_cache::{DecisionCache, EMIT_PREDICATE};
 use crate::continuant_tracker::ContinuantTracker;
+use crate::urn::UrnResolver;
 use regex::Regex;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::fs;
 use std::path::PathBuf;
+use std::sync::Arc;
 
 /// Consensus proposal structure
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -27,7 +30,7 @@ struct ConsensusProposal {
 }
 
 /// Self-improvement configuration
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct SelfImprovementConfig {
     pub enabled: bool,
     pub requires_consensus: bool,
@@ -46,11 +49,32 @@ impl Default for SelfImprovementConfig {
     }
 }
 
+impl SelfImprovementConfig {
+    /// Configuration declared by an ontology's `rbac.self_improvement` (defaults for missing keys)
+    pub fn from_value(self_improvement: &serde_json::Value) -> Self {
+        SelfImprovementConfig {
+            enabled: self_improvement.get("enabled").and_then(|v| v.as_bool()).unwrap_or(false),
+            requires_consensus: self_improvement.get("requires_consensus").and_then(|v| v.as_bool()).unwrap_or(true),
+            allowed_actions: self_improvement
+                .get("allowed_actions")
+                .and_then(|v| v.as_array())
+                .map(|arr| arr.iter().filter_map(|v| v.as_str().map(|s| s.to_string())).collect())
+                .unwrap_or_default(),
+            forbidden_actions: self_improvement
+                .get("forbidden_actions")
+                .and_then(|v| v.as_array())
+                .map(|arr| arr.iter().filter_map(|v| v.as_str().map(|s| s.to_string())).collect())
+                .unwrap_or_default(),
+        }
+    }
+}
+
 /// Permission Checker - RBAC enforcement for ConceptKernel
 pub struct PermissionChecker {
     root: PathBuf,
     pattern_cache: HashMap<String, Regex>,
     ontology_lib: Option<OntologyLibrary>,
+    decision_cache: Arc<DecisionCache>,
 }
 
 impl PermissionChecker {
@@ -62,6 +86,7 @@ impl PermissionChecker {
     /// Note: For backward compatibility. Use `new_with_ontology()` for full RBAC support.
     pub fn new(root: PathBuf) -> Self {
         PermissionChecker {
+            decision_cache: DecisionCache::for_project(&root),
             root,
             pattern_cache: HashMap::new(),
             ontology_lib: None,
@@ -79,12 +104,19 @@ impl PermissionChecker {
         let ontology_lib = OntologyLibrary::new(root.clone())?;
 
         Ok(PermissionChecker {
+            decision_cache: DecisionCache::for_project(&root),
             root,
             pattern_cache: HashMap::new(),
             ontology_lib: Some(ontology_lib),
         })
     }
 
+    /// Use `cache` for emit decisions instead of the project's shared cache
+    pub fn with_decision_cache(mut self, cache: Arc<DecisionCache>) -> Self {
+        self.decision_cache = cache;
+        self
+    }
+
     /// Check if user has permission to perform action on kernel
     ///
     /// # Arguments
@@ -174,7 +206,7 @@ impl PermissionChecker {
             match ontology_lib.check_agent_permission(agent_urn, permission) {
                 Ok(has_permission) => return Ok(has_permission),
                 Err(e) => {
-                    eprintln!("[PermissionChecker] SPARQL permission check failed: {}", e);
+                    tracing::warn!(agent = agent_urn, permission, error = %e, "SPARQL permission check failed");
                     // Fall through to username-based check
                 }
             }
@@ -195,6 +227,18 @@ impl PermissionChecker {
 
     /// Check if source kernel can emit to target kernel
     ///
+    /// Targets in another project (`ckp://project/Kernel:v1` or
+    /// `project/Kernel`) must also be listed in the source's
+    /// `rbac.communication.projects`; kernels without that list can only
+    /// emit within their own project.
+    ///
+    /// Kernels in different tenant namespaces (`namespace~Kernel`, the root
+    /// namespace included) are isolated: the target must declare an edge
+    /// from the source in its `queue_contract.edges`.
+    ///
+    /// Decisions are cached until the source's or target's
+    /// conceptkernel.yaml changes.
+    ///
     /// # Arguments
     /// * `source_kernel_urn` - Source kernel URN or simple name
     /// * `target_kernel_urn` - Target kernel URN or simple name
@@ -212,12 +256,22 @@ impl PermissionChecker {
             format!("ckp://{}", target_kernel_urn)
         };
 
+        let target_name = self.extract_kernel_name(&normalized_target);
+        let depends_on = [self.ontology_path(&source_name), self.ontology_path(&target_name)];
+        let cache = Arc::clone(&self.decision_cache);
+        cache.decide(&source_name, &normalized_target, EMIT_PREDICATE, &depends_on, || {
+            self.evaluate_can_emit(&source_name, &normalized_target, target_kernel_urn)
+        })
+    }
+
+    /// Evaluate the source's communication rules for a normalized target URN
+    fn evaluate_can_emit(&mut self, source_name: &str, normalized_target: &str, target_kernel_urn: &str) -> Result<bool> {
         // Load source kernel's ontology
         let ontology_reader = OntologyReader::new(self.root.clone());
-        let ontology = match ontology_reader.read_by_kernel_name(&source_name) {
+        let ontology = match ontology_reader.read_by_kernel_name(source_name) {
             Ok(ont) => ont,
             Err(e) => {
-                eprintln!("[PermissionChecker] Failed to load ontology for {}: {}", source_name, e);
+                tracing::warn!(kernel = source_name, error = %e, "failed to load ontology, denying communication");
                 // Fail closed - deny on error
                 return Ok(false);
             }
@@ -230,30 +284,57 @@ impl PermissionChecker {
         // Check blacklist first (denied patterns)
         if let Some(denied) = comm.and_then(|c| c.denied.as_ref()) {
             for pattern in denied {
-                if self.matches_pattern(&normalized_target, pattern)? {
-                    eprintln!(
-                        "[PermissionChecker] Communication denied: {} -> {} (blacklist: {})",
-                        source_name, target_kernel_urn, pattern
+                if self.matches_pattern(normalized_target, pattern)? {
+                    tracing::warn!(
+                        kernel = source_name,
+                        target = target_kernel_urn,
+                        pattern = %pattern,
+                        "communication denied by blacklist"
                     );
                     return Ok(false);
                 }
             }
         }
 
+        // Check project scope for cross-project targets
+        let (target_project, _) = UrnResolver::split_project(normalized_target.trim_start_matches("ckp://"));
+        if let Some(project) = target_project {
+            let in_scope = comm
+                .and_then(|c| c.projects.as_ref())
+                .is_some_and(|projects| projects.iter().any(|p| p == "*" || p == project));
+
+            if !in_scope {
+                tracing::warn!(
+                    kernel = source_name,
+                    target = target_kernel_urn,
+                    project,
+                    "target project not in scope"
+                );
+                return Ok(false);
+            }
+        } else {
+            // Check namespace isolation: crossing tenants needs an edge declared by the target
+            let target_name = self.extract_kernel_name(normalized_target);
+            let source_namespace = UrnResolver::namespace_of(source_name);
+            if source_namespace != UrnResolver::namespace_of(&target_name)
+                && !self.declares_edge_from(&ontology_reader, source_name, &target_name)
+            {
+                tracing::warn!(kernel = source_name, target = target_kernel_urn, "cross-namespace edge not declared");
+                return Ok(false);
+            }
+        }
+
         // Check whitelist (allowed patterns)
         if let Some(allowed) = comm.and_then(|c| c.allowed.as_ref()) {
             // If whitelist exists, target must be in it
             for allowed_urn in allowed {
-                if allowed_urn == "ckp://*" || self.matches_pattern(&normalized_target, allowed_urn)? {
+                if allowed_urn == "ckp://*" || self.matches_pattern(normalized_target, allowed_urn)? {
                     return Ok(true);
                 }
             }
 
             // Not in whitelist
-            eprintln!(
-                "[PermissionChecker] Communication not in whitelist: {} -> {}",
-                source_name, target_kernel_urn
-            );
+            tracing::warn!(kernel = source_name, target = target_kernel_urn, "communication not in whitelist");
             return Ok(false);
         }
 
@@ -305,7 +386,7 @@ impl PermissionChecker {
                     return quorum_uri.contains("QuorumLow") || quorum_uri.contains("QuorumHigh");
                 },
                 Err(e) => {
-                    eprintln!("[PermissionChecker] Failed to query quorum for permission '{}': {}", permission, e);
+                    tracing::warn!(permission, error = %e, "failed to query quorum for permission");
                     // Fall through to hardcoded list
                 }
             }
@@ -348,20 +429,7 @@ impl PermissionChecker {
         if let Some(spec) = &ontology.spec {
             if let Some(rbac) = &spec.rbac {
                 if let Some(self_improvement) = &rbac.self_improvement {
-                    return Ok(SelfImprovementConfig {
-                        enabled: self_improvement.get("enabled").and_then(|v| v.as_bool()).unwrap_or(false),
-                        requires_consensus: self_improvement.get("requires_consensus").and_then(|v| v.as_bool()).unwrap_or(true),
-                        allowed_actions: self_improvement
-                            .get("allowed_actions")
-                            .and_then(|v| v.as_array())
-                            .map(|arr| arr.iter().filter_map(|v| v.as_str().map(|s| s.to_string())).collect())
-                            .unwrap_or_default(),
-                        forbidden_actions: self_improvement
-                            .get("forbidden_actions")
-                            .and_then(|v| v.as_array())
-                            .map(|arr| arr.iter().filter_map(|v| v.as_str().map(|s| s.to_string())).collect())
-                            .unwrap_or_default(),
-                    });
+                    return Ok(SelfImprovementConfig::from_value(self_improvement));
                 }
             }
         }
@@ -384,10 +452,7 @@ impl PermissionChecker {
         let ontology = match ontology_reader.read_by_kernel_name(&kernel_name) {
             Ok(ont) => ont,
             Err(e) => {
-                eprintln!(
-                    "[PermissionChecker] Failed to load ontology for {}: {}",
-                    kernel_name, e
-                );
+                tracing::warn!(kernel = %kernel_name, error = %e, "failed to load ontology");
                 return Ok(false); // Fail closed
             }
         };
@@ -439,7 +504,7 @@ impl PermissionChecker {
                     // Fall through to default if no permissions found
                 },
                 Err(e) => {
-                    eprintln!("[PermissionChecker] Failed to query ontology for permissions: {}", e);
+                    tracing::warn!(agent = %agent_urn, error = %e, "failed to query ontology for permissions");
                     // Fall through to default
                 }
             }
@@ -630,6 +695,21 @@ impl PermissionChecker {
         Ok(false)
     }
 
+    /// Path of a kernel's conceptkernel.yaml
+    fn ontology_path(&self, kernel_name: &str) -> PathBuf {
+        self.root.join("concepts").join(kernel_name).join("conceptkernel.yaml")
+    }
+
+    /// Whether the target kernel declares an edge from the source in its queue contract
+    fn declares_edge_from(&self, ontology_reader: &OntologyReader, source_name: &str, target_name: &str) -> bool {
+        ontology_reader
+            .read_edges(target_name)
+            .unwrap_or_default()
+            .iter()
+            .filter_map(|edge| UrnResolver::parse_edge_urn(edge).ok())
+            .any(|edge| edge.source == source_name && edge.target == target_name)
+    }
+
     /// Extract kernel name from URN or return simple name
     ///
     /// # Arguments
@@ -799,6 +879,79 @@ spec: {}
         assert!(allowed);
     }
 
+    #[tokio::test]
+    async fn test_can_emit_to_project_scope() {
+        let (temp, mut checker) = setup_test_env();
+
+        let kernel_dir = temp.path().join("concepts/Gateway");
+        fs::create_dir_all(&kernel_dir).unwrap();
+        fs::write(
+            kernel_dir.join("conceptkernel.yaml"),
+            r#"apiVersion: conceptkernel/v1
+kind: Ontology
+metadata:
+  name: ckp://Gateway:v0.1
+  type: node:cold
+  version: v0.1
+spec:
+  rbac:
+    communication:
+      projects:
+        - workers
+      denied:
+        - ckp://workers/Worker.Admin:*
+"#,
+        )
+        .unwrap();
+
+        assert!(checker.can_emit_to("Gateway", "ckp://workers/Worker.Process:v1").unwrap());
+        assert!(checker.can_emit_to("Gateway", "workers/Worker.Process").unwrap());
+        assert!(!checker.can_emit_to("Gateway", "ckp://billing/Invoice:v1").unwrap());
+        assert!(!checker.can_emit_to("Gateway", "ckp://workers/Worker.Admin:v1").unwrap());
+
+        // Local targets are unaffected by the project scope
+        assert!(checker.can_emit_to("Gateway", "ckp://Local.Kernel:v0.1").unwrap());
+
+        // Kernels without a project scope stay inside their own project
+        create_test_ontology(&temp, "Source", vec!["ckp://*"], vec![]);
+        assert!(!checker.can_emit_to("Source", "ckp://workers/Worker.Process:v1").unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_can_emit_to_namespace_isolation() {
+        let (temp, mut checker) = setup_test_env();
+        create_test_ontology(&temp, "acme~Orders", vec![], vec![]);
+        create_test_ontology(&temp, "acme~Billing", vec![], vec![]);
+
+        // Same namespace: unrestricted
+        assert!(checker.can_emit_to("ckp://acme~Orders:v0.1", "ckp://acme~Billing:v0.1").unwrap());
+
+        // Other tenants and the root namespace are out of reach without a declared edge
+        assert!(!checker.can_emit_to("acme~Orders", "ckp://globex~Billing:v0.1").unwrap());
+        assert!(!checker.can_emit_to("acme~Orders", "System.Gateway").unwrap());
+
+        let kernel_dir = temp.path().join("concepts/globex~Billing");
+        fs::create_dir_all(&kernel_dir).unwrap();
+        fs::write(
+            kernel_dir.join("conceptkernel.yaml"),
+            r#"apiVersion: conceptkernel/v1
+kind: Ontology
+metadata:
+  name: ckp://globex~Billing:v0.1
+  type: node:cold
+  version: v0.1
+spec:
+  queue_contract:
+    edges:
+      - ckp://Edge.PRODUCES.acme~Orders-to-globex~Billing
+"#,
+        )
+        .unwrap();
+
+        assert!(checker.can_emit_to("acme~Orders", "ckp://globex~Billing:v0.1").unwrap());
+        assert!(!checker.can_emit_to("acme~Billing", "ckp://globex~Billing:v0.1").unwrap());
+    }
+
     #[test]
     fn test_matches_pattern_exact() {
         let (_temp, mut checker) = setup_test_env();
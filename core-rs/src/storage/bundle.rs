@@ -0,0 +1,375 @@
+// storage/bundle.rs - Instance export/import bundles
+//
+// A bundle is a tar.gz holding selected instance directories under
+// `instances/<id>.inst/` plus a `bundle.json` manifest with each file's
+// SHA-256, so event history can be moved between environments.
+//
+// Symlinks inside an instance are kept when they resolve within that
+// instance (rewritten relative to the link); links pointing elsewhere are
+// replaced by the file they point to, and links to outside directories or
+// missing targets are left out and listed in the manifest.
+
+use super::scanner::InstanceScanner;
+use crate::errors::{CkpError, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fs::{self, File};
+use std::path::{Component, Path, PathBuf};
+
+/// Manifest file name at the bundle root
+pub const BUNDLE_MANIFEST: &str = "bundle.json";
+
+/// Current bundle format version
+pub const BUNDLE_VERSION: u32 = 1;
+
+/// Directory holding the instances inside a bundle
+const INSTANCES_DIR: &str = "instances";
+
+/// Bundle manifest (`bundle.json`)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BundleManifest {
+    pub version: u32,
+
+    /// Kernel the instances were exported from
+    pub kernel: String,
+
+    pub exported_at: DateTime<Utc>,
+
+    pub instances: Vec<BundledInstance>,
+}
+
+/// One instance directory in a bundle
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BundledInstance {
+    /// Instance directory name (`<id>.inst`)
+    pub dir: String,
+
+    pub files: Vec<BundleFile>,
+
+    /// Symlinks left out because they point outside the instance to a
+    /// directory or to nothing
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub skipped_links: Vec<String>,
+}
+
+/// File (or symlink) inside a bundled instance
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BundleFile {
+    /// Path relative to the instance directory, `/`-separated
+    pub path: String,
+
+    /// SHA-256 of the content; None for symlinks
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sha256: Option<String>,
+
+    /// Relative symlink target within the instance
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub link: Option<String>,
+}
+
+impl InstanceScanner {
+    /// Export instances into a portable bundle
+    ///
+    /// # Arguments
+    /// * `ids` - Instance ids or directory stems; empty exports every instance
+    /// * `dest` - Path of the tar.gz to write
+    ///
+    /// # Errors
+    /// `CkpError::FileNotFound` if an id matches no instance
+    pub fn export(&self, ids: &[&str], dest: &Path) -> Result<BundleManifest> {
+        let storage_path = self.find_storage_dir()?;
+        let entries = self.index_entries(&storage_path)?;
+
+        let mut dirs: Vec<String> = if ids.is_empty() {
+            super::index::inst_dirs(&storage_path)
+                .into_iter()
+                .map(|name| name.to_string_lossy().to_string())
+                .collect()
+        } else {
+            ids.iter()
+                .map(|id| {
+                    let dir = format!("{}.inst", id);
+                    if super::shard::locate(&storage_path, &dir).is_dir() {
+                        return Ok(dir);
+                    }
+                    entries.iter()
+                        .find(|e| e.summary.as_ref().is_some_and(|s| s.id == *id))
+                        .map(|e| e.dir.clone())
+                        .ok_or_else(|| CkpError::FileNotFound(format!("Instance '{}' not found in {}", id, self.kernel_name)))
+                })
+                .collect::<Result<_>>()?
+        };
+        dirs.sort();
+        dirs.dedup();
+
+        let mut manifest = BundleManifest {
+            version: BUNDLE_VERSION,
+            kernel: self.kernel_name.clone(),
+            exported_at: Utc::now(),
+            instances: Vec::with_capacity(dirs.len()),
+        };
+        let mut contents: Vec<(String, Content)> = Vec::new();
+        for dir in dirs {
+            let (instance, files) = collect_instance(&super::shard::locate(&storage_path, &dir), &dir)?;
+            manifest.instances.push(instance);
+            contents.extend(files.into_iter().map(|(path, content)| (format!("{}/{}/{}", INSTANCES_DIR, dir, path), content)));
+        }
+
+        write_bundle(dest, BUNDLE_MANIFEST, &manifest, &contents)?;
+        Ok(manifest)
+    }
+}
+
+/// Content of a bundled path
+pub(crate) enum Content {
+    File(Vec<u8>),
+    Link(String),
+}
+
+/// Files of one instance directory, with symlinks resolved as described above
+fn collect_instance(inst_dir: &Path, dir: &str) -> Result<(BundledInstance, Vec<(String, Content)>)> {
+    let root = fs::canonicalize(inst_dir)
+        .map_err(|e| CkpError::IoError(format!("Failed to resolve {}: {}", dir, e)))?;
+    let mut instance = BundledInstance { dir: dir.to_string(), files: Vec::new(), skipped_links: Vec::new() };
+    let mut contents = Vec::new();
+
+    for entry in walkdir::WalkDir::new(inst_dir).min_depth(1).sort_by_file_name() {
+        let entry = entry.map_err(|e| CkpError::IoError(format!("Failed to walk {}: {}", dir, e)))?;
+        let relative = to_slash(entry.path().strip_prefix(inst_dir).unwrap_or(entry.path()));
+
+        if entry.file_type().is_dir() {
+            continue;
+        }
+
+        let content = if entry.path_is_symlink() {
+            match fs::canonicalize(entry.path()) {
+                Ok(target) if target.starts_with(&root) => {
+                    let from = entry.path().parent().and_then(|p| fs::canonicalize(p).ok()).unwrap_or_else(|| root.clone());
+                    Content::Link(relative_link(&from, &target))
+                }
+                Ok(target) if target.is_file() => Content::File(read(&target)?),
+                _ => {
+                    instance.skipped_links.push(relative);
+                    continue;
+                }
+            }
+        } else {
+            Content::File(read(entry.path())?)
+        };
+
+        instance.files.push(match &content {
+            Content::File(bytes) => BundleFile { path: relative.clone(), sha256: Some(hex::encode(Sha256::digest(bytes))), link: None },
+            Content::Link(target) => BundleFile { path: relative.clone(), sha256: None, link: Some(target.clone()) },
+        });
+        contents.push((relative, content));
+    }
+
+    Ok((instance, contents))
+}
+
+pub(crate) fn read(path: &Path) -> Result<Vec<u8>> {
+    fs::read(path).map_err(|e| CkpError::IoError(format!("Failed to read {}: {}", path.display(), e)))
+}
+
+pub(crate) fn to_slash(path: &Path) -> String {
+    path.components().map(|c| c.as_os_str().to_string_lossy()).collect::<Vec<_>>().join("/")
+}
+
+/// Path from directory `from` to `target`, both canonical
+pub(crate) fn relative_link(from: &Path, target: &Path) -> String {
+    let from: Vec<Component> = from.components().collect();
+    let target: Vec<Component> = target.components().collect();
+    let common = from.iter().zip(&target).take_while(|(a, b)| a == b).count();
+
+    let mut parts: Vec<String> = vec!["..".to_string(); from.len() - common];
+    parts.extend(target[common..].iter().map(|c| c.as_os_str().to_string_lossy().to_string()));
+    parts.join("/")
+}
+
+/// Write a tar.gz with the manifest at its root followed by the contents
+pub(crate) fn write_bundle<M: Serialize>(dest: &Path, manifest_name: &str, manifest: &M, contents: &[(String, Content)]) -> Result<()> {
+    use flate2::write::GzEncoder;
+    use flate2::Compression;
+    use tar::{Builder, EntryType, Header};
+
+    if let Some(parent) = dest.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| CkpError::IoError(format!("Failed to create bundle directory: {}", e)))?;
+    }
+    let partial = PathBuf::from(format!("{}.part", dest.display()));
+    let file = File::create(&partial)
+        .map_err(|e| CkpError::IoError(format!("Failed to create bundle: {}", e)))?;
+    let mut tar = Builder::new(GzEncoder::new(file, Compression::default()));
+    let tar_error = |e: std::io::Error| CkpError::IoError(format!("Failed to add file to bundle: {}", e));
+
+    let manifest_json = serde_json::to_vec_pretty(manifest)?;
+    let mut header = Header::new_gnu();
+    header.set_size(manifest_json.len() as u64);
+    header.set_mode(0o644);
+    header.set_cksum();
+    tar.append_data(&mut header, manifest_name, manifest_json.as_slice()).map_err(tar_error)?;
+
+    for (path, content) in contents {
+        let mut header = Header::new_gnu();
+        match content {
+            Content::File(bytes) => {
+                header.set_size(bytes.len() as u64);
+                header.set_mode(0o644);
+                header.set_cksum();
+                tar.append_data(&mut header, path, bytes.as_slice()).map_err(tar_error)?;
+            }
+            Content::Link(target) => {
+                header.set_entry_type(EntryType::Symlink);
+                header.set_size(0);
+                header.set_mode(0o777);
+                tar.append_link(&mut header, path, target).map_err(tar_error)?;
+            }
+        }
+    }
+
+    tar.into_inner()
+        .and_then(|encoder| encoder.finish())
+        .map_err(|e| CkpError::IoError(format!("Failed to finish bundle: {}", e)))?;
+
+    fs::rename(&partial, dest)
+        .map_err(|e| CkpError::IoError(format!("Failed to finalize bundle: {}", e)))
+}
+
+/// Import the instances of a bundle into a storage directory
+///
+/// Instances already present are left untouched. Files are checked against
+/// the manifest before any instance is moved into storage, which is laid out
+/// sharded when `sharded` is set.
+///
+/// # Returns
+/// Paths of the imported instance directories
+pub(crate) fn import_bundle(bundle: &Path, scanner: &InstanceScanner, storage_dir: &Path, sharded: bool) -> Result<Vec<PathBuf>> {
+    fs::create_dir_all(storage_dir)
+        .map_err(|e| CkpError::IoError(format!("Failed to create storage: {}", e)))?;
+    let staging = storage_dir.with_file_name(format!(".import-{}", uuid::Uuid::new_v4()));
+    fs::create_dir_all(&staging)
+        .map_err(|e| CkpError::IoError(format!("Failed to create import staging directory: {}", e)))?;
+
+    let result = (|| -> Result<Vec<PathBuf>> {
+        unpack(bundle, &staging, 2)?;
+
+        let manifest: BundleManifest = serde_json::from_slice(&read(&staging.join(BUNDLE_MANIFEST))?)?;
+        if manifest.version > BUNDLE_VERSION {
+            return Err(CkpError::ValidationError(format!(
+                "Bundle version {} is newer than supported version {}",
+                manifest.version, BUNDLE_VERSION
+            )));
+        }
+
+        for instance in &manifest.instances {
+            verify_files(&staging.join(INSTANCES_DIR).join(&instance.dir), &instance.dir, &instance.files)?;
+        }
+
+        let mut imported = Vec::new();
+        for instance in &manifest.instances {
+            if !instance.dir.ends_with(".inst") || instance.dir.contains(['/', '\\']) {
+                continue;
+            }
+            if super::shard::locate(storage_dir, &instance.dir).exists() {
+                continue;
+            }
+
+            let target = super::shard::entry_path(storage_dir, &instance.dir, sharded);
+            if let Some(parent) = target.parent() {
+                fs::create_dir_all(parent)?;
+            }
+
+            let index_update = scanner.begin_index_update(&target);
+            fs::rename(staging.join(INSTANCES_DIR).join(&instance.dir), &target)
+                .map_err(|e| CkpError::IoError(format!("Failed to import {}: {}", instance.dir, e)))?;
+            index_update.commit()?;
+            imported.push(target);
+        }
+
+        Ok(imported)
+    })();
+
+    let _ = fs::remove_dir_all(&staging);
+    result
+}
+
+/// Unpack a tar.gz into a staging directory
+///
+/// Symlinks must resolve within their top `root_depth` path components
+/// (e.g. `instances/<dir>` for 2), so nothing unpacked points outside.
+pub(crate) fn unpack(archive: &Path, staging: &Path, root_depth: usize) -> Result<()> {
+    use flate2::read::GzDecoder;
+    use tar::{Archive, EntryType};
+
+    let file = File::open(archive)
+        .map_err(|e| CkpError::IoError(format!("Failed to open bundle: {}", e)))?;
+    let mut archive = Archive::new(GzDecoder::new(file));
+
+    for entry in archive.entries().map_err(|e| CkpError::IoError(format!("Failed to read bundle: {}", e)))? {
+        let mut entry = entry.map_err(|e| CkpError::IoError(format!("Failed to read bundle: {}", e)))?;
+        let path = entry.path().map_err(|e| CkpError::IoError(format!("Invalid bundle entry: {}", e)))?.into_owned();
+
+        if entry.header().entry_type() == EntryType::Symlink {
+            let target = entry.link_name().ok().flatten().map(|t| t.into_owned()).unwrap_or_default();
+            if !link_stays_within(&path, &target, root_depth) {
+                return Err(CkpError::ValidationError(format!(
+                    "Bundle symlink {} escapes its root",
+                    path.display()
+                )));
+            }
+        }
+        let unpacked = entry.unpack_in(staging)
+            .map_err(|e| CkpError::IoError(format!("Failed to unpack {}: {}", path.display(), e)))?;
+        if !unpacked {
+            return Err(CkpError::ValidationError(format!("Unsafe bundle entry: {}", path.display())));
+        }
+    }
+
+    Ok(())
+}
+
+/// Check unpacked files against their manifest checksums
+pub(crate) fn verify_files(dir: &Path, label: &str, files: &[BundleFile]) -> Result<()> {
+    for file in files {
+        let Some(expected) = &file.sha256 else {
+            continue;
+        };
+        let actual = hex::encode(Sha256::digest(read(&dir.join(&file.path))?));
+        if &actual != expected {
+            return Err(CkpError::ValidationError(format!(
+                "Checksum mismatch for {}/{}",
+                label, file.path
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// Whether a relative symlink target resolves within the link's top
+/// `root_depth` path components
+fn link_stays_within(link: &Path, target: &Path, root_depth: usize) -> bool {
+    // Depth of the link's directory below the root
+    let mut depth = link.components().count() as isize - root_depth as isize - 1;
+    if depth < 0 {
+        return false;
+    }
+
+    for component in target.components() {
+        match component {
+            Component::Normal(_) => depth += 1,
+            Component::CurDir => {}
+            Component::ParentDir => {
+                depth -= 1;
+                if depth < 0 {
+                    return false;
+                }
+            }
+            Component::RootDir | Component::Prefix(_) => return false,
+        }
+    }
+    true
+}
@@ -0,0 +1,369 @@
+// storage/index.rs - Persistent instance index
+//
+// Keeps the envelope summaries of a storage directory in a sidecar next to
+// it, so listing and counting instances does not read every receipt.bin:
+//
+//   storage.index.jsonl  one summary per line, sorted by (name, directory),
+//                        followed by a short tail of unsorted updates
+//   storage.index.json   totals (instances and their bytes) and the storage
+//                        directory mtime the index was built against
+//
+// Mints and archives append to the tail and adjust the totals by the size
+// of the instance directory before and after the change, so quotas read
+// storage usage without walking it; once the tail grows past
+// COMPACT_THRESHOLD the file is rewritten fully sorted. An index whose
+// recorded mtime differs from the storage directory (or, when sharded, its
+// newest shard) is stale and rebuilt by the scanner. Updates assume one
+// writer per kernel, as queue processing does.
+
+use super::scanner::{InstanceScanner, InstanceSummary};
+use super::shard;
+use crate::errors::{CkpError, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::ffi::OsString;
+use std::fs::{self, File, OpenOptions};
+use std::io::{BufRead, BufReader, Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+
+/// Index entries, written next to the storage directory
+pub const INDEX_FILE: &str = "storage.index.jsonl";
+
+/// Index metadata, written next to the storage directory
+pub const INDEX_META_FILE: &str = "storage.index.json";
+
+const INDEX_VERSION: u32 = 3;
+
+/// Tail updates tolerated before the entries are rewritten sorted
+const COMPACT_THRESHOLD: usize = 1024;
+
+/// Filesystem timestamps can be coarse; a storage change this close to the
+/// index write may not have moved the directory mtime yet
+const SETTLE_SECONDS: i64 = 2;
+
+/// One line of the index: a summary, or the removal of an instance directory
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(super) struct IndexLine {
+    /// Instance directory name (`<id>.inst`)
+    pub dir: String,
+
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub removed: bool,
+
+    /// Action that created the instance
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub action: Option<String>,
+
+    /// Processing success status
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub success: Option<bool>,
+
+    #[serde(flatten)]
+    pub summary: Option<InstanceSummary>,
+}
+
+impl IndexLine {
+    pub(super) fn entry(dir: String, summary: InstanceSummary) -> Self {
+        Self { dir, removed: false, action: None, success: None, summary: Some(summary) }
+    }
+
+    fn unreadable(dir: String) -> Self {
+        Self { dir, removed: false, action: None, success: None, summary: None }
+    }
+
+    fn removal(dir: String) -> Self {
+        Self { removed: true, ..Self::unreadable(dir) }
+    }
+
+    /// Sort key of the index: lowercase name, then directory
+    pub(super) fn key(&self) -> (String, &str) {
+        let name = self.summary.as_ref().map(|s| s.name.to_lowercase()).unwrap_or_default();
+        (name, &self.dir)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct IndexMeta {
+    version: u32,
+
+    /// `.inst` directories in storage, including unreadable ones
+    total: usize,
+
+    /// `.inst` directories whose receipt could not be read
+    unreadable: usize,
+
+    /// Size of the files in the `.inst` directories
+    bytes: u64,
+
+    /// Length of the sorted part of the entries file
+    sorted_bytes: u64,
+
+    /// Lines appended after the sorted part
+    tail: usize,
+
+    /// Storage directory mtime the index reflects
+    storage_modified: DateTime<Utc>,
+
+    indexed_at: DateTime<Utc>,
+}
+
+/// Sidecar index of one storage directory
+#[derive(Debug, Clone)]
+pub(super) struct InstanceIndex {
+    storage_dir: PathBuf,
+}
+
+impl InstanceIndex {
+    pub(super) fn new(storage_dir: &Path) -> Self {
+        Self { storage_dir: storage_dir.to_path_buf() }
+    }
+
+    fn sidecar(&self, name: &str) -> PathBuf {
+        self.storage_dir.with_file_name(name)
+    }
+
+    /// Current mtime of the storage directory, including its shards
+    pub(super) fn storage_modified(&self) -> Result<DateTime<Utc>> {
+        shard::modified(&self.storage_dir)
+            .map(DateTime::<Utc>::from)
+            .map_err(|e| CkpError::IoError(format!("Failed to stat storage directory: {}", e)))
+    }
+
+    fn load_meta(&self) -> Option<IndexMeta> {
+        let content = fs::read_to_string(self.sidecar(INDEX_META_FILE)).ok()?;
+        let meta: IndexMeta = serde_json::from_str(&content).ok()?;
+        (meta.version == INDEX_VERSION).then_some(meta)
+    }
+
+    fn save_meta(&self, meta: &IndexMeta) -> Result<()> {
+        let path = self.sidecar(INDEX_META_FILE);
+        let part = path.with_extension("json.part");
+        fs::write(&part, serde_json::to_string_pretty(meta)?)
+            .and_then(|_| fs::rename(&part, &path))
+            .map_err(|e| CkpError::IoError(format!("Failed to write instance index: {}", e)))
+    }
+
+    /// Whether the index still matches the storage directory
+    ///
+    /// Within the settle window after the last indexed change, the recorded
+    /// total is also checked against the directory itself.
+    pub(super) fn is_fresh(&self) -> bool {
+        let Some(meta) = self.load_meta() else {
+            return false;
+        };
+        let entries_len = fs::metadata(self.sidecar(INDEX_FILE)).map(|m| m.len()).unwrap_or(0);
+        if entries_len < meta.sorted_bytes || self.storage_modified().ok() != Some(meta.storage_modified) {
+            return false;
+        }
+
+        let settled = meta.indexed_at - meta.storage_modified >= chrono::Duration::seconds(SETTLE_SECONDS);
+        settled || (meta.unreadable == 0 && inst_dirs(&self.storage_dir).len() == meta.total)
+    }
+
+    /// Number of instance directories, if an index exists
+    pub(super) fn total(&self) -> Option<usize> {
+        self.load_meta().map(|meta| meta.total)
+    }
+
+    /// Size of the instance directories, if an index exists
+    pub(super) fn bytes(&self) -> Option<u64> {
+        self.load_meta().map(|meta| meta.bytes)
+    }
+
+    /// Replace the index with a full scan of storage
+    ///
+    /// # Arguments
+    /// * `entries` - Readable instances, sorted with `IndexLine::key`
+    /// * `unreadable` - Instance directories without a readable receipt
+    /// * `bytes` - Size of the instance directories
+    /// * `storage_modified` - Storage directory mtime taken before the scan
+    pub(super) fn write(&self, entries: &[IndexLine], unreadable: usize, bytes: u64, storage_modified: DateTime<Utc>) -> Result<()> {
+        let path = self.sidecar(INDEX_FILE);
+        let part = path.with_extension("jsonl.part");
+        let mut content = String::new();
+        for entry in entries {
+            content.push_str(&serde_json::to_string(entry)?);
+            content.push('\n');
+        }
+        fs::write(&part, &content)
+            .and_then(|_| fs::rename(&part, &path))
+            .map_err(|e| CkpError::IoError(format!("Failed to write instance index: {}", e)))?;
+
+        self.save_meta(&IndexMeta {
+            version: INDEX_VERSION,
+            total: entries.len() + unreadable,
+            unreadable,
+            bytes,
+            sorted_bytes: content.len() as u64,
+            tail: 0,
+            storage_modified,
+            indexed_at: Utc::now(),
+        })
+    }
+
+    /// Indexed summaries sorted by name
+    ///
+    /// # Arguments
+    /// * `limit` - Maximum number of summaries to return (0 = unlimited)
+    pub(super) fn list(&self, limit: usize) -> Result<Vec<InstanceSummary>> {
+        Ok(self.entries(limit)?.into_iter().filter_map(|entry| entry.summary).collect())
+    }
+
+    /// Readable entries with the tail merged into sorted order
+    pub(super) fn entries(&self, limit: usize) -> Result<Vec<IndexLine>> {
+        let meta = self.load_meta()
+            .ok_or_else(|| CkpError::FileNotFound("Instance index not found".to_string()))?;
+        let mut file = File::open(self.sidecar(INDEX_FILE))
+            .map_err(|e| CkpError::IoError(format!("Failed to open instance index: {}", e)))?;
+
+        // Latest tail line per directory overrides the sorted part
+        let mut tail = BTreeMap::new();
+        file.seek(SeekFrom::Start(meta.sorted_bytes))?;
+        for line in BufReader::new(&mut file).lines() {
+            let line: IndexLine = serde_json::from_str(&line?)?;
+            tail.insert(line.dir.clone(), line);
+        }
+        let overridden: BTreeSet<String> = tail.keys().cloned().collect();
+        let mut added: Vec<IndexLine> = tail.into_values().filter(|l| !l.removed && l.summary.is_some()).collect();
+        added.sort_by(|a, b| a.key().cmp(&b.key()));
+        let mut added = added.into_iter().peekable();
+
+        let limit = if limit == 0 { usize::MAX } else { limit };
+        let mut merged = Vec::new();
+        file.seek(SeekFrom::Start(0))?;
+        for line in BufReader::new(file.take(meta.sorted_bytes)).lines() {
+            if merged.len() >= limit {
+                break;
+            }
+            let entry: IndexLine = serde_json::from_str(&line?)?;
+            if overridden.contains(&entry.dir) || entry.summary.is_none() {
+                continue;
+            }
+            while merged.len() < limit && added.peek().is_some_and(|a| a.key() <= entry.key()) {
+                merged.extend(added.next());
+            }
+            if merged.len() < limit {
+                merged.push(entry);
+            }
+        }
+        merged.extend(added);
+        merged.truncate(limit);
+
+        Ok(merged)
+    }
+
+    /// Record a change to one instance directory
+    ///
+    /// # Arguments
+    /// * `line` - The directory's entry now; a removal if it no longer exists,
+    ///   an entry without summary if its receipt is unreadable
+    /// * `existed` - Whether the directory existed before the change
+    /// * `bytes` - Size of the directory before and after the change
+    pub(super) fn record(&self, line: &IndexLine, existed: bool, bytes: (u64, u64)) -> Result<()> {
+        let mut meta = self.load_meta()
+            .ok_or_else(|| CkpError::FileNotFound("Instance index not found".to_string()))?;
+        let exists = !line.removed;
+        let mut file = OpenOptions::new()
+            .append(true)
+            .open(self.sidecar(INDEX_FILE))
+            .map_err(|e| CkpError::IoError(format!("Failed to open instance index: {}", e)))?;
+        writeln!(file, "{}", serde_json::to_string(line)?)
+            .map_err(|e| CkpError::IoError(format!("Failed to update instance index: {}", e)))?;
+
+        match (existed, exists) {
+            (false, true) => meta.total += 1,
+            (true, false) => meta.total = meta.total.saturating_sub(1),
+            _ => {}
+        }
+        meta.bytes = meta.bytes.saturating_sub(bytes.0).saturating_add(bytes.1);
+        // Without per-directory state, unreadable counts only ever err high
+        if exists && line.summary.is_none() {
+            meta.unreadable += 1;
+        }
+        meta.tail += 1;
+        meta.storage_modified = self.storage_modified()?;
+        meta.indexed_at = Utc::now();
+
+        if meta.tail > COMPACT_THRESHOLD {
+            self.save_meta(&meta)?;
+            let entries = self.entries(0)?;
+            let unreadable = meta.total.saturating_sub(entries.len());
+            return self.write(&entries, unreadable, meta.bytes, meta.storage_modified);
+        }
+        self.save_meta(&meta)
+    }
+}
+
+/// Pending index update for one instance directory
+///
+/// Taken before the directory is created or removed, so a change made while
+/// the index was already stale is left to the next rebuild instead of being
+/// recorded against it.
+pub struct IndexUpdate<'a> {
+    scanner: &'a InstanceScanner,
+    inst_dir: PathBuf,
+    index: Option<InstanceIndex>,
+    existed: bool,
+    bytes: u64,
+}
+
+impl<'a> IndexUpdate<'a> {
+    pub(super) fn begin(scanner: &'a InstanceScanner, inst_dir: &Path) -> Self {
+        let index = shard::entry_dir(inst_dir)
+            .map(InstanceIndex::new)
+            .filter(|index| index.is_fresh());
+        let bytes = if index.is_some() { dir_size(inst_dir) } else { 0 };
+
+        Self {
+            scanner,
+            inst_dir: inst_dir.to_path_buf(),
+            index,
+            existed: inst_dir.is_dir(),
+            bytes,
+        }
+    }
+
+    /// Record the instance directory as it is now
+    pub fn commit(self) -> Result<()> {
+        let Some(index) = self.index else {
+            return Ok(());
+        };
+        let Some(dir) = self.inst_dir.file_name().map(|n| n.to_string_lossy().to_string()) else {
+            return Ok(());
+        };
+
+        let line = if self.inst_dir.is_dir() {
+            self.scanner.read_index_line(&self.inst_dir).unwrap_or_else(|_| IndexLine::unreadable(dir))
+        } else {
+            IndexLine::removal(dir)
+        };
+        index.record(&line, self.existed, (self.bytes, dir_size(&self.inst_dir)))
+    }
+}
+
+/// Total size of the files under a directory (symlinks are not followed)
+pub(super) fn dir_size(dir: &Path) -> u64 {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return 0;
+    };
+
+    entries
+        .flatten()
+        .map(|entry| match entry.file_type() {
+            Ok(file_type) if file_type.is_dir() => dir_size(&entry.path()),
+            Ok(file_type) if file_type.is_file() => entry.metadata().map(|m| m.len()).unwrap_or(0),
+            _ => 0,
+        })
+        .sum()
+}
+
+/// Names of the `.inst` directories in storage, flat or sharded
+pub(super) fn inst_dirs(storage_dir: &Path) -> Vec<OsString> {
+    shard::list_entries(storage_dir)
+        .into_iter()
+        .filter(|path| path.is_dir() && path.extension().and_then(|s| s.to_str()) == Some("inst"))
+        .filter_map(|path| path.file_name().map(|name| name.to_os_string()))
+        .collect()
+}
@@ -1,8 +1,25 @@
 // storage/mod.rs - Storage subsystem
 
+pub mod bundle;
+pub mod index;
+pub mod page;
+pub mod query;
+pub mod receipt;
 pub mod scanner;
-
+pub mod search;
+pub mod shard;
+pub mod walk;
+pub mod watch;
+
+pub use bundle::{BundleFile, BundleManifest, BundledInstance, BUNDLE_MANIFEST, BUNDLE_VERSION};
+pub use index::{IndexUpdate, INDEX_FILE, INDEX_META_FILE};
+pub use page::{InstanceOrder, InstancePage, InstancePageRequest, DEFAULT_INSTANCE_PAGE_SIZE};
+pub use query::{FieldComparison, FieldPredicate, InstanceFilter};
+pub use receipt::{Receipt, RECEIPT_FILE, RECEIPT_SCHEMA_VERSION};
 pub use scanner::{InstanceScanner, InstanceSummary, InstanceDetail};
+pub use search::{ProjectInstance, ProjectScanner};
+pub use shard::{ShardMigration, SHARDING_CAPABILITY};
+pub use watch::{InstanceStream, WATCH_POLL_INTERVAL};
 
 #[cfg(test)]
 mod tests {
@@ -70,6 +87,9 @@ mod tests {
             timestamp: Utc::now(),
             action: Some("test_action".to_string()),
             success: Some(true),
+            inputs: vec![],
+            outputs: vec![],
+            digests: Default::default(),
             data: json!({
                 "id": "tx-123",
                 "kernel": "Test.Kernel",
@@ -0,0 +1,103 @@
+// storage/page.rs - Cursor pagination over instances
+//
+// Pages are keyset-based: a cursor encodes the sort key of the last instance
+// returned, and the next page starts strictly after it. Instances minted
+// between requests therefore never shift or repeat earlier pages.
+
+use super::scanner::InstanceSummary;
+use crate::errors::{CkpError, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+
+/// Default page size when `InstancePageRequest::limit` is unset
+pub const DEFAULT_INSTANCE_PAGE_SIZE: usize = 50;
+
+/// Sort order of instance pages
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum InstanceOrder {
+    /// Creation time, then id
+    #[default]
+    Timestamp,
+
+    Id,
+}
+
+/// Request for one page of `InstanceScanner::list_page`
+#[derive(Debug, Clone, Default)]
+pub struct InstancePageRequest {
+    pub order: InstanceOrder,
+
+    /// Newest (or highest id) first
+    pub descending: bool,
+
+    /// `next_cursor` of the previous page; None for the first page
+    pub cursor: Option<String>,
+
+    /// Maximum instances per page (default: DEFAULT_INSTANCE_PAGE_SIZE)
+    pub limit: Option<usize>,
+}
+
+/// One page of instances
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InstancePage {
+    pub instances: Vec<InstanceSummary>,
+
+    /// Cursor for the next page, if there is one
+    #[serde(rename = "nextCursor")]
+    pub next_cursor: Option<String>,
+}
+
+/// Position after which a page starts
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(super) struct PageKey {
+    order: InstanceOrder,
+    descending: bool,
+    timestamp: DateTime<Utc>,
+    id: String,
+
+    /// Instance directory, unique where ids may not be
+    dir: String,
+}
+
+impl PageKey {
+    pub(super) fn new(request: &InstancePageRequest, dir: &str, summary: &InstanceSummary) -> Self {
+        Self {
+            order: request.order,
+            descending: request.descending,
+            timestamp: summary.timestamp,
+            id: summary.id.clone(),
+            dir: dir.to_string(),
+        }
+    }
+
+    /// Opaque cursor string
+    pub(super) fn encode(&self) -> Result<String> {
+        Ok(hex::encode(serde_json::to_vec(self)?))
+    }
+
+    /// Decode a cursor issued for the same order and direction
+    pub(super) fn decode(cursor: &str, request: &InstancePageRequest) -> Result<Self> {
+        let key: PageKey = hex::decode(cursor)
+            .ok()
+            .and_then(|bytes| serde_json::from_slice(&bytes).ok())
+            .ok_or_else(|| CkpError::ValidationError(format!("Invalid instance cursor: {}", cursor)))?;
+
+        if key.order != request.order || key.descending != request.descending {
+            return Err(CkpError::ValidationError(
+                "Instance cursor was issued for a different order".to_string(),
+            ));
+        }
+        Ok(key)
+    }
+
+    /// Compare positions in ascending order
+    pub(super) fn cmp_position(&self, other: &Self) -> Ordering {
+        let primary = match self.order {
+            InstanceOrder::Timestamp => self.timestamp.cmp(&other.timestamp).then_with(|| self.id.cmp(&other.id)),
+            InstanceOrder::Id => self.id.cmp(&other.id),
+        };
+        primary.then_with(|| self.dir.cmp(&other.dir))
+    }
+}
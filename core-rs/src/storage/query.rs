@@ -0,0 +1,171 @@
+// storage/query.rs - Instance queries
+//
+// Filters for InstanceScanner::query. Envelope filters (timestamp range,
+// action, success) are answered from the storage index; only instances that
+// pass them have their receipt read for payload field predicates.
+
+use super::index::IndexLine;
+use super::scanner::InstanceDetail;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::cmp::Ordering;
+
+/// Comparison between a payload field and a predicate value
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FieldComparison {
+    Eq,
+    Ne,
+    Gt,
+    Gte,
+    Lt,
+    Lte,
+}
+
+/// Predicate over one field of an instance's data payload
+///
+/// Numbers compare numerically, RFC 3339 timestamps chronologically and
+/// other strings lexicographically. A missing field never matches.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FieldPredicate {
+    /// Dotted path into the payload (e.g. "order.total")
+    pub field: String,
+
+    pub comparison: FieldComparison,
+
+    pub value: Value,
+}
+
+impl FieldPredicate {
+    pub fn new(field: impl Into<String>, comparison: FieldComparison, value: impl Into<Value>) -> Self {
+        Self {
+            field: field.into(),
+            comparison,
+            value: value.into(),
+        }
+    }
+
+    /// Check the predicate against a data payload
+    pub fn holds(&self, data: &Value) -> bool {
+        let Some(actual) = self.field.split('.').try_fold(data, |current, key| current.get(key)) else {
+            return false;
+        };
+
+        let ordering = compare(actual, &self.value);
+        match self.comparison {
+            FieldComparison::Eq => ordering == Some(Ordering::Equal),
+            FieldComparison::Ne => ordering != Some(Ordering::Equal),
+            FieldComparison::Gt => ordering == Some(Ordering::Greater),
+            FieldComparison::Gte => matches!(ordering, Some(Ordering::Greater | Ordering::Equal)),
+            FieldComparison::Lt => ordering == Some(Ordering::Less),
+            FieldComparison::Lte => matches!(ordering, Some(Ordering::Less | Ordering::Equal)),
+        }
+    }
+}
+
+/// Filters for `InstanceScanner::query`
+#[derive(Debug, Clone, Default)]
+pub struct InstanceFilter {
+    /// Only instances created at or after this time
+    pub since: Option<DateTime<Utc>>,
+
+    /// Only instances created before this time
+    pub until: Option<DateTime<Utc>>,
+
+    /// Only instances created by this action
+    pub action: Option<String>,
+
+    /// Only successful (true) or failed (false) instances
+    pub success: Option<bool>,
+
+    /// Predicates over the data payload, all of which must hold
+    pub fields: Vec<FieldPredicate>,
+
+    /// Maximum number of instances to return
+    pub limit: Option<usize>,
+}
+
+impl InstanceFilter {
+    /// Add a payload field predicate
+    pub fn with_field(mut self, field: impl Into<String>, comparison: FieldComparison, value: impl Into<Value>) -> Self {
+        self.fields.push(FieldPredicate::new(field, comparison, value));
+        self
+    }
+
+    /// Check whether an instance satisfies the filters
+    ///
+    /// `limit` only affects result sets and is ignored here.
+    pub fn matches(&self, detail: &InstanceDetail) -> bool {
+        self.matches_envelope(detail.timestamp, detail.action.as_deref(), detail.success)
+            && self.fields.iter().all(|predicate| predicate.holds(&detail.data))
+    }
+
+    /// Envelope filters checked against an index entry
+    pub(super) fn matches_index(&self, line: &IndexLine) -> bool {
+        line.summary.as_ref().is_some_and(|summary| {
+            self.matches_envelope(summary.timestamp, line.action.as_deref(), line.success)
+        })
+    }
+
+    fn matches_envelope(&self, timestamp: DateTime<Utc>, action: Option<&str>, success: Option<bool>) -> bool {
+        if self.since.is_some_and(|since| timestamp < since) {
+            return false;
+        }
+
+        if self.until.is_some_and(|until| timestamp >= until) {
+            return false;
+        }
+
+        if let Some(wanted) = &self.action {
+            if action != Some(wanted.as_str()) {
+                return false;
+            }
+        }
+
+        if let Some(wanted) = self.success {
+            if success != Some(wanted) {
+                return false;
+            }
+        }
+
+        true
+    }
+}
+
+fn compare(actual: &Value, expected: &Value) -> Option<Ordering> {
+    match (actual, expected) {
+        (Value::Number(a), Value::Number(b)) => a.as_f64()?.partial_cmp(&b.as_f64()?),
+        (Value::String(a), Value::String(b)) => {
+            match (DateTime::parse_from_rfc3339(a), DateTime::parse_from_rfc3339(b)) {
+                (Ok(a), Ok(b)) => Some(a.cmp(&b)),
+                _ => Some(a.cmp(b)),
+            }
+        }
+        (Value::Bool(a), Value::Bool(b)) => Some(a.cmp(b)),
+        _ => (actual == expected).then_some(Ordering::Equal),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_field_predicates() {
+        let data = json!({
+            "total": 12.5,
+            "status": "paid",
+            "dueAt": "2025-11-29T10:00:00+01:00",
+            "order": {"items": 3}
+        });
+
+        assert!(FieldPredicate::new("total", FieldComparison::Gte, 12).holds(&data));
+        assert!(FieldPredicate::new("order.items", FieldComparison::Eq, 3).holds(&data));
+        assert!(FieldPredicate::new("status", FieldComparison::Ne, "refunded").holds(&data));
+        assert!(FieldPredicate::new("dueAt", FieldComparison::Lt, "2025-11-29T09:30:00Z").holds(&data));
+        assert!(!FieldPredicate::new("status", FieldComparison::Gt, 1).holds(&data));
+        assert!(!FieldPredicate::new("missing", FieldComparison::Ne, "x").holds(&data));
+    }
+}
@@ -0,0 +1,279 @@
+// storage/receipt.rs - CKI receipt schema
+//
+// A receipt (receipt.bin, JSON) is the envelope of a Concept Kernel Instance:
+// identity, the action that produced it, the instances it consumed and
+// produced, SHA-256 digests of the files beside it, and the kernel-specific
+// data payload, and the correlation and causation IDs of the chain that led
+// to it. Receipts written before the schema existed carry no
+// `schemaVersion` and are read as version 0 by InstanceScanner. Kernels that
+// negotiate a binary encoding write receipt.msgpack or receipt.cbor instead.
+
+use crate::drivers::{Encoding, MappedFile};
+use crate::errors::{CkpError, Result};
+use chrono::{DateTime, Utc};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+
+/// Receipt file name inside an instance directory
+pub const RECEIPT_FILE: &str = "receipt.bin";
+
+/// Current receipt schema version
+pub const RECEIPT_SCHEMA_VERSION: u32 = 1;
+
+/// Typed CKI receipt
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct Receipt {
+    /// Schema version (0 for receipts predating the schema)
+    #[serde(default)]
+    pub schema_version: u32,
+
+    /// Instance ID (usually txId)
+    pub id: String,
+
+    /// Human-readable name
+    pub name: String,
+
+    /// Kernel that created this instance
+    pub kernel: String,
+
+    /// Creation timestamp
+    pub timestamp: DateTime<Utc>,
+
+    /// Action that created this instance
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub action: Option<String>,
+
+    /// Processing success status
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub success: Option<bool>,
+
+    /// URNs or ids of the instances this one was derived from
+    #[serde(default)]
+    pub inputs: Vec<String>,
+
+    /// URNs or ids of the instances this one produced
+    #[serde(default)]
+    pub outputs: Vec<String>,
+
+    /// Transaction ID of the job that started the chain this instance belongs to
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub correlation_id: Option<String>,
+
+    /// Transaction ID of the job that caused this instance
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub causation_id: Option<String>,
+
+    /// SHA-256 hex digests of files in the instance directory, by relative path
+    #[serde(default)]
+    pub digests: BTreeMap<String, String>,
+
+    /// Data payload (kernel-specific)
+    #[serde(default)]
+    pub data: Value,
+}
+
+impl Receipt {
+    /// Receipt at the current schema version, timestamped now
+    pub fn new(id: impl Into<String>, name: impl Into<String>, kernel: impl Into<String>, data: Value) -> Self {
+        Self {
+            schema_version: RECEIPT_SCHEMA_VERSION,
+            id: id.into(),
+            name: name.into(),
+            kernel: kernel.into(),
+            timestamp: Utc::now(),
+            action: None,
+            success: None,
+            inputs: Vec::new(),
+            outputs: Vec::new(),
+            correlation_id: None,
+            causation_id: None,
+            digests: BTreeMap::new(),
+            data,
+        }
+    }
+
+    /// Receipt for a minted payload
+    ///
+    /// Takes `name`, `action`, `success`, `inputs`, `outputs`,
+    /// `correlationId` and `causationId` from the payload's top level where
+    /// present; the name defaults to the id.
+    pub fn for_payload(id: &str, kernel: &str, data: &Value) -> Self {
+        let strings = |key: &str| -> Vec<String> {
+            data.get(key)
+                .and_then(|v| v.as_array())
+                .map(|items| items.iter().filter_map(|v| v.as_str().map(|s| s.to_string())).collect())
+                .unwrap_or_default()
+        };
+
+        let mut receipt = Self::new(id, data.get("name").and_then(|v| v.as_str()).unwrap_or(id), kernel, data.clone());
+        receipt.action = data.get("action").and_then(|v| v.as_str()).map(|s| s.to_string());
+        receipt.success = data.get("success").and_then(|v| v.as_bool());
+        receipt.inputs = strings("inputs");
+        receipt.outputs = strings("outputs");
+        receipt.correlation_id = data.get("correlationId").and_then(|v| v.as_str()).map(|s| s.to_string());
+        receipt.causation_id = data.get("causationId").and_then(|v| v.as_str()).map(|s| s.to_string());
+        receipt
+    }
+
+    pub fn with_action(mut self, action: impl Into<String>) -> Self {
+        self.action = Some(action.into());
+        self
+    }
+
+    pub fn with_success(mut self, success: bool) -> Self {
+        self.success = Some(success);
+        self
+    }
+
+    pub fn with_input(mut self, input: impl Into<String>) -> Self {
+        self.inputs.push(input.into());
+        self
+    }
+
+    pub fn with_output(mut self, output: impl Into<String>) -> Self {
+        self.outputs.push(output.into());
+        self
+    }
+
+    /// Link to the job that caused this instance (see `JobStamp::caused_by`)
+    pub fn caused_by(mut self, tx_id: &str, correlation_id: Option<&str>) -> Self {
+        self.causation_id = Some(tx_id.to_string());
+        self.correlation_id = Some(correlation_id.unwrap_or(tx_id).to_string());
+        self
+    }
+
+    /// Record the digest of a file's content
+    pub fn with_digest(mut self, path: impl Into<String>, content: &[u8]) -> Self {
+        self.digests.insert(path.into(), hex::encode(Sha256::digest(content)));
+        self
+    }
+
+    /// Check the receipt against the schema
+    ///
+    /// # Errors
+    /// `CkpError::ValidationError` describing the first violation
+    pub fn validate(&self) -> Result<()> {
+        if self.schema_version > RECEIPT_SCHEMA_VERSION {
+            return Err(CkpError::ValidationError(format!(
+                "Receipt schema version {} is newer than supported version {}",
+                self.schema_version, RECEIPT_SCHEMA_VERSION
+            )));
+        }
+
+        for (field, value) in [("id", &self.id), ("name", &self.name), ("kernel", &self.kernel)] {
+            if value.trim().is_empty() {
+                return Err(CkpError::ValidationError(format!("Receipt {} is empty", field)));
+            }
+        }
+
+        if self.action.as_deref().is_some_and(|action| action.trim().is_empty()) {
+            return Err(CkpError::ValidationError("Receipt action is empty".to_string()));
+        }
+
+        for (field, refs) in [("inputs", &self.inputs), ("outputs", &self.outputs)] {
+            if refs.iter().any(|r| r.trim().is_empty()) {
+                return Err(CkpError::ValidationError(format!("Receipt {} contain an empty reference", field)));
+            }
+        }
+
+        for (field, id) in [("correlationId", &self.correlation_id), ("causationId", &self.causation_id)] {
+            if id.as_deref().is_some_and(|id| id.trim().is_empty()) {
+                return Err(CkpError::ValidationError(format!("Receipt {} is empty", field)));
+            }
+        }
+
+        for (path, digest) in &self.digests {
+            let relative = Path::new(path);
+            if path.is_empty() || !relative.components().all(|c| matches!(c, Component::Normal(_))) {
+                return Err(CkpError::ValidationError(format!("Invalid receipt digest path: {}", path)));
+            }
+            if digest.len() != 64 || !digest.bytes().all(|b| b.is_ascii_digit() || (b'a'..=b'f').contains(&b)) {
+                return Err(CkpError::ValidationError(format!("Invalid SHA-256 digest for {}", path)));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Check the recorded digests against the files in an instance directory
+    ///
+    /// Receipts read from disk have not been validated: digest paths that
+    /// would leave the instance directory are rejected rather than read.
+    pub fn verify_digests(&self, inst_dir: &Path) -> Result<()> {
+        for (path, expected) in &self.digests {
+            if path.is_empty() || !Path::new(path).components().all(|c| matches!(c, Component::Normal(_))) {
+                return Err(CkpError::ValidationError(format!("Invalid receipt digest path: {}", path)));
+            }
+            let content = MappedFile::open(&inst_dir.join(path))
+                .map_err(|e| CkpError::IoError(format!("Failed to read {}: {}", path, e)))?;
+            if &hex::encode(Sha256::digest(&content)) != expected {
+                return Err(CkpError::ValidationError(format!("Digest mismatch for {}", path)));
+            }
+        }
+        Ok(())
+    }
+
+    /// Validate and write the receipt into an instance directory
+    pub fn write(&self, inst_dir: &Path) -> Result<PathBuf> {
+        self.write_encoded(inst_dir, Encoding::Json)
+    }
+
+    /// Validate and write the receipt in the given encoding
+    ///
+    /// JSON receipts go to receipt.bin; binary ones to the encoding's own
+    /// file name (see `Encoding::receipt_file_name`).
+    pub fn write_encoded(&self, inst_dir: &Path, encoding: Encoding) -> Result<PathBuf> {
+        self.validate()?;
+        let receipt_path = inst_dir.join(encoding.receipt_file_name());
+        fs::write(&receipt_path, encoding.encode(self)?)
+            .map_err(|e| CkpError::IoError(format!("Failed to write receipt: {}", e)))?;
+        Ok(receipt_path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    /// Test: Receipts round-trip and reject schema violations
+    #[test]
+    fn test_receipt_validation() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let payload = br#"{"total": 3}"#;
+        fs::write(temp.path().join("payload.json"), payload).unwrap();
+
+        let receipt = Receipt::for_payload("tx-1", "Shop.Orders", &json!({"name": "order-1", "action": "create", "inputs": ["tx-0"]}))
+            .with_digest("payload.json", payload);
+        assert_eq!((receipt.name.as_str(), receipt.action.as_deref()), ("order-1", Some("create")));
+        assert_eq!(receipt.inputs, vec!["tx-0"]);
+
+        receipt.write(temp.path()).unwrap();
+        let written: Receipt = serde_json::from_slice(&fs::read(temp.path().join(RECEIPT_FILE)).unwrap()).unwrap();
+        assert_eq!(written, receipt);
+        written.verify_digests(temp.path()).unwrap();
+
+        fs::write(temp.path().join("payload.json"), "{}").unwrap();
+        assert!(matches!(written.verify_digests(temp.path()), Err(CkpError::ValidationError(_))));
+        let escaping = receipt.clone().with_digest("../escape", b"");
+        assert!(matches!(escaping.verify_digests(temp.path()), Err(CkpError::ValidationError(_))));
+
+        let invalid = [
+            Receipt { schema_version: RECEIPT_SCHEMA_VERSION + 1, ..receipt.clone() },
+            Receipt { kernel: " ".to_string(), ..receipt.clone() },
+            receipt.clone().with_output(""),
+            receipt.clone().caused_by(" ", None),
+            receipt.clone().with_digest("../escape", b""),
+            Receipt { digests: BTreeMap::from([("payload.json".to_string(), "XYZ".to_string())]), ..receipt.clone() },
+        ];
+        for receipt in invalid {
+            assert!(matches!(receipt.validate(), Err(CkpError::ValidationError(_))));
+        }
+    }
+}
@@ -3,13 +3,24 @@
 // Provides generic listing and querying of Concept Kernel Instances (CKIs)
 // from any kernel's storage directory. Works by reading receipt.bin files
 // and extracting envelope fields (id, name, timestamp, kernel).
-
+//
+// Listing, paging, counting and queries go through a sidecar index (see
+// storage/index.rs), rebuilt from a full scan whenever it no longer matches
+// the storage directory.
+
+use super::index::{self, IndexLine, IndexUpdate, InstanceIndex};
+use super::page::{InstancePage, InstancePageRequest, PageKey, DEFAULT_INSTANCE_PAGE_SIZE};
+use super::query::InstanceFilter;
+use super::receipt::{Receipt, RECEIPT_FILE};
+use super::shard;
+use super::walk;
+use crate::drivers::{read_encoded, run_blocking, Encoding, MappedFile};
 use crate::errors::CkpError;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
-use std::fs;
-use std::path::PathBuf;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
 
 /// Summary of a stored instance (envelope fields only)
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -39,16 +50,54 @@ pub struct InstanceDetail {
     pub action: Option<String>,
     /// Processing success status
     pub success: Option<bool>,
+    /// Instances this one was derived from
+    #[serde(default)]
+    pub inputs: Vec<String>,
+    /// Instances this one produced
+    #[serde(default)]
+    pub outputs: Vec<String>,
+    /// SHA-256 digests of the instance's files, by relative path
+    #[serde(default)]
+    pub digests: BTreeMap<String, String>,
     /// Full data payload (kernel-specific)
     pub data: Value,
 }
 
+/// Just enough of a JSON receipt to tell typed receipts from legacy ones
+#[derive(Deserialize)]
+struct SchemaProbe {
+    #[serde(rename = "schemaVersion")]
+    schema_version: Option<serde::de::IgnoredAny>,
+}
+
+/// Result of a full scan of a storage directory
+struct StorageScan {
+    /// Readable entries, sorted by name
+    entries: Vec<IndexLine>,
+
+    /// Instance directories, including unreadable ones
+    total: usize,
+
+    /// Size of the files in the instance directories
+    bytes: u64,
+
+    /// Storage directory mtime taken before scanning
+    storage_modified: DateTime<Utc>,
+}
+
+impl StorageScan {
+    fn unreadable(&self) -> usize {
+        self.total - self.entries.len()
+    }
+}
+
 /// Scanner for kernel instance storage
+#[derive(Debug, Clone)]
 pub struct InstanceScanner {
     /// Path to kernel root (e.g., /concepts/System.Oidc.User)
-    kernel_root: PathBuf,
+    pub(super) kernel_root: PathBuf,
     /// Kernel name
-    kernel_name: String,
+    pub(super) kernel_name: String,
 }
 
 impl InstanceScanner {
@@ -70,22 +119,15 @@ impl InstanceScanner {
     pub fn list_instances(&self, limit: usize) -> Result<Vec<InstanceSummary>, CkpError> {
         let storage_path = self.find_storage_dir()?;
 
-        let mut instances = Vec::new();
-
-        // Read all *.inst directories
-        if let Ok(entries) = fs::read_dir(&storage_path) {
-            for entry in entries.flatten() {
-                let path = entry.path();
-                if path.is_dir() && path.extension().and_then(|s| s.to_str()) == Some("inst") {
-                    if let Ok(summary) = self.read_instance_summary(&path) {
-                        instances.push(summary);
-                    }
-                }
+        let index = InstanceIndex::new(&storage_path);
+        if index.is_fresh() {
+            if let Ok(instances) = index.list(limit) {
+                return Ok(instances);
             }
         }
 
-        // Sort by name (alphabetically)
-        instances.sort_by(|a, b| a.name.to_lowercase().cmp(&b.name.to_lowercase()));
+        let scan = self.reindex(&storage_path)?;
+        let mut instances: Vec<InstanceSummary> = scan.entries.into_iter().filter_map(|e| e.summary).collect();
 
         // Apply limit
         if limit > 0 && instances.len() > limit {
@@ -95,6 +137,46 @@ impl InstanceScanner {
         Ok(instances)
     }
 
+    /// List one page of instances
+    ///
+    /// Pages continue from the request's cursor, so instances minted while
+    /// paging neither shift nor repeat earlier results.
+    ///
+    /// # Returns
+    /// Up to `limit` instances, with a cursor if more follow
+    pub fn list_page(&self, request: &InstancePageRequest) -> Result<InstancePage, CkpError> {
+        let storage_path = self.find_storage_dir()?;
+        let after = request.cursor.as_deref().map(|cursor| PageKey::decode(cursor, request)).transpose()?;
+        let limit = request.limit.unwrap_or(DEFAULT_INSTANCE_PAGE_SIZE).max(1);
+
+        let mut keyed: Vec<(PageKey, InstanceSummary)> = self.index_entries(&storage_path)?
+            .into_iter()
+            .filter_map(|entry| entry.summary.map(|summary| (PageKey::new(request, &entry.dir, &summary), summary)))
+            .filter(|(key, _)| {
+                after.as_ref().is_none_or(|after| {
+                    let position = key.cmp_position(after);
+                    if request.descending { position.is_lt() } else { position.is_gt() }
+                })
+            })
+            .collect();
+        keyed.sort_by(|(a, _), (b, _)| {
+            let position = a.cmp_position(b);
+            if request.descending { position.reverse() } else { position }
+        });
+
+        let has_more = keyed.len() > limit;
+        keyed.truncate(limit);
+        let next_cursor = match keyed.last() {
+            Some((key, _)) if has_more => Some(key.encode()?),
+            _ => None,
+        };
+
+        Ok(InstancePage {
+            instances: keyed.into_iter().map(|(_, summary)| summary).collect(),
+            next_cursor,
+        })
+    }
+
     /// Get detailed view of a specific instance by name
     ///
     /// # Arguments
@@ -107,14 +189,11 @@ impl InstanceScanner {
         let name_lower = name.to_lowercase();
 
         // Find matching instance
-        if let Ok(entries) = fs::read_dir(&storage_path) {
-            for entry in entries.flatten() {
-                let path = entry.path();
-                if path.is_dir() && path.extension().and_then(|s| s.to_str()) == Some("inst") {
-                    if let Ok(detail) = self.read_instance_detail(&path) {
-                        if detail.name.to_lowercase() == name_lower {
-                            return Ok(detail);
-                        }
+        for path in shard::list_entries(&storage_path) {
+            if path.is_dir() && path.extension().and_then(|s| s.to_str()) == Some("inst") {
+                if let Ok(detail) = self.read_instance_detail(&path) {
+                    if detail.name.to_lowercase() == name_lower {
+                        return Ok(detail);
                     }
                 }
             }
@@ -126,28 +205,203 @@ impl InstanceScanner {
         )))
     }
 
+    /// Read every instance in storage (envelope + data payload)
+    ///
+    /// Unreadable instances are skipped, as in `list_instances`.
+    ///
+    /// # Returns
+    /// Vector of instance details, sorted by timestamp ascending
+    pub fn list_instance_details(&self) -> Result<Vec<InstanceDetail>, CkpError> {
+        let storage_path = self.find_storage_dir()?;
+        Ok(self.read_details(&[storage_path]))
+    }
+
+    /// Read every instance in storage and in `archive/` beside it
+    ///
+    /// Unreadable instances are skipped, as in `list_instances`.
+    ///
+    /// # Returns
+    /// Vector of instance details, sorted by timestamp ascending
+    pub fn list_instance_details_with_archive(&self) -> Result<Vec<InstanceDetail>, CkpError> {
+        let storage_path = self.find_storage_dir()?;
+        let archive_path = storage_path.with_file_name("archive");
+        Ok(self.read_details(&[storage_path, archive_path]))
+    }
+
+    /// Instances that existed at a point in time
+    ///
+    /// Instances archived since (`archive/` beside storage) are read as
+    /// well, and those minted at or before `at` are kept. Unreadable
+    /// instances are skipped, as in `list_instances`.
+    ///
+    /// # Returns
+    /// Vector of instance details, sorted by timestamp ascending
+    pub fn as_of(&self, at: DateTime<Utc>) -> Result<Vec<InstanceDetail>, CkpError> {
+        let mut details = self.list_instance_details_with_archive()?;
+        details.retain(|detail| detail.timestamp <= at);
+        Ok(details)
+    }
+
+    /// Query instances by envelope and payload fields
+    ///
+    /// Timestamp, action and success filters are evaluated on the index;
+    /// only instances passing them are read for payload predicates.
+    ///
+    /// # Returns
+    /// Matching instance details, sorted by timestamp ascending
+    pub fn query(&self, filter: &InstanceFilter) -> Result<Vec<InstanceDetail>, CkpError> {
+        let storage_path = self.find_storage_dir()?;
+
+        let mut candidates: Vec<(String, InstanceSummary)> = self.index_entries(&storage_path)?
+            .into_iter()
+            .filter(|entry| filter.matches_index(entry))
+            .filter_map(|entry| entry.summary.map(|summary| (entry.dir, summary)))
+            .collect();
+        candidates.sort_by(|(_, a), (_, b)| a.timestamp.cmp(&b.timestamp).then_with(|| a.id.cmp(&b.id)));
+
+        let mut details = Vec::new();
+        for (dir, _) in candidates {
+            if filter.limit.is_some_and(|limit| details.len() >= limit) {
+                break;
+            }
+            if let Ok(detail) = self.read_instance_detail(&shard::locate(&storage_path, &dir)) {
+                if filter.matches(&detail) {
+                    details.push(detail);
+                }
+            }
+        }
+
+        Ok(details)
+    }
+
     /// Count total instances in storage
     pub fn count_instances(&self) -> Result<usize, CkpError> {
         let storage_path = self.find_storage_dir()?;
 
-        let mut count = 0;
-        if let Ok(entries) = fs::read_dir(&storage_path) {
-            for entry in entries.flatten() {
-                let path = entry.path();
-                if path.is_dir() && path.extension().and_then(|s| s.to_str()) == Some("inst") {
-                    count += 1;
-                }
+        let index = InstanceIndex::new(&storage_path);
+        if let Some(total) = index.total().filter(|_| index.is_fresh()) {
+            return Ok(total);
+        }
+
+        Ok(self.reindex(&storage_path)?.total)
+    }
+
+    /// Size of the files in the instance directories in storage
+    ///
+    /// Read from the index, which mints and archives keep current; storage
+    /// is only walked when the index is stale.
+    pub fn storage_bytes(&self) -> Result<u64, CkpError> {
+        let storage_path = self.find_storage_dir()?;
+
+        let index = InstanceIndex::new(&storage_path);
+        if let Some(bytes) = index.bytes().filter(|_| index.is_fresh()) {
+            return Ok(bytes);
+        }
+
+        Ok(self.reindex(&storage_path)?.bytes)
+    }
+
+    /// Rebuild the instance index from a full scan of storage
+    ///
+    /// # Returns
+    /// Number of instances in storage
+    pub fn rebuild_index(&self) -> Result<usize, CkpError> {
+        let storage_path = self.find_storage_dir()?;
+        let scan = self.scan(&storage_path)?;
+        InstanceIndex::new(&storage_path).write(&scan.entries, scan.unreadable(), scan.bytes, scan.storage_modified)?;
+        Ok(scan.total)
+    }
+
+    /// Start keeping the index current across a change to one instance
+    ///
+    /// Call before creating or removing `inst_dir`, then `commit` once the
+    /// instance is fully written (or gone).
+    pub fn begin_index_update(&self, inst_dir: &Path) -> IndexUpdate<'_> {
+        IndexUpdate::begin(self, inst_dir)
+    }
+
+    // ==================== Async Access ====================
+    // Scans read one receipt per instance; async callers use these so that
+    // I/O runs on the blocking pool instead of stalling the executor.
+
+    /// `list_instances` without blocking the async executor
+    pub async fn list_instances_async(&self, limit: usize) -> Result<Vec<InstanceSummary>, CkpError> {
+        let scanner = self.clone();
+        run_blocking(move || scanner.list_instances(limit)).await
+    }
+
+    /// `list_page` without blocking the async executor
+    pub async fn list_page_async(&self, request: InstancePageRequest) -> Result<InstancePage, CkpError> {
+        let scanner = self.clone();
+        run_blocking(move || scanner.list_page(&request)).await
+    }
+
+    /// `describe_instance` without blocking the async executor
+    pub async fn describe_instance_async(&self, name: &str) -> Result<InstanceDetail, CkpError> {
+        let scanner = self.clone();
+        let name = name.to_string();
+        run_blocking(move || scanner.describe_instance(&name)).await
+    }
+
+    /// `query` without blocking the async executor
+    pub async fn query_async(&self, filter: InstanceFilter) -> Result<Vec<InstanceDetail>, CkpError> {
+        let scanner = self.clone();
+        run_blocking(move || scanner.query(&filter)).await
+    }
+
+    /// All readable index entries, rebuilding the index if it is stale
+    pub(super) fn index_entries(&self, storage_path: &Path) -> Result<Vec<IndexLine>, CkpError> {
+        let index = InstanceIndex::new(storage_path);
+        if index.is_fresh() {
+            if let Ok(entries) = index.entries(0) {
+                return Ok(entries);
             }
         }
 
-        Ok(count)
+        Ok(self.reindex(storage_path)?.entries)
+    }
+
+    /// Scan storage and save the result as the index
+    ///
+    /// An index that cannot be written only costs the next call another scan.
+    fn reindex(&self, storage_path: &Path) -> Result<StorageScan, CkpError> {
+        let scan = self.scan(storage_path)?;
+
+        let index = InstanceIndex::new(storage_path);
+        if let Err(e) = index.write(&scan.entries, scan.unreadable(), scan.bytes, scan.storage_modified) {
+            tracing::warn!(kernel = %self.kernel_name, error = %e, "instance index not saved");
+        }
+
+        Ok(scan)
+    }
+
+    /// Read every instance summary in storage
+    fn scan(&self, storage_path: &Path) -> Result<StorageScan, CkpError> {
+        let storage_modified = InstanceIndex::new(storage_path).storage_modified()?;
+
+        // Read all *.inst directories, flat or sharded, in parallel
+        let inst_dirs: Vec<PathBuf> = shard::list_entries(storage_path)
+            .into_iter()
+            .filter(|path| path.is_dir() && path.extension().and_then(|s| s.to_str()) == Some("inst"))
+            .collect();
+        let total = inst_dirs.len();
+        let bytes = inst_dirs.iter().map(|path| index::dir_size(path)).sum();
+        let mut entries: Vec<IndexLine> = walk::par_map(inst_dirs, |path| self.read_index_line(&path).ok())
+            .into_iter()
+            .flatten()
+            .collect();
+
+        // Sort by name (alphabetically)
+        entries.sort_by(|a, b| a.key().cmp(&b.key()));
+
+        Ok(StorageScan { entries, total, bytes, storage_modified })
     }
 
     /// Find the storage directory for this kernel
     ///
     /// Handles both old structure (/concepts/Kernel/storage/) and
     /// new structure (/concepts/Kernel/Kernel/storage/)
-    fn find_storage_dir(&self) -> Result<PathBuf, CkpError> {
+    pub(super) fn find_storage_dir(&self) -> Result<PathBuf, CkpError> {
         // Try new structure first: /concepts/Kernel/Kernel/storage/
         let new_path = self
             .kernel_root
@@ -170,46 +424,58 @@ impl InstanceScanner {
         )))
     }
 
-    /// Read instance summary from receipt.bin (envelope only)
-    fn read_instance_summary(&self, inst_dir: &PathBuf) -> Result<InstanceSummary, CkpError> {
-        let receipt_path = inst_dir.join("receipt.bin");
-        let receipt_str = fs::read_to_string(&receipt_path).map_err(|e| {
-            CkpError::IoError(format!("Failed to read receipt.bin: {}", e))
-        })?;
-
-        let receipt: Value = serde_json::from_str(&receipt_str).map_err(|e| {
-            CkpError::ParseError(format!("Failed to parse receipt.bin: {}", e))
-        })?;
-
-        // Extract envelope fields
-        let id = self.extract_id(&receipt, inst_dir)?;
-        let name = self.extract_name(&receipt, inst_dir)?;
-        let kernel = receipt
-            .get("kernel")
-            .and_then(|v| v.as_str())
-            .unwrap_or(&self.kernel_name)
-            .to_string();
-        let timestamp = self.extract_timestamp(&receipt)?;
+    /// Read an index entry from receipt.bin (envelope only)
+    pub(super) fn read_index_line(&self, inst_dir: &Path) -> Result<IndexLine, CkpError> {
+        let receipt = self.read_receipt(inst_dir)?;
+        let dir = inst_dir.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
 
-        Ok(InstanceSummary {
-            id,
-            name,
-            kernel,
-            timestamp,
+        Ok(IndexLine {
+            action: receipt.action.clone(),
+            success: receipt.success,
+            ..IndexLine::entry(dir, Self::summary_from_receipt(receipt))
         })
     }
 
-    /// Read full instance detail from receipt.bin (envelope + data)
-    fn read_instance_detail(&self, inst_dir: &PathBuf) -> Result<InstanceDetail, CkpError> {
-        let receipt_path = inst_dir.join("receipt.bin");
-        let receipt_str = fs::read_to_string(&receipt_path).map_err(|e| {
+    /// Read instance summary from receipt.bin (envelope only)
+    pub(super) fn read_summary(&self, inst_dir: &Path) -> Result<InstanceSummary, CkpError> {
+        Ok(Self::summary_from_receipt(self.read_receipt(inst_dir)?))
+    }
+
+    /// Read receipt.bin into the typed receipt
+    ///
+    /// Receipts with a `schemaVersion` must conform to the schema; older
+    /// receipts are converted field by field as schema version 0.
+    pub fn read_receipt(&self, inst_dir: &Path) -> Result<Receipt, CkpError> {
+        // Binary receipts postdate the schema, so they decode directly
+        if let Some((encoding, receipt_path)) = Encoding::find_receipt(inst_dir) {
+            if encoding != Encoding::Json {
+                let receipt: Receipt = read_encoded(&receipt_path, encoding)?;
+                receipt.validate()?;
+                return Ok(receipt);
+            }
+        }
+
+        let receipt_path = inst_dir.join(RECEIPT_FILE);
+        let receipt_file = MappedFile::open(&receipt_path).map_err(|e| {
             CkpError::IoError(format!("Failed to read receipt.bin: {}", e))
         })?;
+        let parse_error = |e: serde_json::Error| CkpError::ParseError(format!("Failed to parse receipt.bin: {}", e));
+
+        // Typed receipts decode straight from the file; only legacy ones
+        // go through an intermediate Value
+        let probe: SchemaProbe = serde_json::from_slice(&receipt_file).map_err(parse_error)?;
+        if probe.schema_version.is_some() {
+            let receipt: Receipt = serde_json::from_slice(&receipt_file).map_err(parse_error)?;
+            receipt.validate()?;
+            return Ok(receipt);
+        }
 
-        let receipt: Value = serde_json::from_str(&receipt_str).map_err(|e| {
-            CkpError::ParseError(format!("Failed to parse receipt.bin: {}", e))
-        })?;
+        let receipt: Value = serde_json::from_slice(&receipt_file).map_err(parse_error)?;
+        self.legacy_receipt(receipt, inst_dir)
+    }
 
+    /// Convert a receipt written before the schema existed
+    fn legacy_receipt(&self, mut receipt: Value, inst_dir: &Path) -> Result<Receipt, CkpError> {
         // Extract envelope fields
         let id = self.extract_id(&receipt, inst_dir)?;
         let name = self.extract_name(&receipt, inst_dir)?;
@@ -221,39 +487,86 @@ impl InstanceScanner {
         let timestamp = self.extract_timestamp(&receipt)?;
         let action = receipt.get("action").and_then(|v| v.as_str()).map(|s| s.to_string());
         let success = receipt.get("success").and_then(|v| v.as_bool());
+        let correlation_id = receipt.get("correlationId").and_then(|v| v.as_str()).map(|s| s.to_string());
+        let causation_id = receipt.get("causationId").and_then(|v| v.as_str()).map(|s| s.to_string());
 
         // Extract data payload (kernel-specific fields)
-        let data = if let Some(data_obj) = receipt.get("data") {
-            data_obj.clone()
+        let data = if let Some(data_obj) = receipt.get_mut("data") {
+            data_obj.take()
         } else {
             // Backward compatibility: if no data field, use entire receipt minus envelope
-            let mut data_obj = receipt.clone();
-            if let Some(obj) = data_obj.as_object_mut() {
+            if let Some(obj) = receipt.as_object_mut() {
                 obj.remove("id");
                 obj.remove("name");
                 obj.remove("kernel");
                 obj.remove("timestamp");
                 obj.remove("action");
                 obj.remove("success");
+                obj.remove("correlationId");
+                obj.remove("causationId");
                 obj.remove("txId");
                 obj.remove("processed");
             }
-            data_obj
+            receipt
         };
 
-        Ok(InstanceDetail {
-            id,
-            name,
-            kernel,
-            timestamp,
+        Ok(Receipt {
+            schema_version: 0,
             action,
             success,
-            data,
+            timestamp,
+            correlation_id,
+            causation_id,
+            ..Receipt::new(id, name, kernel, data)
+        })
+    }
+
+    /// Envelope fields of a receipt
+    fn summary_from_receipt(receipt: Receipt) -> InstanceSummary {
+        InstanceSummary {
+            id: receipt.id,
+            name: receipt.name,
+            kernel: receipt.kernel,
+            timestamp: receipt.timestamp,
+        }
+    }
+
+    /// Read full instance detail from receipt.bin (envelope + data)
+    /// Every readable instance in the given directories, sorted by timestamp
+    fn read_details(&self, dirs: &[PathBuf]) -> Vec<InstanceDetail> {
+        let inst_dirs: Vec<PathBuf> = dirs
+            .iter()
+            .flat_map(|dir| shard::list_entries(dir))
+            .filter(|path| path.is_dir() && path.extension().and_then(|s| s.to_str()) == Some("inst"))
+            .collect();
+        let mut details: Vec<InstanceDetail> = walk::par_map(inst_dirs, |path| self.read_instance_detail(&path).ok())
+            .into_iter()
+            .flatten()
+            .collect();
+
+        details.sort_by(|a, b| a.timestamp.cmp(&b.timestamp).then_with(|| a.id.cmp(&b.id)));
+        details
+    }
+
+    fn read_instance_detail(&self, inst_dir: &Path) -> Result<InstanceDetail, CkpError> {
+        let receipt = self.read_receipt(inst_dir)?;
+
+        Ok(InstanceDetail {
+            id: receipt.id,
+            name: receipt.name,
+            kernel: receipt.kernel,
+            timestamp: receipt.timestamp,
+            action: receipt.action,
+            success: receipt.success,
+            inputs: receipt.inputs,
+            outputs: receipt.outputs,
+            digests: receipt.digests,
+            data: receipt.data,
         })
     }
 
     /// Extract ID from receipt (tries multiple fields for backward compatibility)
-    fn extract_id(&self, receipt: &Value, inst_dir: &PathBuf) -> Result<String, CkpError> {
+    fn extract_id(&self, receipt: &Value, inst_dir: &Path) -> Result<String, CkpError> {
         // Try id field first
         if let Some(id) = receipt.get("id").and_then(|v| v.as_str()) {
             return Ok(id.to_string());
@@ -275,7 +588,7 @@ impl InstanceScanner {
     }
 
     /// Extract name from receipt (tries multiple sources for backward compatibility)
-    fn extract_name(&self, receipt: &Value, inst_dir: &PathBuf) -> Result<String, CkpError> {
+    fn extract_name(&self, receipt: &Value, inst_dir: &Path) -> Result<String, CkpError> {
         // Try name field first (envelope)
         if let Some(name) = receipt.get("name").and_then(|v| v.as_str()) {
             return Ok(name.to_string());
@@ -412,6 +725,34 @@ mod tests {
         assert_eq!(instances[0].kernel, "Test.Single");
     }
 
+    /// Test: Async variants return the same results off the executor
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_async_access_matches_sync() {
+        let temp = TempDir::new().unwrap();
+        let kernel_root = temp.path().join("Test.Async");
+        let storage_dir = kernel_root.join("storage");
+        fs::create_dir_all(&storage_dir).unwrap();
+        for i in 0..3 {
+            let data = serde_json::json!({
+                "id": format!("tx-{}", i),
+                "name": format!("async-{}", i),
+                "kernel": "Test.Async",
+                "timestamp": format!("2025-11-29T10:00:0{}Z", i)
+            });
+            create_test_instance(&storage_dir, &format!("tx-{}", i), data);
+        }
+
+        let scanner = InstanceScanner::new(kernel_root, "Test.Async".to_string());
+        let sync_ids: Vec<String> = scanner.list_instances(0).unwrap().into_iter().map(|i| i.id).collect();
+        let async_ids: Vec<String> = scanner.list_instances_async(0).await.unwrap().into_iter().map(|i| i.id).collect();
+        assert_eq!(sync_ids, async_ids);
+
+        let detail = scanner.describe_instance_async("async-1").await.unwrap();
+        assert_eq!(detail.id, "tx-1");
+        assert!(scanner.describe_instance_async("missing").await.is_err());
+        assert_eq!(scanner.query_async(InstanceFilter::default()).await.unwrap().len(), 3);
+    }
+
     /// Test: Scan with multiple instances
     #[test]
     fn test_list_instances_multiple() {
@@ -564,6 +905,32 @@ mod tests {
         assert!(detail.data.get("field1").is_some());
     }
 
+    /// Test: List instance details in timestamp order
+    #[test]
+    fn test_list_instance_details() {
+        let temp = TempDir::new().unwrap();
+        let kernel_root = temp.path().join("Test.Details");
+        let storage_dir = kernel_root.join("storage");
+        fs::create_dir_all(&storage_dir).unwrap();
+
+        for (id, ts) in [("tx-b", "2025-11-29T11:00:00Z"), ("tx-a", "2025-11-29T10:00:00Z")] {
+            let data = serde_json::json!({
+                "id": id,
+                "name": id,
+                "timestamp": ts,
+                "data": {"user_id": "alice"}
+            });
+            create_test_instance(&storage_dir, id, data);
+        }
+
+        let scanner = InstanceScanner::new(kernel_root, "Test.Details".to_string());
+        let details = scanner.list_instance_details().unwrap();
+
+        assert_eq!(details.len(), 2);
+        assert_eq!(details[0].id, "tx-a");
+        assert_eq!(details[1].data["user_id"], "alice");
+    }
+
     /// Test: Describe instance not found
     #[test]
     fn test_describe_instance_not_found() {
@@ -633,4 +1000,171 @@ mod tests {
         assert_eq!(instances[2].name.to_lowercase(), "banana");
         assert_eq!(instances[3].name.to_lowercase(), "zebra");
     }
+
+    /// Test: Index is written on first listing and follows later changes
+    #[test]
+    fn test_index_rebuilds_when_storage_changes() {
+        let temp = TempDir::new().unwrap();
+        let kernel_root = temp.path().join("Test.Index");
+        let storage_dir = kernel_root.join("storage");
+        fs::create_dir_all(&storage_dir).unwrap();
+
+        for (id, name) in [("tx-1", "bravo"), ("tx-2", "alpha")] {
+            let data = serde_json::json!({"id": id, "name": name, "timestamp": "2025-11-29T10:00:00Z"});
+            create_test_instance(&storage_dir, id, data);
+        }
+        fs::create_dir_all(storage_dir.join("tx-broken.inst")).unwrap();
+
+        let scanner = InstanceScanner::new(kernel_root.clone(), "Test.Index".to_string());
+        assert_eq!(scanner.count_instances().unwrap(), 3);
+        assert!(kernel_root.join(crate::storage::INDEX_FILE).exists());
+        assert!(kernel_root.join(crate::storage::INDEX_META_FILE).exists());
+
+        // Changes made without going through the index are still picked up
+        let data = serde_json::json!({"id": "tx-3", "name": "Charlie", "timestamp": "2025-11-29T10:00:00Z"});
+        create_test_instance(&storage_dir, "tx-3", data);
+        assert_eq!(scanner.count_instances().unwrap(), 4);
+        fs::remove_dir_all(storage_dir.join("tx-2.inst")).unwrap();
+
+        let names: Vec<String> = scanner.list_instances(0).unwrap().into_iter().map(|i| i.name).collect();
+        assert_eq!(names, vec!["bravo", "Charlie"]);
+        assert_eq!(scanner.rebuild_index().unwrap(), 3);
+    }
+
+    /// Test: Index updates record mints and archives without a rescan
+    #[test]
+    fn test_index_update_appends_to_index() {
+        let temp = TempDir::new().unwrap();
+        let kernel_root = temp.path().join("Test.Update");
+        let storage_dir = kernel_root.join("storage");
+        fs::create_dir_all(&storage_dir).unwrap();
+
+        let scanner = InstanceScanner::new(kernel_root.clone(), "Test.Update".to_string());
+        assert_eq!(scanner.count_instances().unwrap(), 0);
+
+        for (id, name) in [("tx-1", "mike"), ("tx-2", "delta"), ("tx-3", "zulu")] {
+            let inst_dir = storage_dir.join(format!("{}.inst", id));
+            let update = scanner.begin_index_update(&inst_dir);
+            let data = serde_json::json!({"id": id, "name": name, "timestamp": "2025-11-29T10:00:00Z"});
+            create_test_instance(&storage_dir, id, data);
+            update.commit().unwrap();
+        }
+
+        let inst_dir = storage_dir.join("tx-1.inst");
+        let update = scanner.begin_index_update(&inst_dir);
+        fs::remove_dir_all(&inst_dir).unwrap();
+        update.commit().unwrap();
+
+        // Every change landed in the unsorted tail of the index
+        let index = fs::read_to_string(kernel_root.join(crate::storage::INDEX_FILE)).unwrap();
+        assert_eq!(index.lines().count(), 4);
+        assert!(index.lines().last().unwrap().contains(r#""removed":true"#));
+
+        let names: Vec<String> = scanner.list_instances(0).unwrap().into_iter().map(|i| i.name).collect();
+        assert_eq!(names, vec!["delta", "zulu"]);
+        assert_eq!(scanner.list_instances(1).unwrap()[0].name, "delta");
+        assert_eq!(scanner.count_instances().unwrap(), 2);
+    }
+
+    /// Test: Query filters on envelope fields and payload predicates
+    #[test]
+    fn test_query_instances() {
+        use crate::storage::{FieldComparison, InstanceFilter};
+
+        let temp = TempDir::new().unwrap();
+        let kernel_root = temp.path().join("Test.Query");
+        let storage_dir = kernel_root.join("storage");
+        fs::create_dir_all(&storage_dir).unwrap();
+
+        let receipts = [
+            ("tx-1", "bake", true, "2025-11-01T10:00:00Z", 2),
+            ("tx-2", "bake", false, "2025-11-02T10:00:00Z", 5),
+            ("tx-3", "mix", false, "2025-11-03T10:00:00Z", 1),
+            ("tx-4", "bake", true, "2025-11-04T10:00:00Z", 8),
+        ];
+        for (id, action, success, timestamp, cakes) in receipts {
+            let data = serde_json::json!({
+                "id": id,
+                "name": id,
+                "action": action,
+                "success": success,
+                "timestamp": timestamp,
+                "data": {"cakes": cakes}
+            });
+            create_test_instance(&storage_dir, id, data);
+        }
+
+        let scanner = InstanceScanner::new(kernel_root, "Test.Query".to_string());
+        let ids = |filter: InstanceFilter| -> Vec<String> {
+            scanner.query(&filter).unwrap().into_iter().map(|d| d.id).collect()
+        };
+
+        assert_eq!(ids(InstanceFilter { success: Some(false), ..Default::default() }), vec!["tx-2", "tx-3"]);
+        assert_eq!(
+            ids(InstanceFilter {
+                action: Some("bake".to_string()),
+                since: Some("2025-11-02T00:00:00Z".parse().unwrap()),
+                until: Some("2025-11-04T10:00:00Z".parse().unwrap()),
+                ..Default::default()
+            }),
+            vec!["tx-2"]
+        );
+        assert_eq!(ids(InstanceFilter::default().with_field("cakes", FieldComparison::Gte, 5)), vec!["tx-2", "tx-4"]);
+        assert_eq!(ids(InstanceFilter { limit: Some(1), ..Default::default() }), vec!["tx-1"]);
+    }
+
+    /// Test: Cursor pages are stable across mints between requests
+    #[test]
+    fn test_list_page_with_cursor() {
+        use crate::storage::{InstanceOrder, InstancePageRequest};
+
+        let temp = TempDir::new().unwrap();
+        let kernel_root = temp.path().join("Test.Page");
+        let storage_dir = kernel_root.join("storage");
+        fs::create_dir_all(&storage_dir).unwrap();
+
+        let mint = |id: &str, day: u32| {
+            let data = serde_json::json!({
+                "id": id,
+                "name": id,
+                "timestamp": format!("2025-11-{:02}T10:00:00Z", day)
+            });
+            create_test_instance(&storage_dir, id, data);
+        };
+        for (id, day) in [("tx-a", 3), ("tx-b", 1), ("tx-c", 4), ("tx-d", 2), ("tx-e", 5)] {
+            mint(id, day);
+        }
+
+        let scanner = InstanceScanner::new(kernel_root, "Test.Page".to_string());
+        let mut request = InstancePageRequest {
+            descending: true,
+            limit: Some(2),
+            ..Default::default()
+        };
+        let ids = |page: &InstancePage| page.instances.iter().map(|i| i.id.clone()).collect::<Vec<_>>();
+
+        let first = scanner.list_page(&request).unwrap();
+        assert_eq!(ids(&first), vec!["tx-e", "tx-c"]);
+
+        // A newer instance minted mid-paging does not shift later pages
+        mint("tx-f", 6);
+        request.cursor = first.next_cursor;
+        let second = scanner.list_page(&request).unwrap();
+        assert_eq!(ids(&second), vec!["tx-a", "tx-d"]);
+
+        request.cursor = second.next_cursor;
+        let last = scanner.list_page(&request).unwrap();
+        assert_eq!(ids(&last), vec!["tx-b"]);
+        assert!(last.next_cursor.is_none());
+
+        let by_id = InstancePageRequest { order: InstanceOrder::Id, limit: Some(4), ..Default::default() };
+        let page = scanner.list_page(&by_id).unwrap();
+        assert_eq!(ids(&page), vec!["tx-a", "tx-b", "tx-c", "tx-d"]);
+
+        // Cursors are tied to the order they were issued for
+        let mismatched = InstancePageRequest { cursor: page.next_cursor, ..Default::default() };
+        assert!(matches!(scanner.list_page(&mismatched), Err(CkpError::ValidationError(_))));
+        let garbage = InstancePageRequest { cursor: Some("zz".to_string()), ..Default::default() };
+        assert!(matches!(scanner.list_page(&garbage), Err(CkpError::ValidationError(_))));
+    }
 }
@@ -0,0 +1,207 @@
+// storage/search.rs - Cross-kernel instance search
+//
+// ProjectScanner runs an InstanceScanner over every kernel under each
+// project's concepts/ directory, in parallel on the shared scan pool
+// (storage/walk.rs), and tags results with the project and kernel that
+// hold them.
+
+use super::query::InstanceFilter;
+use super::scanner::{InstanceDetail, InstanceScanner, InstanceSummary};
+use super::walk;
+use crate::errors::{CkpError, Result};
+use crate::project::ProjectRegistry;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+
+/// Instance found by a project-level search
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectInstance<T = InstanceSummary> {
+    /// Project name
+    pub project: String,
+
+    /// Kernel whose storage holds the instance
+    pub kernel: String,
+
+    pub instance: T,
+}
+
+/// A kernel directory to scan
+#[derive(Debug, Clone)]
+struct KernelLocation {
+    project: String,
+    kernel: String,
+    path: PathBuf,
+}
+
+/// Instance search across the kernels of one or more projects
+#[derive(Debug, Clone, Default)]
+pub struct ProjectScanner {
+    /// (project name, project root)
+    projects: Vec<(String, PathBuf)>,
+}
+
+impl ProjectScanner {
+    /// Scanner over a single project, named after its root directory
+    pub fn new(project_root: PathBuf) -> Self {
+        let name = project_root.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+        Self::default().with_project(name, project_root)
+    }
+
+    /// Scanner over every project in a registry
+    pub fn from_registry(registry: &mut ProjectRegistry) -> Result<Self> {
+        Ok(registry.list()?
+            .into_iter()
+            .fold(Self::default(), |scanner, project| scanner.with_project(project.name, PathBuf::from(project.path))))
+    }
+
+    pub fn with_project(mut self, name: impl Into<String>, project_root: PathBuf) -> Self {
+        self.projects.push((name.into(), project_root));
+        self
+    }
+
+    /// Find instances by id, instance directory or name (case-insensitive)
+    ///
+    /// # Returns
+    /// Matches ordered by project, then kernel
+    pub fn find(&self, needle: &str) -> Result<Vec<ProjectInstance>> {
+        let needle_lower = needle.to_lowercase();
+        self.scan(|scanner| {
+            let storage_path = scanner.find_storage_dir()?;
+            Ok(scanner.index_entries(&storage_path)?
+                .into_iter()
+                .filter(|entry| {
+                    entry.dir.strip_suffix(".inst") == Some(needle)
+                        || entry.summary.as_ref().is_some_and(|s| s.id == needle || s.name.to_lowercase() == needle_lower)
+                })
+                .filter_map(|entry| entry.summary)
+                .collect())
+        })
+    }
+
+    /// List instances of every kernel
+    ///
+    /// # Arguments
+    /// * `limit` - Maximum instances per kernel (0 = unlimited)
+    pub fn list_instances(&self, limit: usize) -> Result<Vec<ProjectInstance>> {
+        self.scan(|scanner| scanner.list_instances(limit))
+    }
+
+    /// Query instances of every kernel
+    ///
+    /// `filter.limit` applies per kernel.
+    pub fn query(&self, filter: &InstanceFilter) -> Result<Vec<ProjectInstance<InstanceDetail>>> {
+        self.scan(|scanner| scanner.query(filter))
+    }
+
+    /// Kernel directories of all projects, sorted within each project
+    fn kernels(&self) -> Result<Vec<KernelLocation>> {
+        let mut kernels = Vec::new();
+
+        for (project, root) in &self.projects {
+            let concepts = root.join("concepts");
+            if !concepts.exists() {
+                continue;
+            }
+
+            let mut paths: Vec<PathBuf> = fs::read_dir(&concepts)
+                .map_err(|e| CkpError::IoError(format!("Failed to read concepts directory: {}", e)))?
+                .filter_map(|entry| entry.ok().map(|e| e.path()))
+                .filter(|path| path.is_dir())
+                .collect();
+            paths.sort();
+
+            for path in paths {
+                let kernel = path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+                if kernel.starts_with('.') {
+                    continue;
+                }
+                kernels.push(KernelLocation { project: project.clone(), kernel, path });
+            }
+        }
+
+        Ok(kernels)
+    }
+
+    /// Run a per-kernel scan on the shared scan pool
+    ///
+    /// Kernels without storage are skipped; any other error fails the scan.
+    fn scan<T, F>(&self, per_kernel: F) -> Result<Vec<ProjectInstance<T>>>
+    where
+        T: Send,
+        F: Fn(&InstanceScanner) -> Result<Vec<T>> + Sync,
+    {
+        let kernels = self.kernels()?;
+        let results = walk::par_map(kernels.iter().collect(), |location| {
+            per_kernel(&InstanceScanner::new(location.path.clone(), location.kernel.clone()))
+        });
+
+        let mut found = Vec::new();
+        for (location, result) in kernels.iter().zip(results) {
+            let instances = match result {
+                Ok(instances) => instances,
+                Err(CkpError::FileNotFound(_)) => continue,
+                Err(e) => return Err(e),
+            };
+
+            found.extend(instances.into_iter().map(|instance| ProjectInstance {
+                project: location.project.clone(),
+                kernel: location.kernel.clone(),
+                instance,
+            }));
+        }
+
+        Ok(found)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::project::ProjectInfo;
+    use std::path::Path;
+    use tempfile::TempDir;
+
+    fn mint(project: &Path, kernel: &str, id: &str, name: &str) {
+        let inst_dir = project.join("concepts").join(kernel).join("storage").join(format!("{}.inst", id));
+        fs::create_dir_all(&inst_dir).unwrap();
+        let receipt = serde_json::json!({"id": id, "name": name, "timestamp": "2025-11-29T10:00:00Z"});
+        fs::write(inst_dir.join("receipt.bin"), receipt.to_string()).unwrap();
+    }
+
+    #[test]
+    fn test_find_across_projects() {
+        let temp = TempDir::new().unwrap();
+        let shop = temp.path().join("shop");
+        let bakery = temp.path().join("bakery");
+        mint(&shop, "Shop.Orders", "tx-1", "order-1");
+        mint(&shop, "Shop.Orders", "tx-2", "order-2");
+        mint(&bakery, "Bakery.Cakes", "tx-3", "Sponge");
+        mint(&bakery, "Bakery.Ovens", "tx-42", "oven");
+        fs::create_dir_all(bakery.join("concepts/Bakery.Empty")).unwrap();
+
+        let mut registry = ProjectRegistry::from_dir(temp.path().join("registry")).unwrap();
+        for (name, path) in [("shop", &shop), ("bakery", &bakery)] {
+            registry.register(ProjectInfo {
+                name: name.to_string(),
+                id: name.to_string(),
+                path: path.to_string_lossy().to_string(),
+                version: "v1.3.14".to_string(),
+                preferred_slot: None,
+            }).unwrap();
+        }
+        let scanner = ProjectScanner::from_registry(&mut registry).unwrap();
+
+        let found = scanner.find("tx-42").unwrap();
+        assert_eq!(found.len(), 1);
+        assert_eq!((found[0].project.as_str(), found[0].kernel.as_str()), ("bakery", "Bakery.Ovens"));
+        assert_eq!(scanner.find("sponge").unwrap()[0].instance.id, "tx-3");
+        assert!(scanner.find("tx-404").unwrap().is_empty());
+
+        assert_eq!(scanner.list_instances(0).unwrap().len(), 4);
+        let shop_only = ProjectScanner::new(shop);
+        let kernels: Vec<String> = shop_only.list_instances(1).unwrap().into_iter().map(|i| i.kernel).collect();
+        assert_eq!(kernels, vec!["Shop.Orders"]);
+    }
+}
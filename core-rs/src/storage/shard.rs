@@ -0,0 +1,311 @@
+// storage/shard.rs - Two-level directory sharding
+//
+// Thousands of entries in one directory slow most filesystems down. Kernels
+// that advertise the `sharding` capability in their ontology spread storage
+// instances and inbox jobs over subdirectories named after two characters of
+// the transaction ID's random part:
+//
+//   storage/ab/1764410400000-ab12cd34.inst
+//   queue/inbox/ab/1764410400000-ab12cd34.job
+//
+// Writers pick the layout from the capability; readers resolve both, so flat
+// and sharded entries can coexist while a kernel is migrated.
+
+use crate::errors::{CkpError, Result};
+use crate::ontology::OntologyReader;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+/// Ontology capability enabling sharded storage and inbox directories
+pub const SHARDING_CAPABILITY: &str = "sharding";
+
+/// Whether a kernel advertises the sharding capability
+///
+/// # Arguments
+/// * `project_root` - Project root (the directory holding concepts/)
+/// * `kernel` - Kernel name
+pub fn is_sharded(project_root: &Path, kernel: &str) -> bool {
+    OntologyReader::new(project_root.to_path_buf())
+        .read_capabilities(kernel)
+        .map(|capabilities| capabilities.iter().any(|c| c == SHARDING_CAPABILITY))
+        .unwrap_or(false)
+}
+
+/// Whether a directory name is a shard (two lowercase ASCII letters or digits)
+pub fn is_shard_dir_name(name: &str) -> bool {
+    name.len() == 2 && name.bytes().all(|b| b.is_ascii_lowercase() || b.is_ascii_digit())
+}
+
+/// Shard of an entry such as `1764410400000-ab12cd34.inst`
+///
+/// The first two characters of the part after the transaction ID's last
+/// dash, which is random in generated IDs; IDs without a usable prefix are
+/// spread by hash.
+///
+/// # Example
+/// ```
+/// use ckp_core::storage::shard::shard_name;
+///
+/// assert_eq!(shard_name("1764410400000-ab12cd34.inst"), "ab");
+/// assert_eq!(shard_name("tx-AB123.msgpack.job"), "ab");
+/// assert_eq!(shard_name("tx_1.inst").len(), 2);
+/// ```
+pub fn shard_name(entry: &str) -> String {
+    let tx_id = entry.split('.').next().unwrap_or(entry);
+    let random = tx_id.rsplit('-').next().unwrap_or(tx_id);
+    let prefix = random.get(..2).map(str::to_ascii_lowercase).unwrap_or_default();
+
+    if is_shard_dir_name(&prefix) {
+        prefix
+    } else {
+        format!("{:02x}", crc32fast::hash(tx_id.as_bytes()) & 0xff)
+    }
+}
+
+/// Path to write an entry to in a flat or sharded directory
+pub fn entry_path(dir: &Path, entry: &str, sharded: bool) -> PathBuf {
+    if sharded {
+        dir.join(shard_name(entry)).join(entry)
+    } else {
+        dir.join(entry)
+    }
+}
+
+/// Existing path of an entry in either layout (the flat path if absent)
+pub fn locate(dir: &Path, entry: &str) -> PathBuf {
+    let flat = dir.join(entry);
+    if flat.symlink_metadata().is_ok() {
+        return flat;
+    }
+
+    let sharded = entry_path(dir, entry, true);
+    if sharded.symlink_metadata().is_ok() {
+        sharded
+    } else {
+        flat
+    }
+}
+
+/// Every entry of a directory, looking into its shard directories
+pub fn list_entries(dir: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+
+    let mut paths = Vec::new();
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let is_shard = entry.file_name().to_str().is_some_and(is_shard_dir_name) && path.is_dir();
+        if !is_shard {
+            paths.push(path);
+        } else if let Ok(shard) = fs::read_dir(&path) {
+            paths.extend(shard.flatten().map(|e| e.path()));
+        }
+    }
+    paths
+}
+
+/// Directory an entry belongs to, looking through its shard directory
+pub fn entry_dir(path: &Path) -> Option<&Path> {
+    let parent = path.parent()?;
+    match parent.file_name().and_then(|n| n.to_str()) {
+        Some(name) if is_shard_dir_name(name) => parent.parent(),
+        _ => Some(parent),
+    }
+}
+
+/// Latest modification time of a directory and its shard directories
+///
+/// Adding an entry to an existing shard leaves the parent's mtime alone, so
+/// change detection on a sharded directory has to look one level down.
+pub fn modified(dir: &Path) -> std::io::Result<SystemTime> {
+    let mut latest = fs::metadata(dir)?.modified()?;
+
+    for entry in fs::read_dir(dir)?.flatten() {
+        if !entry.file_name().to_str().is_some_and(is_shard_dir_name) {
+            continue;
+        }
+        if let Ok(modified) = entry.metadata().and_then(|m| m.modified()) {
+            latest = latest.max(modified);
+        }
+    }
+    Ok(latest)
+}
+
+/// Entries moved by `migrate_kernel`
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ShardMigration {
+    /// Storage instances moved
+    pub instances: usize,
+    /// Inbox jobs moved
+    pub jobs: usize,
+    /// Edge queue links repointed at moved instances
+    pub relinked: usize,
+}
+
+/// Move a kernel's storage and inbox to the sharded (or back to the flat) layout
+///
+/// Edge queue links in every kernel of the project that point at a moved
+/// instance are repointed, so pending routes survive. The instance index is
+/// left for the caller to rebuild.
+///
+/// # Arguments
+/// * `concepts_dir` - The project's concepts/ directory
+/// * `kernel` - Kernel to migrate
+/// * `shard` - True to shard, false to flatten
+pub fn migrate_kernel(concepts_dir: &Path, kernel: &str, shard: bool) -> Result<ShardMigration> {
+    let kernel_dir = concepts_dir.join(kernel);
+    if !kernel_dir.is_dir() {
+        return Err(CkpError::KernelNotFound(kernel.to_string()));
+    }
+    let storage_dir = kernel_dir.join("storage");
+    let links = edge_links_into(concepts_dir, &storage_dir);
+
+    let mut migration = ShardMigration {
+        instances: relayout(&storage_dir, shard, ".inst")?,
+        jobs: relayout(&kernel_dir.join("queue/inbox"), shard, ".job")?,
+        relinked: 0,
+    };
+
+    for (link, entry) in links {
+        let target = locate(&storage_dir, &entry);
+        let relative = link
+            .parent()
+            .and_then(|queue| pathdiff::diff_paths(&target, queue))
+            .ok_or_else(|| CkpError::Path(format!("Cannot link {} to {}", link.display(), target.display())))?;
+
+        fs::remove_file(&link)?;
+        #[cfg(unix)]
+        std::os::unix::fs::symlink(&relative, &link)?;
+        #[cfg(windows)]
+        std::os::windows::fs::symlink_dir(&relative, &link)?;
+        migration.relinked += 1;
+    }
+
+    Ok(migration)
+}
+
+/// Move entries with the given suffix into or out of shard directories
+fn relayout(dir: &Path, shard: bool, suffix: &str) -> Result<usize> {
+    let mut moved = 0;
+
+    for path in list_entries(dir) {
+        let Some(entry) = path.file_name().and_then(|n| n.to_str()) else {
+            continue;
+        };
+        if !entry.ends_with(suffix) {
+            continue;
+        }
+
+        let target = entry_path(dir, entry, shard);
+        if target == path {
+            continue;
+        }
+        if let Some(parent) = target.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::rename(&path, &target)?;
+        moved += 1;
+    }
+
+    // Flattening leaves the shard directories empty
+    if !shard {
+        for entry in fs::read_dir(dir).into_iter().flatten().flatten() {
+            if entry.file_name().to_str().is_some_and(is_shard_dir_name) {
+                let _ = fs::remove_dir(entry.path());
+            }
+        }
+    }
+    Ok(moved)
+}
+
+/// Edge queue links anywhere in the project that resolve into `storage_dir`
+///
+/// # Returns
+/// Link paths with the name of the instance each points at
+fn edge_links_into(concepts_dir: &Path, storage_dir: &Path) -> Vec<(PathBuf, String)> {
+    let Ok(storage_dir) = fs::canonicalize(storage_dir) else {
+        return Vec::new();
+    };
+
+    let mut links = Vec::new();
+    for kernel in fs::read_dir(concepts_dir).into_iter().flatten().flatten() {
+        for queue in fs::read_dir(kernel.path().join("queue/edges")).into_iter().flatten().flatten() {
+            for link in fs::read_dir(queue.path()).into_iter().flatten().flatten() {
+                let path = link.path();
+                if !path.symlink_metadata().is_ok_and(|m| m.file_type().is_symlink()) {
+                    continue;
+                }
+                let Ok(target) = fs::canonicalize(&path) else {
+                    continue;
+                };
+                if entry_dir(&target) == Some(storage_dir.as_path()) {
+                    if let Some(entry) = target.file_name().and_then(|n| n.to_str()) {
+                        links.push((path, entry.to_string()));
+                    }
+                }
+            }
+        }
+    }
+    links
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_shard_layout_resolution() {
+        assert_eq!(shard_name("1764410400000-ab12cd34.inst"), "ab");
+        assert_eq!(shard_name("tx-ab123.cbor.job"), "ab");
+        // Non-alphanumeric prefixes fall back to a stable hash
+        assert_eq!(shard_name("tx-_x.inst"), shard_name("tx-_x.job"));
+        assert!(is_shard_dir_name(&shard_name("tx-_x.inst")));
+
+        let temp = TempDir::new().unwrap();
+        let dir = temp.path();
+        fs::create_dir_all(entry_path(dir, "tx-ab1.inst", true)).unwrap();
+        fs::create_dir_all(entry_path(dir, "tx-cd2.inst", false)).unwrap();
+
+        assert_eq!(locate(dir, "tx-ab1.inst"), dir.join("ab/tx-ab1.inst"));
+        assert_eq!(locate(dir, "tx-cd2.inst"), dir.join("tx-cd2.inst"));
+        assert_eq!(entry_dir(&dir.join("ab/tx-ab1.inst")), Some(dir));
+
+        let mut entries = list_entries(dir);
+        entries.sort();
+        assert_eq!(entries, vec![dir.join("ab/tx-ab1.inst"), dir.join("tx-cd2.inst")]);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_migrate_kernel_round_trip() {
+        let temp = TempDir::new().unwrap();
+        let concepts = temp.path().join("concepts");
+        let storage = concepts.join("Source/storage");
+        let inbox = concepts.join("Source/queue/inbox");
+        let queue = concepts.join("Target/queue/edges/PRODUCES.Source");
+        fs::create_dir_all(&inbox).unwrap();
+        fs::create_dir_all(&queue).unwrap();
+        for id in ["tx-ab1", "tx-cd2"] {
+            fs::create_dir_all(storage.join(format!("{}.inst", id))).unwrap();
+            fs::write(inbox.join(format!("{}.job", id)), "{}").unwrap();
+        }
+        std::os::unix::fs::symlink("../../../../Source/storage/tx-ab1.inst", queue.join("tx-ab1.inst")).unwrap();
+
+        let migration = migrate_kernel(&concepts, "Source", true).unwrap();
+        assert_eq!(migration, ShardMigration { instances: 2, jobs: 2, relinked: 1 });
+        assert!(storage.join("ab/tx-ab1.inst").is_dir());
+        assert!(inbox.join("cd/tx-cd2.job").is_file());
+        assert!(queue.join("tx-ab1.inst").is_dir(), "edge link should follow the instance");
+
+        let migration = migrate_kernel(&concepts, "Source", false).unwrap();
+        assert_eq!(migration.instances, 2);
+        assert!(storage.join("tx-ab1.inst").is_dir());
+        assert!(!storage.join("ab").exists());
+        assert!(queue.join("tx-ab1.inst").is_dir());
+
+        assert!(migrate_kernel(&concepts, "Missing", true).is_err());
+    }
+}
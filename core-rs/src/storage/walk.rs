@@ -0,0 +1,136 @@
+// storage/walk.rs - Parallel directory traversal
+//
+// Project-wide scans (retention checks, storage indexing, cross-kernel search,
+// edge discovery) spend most of their time waiting on the filesystem, one
+// entry after another. They share one rayon pool here, so their parallelism
+// stays bounded even when scans nest (a project search indexing each kernel's
+// storage). The pool size defaults to the number of CPUs and can be set with
+// CKP_SCAN_THREADS. Results always come back in input order.
+
+use crate::errors::{CkpError, Result};
+use rayon::prelude::*;
+use rayon::{ThreadPool, ThreadPoolBuilder};
+use std::fs::{self, DirEntry};
+use std::path::Path;
+use std::sync::OnceLock;
+
+/// Environment variable bounding the number of scan threads
+pub const SCAN_THREADS_ENV: &str = "CKP_SCAN_THREADS";
+
+/// What `walk` does with a directory entry
+#[derive(Debug)]
+pub enum Visit<T> {
+    /// Ignore the entry
+    Skip,
+    /// Walk into the entry (a directory)
+    Descend,
+    /// Report a result for the entry
+    Yield(T),
+}
+
+/// Pool shared by all parallel scans
+fn pool() -> &'static ThreadPool {
+    static POOL: OnceLock<ThreadPool> = OnceLock::new();
+
+    POOL.get_or_init(|| {
+        let threads = std::env::var(SCAN_THREADS_ENV)
+            .ok()
+            .and_then(|value| value.parse().ok())
+            .unwrap_or(0);
+        ThreadPoolBuilder::new()
+            .num_threads(threads)
+            .thread_name(|i| format!("ckp-scan-{}", i))
+            .build()
+            .expect("failed to build scan thread pool")
+    })
+}
+
+/// Map items on the scan pool, keeping their order
+///
+/// # Example
+/// ```
+/// use ckp_core::storage::walk::par_map;
+///
+/// let lengths = par_map(vec!["a", "bcd", "ef"], |s| s.len());
+/// assert_eq!(lengths, vec![1, 3, 2]);
+/// ```
+pub fn par_map<T, U, F>(items: Vec<T>, f: F) -> Vec<U>
+where
+    T: Send,
+    U: Send,
+    F: Fn(T) -> U + Sync + Send,
+{
+    pool().install(|| items.into_par_iter().map(f).collect())
+}
+
+/// Walk a directory tree in parallel
+///
+/// `visit` is called for every entry and decides whether to skip it, descend
+/// into it or yield a result. Sibling entries, and the subtrees below them,
+/// are visited concurrently.
+///
+/// # Returns
+/// Yielded results in directory order, depth first
+///
+/// # Errors
+/// The first error from reading a directory or from `visit`
+pub fn walk<T, F>(dir: &Path, visit: &F) -> Result<Vec<T>>
+where
+    T: Send,
+    F: Fn(&DirEntry) -> Result<Visit<T>> + Sync,
+{
+    let entries = fs::read_dir(dir)
+        .and_then(|entries| entries.collect::<std::io::Result<Vec<_>>>())
+        .map_err(|e| CkpError::IoError(format!("Failed to read directory {}: {}", dir.display(), e)))?;
+
+    let nested: Vec<Vec<T>> = pool().install(|| {
+        entries
+            .par_iter()
+            .map(|entry| match visit(entry)? {
+                Visit::Skip => Ok(Vec::new()),
+                Visit::Yield(item) => Ok(vec![item]),
+                Visit::Descend => walk(&entry.path(), visit),
+            })
+            .collect::<Result<_>>()
+    })?;
+
+    Ok(nested.into_iter().flatten().collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_walk_visits_tree_in_order() {
+        let temp = TempDir::new().unwrap();
+        for file in ["a/1.txt", "a/b/2.txt", "a/skip/3.txt", "c/4.txt", "c/5.log"] {
+            let path = temp.path().join(file);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, file).unwrap();
+        }
+
+        let mut found = walk(temp.path(), &|entry| {
+            let path = entry.path();
+            Ok(if entry.file_name() == "skip" {
+                Visit::Skip
+            } else if path.is_dir() {
+                Visit::Descend
+            } else if path.extension().is_some_and(|ext| ext == "txt") {
+                Visit::Yield(fs::read_to_string(&path)?)
+            } else {
+                Visit::Skip
+            })
+        })
+        .unwrap();
+        found.sort();
+        assert_eq!(found, vec!["a/1.txt", "a/b/2.txt", "c/4.txt"]);
+
+        let error = walk(&temp.path().join("missing"), &|_| Ok(Visit::<()>::Skip)).unwrap_err();
+        assert!(error.to_string().contains("missing"));
+
+        let squares = par_map((0..100u64).collect(), |n| n * n);
+        assert_eq!(squares[99], 99 * 99);
+    }
+}
@@ -0,0 +1,211 @@
+// storage/watch.rs - Watch storage for new instances
+//
+// A background thread turns new `.inst` directories into InstanceSummary
+// events. Filesystem notifications (notify crate) drive it where available;
+// otherwise, or when the watcher fails, storage is rescanned on an interval.
+// A directory is reported once its receipt.bin is readable, since minting
+// creates the directory before writing the receipt. In sharded storage each
+// shard directory is watched as well, including shards created later.
+
+use super::index::inst_dirs;
+use super::shard;
+use super::scanner::{InstanceScanner, InstanceSummary};
+use crate::errors::CkpError;
+use notify::event::ModifyKind;
+use notify::{Config as NotifyConfig, Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use std::collections::{HashMap, HashSet};
+use std::ffi::OsString;
+use std::path::{Path, PathBuf};
+use std::pin::Pin;
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::time::{Duration, Instant};
+use tokio::sync::mpsc::{unbounded_channel, UnboundedSender};
+use tokio_stream::wrappers::UnboundedReceiverStream;
+use tokio_stream::Stream;
+
+/// Stream of instances appearing in a kernel's storage
+pub type InstanceStream = Pin<Box<dyn Stream<Item = InstanceSummary> + Send>>;
+
+/// Default rescan interval when filesystem notifications are unavailable
+pub const WATCH_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// How often instances still missing their receipt are re-read
+const RECEIPT_RETRY_INTERVAL: Duration = Duration::from_millis(100);
+
+/// How long an instance may lack a readable receipt before it is skipped
+const RECEIPT_WAIT: Duration = Duration::from_secs(30);
+
+type NotifyEvents = (RecommendedWatcher, Receiver<notify::Result<Event>>);
+
+impl InstanceScanner {
+    /// Stream summaries of instances minted from now on
+    ///
+    /// Uses filesystem notifications, falling back to polling every
+    /// `WATCH_POLL_INTERVAL`. The background watcher stops once the stream
+    /// is dropped.
+    pub fn watch(&self) -> Result<InstanceStream, CkpError> {
+        let storage_path = self.find_storage_dir()?;
+        let events = notify_events(&storage_path);
+        self.spawn_watch(storage_path, events, WATCH_POLL_INTERVAL)
+    }
+
+    /// Stream summaries of new instances by rescanning storage on an interval
+    ///
+    /// For filesystems without change notifications (e.g. network mounts).
+    pub fn watch_polling(&self, interval: Duration) -> Result<InstanceStream, CkpError> {
+        let storage_path = self.find_storage_dir()?;
+        self.spawn_watch(storage_path, None, interval)
+    }
+
+    fn spawn_watch(&self, storage_path: PathBuf, events: Option<NotifyEvents>, interval: Duration) -> Result<InstanceStream, CkpError> {
+        // Snapshot after the watcher is live, so nothing falls between the two
+        let seen: HashSet<OsString> = inst_dirs(&storage_path).into_iter().collect();
+        let scanner = InstanceScanner::new(self.kernel_root.clone(), self.kernel_name.clone());
+        let (tx, rx) = unbounded_channel();
+
+        std::thread::Builder::new()
+            .name(format!("ckp-watch-{}", self.kernel_name))
+            .spawn(move || run_watch(scanner, storage_path, seen, events, interval, tx))
+            .map_err(|e| CkpError::IoError(format!("Failed to start storage watcher: {}", e)))?;
+
+        Ok(Box::pin(UnboundedReceiverStream::new(rx)))
+    }
+}
+
+/// Filesystem notifications for the storage directory, if the platform provides them
+fn notify_events(storage_path: &Path) -> Option<NotifyEvents> {
+    let (tx, rx) = mpsc::channel();
+    let mut watcher = RecommendedWatcher::new(tx, NotifyConfig::default()).ok()?;
+    watcher.watch(storage_path, RecursiveMode::NonRecursive).ok()?;
+    for entry in std::fs::read_dir(storage_path).ok()?.flatten() {
+        if is_shard_dir(&entry.path()) {
+            watcher.watch(&entry.path(), RecursiveMode::NonRecursive).ok()?;
+        }
+    }
+    Some((watcher, rx))
+}
+
+fn is_shard_dir(path: &Path) -> bool {
+    path.file_name().and_then(|n| n.to_str()).is_some_and(shard::is_shard_dir_name) && path.is_dir()
+}
+
+fn run_watch(
+    scanner: InstanceScanner,
+    storage_path: PathBuf,
+    mut seen: HashSet<OsString>,
+    mut events: Option<NotifyEvents>,
+    interval: Duration,
+    tx: UnboundedSender<InstanceSummary>,
+) {
+    // Instance directories waiting for their receipt, with when they were first seen
+    let mut pending: HashMap<OsString, Instant> = HashMap::new();
+    let mut last_scan = Instant::now();
+
+    while !tx.is_closed() {
+        let wait = if pending.is_empty() { interval } else { RECEIPT_RETRY_INTERVAL.min(interval) };
+        let mut candidates: Vec<OsString> = pending.keys().cloned().collect();
+
+        let rescan = match &mut events {
+            Some((watcher, rx)) => match rx.recv_timeout(wait) {
+                Ok(Ok(event)) => {
+                    let mut new_shard = false;
+                    if matches!(event.kind, EventKind::Create(_) | EventKind::Modify(ModifyKind::Name(_)) | EventKind::Any) {
+                        for path in &event.paths {
+                            // Entries may land in a new shard before it is watched
+                            if is_shard_dir(path) && watcher.watch(path, RecursiveMode::NonRecursive).is_ok() {
+                                new_shard = true;
+                            }
+                        }
+                        candidates.extend(event.paths.iter().filter_map(|path| path.file_name().map(|n| n.to_os_string())));
+                    }
+                    new_shard
+                }
+                // Events may have been lost
+                Ok(Err(_)) => true,
+                Err(RecvTimeoutError::Timeout) => false,
+                Err(RecvTimeoutError::Disconnected) => {
+                    events = None;
+                    true
+                }
+            },
+            None => {
+                std::thread::sleep(wait);
+                last_scan.elapsed() >= interval
+            }
+        };
+        if rescan {
+            candidates.extend(inst_dirs(&storage_path).into_iter().filter(|name| !seen.contains(name)));
+            last_scan = Instant::now();
+        }
+
+        for name in candidates {
+            if seen.contains(&name) || Path::new(&name).extension().and_then(|s| s.to_str()) != Some("inst") {
+                continue;
+            }
+
+            let inst_dir = shard::locate(&storage_path, &name.to_string_lossy());
+            if !inst_dir.is_dir() {
+                pending.remove(&name);
+                continue;
+            }
+
+            match scanner.read_summary(&inst_dir) {
+                Ok(summary) => {
+                    pending.remove(&name);
+                    seen.insert(name);
+                    if tx.send(summary).is_err() {
+                        return;
+                    }
+                }
+                Err(_) => {
+                    let first_seen = *pending.entry(name.clone()).or_insert_with(Instant::now);
+                    if first_seen.elapsed() >= RECEIPT_WAIT {
+                        pending.remove(&name);
+                        seen.insert(name);
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tokio_stream::StreamExt;
+
+    fn mint(storage_dir: &Path, id: &str) {
+        let inst_dir = storage_dir.join(format!("{}.inst", id));
+        fs::create_dir_all(&inst_dir).unwrap();
+        let receipt = serde_json::json!({"id": id, "name": id, "timestamp": "2025-11-29T10:00:00Z"});
+        fs::write(inst_dir.join("receipt.bin"), receipt.to_string()).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_watch_reports_new_instances() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let kernel_root = temp.path().join("Test.Watch");
+        let storage_dir = kernel_root.join("storage");
+        fs::create_dir_all(&storage_dir).unwrap();
+        mint(&storage_dir, "tx-old");
+
+        let scanner = InstanceScanner::new(kernel_root, "Test.Watch".to_string());
+        for polling in [false, true] {
+            let mut stream = if polling {
+                scanner.watch_polling(Duration::from_millis(20)).unwrap()
+            } else {
+                scanner.watch().unwrap()
+            };
+            let id = format!("tx-{}", uuid::Uuid::new_v4());
+
+            // Receipt lands after the directory, as during a real mint
+            fs::create_dir_all(storage_dir.join(format!("{}.inst", id))).unwrap();
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            mint(&storage_dir, &id);
+
+            let summary = tokio::time::timeout(Duration::from_secs(5), stream.next()).await.unwrap().unwrap();
+            assert_eq!(summary.id, id);
+        }
+    }
+}
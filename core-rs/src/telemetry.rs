@@ -0,0 +1,309 @@
+//! Trace context propagation through job files
+//!
+//! Jobs carry a W3C `traceparent` so one request can be followed across a
+//! pipeline of kernels. Writing a job records the current trace context; the
+//! governor hands the context of the job it runs to the tool process through
+//! the `TRACEPARENT` environment variable, which tools pick up when they emit
+//! in turn (Node.js tools can read the same variable).
+//!
+//! With the `otel` feature and `OTEL_EXPORTER_OTLP_ENDPOINT` set, `tracing`
+//! spans are exported over OTLP and contexts come from the active span, so
+//! the pipeline shows up end-to-end in Jaeger or Tempo. Without it, contexts
+//! are passed through as-is and their trace ids still appear in log events.
+
+use rand::RngCore;
+
+/// Environment variable carrying the trace context into tool processes
+pub const TRACEPARENT_ENV: &str = "TRACEPARENT";
+
+/// W3C trace context of a job
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TraceContext {
+    pub trace_id: [u8; 16],
+    pub parent_id: [u8; 8],
+    pub flags: u8,
+}
+
+impl TraceContext {
+    /// Parse a `traceparent` header value (`00-<trace-id>-<parent-id>-<flags>`)
+    ///
+    /// # Returns
+    /// None if the value is malformed or has all-zero ids
+    pub fn parse(traceparent: &str) -> Option<Self> {
+        let mut parts = traceparent.trim().split('-');
+        let version = parts.next()?;
+        let trace_id = parts.next()?;
+        let parent_id = parts.next()?;
+        let flags = parts.next()?;
+
+        // Future versions may append fields; version 00 must not
+        if version.len() != 2 || version == "ff" || (version == "00" && parts.next().is_some()) {
+            return None;
+        }
+
+        let mut context = TraceContext { trace_id: [0; 16], parent_id: [0; 8], flags: 0 };
+        decode_hex(trace_id, &mut context.trace_id)?;
+        decode_hex(parent_id, &mut context.parent_id)?;
+        let mut flag_byte = [0u8; 1];
+        decode_hex(flags, &mut flag_byte)?;
+        context.flags = flag_byte[0];
+
+        if context.trace_id == [0; 16] || context.parent_id == [0; 8] {
+            return None;
+        }
+        Some(context)
+    }
+
+    /// Start a new trace
+    pub fn new_root(sampled: bool) -> Self {
+        let mut rng = rand::thread_rng();
+        let mut context = TraceContext { trace_id: [0; 16], parent_id: [0; 8], flags: sampled as u8 };
+        while context.trace_id == [0; 16] {
+            rng.fill_bytes(&mut context.trace_id);
+        }
+        while context.parent_id == [0; 8] {
+            rng.fill_bytes(&mut context.parent_id);
+        }
+        context
+    }
+
+    /// Context for work done on behalf of this one: same trace, new parent id
+    pub fn child(&self) -> Self {
+        let mut child = *self;
+        let mut rng = rand::thread_rng();
+        while child.parent_id == self.parent_id || child.parent_id == [0; 8] {
+            rng.fill_bytes(&mut child.parent_id);
+        }
+        child
+    }
+
+    /// Whether the trace was sampled upstream
+    pub fn is_sampled(&self) -> bool {
+        self.flags & 0x01 != 0
+    }
+
+    /// Trace id as 32 lowercase hex digits
+    pub fn trace_id_hex(&self) -> String {
+        hex::encode(self.trace_id)
+    }
+}
+
+impl std::fmt::Display for TraceContext {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "00-{}-{}-{:02x}",
+            hex::encode(self.trace_id),
+            hex::encode(self.parent_id),
+            self.flags
+        )
+    }
+}
+
+// Lowercase hex only, as the W3C format requires
+fn decode_hex(value: &str, out: &mut [u8]) -> Option<()> {
+    if value.len() != out.len() * 2 || value.bytes().any(|b| b.is_ascii_uppercase()) {
+        return None;
+    }
+    hex::decode_to_slice(value, out).ok()
+}
+
+/// Trace context to record on a job written now
+///
+/// Taken from the active span when spans are exported, otherwise continued
+/// from `TRACEPARENT` (set for tools spawned by a governor).
+pub fn current_traceparent() -> Option<String> {
+    let inherited = std::env::var(TRACEPARENT_ENV).ok();
+    traceparent_for(&tracing::Span::current(), inherited.as_deref())
+}
+
+/// Trace context for work done on behalf of `span`, e.g. a spawned tool
+///
+/// # Arguments
+/// * `span` - Span the work belongs to
+/// * `parent` - Context the span continues, used when spans are not exported
+pub fn traceparent_for(span: &tracing::Span, parent: Option<&str>) -> Option<String> {
+    #[cfg(feature = "otel")]
+    if let Some(traceparent) = otel::span_traceparent(span) {
+        return Some(traceparent);
+    }
+    #[cfg(not(feature = "otel"))]
+    let _ = span;
+
+    parent
+        .and_then(TraceContext::parse)
+        .map(|context| context.child().to_string())
+}
+
+/// Span for processing a job, continuing the job's trace
+///
+/// # Example
+/// ```no_run
+/// # use ckp_core::kernel::Kernel;
+/// # fn example(kernel: &Kernel) -> ckp_core::errors::Result<()> {
+/// for job in kernel.inbox_iter()? {
+///     let job = job?;
+///     let _span = job.span().entered();
+///     // Jobs emitted here carry the same trace
+///     job.archive()?;
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub fn job_span(kernel: &str, tx_id: &str, traceparent: Option<&str>) -> tracing::Span {
+    let span = tracing::info_span!("job", kernel, tx_id, trace_id = tracing::field::Empty);
+    set_parent(&span, traceparent);
+    span
+}
+
+/// Continue a job's trace in `span`
+///
+/// Records the trace id on spans declaring a `trace_id` field, so log
+/// events can be joined with the job's other work even without export.
+pub fn set_parent(span: &tracing::Span, traceparent: Option<&str>) {
+    let context = match traceparent.and_then(TraceContext::parse) {
+        Some(context) => context,
+        None => return,
+    };
+    span.record("trace_id", context.trace_id_hex().as_str());
+
+    #[cfg(feature = "otel")]
+    otel::set_parent(span, &context);
+}
+
+/// Flush spans still buffered for export; call before the process exits
+pub fn shutdown() {
+    #[cfg(feature = "otel")]
+    otel::shutdown();
+}
+
+#[cfg(feature = "otel")]
+pub use otel::layer;
+
+/// OTLP export of `tracing` spans
+#[cfg(feature = "otel")]
+mod otel {
+    use super::TraceContext;
+    use crate::errors::{CkpError, Result};
+    use once_cell::sync::OnceCell;
+    use opentelemetry::propagation::TextMapPropagator;
+    use opentelemetry::trace::{TraceContextExt, TracerProvider as _};
+    use opentelemetry::{global, KeyValue};
+    use opentelemetry_sdk::propagation::TraceContextPropagator;
+    use opentelemetry_sdk::trace::SdkTracerProvider;
+    use opentelemetry_sdk::Resource;
+    use std::collections::HashMap;
+    use tracing::Subscriber;
+    use tracing_opentelemetry::{OpenTelemetryLayer, OpenTelemetrySpanExt};
+    use tracing_subscriber::registry::LookupSpan;
+
+    /// Standard variable naming the OTLP collector; export is off without it
+    const OTLP_ENDPOINT_ENV: &str = "OTEL_EXPORTER_OTLP_ENDPOINT";
+
+    static PROVIDER: OnceCell<SdkTracerProvider> = OnceCell::new();
+
+    /// Layer exporting spans over OTLP/HTTP, if an endpoint is configured
+    ///
+    /// # Errors
+    /// `CkpError::IoError` if the exporter cannot be built; the caller
+    /// reports it once its subscriber is installed
+    ///
+    /// The collector and service name follow the standard `OTEL_*`
+    /// environment variables; the service name defaults to "ckp".
+    pub fn layer<S>() -> Result<Option<OpenTelemetryLayer<S, opentelemetry_sdk::trace::Tracer>>>
+    where
+        S: Subscriber + for<'span> LookupSpan<'span>,
+    {
+        if std::env::var_os(OTLP_ENDPOINT_ENV).is_none() {
+            return Ok(None);
+        }
+
+        let exporter = opentelemetry_otlp::SpanExporter::builder()
+            .with_http()
+            .build()
+            .map_err(|e| CkpError::IoError(format!("Failed to build OTLP exporter: {}", e)))?;
+
+        let service = std::env::var("OTEL_SERVICE_NAME").unwrap_or_else(|_| "ckp".to_string());
+        let provider = SdkTracerProvider::builder()
+            .with_batch_exporter(exporter)
+            .with_resource(
+                Resource::builder()
+                    .with_service_name(service)
+                    .with_attribute(KeyValue::new("ckp.version", crate::VERSION))
+                    .build(),
+            )
+            .build();
+
+        let tracer = provider.tracer("ckp");
+        global::set_text_map_propagator(TraceContextPropagator::new());
+        global::set_tracer_provider(provider.clone());
+        let _ = PROVIDER.set(provider);
+
+        Ok(Some(tracing_opentelemetry::layer().with_tracer(tracer)))
+    }
+
+    pub(super) fn shutdown() {
+        if let Some(provider) = PROVIDER.get() {
+            if let Err(e) = provider.shutdown() {
+                tracing::warn!(error = %e, "failed to flush spans");
+            }
+        }
+    }
+
+    pub(super) fn span_traceparent(span: &tracing::Span) -> Option<String> {
+        let context = span.context();
+        let span_context = context.span().span_context().clone();
+        if !span_context.is_valid() {
+            return None;
+        }
+        Some(format!(
+            "00-{}-{}-{:02x}",
+            span_context.trace_id(),
+            span_context.span_id(),
+            span_context.trace_flags().to_u8()
+        ))
+    }
+
+    pub(super) fn set_parent(span: &tracing::Span, context: &TraceContext) {
+        let carrier = HashMap::from([("traceparent".to_string(), context.to_string())]);
+        let parent = global::get_text_map_propagator(|propagator| propagator.extract(&carrier));
+        let _ = span.set_parent(parent);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TRACEPARENT: &str = "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01";
+
+    #[test]
+    fn test_traceparent_parsing() {
+        let context = TraceContext::parse(TRACEPARENT).unwrap();
+        assert_eq!(context.trace_id_hex(), "4bf92f3577b34da6a3ce929d0e0e4736");
+        assert!(context.is_sampled());
+        assert_eq!(context.to_string(), TRACEPARENT);
+
+        // Malformed, uppercase, zero ids and unknown trailing fields are rejected
+        assert!(TraceContext::parse("00-4bf92f35-00f067aa0ba902b7-01").is_none());
+        assert!(TraceContext::parse(&TRACEPARENT.to_uppercase()).is_none());
+        assert!(TraceContext::parse("00-00000000000000000000000000000000-00f067aa0ba902b7-01").is_none());
+        assert!(TraceContext::parse(&format!("{}-extra", TRACEPARENT)).is_none());
+        assert!(TraceContext::parse(&TRACEPARENT.replacen("00", "ff", 1)).is_none());
+    }
+
+    #[test]
+    fn test_child_context_continues_trace() {
+        let root = TraceContext::new_root(true);
+        let child = root.child();
+        assert_eq!(child.trace_id, root.trace_id);
+        assert_ne!(child.parent_id, root.parent_id);
+        assert_eq!(child.flags, root.flags);
+
+        // Without exported spans, the parent context is continued
+        let continued = traceparent_for(&tracing::Span::none(), Some(TRACEPARENT)).unwrap();
+        let continued = TraceContext::parse(&continued).unwrap();
+        assert_eq!(continued.trace_id_hex(), "4bf92f3577b34da6a3ce929d0e0e4736");
+        assert!(traceparent_for(&tracing::Span::none(), None).is_none());
+    }
+}
@@ -97,7 +97,7 @@ impl CkdlParser {
                         category: current_category.clone(),
                     });
                 } else {
-                    eprintln!("Warning: Invalid EXTERN URN '{}'", urn_str);
+                    tracing::warn!(urn = urn_str, "invalid EXTERN URN");
                 }
             } else if line.starts_with("KERNEL ") {
                 // Save previous kernel if any
@@ -113,7 +113,7 @@ impl CkdlParser {
                         port: None,
                     });
                 } else {
-                    eprintln!("Warning: Invalid KERNEL URN '{}'", urn_str);
+                    tracing::warn!(urn = urn_str, "invalid KERNEL URN");
                 }
             } else if line.starts_with("TYPE:") {
                 if let Some(ref mut kernel) = current_kernel {
@@ -134,7 +134,7 @@ impl CkdlParser {
                 let urn_str = line[5..].trim();
                 match Self::parse_edge(urn_str) {
                     Ok(edge) => edges.push(edge),
-                    Err(e) => eprintln!("Warning: Failed to parse EDGE: {} - Error: {}", urn_str, e),
+                    Err(e) => tracing::warn!(urn = urn_str, error = %e, "failed to parse EDGE"),
                 }
             }
         }
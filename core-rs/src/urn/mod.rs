@@ -10,6 +10,18 @@
 //! ckp://Recipes.BakeCake:v0.1#storage/tx-123.inst
 //! ```
 //!
+//! **Cross-project kernel URN:**
+//! ```text
+//! ckp://project-name/Domain.Concept:version#stage/path
+//! ckp://workers/Worker.Process:v1#inbox
+//! ```
+//!
+//! **Namespaced (tenant) kernel URN:**
+//! ```text
+//! ckp://namespace~Domain.Concept:version#stage/path
+//! ckp://acme~Orders.Checkout:v1#inbox
+//! ```
+//!
 //! **Edge URN:**
 //! ```text
 //! ckp://Edge.PREDICATE.Source-to-Target:version
@@ -23,7 +35,7 @@ mod validator;
 pub use ckdl_parser::{
     CkdlParser, CkdlDocument, ExternDeclaration, KernelDeclaration, EdgeDeclaration
 };
-pub use resolver::{UrnResolver, ParsedUrn, ParsedEdgeUrn, ParsedAgentUrn, AgentType, ParsedQueryUrn, ParsedQueryUrnV2};
+pub use resolver::{UrnResolver, NAMESPACE_SEPARATOR, ParsedUrn, ParsedProjectUrn, ParsedEdgeUrn, ParsedAgentUrn, AgentType, ParsedQueryUrn, ParsedQueryUrnV2};
 pub use validator::UrnValidator;
 
 #[cfg(test)]
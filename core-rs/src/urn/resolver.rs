@@ -2,7 +2,15 @@
 
 use crate::errors::{CkpError, Result};
 use regex::Regex;
-use std::path::{Path, PathBuf};
+use std::path::{Component, Path, PathBuf};
+use std::sync::LazyLock;
+
+/// Separator between a tenant namespace and a kernel name
+///
+/// A namespaced kernel lives in `concepts/{namespace}~{Kernel}` and is
+/// addressed as `ckp://{namespace}~{Kernel}:{version}`. Kernels without a
+/// namespace belong to the shared root namespace.
+pub const NAMESPACE_SEPARATOR: char = '~';
 
 /// Parsed kernel URN components
 #[derive(Debug, Clone, PartialEq)]
@@ -13,6 +21,39 @@ pub struct ParsedUrn {
     pub path: Option<String>,
 }
 
+impl ParsedUrn {
+    /// Tenant namespace of the kernel, if it has one
+    pub fn namespace(&self) -> Option<&str> {
+        UrnResolver::split_namespace(&self.kernel).0
+    }
+}
+
+/// Parsed cross-project kernel URN components
+///
+/// Format: `ckp://{project}/{Kernel}:{version}#{stage}/{path}`, addressing a
+/// kernel in another registered project. Project names carry no dots or
+/// colons, which keeps them apart from kernel names and query URNs.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParsedProjectUrn {
+    pub project: String,
+    pub kernel: String,
+    pub version: String,
+    pub stage: Option<String>,
+    pub path: Option<String>,
+}
+
+impl ParsedProjectUrn {
+    /// Kernel URN within the target project (project prefix dropped)
+    pub fn local_urn(&self) -> String {
+        UrnResolver::build(&ParsedUrn {
+            kernel: self.kernel.clone(),
+            version: self.version.clone(),
+            stage: self.stage.clone(),
+            path: self.path.clone(),
+        })
+    }
+}
+
 /// Parsed edge URN components
 ///
 /// Supports two formats based on `edge_versioning` flag in .ckproject:
@@ -144,14 +185,39 @@ impl UrnResolver {
             .captures(urn)
             .ok_or_else(|| CkpError::InvalidUrnFormat(urn.to_string()))?;
 
+        let kernel = caps.get(1).unwrap().as_str();
+        if kernel.contains(NAMESPACE_SEPARATOR) && Self::split_namespace(kernel).0.is_none() {
+            return Err(CkpError::InvalidUrnFormat(urn.to_string()));
+        }
+
         Ok(ParsedUrn {
-            kernel: caps.get(1).unwrap().as_str().to_string(),
+            kernel: kernel.to_string(),
             version: caps.get(2).unwrap().as_str().to_string(),
             stage: caps.get(3).map(|m| m.as_str().to_string()),
             path: caps.get(4).map(|m| m.as_str().to_string()),
         })
     }
 
+    /// Parse a kernel URN addressed from inside a tenant namespace
+    ///
+    /// Unqualified kernels resolve into `namespace`; kernels in any other
+    /// namespace are rejected.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ckp_core::UrnResolver;
+    ///
+    /// let parsed = UrnResolver::parse_in_namespace("ckp://Orders.Checkout:v1#inbox", Some("acme")).unwrap();
+    /// assert_eq!(parsed.kernel, "acme~Orders.Checkout");
+    /// assert!(UrnResolver::parse_in_namespace("ckp://globex~Orders.Checkout:v1", Some("acme")).is_err());
+    /// ```
+    pub fn parse_in_namespace(urn: &str, namespace: Option<&str>) -> Result<ParsedUrn> {
+        let mut parsed = Self::parse(urn)?;
+        parsed.kernel = Self::scope_kernel(&parsed.kernel, namespace)?;
+        Ok(parsed)
+    }
+
     /// Resolve URN to absolute filesystem path
     ///
     /// # Examples
@@ -166,8 +232,18 @@ impl UrnResolver {
     /// ).unwrap();
     /// assert_eq!(path, Path::new("/test/concepts/Recipes.BakeCake/queue/inbox"));
     /// ```
+    ///
+    /// # Errors
+    /// `CkpError::InvalidPath` if the kernel or path would leave `concepts_root`
     pub fn resolve_to_path(urn: &str, concepts_root: &Path) -> Result<PathBuf> {
         let parsed = Self::parse(urn)?;
+
+        // URNs arrive in job files from shared directories: never resolve
+        // outside the concepts root
+        for part in std::iter::once(&parsed.kernel).chain(parsed.path.as_ref()) {
+            Self::ensure_contained(part, urn)?;
+        }
+
         let base_path = concepts_root.join(&parsed.kernel);
 
         // If no stage specified, return kernel root
@@ -205,6 +281,32 @@ impl UrnResolver {
         Ok(full_path)
     }
 
+    /// Check that a URN part stays below the directory it is joined to
+    ///
+    /// `part` (a kernel name, stage or path) may only consist of plain
+    /// components: no `..`, no root and no prefix.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ckp_core::UrnResolver;
+    ///
+    /// assert!(UrnResolver::ensure_contained("Recipes.BakeCake", "ckp://Recipes.BakeCake").is_ok());
+    /// assert!(UrnResolver::ensure_contained("../System.Rbac", "ckp://../System.Rbac").is_err());
+    /// ```
+    ///
+    /// # Errors
+    /// `CkpError::InvalidPath` naming `urn` if `part` is empty or would escape
+    pub fn ensure_contained(part: &str, urn: &str) -> Result<()> {
+        let mut components = Path::new(part).components().peekable();
+        if components.peek().is_none()
+            || !components.all(|c| matches!(c, Component::Normal(_) | Component::CurDir))
+        {
+            return Err(CkpError::InvalidPath(format!("{} escapes the concepts root", urn)));
+        }
+        Ok(())
+    }
+
     /// Build URN from components
     ///
     /// # Examples
@@ -407,6 +509,170 @@ impl UrnResolver {
         kernel_name_or_urn.to_string()
     }
 
+    /// Check if URN addresses a kernel in another project
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ckp_core::UrnResolver;
+    ///
+    /// assert!(UrnResolver::is_project_urn("ckp://workers/Worker.Process:v1"));
+    /// assert!(!UrnResolver::is_project_urn("ckp://Recipes.BakeCake:v0.1"));
+    /// assert!(!UrnResolver::is_project_urn("ckp://Agent/user:admin"));
+    /// ```
+    pub fn is_project_urn(urn: &str) -> bool {
+        Self::parse_project_urn(urn).is_ok()
+    }
+
+    /// Parse cross-project kernel URN
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ckp_core::UrnResolver;
+    ///
+    /// let parsed = UrnResolver::parse_project_urn("ckp://workers/Worker.Process:v1#inbox").unwrap();
+    /// assert_eq!(parsed.project, "workers");
+    /// assert_eq!(parsed.kernel, "Worker.Process");
+    /// assert_eq!(parsed.local_urn(), "ckp://Worker.Process:v1#inbox");
+    /// ```
+    pub fn parse_project_urn(urn: &str) -> Result<ParsedProjectUrn> {
+        // Project URN regex: ckp://[project]/[kernel]:[version]#[stage]/[path]
+        // Groups: (project)(kernel)(version)(stage?)(path?)
+        static PROJECT_URN: LazyLock<Regex> = LazyLock::new(|| {
+            Regex::new(r"^ckp://([A-Za-z0-9][A-Za-z0-9_-]*)/([^:/#]+):([^#]+)(?:#([^/]+)(?:/(.+))?)?$")
+                .expect("project URN pattern is valid")
+        });
+
+        let caps = PROJECT_URN
+            .captures(urn)
+            .filter(|caps| &caps[1] != "Agent")
+            .ok_or_else(|| CkpError::InvalidUrnFormat(urn.to_string()))?;
+
+        Ok(ParsedProjectUrn {
+            project: caps.get(1).unwrap().as_str().to_string(),
+            kernel: caps.get(2).unwrap().as_str().to_string(),
+            version: caps.get(3).unwrap().as_str().to_string(),
+            stage: caps.get(4).map(|m| m.as_str().to_string()),
+            path: caps.get(5).map(|m| m.as_str().to_string()),
+        })
+    }
+
+    /// Split a project-qualified kernel name (`project/Kernel`)
+    ///
+    /// Edge targets in another project are written this way.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ckp_core::UrnResolver;
+    ///
+    /// assert_eq!(UrnResolver::split_project("workers/Worker.Process"), (Some("workers"), "Worker.Process"));
+    /// assert_eq!(UrnResolver::split_project("Worker.Process"), (None, "Worker.Process"));
+    /// ```
+    pub fn split_project(kernel_name: &str) -> (Option<&str>, &str) {
+        match kernel_name.split_once('/') {
+            Some((project, kernel))
+                if !project.is_empty() && project != "Agent" && !project.contains(['.', ':']) =>
+            {
+                (Some(project), kernel)
+            }
+            _ => (None, kernel_name),
+        }
+    }
+
+    /// Split a namespaced kernel name (`namespace~Kernel`)
+    ///
+    /// Names with a malformed namespace are returned whole, without one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ckp_core::UrnResolver;
+    ///
+    /// assert_eq!(UrnResolver::split_namespace("acme~Orders.Checkout"), (Some("acme"), "Orders.Checkout"));
+    /// assert_eq!(UrnResolver::split_namespace("Orders.Checkout"), (None, "Orders.Checkout"));
+    /// ```
+    pub fn split_namespace(kernel_name: &str) -> (Option<&str>, &str) {
+        match kernel_name.split_once(NAMESPACE_SEPARATOR) {
+            Some((namespace, kernel))
+                if Self::is_valid_namespace(namespace) && !kernel.is_empty() && !kernel.contains(NAMESPACE_SEPARATOR) =>
+            {
+                (Some(namespace), kernel)
+            }
+            _ => (None, kernel_name),
+        }
+    }
+
+    /// Tenant namespace of a kernel name or URN
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ckp_core::UrnResolver;
+    ///
+    /// assert_eq!(UrnResolver::namespace_of("ckp://acme~Orders.Checkout:v1#inbox"), Some("acme".to_string()));
+    /// assert_eq!(UrnResolver::namespace_of("acme~Orders.Checkout"), Some("acme".to_string()));
+    /// assert_eq!(UrnResolver::namespace_of("ckp://Orders.Checkout"), None);
+    /// ```
+    pub fn namespace_of(kernel_name_or_urn: &str) -> Option<String> {
+        let name = kernel_name_or_urn.trim_start_matches("ckp://");
+        let name = name.split([':', '#']).next().unwrap_or(name);
+        Self::split_namespace(name).0.map(str::to_string)
+    }
+
+    /// Check if a tenant namespace name is valid
+    ///
+    /// Namespaces follow project names: letters, digits, `_` and `-`,
+    /// starting with a letter or digit.
+    pub fn is_valid_namespace(namespace: &str) -> bool {
+        let mut chars = namespace.chars();
+        chars.next().is_some_and(|c| c.is_ascii_alphanumeric())
+            && chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+    }
+
+    /// Qualify a kernel name with a tenant namespace
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ckp_core::UrnResolver;
+    ///
+    /// assert_eq!(UrnResolver::qualify(Some("acme"), "Orders.Checkout"), "acme~Orders.Checkout");
+    /// assert_eq!(UrnResolver::qualify(None, "Orders.Checkout"), "Orders.Checkout");
+    /// ```
+    pub fn qualify(namespace: Option<&str>, kernel: &str) -> String {
+        match namespace {
+            Some(namespace) => format!("{}{}{}", namespace, NAMESPACE_SEPARATOR, kernel),
+            None => kernel.to_string(),
+        }
+    }
+
+    /// Resolve a kernel name from inside a tenant namespace
+    ///
+    /// Unqualified names resolve into `namespace`; with no namespace, only
+    /// unqualified names are accepted.
+    ///
+    /// # Errors
+    ///
+    /// `CkpError::Rbac` if the name belongs to another namespace, or
+    /// `CkpError::ValidationError` if `namespace` is invalid
+    pub fn scope_kernel(kernel_name: &str, namespace: Option<&str>) -> Result<String> {
+        if let Some(namespace) = namespace.filter(|ns| !Self::is_valid_namespace(ns)) {
+            return Err(CkpError::ValidationError(format!("Invalid namespace: {}", namespace)));
+        }
+
+        match Self::split_namespace(kernel_name) {
+            (None, kernel) if !kernel.contains(NAMESPACE_SEPARATOR) => Ok(Self::qualify(namespace, kernel)),
+            (Some(own), _) if Some(own) == namespace => Ok(kernel_name.to_string()),
+            _ => Err(CkpError::Rbac(format!(
+                "Kernel {} is outside namespace {}",
+                kernel_name,
+                namespace.unwrap_or("(root)")
+            ))),
+        }
+    }
+
     /// Check if URN is an agent URN
     ///
     /// # Examples
@@ -744,6 +1010,23 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_resolve_to_path_rejects_escapes() {
+        let root = Path::new("/test/concepts");
+        for urn in [
+            "ckp://../Recipes.BakeCake:v0.1#inbox",
+            "ckp:///etc:v0.1",
+            "ckp://Recipes.BakeCake:v0.1#storage/../../../etc/passwd",
+            "ckp://Recipes.BakeCake:v0.1#storage//etc/passwd",
+        ] {
+            assert!(
+                matches!(UrnResolver::resolve_to_path(urn, root), Err(CkpError::InvalidPath(_))),
+                "{} should be rejected",
+                urn
+            );
+        }
+    }
+
     #[test]
     fn test_build_urn() {
         let components = ParsedUrn {
@@ -1172,4 +1455,49 @@ mod tests {
         assert_eq!(parsed.params.get("timestamp_from"), Some(&"2025-12-01".to_string()));
         assert_eq!(parsed.params.get("timestamp_to"), Some(&"2025-12-31".to_string()));
     }
+
+    /// Test: Cross-project URNs parse apart from kernel, agent and query URNs
+    #[test]
+    fn test_parse_project_urn() {
+        let parsed = UrnResolver::parse_project_urn("ckp://gateway-2/Worker.Process:v1.0#storage/tx-1.inst").unwrap();
+        assert_eq!(parsed.project, "gateway-2");
+        assert_eq!(parsed.kernel, "Worker.Process");
+        assert_eq!(parsed.version, "v1.0");
+        assert_eq!(parsed.stage, Some("storage".to_string()));
+        assert_eq!(parsed.path, Some("tx-1.inst".to_string()));
+        assert_eq!(parsed.local_urn(), "ckp://Worker.Process:v1.0#storage/tx-1.inst");
+
+        for urn in [
+            "ckp://Worker.Process:v1.0",
+            "ckp://Agent/user:admin",
+            "ckp://System.Gateway:v1.0/Process?limit=20",
+            "ckp://Edge.PRODUCES.A-to-B:v1.0",
+            "ckp://workers/Worker.Process",
+        ] {
+            assert!(!UrnResolver::is_project_urn(urn), "Expected non-project URN: {}", urn);
+        }
+    }
+
+    /// Test: Namespaced kernels parse, and stay inside their namespace when scoped
+    #[test]
+    fn test_namespaced_kernel_urns() {
+        let parsed = UrnResolver::parse("ckp://acme~Orders.Checkout:v1#storage/tx-1.inst").unwrap();
+        assert_eq!(parsed.kernel, "acme~Orders.Checkout");
+        assert_eq!(parsed.namespace(), Some("acme"));
+        assert_eq!(
+            UrnResolver::resolve_to_path("ckp://acme~Orders.Checkout:v1#inbox", Path::new("/c")).unwrap(),
+            Path::new("/c/acme~Orders.Checkout/queue/inbox")
+        );
+
+        for urn in ["ckp://~Orders:v1", "ckp://acme~:v1", "ckp://a~b~Orders:v1", "ckp://ac.me~Orders:v1"] {
+            assert!(UrnResolver::parse(urn).is_err(), "Expected malformed namespace: {}", urn);
+        }
+
+        assert_eq!(UrnResolver::scope_kernel("Orders", Some("acme")).unwrap(), "acme~Orders");
+        assert_eq!(UrnResolver::scope_kernel("acme~Orders", Some("acme")).unwrap(), "acme~Orders");
+        assert_eq!(UrnResolver::scope_kernel("Orders", None).unwrap(), "Orders");
+        assert!(matches!(UrnResolver::scope_kernel("globex~Orders", Some("acme")), Err(CkpError::Rbac(_))));
+        assert!(matches!(UrnResolver::scope_kernel("acme~Orders", None), Err(CkpError::Rbac(_))));
+        assert!(UrnResolver::scope_kernel("Orders", Some("bad.ns")).is_err());
+    }
 }
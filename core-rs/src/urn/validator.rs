@@ -97,6 +97,11 @@ impl UrnValidator {
     /// assert!(result.valid);
     /// ```
     pub fn validate_kernel_urn(urn: &str) -> ValidationResult {
+        // Cross-project URN: validate the kernel URN inside the target project
+        if let Ok(parsed) = UrnResolver::parse_project_urn(urn) {
+            return Self::validate_kernel_urn(&parsed.local_urn());
+        }
+
         let mut result = ValidationResult::new();
 
         match UrnResolver::parse(urn) {
@@ -195,6 +200,7 @@ impl UrnValidator {
     /// - Can contain: letters, numbers, dots, hyphens
     /// - Cannot start or end with dot or hyphen
     /// - Must be at least 1 character
+    /// - May carry a tenant namespace prefix (`namespace~Kernel`)
     ///
     /// # Examples
     ///
@@ -203,10 +209,13 @@ impl UrnValidator {
     ///
     /// assert!(UrnValidator::is_valid_kernel_name("Recipes.BakeCake"));
     /// assert!(UrnValidator::is_valid_kernel_name("System.Gateway.HTTP"));
+    /// assert!(UrnValidator::is_valid_kernel_name("acme~Recipes.BakeCake"));
     /// assert!(!UrnValidator::is_valid_kernel_name(".Invalid"));
     /// assert!(!UrnValidator::is_valid_kernel_name("Invalid-"));
+    /// assert!(!UrnValidator::is_valid_kernel_name("~Invalid"));
     /// ```
     pub fn is_valid_kernel_name(name: &str) -> bool {
+        let (_, name) = UrnResolver::split_namespace(name);
         if name.is_empty() {
             return false;
         }
@@ -339,6 +348,12 @@ mod tests {
         assert!(result.errors.is_empty());
     }
 
+    #[test]
+    fn test_validate_cross_project_urn() {
+        assert!(UrnValidator::validate("ckp://workers/Worker.Process:v1.0#inbox").valid);
+        assert!(!UrnValidator::validate("ckp://workers/Worker.Process:latest").valid);
+    }
+
     #[test]
     fn test_validate_invalid_urn() {
         let result = UrnValidator::validate("invalid-urn");
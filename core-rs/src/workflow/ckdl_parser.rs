@@ -41,6 +41,7 @@ pub struct WorkflowKernel {
     pub description: String,
     pub capabilities: Vec<String>,
     pub actions: Vec<String>,
+    pub outputs: Vec<String>,
     pub origin: ComponentOrigin,
 }
 
@@ -252,6 +253,7 @@ fn parse_kernel(lines: &[&str], start: usize, reader: &OntologyReader) -> Result
     let mut description = String::new();
     let mut capabilities = Vec::new();
     let mut actions = Vec::new();
+    let mut outputs = Vec::new();
     let mut i = start + 1;
 
     while i < lines.len() {
@@ -282,6 +284,14 @@ fn parse_kernel(lines: &[&str], start: usize, reader: &OntologyReader) -> Result
                 i += 1;
             }
             continue;
+        } else if line.trim().starts_with("OUTPUTS:") {
+            i += 1;
+            while i < lines.len() && lines[i].trim().starts_with('-') {
+                let output = lines[i].trim().strip_prefix('-').unwrap().trim().trim_matches('"');
+                outputs.push(output.to_string());
+                i += 1;
+            }
+            continue;
         }
 
         i += 1;
@@ -298,6 +308,7 @@ fn parse_kernel(lines: &[&str], start: usize, reader: &OntologyReader) -> Result
             description,
             capabilities,
             actions,
+            outputs,
             origin,
         },
         i - start,
@@ -469,6 +480,7 @@ pub fn ckdl_to_workflow(ckdl: CkdlWorkflow) -> Workflow {
             status: PhaseStatus::Pending,
             started_at: None,
             completed_at: None,
+            outputs: k.outputs.clone(),
         }
     }).collect();
 
@@ -0,0 +1,302 @@
+/// Workflow events and the workflows they trigger
+///
+/// Events such as "kernel-registered" are jobs emitted to the System.Workflow
+/// kernel, whose payload is a `WorkflowEvent`. `WorkflowTriggers` holds the
+/// workflows declared with `TRIGGER: on-event(<event>)` (or just the event
+/// name) and starts the ones an event triggers: each start is recorded as a
+/// `WorkflowRun`, and every entry phase of the workflow (a phase no workflow
+/// edge leads to) gets a job naming the workflow and run and carrying the
+/// event.
+///
+/// KernelManager announces each kernel it creates, so onboarding workflows
+/// (RBAC review, registry update) can be started from "kernel-registered".
+
+use crate::drivers::{FileSystemDriver, JobFileBuilder, StorageDriver};
+use crate::errors::Result;
+use crate::workflow::{ckdl_to_workflow, parse_ckdl_file, PhaseStatus, Workflow, WorkflowRun, WorkflowTrigger};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Kernel receiving workflow events
+pub const WORKFLOW_KERNEL: &str = "System.Workflow";
+
+/// Event announced when a kernel is created
+pub const KERNEL_REGISTERED: &str = "kernel-registered";
+
+/// Event that can trigger workflows
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WorkflowEvent {
+    /// Event name (e.g., "kernel-registered")
+    pub event: String,
+
+    /// Kernel the event is about
+    pub kernel: String,
+
+    /// Event details (e.g., version and template of a registered kernel)
+    #[serde(default, skip_serializing_if = "Value::is_null")]
+    pub details: Value,
+}
+
+impl WorkflowEvent {
+    pub fn new(event: impl Into<String>, kernel: impl Into<String>) -> Self {
+        Self {
+            event: event.into(),
+            kernel: kernel.into(),
+            details: Value::Null,
+        }
+    }
+
+    /// A kernel was created
+    pub fn kernel_registered(kernel: &str, version: &str, template: &str) -> Self {
+        Self::new(KERNEL_REGISTERED, kernel)
+            .with_detail("version", version)
+            .with_detail("template", template)
+    }
+
+    pub fn with_detail(mut self, key: &str, value: impl Into<Value>) -> Self {
+        if !self.details.is_object() {
+            self.details = Value::Object(serde_json::Map::new());
+        }
+        self.details[key] = value.into();
+        self
+    }
+
+    /// Emit the event to System.Workflow on behalf of `source`
+    ///
+    /// # Returns
+    /// Transaction ID of the event job
+    pub fn emit(&self, root: &Path, source: &str) -> Result<String> {
+        let job = JobFileBuilder::new(WORKFLOW_KERNEL, serde_json::to_value(self)?)
+            .with_source(source, format!("ckp://{}", source))
+            .build()?;
+        FileSystemDriver::new(root.to_path_buf(), source.to_string()).write_job(WORKFLOW_KERNEL, job)
+    }
+}
+
+impl WorkflowTrigger {
+    /// Whether this trigger starts its workflow on `event`
+    ///
+    /// Event triggers name the event bare or as `on-event(<event>)`.
+    pub fn is_triggered_by(&self, event: &str) -> bool {
+        match self {
+            WorkflowTrigger::OnEvent(trigger) => {
+                let trigger = trigger.trim();
+                let name = trigger
+                    .split_once('(')
+                    .and_then(|(_, rest)| rest.strip_suffix(')'))
+                    .unwrap_or(trigger);
+                name.trim() == event
+            }
+            _ => false,
+        }
+    }
+}
+
+/// Workflow phase started by an event
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TriggeredWorkflow {
+    pub workflow_urn: String,
+
+    /// Run the phase was started in
+    pub run_id: String,
+    pub phase: String,
+
+    /// Kernel of the phase, which received the start job
+    pub kernel: String,
+    pub tx_id: String,
+}
+
+/// Workflows started by events
+pub struct WorkflowTriggers {
+    root: PathBuf,
+    workflows: Vec<Workflow>,
+}
+
+impl WorkflowTriggers {
+    /// Triggers of `workflows`, starting them in the project at `root`
+    pub fn new(root: PathBuf, workflows: Vec<Workflow>) -> Self {
+        Self { root, workflows }
+    }
+
+    /// Triggers of the workflows defined by the .ckdl files in a directory
+    ///
+    /// Files that do not parse are skipped with a warning.
+    pub fn from_ckdl_dir(root: PathBuf, dir: &Path) -> Result<Self> {
+        let mut paths: Vec<PathBuf> = fs::read_dir(dir)?
+            .flatten()
+            .map(|entry| entry.path())
+            .filter(|path| path.extension().is_some_and(|ext| ext == "ckdl"))
+            .collect();
+        paths.sort();
+
+        let mut workflows = Vec::new();
+        for path in paths {
+            match parse_ckdl_file(&path, &root) {
+                Ok(ckdl) => workflows.push(ckdl_to_workflow(ckdl)),
+                Err(e) => tracing::warn!(path = %path.display(), error = %e, "skipping unreadable workflow"),
+            }
+        }
+        Ok(Self::new(root, workflows))
+    }
+
+    /// Workflows an event triggers
+    pub fn matching(&self, event: &str) -> Vec<&Workflow> {
+        self.workflows.iter().filter(|workflow| workflow.trigger.is_triggered_by(event)).collect()
+    }
+
+    /// Start the workflows an event triggers
+    ///
+    /// Each start records a run, and each entry phase's kernel receives
+    /// `{"workflow": <urn>, "run": <run id>, "phase": <name>, "event": <event>}`
+    /// from System.Workflow, caused by the run.
+    pub fn dispatch(&self, event: &WorkflowEvent) -> Result<Vec<TriggeredWorkflow>> {
+        let driver = FileSystemDriver::new(self.root.clone(), WORKFLOW_KERNEL.to_string());
+        let mut started = Vec::new();
+
+        for workflow in self.matching(&event.event) {
+            let entries = entry_phases(workflow);
+            let mut run = WorkflowRun::new(workflow, driver.generate_tx_id()).with_event(event.clone());
+            for phase in run.phases.iter_mut() {
+                if entries.iter().any(|entry| entry.phase_name == phase.phase_name) {
+                    phase.status = PhaseStatus::InProgress;
+                    phase.started_at = Some(run.started_at.clone());
+                }
+            }
+            run.save(&self.root)?;
+
+            for phase in entries {
+                let kernel = kernel_name(&phase.kernel_urn).to_string();
+                let payload = serde_json::json!({
+                    "workflow": workflow.workflow_urn,
+                    "run": run.run_id,
+                    "phase": phase.phase_name,
+                    "event": event,
+                });
+                let job = JobFileBuilder::new(kernel.clone(), payload)
+                    .with_source(WORKFLOW_KERNEL, format!("ckp://{}", WORKFLOW_KERNEL))
+                    .caused_by(&run.run_id, None)
+                    .build()?;
+                let tx_id = driver.write_job(&kernel, job)?;
+
+                tracing::info!(
+                    workflow = %workflow.workflow_urn,
+                    run = %run.run_id,
+                    kernel = %kernel,
+                    tx_id = %tx_id,
+                    event = %event.event,
+                    source = %event.kernel,
+                    "event started workflow"
+                );
+                started.push(TriggeredWorkflow {
+                    workflow_urn: workflow.workflow_urn.clone(),
+                    run_id: run.run_id.clone(),
+                    phase: phase.phase_name.clone(),
+                    kernel,
+                    tx_id,
+                });
+            }
+        }
+        Ok(started)
+    }
+
+    /// Bind the instances minted so far in a run to its phases' outputs
+    ///
+    /// # Returns
+    /// The run, saved with its outputs
+    pub fn collect_outputs(&self, run_id: &str) -> Result<WorkflowRun> {
+        let mut run = WorkflowRun::load(&self.root, run_id)?;
+        let bound = run.collect(&self.root)?;
+        run.save(&self.root)?;
+
+        tracing::debug!(workflow = %run.workflow_urn, run = %run_id, bound, "collected run outputs");
+        Ok(run)
+    }
+}
+
+/// Phases no edge of the workflow leads to (the first phase if every one is a target)
+fn entry_phases(workflow: &Workflow) -> Vec<&crate::workflow::WorkflowPhase> {
+    let entries: Vec<_> = workflow
+        .phases
+        .iter()
+        .filter(|phase| {
+            let kernel = kernel_name(&phase.kernel_urn);
+            !workflow.edges.iter().any(|edge| kernel_name(&edge.target) == kernel)
+        })
+        .collect();
+
+    if entries.is_empty() {
+        workflow.phases.iter().take(1).collect()
+    } else {
+        entries
+    }
+}
+
+/// Kernel name of a kernel reference (`Kernel`, `ckp://Kernel:v1`)
+pub(crate) fn kernel_name(reference: &str) -> &str {
+    let reference = reference.trim_start_matches("ckp://");
+    reference.split([':', '#']).next().unwrap_or(reference)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::workflow::{PhaseStatus, WorkflowEdge, WorkflowPhase, WorkflowStatus};
+    use tempfile::TempDir;
+
+    fn phase(kernel_urn: &str) -> WorkflowPhase {
+        WorkflowPhase {
+            phase_name: kernel_name(kernel_urn).to_string(),
+            kernel_urn: kernel_urn.to_string(),
+            status: PhaseStatus::Pending,
+            started_at: None,
+            completed_at: None,
+            outputs: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_kernel_registered_starts_onboarding_entry_phases() {
+        let temp = TempDir::new().unwrap();
+        let onboarding = Workflow {
+            workflow_urn: "ckp://Process#Onboarding:v1".to_string(),
+            label: "Onboarding".to_string(),
+            description: String::new(),
+            version: "1.0".to_string(),
+            trigger: WorkflowTrigger::OnEvent("on-event(kernel-registered)".to_string()),
+            phases: vec![phase("ckp://Rbac.Review:v1"), phase("ckp://Registry.Update:v1"), phase("ckp://Onboarding.Report:v1")],
+            edges: vec![WorkflowEdge {
+                edge_urn: "ckp://Edge.PRODUCES.Rbac.Review-to-Onboarding.Report".to_string(),
+                source: "ckp://Rbac.Review:v1".to_string(),
+                target: "ckp://Onboarding.Report:v1".to_string(),
+                predicate: "PRODUCES".to_string(),
+                trigger: String::new(),
+                action: None,
+            }],
+            status: WorkflowStatus::Pending,
+        };
+        let nightly = Workflow {
+            workflow_urn: "ckp://Process#Nightly:v1".to_string(),
+            trigger: WorkflowTrigger::OnSchedule("daily".to_string()),
+            ..onboarding.clone()
+        };
+        let triggers = WorkflowTriggers::new(temp.path().to_path_buf(), vec![onboarding, nightly]);
+        assert!(WorkflowTrigger::OnEvent("kernel-registered".to_string()).is_triggered_by(KERNEL_REGISTERED));
+        assert_eq!(triggers.matching(KERNEL_REGISTERED).len(), 1);
+
+        let event = WorkflowEvent::kernel_registered("Orders.Intake", "v0.1", "node:cold");
+        let started = triggers.dispatch(&event).unwrap();
+        let kernels: Vec<&str> = started.iter().map(|s| s.kernel.as_str()).collect();
+        assert_eq!(kernels, vec!["Rbac.Review", "Registry.Update"]);
+
+        let job = fs::read_to_string(temp.path().join("concepts/Rbac.Review/queue/inbox").join(format!("{}.job", started[0].tx_id))).unwrap();
+        let job: Value = serde_json::from_str(&job).unwrap();
+        assert_eq!(job["payload"]["event"]["kernel"], "Orders.Intake");
+        assert_eq!(job["payload"]["event"]["details"]["version"], "v0.1");
+        assert_eq!(job["source"], WORKFLOW_KERNEL);
+        assert_eq!(job["payload"]["run"], started[0].run_id.as_str());
+        assert_eq!(job["correlationId"], started[0].run_id.as_str());
+    }
+}
@@ -7,18 +7,26 @@
 /// - Validating workflow structure
 /// - Executing workflows by coordinating kernel actions
 /// - Querying workflow status and history
+/// - Starting workflows from events (e.g., "kernel-registered")
+/// - Binding phase instances to named run outputs
 
 pub mod validator;
 pub mod ckdl_parser;
+pub mod events;
+pub mod runs;
 
 pub use ckdl_parser::{
     parse_ckdl_file, ckdl_to_workflow,
     CkdlWorkflow, ExternKernel, WorkflowKernel, CkdlEdge,
     ComponentOrigin, ComponentAnalysis,
 };
+pub use events::{TriggeredWorkflow, WorkflowEvent, WorkflowTriggers, KERNEL_REGISTERED, WORKFLOW_KERNEL};
+pub use runs::WorkflowRun;
 
+use crate::errors::CkpError;
 use crate::ontology::{OntologyLibrary, OntologyError};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::path::Path;
 
 /// Workflow stored in System.Workflow kernel
@@ -42,6 +50,10 @@ pub struct WorkflowPhase {
     pub status: PhaseStatus,
     pub started_at: Option<String>,
     pub completed_at: Option<String>,
+
+    /// Names the phase kernel's instances are bound to in a run's outputs
+    #[serde(default)]
+    pub outputs: Vec<String>,
 }
 
 /// Workflow edge defining kernel interactions
@@ -147,24 +159,18 @@ impl WorkflowAPI {
         // Parse CKDL file with component origin analysis
         let ckdl_workflow = parse_ckdl_file(ckdl_path, &self.library.project_root)?;
 
-        // Print component analysis
-        eprintln!("[WorkflowAPI] Parsed CKDL workflow:");
-        eprintln!("[WorkflowAPI]   URN: {}", ckdl_workflow.workflow_urn);
-        eprintln!("[WorkflowAPI]   Label: {}", ckdl_workflow.label);
-        eprintln!("[WorkflowAPI]   EXTERN kernels: {}", ckdl_workflow.analysis.total_extern);
-        eprintln!("[WorkflowAPI]   Workflow kernels: {}", ckdl_workflow.analysis.total_workflow_kernels);
-        eprintln!("[WorkflowAPI]   Edges: {}", ckdl_workflow.analysis.total_edges);
-        eprintln!();
-        eprintln!("[WorkflowAPI] Component Analysis:");
-        eprintln!("[WorkflowAPI]   Forked kernels: {}", ckdl_workflow.analysis.forked_kernels.len());
-        for k in &ckdl_workflow.analysis.forked_kernels {
-            eprintln!("[WorkflowAPI]     ✓ {}", k);
-        }
-        eprintln!("[WorkflowAPI]   Brand new kernels: {}", ckdl_workflow.analysis.brand_new_kernels.len());
-        for k in &ckdl_workflow.analysis.brand_new_kernels {
-            eprintln!("[WorkflowAPI]     + {}", k);
-        }
-        eprintln!();
+        // Report component analysis
+        let analysis = &ckdl_workflow.analysis;
+        tracing::info!(
+            workflow = %ckdl_workflow.workflow_urn,
+            label = %ckdl_workflow.label,
+            extern_kernels = analysis.total_extern,
+            workflow_kernels = analysis.total_workflow_kernels,
+            edges = analysis.total_edges,
+            forked = ?analysis.forked_kernels,
+            brand_new = ?analysis.brand_new_kernels,
+            "parsed CKDL workflow"
+        );
 
         // Convert to Workflow struct
         let _workflow = ckdl_to_workflow(ckdl_workflow.clone());
@@ -336,6 +342,33 @@ ORDER BY ?source
         validator::detect_cycles_via_sparql(&self.library, workflow_urn)
     }
 
+    /// Outputs bound in a workflow run
+    ///
+    /// Maps each output name declared by the run's phases to the URNs of the
+    /// instances bound to it, as of the run's last collection.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use ckp_core::workflow::WorkflowAPI;
+    /// # use ckp_core::ontology::OntologyLibrary;
+    /// # use std::path::PathBuf;
+    /// # let library = OntologyLibrary::new(PathBuf::from("."))?;
+    /// # let workflow_api = WorkflowAPI::new(library);
+    /// let outputs = workflow_api.get_run_outputs("1764410400000-1a2b3c4d")?;
+    ///
+    /// for urn in outputs.get("report").into_iter().flatten() {
+    ///     println!("report: {}", urn);
+    /// }
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn get_run_outputs(&self, run_id: &str) -> Result<BTreeMap<String, Vec<String>>, OntologyError> {
+        let run = WorkflowRun::load(&self.library.project_root, run_id).map_err(|e| match e {
+            CkpError::FileNotFound(msg) => OntologyError::NotFound(msg),
+            other => OntologyError::LoadError(other.to_string()),
+        })?;
+        Ok(run.outputs)
+    }
+
     // Helper methods
 
     fn parse_workflow_status(s: Option<&String>) -> WorkflowStatus {
@@ -0,0 +1,210 @@
+/// Workflow runs and the outputs bound to them
+///
+/// Every start of a workflow is a run, recorded as a System.Workflow
+/// instance (`concepts/System.Workflow/storage/{run_id}.inst`). The run ID
+/// causes the start jobs of the entry phases, so everything the phases do
+/// carries it as correlation ID (see `causality`).
+///
+/// A phase declares named outputs with an `OUTPUTS:` list in its CKDL kernel
+/// block. Collecting a run binds the URNs of the instances its phase kernels
+/// minted in the run's chain under each output name the phase declares, and
+/// writes them to the run's payload and to the `outputs` of its receipt.
+/// Consumers read a run's outputs back without scanning kernel storage.
+
+use crate::causality::{correlated_with, CausalKind};
+use crate::errors::{CkpError, Result};
+use crate::storage::Receipt;
+use crate::workflow::events::{kernel_name, WorkflowEvent, WORKFLOW_KERNEL};
+use crate::workflow::{Workflow, WorkflowPhase};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Record of one run of a workflow
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkflowRun {
+    /// Run ID, also the correlation ID of the run's chain
+    pub run_id: String,
+    pub workflow_urn: String,
+    pub started_at: String,
+
+    /// Event that started the run
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub event: Option<WorkflowEvent>,
+
+    /// Phases of the workflow, with the outputs each declares
+    pub phases: Vec<WorkflowPhase>,
+
+    /// Output name -> URNs of the instances bound to it, in transaction order
+    #[serde(default)]
+    pub outputs: BTreeMap<String, Vec<String>>,
+}
+
+impl WorkflowRun {
+    /// New run of `workflow`, started now
+    pub fn new(workflow: &Workflow, run_id: impl Into<String>) -> Self {
+        Self {
+            run_id: run_id.into(),
+            workflow_urn: workflow.workflow_urn.clone(),
+            started_at: chrono::Utc::now().to_rfc3339(),
+            event: None,
+            phases: workflow.phases.clone(),
+            outputs: BTreeMap::new(),
+        }
+    }
+
+    pub fn with_event(mut self, event: WorkflowEvent) -> Self {
+        self.event = Some(event);
+        self
+    }
+
+    /// Instance directory of a run in the project at `root`
+    pub fn instance_dir(root: &Path, run_id: &str) -> PathBuf {
+        root.join("concepts")
+            .join(WORKFLOW_KERNEL)
+            .join("storage")
+            .join(format!("{}.inst", run_id))
+    }
+
+    /// Load a run by ID
+    pub fn load(root: &Path, run_id: &str) -> Result<Self> {
+        let path = Self::instance_dir(root, run_id).join("payload.json");
+        if !path.exists() {
+            return Err(CkpError::FileNotFound(format!("Workflow run not found: {}", run_id)));
+        }
+
+        Ok(serde_json::from_str(&fs::read_to_string(path)?)?)
+    }
+
+    /// Write the run's payload and receipt
+    ///
+    /// The receipt lists each bound URN once as an output of the run.
+    pub fn save(&self, root: &Path) -> Result<()> {
+        let inst_dir = Self::instance_dir(root, &self.run_id);
+        fs::create_dir_all(&inst_dir)
+            .map_err(|e| CkpError::IoError(format!("Failed to create workflow run instance: {}", e)))?;
+
+        fs::write(inst_dir.join("payload.json"), serde_json::to_string_pretty(self)?)
+            .map_err(|e| CkpError::IoError(format!("Failed to write workflow run: {}", e)))?;
+
+        let mut receipt = Receipt::new(
+            &self.run_id,
+            &self.workflow_urn,
+            WORKFLOW_KERNEL,
+            serde_json::json!({"workflow": self.workflow_urn, "outputs": self.outputs}),
+        )
+        .with_action("workflow.run");
+        for urn in self.outputs.values().flatten() {
+            if !receipt.outputs.contains(urn) {
+                receipt.outputs.push(urn.clone());
+            }
+        }
+        receipt.write(&inst_dir)?;
+        Ok(())
+    }
+
+    /// Bind the instances the phase kernels minted in this run to the phases' outputs
+    ///
+    /// Replaces the previous bindings, so collecting again picks up
+    /// instances minted since.
+    ///
+    /// # Returns
+    /// Number of URNs bound
+    pub fn collect(&mut self, root: &Path) -> Result<usize> {
+        let records = correlated_with(root, &self.run_id)?;
+
+        let mut outputs: BTreeMap<String, Vec<String>> = BTreeMap::new();
+        for phase in self.phases.iter().filter(|phase| !phase.outputs.is_empty()) {
+            let kernel = kernel_name(&phase.kernel_urn);
+            let urns: Vec<String> = records
+                .iter()
+                .filter(|record| record.kind == CausalKind::Instance && record.kernel == kernel)
+                .map(|record| instance_urn(&phase.kernel_urn, &record.tx_id))
+                .collect();
+            for name in &phase.outputs {
+                outputs.entry(name.clone()).or_default().extend(urns.iter().cloned());
+            }
+        }
+
+        self.outputs = outputs;
+        Ok(self.outputs.values().map(Vec::len).sum())
+    }
+}
+
+/// URN of an instance of a phase kernel (`ckp://Kernel:v1#storage/{tx}.inst`)
+fn instance_urn(kernel_urn: &str, tx_id: &str) -> String {
+    let kernel = kernel_urn.trim_start_matches("ckp://");
+    let kernel = kernel.split('#').next().unwrap_or(kernel);
+    format!("ckp://{}#storage/{}.inst", kernel, tx_id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::drivers::{FileSystemDriver, StorageDriver};
+    use crate::workflow::{PhaseStatus, WorkflowStatus, WorkflowTrigger, WorkflowTriggers};
+    use serde_json::json;
+    use tempfile::TempDir;
+
+    fn phase(kernel_urn: &str, outputs: &[&str]) -> WorkflowPhase {
+        WorkflowPhase {
+            phase_name: kernel_name(kernel_urn).to_string(),
+            kernel_urn: kernel_urn.to_string(),
+            status: PhaseStatus::Pending,
+            started_at: None,
+            completed_at: None,
+            outputs: outputs.iter().map(|s| s.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn test_run_binds_phase_instances_to_outputs() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path();
+        let workflow = Workflow {
+            workflow_urn: "ckp://Process#Onboarding:v1".to_string(),
+            label: "Onboarding".to_string(),
+            description: String::new(),
+            version: "1.0".to_string(),
+            trigger: WorkflowTrigger::OnEvent("kernel-registered".to_string()),
+            phases: vec![
+                phase("ckp://Rbac.Review:v1", &["review", "audit"]),
+                phase("ckp://Registry.Update:v1", &[]),
+            ],
+            edges: Vec::new(),
+            status: WorkflowStatus::Pending,
+        };
+        let triggers = WorkflowTriggers::new(root.to_path_buf(), vec![workflow]);
+        let started = triggers
+            .dispatch(&WorkflowEvent::new("kernel-registered", "Orders.Intake"))
+            .unwrap();
+        let run_id = started[0].run_id.clone();
+        assert_eq!(started[1].run_id, run_id);
+        assert!(WorkflowRun::load(root, &run_id).unwrap().outputs.is_empty());
+
+        // Both phase kernels mint in the run's chain; Rbac.Review also outside it
+        let mint = |kernel: &str, tx_id: &str, correlation_id: &str| {
+            let driver = FileSystemDriver::new(root.to_path_buf(), kernel.to_string());
+            let evidence = json!({"causationId": correlation_id, "correlationId": correlation_id});
+            StorageDriver::mint_storage_artifact(&driver, kernel, tx_id, evidence).unwrap();
+        };
+        mint("Rbac.Review", "2000-00000001", &run_id);
+        mint("Registry.Update", "2000-00000002", &run_id);
+        mint("Rbac.Review", "2000-00000003", "1000-00000009");
+
+        let run = triggers.collect_outputs(&run_id).unwrap();
+        let review = vec!["ckp://Rbac.Review:v1#storage/2000-00000001.inst".to_string()];
+        assert_eq!(run.outputs.get("review"), Some(&review));
+        assert_eq!(run.outputs.get("audit"), Some(&review));
+        assert_eq!(run.outputs.len(), 2);
+
+        let saved = WorkflowRun::load(root, &run_id).unwrap();
+        assert_eq!(saved.outputs, run.outputs);
+        let receipt = fs::read_to_string(WorkflowRun::instance_dir(root, &run_id).join("receipt.bin")).unwrap();
+        let receipt: Receipt = serde_json::from_str(&receipt).unwrap();
+        assert_eq!(receipt.outputs, review);
+        assert!(matches!(WorkflowRun::load(root, "missing"), Err(CkpError::FileNotFound(_))));
+    }
+}
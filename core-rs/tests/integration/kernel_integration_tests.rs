@@ -9,6 +9,7 @@
 use ckp_core::{
     Kernel, EdgeMetadata, UrnResolver,
 };
+use ckp_core::causality;
 use ckp_core::drivers::FileSystemDriver;
 use std::fs;
 use tempfile::TempDir;
@@ -231,8 +232,15 @@ async fn test_multi_kernel_emit_chain() {
         .collect();
     assert_eq!(jobs_b.len(), 1, "KernelB should have received 1 job");
 
-    // B emits to C
-    let _tx_id_bc = kernel_b.emit("KernelC", json!({"step": 2, "data": "from_b", "parent_tx": tx_id_ab})).await.unwrap();
+    // B processes A's job and emits to C on its behalf
+    let job_ab = kernel_b.inbox_iter().unwrap().next().unwrap().unwrap();
+    let cause = job_ab.cause();
+    let job_bc = kernel_b
+        .job_builder("KernelC", json!({"step": 2, "data": "from_b"}))
+        .caused_by(&cause.tx_id, Some(&cause.correlation_id))
+        .build()
+        .unwrap();
+    let tx_id_bc = kernel_b.emit_job(job_bc).await.unwrap();
 
     // Verify C received from B
     let inbox_c = kernel_c_dir.join("queue/inbox");
@@ -248,7 +256,16 @@ async fn test_multi_kernel_emit_chain() {
     let job_c: serde_json::Value = serde_json::from_str(&job_c_content).unwrap();
     assert_eq!(job_c["payload"]["step"], 2);
     assert_eq!(job_c["payload"]["data"], "from_b");
-    assert_eq!(job_c["payload"]["parent_tx"], tx_id_ab);
+    assert_eq!(job_c["causationId"], tx_id_ab);
+    assert_eq!(job_c["correlationId"], tx_id_ab);
+
+    // The chain reads back from A's transaction
+    let caused: Vec<String> = causality::caused_by(&project_root, &tx_id_ab)
+        .unwrap()
+        .into_iter()
+        .map(|record| record.tx_id)
+        .collect();
+    assert_eq!(caused, vec![tx_id_bc]);
 }
 
 /// Test: Storage artifact minting and retrieval
@@ -1845,6 +1862,91 @@ async fn test_perf_concurrent_memory_leak() {
     println!("[PERF] Concurrent memory test passed: {} tasks completed", task_count);
 }
 
+/// Test: Emit tail latency under concurrency (Test 2b/7)
+///
+/// Emits write their job files on the blocking pool, so a heartbeat task on
+/// the same two-worker runtime keeps firing on time while 50 emitters write
+/// to one inbox. Blocking writes on the workers show up as heartbeat lag.
+///
+/// The wall-clock bound depends on the machine, so the test only runs on
+/// request: `cargo test -- --ignored test_perf_emit_tail_latency`.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+#[ignore] // Timing-sensitive; flaky on loaded CI runners
+async fn test_perf_emit_tail_latency() {
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::Arc;
+    use std::time::{Duration, Instant};
+
+    let temp_dir = TempDir::new().unwrap();
+    let project_root = temp_dir.path().to_path_buf();
+    let concepts_root = Arc::new(project_root.join("concepts"));
+    create_test_ontology(&project_root, "TailLatencyTarget");
+
+    // Heartbeat: how late each 1ms tick fires while emits are in flight
+    let done = Arc::new(AtomicBool::new(false));
+    let heartbeat = {
+        let done = Arc::clone(&done);
+        tokio::spawn(async move {
+            let mut lags = Vec::new();
+            while !done.load(Ordering::SeqCst) {
+                let start = Instant::now();
+                tokio::time::sleep(Duration::from_millis(1)).await;
+                lags.push(start.elapsed().saturating_sub(Duration::from_millis(1)));
+            }
+            lags
+        })
+    };
+
+    let mut handles = vec![];
+    for i in 0..50 {
+        let root_clone = Arc::clone(&concepts_root);
+        handles.push(tokio::spawn(async move {
+            let mut kernel = Kernel::new((*root_clone).clone(), Some(format!("TailSource{}", i)), false);
+            let mut latencies = Vec::new();
+            for j in 0..20 {
+                let payload = serde_json::json!({"task": i, "index": j, "buffer": vec![0u8; 1024]});
+                let start = Instant::now();
+                kernel.emit("TailLatencyTarget", payload).await.unwrap();
+                latencies.push(start.elapsed());
+            }
+            latencies
+        }));
+    }
+
+    let mut latencies = Vec::new();
+    for handle in handles {
+        latencies.extend(handle.await.unwrap());
+    }
+    done.store(true, Ordering::SeqCst);
+    let mut lags = heartbeat.await.unwrap();
+
+    latencies.sort();
+    lags.sort();
+    let percentile = |sorted: &[Duration], p: f64| sorted[((sorted.len() - 1) as f64 * p) as usize];
+
+    assert_eq!(latencies.len(), 1000);
+    println!(
+        "[PERF] Emit latency: p50={:?} p99={:?} max={:?}",
+        percentile(&latencies, 0.50),
+        percentile(&latencies, 0.99),
+        latencies[latencies.len() - 1]
+    );
+    if !lags.is_empty() {
+        let p99_lag = percentile(&lags, 0.99);
+        println!(
+            "[PERF] Executor heartbeat lag: p50={:?} p99={:?} ({} ticks)",
+            percentile(&lags, 0.50),
+            p99_lag,
+            lags.len()
+        );
+        assert!(
+            p99_lag < Duration::from_millis(50),
+            "Executor stalled during concurrent emits: p99 heartbeat lag {:?}",
+            p99_lag
+        );
+    }
+}
+
 /// Test: Large payload handling - 10MB payloads (Test 3/7)
 #[tokio::test]
 async fn test_perf_large_payload_10mb() {
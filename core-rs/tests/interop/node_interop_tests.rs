@@ -0,0 +1,312 @@
+//! Node.js interop conformance tests
+//!
+//! Runs mixed-runtime scenarios against a shared temporary concepts tree and
+//! checks that both runtimes read and write the same bytes:
+//! - Node emits → Rust kernel processes the job
+//! - Rust mints an instance → Node edge router routes it
+//!
+//! Build with the `node-interop` feature and point `CKP_NODE_CK` at the Node
+//! `ck` binary:
+//!
+//! ```text
+//! CKP_NODE_CK=$(which ck) cargo test --features node-interop --test node_interop
+//! ```
+//!
+//! Without `CKP_NODE_CK` every scenario is skipped with a notice.
+
+use ckp_core::drivers::{FileSystemDriver, JobFile};
+use ckp_core::edge::EdgeKernel;
+use ckp_core::{validate_protocol_file, Kernel, ProtocolFile};
+use serde_json::{json, Value};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command, Output, Stdio};
+use std::time::{Duration, Instant};
+use tempfile::TempDir;
+
+/// Environment variable holding the Node `ck` binary path
+const NODE_CK_ENV: &str = "CKP_NODE_CK";
+
+/// How long the Node edge router gets to route an instance
+const ROUTE_TIMEOUT: Duration = Duration::from_secs(15);
+
+// ==================== Node Runtime ====================
+
+/// Node `ck` binary under test
+struct NodeCk {
+    binary: PathBuf,
+}
+
+impl NodeCk {
+    /// Binary from `CKP_NODE_CK`, or None to skip the scenario
+    fn from_env(scenario: &str) -> Option<Self> {
+        match std::env::var_os(NODE_CK_ENV) {
+            Some(binary) => Some(Self { binary: PathBuf::from(binary) }),
+            None => {
+                eprintln!("[interop] Skipping {}: {} is not set", scenario, NODE_CK_ENV);
+                None
+            }
+        }
+    }
+
+    fn command(&self, root: &Path) -> Command {
+        let mut cmd = Command::new(&self.binary);
+        cmd.current_dir(root).env("HOME", root).stdin(Stdio::null());
+        cmd
+    }
+
+    /// `ck emit <target> <payload>` inside the project
+    fn emit(&self, root: &Path, target: &str, payload: &Value) -> Output {
+        let output = self
+            .command(root)
+            .args(["emit", target, &payload.to_string()])
+            .output()
+            .expect("Failed to run Node ck");
+        assert!(
+            output.status.success(),
+            "Node ck emit failed: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+        output
+    }
+
+    /// `ck daemon edge-router` for the project, running until killed
+    fn spawn_edge_router(&self, root: &Path) -> Child {
+        self.command(root)
+            .args(["daemon", "edge-router", "--project"])
+            .arg(root)
+            .stdout(Stdio::null())
+            .stderr(Stdio::inherit())
+            .spawn()
+            .expect("Failed to start Node edge router")
+    }
+}
+
+/// Stops a spawned Node process when the scenario ends, even on panic
+struct KillOnDrop(Child);
+
+impl Drop for KillOnDrop {
+    fn drop(&mut self) {
+        let _ = self.0.kill();
+        let _ = self.0.wait();
+    }
+}
+
+// ==================== Shared Concepts Tree ====================
+
+/// Project with a source kernel notifying a target kernel
+fn create_project(root: &Path, source: &str, target: &str) {
+    fs::write(
+        root.join(".ckproject"),
+        r#"apiVersion: conceptkernel/v1
+kind: Project
+metadata:
+  name: interop
+  id: interop
+spec:
+  domain: Test.Interop
+  version: 1.3.16
+"#,
+    )
+    .unwrap();
+
+    for kernel in [source, target] {
+        let kernel_dir = root.join("concepts").join(kernel);
+        fs::create_dir_all(kernel_dir.join("queue/inbox")).unwrap();
+        fs::create_dir_all(kernel_dir.join("queue/edges")).unwrap();
+        fs::create_dir_all(kernel_dir.join("storage")).unwrap();
+    }
+
+    fs::write(
+        root.join("concepts").join(source).join("conceptkernel.yaml"),
+        format!(
+            r#"apiVersion: conceptkernel/v1
+kind: Ontology
+metadata:
+  name: ckp://{source}:v0.1
+  type: node:cold
+  version: v0.1
+spec:
+  notification_contract:
+    - target_kernel: {target}
+      queue: edges
+"#
+        ),
+    )
+    .unwrap();
+
+    fs::write(
+        root.join("concepts").join(target).join("conceptkernel.yaml"),
+        format!(
+            r#"apiVersion: conceptkernel/v1
+kind: Ontology
+metadata:
+  name: ckp://{target}:v0.1
+  type: node:cold
+  version: v0.1
+spec:
+  queue_contract:
+    edges:
+      - "*"
+"#
+        ),
+    )
+    .unwrap();
+}
+
+/// Job files in a kernel's inbox
+fn inbox_jobs(root: &Path, kernel: &str) -> Vec<PathBuf> {
+    let inbox = root.join("concepts").join(kernel).join("queue/inbox");
+    let mut jobs: Vec<PathBuf> = fs::read_dir(inbox)
+        .unwrap()
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| path.extension().and_then(|e| e.to_str()) == Some("job"))
+        .collect();
+    jobs.sort();
+    jobs
+}
+
+/// Edge queue entries of a kernel as (path relative to the kernel, link target)
+fn edge_links(root: &Path, kernel: &str) -> Vec<(PathBuf, PathBuf)> {
+    let kernel_dir = root.join("concepts").join(kernel);
+    let mut links = Vec::new();
+    let Ok(queues) = fs::read_dir(kernel_dir.join("queue/edges")) else {
+        return links;
+    };
+
+    for queue in queues.filter_map(|entry| entry.ok()) {
+        for entry in fs::read_dir(queue.path()).unwrap().filter_map(|entry| entry.ok()) {
+            if let Ok(link_target) = fs::read_link(entry.path()) {
+                let relative = entry.path().strip_prefix(&kernel_dir).unwrap().to_path_buf();
+                links.push((relative, link_target));
+            }
+        }
+    }
+    links.sort();
+    links
+}
+
+/// Keys of a JSON object, sorted
+fn keys(value: &Value) -> Vec<String> {
+    let mut keys: Vec<String> = value.as_object().unwrap().keys().cloned().collect();
+    keys.sort();
+    keys
+}
+
+// ==================== Scenarios ====================
+
+/// Node emits a job that the Rust kernel API reads, processes and archives
+#[tokio::test]
+async fn test_node_emit_rust_processes() {
+    let Some(node) = NodeCk::from_env("test_node_emit_rust_processes") else {
+        return;
+    };
+    let temp = TempDir::new().unwrap();
+    let root = temp.path();
+    create_project(root, "Interop.Source", "Interop.Target");
+
+    let payload = json!({"order": 42, "items": ["a", "b"], "note": "ünïcode"});
+    node.emit(root, "Interop.Target", &payload);
+
+    let jobs = inbox_jobs(root, "Interop.Target");
+    assert_eq!(jobs.len(), 1, "Node emit should write exactly one job");
+    let node_bytes = fs::read(&jobs[0]).unwrap();
+    validate_protocol_file(ProtocolFile::Job, &node_bytes).unwrap();
+
+    // Rust writes the same bytes back for the job it parsed
+    let parsed: JobFile = serde_json::from_slice(&node_bytes).unwrap();
+    let rust_bytes = serde_json::to_string_pretty(&parsed).unwrap();
+    assert_eq!(
+        String::from_utf8_lossy(&node_bytes).trim_end(),
+        rust_bytes,
+        "Job written by Node differs from Rust serialization"
+    );
+    assert_eq!(jobs[0].file_stem().unwrap().to_str().unwrap(), parsed.tx_id);
+
+    // A Rust-emitted job has the same shape
+    let mut emitter = Kernel::new(root.to_path_buf(), None, false);
+    emitter.emit("Interop.Target", payload.clone()).await.unwrap();
+    let jobs = inbox_jobs(root, "Interop.Target");
+    assert_eq!(jobs.len(), 2);
+    let node_job: Value = serde_json::from_slice(&node_bytes).unwrap();
+    for path in &jobs {
+        let mut rust_job: Value = serde_json::from_slice(&fs::read(path).unwrap()).unwrap();
+        rust_job.as_object_mut().unwrap().remove("traceparent");
+        assert_eq!(keys(&rust_job), keys(&node_job));
+    }
+
+    // The Rust kernel processes and archives the Node job
+    let target = Kernel::new(root.to_path_buf(), Some("Interop.Target".to_string()), false);
+    let mut processed = 0;
+    for job in target.inbox_iter().unwrap() {
+        let job = job.unwrap();
+        assert_eq!(job.payload(), &payload);
+        job.archive().unwrap();
+        processed += 1;
+    }
+    assert_eq!(processed, 2);
+    assert!(inbox_jobs(root, "Interop.Target").is_empty());
+    let archived = root.join("concepts/Interop.Target/archive").join(format!("{}.job", parsed.tx_id));
+    assert_eq!(fs::read(archived).unwrap(), node_bytes);
+}
+
+/// Rust mints an instance that the Node edge router routes like Rust would
+#[test]
+fn test_rust_emit_node_routes() {
+    let Some(node) = NodeCk::from_env("test_rust_emit_node_routes") else {
+        return;
+    };
+    let (source, target) = ("Interop.Source", "Interop.Target");
+    let tx_id = "1764410400000-interop";
+    let data = json!({"order": 42, "status": "paid"});
+
+    // Node routes in one tree...
+    let node_tree = TempDir::new().unwrap();
+    create_project(node_tree.path(), source, target);
+    let router = KillOnDrop(node.spawn_edge_router(node_tree.path()));
+    // Let the router take its initial snapshot so the instance is a new arrival
+    std::thread::sleep(Duration::from_millis(500));
+
+    let driver = FileSystemDriver::new(node_tree.path().to_path_buf(), source.to_string());
+    let instance = driver.mint_storage_artifact(&data, tx_id).unwrap();
+    validate_protocol_file(ProtocolFile::Receipt, &fs::read(instance.join("receipt.bin")).unwrap()).unwrap();
+
+    let started = Instant::now();
+    let node_links = loop {
+        let links = edge_links(node_tree.path(), target);
+        if !links.is_empty() {
+            break links;
+        }
+        assert!(started.elapsed() < ROUTE_TIMEOUT, "Node edge router did not route {}", tx_id);
+        std::thread::sleep(Duration::from_millis(100));
+    };
+    drop(router);
+
+    // ...and Rust routes the same instance in a twin tree
+    let rust_tree = TempDir::new().unwrap();
+    create_project(rust_tree.path(), source, target);
+    let driver = FileSystemDriver::new(rust_tree.path().to_path_buf(), source.to_string());
+    let instance = driver.mint_storage_artifact(&data, tx_id).unwrap();
+    let mut edges = EdgeKernel::new(rust_tree.path().to_path_buf()).unwrap();
+    edges.create_edge("PRODUCES", source, target).unwrap();
+    edges.route_instance(&instance, source).unwrap();
+
+    assert_eq!(
+        node_links,
+        edge_links(rust_tree.path(), target),
+        "Node and Rust edge queues differ"
+    );
+    for (link, _) in &node_links {
+        let routed = node_tree.path().join("concepts").join(target).join(link);
+        assert!(routed.join("receipt.json").exists(), "Routed link {} is dangling", link.display());
+    }
+
+    // Edge definitions written by Node follow the protocol schema
+    for entry in fs::read_dir(node_tree.path().join("concepts/.edges")).into_iter().flatten().flatten() {
+        let definition = entry.path().join("edgekernel.yaml");
+        if let Ok(yaml) = fs::read_to_string(&definition) {
+            let value: Value = serde_yaml::from_str(&yaml).unwrap();
+            validate_protocol_file(ProtocolFile::Edge, value.to_string().as_bytes()).unwrap();
+        }
+    }
+}
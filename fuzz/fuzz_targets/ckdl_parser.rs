@@ -0,0 +1,19 @@
+// Fuzz target: CkdlParser
+//
+// CKDL documents are shared between projects; malformed declarations must be
+// skipped or reported, never panic.
+
+#![no_main]
+
+use ckp_core::urn::CkdlParser;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(content) = std::str::from_utf8(data) else {
+        return;
+    };
+
+    if let Ok(document) = CkdlParser::parse(content) {
+        let _ = CkdlParser::parse(&document.to_ckdl());
+    }
+});
@@ -0,0 +1,23 @@
+// Fuzz target: EdgeMetadata
+//
+// Edge metadata files (queue/edges/*/metadata.yaml) may have been written by
+// any runtime version. Parsing and migrating them must never panic.
+
+#![no_main]
+
+use ckp_core::EdgeMetadata;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(yaml) = std::str::from_utf8(data) else {
+        return;
+    };
+
+    let _ = EdgeMetadata::parse_urn(yaml);
+    if let Ok(mut metadata) = EdgeMetadata::from_yaml(yaml) {
+        metadata.migrate();
+        if let Ok(written) = metadata.to_yaml() {
+            let _ = EdgeMetadata::from_yaml(&written);
+        }
+    }
+});
@@ -0,0 +1,41 @@
+// Fuzz target: job files and receipts
+//
+// Job files and receipts are read from queue and storage directories other
+// processes write to, in any of the negotiated encodings. The first byte
+// picks the encoding; the rest is decoded as a job and as a receipt.
+
+#![no_main]
+
+use ckp_core::drivers::{Encoding, JobFile, JobView};
+use ckp_core::Receipt;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let Some((selector, bytes)) = data.split_first() else {
+        return;
+    };
+    let encoding = Encoding::ALL[*selector as usize % Encoding::ALL.len()];
+
+    if let Ok(job) = encoding.decode::<JobFile>(bytes) {
+        let encoded = encoding.encode(&job).expect("decoded job re-encodes");
+        let _ = encoding.decode::<JobFile>(&encoded);
+    }
+
+    if let Ok(receipt) = encoding.decode::<Receipt>(bytes) {
+        if receipt.validate().is_ok() {
+            let dir = std::env::temp_dir().join("ck-fuzz-receipt");
+            let _ = receipt.verify_digests(&dir);
+        }
+    }
+
+    if encoding == Encoding::Json {
+        if let Ok(view) = JobView::parse(bytes) {
+            let _ = view.to_job_file();
+            let _ = view.payload_as::<serde_json::Value>();
+        }
+    }
+
+    if let Ok(name) = std::str::from_utf8(bytes) {
+        let _ = Encoding::parse_job_file_name(name);
+    }
+});
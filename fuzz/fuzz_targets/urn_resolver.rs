@@ -0,0 +1,34 @@
+// Fuzz target: UrnResolver
+//
+// URNs arrive in job files, edge metadata and CKDL documents written by other
+// runtimes. Every parser must return a typed error, never panic, and
+// resolved paths must stay under the concepts root.
+
+#![no_main]
+
+use ckp_core::UrnResolver;
+use libfuzzer_sys::fuzz_target;
+use std::path::Path;
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(urn) = std::str::from_utf8(data) else {
+        return;
+    };
+
+    if let Ok(parsed) = UrnResolver::parse(urn) {
+        let _ = UrnResolver::build(&parsed);
+    }
+    let _ = UrnResolver::parse_in_namespace(urn, Some("acme"));
+    let _ = UrnResolver::parse_edge_urn(urn);
+    let _ = UrnResolver::parse_project_urn(urn);
+    let _ = UrnResolver::parse_agent_urn(urn);
+    let _ = UrnResolver::parse_query_urn(urn);
+    let _ = UrnResolver::parse_query_urn_v2(urn);
+    let _ = UrnResolver::extract_tx_id(urn);
+    let _ = UrnResolver::normalize_kernel_name(urn);
+
+    let root = Path::new("/concepts");
+    if let Ok(path) = UrnResolver::resolve_to_path(urn, root) {
+        assert!(path.starts_with(root), "{} resolved outside the concepts root", urn);
+    }
+});